@@ -206,7 +206,7 @@ impl TryFrom<CreateFunction> for ScalarFunctionWrapper {
 
     fn try_from(definition: CreateFunction) -> RResult<Self, Self::Error> {
         Ok(Self {
-            name: definition.name,
+            name: definition.name.table().to_string(),
             expr: definition
                 .params
                 .function_body
@@ -0,0 +1,105 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Comments on tables and their columns.
+
+use std::collections::HashMap;
+
+use arrow::datatypes::Schema;
+use datafusion_expr::COMMENT_METADATA_KEY;
+use parking_lot::RwLock;
+
+/// The comments on a table and on its columns, which can be changed after
+/// the table is created by `COMMENT ON TABLE` and `COMMENT ON COLUMN`.
+///
+/// [`TableProvider`]s supporting these statements can hold a `TableComments`
+/// to implement [`TableProvider::get_table_comment`],
+/// [`TableProvider::set_table_comment`] and their column counterparts.
+///
+/// [`TableProvider`]: crate::TableProvider
+/// [`TableProvider::get_table_comment`]: crate::TableProvider::get_table_comment
+/// [`TableProvider::set_table_comment`]: crate::TableProvider::set_table_comment
+#[derive(Debug, Default)]
+pub struct TableComments {
+    table: RwLock<Option<String>>,
+    columns: RwLock<HashMap<String, String>>,
+}
+
+impl TableComments {
+    /// Create the comments of a table with the given schema, without a
+    /// comment on the table and with the comments on its columns initialized
+    /// from the [`COMMENT_METADATA_KEY`] metadata of its fields.
+    pub fn new(schema: &Schema) -> Self {
+        let columns = schema
+            .fields()
+            .iter()
+            .filter_map(|field| {
+                let comment = field.metadata().get(COMMENT_METADATA_KEY)?;
+                Some((field.name().clone(), comment.clone()))
+            })
+            .collect();
+        Self {
+            table: RwLock::new(None),
+            columns: RwLock::new(columns),
+        }
+    }
+
+    /// Get the comment on the table, if any
+    pub fn table(&self) -> Option<String> {
+        self.table.read().clone()
+    }
+
+    /// Get the comment on `column`, if any
+    pub fn column(&self, column: &str) -> Option<String> {
+        self.columns.read().get(column).cloned()
+    }
+
+    /// Set the comment on the table, or remove it if `comment` is `None`
+    pub fn set_table(&self, comment: Option<String>) {
+        *self.table.write() = comment;
+    }
+
+    /// Set the comment on `column`, or remove it if `comment` is `None`
+    pub fn set_column(&self, column: &str, comment: Option<String>) {
+        let mut columns = self.columns.write();
+        match comment {
+            Some(comment) => columns.insert(column.to_string(), comment),
+            None => columns.remove(column),
+        };
+    }
+
+    /// Return `schema` with the [`COMMENT_METADATA_KEY`] metadata of its
+    /// fields replaced by the current comments on the columns
+    pub fn apply(&self, schema: &Schema) -> Schema {
+        let columns = self.columns.read();
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let mut metadata = field.metadata().clone();
+                match columns.get(field.name()) {
+                    Some(comment) => {
+                        metadata.insert(COMMENT_METADATA_KEY.to_string(), comment.clone())
+                    }
+                    None => metadata.remove(COMMENT_METADATA_KEY),
+                };
+                field.as_ref().clone().with_metadata(metadata)
+            })
+            .collect::<Vec<_>>();
+        Schema::new_with_metadata(fields, schema.metadata().clone())
+    }
+}
@@ -19,6 +19,7 @@
 
 use crate::{CatalogProvider, CatalogProviderList, SchemaProvider, TableProvider};
 use async_trait::async_trait;
+use datafusion_expr::{AggregateUDF, ScalarUDF, WindowUDF};
 use std::any::Any;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -174,6 +175,64 @@ impl SchemaProvider for DynamicFileSchemaProvider {
     fn table_exist(&self, name: &str) -> bool {
         self.inner.table_exist(name)
     }
+
+    fn function_names(&self) -> Vec<String> {
+        self.inner.function_names()
+    }
+
+    fn udf(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        self.inner.udf(name)
+    }
+
+    fn udaf(&self, name: &str) -> Option<Arc<AggregateUDF>> {
+        self.inner.udaf(name)
+    }
+
+    fn udwf(&self, name: &str) -> Option<Arc<WindowUDF>> {
+        self.inner.udwf(name)
+    }
+
+    fn register_udf(
+        &self,
+        udf: Arc<ScalarUDF>,
+    ) -> datafusion_common::Result<Option<Arc<ScalarUDF>>> {
+        self.inner.register_udf(udf)
+    }
+
+    fn register_udaf(
+        &self,
+        udaf: Arc<AggregateUDF>,
+    ) -> datafusion_common::Result<Option<Arc<AggregateUDF>>> {
+        self.inner.register_udaf(udaf)
+    }
+
+    fn register_udwf(
+        &self,
+        udwf: Arc<WindowUDF>,
+    ) -> datafusion_common::Result<Option<Arc<WindowUDF>>> {
+        self.inner.register_udwf(udwf)
+    }
+
+    fn deregister_udf(
+        &self,
+        name: &str,
+    ) -> datafusion_common::Result<Option<Arc<ScalarUDF>>> {
+        self.inner.deregister_udf(name)
+    }
+
+    fn deregister_udaf(
+        &self,
+        name: &str,
+    ) -> datafusion_common::Result<Option<Arc<AggregateUDF>>> {
+        self.inner.deregister_udaf(name)
+    }
+
+    fn deregister_udwf(
+        &self,
+        name: &str,
+    ) -> datafusion_common::Result<Option<Arc<WindowUDF>>> {
+        self.inner.deregister_udwf(name)
+    }
 }
 
 /// [UrlTableFactory] is a factory that can create a table provider from the given url.
@@ -37,12 +37,14 @@ pub use memory::{
 };
 mod r#async;
 mod catalog;
+mod comment;
 mod dynamic_file;
 pub mod information_schema;
 mod schema;
 mod session;
 mod table;
 pub use catalog::*;
+pub use comment::*;
 pub use dynamic_file::catalog::*;
 pub use r#async::*;
 pub use schema::*;
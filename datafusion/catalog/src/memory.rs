@@ -22,6 +22,7 @@ use crate::{CatalogProvider, CatalogProviderList, SchemaProvider, TableProvider}
 use async_trait::async_trait;
 use dashmap::DashMap;
 use datafusion_common::{exec_err, DataFusionError};
+use datafusion_expr::{AggregateUDF, ScalarUDF, WindowUDF};
 use std::any::Any;
 use std::sync::Arc;
 
@@ -139,6 +140,9 @@ impl CatalogProvider for MemoryCatalogProvider {
 #[derive(Debug)]
 pub struct MemorySchemaProvider {
     tables: DashMap<String, Arc<dyn TableProvider>>,
+    scalar_functions: DashMap<String, Arc<ScalarUDF>>,
+    aggregate_functions: DashMap<String, Arc<AggregateUDF>>,
+    window_functions: DashMap<String, Arc<WindowUDF>>,
 }
 
 impl MemorySchemaProvider {
@@ -146,6 +150,9 @@ impl MemorySchemaProvider {
     pub fn new() -> Self {
         Self {
             tables: DashMap::new(),
+            scalar_functions: DashMap::new(),
+            aggregate_functions: DashMap::new(),
+            window_functions: DashMap::new(),
         }
     }
 }
@@ -197,4 +204,104 @@ impl SchemaProvider for MemorySchemaProvider {
     fn table_exist(&self, name: &str) -> bool {
         self.tables.contains_key(name)
     }
+
+    fn function_names(&self) -> Vec<String> {
+        let scalar_names = self.scalar_functions.iter().map(|f| f.key().clone());
+        let aggregate_names = self.aggregate_functions.iter().map(|f| f.key().clone());
+        let window_names = self.window_functions.iter().map(|f| f.key().clone());
+        scalar_names
+            .chain(aggregate_names)
+            .chain(window_names)
+            .collect()
+    }
+
+    fn udf(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        self.scalar_functions
+            .get(name)
+            .map(|f| Arc::clone(f.value()))
+    }
+
+    fn udaf(&self, name: &str) -> Option<Arc<AggregateUDF>> {
+        self.aggregate_functions
+            .get(name)
+            .map(|f| Arc::clone(f.value()))
+    }
+
+    fn udwf(&self, name: &str) -> Option<Arc<WindowUDF>> {
+        self.window_functions
+            .get(name)
+            .map(|f| Arc::clone(f.value()))
+    }
+
+    fn register_udf(
+        &self,
+        udf: Arc<ScalarUDF>,
+    ) -> datafusion_common::Result<Option<Arc<ScalarUDF>>> {
+        udf.aliases().iter().for_each(|alias| {
+            self.scalar_functions
+                .insert(alias.clone(), Arc::clone(&udf));
+        });
+        Ok(self.scalar_functions.insert(udf.name().into(), udf))
+    }
+
+    fn register_udaf(
+        &self,
+        udaf: Arc<AggregateUDF>,
+    ) -> datafusion_common::Result<Option<Arc<AggregateUDF>>> {
+        udaf.aliases().iter().for_each(|alias| {
+            self.aggregate_functions
+                .insert(alias.clone(), Arc::clone(&udaf));
+        });
+        Ok(self.aggregate_functions.insert(udaf.name().into(), udaf))
+    }
+
+    fn register_udwf(
+        &self,
+        udwf: Arc<WindowUDF>,
+    ) -> datafusion_common::Result<Option<Arc<WindowUDF>>> {
+        udwf.aliases().iter().for_each(|alias| {
+            self.window_functions
+                .insert(alias.clone(), Arc::clone(&udwf));
+        });
+        Ok(self.window_functions.insert(udwf.name().into(), udwf))
+    }
+
+    fn deregister_udf(
+        &self,
+        name: &str,
+    ) -> datafusion_common::Result<Option<Arc<ScalarUDF>>> {
+        let udf = self.scalar_functions.remove(name).map(|(_, udf)| udf);
+        if let Some(udf) = &udf {
+            for alias in udf.aliases() {
+                self.scalar_functions.remove(alias);
+            }
+        }
+        Ok(udf)
+    }
+
+    fn deregister_udaf(
+        &self,
+        name: &str,
+    ) -> datafusion_common::Result<Option<Arc<AggregateUDF>>> {
+        let udaf = self.aggregate_functions.remove(name).map(|(_, udaf)| udaf);
+        if let Some(udaf) = &udaf {
+            for alias in udaf.aliases() {
+                self.aggregate_functions.remove(alias);
+            }
+        }
+        Ok(udaf)
+    }
+
+    fn deregister_udwf(
+        &self,
+        name: &str,
+    ) -> datafusion_common::Result<Option<Arc<WindowUDF>>> {
+        let udwf = self.window_functions.remove(name).map(|(_, udwf)| udwf);
+        if let Some(udwf) = &udwf {
+            for alias in udwf.aliases() {
+                self.window_functions.remove(alias);
+            }
+        }
+        Ok(udwf)
+    }
 }
@@ -16,7 +16,7 @@
 // under the License.
 
 //! Describes the interface and built-in implementations of schemas,
-//! representing collections of named tables.
+//! representing collections of named tables and functions.
 
 use async_trait::async_trait;
 use datafusion_common::{exec_err, DataFusionError};
@@ -26,8 +26,10 @@ use std::sync::Arc;
 
 use crate::table::TableProvider;
 use datafusion_common::Result;
+use datafusion_expr::{AggregateUDF, ScalarUDF, WindowUDF};
 
-/// Represents a schema, comprising a number of named tables.
+/// Represents a schema, comprising a number of named tables and, optionally,
+/// functions.
 ///
 /// Please see [`CatalogProvider`] for details of implementing a custom catalog.
 ///
@@ -79,4 +81,79 @@ pub trait SchemaProvider: Debug + Sync + Send {
 
     /// Returns true if table exist in the schema provider, false otherwise.
     fn table_exist(&self, name: &str) -> bool;
+
+    /// Retrieves the names of the scalar, aggregate and window functions
+    /// registered in this schema.
+    fn function_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Retrieves the scalar function `name` from the schema, if it exists.
+    #[allow(unused_variables)]
+    fn udf(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        None
+    }
+
+    /// Retrieves the aggregate function `name` from the schema, if it exists.
+    #[allow(unused_variables)]
+    fn udaf(&self, name: &str) -> Option<Arc<AggregateUDF>> {
+        None
+    }
+
+    /// Retrieves the window function `name` from the schema, if it exists.
+    #[allow(unused_variables)]
+    fn udwf(&self, name: &str) -> Option<Arc<WindowUDF>> {
+        None
+    }
+
+    /// If supported by the implementation, adds a scalar function to this
+    /// schema under its name and aliases, and returns the previously
+    /// registered function with the same name, if any.
+    #[allow(unused_variables)]
+    fn register_udf(&self, udf: Arc<ScalarUDF>) -> Result<Option<Arc<ScalarUDF>>> {
+        exec_err!("schema provider does not support registering functions")
+    }
+
+    /// If supported by the implementation, adds an aggregate function to this
+    /// schema under its name and aliases, and returns the previously
+    /// registered function with the same name, if any.
+    #[allow(unused_variables)]
+    fn register_udaf(
+        &self,
+        udaf: Arc<AggregateUDF>,
+    ) -> Result<Option<Arc<AggregateUDF>>> {
+        exec_err!("schema provider does not support registering functions")
+    }
+
+    /// If supported by the implementation, adds a window function to this
+    /// schema under its name and aliases, and returns the previously
+    /// registered function with the same name, if any.
+    #[allow(unused_variables)]
+    fn register_udwf(&self, udwf: Arc<WindowUDF>) -> Result<Option<Arc<WindowUDF>>> {
+        exec_err!("schema provider does not support registering functions")
+    }
+
+    /// If supported by the implementation, removes the scalar function `name`
+    /// and its aliases from this schema, and returns the removed function, if
+    /// any.
+    #[allow(unused_variables)]
+    fn deregister_udf(&self, name: &str) -> Result<Option<Arc<ScalarUDF>>> {
+        exec_err!("schema provider does not support deregistering functions")
+    }
+
+    /// If supported by the implementation, removes the aggregate function
+    /// `name` and its aliases from this schema, and returns the removed
+    /// function, if any.
+    #[allow(unused_variables)]
+    fn deregister_udaf(&self, name: &str) -> Result<Option<Arc<AggregateUDF>>> {
+        exec_err!("schema provider does not support deregistering functions")
+    }
+
+    /// If supported by the implementation, removes the window function `name`
+    /// and its aliases from this schema, and returns the removed function, if
+    /// any.
+    #[allow(unused_variables)]
+    fn deregister_udwf(&self, name: &str) -> Result<Option<Arc<WindowUDF>>> {
+        exec_err!("schema provider does not support deregistering functions")
+    }
 }
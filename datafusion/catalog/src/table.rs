@@ -24,12 +24,13 @@ use crate::session::Session;
 use arrow::datatypes::SchemaRef;
 use async_trait::async_trait;
 use datafusion_common::Result;
-use datafusion_common::{not_impl_err, Constraints, Statistics};
+use datafusion_common::{not_impl_err, Constraints, Statistics, TableReference};
 use datafusion_expr::Expr;
 
 use datafusion_expr::dml::InsertOp;
 use datafusion_expr::{
-    CreateExternalTable, LogicalPlan, TableProviderFilterPushDown, TableType,
+    CreateExternalTable, DdlStatement, LogicalPlan, TableProviderFilterPushDown,
+    TableType, COMMENT_METADATA_KEY,
 };
 use datafusion_physical_plan::ExecutionPlan;
 
@@ -73,6 +74,15 @@ pub trait TableProvider: Debug + Sync + Send {
         None
     }
 
+    /// Get a [`DdlStatement`] that creates a table equivalent to this one
+    /// under `name`, generated from the metadata of the table, if available.
+    ///
+    /// This is used to describe tables that have no stored definition (see
+    /// [`Self::get_table_definition`]), for example by `SHOW CREATE TABLE`.
+    fn get_table_ddl(&self, _name: &TableReference) -> Result<Option<DdlStatement>> {
+        Ok(None)
+    }
+
     /// Get the [`LogicalPlan`] of this table, if available.
     fn get_logical_plan(&self) -> Option<Cow<LogicalPlan>> {
         None
@@ -83,6 +93,41 @@ pub trait TableProvider: Debug + Sync + Send {
         None
     }
 
+    /// Get the comment on this table, as set by `CREATE TABLE ... COMMENT`
+    /// or `COMMENT ON TABLE`, if any.
+    fn get_table_comment(&self) -> Option<String> {
+        None
+    }
+
+    /// Get the comment on a column of this table, if any.
+    ///
+    /// By default, this is the [`COMMENT_METADATA_KEY`] metadata of the
+    /// field of the column in [`Self::schema`], as set by the `COMMENT`
+    /// option of the column in `CREATE TABLE`.
+    fn get_column_comment(&self, column: &str) -> Option<String> {
+        let schema = self.schema();
+        let field = schema.field_with_name(column).ok()?;
+        field.metadata().get(COMMENT_METADATA_KEY).cloned()
+    }
+
+    /// Set the comment on this table, or remove it if `comment` is `None`,
+    /// as done by `COMMENT ON TABLE`.
+    ///
+    /// See [`TableComments`] for keeping track of changed comments.
+    ///
+    /// [`TableComments`]: crate::TableComments
+    fn set_table_comment(&self, _comment: Option<String>) -> Result<()> {
+        not_impl_err!("COMMENT ON TABLE not supported for this table")
+    }
+
+    /// Set the comment on a column of this table, or remove it if `comment`
+    /// is `None`, as done by `COMMENT ON COLUMN`.
+    ///
+    /// `column` is the name of a field of [`Self::schema`].
+    fn set_column_comment(&self, _column: &str, _comment: Option<String>) -> Result<()> {
+        not_impl_err!("COMMENT ON COLUMN not supported for this table")
+    }
+
     /// Create an [`ExecutionPlan`] for scanning the table with optionally
     /// specified `projection`, `filter` and `limit`, described below.
     ///
@@ -296,6 +341,103 @@ pub trait TableProvider: Debug + Sync + Send {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         not_impl_err!("Insert into not implemented for this table")
     }
+
+    /// Return an [`ExecutionPlan`] to delete the rows of this table
+    /// matching all of the given `filters`, if supported.
+    ///
+    /// `filters` are the conjuncts of the `WHERE` clause of the `DELETE`
+    /// statement, with columns referenced by their unqualified names. An
+    /// empty list deletes every row of the table.
+    ///
+    /// If the `WHERE` clause can not be expressed as `filters` alone, such
+    /// as when it contains subqueries, `selection` is a plan producing the
+    /// rows of the table to delete, and only these rows should be deleted.
+    /// See [`SelectedRows`] for how rows are identified.
+    ///
+    /// The returned plan should return a single row in a UInt64
+    /// column called "count" holding the number of rows deleted,
+    /// as described in [`Self::insert_into`].
+    ///
+    /// # See Also
+    ///
+    /// See [`DataMutationExec`] for the common pattern of applying
+    /// a mutation to the rows of a table.
+    ///
+    /// [`DataMutationExec`]: datafusion_physical_plan::dml::DataMutationExec
+    /// [`SelectedRows`]: datafusion_physical_plan::dml::SelectedRows
+    async fn delete_from(
+        &self,
+        _state: &dyn Session,
+        _filters: Vec<Expr>,
+        _selection: Option<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Delete not implemented for this table")
+    }
+
+    /// Return an [`ExecutionPlan`] to update the rows of this table
+    /// matching all of the given `filters`, if supported.
+    ///
+    /// `assignments` maps the name of each modified column to the
+    /// expression computing its new value from the values of the row
+    /// before the update. `filters` are the conjuncts of the `WHERE`
+    /// clause of the `UPDATE` statement. Columns are referenced by their
+    /// unqualified names. If the rows to update can not be selected by
+    /// `filters` alone, `selection` is a plan producing them, as described
+    /// in [`Self::delete_from`].
+    ///
+    /// The returned plan should return a single row in a UInt64
+    /// column called "count" holding the number of rows updated,
+    /// as described in [`Self::insert_into`].
+    ///
+    /// # See Also
+    ///
+    /// See [`DataMutationExec`] for the common pattern of applying
+    /// a mutation to the rows of a table.
+    ///
+    /// [`DataMutationExec`]: datafusion_physical_plan::dml::DataMutationExec
+    async fn update(
+        &self,
+        _state: &dyn Session,
+        _assignments: Vec<(String, Expr)>,
+        _filters: Vec<Expr>,
+        _selection: Option<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Update not implemented for this table")
+    }
+
+    /// Return an [`ExecutionPlan`] to apply the changes of a `MERGE`
+    /// statement to this table, if supported.
+    ///
+    /// Each row of `input` holds the values of the columns of the table,
+    /// followed by a UInt8 column called [`MERGE_ACTION_COLUMN`] holding
+    /// the [`MergeAction`] to apply to the row and a UInt64 column called
+    /// [`MERGE_TARGET_ROW_COLUMN`] identifying the existing row of the table
+    /// the row was matched with, if any. Rows of the table which no
+    /// source row matched are also included, with the [`MergeAction::Keep`]
+    /// action, so that the contents of the table after the statement are
+    /// all the rows of `input` except the deleted ones.
+    ///
+    /// The returned plan should return a single row in a UInt64
+    /// column called "count" holding the number of rows inserted,
+    /// updated or deleted, as described in [`Self::insert_into`].
+    ///
+    /// # See Also
+    ///
+    /// See [`merge_rows`] for validating the rows of `input` and
+    /// computing the new contents of the table.
+    ///
+    /// [`MERGE_ACTION_COLUMN`]: datafusion_expr::dml::MERGE_ACTION_COLUMN
+    /// [`MERGE_TARGET_ROW_COLUMN`]: datafusion_expr::dml::MERGE_TARGET_ROW_COLUMN
+    /// [`MergeAction`]: datafusion_expr::dml::MergeAction
+    /// [`MergeAction::Keep`]: datafusion_expr::dml::MergeAction::Keep
+    /// [`merge_rows`]: datafusion_physical_plan::dml::merge_rows
+    async fn merge_into(
+        &self,
+        _state: &dyn Session,
+        _input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Merge not implemented for this table")
+    }
 }
 
 /// A factory which creates [`TableProvider`]s at runtime given a URL.
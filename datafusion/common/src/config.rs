@@ -220,6 +220,11 @@ config_namespace! {
         /// parallel file scanning. Setting this to `true` ensures that newlines in values are
         /// parsed successfully, which may reduce performance.
         pub newlines_in_values: bool, default = false
+
+        /// Comma separated list of schemas, optionally qualified with a catalog,
+        /// searched in order for functions called without a schema name that are
+        /// not registered with the session. Defaults to the default schema.
+        pub search_path: Option<String>, default = None
     }
 }
 
@@ -359,6 +364,12 @@ config_namespace! {
         /// Should DataFusion support recursive CTEs
         pub enable_recursive_ctes: bool, default = true
 
+        /// The maximum number of times the recursive term of a recursive CTE
+        /// is evaluated, after which the query fails. Guards against recursive
+        /// queries that never terminate, e.g. on cyclic data. If `None`, the
+        /// number of iterations is unlimited
+        pub recursive_cte_max_iterations: Option<usize>, default = None
+
         /// Attempt to eliminate sorts by packing & sorting files with non-overlapping
         /// statistics into the same file groups.
         /// Currently experimental
@@ -389,6 +400,11 @@ config_namespace! {
         /// in joins can reduce memory usage when joining large
         /// tables with a highly-selective join filter, but is also slightly slower.
         pub enforce_batch_size_in_joins: bool, default = false
+
+        /// Should DataFusion check that the rows inserted into a table do not
+        /// duplicate the values of a primary key or unique constraint of the
+        /// table. The check reads all existing rows of the table on every insert.
+        pub enforce_unique_constraints: bool, default = false
     }
 }
 
@@ -578,6 +594,11 @@ config_namespace! {
         /// during aggregations, if possible
         pub enable_topk_aggregation: bool, default = true
 
+        /// When set to true, the logical plan optimizer will rewrite queries to read
+        /// from materialized views that contain their result. The result reflects the
+        /// contents of the view as of its last refresh, so it is disabled by default
+        pub enable_materialized_view_rewrite: bool, default = false
+
         /// When set to true, the optimizer will insert filters before a join between
         /// a nullable and non-nullable column to filter out nulls on the nullable side. This
         /// filter can add additional overhead when the file format does not fully support
@@ -23,7 +23,7 @@ use std::fmt::{Display, Formatter};
 use std::hash::Hash;
 use std::sync::Arc;
 
-use crate::error::{DataFusionError, Result, _plan_err, _schema_err};
+use crate::error::{_plan_err, _schema_err, DataFusionError, Result};
 use crate::{
     field_not_found, unqualified_field_not_found, Column, FunctionalDependencies,
     SchemaError, TableReference,
@@ -21,8 +21,9 @@ use base64::Engine;
 use std::sync::Arc;
 
 use crate::{
+    _internal_datafusion_err,
     config::{ParquetOptions, TableParquetOptions},
-    DataFusionError, Result, _internal_datafusion_err,
+    DataFusionError, Result,
 };
 
 use arrow_schema::Schema;
@@ -15,9 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
 use arrow::compute::CastOptions;
 use arrow::util::display::{DurationFormat, FormatOptions};
 
+use crate::error::{_plan_err, DataFusionError};
+
 /// The default [`FormatOptions`] to use within DataFusion
 pub const DEFAULT_FORMAT_OPTIONS: FormatOptions<'static> =
     FormatOptions::new().with_duration_format(DurationFormat::Pretty);
@@ -31,3 +36,49 @@ pub const DEFAULT_CAST_OPTIONS: CastOptions<'static> = CastOptions {
 pub const DEFAULT_CLI_FORMAT_OPTIONS: FormatOptions<'static> = FormatOptions::new()
     .with_duration_format(DurationFormat::Pretty)
     .with_null("NULL");
+
+/// The output format of `EXPLAIN` and `EXPLAIN ANALYZE`
+///
+/// Set with `EXPLAIN FORMAT <format> ...` or `EXPLAIN (FORMAT <format>) ...`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Default)]
+pub enum ExplainFormat {
+    /// Indented text, one line per operator (the default)
+    ///
+    /// ```text
+    /// ProjectionExec: expr=[a@0 as a]
+    ///   DataSourceExec: partitions=1, partition_sizes=[1]
+    /// ```
+    #[default]
+    Indent,
+    /// A JSON tree of the physical plan, with the properties, statistics
+    /// and metrics of each operator
+    Json,
+    /// A graphviz `dot` graph of the physical plan
+    Graphviz,
+}
+
+impl FromStr for ExplainFormat {
+    type Err = DataFusionError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_lowercase().as_str() {
+            "indent" | "text" => Ok(ExplainFormat::Indent),
+            "json" => Ok(ExplainFormat::Json),
+            "graphviz" => Ok(ExplainFormat::Graphviz),
+            _ => _plan_err!(
+                "Invalid explain format. Expected 'indent', 'json' or 'graphviz'. Got '{format}'"
+            ),
+        }
+    }
+}
+
+impl Display for ExplainFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = match self {
+            ExplainFormat::Indent => "indent",
+            ExplainFormat::Json => "json",
+            ExplainFormat::Graphviz => "graphviz",
+        };
+        write!(f, "{format}")
+    }
+}
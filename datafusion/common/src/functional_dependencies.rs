@@ -33,6 +33,14 @@ pub enum Constraint {
     PrimaryKey(Vec<usize>),
     /// Columns with the given indices form a composite unique key:
     Unique(Vec<usize>),
+    /// Rows must not make the given boolean expression evaluate to `false`.
+    /// The expression is kept as SQL text referring to columns by name:
+    Check {
+        /// Name of the constraint, if one was given
+        name: Option<String>,
+        /// SQL text of the expression
+        expr: String,
+    },
 }
 
 /// This object encapsulates a list of functional constraints:
@@ -83,6 +91,9 @@ impl Constraints {
                         (new_indices.len() == indices.len())
                             .then_some(Constraint::Unique(new_indices))
                     }
+                    // Check constraints refer to columns by name and may use
+                    // columns not included in the projection
+                    Constraint::Check { .. } => None,
                 }
             })
             .collect::<Vec<_>>();
@@ -212,7 +223,7 @@ impl FunctionalDependencies {
             // Construct dependency objects based on each individual constraint:
             let dependencies = constraints
                 .iter()
-                .map(|constraint| {
+                .filter_map(|constraint| {
                     // All the field indices are associated with the whole table
                     // since we are dealing with table level constraints:
                     let dependency = match constraint {
@@ -226,10 +237,11 @@ impl FunctionalDependencies {
                             (0..n_field).collect::<Vec<_>>(),
                             true,
                         ),
+                        Constraint::Check { .. } => return None,
                     };
                     // As primary keys are guaranteed to be unique, set the
                     // functional dependency mode to `Dependency::Single`:
-                    dependency.with_mode(Dependency::Single)
+                    Some(dependency.with_mode(Dependency::Single))
                 })
                 .collect::<Vec<_>>();
             Self::new(dependencies)
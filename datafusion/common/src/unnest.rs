@@ -66,6 +66,24 @@ use crate::Column;
 /// `recursions` instruct how a column should be unnested (e.g unnesting a column multiple
 /// time, with depth = 1 and depth = 2). Any unnested column not being mentioned inside this
 /// options is inferred to be unnested with depth = 1
+///
+/// If `ordinality_column` is set, an additional `Int64` column with that name
+/// is appended to the output, holding the 1-based position of each output row
+/// among the rows produced from the same input row (SQL `WITH ORDINALITY`).
+///
+/// ## `Unnest(c1)`, preserve_nulls: false, ordinality_column: "n"
+/// ```text
+///      ┌─────────┐ ┌─────┐                ┌─────────┐ ┌─────┐ ┌─────┐
+///      │ {1, 2}  │ │  A  │   Unnest       │    1    │ │  A  │ │  1  │
+///      ├─────────┤ ├─────┤                ├─────────┤ ├─────┤ ├─────┤
+///      │  null   │ │  B  │                │    2    │ │  A  │ │  2  │
+///      ├─────────┤ ├─────┤ ────────────▶  ├─────────┤ ├─────┤ ├─────┤
+///      │   {}    │ │  D  │                │    3    │ │  E  │ │  1  │
+///      ├─────────┤ ├─────┤                └─────────┘ └─────┘ └─────┘
+///      │   {3}   │ │  E  │                    c1        c2      n
+///      └─────────┘ └─────┘
+///        c1         c2
+/// ```
 #[derive(Debug, Clone, PartialEq, PartialOrd, Hash, Eq)]
 pub struct UnnestOptions {
     /// Should nulls in the input be preserved? Defaults to true
@@ -74,6 +92,9 @@ pub struct UnnestOptions {
     /// declare them here. Any unnested columns not being mentioned inside this option
     /// will be unnested with depth = 1
     pub recursions: Vec<RecursionUnnestOption>,
+    /// If set, the name of an additional column holding the 1-based position
+    /// of each unnested row within its input row
+    pub ordinality_column: Option<String>,
 }
 
 /// Instruction on how to unnest a column (mostly with a list type)
@@ -91,6 +112,7 @@ impl Default for UnnestOptions {
             // default to true to maintain backwards compatible behavior
             preserve_nulls: true,
             recursions: vec![],
+            ordinality_column: None,
         }
     }
 }
@@ -113,4 +135,11 @@ impl UnnestOptions {
         self.recursions.push(recursion);
         self
     }
+
+    /// Append a column with the given name holding the 1-based position of
+    /// each unnested row, as described on [`Self`]
+    pub fn with_ordinality_column(mut self, column_name: impl Into<String>) -> Self {
+        self.ordinality_column = Some(column_name.into());
+        self
+    }
 }
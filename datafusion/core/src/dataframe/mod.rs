@@ -50,10 +50,12 @@ use arrow_schema::{Schema, SchemaRef};
 use datafusion_common::config::{CsvOptions, JsonOptions};
 use datafusion_common::{
     exec_err, not_impl_err, plan_err, Column, DFSchema, DataFusionError, ParamValues,
-    SchemaError, UnnestOptions,
+    SchemaError, TableReference, UnnestOptions,
 };
 use datafusion_expr::dml::InsertOp;
-use datafusion_expr::{case, is_null, lit, SortExpr};
+use datafusion_expr::{
+    case, is_null, lit, CreateView, DdlStatement, SampleMethod, SortExpr,
+};
 use datafusion_expr::{
     utils::COUNT_STAR_EXPANSION, TableProviderFilterPushDown, UNNAMED_TABLE,
 };
@@ -438,6 +440,99 @@ impl DataFrame {
         })
     }
 
+    /// Rotate the given values of `pivot_column` into columns, computing
+    /// each aggregate in `aggr_expr` once per value.
+    ///
+    /// The rows are grouped by all columns that are not referenced by
+    /// `aggr_expr` or `pivot_column`. The new columns are named after the
+    /// pivot values, which can be given a different name with an alias.
+    ///
+    /// See [`LogicalPlanBuilder::pivot`] for details.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::functions_aggregate::expr_fn::sum;
+    /// # use datafusion_common::assert_batches_sorted_eq;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    /// let df = df.pivot(
+    ///     vec![sum(col("c"))],
+    ///     col("b"),
+    ///     vec![lit(2), lit(5).alias("five")],
+    /// )?;
+    /// let expected = vec![
+    ///     "+---+---+------+",
+    ///     "| a | 2 | five |",
+    ///     "+---+---+------+",
+    ///     "| 1 | 3 |      |",
+    ///     "+---+---+------+"
+    /// ];
+    /// # assert_batches_sorted_eq!(expected, &df.collect().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pivot(
+        self,
+        aggr_expr: Vec<Expr>,
+        pivot_column: Expr,
+        pivot_values: Vec<Expr>,
+    ) -> Result<DataFrame> {
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .pivot(aggr_expr, pivot_column, pivot_values)?
+            .build()?;
+        Ok(DataFrame {
+            session_state: self.session_state,
+            plan,
+        })
+    }
+
+    /// Rotate `columns` into rows, storing the column name in `name_column`
+    /// and its value in `value_column`.
+    ///
+    /// Rows where the unpivoted value is null are not returned.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion_common::assert_batches_sorted_eq;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    /// let df = df.unpivot("value", "name", &["b", "c"])?;
+    /// let expected = vec![
+    ///     "+---+------+-------+",
+    ///     "| a | name | value |",
+    ///     "+---+------+-------+",
+    ///     "| 1 | b    | 2     |",
+    ///     "| 1 | c    | 3     |",
+    ///     "+---+------+-------+"
+    /// ];
+    /// # assert_batches_sorted_eq!(expected, &df.collect().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unpivot(
+        self,
+        value_column: &str,
+        name_column: &str,
+        columns: &[&str],
+    ) -> Result<DataFrame> {
+        let columns = columns.iter().map(|c| Column::from(*c)).collect();
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .unpivot(value_column, name_column, columns)?
+            .build()?;
+        Ok(DataFrame {
+            session_state: self.session_state,
+            plan,
+        })
+    }
+
     /// Return a DataFrame with only rows for which `predicate` evaluates to
     /// `true`.
     ///
@@ -598,6 +693,43 @@ impl DataFrame {
         })
     }
 
+    /// Returns a new `DataFrame` with a random sample of the rows of this one.
+    ///
+    /// # Arguments
+    /// `method` - [`SampleMethod::Bernoulli`] to pick individual rows, or
+    /// [`SampleMethod::System`] to pick whole blocks of rows
+    /// `fraction` - Fraction of the rows to keep, between 0 and 1
+    /// `seed` - Seed making the sample repeatable, or `None` for a random one
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::logical_expr::SampleMethod;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example_long.csv", CsvReadOptions::new()).await?;
+    /// let df = df.sample(SampleMethod::Bernoulli, 0.5, Some(42))?;
+    /// # assert!(df.count().await? <= 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sample(
+        self,
+        method: SampleMethod,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Result<DataFrame> {
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .sample(method, fraction, seed)?
+            .build()?;
+        Ok(DataFrame {
+            session_state: self.session_state,
+            plan,
+        })
+    }
+
     /// Calculate the union of two [`DataFrame`]s, preserving duplicate rows.
     ///
     /// The two [`DataFrame`]s must have exactly the same schema
@@ -673,6 +805,84 @@ impl DataFrame {
         })
     }
 
+    /// Calculate the union of two [`DataFrame`]s using column names, preserving
+    /// duplicate rows.
+    ///
+    /// The columns of the two [`DataFrame`]s are matched by name rather than
+    /// by position. Columns that only exist in one of the [`DataFrame`]s are
+    /// filled with nulls for the rows of the other one.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion_common::assert_batches_sorted_eq;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    /// let d2 = df.clone().select_columns(&["c", "a"])?;
+    /// let df = df.union_by_name(d2)?;
+    /// let expected = vec![
+    ///     "+---+---+---+",
+    ///     "| a | b | c |",
+    ///     "+---+---+---+",
+    ///     "| 1 | 2 | 3 |",
+    ///     "| 1 |   | 3 |",
+    ///     "+---+---+---+"
+    /// ];
+    /// # assert_batches_sorted_eq!(expected, &df.collect().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn union_by_name(self, dataframe: DataFrame) -> Result<DataFrame> {
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .union_by_name(dataframe.plan)?
+            .build()?;
+        Ok(DataFrame {
+            session_state: self.session_state,
+            plan,
+        })
+    }
+
+    /// Calculate the distinct union of two [`DataFrame`]s using column names.
+    ///
+    /// The columns of the two [`DataFrame`]s are matched by name rather than
+    /// by position, as in [`DataFrame::union_by_name`]. Any duplicate rows are
+    /// discarded.
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion_common::assert_batches_sorted_eq;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    /// let d2 = df.clone().select_columns(&["c", "b", "a"])?;
+    /// let df = df.union_by_name_distinct(d2)?;
+    /// let expected = vec![
+    ///     "+---+---+---+",
+    ///     "| a | b | c |",
+    ///     "+---+---+---+",
+    ///     "| 1 | 2 | 3 |",
+    ///     "+---+---+---+"
+    /// ];
+    /// # assert_batches_sorted_eq!(expected, &df.collect().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn union_by_name_distinct(self, dataframe: DataFrame) -> Result<DataFrame> {
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .union_by_name_distinct(dataframe.plan)?
+            .build()?;
+        Ok(DataFrame {
+            session_state: self.session_state,
+            plan,
+        })
+    }
+
     /// Return a new `DataFrame` with all duplicated rows removed.
     ///
     /// # Example
@@ -1950,6 +2160,16 @@ impl TableProvider for DataFrameTableProvider {
         TableType::View
     }
 
+    fn get_table_ddl(&self, name: &TableReference) -> Result<Option<DdlStatement>> {
+        Ok(Some(DdlStatement::CreateView(CreateView {
+            name: name.clone(),
+            input: Arc::new(self.plan.clone()),
+            or_replace: false,
+            definition: None,
+            temporary: false,
+        })))
+    }
+
     async fn scan(
         &self,
         state: &dyn Session,
@@ -62,4 +62,10 @@ pub trait FileSource: Send + Sync {
     fn fmt_extra(&self, _t: DisplayFormatType, _f: &mut Formatter) -> fmt::Result {
         Ok(())
     }
+    /// Returns true if the source reads a sample of the row groups of every
+    /// file when [`FileScanConfig::sample`] is set. Otherwise, whole files
+    /// are skipped.
+    fn supports_row_group_sampling(&self) -> bool {
+        false
+    }
 }
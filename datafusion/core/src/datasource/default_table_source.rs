@@ -23,8 +23,10 @@ use std::{any::Any, borrow::Cow};
 use crate::datasource::TableProvider;
 
 use arrow::datatypes::SchemaRef;
-use datafusion_common::{internal_err, Constraints};
-use datafusion_expr::{Expr, TableProviderFilterPushDown, TableSource, TableType};
+use datafusion_common::{internal_err, Constraints, TableReference};
+use datafusion_expr::{
+    DdlStatement, Expr, TableProviderFilterPushDown, TableSource, TableType,
+};
 
 /// DataFusion default table source, wrapping TableProvider.
 ///
@@ -82,6 +84,17 @@ impl TableSource for DefaultTableSource {
     fn get_column_default(&self, column: &str) -> Option<&Expr> {
         self.table_provider.get_column_default(column)
     }
+
+    fn get_table_definition(&self) -> Option<&str> {
+        self.table_provider.get_table_definition()
+    }
+
+    fn get_table_ddl(
+        &self,
+        name: &TableReference,
+    ) -> datafusion_common::Result<Option<DdlStatement>> {
+        self.table_provider.get_table_ddl(name)
+    }
 }
 
 /// Wrap TableProvider in TableSource
@@ -433,8 +433,8 @@ impl FileFormat for ParquetFormat {
         conf: FileSinkConfig,
         order_requirements: Option<LexRequirement>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        if conf.insert_op != InsertOp::Append {
-            return not_impl_err!("Overwrites are not implemented yet for Parquet");
+        if conf.insert_op == InsertOp::Replace {
+            return not_impl_err!("Replace is not implemented for Parquet");
         }
 
         let sink = Arc::new(ParquetSink::new(conf, self.options.clone()));
@@ -2547,7 +2547,7 @@ mod tests {
             table_paths: vec![ListingTableUrl::parse(table_path)?],
             output_schema: schema.clone(),
             table_partition_cols: vec![],
-            insert_op: InsertOp::Overwrite,
+            insert_op: InsertOp::Append,
             keep_partition_by_columns: false,
             file_extension: "parquet".into(),
         };
@@ -2632,7 +2632,7 @@ mod tests {
             table_paths: vec![ListingTableUrl::parse("file:///")?],
             output_schema: schema.clone(),
             table_partition_cols: vec![("a".to_string(), DataType::Utf8)], // add partitioning
-            insert_op: InsertOp::Overwrite,
+            insert_op: InsertOp::Append,
             keep_partition_by_columns: false,
             file_extension: "parquet".into(),
         };
@@ -2715,7 +2715,7 @@ mod tests {
                 table_paths: vec![ListingTableUrl::parse("file:///")?],
                 output_schema: schema.clone(),
                 table_partition_cols: vec![],
-                insert_op: InsertOp::Overwrite,
+                insert_op: InsertOp::Append,
                 keep_partition_by_columns: false,
                 file_extension: "parquet".into(),
             };
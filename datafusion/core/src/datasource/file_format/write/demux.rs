@@ -90,13 +90,13 @@ pub type DemuxedStreamReceiver = UnboundedReceiver<(Path, RecordBatchReceiver)>;
 /// ```
 pub(crate) fn start_demuxer_task(
     config: &FileSinkConfig,
+    base_output_path: ListingTableUrl,
     data: SendableRecordBatchStream,
     context: &Arc<TaskContext>,
 ) -> (SpawnedTask<Result<()>>, DemuxedStreamReceiver) {
     let (tx, rx) = mpsc::unbounded_channel();
     let context = Arc::clone(context);
     let file_extension = config.file_extension.clone();
-    let base_output_path = config.table_paths[0].clone();
     let task = if config.table_partition_cols.is_empty() {
         let single_file_output = !base_output_path.is_collection()
             && base_output_path.file_extension().is_some();
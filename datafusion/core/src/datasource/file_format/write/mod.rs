@@ -18,19 +18,23 @@
 //! Module containing helper methods/traits related to enabling
 //! write support for the various file formats
 
+use std::collections::HashSet;
 use std::io::Write;
 use std::sync::Arc;
 
 use crate::datasource::file_format::file_compression_type::FileCompressionType;
+use crate::datasource::listing::ListingTableUrl;
 use crate::datasource::physical_plan::FileSinkConfig;
 use crate::error::Result;
 
 use arrow_array::RecordBatch;
 use arrow_schema::Schema;
 use bytes::Bytes;
+use futures::TryStreamExt;
 use object_store::buffered::BufWriter;
 use object_store::path::Path;
 use object_store::ObjectStore;
+use rand::distributions::DistString;
 use tokio::io::AsyncWrite;
 
 pub(crate) mod demux;
@@ -109,3 +113,500 @@ pub(crate) fn get_writer_schema(config: &FileSinkConfig) -> Arc<Schema> {
         Arc::clone(config.output_schema())
     }
 }
+
+/// Name prefix of the hidden directories that new files are staged in, see
+/// [`StagingDirectory`]
+const STAGING_DIRECTORY_PREFIX: &str = ".staging-";
+
+/// A hidden directory of a table that new files are written to before they
+/// replace existing files of the table.
+///
+/// The staged files are only moved into the table once all of them have been
+/// written, so a failed write leaves the table unchanged and readers of the
+/// table never see a partially written file. The staging directory is not a
+/// partition of the table, so listing the table ignores it unless
+/// `datafusion.execution.listing_table_ignore_subdirectory` is disabled.
+///
+/// Object stores can not rename several files at once, so replacing files of
+/// the table is not atomic: a reader listing the table meanwhile may see both
+/// old and new files, or neither. Object stores also have no directories to
+/// remove, so on a local file system the empty staging directory is left
+/// behind.
+#[derive(Debug, Clone)]
+pub(crate) struct StagingDirectory {
+    /// The table the staged files are moved to
+    table_path: ListingTableUrl,
+    /// The staging directory, inside of `table_path`
+    path: ListingTableUrl,
+}
+
+impl StagingDirectory {
+    /// Creates a new, uniquely named staging directory for the table
+    /// directory `table_path`
+    pub(crate) fn try_new(table_path: &ListingTableUrl) -> Result<Self> {
+        let table_url = table_path.as_str();
+        let separator = if table_url.ends_with('/') { "" } else { "/" };
+        let write_id =
+            rand::distributions::Alphanumeric.sample_string(&mut rand::thread_rng(), 16);
+        let path = ListingTableUrl::parse(format!(
+            "{table_url}{separator}{STAGING_DIRECTORY_PREFIX}{write_id}/"
+        ))?;
+        Ok(Self {
+            table_path: table_path.clone(),
+            path,
+        })
+    }
+
+    /// The staging directory, which new files are written to
+    pub(crate) fn path(&self) -> &ListingTableUrl {
+        &self.path
+    }
+
+    /// Returns the location in the table that the staged file at `location`
+    /// is moved to
+    pub(crate) fn table_location(&self, location: &Path) -> Path {
+        let staging_parts = self.path.prefix().parts().count();
+        self.table_path
+            .prefix()
+            .parts()
+            .chain(location.parts().skip(staging_parts))
+            .collect()
+    }
+
+    /// Returns the location in the staging directory of the file of the table
+    /// at `location`, the inverse of [`Self::table_location`]
+    fn staged_location(&self, location: &Path) -> Path {
+        let table_parts = self.table_path.prefix().parts().count();
+        self.path
+            .prefix()
+            .parts()
+            .chain(location.parts().skip(table_parts))
+            .collect()
+    }
+
+    /// Lists the staged files
+    async fn list(&self, object_store: &dyn ObjectStore) -> Result<Vec<Path>> {
+        Ok(object_store
+            .list(Some(self.path.prefix()))
+            .map_ok(|meta| meta.location)
+            .try_collect::<Vec<_>>()
+            .await?)
+    }
+
+    /// Moves all of the staged files into the table, returning their new
+    /// locations.
+    ///
+    /// If a file can not be moved, the files moved so far and the remaining
+    /// staged files are removed, which leaves the table unchanged.
+    pub(crate) async fn publish(
+        &self,
+        object_store: &dyn ObjectStore,
+    ) -> Result<Vec<Path>> {
+        let staged = self.list(object_store).await?;
+        let mut published = Vec::with_capacity(staged.len());
+        for location in &staged {
+            let table_location = self.table_location(location);
+            if let Err(e) = object_store.rename(location, &table_location).await {
+                // a rename may fail after copying the file, and the names of
+                // new files are unique, so the copy can be removed as well
+                for location in published.iter().chain([&table_location]) {
+                    remove_file(object_store, location).await;
+                }
+                self.abort(object_store).await;
+                return Err(e.into());
+            }
+            published.push(table_location);
+        }
+        Ok(published)
+    }
+
+    /// Moves the files of the table at `locations` into the staging
+    /// directory, so that they can be restored by [`Self::restore`].
+    ///
+    /// If a file can not be moved, the files moved so far are restored.
+    async fn stash(
+        &self,
+        object_store: &dyn ObjectStore,
+        locations: &[Path],
+    ) -> Result<()> {
+        for location in locations {
+            let staged_location = self.staged_location(location);
+            if let Err(e) = object_store.rename(location, &staged_location).await {
+                self.restore(object_store).await;
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the files stashed by [`Self::stash`] back into the table, only
+    /// logging the files that can not be moved
+    async fn restore(&self, object_store: &dyn ObjectStore) {
+        let staged = match self.list(object_store).await {
+            Ok(staged) => staged,
+            Err(e) => {
+                log::warn!("failed to list the stashed files of {}: {e}", self.path);
+                return;
+            }
+        };
+        for location in staged {
+            let table_location = self.table_location(&location);
+            if let Err(e) = object_store.rename(&location, &table_location).await {
+                log::warn!("failed to restore {table_location} from {location}: {e}");
+            }
+        }
+    }
+
+    /// Removes all of the staged files, after a failed write or once the
+    /// stashed files have been replaced
+    pub(crate) async fn abort(&self, object_store: &dyn ObjectStore) {
+        let staged = object_store
+            .list(Some(self.path.prefix()))
+            .try_collect::<Vec<_>>()
+            .await;
+        match staged {
+            Ok(staged) => {
+                for meta in staged {
+                    remove_file(object_store, &meta.location).await;
+                }
+            }
+            Err(e) => log::warn!("failed to list the staged files of {}: {e}", self.path),
+        }
+    }
+}
+
+/// Removes the file at `location` while cleaning up after an error, only
+/// logging a failure to do so
+async fn remove_file(object_store: &dyn ObjectStore, location: &Path) {
+    if let Err(e) = object_store.delete(location).await {
+        log::warn!("failed to remove {location} after error: {e}");
+    }
+}
+
+/// Returns true if `location` is inside of a [`StagingDirectory`]
+fn is_staged(location: &Path) -> bool {
+    location
+        .parts()
+        .any(|part| part.as_ref().starts_with(STAGING_DIRECTORY_PREFIX))
+}
+
+/// Lists the files of the output location of `config` that are replaced by
+/// the files staged in `staging`. Staged files are not part of the output.
+///
+/// Without partition columns, all of the existing files in the output
+/// directory are replaced. With hive style partitioning, only the files of
+/// the partitions present in the written data are replaced.
+async fn list_replaced_files(
+    config: &FileSinkConfig,
+    object_store: &dyn ObjectStore,
+    staging: &StagingDirectory,
+) -> Result<Vec<Path>> {
+    let mut replaced_directories = staging
+        .list(object_store)
+        .await?
+        .iter()
+        .map(|location| parent_directory(&staging.table_location(location)).to_string())
+        .collect::<HashSet<_>>();
+    if config.table_partition_cols.is_empty() {
+        replaced_directories.insert(config.table_paths[0].prefix().to_string());
+    }
+
+    let suffix = format!(".{}", config.file_extension);
+    Ok(object_store
+        .list(Some(config.table_paths[0].prefix()))
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .map(|meta| meta.location)
+        .filter(|location| {
+            location.as_ref().ends_with(&suffix)
+                && !is_staged(location)
+                && replaced_directories.contains(parent_directory(location))
+        })
+        .collect())
+}
+
+/// Moves the files of the output location of `config` that are replaced by
+/// the files staged in `staging` into a new staging directory
+async fn stash_replaced_files(
+    config: &FileSinkConfig,
+    object_store: &dyn ObjectStore,
+    staging: &StagingDirectory,
+) -> Result<StagingDirectory> {
+    let replaced = StagingDirectory::try_new(&config.table_paths[0])?;
+    let locations = list_replaced_files(config, object_store, staging).await?;
+    replaced.stash(object_store, &locations).await?;
+    Ok(replaced)
+}
+
+/// Completes overwriting the output location of `config` once the new files
+/// have been written to `staging`, with `result` the outcome of writing them.
+///
+/// The new files are only moved into the output location if all of them were
+/// written successfully. Otherwise the staged files are removed and the
+/// existing files are left untouched.
+///
+/// The replaced files are first moved out of the output location into a
+/// second staging directory, and only removed once the new files have been
+/// moved in. If moving any of the files fails, the files moved so far are
+/// moved back, so that the output location keeps its existing files unless
+/// moving them back fails as well. As a failure to remove the replaced files
+/// afterwards is only logged, they may be left behind in the hidden staging
+/// directory.
+pub(crate) async fn commit_overwrite(
+    config: &FileSinkConfig,
+    object_store: &dyn ObjectStore,
+    staging: &StagingDirectory,
+    result: Result<u64>,
+) -> Result<u64> {
+    let row_count = match result {
+        Ok(row_count) => row_count,
+        Err(e) => {
+            staging.abort(object_store).await;
+            return Err(e);
+        }
+    };
+
+    let replaced = match stash_replaced_files(config, object_store, staging).await {
+        Ok(replaced) => replaced,
+        Err(e) => {
+            staging.abort(object_store).await;
+            return Err(e);
+        }
+    };
+
+    if let Err(e) = staging.publish(object_store).await {
+        replaced.restore(object_store).await;
+        return Err(e);
+    }
+    replaced.abort(object_store).await;
+    Ok(row_count)
+}
+
+/// Returns the directory containing the file at `location`
+fn parent_directory(location: &Path) -> &str {
+    location
+        .as_ref()
+        .rsplit_once(object_store::path::DELIMITER)
+        .map(|(directory, _)| directory)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fmt::{self, Display, Formatter};
+
+    use crate::execution::object_store::ObjectStoreUrl;
+    use async_trait::async_trait;
+    use datafusion_expr::dml::InsertOp;
+    use futures::stream::BoxStream;
+    use object_store::memory::InMemory;
+    use object_store::{
+        GetOptions, GetResult, ListResult, MultipartUpload, ObjectMeta, PutMultipartOpts,
+        PutOptions, PutPayload, PutResult,
+    };
+
+    /// An [`ObjectStore`] that fails to delete the files matching `fail_delete`,
+    /// which also fails to rename them after copying them
+    #[derive(Debug)]
+    struct FailingDeleteObjectStore {
+        inner: InMemory,
+        fail_delete: fn(&Path) -> bool,
+    }
+
+    impl Display for FailingDeleteObjectStore {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            write!(f, "FailingDelete({})", self.inner)
+        }
+    }
+
+    #[async_trait]
+    impl ObjectStore for FailingDeleteObjectStore {
+        async fn put_opts(
+            &self,
+            location: &Path,
+            payload: PutPayload,
+            opts: PutOptions,
+        ) -> object_store::Result<PutResult> {
+            self.inner.put_opts(location, payload, opts).await
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            location: &Path,
+            opts: PutMultipartOpts,
+        ) -> object_store::Result<Box<dyn MultipartUpload>> {
+            self.inner.put_multipart_opts(location, opts).await
+        }
+
+        async fn get_opts(
+            &self,
+            location: &Path,
+            options: GetOptions,
+        ) -> object_store::Result<GetResult> {
+            self.inner.get_opts(location, options).await
+        }
+
+        async fn delete(&self, location: &Path) -> object_store::Result<()> {
+            if (self.fail_delete)(location) {
+                return Err(object_store::Error::Generic {
+                    store: "FailingDelete",
+                    source: format!("failed to delete {location}").into(),
+                });
+            }
+            self.inner.delete(location).await
+        }
+
+        fn list(
+            &self,
+            prefix: Option<&Path>,
+        ) -> BoxStream<'_, object_store::Result<ObjectMeta>> {
+            self.inner.list(prefix)
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            prefix: Option<&Path>,
+        ) -> object_store::Result<ListResult> {
+            self.inner.list_with_delimiter(prefix).await
+        }
+
+        async fn copy(&self, from: &Path, to: &Path) -> object_store::Result<()> {
+            self.inner.copy(from, to).await
+        }
+
+        async fn copy_if_not_exists(
+            &self,
+            from: &Path,
+            to: &Path,
+        ) -> object_store::Result<()> {
+            self.inner.copy_if_not_exists(from, to).await
+        }
+    }
+
+    async fn list(object_store: &dyn ObjectStore) -> Result<Vec<String>> {
+        let mut locations = object_store
+            .list(None)
+            .map_ok(|meta| meta.location.to_string())
+            .try_collect::<Vec<_>>()
+            .await?;
+        locations.sort();
+        Ok(locations)
+    }
+
+    #[tokio::test]
+    async fn staging_directory_publish_and_abort() -> Result<()> {
+        let object_store = InMemory::new();
+        let table_path = ListingTableUrl::parse("memory:///table/")?;
+        object_store
+            .put(&Path::from("table/old.csv"), PutPayload::from_static(b"a"))
+            .await?;
+
+        let staging = StagingDirectory::try_new(&table_path)?;
+        let staged = Path::from(format!("{}/p=1/new.csv", staging.path().prefix()));
+        assert_eq!(
+            staging.table_location(&staged),
+            Path::from("table/p=1/new.csv")
+        );
+        assert!(is_staged(&staged));
+
+        object_store
+            .put(&staged, PutPayload::from_static(b"b"))
+            .await?;
+        let published = staging.publish(&object_store).await?;
+        assert_eq!(published, vec![Path::from("table/p=1/new.csv")]);
+        assert_eq!(
+            list(&object_store).await?,
+            vec!["table/old.csv", "table/p=1/new.csv"]
+        );
+
+        let staging = StagingDirectory::try_new(&table_path)?;
+        let staged = Path::from(format!("{}/other.csv", staging.path().prefix()));
+        object_store
+            .put(&staged, PutPayload::from_static(b"c"))
+            .await?;
+        staging.abort(&object_store).await;
+        assert_eq!(
+            list(&object_store).await?,
+            vec!["table/old.csv", "table/p=1/new.csv"]
+        );
+        Ok(())
+    }
+
+    /// Overwrites the files `a.csv` and `b.csv` of a table with a staged
+    /// `new.csv`, and returns the result and the files of the table that are
+    /// not staged
+    async fn overwrite_with_failing_delete(
+        fail_delete: fn(&Path) -> bool,
+    ) -> Result<(Result<u64>, Vec<String>)> {
+        let object_store = FailingDeleteObjectStore {
+            inner: InMemory::new(),
+            fail_delete,
+        };
+        let table_path = ListingTableUrl::parse("memory:///table/")?;
+        let config = FileSinkConfig {
+            object_store_url: ObjectStoreUrl::parse("memory://")?,
+            file_groups: vec![],
+            table_paths: vec![table_path.clone()],
+            output_schema: Arc::new(Schema::empty()),
+            table_partition_cols: vec![],
+            insert_op: InsertOp::Overwrite,
+            keep_partition_by_columns: false,
+            file_extension: "csv".to_string(),
+        };
+        for name in ["a", "b"] {
+            object_store
+                .put(
+                    &Path::from(format!("table/{name}.csv")),
+                    PutPayload::from_static(b"old"),
+                )
+                .await?;
+        }
+        let staging = StagingDirectory::try_new(&table_path)?;
+        object_store
+            .put(
+                &Path::from(format!("{}/new.csv", staging.path().prefix())),
+                PutPayload::from_static(b"new"),
+            )
+            .await?;
+
+        let result = commit_overwrite(&config, &object_store, &staging, Ok(1)).await;
+        let files = list(&object_store)
+            .await?
+            .into_iter()
+            .filter(|location| !is_staged(&Path::from(location.as_str())))
+            .collect();
+        Ok((result, files))
+    }
+
+    #[tokio::test]
+    async fn commit_overwrite_restores_replaced_files() -> Result<()> {
+        // moving a replaced file out of the table fails
+        let (result, files) =
+            overwrite_with_failing_delete(|location| location.as_ref() == "table/b.csv")
+                .await?;
+        assert!(result.is_err());
+        assert_eq!(files, vec!["table/a.csv", "table/b.csv"]);
+
+        // moving the new file into the table fails
+        let (result, files) = overwrite_with_failing_delete(|location| {
+            is_staged(location) && location.filename() == Some("new.csv")
+        })
+        .await?;
+        assert!(result.is_err());
+        assert_eq!(files, vec!["table/a.csv", "table/b.csv"]);
+
+        // removing a replaced file once the new file is in the table fails,
+        // which leaves it in the staging directory
+        let (result, files) = overwrite_with_failing_delete(|location| {
+            is_staged(location) && location.filename() == Some("a.csv")
+        })
+        .await?;
+        assert_eq!(result?, 1);
+        assert_eq!(files, vec!["table/new.csv"]);
+
+        Ok(())
+    }
+}
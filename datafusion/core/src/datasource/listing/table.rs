@@ -18,36 +18,61 @@
 //! The table implementation.
 
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::{any::Any, str::FromStr, sync::Arc};
 
 use super::helpers::{expr_applicable_for_cols, pruned_partition_list, split_files};
 use super::{ListingTableUrl, PartitionedFile};
 
+#[cfg(feature = "parquet")]
+use crate::datasource::file_format::parquet::ParquetFormat;
+use crate::datasource::file_format::write::StagingDirectory;
 use crate::datasource::{
     create_ordering,
     file_format::{
-        file_compression_type::FileCompressionType, FileFormat, FilePushdownSupport,
+        csv::CsvFormat, file_compression_type::FileCompressionType, json::JsonFormat,
+        FileFormat, FilePushdownSupport,
     },
     get_statistics_with_limit,
     physical_plan::{FileScanConfig, FileSinkConfig},
 };
 use crate::execution::context::SessionState;
-use datafusion_catalog::TableProvider;
-use datafusion_common::{config_err, DataFusionError, Result};
+use datafusion_catalog::{TableComments, TableProvider};
+use datafusion_common::config::{ConfigFileType, TableOptions};
+use datafusion_common::{config_err, DataFusionError, Result, TableReference};
 use datafusion_expr::dml::InsertOp;
-use datafusion_expr::{utils::conjunction, Expr, TableProviderFilterPushDown};
-use datafusion_expr::{SortExpr, TableType};
-use datafusion_physical_plan::{empty::EmptyExec, ExecutionPlan, Statistics};
+use datafusion_expr::{
+    utils::conjunction, Expr, ExprSchemable, TableProviderFilterPushDown,
+};
+use datafusion_expr::{CreateExternalTable, DdlStatement, SortExpr, TableType};
+use datafusion_physical_plan::dml::{
+    check_replace_keys, delete_rows, merge_input_schema, merge_rows, update_rows,
+    DataMutation, DataMutationExec, ReplacedKeys, SelectedRows,
+};
+use datafusion_physical_plan::insert::{DataSink, DataSinkExec};
+use datafusion_physical_plan::memory::MemorySourceConfig;
+use datafusion_physical_plan::sorts::sort::SortExec;
+use datafusion_physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion_physical_plan::streaming::{PartitionStream, StreamingTableExec};
+use datafusion_physical_plan::union::UnionExec;
+use datafusion_physical_plan::{
+    empty::EmptyExec, execute_stream, DisplayAs, DisplayFormatType, ExecutionPlan,
+    ExecutionPlanProperties, PhysicalExpr, SendableRecordBatchStream, Statistics,
+};
 
 use arrow::datatypes::{DataType, Field, SchemaBuilder, SchemaRef};
+use arrow::record_batch::RecordBatch;
 use arrow_schema::Schema;
 use datafusion_common::{
-    config_datafusion_err, internal_err, plan_err, project_schema, Constraints,
-    SchemaExt, ToDFSchema,
+    config_datafusion_err, internal_err, not_impl_err, plan_err, project_schema,
+    Constraints, SchemaExt, ToDFSchema,
 };
 use datafusion_execution::cache::{
     cache_manager::FileStatisticsCache, cache_unit::DefaultFileStatisticsCache,
 };
+use datafusion_execution::object_store::ObjectStoreUrl;
+use datafusion_execution::TaskContext;
 use datafusion_physical_expr::{
     create_physical_expr, LexOrdering, PhysicalSortRequirement,
 };
@@ -57,7 +82,9 @@ use datafusion_catalog::Session;
 use datafusion_physical_expr_common::sort_expr::LexRequirement;
 use futures::{future, stream, StreamExt, TryStreamExt};
 use itertools::Itertools;
+use object_store::path::{Path, PathPart};
 use object_store::ObjectStore;
+use rand::distributions::DistString;
 
 /// Configuration for creating a [`ListingTable`]
 #[derive(Debug, Clone)]
@@ -715,6 +742,7 @@ pub struct ListingTable {
     collected_statistics: FileStatisticsCache,
     constraints: Constraints,
     column_defaults: HashMap<String, Expr>,
+    comments: TableComments,
 }
 
 impl ListingTable {
@@ -752,6 +780,7 @@ impl ListingTable {
         let table = Self {
             table_paths: config.table_paths,
             file_schema,
+            comments: TableComments::new(&table_schema),
             table_schema,
             options,
             definition: None,
@@ -990,6 +1019,36 @@ impl TableProvider for ListingTable {
         self.definition.as_deref()
     }
 
+    fn get_table_ddl(&self, name: &TableReference) -> Result<Option<DdlStatement>> {
+        // `CREATE EXTERNAL TABLE` has a single location
+        let [table_path] = self.table_paths.as_slice() else {
+            return Ok(None);
+        };
+
+        Ok(Some(DdlStatement::CreateExternalTable(
+            CreateExternalTable {
+                schema: self.comments.apply(&self.table_schema).to_dfschema_ref()?,
+                name: name.clone(),
+                location: table_path.to_string(),
+                file_type: self.options.format.get_ext().to_uppercase(),
+                table_partition_cols: self
+                    .options
+                    .table_partition_cols
+                    .iter()
+                    .map(|(name, _)| name.clone())
+                    .collect(),
+                if_not_exists: false,
+                temporary: false,
+                definition: None,
+                order_exprs: self.options.file_sort_order.clone(),
+                unbounded: false,
+                options: format_options(self.options.format.as_ref()),
+                constraints: self.constraints.clone(),
+                column_defaults: self.column_defaults.clone(),
+            },
+        )))
+    }
+
     async fn insert_into(
         &self,
         state: &dyn Session,
@@ -1027,6 +1086,21 @@ impl TableProvider for ListingTable {
             );
         }
 
+        let insert_op = match insert_op {
+            InsertOp::Replace => {
+                check_replace_keys(&self.constraints)?;
+                let sink = ListingTableReplaceSink {
+                    rewrite: self.create_rewrite(state).await?,
+                    schema: self.schema(),
+                    constraints: self.constraints.clone(),
+                    scan: self.scan(state, None, &[], None).await?,
+                    ordering: self.try_create_output_ordering()?.into_iter().next(),
+                };
+                return Ok(Arc::new(DataSinkExec::new(input, Arc::new(sink), None)));
+            }
+            insert_op => insert_op,
+        };
+
         // Get the object store for the table path.
         let store = state.runtime_env().object_store(table_path)?;
 
@@ -1058,26 +1132,7 @@ impl TableProvider for ListingTable {
             file_extension: self.options().format.get_ext(),
         };
 
-        let order_requirements = if !self.options().file_sort_order.is_empty() {
-            // Multiple sort orders in outer vec are equivalent, so we pass only the first one
-            let orderings = self.try_create_output_ordering()?;
-            let Some(ordering) = orderings.first() else {
-                return internal_err!(
-                    "Expected ListingTable to have a sort order, but none found!"
-                );
-            };
-            // Converts Vec<Vec<SortExpr>> into type required by execution plan to specify its required input ordering
-            Some(LexRequirement::new(
-                ordering
-                    .into_iter()
-                    .cloned()
-                    .map(PhysicalSortRequirement::from)
-                    .collect::<Vec<_>>(),
-            ))
-        } else {
-            None
-        };
-
+        let order_requirements = self.write_order_requirements()?;
         self.options()
             .format
             .create_writer_physical_plan(input, session_state, config, order_requirements)
@@ -1087,9 +1142,329 @@ impl TableProvider for ListingTable {
     fn get_column_default(&self, column: &str) -> Option<&Expr> {
         self.column_defaults.get(column)
     }
+
+    fn get_table_comment(&self) -> Option<String> {
+        self.comments.table()
+    }
+
+    fn get_column_comment(&self, column: &str) -> Option<String> {
+        self.comments.column(column)
+    }
+
+    fn set_table_comment(&self, comment: Option<String>) -> Result<()> {
+        self.comments.set_table(comment);
+        Ok(())
+    }
+
+    fn set_column_comment(&self, column: &str, comment: Option<String>) -> Result<()> {
+        self.comments.set_column(column, comment);
+        Ok(())
+    }
+
+    /// Returns an ExecutionPlan that deletes the rows of this table matching all
+    /// of the given `filters` and produced by `selection`, by rewriting every
+    /// file containing such rows.
+    async fn delete_from(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+        selection: Option<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.create_mutation_plan(state, filters, selection, None)
+            .await
+    }
+
+    /// Returns an ExecutionPlan that updates the rows of this table matching all
+    /// of the given `filters` and produced by `selection`, by rewriting every
+    /// file containing such rows.
+    async fn update(
+        &self,
+        state: &dyn Session,
+        assignments: Vec<(String, Expr)>,
+        filters: Vec<Expr>,
+        selection: Option<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.create_mutation_plan(state, filters, selection, Some(assignments))
+            .await
+    }
+
+    /// Returns an ExecutionPlan that merges the rows produced by `input` into
+    /// this table, by writing the new contents of the table to new files and
+    /// then deleting all of the existing files.
+    async fn merge_into(
+        &self,
+        state: &dyn Session,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let sink = ListingTableMergeSink {
+            rewrite: self.create_rewrite(state).await?,
+            table_schema: self.schema(),
+            schema: merge_input_schema(&self.schema()),
+        };
+        let order_requirements = self.write_order_requirements()?;
+        Ok(Arc::new(DataSinkExec::new(
+            input,
+            Arc::new(sink),
+            order_requirements,
+        )))
+    }
 }
 
 impl ListingTable {
+    /// Prepares replacing all of the files of this table with new ones
+    async fn create_rewrite(&self, state: &dyn Session) -> Result<ListingTableRewrite> {
+        let table_path = &self.table_paths()[0];
+        if self.table_paths.len() != 1 || !table_path.is_collection() {
+            return plan_err!(
+                "Rewriting a ListingTable is only supported for a single directory, URL is possibly missing a trailing `/`"
+            );
+        }
+
+        // TODO (https://github.com/apache/datafusion/issues/11600) remove downcast_ref from here?
+        let session_state = state.as_any().downcast_ref::<SessionState>().unwrap();
+        let store = state.runtime_env().object_store(table_path)?;
+        let files = pruned_partition_list(
+            session_state,
+            store.as_ref(),
+            table_path,
+            &[],
+            &self.options.file_extension,
+            &self.options.table_partition_cols,
+        )
+        .await?
+        .map_ok(|file| file.object_meta.location)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        let staging = StagingDirectory::try_new(table_path)?;
+        let config = FileSinkConfig {
+            object_store_url: table_path.object_store(),
+            table_paths: vec![staging.path().clone()],
+            file_groups: vec![],
+            output_schema: self.schema(),
+            table_partition_cols: self.options.table_partition_cols.clone(),
+            insert_op: InsertOp::Append,
+            keep_partition_by_columns: state
+                .config_options()
+                .execution
+                .keep_partition_by_columns,
+            file_extension: self.options().format.get_ext(),
+        };
+        let writer = self
+            .options()
+            .format
+            .create_writer_physical_plan(
+                Arc::new(EmptyExec::new(self.schema())),
+                session_state,
+                config,
+                None,
+            )
+            .await?;
+
+        Ok(ListingTableRewrite {
+            staging,
+            files,
+            writer,
+        })
+    }
+
+    /// Returns the ordering the rows written to this table must follow, if
+    /// the table has a sort order
+    fn write_order_requirements(&self) -> Result<Option<LexRequirement>> {
+        if self.options().file_sort_order.is_empty() {
+            return Ok(None);
+        }
+        // Multiple sort orders in outer vec are equivalent, so we pass only the first one
+        let orderings = self.try_create_output_ordering()?;
+        let Some(ordering) = orderings.first() else {
+            return internal_err!(
+                "Expected ListingTable to have a sort order, but none found!"
+            );
+        };
+        // Converts Vec<Vec<SortExpr>> into type required by execution plan to specify its required input ordering
+        Ok(Some(LexRequirement::new(
+            ordering
+                .into_iter()
+                .cloned()
+                .map(PhysicalSortRequirement::from)
+                .collect::<Vec<_>>(),
+        )))
+    }
+
+    /// Creates a plan rewriting the files of this table that contain rows
+    /// matching all of the given `filters` and produced by `selection`,
+    /// either deleting these rows or, if `assignments` are specified,
+    /// updating them.
+    async fn create_mutation_plan(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+        selection: Option<Arc<dyn ExecutionPlan>>,
+        assignments: Option<Vec<(String, Expr)>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let Some(table_path) = self.table_paths.first() else {
+            return plan_err!("Cannot modify a ListingTable without table paths");
+        };
+        if self.table_paths.iter().any(|path| !path.is_collection()) {
+            return plan_err!(
+                "Modifying a ListingTable backed by a single file is not supported, URL is possibly missing a trailing `/`"
+            );
+        }
+
+        let table_partition_cols = self
+            .options
+            .table_partition_cols
+            .iter()
+            .map(|col| Ok(self.table_schema.field_with_name(&col.0)?.clone()))
+            .collect::<Result<Vec<_>>>()?;
+        let table_partition_col_names = table_partition_cols
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect::<Vec<_>>();
+
+        let table_df_schema = self.table_schema.as_ref().clone().to_dfschema()?;
+        let assignments = assignments
+            .map(|assignments| {
+                assignments
+                    .into_iter()
+                    .map(|(name, expr)| {
+                        if table_partition_col_names.contains(&name.as_str()) {
+                            return plan_err!(
+                                "Updating partition column '{name}' of a ListingTable is not supported"
+                            );
+                        }
+                        let index = self.table_schema.index_of(&name)?;
+                        let expr = expr.cast_to(
+                            self.table_schema.field(index).data_type(),
+                            &table_df_schema,
+                        )?;
+                        Ok((index, state.create_physical_expr(expr, &table_df_schema)?))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?;
+
+        // Filters on partition columns only prune the files to rewrite, all of
+        // the filters are still evaluated against the rows of the remaining files
+        let partition_filters = filters
+            .iter()
+            .filter(|filter| {
+                can_be_evaluted_for_partition_pruning(&table_partition_col_names, filter)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        let predicate = conjunction(filters)
+            .map(|filter| state.create_physical_expr(filter, &table_df_schema))
+            .transpose()?;
+
+        // TODO (https://github.com/apache/datafusion/issues/11600) remove downcast_ref from here?
+        let session_state = state.as_any().downcast_ref::<SessionState>().unwrap();
+        let store = state.runtime_env().object_store(table_path)?;
+        let object_store_url = table_path.object_store();
+        let file_extension = match self.options.file_extension.trim_start_matches('.') {
+            "" => self.options.format.get_ext(),
+            extension => extension.to_string(),
+        };
+        let mut staging_directories = Vec::with_capacity(self.table_paths.len());
+        let mut rewrites = vec![];
+        for table_path in &self.table_paths {
+            let staging = StagingDirectory::try_new(table_path)?;
+            let files = pruned_partition_list(
+                session_state,
+                store.as_ref(),
+                table_path,
+                &partition_filters,
+                &self.options.file_extension,
+                &self.options.table_partition_cols,
+            )
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+
+            for file in files {
+                let scan = self
+                    .options
+                    .format
+                    .create_physical_plan(
+                        session_state,
+                        FileScanConfig::new(
+                            object_store_url.clone(),
+                            Arc::clone(&self.file_schema),
+                            self.options.format.file_source(),
+                        )
+                        .with_file_groups(vec![vec![file.clone()]])
+                        .with_table_partition_cols(table_partition_cols.clone()),
+                        None,
+                    )
+                    .await?;
+
+                // The rewritten file is staged in the same directory relative
+                // to the table as the original one, so that it keeps the same
+                // partition values once it is moved into the table
+                let mut directory = file
+                    .object_meta
+                    .location
+                    .parts()
+                    .skip(table_path.prefix().parts().count())
+                    .collect::<Vec<_>>();
+                directory.pop();
+                let write_id = rand::distributions::Alphanumeric
+                    .sample_string(&mut rand::thread_rng(), 16);
+                let new_file = staging
+                    .path()
+                    .prefix()
+                    .parts()
+                    .chain(directory)
+                    .chain(std::iter::once(PathPart::from(format!(
+                        "{write_id}.{file_extension}"
+                    ))))
+                    .collect::<Path>();
+                let config = FileSinkConfig {
+                    object_store_url: object_store_url.clone(),
+                    table_paths: vec![ListingTableUrl::parse(format!(
+                        "{}{new_file}",
+                        object_store_url.as_str()
+                    ))?],
+                    file_groups: vec![],
+                    output_schema: Arc::clone(&self.file_schema),
+                    table_partition_cols: vec![],
+                    insert_op: InsertOp::Append,
+                    keep_partition_by_columns: false,
+                    file_extension: file_extension.clone(),
+                };
+                let writer = self
+                    .options
+                    .format
+                    .create_writer_physical_plan(
+                        Arc::clone(&scan),
+                        session_state,
+                        config,
+                        None,
+                    )
+                    .await?;
+
+                rewrites.push(FileRewrite {
+                    file: file.object_meta.location,
+                    scan,
+                    writer,
+                });
+            }
+            staging_directories.push(staging);
+        }
+
+        let mutation = ListingTableMutation {
+            object_store_url,
+            file_schema: Arc::clone(&self.file_schema),
+            staging_directories,
+            rewrites,
+            predicate,
+            assignments,
+        };
+        Ok(Arc::new(
+            DataMutationExec::new(Arc::new(mutation)).with_selection(selection),
+        ))
+    }
+
     /// Get the list of files for a scan as well as the file level statistics.
     /// The list is grouped to let the execution plan know how the files should
     /// be distributed to different threads / executors.
@@ -1188,14 +1563,469 @@ impl ListingTable {
     }
 }
 
+/// A file of a [`ListingTable`] that may be rewritten by a [`ListingTableMutation`]
+#[derive(Debug)]
+struct FileRewrite {
+    /// Location of the original file
+    file: Path,
+    /// Plan reading the rows of the original file, including the partition columns
+    scan: Arc<dyn ExecutionPlan>,
+    /// Plan whose [`DataSink`] writes the rewritten file to a [`StagingDirectory`]
+    ///
+    /// [`DataSink`]: datafusion_physical_plan::insert::DataSink
+    writer: Arc<dyn ExecutionPlan>,
+}
+
+/// Implements deleting and updating the rows of a [`ListingTable`]
+///
+/// Every file containing rows selected by the predicate is streamed through
+/// the modification and written to a new file in a [`StagingDirectory`] of
+/// the table. Once all of the new files have been written, they are moved
+/// into the table and the original files are deleted. If any file fails to
+/// be rewritten, the staged files are removed again and the table is left
+/// unchanged.
+#[derive(Debug)]
+struct ListingTableMutation {
+    object_store_url: ObjectStoreUrl,
+    /// File fields only
+    file_schema: SchemaRef,
+    /// Directories the rewritten files are staged in, one per table path
+    staging_directories: Vec<StagingDirectory>,
+    rewrites: Vec<FileRewrite>,
+    /// Selects the rows to modify, or all rows if `None`
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    /// Index and new value of each updated column, or `None` to
+    /// delete the selected rows
+    assignments: Option<Vec<(usize, Arc<dyn PhysicalExpr>)>>,
+}
+
+impl DisplayAs for ListingTableMutation {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "ListingTable (files={})", self.rewrites.len())?;
+                match &self.assignments {
+                    None => write!(f, ", op=Delete")?,
+                    Some(assignments) => {
+                        let assignments = assignments
+                            .iter()
+                            .map(|(index, expr)| {
+                                format!(
+                                    "{}={expr}",
+                                    self.file_schema.field(*index).name()
+                                )
+                            })
+                            .join(", ");
+                        write!(f, ", op=Update, assignments=[{assignments}]")?
+                    }
+                }
+                if let Some(predicate) = &self.predicate {
+                    write!(f, ", predicate={predicate}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ListingTableMutation {
+    /// Stages the new version of every file containing selected rows,
+    /// recording the files to replace in `replaced`. Returns the number of
+    /// rows affected.
+    async fn rewrite_files(
+        &self,
+        context: &Arc<TaskContext>,
+        selected: Option<&Arc<SelectedRows>>,
+        replaced: &mut Vec<Path>,
+    ) -> Result<usize> {
+        let mut row_count = 0;
+        for rewrite in &self.rewrites {
+            // Only rewrite the files with selected rows, which is checked
+            // before writing anything, stopping at the first selected row
+            if !self
+                .selects_any_row(rewrite, context, selected.map(Arc::as_ref))
+                .await?
+            {
+                continue;
+            }
+
+            let Some(writer) = rewrite.writer.as_any().downcast_ref::<DataSinkExec>()
+            else {
+                return not_impl_err!(
+                    "Modifying a ListingTable requires its format to write through a DataSinkExec"
+                );
+            };
+            let affected = Arc::new(AtomicU64::new(0));
+            let data = self.modified_rows(
+                rewrite,
+                context,
+                selected.cloned(),
+                Arc::clone(&affected),
+            )?;
+            writer.sink().write_all(data, context).await?;
+            row_count += affected.load(Ordering::Relaxed) as usize;
+            replaced.push(rewrite.file.clone());
+        }
+        Ok(row_count)
+    }
+
+    /// Returns true if the file of `rewrite` has a row selected by the
+    /// predicate and `selected`
+    async fn selects_any_row(
+        &self,
+        rewrite: &FileRewrite,
+        context: &Arc<TaskContext>,
+        selected: Option<&SelectedRows>,
+    ) -> Result<bool> {
+        let mut batches = execute_stream(Arc::clone(&rewrite.scan), Arc::clone(context))?;
+        while let Some(batch) = batches.try_next().await? {
+            let (_, count) = delete_rows(&batch, self.predicate.as_ref(), selected)?;
+            if count > 0 {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns the rows of the file of `rewrite` once modified, without the
+    /// partition columns, counting the modified rows in `affected`
+    fn modified_rows(
+        &self,
+        rewrite: &FileRewrite,
+        context: &Arc<TaskContext>,
+        selected: Option<Arc<SelectedRows>>,
+        affected: Arc<AtomicU64>,
+    ) -> Result<SendableRecordBatchStream> {
+        let file_columns = (0..self.file_schema.fields().len()).collect::<Vec<_>>();
+        let predicate = self.predicate.clone();
+        let assignments = self.assignments.clone();
+        let batches = execute_stream(Arc::clone(&rewrite.scan), Arc::clone(context))?
+            .map(move |batch| {
+                let batch = batch?;
+                let (batch, batch_affected) = match &assignments {
+                    None => delete_rows(&batch, predicate.as_ref(), selected.as_deref())?,
+                    Some(assignments) => update_rows(
+                        &batch,
+                        predicate.as_ref(),
+                        selected.as_deref(),
+                        assignments,
+                    )?,
+                };
+                affected.fetch_add(batch_affected as u64, Ordering::Relaxed);
+                Ok(batch.project(&file_columns)?)
+            })
+            // A file whose rows are all deleted is not written again
+            .try_filter(|batch| future::ready(batch.num_rows() > 0));
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.file_schema),
+            batches,
+        )))
+    }
+}
+
+#[async_trait]
+impl DataMutation for ListingTableMutation {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn apply(
+        &self,
+        context: &Arc<TaskContext>,
+        selected: Option<Arc<SelectedRows>>,
+    ) -> Result<u64> {
+        let store = context.runtime_env().object_store(&self.object_store_url)?;
+
+        let mut replaced = vec![];
+        let row_count = match self
+            .rewrite_files(context, selected.as_ref(), &mut replaced)
+            .await
+        {
+            Ok(row_count) => row_count,
+            Err(e) => {
+                // Roll back by removing the staged files, the original files
+                // have not been modified yet
+                for staging in &self.staging_directories {
+                    staging.abort(store.as_ref()).await;
+                }
+                return Err(e);
+            }
+        };
+
+        for (i, staging) in self.staging_directories.iter().enumerate() {
+            if let Err(e) = staging.publish(store.as_ref()).await {
+                for staging in &self.staging_directories[i + 1..] {
+                    staging.abort(store.as_ref()).await;
+                }
+                return Err(e);
+            }
+        }
+        for location in replaced {
+            store.delete(&location).await?;
+        }
+        Ok(row_count as u64)
+    }
+}
+
+/// Replaces all of the files of a [`ListingTable`] with new ones
+///
+/// The new contents of the table are written to a [`StagingDirectory`].
+/// Once they have all been written, they are moved into the table and the
+/// existing files are deleted. If writing fails, the staged files are
+/// removed again and the table is left unchanged.
+#[derive(Debug)]
+struct ListingTableRewrite {
+    /// Directory the new files are written to
+    staging: StagingDirectory,
+    /// Existing files of the table
+    files: Vec<Path>,
+    /// Plan whose [`DataSink`] writes the new files
+    writer: Arc<dyn ExecutionPlan>,
+}
+
+impl ListingTableRewrite {
+    /// Replaces the contents of the table with the rows of `data`
+    async fn write(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<()> {
+        let Some(writer) = self.writer.as_any().downcast_ref::<DataSinkExec>() else {
+            return not_impl_err!(
+                "Rewriting a ListingTable requires its format to write through a DataSinkExec"
+            );
+        };
+        let store = context.runtime_env().object_store(self.staging.path())?;
+
+        if let Err(e) = writer.sink().write_all(data, context).await {
+            self.staging.abort(store.as_ref()).await;
+            return Err(e);
+        }
+
+        self.staging.publish(store.as_ref()).await?;
+        for location in &self.files {
+            store.delete(location).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Implements merging rows into a [`ListingTable`], see [`ListingTableRewrite`]
+#[derive(Debug)]
+struct ListingTableMergeSink {
+    rewrite: ListingTableRewrite,
+    table_schema: SchemaRef,
+    /// Schema of the input rows, see [`merge_input_schema`]
+    schema: SchemaRef,
+}
+
+impl DisplayAs for ListingTableMergeSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let file_count = self.rewrite.files.len();
+                write!(f, "ListingTable (files={file_count}), op=Merge")
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DataSink for ListingTableMergeSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let affected = Arc::new(AtomicU64::new(0));
+        let data =
+            merge_rows(data, Arc::clone(&self.table_schema), Arc::clone(&affected))?;
+        self.rewrite.write(data, context).await?;
+        Ok(affected.load(Ordering::Relaxed))
+    }
+}
+
+/// Implements [`InsertOp::Replace`] for a [`ListingTable`] with primary or
+/// unique keys, by rewriting the whole table, see [`ListingTableRewrite`]
+#[derive(Debug)]
+struct ListingTableReplaceSink {
+    rewrite: ListingTableRewrite,
+    schema: SchemaRef,
+    constraints: Constraints,
+    /// Plan reading the existing rows of the table
+    scan: Arc<dyn ExecutionPlan>,
+    /// Sort order of the files of the table, if any
+    ordering: Option<LexOrdering>,
+}
+
+impl DisplayAs for ListingTableReplaceSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let file_count = self.rewrite.files.len();
+                write!(f, "ListingTable (files={file_count}), op=Replace Into")
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DataSink for ListingTableReplaceSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Writes the existing rows that are not replaced by an inserted row,
+    /// followed by the inserted rows. Only the inserted rows are held in
+    /// memory, the existing rows are streamed from the files of the table.
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let inserted = data
+            .map(|batch| -> Result<_> {
+                let batch = batch?;
+                Ok(RecordBatch::try_new(
+                    Arc::clone(&self.schema),
+                    batch.columns().to_vec(),
+                )?)
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
+        let row_count = inserted.iter().map(|batch| batch.num_rows()).sum::<usize>();
+        let (replaced_keys, inserted) =
+            ReplacedKeys::try_new(&self.schema, &self.constraints, &inserted)?;
+        let replaced_keys = Arc::new(replaced_keys);
+
+        let partitions = (0..self.scan.output_partitioning().partition_count())
+            .map(|partition| {
+                Arc::new(RemainingRows {
+                    schema: Arc::clone(&self.schema),
+                    scan: Arc::clone(&self.scan),
+                    partition,
+                    replaced_keys: Arc::clone(&replaced_keys),
+                }) as Arc<dyn PartitionStream>
+            })
+            .collect();
+        let remaining = StreamingTableExec::try_new(
+            Arc::clone(&self.schema),
+            partitions,
+            None,
+            vec![],
+            false,
+            None,
+        )?;
+        let inserted = MemorySourceConfig::try_new_exec(
+            &[inserted],
+            Arc::clone(&self.schema),
+            None,
+        )?;
+        let mut plan: Arc<dyn ExecutionPlan> =
+            Arc::new(UnionExec::new(vec![Arc::new(remaining), inserted]));
+        if let Some(ordering) = &self.ordering {
+            plan = Arc::new(SortExec::new(ordering.clone(), plan));
+        }
+
+        let data = execute_stream(plan, Arc::clone(context))?;
+        self.rewrite.write(data, context).await?;
+        Ok(row_count as u64)
+    }
+}
+
+/// The existing rows of a partition of a [`ListingTable`] that are not
+/// replaced by `REPLACE INTO`, see [`ListingTableReplaceSink`]
+#[derive(Debug)]
+struct RemainingRows {
+    schema: SchemaRef,
+    /// Plan reading the existing rows of the table
+    scan: Arc<dyn ExecutionPlan>,
+    partition: usize,
+    replaced_keys: Arc<ReplacedKeys>,
+}
+
+impl PartitionStream for RemainingRows {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let replaced_keys = Arc::clone(&self.replaced_keys);
+        let stream = stream::once(future::ready(self.scan.execute(self.partition, ctx)))
+            .try_flatten()
+            .and_then(move |batch| {
+                future::ready(
+                    replaced_keys
+                        .remove_replaced(&batch)
+                        .map(|(batch, _)| batch),
+                )
+            });
+        Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            stream,
+        ))
+    }
+}
+
+/// Returns the options of `format` that differ from their defaults, keyed
+/// as in the `OPTIONS` clause of `CREATE EXTERNAL TABLE`
+fn format_options(format: &dyn FileFormat) -> HashMap<String, String> {
+    let format = format.as_any();
+    let mut options = TableOptions::default();
+    if let Some(csv) = format.downcast_ref::<CsvFormat>() {
+        options.set_config_format(ConfigFileType::CSV);
+        options.csv = csv.options().clone();
+    } else if let Some(json) = format.downcast_ref::<JsonFormat>() {
+        options.set_config_format(ConfigFileType::JSON);
+        options.json = json.options().clone();
+    }
+    #[cfg(feature = "parquet")]
+    if let Some(parquet) = format.downcast_ref::<ParquetFormat>() {
+        options.set_config_format(ConfigFileType::PARQUET);
+        options.parquet = parquet.options().clone();
+    }
+    // other formats have no options that can be set in SQL
+    let Some(file_type) = options.current_format.clone() else {
+        return HashMap::new();
+    };
+
+    let mut defaults = TableOptions::default();
+    defaults.set_config_format(file_type);
+    let defaults = defaults
+        .entries()
+        .into_iter()
+        .map(|entry| (entry.key, entry.value))
+        .collect::<HashMap<_, _>>();
+
+    options
+        .entries()
+        .into_iter()
+        .filter_map(|entry| {
+            let value = entry.value?;
+            let is_default = defaults
+                .get(&entry.key)
+                .is_some_and(|default| default.as_ref() == Some(&value));
+            (!is_default).then_some((entry.key, value))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::datasource::file_format::avro::AvroFormat;
-    use crate::datasource::file_format::csv::CsvFormat;
-    use crate::datasource::file_format::json::JsonFormat;
-    #[cfg(feature = "parquet")]
-    use crate::datasource::file_format::parquet::ParquetFormat;
     use crate::datasource::{provider_as_source, MemTable};
     use crate::execution::options::ArrowReadOptions;
     use crate::prelude::*;
@@ -0,0 +1,230 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Materialized view data source which stores the result of a LogicalPlan.
+
+use std::{any::Any, sync::Arc};
+
+use crate::{
+    error::Result,
+    logical_expr::{Expr, LogicalPlan},
+    physical_plan::{
+        coalesce_partitions::CoalescePartitionsExec, collect, ExecutionPlan,
+        ExecutionPlanProperties,
+    },
+};
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+use datafusion_catalog::Session;
+use datafusion_common::{plan_err, Constraints, SchemaExt, Statistics};
+use datafusion_execution::TaskContext;
+use datafusion_expr::dml::InsertOp;
+use datafusion_expr::TableProviderFilterPushDown;
+
+use crate::datasource::{TableProvider, TableType};
+
+/// An implementation of `TableProvider` that stores the result of a logical
+/// plan in another `TableProvider`, such as a [`MemTable`] or a
+/// [`ListingTable`].
+///
+/// The stored result is only recomputed by [`MaterializedView::refresh`].
+///
+/// [`MemTable`]: crate::datasource::MemTable
+/// [`ListingTable`]: crate::datasource::listing::ListingTable
+#[derive(Debug)]
+pub struct MaterializedView {
+    /// LogicalPlan computing the contents of the view
+    logical_plan: LogicalPlan,
+    /// Table holding the contents of the view
+    storage: Arc<dyn TableProvider>,
+    /// SQL used to create the view, if available
+    definition: Option<String>,
+}
+
+impl MaterializedView {
+    /// Create a new materialized view storing the result of `logical_plan`
+    /// in `storage`.
+    ///
+    /// The view is empty until it is [refreshed](Self::refresh).
+    pub fn try_new(
+        logical_plan: LogicalPlan,
+        storage: Arc<dyn TableProvider>,
+        definition: Option<String>,
+    ) -> Result<Self> {
+        let plan_schema = logical_plan.schema().as_arrow();
+        if !storage
+            .schema()
+            .logically_equivalent_names_and_types(plan_schema)
+        {
+            return plan_err!(
+                "Storage of materialized view must have the schema of its query. \
+                Expected: {plan_schema}, got: {}",
+                storage.schema()
+            );
+        }
+
+        Ok(Self {
+            logical_plan,
+            storage,
+            definition,
+        })
+    }
+
+    /// Get definition ref
+    pub fn definition(&self) -> Option<&String> {
+        self.definition.as_ref()
+    }
+
+    /// Get logical_plan ref
+    pub fn logical_plan(&self) -> &LogicalPlan {
+        &self.logical_plan
+    }
+
+    /// Get the table holding the contents of the view
+    pub fn storage(&self) -> &Arc<dyn TableProvider> {
+        &self.storage
+    }
+
+    /// Recompute the contents of the view, replacing the stored result.
+    ///
+    /// The view must not be registered for query rewriting in `state`, see
+    /// [`SessionState::register_materialized_view`], as its query would then
+    /// read its own stale contents.
+    ///
+    /// [`SessionState::register_materialized_view`]: crate::execution::session_state::SessionState::register_materialized_view
+    pub async fn refresh(&self, state: &dyn Session) -> Result<()> {
+        let mut input = state.create_physical_plan(&self.logical_plan).await?;
+        // the sink only writes the first partition of its input
+        if input.output_partitioning().partition_count() > 1 {
+            input = Arc::new(CoalescePartitionsExec::new(input));
+        }
+        let plan = self
+            .storage
+            .insert_into(state, input, InsertOp::Overwrite)
+            .await?;
+        collect(plan, Arc::new(TaskContext::from(state))).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TableProvider for MaterializedView {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.storage.schema()
+    }
+
+    fn constraints(&self) -> Option<&Constraints> {
+        self.storage.constraints()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::View
+    }
+
+    fn get_table_definition(&self) -> Option<&str> {
+        self.definition.as_deref()
+    }
+
+    fn get_table_comment(&self) -> Option<String> {
+        self.storage.get_table_comment()
+    }
+
+    fn get_column_comment(&self, column: &str) -> Option<String> {
+        self.storage.get_column_comment(column)
+    }
+
+    fn set_table_comment(&self, comment: Option<String>) -> Result<()> {
+        self.storage.set_table_comment(comment)
+    }
+
+    fn set_column_comment(&self, column: &str, comment: Option<String>) -> Result<()> {
+        self.storage.set_column_comment(column, comment)
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        self.storage.supports_filters_pushdown(filters)
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        self.storage.statistics()
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.storage.scan(state, projection, filters, limit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::assert_batches_sorted_eq;
+    use crate::datasource::MemTable;
+    use crate::prelude::SessionContext;
+
+    #[tokio::test]
+    async fn refresh_replaces_contents() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE t AS VALUES (1), (2)")
+            .await?
+            .collect()
+            .await?;
+
+        let plan = ctx
+            .sql("SELECT column1 AS a FROM t")
+            .await?
+            .into_unoptimized_plan();
+        let schema = Arc::new(plan.schema().as_arrow().clone());
+        let storage = Arc::new(MemTable::try_new(schema, vec![vec![]])?);
+        let view = Arc::new(MaterializedView::try_new(plan, storage, None)?);
+        ctx.register_table("v", Arc::clone(&view) as _)?;
+
+        // empty until refreshed
+        let results = ctx.sql("SELECT * FROM v").await?.collect().await?;
+        assert!(results.iter().all(|batch| batch.num_rows() == 0));
+
+        view.refresh(&ctx.state()).await?;
+        ctx.sql("INSERT INTO t VALUES (3)").await?.collect().await?;
+
+        // only refreshing picks up the new row
+        let expected = ["+---+", "| a |", "+---+", "| 1 |", "| 2 |", "+---+"];
+        let results = ctx.sql("SELECT * FROM v").await?.collect().await?;
+        assert_batches_sorted_eq!(expected, &results);
+
+        view.refresh(&ctx.state()).await?;
+        let expected = [
+            "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "+---+",
+        ];
+        let results = ctx.sql("SELECT * FROM v").await?.collect().await?;
+        assert_batches_sorted_eq!(expected, &results);
+
+        Ok(())
+    }
+}
@@ -20,32 +20,43 @@
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt::{self, Debug};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use crate::datasource::{TableProvider, TableType};
 use crate::error::Result;
 use crate::execution::context::SessionState;
 use crate::logical_expr::Expr;
+use crate::physical_plan::dml::{
+    check_replace_keys, delete_rows, merge_input_schema, merge_rows, update_rows,
+    DataMutation, DataMutationExec, ReplacedKeys, SelectedRows,
+};
 use crate::physical_plan::insert::{DataSink, DataSinkExec};
 use crate::physical_plan::repartition::RepartitionExec;
 use crate::physical_plan::{
     common, DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties,
-    Partitioning, SendableRecordBatchStream,
+    Partitioning, PhysicalExpr, SendableRecordBatchStream,
 };
 use crate::physical_planner::create_physical_sort_exprs;
 
-use arrow::datatypes::SchemaRef;
+use arrow::datatypes::{DataType, SchemaRef};
 use arrow::record_batch::RecordBatch;
-use datafusion_catalog::Session;
-use datafusion_common::{not_impl_err, plan_err, Constraints, DFSchema, SchemaExt};
+use datafusion_catalog::{Session, TableComments};
+use datafusion_common::{
+    exec_err, plan_err, Constraints, DFSchema, SchemaExt, TableReference,
+};
 use datafusion_execution::TaskContext;
 use datafusion_expr::dml::InsertOp;
-use datafusion_expr::SortExpr;
+use datafusion_expr::utils::conjunction;
+use datafusion_expr::{
+    CreateMemoryTable, DdlStatement, EmptyRelation, ExprSchemable, LogicalPlan, SortExpr,
+};
 use datafusion_physical_plan::memory::MemorySourceConfig;
 use datafusion_physical_plan::source::DataSourceExec;
 
 use async_trait::async_trait;
 use futures::StreamExt;
+use itertools::Itertools;
 use log::debug;
 use parking_lot::Mutex;
 use tokio::sync::RwLock;
@@ -64,6 +75,8 @@ pub struct MemTable {
     pub(crate) batches: Vec<PartitionData>,
     constraints: Constraints,
     column_defaults: HashMap<String, Expr>,
+    /// Comments on the table and its columns
+    comments: TableComments,
     /// Optional pre-known sort order(s). Must be `SortExpr`s.
     /// inserting data into this table removes the order
     pub sort_order: Arc<Mutex<Vec<Vec<SortExpr>>>>,
@@ -84,6 +97,7 @@ impl MemTable {
         }
 
         Ok(Self {
+            comments: TableComments::new(&schema),
             schema,
             batches: partitions
                 .into_iter()
@@ -110,6 +124,12 @@ impl MemTable {
         self
     }
 
+    /// Assign the comment on the table
+    pub fn with_comment(self, comment: Option<String>) -> Self {
+        self.comments.set_table(comment);
+        self
+    }
+
     /// Specify an optional pre-known sort order(s). Must be `SortExpr`s.
     ///
     /// If the data is not sorted by this order, DataFusion may produce
@@ -263,6 +283,9 @@ impl TableProvider for MemTable {
     ///
     /// * `state` - The [`SessionState`] containing the context for executing the plan.
     /// * `input` - The [`ExecutionPlan`] to execute and insert.
+    /// * `insert_op` - [`InsertOp::Overwrite`] replaces all of the rows of the table,
+    ///   and [`InsertOp::Replace`] replaces the rows with the same primary or unique key
+    ///   as an inserted row.
     ///
     /// # Returns
     ///
@@ -298,16 +321,143 @@ impl TableProvider for MemTable {
                     .collect::<Vec<_>>()
             );
         }
-        if insert_op != InsertOp::Append {
-            return not_impl_err!("{insert_op} not implemented for MemoryTable yet");
+        let sink = MemSink::try_new(
+            self.batches.clone(),
+            Arc::clone(&self.schema),
+            insert_op,
+            self.constraints.clone(),
+        )?;
+        Ok(Arc::new(DataSinkExec::new(input, Arc::new(sink), None)))
+    }
+
+    /// Returns an ExecutionPlan that deletes the rows of this [`MemTable`] matching all of the given `filters`
+    /// and produced by `selection`.
+    async fn delete_from(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+        selection: Option<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let df_schema = DFSchema::try_from(Arc::clone(&self.schema))?;
+        let predicate = conjunction(filters)
+            .map(|filter| state.create_physical_expr(filter, &df_schema))
+            .transpose()?;
+        let mutation = MemMutation::try_new(
+            self.batches.clone(),
+            Arc::clone(&self.schema),
+            predicate,
+            None,
+        )?;
+        Ok(Arc::new(
+            DataMutationExec::new(Arc::new(mutation)).with_selection(selection),
+        ))
+    }
+
+    /// Returns an ExecutionPlan that updates the rows of this [`MemTable`] matching all of the given `filters`
+    /// and produced by `selection`.
+    async fn update(
+        &self,
+        state: &dyn Session,
+        assignments: Vec<(String, Expr)>,
+        filters: Vec<Expr>,
+        selection: Option<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        // If we are updating the table, any sort order may be messed up so reset it here
+        *self.sort_order.lock() = vec![];
+
+        let df_schema = DFSchema::try_from(Arc::clone(&self.schema))?;
+        let predicate = conjunction(filters)
+            .map(|filter| state.create_physical_expr(filter, &df_schema))
+            .transpose()?;
+        let assignments = assignments
+            .into_iter()
+            .map(|(name, expr)| {
+                let index = self.schema.index_of(&name)?;
+                let expr =
+                    expr.cast_to(self.schema.field(index).data_type(), &df_schema)?;
+                Ok((index, state.create_physical_expr(expr, &df_schema)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mutation = MemMutation::try_new(
+            self.batches.clone(),
+            Arc::clone(&self.schema),
+            predicate,
+            Some(assignments),
+        )?;
+        Ok(Arc::new(
+            DataMutationExec::new(Arc::new(mutation)).with_selection(selection),
+        ))
+    }
+
+    /// Returns an ExecutionPlan that merges the rows produced by `input` into this [`MemTable`].
+    async fn merge_into(
+        &self,
+        _state: &dyn Session,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        // If we are merging into the table, any sort order may be messed up so reset it here
+        *self.sort_order.lock() = vec![];
+
+        let mut snapshot = Vec::with_capacity(self.batches.len());
+        for partition in self.batches.iter() {
+            snapshot.push(partition.read().await.clone());
         }
-        let sink = MemSink::try_new(self.batches.clone(), Arc::clone(&self.schema))?;
+        let sink = MemMergeSink::try_new(
+            self.batches.clone(),
+            snapshot,
+            Arc::clone(&self.schema),
+        )?;
         Ok(Arc::new(DataSinkExec::new(input, Arc::new(sink), None)))
     }
 
     fn get_column_default(&self, column: &str) -> Option<&Expr> {
         self.column_defaults.get(column)
     }
+
+    fn get_table_comment(&self) -> Option<String> {
+        self.comments.table()
+    }
+
+    fn get_column_comment(&self, column: &str) -> Option<String> {
+        self.comments.column(column)
+    }
+
+    fn set_table_comment(&self, comment: Option<String>) -> Result<()> {
+        self.comments.set_table(comment);
+        Ok(())
+    }
+
+    fn set_column_comment(&self, column: &str, comment: Option<String>) -> Result<()> {
+        self.comments.set_column(column, comment);
+        Ok(())
+    }
+
+    fn get_table_ddl(&self, name: &TableReference) -> Result<Option<DdlStatement>> {
+        let schema = DFSchema::try_from(self.comments.apply(&self.schema))?;
+        let column_defaults = schema
+            .fields()
+            .iter()
+            .filter_map(|field| {
+                let default = self.column_defaults.get(field.name())?;
+                Some((field.name().clone(), default.clone()))
+            })
+            .collect();
+
+        // the DDL only describes the columns, not the data of the table
+        Ok(Some(DdlStatement::CreateMemoryTable(CreateMemoryTable {
+            name: name.clone(),
+            constraints: self.constraints.clone(),
+            input: Arc::new(LogicalPlan::EmptyRelation(EmptyRelation {
+                produce_one_row: false,
+                schema: Arc::new(schema),
+            })),
+            if_not_exists: false,
+            or_replace: false,
+            column_defaults,
+            temporary: false,
+            comment: self.comments.table(),
+        })))
+    }
 }
 
 /// Implements for writing to a [`MemTable`]
@@ -315,12 +465,16 @@ struct MemSink {
     /// Target locations for writing data
     batches: Vec<PartitionData>,
     schema: SchemaRef,
+    insert_op: InsertOp,
+    /// Keys identifying the rows replaced by [`InsertOp::Replace`]
+    constraints: Constraints,
 }
 
 impl Debug for MemSink {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("MemSink")
             .field("num_partitions", &self.batches.len())
+            .field("insert_op", &self.insert_op)
             .finish()
     }
 }
@@ -330,7 +484,11 @@ impl DisplayAs for MemSink {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 let partition_count = self.batches.len();
-                write!(f, "MemoryTable (partitions={partition_count})")
+                write!(f, "MemoryTable (partitions={partition_count})")?;
+                if self.insert_op != InsertOp::Append {
+                    write!(f, ", op={}", self.insert_op)?;
+                }
+                Ok(())
             }
         }
     }
@@ -340,11 +498,73 @@ impl MemSink {
     /// Creates a new [`MemSink`].
     ///
     /// The caller is responsible for ensuring that there is at least one partition to insert into.
-    fn try_new(batches: Vec<PartitionData>, schema: SchemaRef) -> Result<Self> {
+    fn try_new(
+        batches: Vec<PartitionData>,
+        schema: SchemaRef,
+        insert_op: InsertOp,
+        constraints: Constraints,
+    ) -> Result<Self> {
         if batches.is_empty() {
             return plan_err!("Cannot insert into MemTable with zero partitions");
         }
-        Ok(Self { batches, schema })
+        if insert_op == InsertOp::Replace {
+            check_replace_keys(&constraints)?;
+        }
+        Ok(Self {
+            batches,
+            schema,
+            insert_op,
+            constraints,
+        })
+    }
+
+    /// Replaces the existing rows with the rows of `data`, or only the
+    /// existing rows with the same key for [`InsertOp::Replace`]
+    async fn write_replacing(&self, data: SendableRecordBatchStream) -> Result<u64> {
+        let num_partitions = self.batches.len();
+
+        // The input may read this table, so collect it before modifying the table
+        let batches = common::collect(data).await?;
+        let row_count = batches.iter().map(|batch| batch.num_rows()).sum::<usize>();
+        let (replaced_keys, batches) = match self.insert_op {
+            InsertOp::Replace => {
+                let (replaced_keys, batches) =
+                    ReplacedKeys::try_new(&self.schema, &self.constraints, &batches)?;
+                (Some(replaced_keys), batches)
+            }
+            _ => (None, batches),
+        };
+
+        // Hold the locks of all partitions so that the whole table is
+        // modified atomically
+        let mut partitions = Vec::with_capacity(num_partitions);
+        for partition in self.batches.iter() {
+            partitions.push(partition.write().await);
+        }
+
+        let mut new_partitions = vec![vec![]; num_partitions];
+        if let Some(replaced_keys) = &replaced_keys {
+            for (batches, new_batches) in partitions.iter().zip(new_partitions.iter_mut())
+            {
+                for batch in batches.iter() {
+                    let (batch, _) = replaced_keys.remove_replaced(batch)?;
+                    if batch.num_rows() > 0 {
+                        new_batches.push(batch);
+                    }
+                }
+            }
+        }
+        // distribute the inserted data round robin style into num_partitions
+        for (i, batch) in batches.into_iter().enumerate() {
+            if batch.num_rows() > 0 {
+                new_partitions[i % num_partitions].push(batch);
+            }
+        }
+
+        for (partition, new_batches) in partitions.iter_mut().zip(new_partitions) {
+            **partition = new_batches;
+        }
+        Ok(row_count as u64)
     }
 }
 
@@ -363,6 +583,9 @@ impl DataSink for MemSink {
         mut data: SendableRecordBatchStream,
         _context: &Arc<TaskContext>,
     ) -> Result<u64> {
+        if self.insert_op != InsertOp::Append {
+            return self.write_replacing(data).await;
+        }
         let num_partitions = self.batches.len();
 
         // buffer up the data round robin style into num_partitions
@@ -386,6 +609,253 @@ impl DataSink for MemSink {
     }
 }
 
+/// Implements merging rows into a [`MemTable`]
+struct MemMergeSink {
+    /// Target locations of the merged data
+    batches: Vec<PartitionData>,
+    /// Contents of the partitions when the merge was planned, which the
+    /// input reads
+    snapshot: Vec<Vec<RecordBatch>>,
+    table_schema: SchemaRef,
+    /// Schema of the input rows, see [`merge_input_schema`]
+    schema: SchemaRef,
+}
+
+impl Debug for MemMergeSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemMergeSink")
+            .field("num_partitions", &self.batches.len())
+            .finish()
+    }
+}
+
+impl DisplayAs for MemMergeSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let partition_count = self.batches.len();
+                write!(f, "MemoryTable (partitions={partition_count}), op=Merge")
+            }
+        }
+    }
+}
+
+impl MemMergeSink {
+    /// Creates a new [`MemMergeSink`].
+    fn try_new(
+        batches: Vec<PartitionData>,
+        snapshot: Vec<Vec<RecordBatch>>,
+        table_schema: SchemaRef,
+    ) -> Result<Self> {
+        if batches.is_empty() {
+            return plan_err!("Cannot merge into MemTable with zero partitions");
+        }
+        let schema = merge_input_schema(&table_schema);
+        Ok(Self {
+            batches,
+            snapshot,
+            table_schema,
+            schema,
+        })
+    }
+}
+
+/// Returns true if `left` and `right` are the same batches, rather than
+/// batches with equal contents
+fn same_batches(left: &[RecordBatch], right: &[RecordBatch]) -> bool {
+    left.len() == right.len()
+        && left.iter().zip(right).all(|(left, right)| {
+            left.num_rows() == right.num_rows()
+                && left
+                    .columns()
+                    .iter()
+                    .zip(right.columns())
+                    .all(|(left, right)| Arc::ptr_eq(left, right))
+        })
+}
+
+#[async_trait]
+impl DataSink for MemMergeSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let num_partitions = self.batches.len();
+
+        // Hold the locks of all partitions while the merged rows are read,
+        // computed and written, so that no other write to the table can
+        // happen in between and be lost when the table is replaced. The
+        // input does not lock the table, as scans of a `MemTable` copy its
+        // batches when they are planned.
+        let mut partitions = Vec::with_capacity(num_partitions);
+        for partition in self.batches.iter() {
+            partitions.push(partition.write().await);
+        }
+        let modified = partitions
+            .iter()
+            .zip(&self.snapshot)
+            .any(|(batches, snapshot)| !same_batches(batches, snapshot));
+        if modified {
+            return exec_err!("MemTable was modified after the MERGE was planned");
+        }
+
+        // The input reads the whole table, so compute all of the new
+        // contents before replacing any of them
+        let affected = Arc::new(AtomicU64::new(0));
+        let data =
+            merge_rows(data, Arc::clone(&self.table_schema), Arc::clone(&affected))?;
+        let batches = common::collect(data).await?;
+
+        // redistribute the data round robin style into num_partitions
+        let mut new_batches = vec![vec![]; num_partitions];
+        for (i, batch) in batches.into_iter().enumerate() {
+            if batch.num_rows() > 0 {
+                new_batches[i % num_partitions].push(batch);
+            }
+        }
+
+        for (partition, new_batches) in partitions.iter_mut().zip(new_batches) {
+            **partition = new_batches;
+        }
+
+        Ok(affected.load(Ordering::Relaxed))
+    }
+}
+
+/// Implements deleting and updating the rows of a [`MemTable`]
+struct MemMutation {
+    /// Target locations of the modified data
+    batches: Vec<PartitionData>,
+    schema: SchemaRef,
+    /// Selects the rows to modify, or all rows if `None`
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    /// Index and new value of each updated column, or `None` to
+    /// delete the selected rows
+    assignments: Option<Vec<(usize, Arc<dyn PhysicalExpr>)>>,
+}
+
+impl Debug for MemMutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemMutation")
+            .field("num_partitions", &self.batches.len())
+            .field("predicate", &self.predicate)
+            .field("assignments", &self.assignments)
+            .finish()
+    }
+}
+
+impl DisplayAs for MemMutation {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let partition_count = self.batches.len();
+                write!(f, "MemoryTable (partitions={partition_count})")?;
+                match &self.assignments {
+                    None => write!(f, ", op=Delete")?,
+                    Some(assignments) => {
+                        let assignments = assignments
+                            .iter()
+                            .map(|(index, expr)| {
+                                format!("{}={expr}", self.schema.field(*index).name())
+                            })
+                            .join(", ");
+                        write!(f, ", op=Update, assignments=[{assignments}]")?
+                    }
+                }
+                if let Some(predicate) = &self.predicate {
+                    write!(f, ", predicate={predicate}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl MemMutation {
+    /// Creates a new [`MemMutation`].
+    fn try_new(
+        batches: Vec<PartitionData>,
+        schema: SchemaRef,
+        predicate: Option<Arc<dyn PhysicalExpr>>,
+        assignments: Option<Vec<(usize, Arc<dyn PhysicalExpr>)>>,
+    ) -> Result<Self> {
+        if let Some(predicate) = &predicate {
+            if predicate.data_type(&schema)? != DataType::Boolean {
+                return plan_err!(
+                    "Cannot create filter with non-boolean predicate '{predicate}'"
+                );
+            }
+        }
+        Ok(Self {
+            batches,
+            schema,
+            predicate,
+            assignments,
+        })
+    }
+}
+
+#[async_trait]
+impl DataMutation for MemMutation {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn apply(
+        &self,
+        _context: &Arc<TaskContext>,
+        selected: Option<Arc<SelectedRows>>,
+    ) -> Result<u64> {
+        // Hold the locks of all partitions so that the whole table is
+        // modified atomically
+        let mut partitions = Vec::with_capacity(self.batches.len());
+        for partition in self.batches.iter() {
+            partitions.push(partition.write().await);
+        }
+
+        // Compute all of the new batches before replacing any of them, so
+        // that an error leaves the table unchanged
+        let mut row_count = 0;
+        let mut new_partitions = Vec::with_capacity(partitions.len());
+        for batches in partitions.iter() {
+            let mut new_batches = Vec::with_capacity(batches.len());
+            for batch in batches.iter() {
+                let (new_batch, affected) = match &self.assignments {
+                    None => {
+                        delete_rows(batch, self.predicate.as_ref(), selected.as_deref())?
+                    }
+                    Some(assignments) => update_rows(
+                        batch,
+                        self.predicate.as_ref(),
+                        selected.as_deref(),
+                        assignments,
+                    )?,
+                };
+                row_count += affected;
+                if new_batch.num_rows() > 0 {
+                    new_batches.push(new_batch);
+                }
+            }
+            new_partitions.push(new_batches);
+        }
+
+        for (partition, new_batches) in partitions.iter_mut().zip(new_partitions) {
+            **partition = new_batches;
+        }
+
+        Ok(row_count as u64)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -395,7 +865,7 @@ mod tests {
     use crate::prelude::SessionContext;
 
     use arrow::array::{AsArray, Int32Array};
-    use arrow::datatypes::{DataType, Field, Schema, UInt64Type};
+    use arrow::datatypes::{DataType, Field, Int64Type, Schema, UInt64Type};
     use arrow::error::ArrowError;
     use datafusion_common::DataFusionError;
     use datafusion_expr::LogicalPlanBuilder;
@@ -812,4 +1282,113 @@ mod tests {
         );
         Ok(())
     }
+
+    // Test deleting and updating rows spread over several partitions
+    #[tokio::test]
+    async fn test_delete_and_update_multi_partition() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(Int32Array::from(vec![Some(10), None, Some(30)])),
+            ],
+        )?;
+        let table = Arc::new(MemTable::try_new(
+            schema,
+            vec![vec![batch.clone()], vec![batch]],
+        )?);
+
+        let session_ctx = SessionContext::new();
+        session_ctx.register_table("t", table.clone())?;
+
+        // rows where `b > 15` evaluates to NULL are not deleted
+        let res = session_ctx
+            .sql("DELETE FROM t WHERE b > 15")
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(extract_count(res), 2);
+
+        let res = session_ctx
+            .sql("UPDATE t SET b = a * 100 WHERE b IS NULL")
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(extract_count(res), 2);
+
+        for partition in table.batches.iter() {
+            let batches = partition.read().await;
+            assert_eq!(batches.len(), 1);
+            let b = batches[0]
+                .column(1)
+                .as_primitive::<arrow::datatypes::Int32Type>();
+            assert_eq!(b.iter().collect::<Vec<_>>(), vec![Some(10), Some(200)]);
+        }
+        Ok(())
+    }
+
+    // Test that a failing update leaves the table unchanged
+    #[tokio::test]
+    async fn test_update_violating_nullability() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
+        )?;
+        let table = Arc::new(MemTable::try_new(schema, vec![vec![batch.clone()]])?);
+
+        let session_ctx = SessionContext::new();
+        session_ctx.register_table("t", table.clone())?;
+
+        let err = session_ctx
+            .sql("UPDATE t SET a = NULL WHERE a > 1")
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert!(
+            err.strip_backtrace().contains(
+                "Column 'a' is declared as non-nullable but contains null values"
+            ),
+            "unexpected error: {err}"
+        );
+        assert_eq!(*table.batches[0].read().await, vec![batch]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn merge_fails_after_concurrent_write() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE t (a INT, b INT) AS VALUES (1, 1)")
+            .await?
+            .collect()
+            .await?;
+        let plan = ctx
+            .sql(
+                "MERGE INTO t USING (VALUES (1, 2)) AS s(a, b) ON t.a = s.a \
+                WHEN MATCHED THEN UPDATE SET b = s.b",
+            )
+            .await?
+            .create_physical_plan()
+            .await?;
+
+        // the merge would overwrite the inserted row
+        ctx.sql("INSERT INTO t VALUES (2, 2)")
+            .await?
+            .collect()
+            .await?;
+        let e = collect(plan, ctx.task_ctx()).await.unwrap_err();
+        assert_eq!(
+            e.strip_backtrace(),
+            "Execution error: MemTable was modified after the MERGE was planned"
+        );
+
+        let rows = ctx.sql("SELECT count(*) FROM t").await?.collect().await?;
+        assert_eq!(rows[0].column(0).as_primitive::<Int64Type>().value(0), 2);
+        Ok(())
+    }
 }
@@ -28,6 +28,7 @@ pub mod empty;
 pub mod file_format;
 pub mod listing;
 pub mod listing_table_factory;
+pub mod materialized_view;
 pub mod memory;
 pub mod physical_plan;
 pub mod provider;
@@ -40,6 +41,7 @@ pub mod view;
 pub use self::default_table_source::{
     provider_as_source, source_as_provider, DefaultTableSource,
 };
+pub use self::materialized_view::MaterializedView;
 pub use self::memory::MemTable;
 pub use self::view::ViewTable;
 pub use crate::catalog::TableProvider;
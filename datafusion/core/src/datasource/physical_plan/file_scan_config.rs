@@ -50,6 +50,7 @@ use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
 use datafusion_physical_plan::projection::{
     all_alias_free_columns, new_projections_for_columns, ProjectionExec,
 };
+use datafusion_physical_plan::sample::{sample_statistics, SystemSample};
 use datafusion_physical_plan::source::{DataSource, DataSourceExec};
 use datafusion_physical_plan::{DisplayAs, DisplayFormatType, ExecutionPlan};
 use log::warn;
@@ -152,6 +153,11 @@ pub struct FileScanConfig {
     pub new_lines_in_values: bool,
     /// File source such as `ParquetSource`, `CsvSource`, `JsonSource`, etc.
     pub source: Arc<dyn FileSource>,
+    /// Optional `SYSTEM` sample of the files to read. Sources that support
+    /// it read a sample of the row groups of every file, see
+    /// [`FileSource::supports_row_group_sampling`], otherwise whole files
+    /// are skipped.
+    sample: Option<SystemSample>,
 }
 
 impl DataSource for FileScanConfig {
@@ -168,9 +174,18 @@ impl DataSource for FileScanConfig {
             .with_schema(Arc::clone(&self.file_schema))
             .with_projection(self);
 
-        let opener = source.create_file_opener(object_store, self, partition)?;
+        let sampled_files;
+        let config = match &self.sample {
+            Some(sample) if !source.supports_row_group_sampling() => {
+                sampled_files = self.with_sampled_files(sample);
+                &sampled_files
+            }
+            _ => self,
+        };
+
+        let opener = source.create_file_opener(object_store, config, partition)?;
 
-        let stream = FileStream::new(self, partition, opener, source.metrics())?;
+        let stream = FileStream::new(config, partition, opener, source.metrics())?;
         Ok(Box::pin(stream))
     }
 
@@ -198,6 +213,10 @@ impl DataSource for FileScanConfig {
             write!(f, ", {}", self.constraints)?;
         }
 
+        if let Some(sample) = &self.sample {
+            write!(f, ", {sample}")?;
+        }
+
         self.fmt_file_source(t, f)
     }
 
@@ -238,7 +257,11 @@ impl DataSource for FileScanConfig {
     }
 
     fn statistics(&self) -> Result<Statistics> {
-        self.source.statistics()
+        let statistics = self.source.statistics()?;
+        Ok(match &self.sample {
+            Some(sample) => sample_statistics(statistics, sample.fraction),
+            None => statistics,
+        })
     }
 
     fn with_fetch(&self, limit: Option<usize>) -> Option<Arc<dyn DataSource>> {
@@ -254,6 +277,10 @@ impl DataSource for FileScanConfig {
         self.source.metrics().clone()
     }
 
+    fn with_sample(&self, sample: SystemSample) -> Option<Arc<dyn DataSource>> {
+        Some(Arc::new(self.clone().with_sample(Some(sample))))
+    }
+
     fn try_swapping_with_projection(
         &self,
         projection: &ProjectionExec,
@@ -309,6 +336,7 @@ impl FileScanConfig {
             file_compression_type: FileCompressionType::UNCOMPRESSED,
             new_lines_in_values: false,
             source: Arc::clone(&file_source),
+            sample: None,
         };
 
         config = config.with_source(Arc::clone(&file_source));
@@ -351,6 +379,15 @@ impl FileScanConfig {
         self
     }
 
+    /// Set the `SYSTEM` sample of the files. Sources that support it read a
+    /// sample of the row groups of every file, see
+    /// [`FileSource::supports_row_group_sampling`], otherwise whole files are
+    /// skipped.
+    pub fn with_sample(mut self, sample: Option<SystemSample>) -> Self {
+        self.sample = sample;
+        self
+    }
+
     /// Add a file as a single group
     ///
     /// See [Self::file_groups] for more information.
@@ -599,6 +636,22 @@ impl FileScanConfig {
         &self.source
     }
 
+    /// Returns the `SYSTEM` sample of the files, if any
+    pub fn sample(&self) -> Option<&SystemSample> {
+        self.sample.as_ref()
+    }
+
+    /// Returns a copy of this configuration that only reads the files
+    /// selected by `sample`. A file is kept or skipped as a whole, even if it
+    /// is split into ranges read by different partitions.
+    fn with_sampled_files(&self, sample: &SystemSample) -> Self {
+        let mut config = self.clone();
+        for file_group in config.file_groups.iter_mut() {
+            file_group.retain(|file| sample.selects(file.object_meta.location.as_ref()));
+        }
+        config
+    }
+
     fn supports_repartition(&self) -> bool {
         !(self.file_compression_type.is_compressed()
             || self.new_lines_in_values
@@ -66,6 +66,7 @@ use std::{
 
 use super::{file_format::write::demux::start_demuxer_task, listing::ListingTableUrl};
 use crate::datasource::file_format::write::demux::DemuxedStreamReceiver;
+use crate::datasource::file_format::write::{commit_overwrite, StagingDirectory};
 use crate::error::Result;
 use crate::physical_plan::{DisplayAs, DisplayFormatType};
 use crate::{
@@ -77,6 +78,7 @@ use crate::{
 };
 
 use arrow::datatypes::{DataType, SchemaRef};
+use datafusion_common::not_impl_err;
 use datafusion_common_runtime::SpawnedTask;
 use datafusion_execution::{SendableRecordBatchStream, TaskContext};
 use datafusion_physical_expr::expressions::Column;
@@ -124,6 +126,12 @@ pub trait FileSink: DataSink {
     ) -> Result<u64>;
 
     /// File sink implementation of the [`DataSink::write_all`] method.
+    ///
+    /// For [`InsertOp::Overwrite`], the new files are written to a
+    /// [`StagingDirectory`] of the output location, and only replace the files
+    /// previously in the output location once all of them have been written.
+    /// With hive style partitioning, only the partitions present in `data`
+    /// are overwritten.
     async fn write_all(
         &self,
         data: SendableRecordBatchStream,
@@ -133,14 +141,44 @@ pub trait FileSink: DataSink {
         let object_store = context
             .runtime_env()
             .object_store(&config.object_store_url)?;
-        let (demux_task, file_stream_rx) = start_demuxer_task(config, data, context);
-        self.spawn_writer_tasks_and_join(
-            context,
-            demux_task,
-            file_stream_rx,
-            object_store,
-        )
-        .await
+        let base_output_path = &config.table_paths[0];
+        let staging = match config.insert_op {
+            InsertOp::Append => None,
+            // A single output file is replaced as a whole when it is written
+            InsertOp::Overwrite
+                if !base_output_path.is_collection()
+                    && base_output_path.file_extension().is_some() =>
+            {
+                None
+            }
+            InsertOp::Overwrite => Some(StagingDirectory::try_new(base_output_path)?),
+            InsertOp::Replace => {
+                return not_impl_err!(
+                    "{} is not supported when writing files",
+                    config.insert_op
+                );
+            }
+        };
+        let output_path = match &staging {
+            Some(staging) => staging.path(),
+            None => base_output_path,
+        };
+        let (demux_task, file_stream_rx) =
+            start_demuxer_task(config, output_path.clone(), data, context);
+        let result = self
+            .spawn_writer_tasks_and_join(
+                context,
+                demux_task,
+                file_stream_rx,
+                Arc::clone(&object_store),
+            )
+            .await;
+        match staging {
+            Some(staging) => {
+                commit_overwrite(config, object_store.as_ref(), &staging, result).await
+            }
+            None => result,
+        }
     }
 }
 
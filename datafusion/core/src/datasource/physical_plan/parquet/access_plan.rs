@@ -16,6 +16,7 @@
 // under the License.
 
 use datafusion_common::{internal_err, Result};
+use datafusion_physical_plan::sample::SystemSample;
 use parquet::arrow::arrow_reader::{RowSelection, RowSelector};
 use parquet::file::metadata::RowGroupMetaData;
 
@@ -144,6 +145,16 @@ impl ParquetAccessPlan {
         self.set(idx, RowGroupAccess::Scan);
     }
 
+    /// Skips the row groups that are not part of a `SYSTEM` sample. Row
+    /// groups are identified by `file_name` and their index.
+    pub fn sample(&mut self, sample: &SystemSample, file_name: &str) {
+        for idx in 0..self.row_groups.len() {
+            if !sample.selects((file_name, idx)) {
+                self.skip(idx);
+            }
+        }
+    }
+
     /// Return true if the i-th row group should be scanned
     pub fn should_scan(&self, idx: usize) -> bool {
         self.row_groups[idx].should_scan()
@@ -37,6 +37,7 @@ use datafusion_common::{exec_err, Result};
 use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
 use datafusion_physical_optimizer::pruning::PruningPredicate;
 use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
+use datafusion_physical_plan::sample::SystemSample;
 
 use futures::{StreamExt, TryStreamExt};
 use log::debug;
@@ -82,6 +83,8 @@ pub(super) struct ParquetOpener {
     pub enable_bloom_filter: bool,
     /// Schema adapter factory
     pub schema_adapter_factory: Arc<dyn SchemaAdapterFactory>,
+    /// Optional `SYSTEM` sample of the row groups to read
+    pub sample: Option<SystemSample>,
 }
 
 impl FileOpener for ParquetOpener {
@@ -121,6 +124,7 @@ impl FileOpener for ParquetOpener {
         );
         let enable_bloom_filter = self.enable_bloom_filter;
         let limit = self.limit;
+        let sample = self.sample;
 
         Ok(Box::pin(async move {
             let options = ArrowReaderOptions::new().with_page_index(enable_page_index);
@@ -195,8 +199,11 @@ impl FileOpener for ParquetOpener {
             let predicate = pruning_predicate.as_ref().map(|p| p.as_ref());
             let rg_metadata = file_metadata.row_groups();
             // track which row groups to actually read
-            let access_plan =
+            let mut access_plan =
                 create_initial_plan(&file_name, extensions, rg_metadata.len())?;
+            if let Some(sample) = &sample {
+                access_plan.sample(sample, &file_name);
+            }
             let mut row_groups = RowGroupAccessPlanFilter::new(access_plan);
             // if there is a range restricting what parts of the file to read
             if let Some(range) = file_range.as_ref() {
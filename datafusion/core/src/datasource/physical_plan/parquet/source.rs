@@ -504,6 +504,7 @@ impl FileSource for ParquetSource {
             enable_page_index: self.enable_page_index(),
             enable_bloom_filter: self.bloom_filter_on_read(),
             schema_adapter_factory,
+            sample: base_config.sample().copied(),
         }))
     }
 
@@ -557,6 +558,10 @@ impl FileSource for ParquetSource {
         "parquet"
     }
 
+    fn supports_row_group_sampling(&self) -> bool {
+        true
+    }
+
     fn fmt_extra(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
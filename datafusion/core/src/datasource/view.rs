@@ -26,10 +26,12 @@ use crate::{
 };
 use arrow::datatypes::SchemaRef;
 use async_trait::async_trait;
-use datafusion_catalog::Session;
+use datafusion_catalog::{Session, TableComments};
 use datafusion_common::config::ConfigOptions;
-use datafusion_common::Column;
-use datafusion_expr::{LogicalPlanBuilder, TableProviderFilterPushDown};
+use datafusion_common::{Column, TableReference};
+use datafusion_expr::{
+    CreateView, DdlStatement, LogicalPlanBuilder, TableProviderFilterPushDown,
+};
 use datafusion_optimizer::analyzer::expand_wildcard_rule::ExpandWildcardRule;
 use datafusion_optimizer::analyzer::type_coercion::TypeCoercion;
 use datafusion_optimizer::Analyzer;
@@ -45,6 +47,8 @@ pub struct ViewTable {
     table_schema: SchemaRef,
     /// SQL used to create the view, if available
     definition: Option<String>,
+    /// Comments on the view and its columns
+    comments: TableComments,
 }
 
 impl ViewTable {
@@ -55,10 +59,11 @@ impl ViewTable {
         definition: Option<String>,
     ) -> Result<Self> {
         let logical_plan = Self::apply_required_rule(logical_plan)?;
-        let table_schema = logical_plan.schema().as_ref().to_owned().into();
+        let table_schema: SchemaRef = logical_plan.schema().as_ref().to_owned().into();
 
         let view = Self {
             logical_plan,
+            comments: TableComments::new(&table_schema),
             table_schema,
             definition,
         };
@@ -107,6 +112,35 @@ impl TableProvider for ViewTable {
     fn get_table_definition(&self) -> Option<&str> {
         self.definition.as_deref()
     }
+
+    fn get_table_comment(&self) -> Option<String> {
+        self.comments.table()
+    }
+
+    fn get_column_comment(&self, column: &str) -> Option<String> {
+        self.comments.column(column)
+    }
+
+    fn set_table_comment(&self, comment: Option<String>) -> Result<()> {
+        self.comments.set_table(comment);
+        Ok(())
+    }
+
+    fn set_column_comment(&self, column: &str, comment: Option<String>) -> Result<()> {
+        self.comments.set_column(column, comment);
+        Ok(())
+    }
+
+    fn get_table_ddl(&self, name: &TableReference) -> Result<Option<DdlStatement>> {
+        Ok(Some(DdlStatement::CreateView(CreateView {
+            name: name.clone(),
+            input: Arc::new(self.logical_plan.clone()),
+            or_replace: false,
+            definition: None,
+            temporary: false,
+        })))
+    }
+
     fn supports_filters_pushdown(
         &self,
         filters: &[&Expr],
@@ -19,30 +19,32 @@
 
 use datafusion_catalog::memory::MemorySchemaProvider;
 use datafusion_catalog::MemoryCatalogProvider;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::sync::{Arc, Weak};
 
 use super::options::ReadOptions;
 use crate::{
     catalog::{
-        CatalogProvider, CatalogProviderList, TableProvider, TableProviderFactory,
+        CatalogProvider, CatalogProviderList, SchemaProvider, TableProvider,
+        TableProviderFactory,
     },
     catalog_common::listing_schema::ListingSchemaProvider,
     dataframe::DataFrame,
     datasource::listing::{
         ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
     },
-    datasource::{provider_as_source, MemTable, ViewTable},
+    datasource::{provider_as_source, MaterializedView, MemTable, ViewTable},
     error::{DataFusionError, Result},
     execution::{options::ArrowReadOptions, runtime_env::RuntimeEnv, FunctionRegistry},
     logical_expr::AggregateUDF,
     logical_expr::ScalarUDF,
     logical_expr::{
-        CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
-        CreateMemoryTable, CreateView, DropCatalogSchema, DropFunction, DropTable,
-        DropView, Execute, LogicalPlan, LogicalPlanBuilder, Prepare, SetVariable,
-        TableType, UNNAMED_TABLE,
+        CommentOn, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
+        CreateFunction, CreateMaterializedView, CreateMemoryTable, CreateView,
+        DropCatalogSchema, DropFunction, DropTable, DropView, Execute, LogicalPlan,
+        LogicalPlanBuilder, Prepare, RefreshMaterializedView, SetVariable, TableType,
+        UNNAMED_TABLE,
     },
     physical_expr::PhysicalExpr,
     physical_plan::ExecutionPlan,
@@ -54,7 +56,8 @@ use arrow::record_batch::RecordBatch;
 use arrow_schema::Schema;
 use datafusion_common::{
     config::{ConfigExtension, TableOptions},
-    exec_datafusion_err, exec_err, not_impl_err, plan_datafusion_err, plan_err,
+    config_datafusion_err, exec_datafusion_err, exec_err, not_impl_err,
+    plan_datafusion_err, plan_err,
     tree_node::{TreeNodeRecursion, TreeNodeVisitor},
     DFSchema, ParamValues, ScalarValue, SchemaReference, TableReference,
 };
@@ -71,6 +74,7 @@ pub use crate::execution::session_state::SessionState;
 
 use crate::datasource::dynamic_file::DynamicListTableFactory;
 use crate::execution::session_state::SessionStateBuilder;
+use crate::execution::sql_function::SqlFunctionFactory;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use datafusion_catalog::{
@@ -393,9 +397,12 @@ impl SessionContext {
         ));
 
         let session_id = self.session_id.clone();
+        // This is still the same session, which keeps its temporary tables
+        let temporary_schema = Arc::clone(self.state.read().temporary_schema());
         let ctx: SessionContext = self
             .into_state_builder()
             .with_session_id(session_id)
+            .with_temporary_schema(temporary_schema)
             .with_catalog_list(catalog_list)
             .build()
             .into();
@@ -447,6 +454,9 @@ impl SessionContext {
     }
 
     /// Registers a [`FunctionFactory`] to handle `CREATE FUNCTION` statements
+    ///
+    /// Without one, only functions written in SQL can be created, see
+    /// [`SqlFunctionFactory`].
     pub fn with_function_factory(
         self,
         function_factory: Arc<dyn FunctionFactory>,
@@ -668,6 +678,13 @@ impl SessionContext {
                     DdlStatement::CreateView(cmd) => {
                         Box::pin(self.create_view(cmd)).await
                     }
+                    DdlStatement::CreateMaterializedView(cmd) => {
+                        Box::pin(self.create_materialized_view(cmd)).await
+                    }
+                    DdlStatement::RefreshMaterializedView(cmd) => {
+                        Box::pin(self.refresh_materialized_view(cmd)).await
+                    }
+                    DdlStatement::CommentOn(cmd) => Box::pin(self.comment_on(cmd)).await,
                     DdlStatement::CreateCatalogSchema(cmd) => {
                         Box::pin(self.create_catalog_schema(cmd)).await
                     }
@@ -774,11 +791,9 @@ impl SessionContext {
         &self,
         cmd: &CreateExternalTable,
     ) -> Result<DataFrame> {
-        let exist = self.table_exist(cmd.name.clone())?;
-
-        if cmd.temporary {
-            return not_impl_err!("Temporary tables not supported");
-        }
+        let exist = self
+            .created_table_schema(&cmd.name, cmd.temporary)?
+            .table_exist(cmd.name.table());
 
         if exist {
             match cmd.if_not_exists {
@@ -791,7 +806,7 @@ impl SessionContext {
 
         let table_provider: Arc<dyn TableProvider> =
             self.create_custom_table(cmd).await?;
-        self.register_table(cmd.name.clone(), table_provider)?;
+        self.register_created_table(cmd.name.clone(), table_provider, cmd.temporary)?;
         self.return_empty_dataframe()
     }
 
@@ -804,57 +819,116 @@ impl SessionContext {
             constraints,
             column_defaults,
             temporary,
+            comment,
         } = cmd;
 
         let input = Arc::unwrap_or_clone(input);
         let input = self.state().optimize(&input)?;
 
-        if temporary {
-            return not_impl_err!("Temporary tables not supported");
-        }
-
-        let table = self.table(name.clone()).await;
+        // a temporary table only shadows the catalog table of the same name,
+        // so each is created and replaced independently of the other
+        let table = self
+            .created_table_schema(&name, temporary)?
+            .table_exist(name.table());
         match (if_not_exists, or_replace, table) {
-            (true, false, Ok(_)) => self.return_empty_dataframe(),
-            (false, true, Ok(_)) => {
-                self.deregister_table(name.clone())?;
+            (true, false, true) => self.return_empty_dataframe(),
+            (false, true, true) => {
+                self.deregister_replaced_table(&name, temporary)?;
                 let schema = Arc::new(input.schema().as_ref().into());
                 let physical = DataFrame::new(self.state(), input);
 
                 let batches: Vec<_> = physical.collect_partitioned().await?;
                 let table = Arc::new(
-                    // pass constraints and column defaults to the mem table.
+                    // pass constraints, column defaults and comment to the mem table.
                     MemTable::try_new(schema, batches)?
                         .with_constraints(constraints)
-                        .with_column_defaults(column_defaults.into_iter().collect()),
+                        .with_column_defaults(column_defaults.into_iter().collect())
+                        .with_comment(comment),
                 );
 
-                self.register_table(name.clone(), table)?;
+                self.register_created_table(name.clone(), table, temporary)?;
                 self.return_empty_dataframe()
             }
-            (true, true, Ok(_)) => {
+            (true, true, true) => {
                 exec_err!("'IF NOT EXISTS' cannot coexist with 'REPLACE'")
             }
-            (_, _, Err(_)) => {
+            (_, _, false) => {
                 let df_schema = input.schema();
                 let schema = Arc::new(df_schema.as_ref().into());
                 let physical = DataFrame::new(self.state(), input);
 
                 let batches: Vec<_> = physical.collect_partitioned().await?;
                 let table = Arc::new(
-                    // pass constraints and column defaults to the mem table.
+                    // pass constraints, column defaults and comment to the mem table.
                     MemTable::try_new(schema, batches)?
                         .with_constraints(constraints)
-                        .with_column_defaults(column_defaults.into_iter().collect()),
+                        .with_column_defaults(column_defaults.into_iter().collect())
+                        .with_comment(comment),
                 );
 
-                self.register_table(name, table)?;
+                self.register_created_table(name, table, temporary)?;
                 self.return_empty_dataframe()
             }
-            (false, false, Ok(_)) => exec_err!("Table '{name}' already exists"),
+            (false, false, true) => exec_err!("Table '{name}' already exists"),
         }
     }
 
+    /// Returns the schema that a DDL statement creates the table `name` in,
+    /// which is the temporary schema of this session for temporary tables and
+    /// the catalog schema of `name` otherwise, even if a temporary table of
+    /// the same name shadows it.
+    ///
+    /// Temporary tables live in a per-session schema, so they can only be
+    /// referenced by a bare name.
+    fn created_table_schema(
+        &self,
+        name: &TableReference,
+        temporary: bool,
+    ) -> Result<Arc<dyn SchemaProvider>> {
+        if !temporary {
+            return self.state.read().catalog_schema_for_ref(name.clone());
+        }
+        match name {
+            TableReference::Bare { .. } => {
+                Ok(Arc::clone(self.state.read().temporary_schema()))
+            }
+            _ => plan_err!("Temporary table '{name}' cannot be qualified with a schema"),
+        }
+    }
+
+    /// Registers a table created by a DDL statement, either in the catalog or
+    /// as a temporary table of this session
+    fn register_created_table(
+        &self,
+        name: TableReference,
+        table: Arc<dyn TableProvider>,
+        temporary: bool,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        self.created_table_schema(&name, temporary)?
+            .register_table(name.table().to_owned(), table)
+    }
+
+    /// Deregisters the table replaced by a DDL statement from the schema that
+    /// the statement creates the table in
+    fn deregister_replaced_table(
+        &self,
+        name: &TableReference,
+        temporary: bool,
+    ) -> Result<()> {
+        let previous = self
+            .created_table_schema(name, temporary)?
+            .deregister_table(name.table())?;
+        if previous
+            .as_ref()
+            .is_some_and(|table| table.as_any().is::<MaterializedView>())
+        {
+            self.state
+                .write()
+                .deregister_materialized_view(name.clone());
+        }
+        Ok(())
+    }
+
     async fn create_view(&self, cmd: CreateView) -> Result<DataFrame> {
         let CreateView {
             name,
@@ -887,6 +961,141 @@ impl SessionContext {
         }
     }
 
+    async fn create_materialized_view(
+        &self,
+        cmd: CreateMaterializedView,
+    ) -> Result<DataFrame> {
+        let CreateMaterializedView {
+            name,
+            input,
+            if_not_exists,
+            or_replace,
+            location,
+            definition,
+        } = cmd;
+
+        let exists = self.table_exist(name.clone())?;
+        match (if_not_exists, or_replace, exists) {
+            (true, _, true) => return self.return_empty_dataframe(),
+            (false, false, true) => return exec_err!("Table '{name}' already exists"),
+            // only a materialized view is replaced by another one
+            (false, true, true) => {
+                let provider = self.table_provider(name.clone()).await?;
+                if !provider.as_any().is::<MaterializedView>() {
+                    return plan_err!("'{name}' is not a materialized view");
+                }
+            }
+            _ => {}
+        }
+
+        let input = Arc::unwrap_or_clone(input);
+        let schema: SchemaRef = Arc::new(input.schema().as_arrow().clone());
+        let storage: Arc<dyn TableProvider> = match location {
+            Some(location) => {
+                self.create_materialized_view_storage(location, schema)
+                    .await?
+            }
+            None => {
+                let partitions = vec![vec![]; self.copied_config().target_partitions()];
+                Arc::new(MemTable::try_new(schema, partitions)?)
+            }
+        };
+        let view = Arc::new(MaterializedView::try_new(input, storage, definition)?);
+
+        // compute the contents before replacing any existing view, so that a
+        // failing query leaves the catalog unchanged
+        self.refresh_materialized_view_contents(&name, &view)
+            .await?;
+        if exists {
+            self.deregister_table(name.clone())?;
+        }
+        self.register_table(name, view)?;
+        self.return_empty_dataframe()
+    }
+
+    /// Creates the [`ListingTable`] storing the contents of a materialized view
+    /// as Parquet files in the directory `location`
+    async fn create_materialized_view_storage(
+        &self,
+        location: String,
+        schema: SchemaRef,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let state = self.state();
+        let file_format = state
+            .get_file_format_factory("parquet")
+            .ok_or_else(|| {
+                config_datafusion_err!(
+                    "Unable to create materialized view stored as parquet! Could not find FileFormat."
+                )
+            })?
+            .create(&state, &HashMap::new())?;
+
+        let location = if location.ends_with('/') {
+            location
+        } else {
+            format!("{location}/")
+        };
+        let table_path = ListingTableUrl::parse(location)?;
+        let options = ListingOptions::new(Arc::clone(&file_format))
+            .with_file_extension(format!(".{}", file_format.get_ext()))
+            .with_target_partitions(state.config().target_partitions());
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .with_schema(schema);
+        Ok(Arc::new(ListingTable::try_new(config)?))
+    }
+
+    async fn refresh_materialized_view(
+        &self,
+        cmd: RefreshMaterializedView,
+    ) -> Result<DataFrame> {
+        let RefreshMaterializedView { name, .. } = cmd;
+        let provider = self.table_provider(name.clone()).await?;
+        let Some(view) = provider.as_any().downcast_ref::<MaterializedView>() else {
+            return plan_err!("'{name}' is not a materialized view");
+        };
+        self.refresh_materialized_view_contents(&name, view).await?;
+        self.return_empty_dataframe()
+    }
+
+    /// Recomputes the contents of the materialized view `name`
+    async fn refresh_materialized_view_contents(
+        &self,
+        name: &TableReference,
+        view: &MaterializedView,
+    ) -> Result<()> {
+        // the query of the view must not be rewritten to read from the
+        // view itself
+        let mut state = self.state();
+        state.deregister_materialized_view(name.clone());
+        view.refresh(&state).await
+    }
+
+    async fn comment_on(&self, cmd: CommentOn) -> Result<DataFrame> {
+        let CommentOn {
+            table,
+            column,
+            comment,
+            if_exists,
+            ..
+        } = cmd;
+        let provider = match self.table_provider(table.clone()).await {
+            Ok(provider) => provider,
+            Err(_) if if_exists => return self.return_empty_dataframe(),
+            Err(e) => return Err(e),
+        };
+        match column {
+            Some(column) => {
+                if provider.schema().field_with_name(&column).is_err() {
+                    return plan_err!("Column '{column}' not found in table '{table}'");
+                }
+                provider.set_column_comment(&column, comment)?;
+            }
+            None => provider.set_table_comment(comment)?,
+        }
+        self.return_empty_dataframe()
+    }
+
     async fn create_catalog_schema(&self, cmd: CreateCatalogSchema) -> Result<DataFrame> {
         let CreateCatalogSchema {
             schema_name,
@@ -1056,17 +1265,22 @@ impl SessionContext {
         let table = table_ref.table().to_owned();
         let maybe_schema = {
             let state = self.state.read();
-            let resolved = state.resolve_table_ref(table_ref);
-            state
-                .catalog_list()
-                .catalog(&resolved.catalog)
-                .and_then(|c| c.schema(&resolved.schema))
+            state.temporary_schema_for_ref(&table_ref).or_else(|| {
+                let resolved = state.resolve_table_ref(table_ref.clone());
+                state
+                    .catalog_list()
+                    .catalog(&resolved.catalog)
+                    .and_then(|c| c.schema(&resolved.schema))
+            })
         };
 
         if let Some(schema) = maybe_schema {
             if let Some(table_provider) = schema.table(&table).await? {
                 if table_provider.table_type() == table_type {
                     schema.deregister_table(&table)?;
+                    if table_provider.as_any().is::<MaterializedView>() {
+                        self.state.write().deregister_materialized_view(table_ref);
+                    }
                     return Ok(true);
                 }
             }
@@ -1076,18 +1290,39 @@ impl SessionContext {
     }
 
     async fn create_function(&self, stmt: CreateFunction) -> Result<DataFrame> {
+        let name = stmt.name.clone();
         let function = {
             let state = self.state.read().clone();
             let function_factory = state.function_factory();
 
             match function_factory {
                 Some(f) => f.create(&state, stmt).await?,
-                _ => Err(DataFusionError::Configuration(
-                    "Function factory has not been configured".into(),
-                ))?,
+                None => SqlFunctionFactory::default().create(&state, stmt).await?,
             }
         };
 
+        // functions qualified with a schema are registered in that schema
+        if !matches!(name, TableReference::Bare { .. }) {
+            let schema = self.state.read().schema_for_ref(name.clone())?;
+            match function {
+                RegisterFunction::Scalar(f) => {
+                    schema.register_udf(f)?;
+                }
+                RegisterFunction::Aggregate(f) => {
+                    schema.register_udaf(f)?;
+                }
+                RegisterFunction::Window(f) => {
+                    schema.register_udwf(f)?;
+                }
+                RegisterFunction::Table(..) => {
+                    return not_impl_err!(
+                        "Table functions can not be created in a schema: {name}"
+                    );
+                }
+            };
+            return self.return_empty_dataframe();
+        }
+
         match function {
             RegisterFunction::Scalar(f) => {
                 self.state.write().register_udf(f)?;
@@ -1108,10 +1343,17 @@ impl SessionContext {
         // we don't know function type at this point
         // decision has been made to drop all functions
         let mut dropped = false;
-        dropped |= self.state.write().deregister_udf(&stmt.name)?.is_some();
-        dropped |= self.state.write().deregister_udaf(&stmt.name)?.is_some();
-        dropped |= self.state.write().deregister_udwf(&stmt.name)?.is_some();
-        dropped |= self.state.write().deregister_udtf(&stmt.name)?.is_some();
+        let name = stmt.name.table();
+        if matches!(stmt.name, TableReference::Bare { .. }) {
+            dropped |= self.state.write().deregister_udf(name)?.is_some();
+            dropped |= self.state.write().deregister_udaf(name)?.is_some();
+            dropped |= self.state.write().deregister_udwf(name)?.is_some();
+            dropped |= self.state.write().deregister_udtf(name)?.is_some();
+        } else if let Ok(schema) = self.state.read().schema_for_ref(stmt.name.clone()) {
+            dropped |= schema.deregister_udf(name)?.is_some();
+            dropped |= schema.deregister_udaf(name)?.is_some();
+            dropped |= schema.deregister_udwf(name)?.is_some();
+        }
 
         // DROP FUNCTION IF EXISTS drops the specified function only if that
         // function exists and in this way, it avoids error. While the DROP FUNCTION
@@ -1464,10 +1706,40 @@ impl SessionContext {
     ) -> Result<Option<Arc<dyn TableProvider>>> {
         let table_ref: TableReference = table_ref.into();
         let table = table_ref.table().to_owned();
+        let mut state = self.state.write();
+        let previous = state
+            .schema_for_ref(table_ref.clone())?
+            .register_table(table, Arc::clone(&provider))?;
+        if let Some(view) = provider.as_any().downcast_ref::<MaterializedView>() {
+            let plan = view.logical_plan().clone();
+            state.register_materialized_view(
+                table_ref,
+                plan,
+                provider_as_source(provider),
+            )?;
+        }
+        Ok(previous)
+    }
+
+    /// Registers a [`TableProvider`] as a temporary table of this session.
+    ///
+    /// Temporary tables are only visible to this [`SessionContext`], even if
+    /// its [`CatalogProviderList`] is shared with other sessions. A bare
+    /// reference to `name` resolves to the temporary table, shadowing any
+    /// catalog table of the same name, and the table is dropped when the
+    /// session is dropped.
+    ///
+    /// If a temporary table of the same name was already registered, returns
+    /// "Table already exists" error.
+    pub fn register_temporary_table(
+        &self,
+        name: impl Into<String>,
+        provider: Arc<dyn TableProvider>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
         self.state
             .read()
-            .schema_for_ref(table_ref)?
-            .register_table(table, provider)
+            .temporary_schema()
+            .register_table(name.into(), provider)
     }
 
     /// Deregisters the given table.
@@ -1479,10 +1751,17 @@ impl SessionContext {
     ) -> Result<Option<Arc<dyn TableProvider>>> {
         let table_ref = table_ref.into();
         let table = table_ref.table().to_owned();
-        self.state
-            .read()
-            .schema_for_ref(table_ref)?
-            .deregister_table(&table)
+        let mut state = self.state.write();
+        let previous = state
+            .schema_for_ref(table_ref.clone())?
+            .deregister_table(&table)?;
+        if previous
+            .as_ref()
+            .is_some_and(|table| table.as_any().is::<MaterializedView>())
+        {
+            state.deregister_materialized_view(table_ref);
+        }
+        Ok(previous)
     }
 
     /// Return `true` if the specified table exists in the schema provider.
@@ -1640,6 +1919,13 @@ impl FunctionRegistry for SessionContext {
     ) -> Result<()> {
         self.state.write().register_expr_planner(expr_planner)
     }
+
+    fn register_expr_planner_first(
+        &mut self,
+        expr_planner: Arc<dyn ExprPlanner>,
+    ) -> Result<()> {
+        self.state.write().register_expr_planner_first(expr_planner)
+    }
 }
 
 /// Create a new task context instance from SessionContext
@@ -1674,6 +1960,8 @@ pub trait QueryPlanner: Debug {
 
 /// A pluggable interface to handle `CREATE FUNCTION` statements
 /// and interact with [SessionState] to registers new udf, udaf or udwf.
+///
+/// [`SqlFunctionFactory`] is used when no factory has been configured.
 
 #[async_trait]
 pub trait FunctionFactory: Debug + Sync + Send {
@@ -2230,6 +2518,67 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn temporary_tables_are_private_to_session() -> Result<()> {
+        let ctx1 = SessionContext::new();
+        ctx1.sql("CREATE TABLE t AS VALUES (1)")
+            .await?
+            .collect()
+            .await?;
+
+        // a second session sharing the same catalogs
+        let state = SessionStateBuilder::new()
+            .with_config(
+                SessionConfig::new().with_create_default_catalog_and_schema(false),
+            )
+            .with_default_features()
+            .with_catalog_list(Arc::clone(ctx1.state().catalog_list()))
+            .build();
+        let ctx2 = SessionContext::new_with_state(state);
+
+        ctx1.sql("CREATE TEMPORARY TABLE t AS VALUES (2)")
+            .await?
+            .collect()
+            .await?;
+        ctx1.sql("CREATE TEMPORARY TABLE scratch AS VALUES (3)")
+            .await?
+            .collect()
+            .await?;
+
+        // the temporary table shadows the catalog table in its own session only
+        let expected = [
+            "+---------+",
+            "| column1 |",
+            "+---------+",
+            "| 2       |",
+            "+---------+",
+        ];
+        assert_batches_eq!(expected, &plan_and_collect(&ctx1, "SELECT * FROM t").await?);
+        let expected = [
+            "+---------+",
+            "| column1 |",
+            "+---------+",
+            "| 1       |",
+            "+---------+",
+        ];
+        assert_batches_eq!(expected, &plan_and_collect(&ctx2, "SELECT * FROM t").await?);
+        assert!(!ctx2.table_exist("scratch")?);
+
+        // dropping the temporary table reveals the catalog table again
+        ctx1.sql("DROP TABLE t").await?.collect().await?;
+        assert_batches_eq!(expected, &plan_and_collect(&ctx1, "SELECT * FROM t").await?);
+
+        // temporary tables go away with their session
+        let scratch = ctx1.table_provider("scratch").await?;
+        let scratch_weak = Arc::downgrade(&scratch);
+        drop(scratch);
+        drop(ctx1);
+        assert_eq!(Weak::strong_count(&scratch_weak), 0);
+        assert!(!ctx2.table_exist("scratch")?);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn custom_type_planner() -> Result<()> {
         let state = SessionStateBuilder::new()
@@ -2263,6 +2612,18 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn enable_url_table_keeps_temporary_tables() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TEMPORARY TABLE tmp AS VALUES (1)")
+            .await?
+            .collect()
+            .await?;
+        let ctx = ctx.enable_url_table();
+        assert!(ctx.state().temporary_schema().table_exist("tmp"));
+        Ok(())
+    }
+
     struct MyPhysicalPlanner {}
 
     #[async_trait]
@@ -19,6 +19,7 @@
 
 pub mod context;
 pub mod session_state;
+pub mod sql_function;
 pub use session_state::{SessionState, SessionStateBuilder};
 
 mod session_state_defaults;
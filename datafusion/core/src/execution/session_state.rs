@@ -18,6 +18,7 @@
 //! [`SessionState`]: information required to run queries in a session
 
 use std::any::Any;
+use std::cell::OnceCell;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
@@ -33,7 +34,7 @@ use crate::physical_planner::{DefaultPhysicalPlanner, PhysicalPlanner};
 use datafusion_catalog::information_schema::{
     InformationSchemaProvider, INFORMATION_SCHEMA,
 };
-use datafusion_catalog::MemoryCatalogProviderList;
+use datafusion_catalog::{MemoryCatalogProviderList, MemorySchemaProvider};
 
 use arrow_schema::{DataType, SchemaRef};
 use datafusion_catalog::{Session, TableFunction, TableFunctionImpl};
@@ -59,6 +60,7 @@ use datafusion_expr::{
     AggregateUDF, Explain, Expr, ExprSchemable, LogicalPlan, ScalarUDF, TableSource,
     WindowUDF,
 };
+use datafusion_optimizer::materialized_view_rewrite::MaterializedViewCandidate;
 use datafusion_optimizer::simplify_expressions::ExprSimplifier;
 use datafusion_optimizer::{
     Analyzer, AnalyzerRule, Optimizer, OptimizerConfig, OptimizerRule,
@@ -178,6 +180,15 @@ pub struct SessionState {
     /// Cache logical plans of prepared statements for later execution.
     /// Key is the prepared statement name.
     prepared_plans: HashMap<String, Arc<PreparedPlan>>,
+    /// Tables created with `CREATE TEMPORARY TABLE`.
+    ///
+    /// These are private to the session: they shadow tables of the same name
+    /// in the catalog when referenced by a bare name, are never registered in
+    /// the (possibly shared) [`CatalogProviderList`], and are dropped together
+    /// with the session.
+    temporary_schema: Arc<dyn SchemaProvider>,
+    /// Materialized views that queries may be rewritten to read from.
+    materialized_views: Vec<MaterializedViewCandidate>,
 }
 
 impl Debug for SessionState {
@@ -195,6 +206,8 @@ impl Debug for SessionState {
             .field("table_options", &self.table_options)
             .field("table_factories", &self.table_factories)
             .field("function_factory", &self.function_factory)
+            .field("temporary_schema", &self.temporary_schema)
+            .field("materialized_views", &self.materialized_views)
             .field("expr_planners", &self.expr_planners)
             .field("type_planner", &self.type_planner)
             .field("query_planners", &self.query_planner)
@@ -300,9 +313,26 @@ impl SessionState {
 
     /// Retrieve the [`SchemaProvider`] for a specific [`TableReference`], if it
     /// exists.
+    ///
+    /// A bare reference to a temporary table of this session resolves to the
+    /// [temporary schema](Self::temporary_schema), shadowing any catalog table
+    /// with the same name.
     pub fn schema_for_ref(
         &self,
         table_ref: impl Into<TableReference>,
+    ) -> datafusion_common::Result<Arc<dyn SchemaProvider>> {
+        let table_ref = table_ref.into();
+        if let Some(schema) = self.temporary_schema_for_ref(&table_ref) {
+            return Ok(schema);
+        }
+        self.catalog_schema_for_ref(table_ref)
+    }
+
+    /// Retrieve the [`SchemaProvider`] of the catalog for a specific
+    /// [`TableReference`], ignoring the temporary tables of this session.
+    pub(crate) fn catalog_schema_for_ref(
+        &self,
+        table_ref: TableReference,
     ) -> datafusion_common::Result<Arc<dyn SchemaProvider>> {
         let resolved_ref = self.resolve_table_ref(table_ref);
         if self.config.information_schema() && *resolved_ref.schema == *INFORMATION_SCHEMA
@@ -326,6 +356,96 @@ impl SessionState {
             })
     }
 
+    /// Returns the `(catalog, schema)` names of the schemas searched, in
+    /// order, for functions called without a schema name that are not
+    /// registered with the session.
+    ///
+    /// These are the schemas listed in `datafusion.catalog.search_path`, or
+    /// the default schema if it is not set.
+    pub fn function_search_path(&self) -> Vec<(String, String)> {
+        let catalog_options = &self.config_options().catalog;
+        let default_schema = catalog_options.default_schema.as_str();
+        let search_path = catalog_options
+            .search_path
+            .as_deref()
+            .unwrap_or(default_schema);
+
+        search_path
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (catalog, schema) = entry
+                    .split_once('.')
+                    .unwrap_or((catalog_options.default_catalog.as_str(), entry));
+                (catalog.to_string(), schema.to_string())
+            })
+            .collect()
+    }
+
+    /// Returns the scalar function registered in the schema `name` is
+    /// qualified with, qualified with that schema.
+    fn schema_udf(&self, name: &TableReference) -> Option<Arc<ScalarUDF>> {
+        let schema = self.schema_for_ref(name.clone()).ok()?;
+        let udf = schema.udf(name.table())?;
+        let name = self.resolve_table_ref(name.clone());
+        Some(Arc::new(
+            udf.as_ref()
+                .clone()
+                .with_schema(&name.catalog, &name.schema),
+        ))
+    }
+
+    /// Returns the aggregate function registered in the schema `name` is
+    /// qualified with, qualified with that schema.
+    fn schema_udaf(&self, name: &TableReference) -> Option<Arc<AggregateUDF>> {
+        let schema = self.schema_for_ref(name.clone()).ok()?;
+        let udaf = schema.udaf(name.table())?;
+        let name = self.resolve_table_ref(name.clone());
+        Some(Arc::new(
+            udaf.as_ref()
+                .clone()
+                .with_schema(&name.catalog, &name.schema),
+        ))
+    }
+
+    /// Returns the window function registered in the schema `name` is
+    /// qualified with, qualified with that schema.
+    fn schema_udwf(&self, name: &TableReference) -> Option<Arc<WindowUDF>> {
+        let schema = self.schema_for_ref(name.clone()).ok()?;
+        let udwf = schema.udwf(name.table())?;
+        let name = self.resolve_table_ref(name.clone());
+        Some(Arc::new(
+            udwf.as_ref()
+                .clone()
+                .with_schema(&name.catalog, &name.schema),
+        ))
+    }
+
+    /// Returns true if `name` is a function registered with the session, which
+    /// takes precedence over the functions of the search path.
+    fn is_session_function(&self, name: &str) -> bool {
+        self.scalar_functions.contains_key(name)
+            || self.aggregate_functions.contains_key(name)
+            || self.window_functions.contains_key(name)
+    }
+
+    /// Returns the [temporary schema](Self::temporary_schema) if `table_ref`
+    /// is a bare reference to one of this session's temporary tables.
+    pub(crate) fn temporary_schema_for_ref(
+        &self,
+        table_ref: &TableReference,
+    ) -> Option<Arc<dyn SchemaProvider>> {
+        match table_ref {
+            TableReference::Bare { table }
+                if self.temporary_schema.table_exist(table) =>
+            {
+                Some(Arc::clone(&self.temporary_schema))
+            }
+            _ => None,
+        }
+    }
+
     #[deprecated(since = "40.0.0", note = "Use SessionStateBuilder")]
     /// Replace the random session id.
     pub fn with_session_id(mut self, session_id: String) -> Self {
@@ -555,13 +675,27 @@ impl SessionState {
         let mut provider = SessionContextProvider {
             state: self,
             tables: HashMap::with_capacity(references.len()),
+            temporary_tables: HashMap::new(),
+            function_search_path: OnceCell::new(),
         };
 
         for reference in references {
-            let resolved = self.resolve_table_ref(reference);
+            // temporary tables are keyed by their bare name, so that a query
+            // can still reach a catalog table of the same name by qualifying it
+            if let Some(schema) = self.temporary_schema_for_ref(&reference) {
+                let name = reference.table().to_string();
+                if let Entry::Vacant(v) = provider.temporary_tables.entry(name) {
+                    if let Some(table) = schema.table(v.key()).await? {
+                        v.insert(provider_as_source(table));
+                    }
+                }
+                continue;
+            }
+
+            let resolved = self.resolve_table_ref(reference.clone());
             if let Entry::Vacant(v) = provider.tables.entry(resolved) {
                 let resolved = v.key();
-                if let Ok(schema) = self.schema_for_ref(resolved.clone()) {
+                if let Ok(schema) = self.schema_for_ref(reference) {
                     if let Some(table) = schema.table(&resolved.table).await? {
                         v.insert(provider_as_source(table));
                     }
@@ -623,6 +757,8 @@ impl SessionState {
         let provider = SessionContextProvider {
             state: self,
             tables: HashMap::new(),
+            temporary_tables: HashMap::new(),
+            function_search_path: OnceCell::new(),
         };
 
         let query = SqlToRel::new_with_options(&provider, self.get_parser_options());
@@ -672,6 +808,7 @@ impl SessionState {
                         stringified_plans,
                         schema: Arc::clone(&e.schema),
                         logical_optimization_succeeded: false,
+                        explain_format: e.explain_format,
                     }));
                 }
                 Err(e) => return Err(e),
@@ -708,6 +845,7 @@ impl SessionState {
                 stringified_plans,
                 schema: Arc::clone(&e.schema),
                 logical_optimization_succeeded,
+                explain_format: e.explain_format,
             }))
         } else {
             let analyzed_plan = self.analyzer.execute_and_check(
@@ -876,6 +1014,11 @@ impl SessionState {
         &self.catalog_list
     }
 
+    /// Return the schema holding the temporary tables of this session
+    pub fn temporary_schema(&self) -> &Arc<dyn SchemaProvider> {
+        &self.temporary_schema
+    }
+
     /// set the catalog list
     pub(crate) fn register_catalog_list(
         &mut self,
@@ -964,6 +1107,43 @@ impl SessionState {
             None => exec_err!("Prepared statement '{}' does not exist", name),
         }
     }
+
+    /// Registers the materialized view `name`, whose contents are the result
+    /// of `plan` and are read from `source`, so that the optimizer can rewrite
+    /// queries to read from it. Replaces any view registered with the same
+    /// name.
+    ///
+    /// This is done by [`SessionContext`] for the [`MaterializedView`]s it
+    /// registers.
+    ///
+    /// [`SessionContext`]: crate::execution::context::SessionContext
+    /// [`MaterializedView`]: crate::datasource::MaterializedView
+    pub fn register_materialized_view(
+        &mut self,
+        name: impl Into<TableReference>,
+        plan: LogicalPlan,
+        source: Arc<dyn TableSource>,
+    ) -> datafusion_common::Result<()> {
+        // queries are matched in the form they have after analysis
+        let plan =
+            self.analyzer
+                .execute_and_check(plan, self.config_options(), |_, _| {})?;
+        let view = MaterializedViewCandidate::try_new(name, plan, source)?;
+        self.deregister_materialized_view(view.name().clone());
+        self.materialized_views.push(view);
+        Ok(())
+    }
+
+    /// Stops rewriting queries to read from the materialized view `name`.
+    pub fn deregister_materialized_view(&mut self, name: impl Into<TableReference>) {
+        let name = self.resolve_table_ref(name);
+        let catalog = &self.config.options().catalog;
+        let (default_catalog, default_schema) =
+            (&catalog.default_catalog, &catalog.default_schema);
+        self.materialized_views.retain(|view| {
+            view.name().clone().resolve(default_catalog, default_schema) != name
+        });
+    }
 }
 
 /// A builder to be used for building [`SessionState`]'s. Defaults will
@@ -991,6 +1171,7 @@ pub struct SessionStateBuilder {
     table_factories: Option<HashMap<String, Arc<dyn TableProviderFactory>>>,
     runtime_env: Option<Arc<RuntimeEnv>>,
     function_factory: Option<Arc<dyn FunctionFactory>>,
+    temporary_schema: Option<Arc<dyn SchemaProvider>>,
     // fields to support convenience functions
     analyzer_rules: Option<Vec<Arc<dyn AnalyzerRule + Send + Sync>>>,
     optimizer_rules: Option<Vec<Arc<dyn OptimizerRule + Send + Sync>>>,
@@ -1021,6 +1202,7 @@ impl SessionStateBuilder {
             table_factories: None,
             runtime_env: None,
             function_factory: None,
+            temporary_schema: None,
             // fields to support convenience functions
             analyzer_rules: None,
             optimizer_rules: None,
@@ -1033,6 +1215,13 @@ impl SessionStateBuilder {
     /// be cloned from what is set in the provided session state. If the default
     /// catalog exists in existing session state, the new session state will not
     /// create default catalog and schema.
+    ///
+    /// The temporary tables of the existing session are not shared with the
+    /// new session, which starts with an empty [temporary schema]. Pass the
+    /// temporary schema of the existing session to
+    /// [`Self::with_temporary_schema`] to keep them.
+    ///
+    /// [temporary schema]: SessionState::temporary_schema
     pub fn new_from_existing(existing: SessionState) -> Self {
         let default_catalog_exist = existing
             .catalog_list()
@@ -1071,6 +1260,7 @@ impl SessionStateBuilder {
             table_factories: Some(existing.table_factories),
             runtime_env: Some(existing.runtime_env),
             function_factory: existing.function_factory,
+            temporary_schema: None,
 
             // fields to support convenience functions
             analyzer_rules: None,
@@ -1315,6 +1505,15 @@ impl SessionStateBuilder {
         self
     }
 
+    /// Set the schema holding the temporary tables of the session
+    pub fn with_temporary_schema(
+        mut self,
+        temporary_schema: Arc<dyn SchemaProvider>,
+    ) -> Self {
+        self.temporary_schema = Some(temporary_schema);
+        self
+    }
+
     /// Register an `ObjectStore` to the [`RuntimeEnv`]. See [`RuntimeEnv::register_object_store`]
     /// for more details.
     ///
@@ -1377,6 +1576,7 @@ impl SessionStateBuilder {
             table_factories,
             runtime_env,
             function_factory,
+            temporary_schema,
             analyzer_rules,
             optimizer_rules,
             physical_optimizer_rules,
@@ -1411,6 +1611,9 @@ impl SessionStateBuilder {
             runtime_env,
             function_factory,
             prepared_plans: HashMap::new(),
+            temporary_schema: temporary_schema
+                .unwrap_or_else(|| Arc::new(MemorySchemaProvider::new())),
+            materialized_views: vec![],
         };
 
         if let Some(file_formats) = file_formats {
@@ -1589,6 +1792,11 @@ impl SessionStateBuilder {
         &mut self.function_factory
     }
 
+    /// Returns the current temporary_schema value
+    pub fn temporary_schema(&mut self) -> &mut Option<Arc<dyn SchemaProvider>> {
+        &mut self.temporary_schema
+    }
+
     /// Returns the current analyzer_rules value
     pub fn analyzer_rules(
         &mut self,
@@ -1626,6 +1834,7 @@ impl Debug for SessionStateBuilder {
             .field("table_options", &self.table_options)
             .field("table_factories", &self.table_factories)
             .field("function_factory", &self.function_factory)
+            .field("temporary_schema", &self.temporary_schema)
             .field("expr_planners", &self.expr_planners)
             .field("type_planner", &self.type_planner)
             .field("query_planners", &self.query_planner)
@@ -1662,6 +1871,50 @@ impl From<SessionState> for SessionStateBuilder {
 struct SessionContextProvider<'a> {
     state: &'a SessionState,
     tables: HashMap<ResolvedTableReference, Arc<dyn TableSource>>,
+    /// Temporary tables of the session, referenced by their bare name
+    temporary_tables: HashMap<String, Arc<dyn TableSource>>,
+    /// Schemas of the [function search path](SessionState::function_search_path),
+    /// resolved the first time a function is looked up in them
+    function_search_path: OnceCell<Vec<SearchPathSchema>>,
+}
+
+/// A schema of the function search path
+struct SearchPathSchema {
+    catalog: String,
+    name: String,
+    provider: Arc<dyn SchemaProvider>,
+}
+
+impl SessionContextProvider<'_> {
+    /// Returns the first function `name` found by `lookup` in the schemas of
+    /// the function search path, unless `name` is registered with the session
+    fn search_path_function<T>(
+        &self,
+        name: &str,
+        lookup: impl FnMut(&SearchPathSchema) -> Option<T>,
+    ) -> Option<T> {
+        if self.state.is_session_function(name) {
+            return None;
+        }
+        self.function_search_path
+            .get_or_init(|| {
+                self.state
+                    .function_search_path()
+                    .into_iter()
+                    .filter_map(|(catalog, name)| {
+                        let provider =
+                            self.state.catalog_list.catalog(&catalog)?.schema(&name)?;
+                        Some(SearchPathSchema {
+                            catalog,
+                            name,
+                            provider,
+                        })
+                    })
+                    .collect()
+            })
+            .iter()
+            .find_map(lookup)
+    }
 }
 
 impl ContextProvider for SessionContextProvider<'_> {
@@ -1681,6 +1934,11 @@ impl ContextProvider for SessionContextProvider<'_> {
         &self,
         name: TableReference,
     ) -> datafusion_common::Result<Arc<dyn TableSource>> {
+        if let TableReference::Bare { table } = &name {
+            if let Some(source) = self.temporary_tables.get(table.as_ref()) {
+                return Ok(Arc::clone(source));
+            }
+        }
         let name = self.state.resolve_table_ref(name);
         self.tables
             .get(&name)
@@ -1717,15 +1975,60 @@ impl ContextProvider for SessionContextProvider<'_> {
     }
 
     fn get_function_meta(&self, name: &str) -> Option<Arc<ScalarUDF>> {
-        self.state.scalar_functions().get(name).cloned()
+        match self.state.scalar_functions().get(name) {
+            Some(udf) => Some(Arc::clone(udf)),
+            None => self.search_path_function(name, |schema| {
+                let udf = schema.provider.udf(name)?;
+                Some(Arc::new(
+                    udf.as_ref()
+                        .clone()
+                        .with_schema(&schema.catalog, &schema.name),
+                ))
+            }),
+        }
     }
 
     fn get_aggregate_meta(&self, name: &str) -> Option<Arc<AggregateUDF>> {
-        self.state.aggregate_functions().get(name).cloned()
+        match self.state.aggregate_functions().get(name) {
+            Some(udaf) => Some(Arc::clone(udaf)),
+            None => self.search_path_function(name, |schema| {
+                let udaf = schema.provider.udaf(name)?;
+                Some(Arc::new(
+                    udaf.as_ref()
+                        .clone()
+                        .with_schema(&schema.catalog, &schema.name),
+                ))
+            }),
+        }
     }
 
     fn get_window_meta(&self, name: &str) -> Option<Arc<WindowUDF>> {
-        self.state.window_functions().get(name).cloned()
+        match self.state.window_functions().get(name) {
+            Some(udwf) => Some(Arc::clone(udwf)),
+            None => self.search_path_function(name, |schema| {
+                let udwf = schema.provider.udwf(name)?;
+                Some(Arc::new(
+                    udwf.as_ref()
+                        .clone()
+                        .with_schema(&schema.catalog, &schema.name),
+                ))
+            }),
+        }
+    }
+
+    fn get_schema_function_meta(&self, name: &TableReference) -> Option<Arc<ScalarUDF>> {
+        self.state.schema_udf(name)
+    }
+
+    fn get_schema_aggregate_meta(
+        &self,
+        name: &TableReference,
+    ) -> Option<Arc<AggregateUDF>> {
+        self.state.schema_udaf(name)
+    }
+
+    fn get_schema_window_meta(&self, name: &TableReference) -> Option<Arc<WindowUDF>> {
+        self.state.schema_udwf(name)
     }
 
     fn get_variable_type(&self, variable_names: &[String]) -> Option<DataType> {
@@ -1779,27 +2082,48 @@ impl FunctionRegistry for SessionState {
     }
 
     fn udf(&self, name: &str) -> datafusion_common::Result<Arc<ScalarUDF>> {
-        let result = self.scalar_functions.get(name);
-
-        result.cloned().ok_or_else(|| {
-            plan_datafusion_err!("There is no UDF named \"{name}\" in the registry")
-        })
+        let result = self.scalar_functions.get(name).cloned();
+
+        // functions registered in a schema are referred to by their
+        // qualified name, e.g. in serialized plans
+        result
+            .or_else(|| match TableReference::parse_str(name) {
+                TableReference::Bare { .. } => None,
+                name => self.schema_udf(&name),
+            })
+            .ok_or_else(|| {
+                plan_datafusion_err!("There is no UDF named \"{name}\" in the registry")
+            })
     }
 
     fn udaf(&self, name: &str) -> datafusion_common::Result<Arc<AggregateUDF>> {
-        let result = self.aggregate_functions.get(name);
-
-        result.cloned().ok_or_else(|| {
-            plan_datafusion_err!("There is no UDAF named \"{name}\" in the registry")
-        })
+        let result = self.aggregate_functions.get(name).cloned();
+
+        // functions registered in a schema are referred to by their
+        // qualified name, e.g. in serialized plans
+        result
+            .or_else(|| match TableReference::parse_str(name) {
+                TableReference::Bare { .. } => None,
+                name => self.schema_udaf(&name),
+            })
+            .ok_or_else(|| {
+                plan_datafusion_err!("There is no UDAF named \"{name}\" in the registry")
+            })
     }
 
     fn udwf(&self, name: &str) -> datafusion_common::Result<Arc<WindowUDF>> {
-        let result = self.window_functions.get(name);
-
-        result.cloned().ok_or_else(|| {
-            plan_datafusion_err!("There is no UDWF named \"{name}\" in the registry")
-        })
+        let result = self.window_functions.get(name).cloned();
+
+        // functions registered in a schema are referred to by their
+        // qualified name, e.g. in serialized plans
+        result
+            .or_else(|| match TableReference::parse_str(name) {
+                TableReference::Bare { .. } => None,
+                name => self.schema_udwf(&name),
+            })
+            .ok_or_else(|| {
+                plan_datafusion_err!("There is no UDWF named \"{name}\" in the registry")
+            })
     }
 
     fn register_udf(
@@ -1893,6 +2217,14 @@ impl FunctionRegistry for SessionState {
         self.expr_planners.push(expr_planner);
         Ok(())
     }
+
+    fn register_expr_planner_first(
+        &mut self,
+        expr_planner: Arc<dyn ExprPlanner>,
+    ) -> datafusion_common::Result<()> {
+        self.expr_planners.insert(0, expr_planner);
+        Ok(())
+    }
 }
 
 impl OptimizerConfig for SessionState {
@@ -1911,6 +2243,10 @@ impl OptimizerConfig for SessionState {
     fn function_registry(&self) -> Option<&dyn FunctionRegistry> {
         Some(self)
     }
+
+    fn materialized_views(&self) -> &[MaterializedViewCandidate] {
+        &self.materialized_views
+    }
 }
 
 /// Create a new task context instance from SessionState
@@ -2000,6 +2336,7 @@ mod tests {
     use datafusion_optimizer::optimizer::OptimizerRule;
     use datafusion_optimizer::Optimizer;
     use datafusion_sql::planner::{PlannerContext, SqlToRel};
+    use std::cell::OnceCell;
     use std::collections::HashMap;
     use std::sync::Arc;
 
@@ -2010,6 +2347,8 @@ mod tests {
             let provider = SessionContextProvider {
                 state,
                 tables: HashMap::new(),
+                temporary_tables: HashMap::new(),
+                function_search_path: OnceCell::new(),
             };
 
             let sql = "[1,2,3]";
@@ -2096,6 +2435,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_from_existing_temporary_tables() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let table = MemTable::try_new(schema, vec![vec![]])?;
+
+        let session_state = SessionStateBuilder::new().build();
+        session_state
+            .temporary_schema()
+            .register_table("tmp".to_string(), Arc::new(table))?;
+
+        // sessions derived from an existing session have their own temporary tables
+        let new_state =
+            SessionStateBuilder::new_from_existing(session_state.clone()).build();
+        assert!(!new_state.temporary_schema().table_exist("tmp"));
+        new_state.temporary_schema().register_table(
+            "other".to_string(),
+            Arc::new(MemTable::try_new(
+                Arc::new(Schema::new(vec![Field::new("b", DataType::Int32, true)])),
+                vec![vec![]],
+            )?),
+        )?;
+        assert!(!session_state.temporary_schema().table_exist("other"));
+
+        // unless the temporary schema is kept explicitly
+        let temporary_schema = Arc::clone(session_state.temporary_schema());
+        let new_state = SessionStateBuilder::new_from_existing(session_state)
+            .with_temporary_schema(temporary_schema)
+            .build();
+        assert!(new_state.temporary_schema().table_exist("tmp"));
+        Ok(())
+    }
+
     #[test]
     fn test_session_state_with_optimizer_rules() {
         #[derive(Default, Debug)]
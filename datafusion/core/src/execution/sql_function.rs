@@ -0,0 +1,528 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`SqlFunctionFactory`]: creates functions whose body is written in SQL
+
+use std::any::Any;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+use crate::datasource::{TableProvider, ViewTable};
+use crate::execution::context::{FunctionFactory, RegisterFunction};
+use crate::execution::session_state::SessionState;
+
+use arrow::array::{ArrayRef, RecordBatch, RecordBatchOptions};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use async_trait::async_trait;
+use datafusion_catalog::TableFunctionImpl;
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_common::{
+    internal_datafusion_err, not_impl_err, plan_datafusion_err, plan_err, Column,
+    DFSchema, Result, ScalarValue,
+};
+use datafusion_expr::execution_props::ExecutionProps;
+use datafusion_expr::expr::Placeholder;
+use datafusion_expr::expr_rewriter::NamePreserver;
+use datafusion_expr::simplify::{ExprSimplifyResult, SimplifyContext, SimplifyInfo};
+use datafusion_expr::{
+    ColumnarValue, CreateFunction, CreateFunctionBody, Expr, ExprSchemable, LogicalPlan,
+    OperateFunctionArg, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature,
+    TypeSignature, Volatility,
+};
+use datafusion_optimizer::simplify_expressions::ExprSimplifier;
+use datafusion_physical_expr::{create_physical_expr, PhysicalExpr};
+use datafusion_sql::parser::Statement;
+use datafusion_sql::planner::IdentNormalizer;
+use sqlparser::ast::{
+    visit_expressions_mut, Expr as SQLExpr, Statement as SQLStatement, Value,
+};
+
+/// [`FunctionFactory`] for functions written in SQL.
+///
+/// This factory is used by [`SessionContext`] to handle `CREATE FUNCTION`
+/// statements when no other [`FunctionFactory`] has been configured. It
+/// supports two kinds of functions, with `LANGUAGE SQL` or no language:
+///
+/// * Scalar functions whose body is an expression, for example
+///   `CREATE FUNCTION add_tax(price DOUBLE, rate DOUBLE DEFAULT 0.2) RETURNS DOUBLE RETURN price * (1 + rate)`.
+///   They are inlined as an expression wherever they are called.
+///
+/// * Table functions (table macros) whose body is a query string, for example
+///   `CREATE FUNCTION big_orders(min_qty BIGINT) AS 'SELECT * FROM orders WHERE qty >= min_qty'`.
+///   They are inlined as a view wherever they are called, and can not
+///   declare a return type. A scalar function with a string body must
+///   therefore declare its return type.
+///
+/// Parameters are referenced by name, or by position as `$1`, `$2`, etc.
+/// Trailing parameters may have a default value used when the argument is
+/// omitted.
+///
+/// [`SessionContext`]: crate::execution::context::SessionContext
+#[derive(Debug, Default)]
+pub struct SqlFunctionFactory {}
+
+#[async_trait]
+impl FunctionFactory for SqlFunctionFactory {
+    async fn create(
+        &self,
+        state: &SessionState,
+        statement: CreateFunction,
+    ) -> Result<RegisterFunction> {
+        let CreateFunction {
+            name,
+            args,
+            return_type,
+            params:
+                CreateFunctionBody {
+                    language,
+                    behavior,
+                    function_body,
+                },
+            ..
+        } = statement;
+
+        if let Some(language) = language {
+            if !language.value.eq_ignore_ascii_case("sql") {
+                return not_impl_err!(
+                    "Functions in language {language} require a custom FunctionFactory, \
+                    only SQL functions are supported"
+                );
+            }
+        }
+        let Some(body) = function_body else {
+            return plan_err!("Function {name} has no body");
+        };
+        let params = args.unwrap_or_default();
+        let required = params
+            .iter()
+            .take_while(|param| param.default_expr.is_none())
+            .count();
+        if params[required..]
+            .iter()
+            .any(|param| param.default_expr.is_none())
+        {
+            return plan_err!(
+                "Parameters of function {name} following a parameter with a default \
+                value must also have a default value"
+            );
+        }
+
+        match body {
+            // a string body is the query of a table macro
+            Expr::Literal(ScalarValue::Utf8(Some(query))) if return_type.is_none() => {
+                let function = SqlTableFunction::try_new(state, params, &query).await?;
+                Ok(RegisterFunction::Table(
+                    name.table().to_string(),
+                    Arc::new(function),
+                ))
+            }
+            body => {
+                let volatility = behavior.unwrap_or(Volatility::Volatile);
+                let function = SqlScalarFunction::try_new(
+                    name.table().to_string(),
+                    params,
+                    required,
+                    return_type,
+                    body,
+                    volatility,
+                )?;
+                Ok(RegisterFunction::Scalar(Arc::new(
+                    ScalarUDF::new_from_impl(function),
+                )))
+            }
+        }
+    }
+}
+
+/// Scalar function defined by a SQL expression, where the parameters are
+/// placeholders `$1`, `$2`, ...
+#[derive(Debug)]
+struct SqlScalarFunction {
+    name: String,
+    params: Vec<OperateFunctionArg>,
+    /// Number of times each parameter is referenced by the body
+    uses: Vec<usize>,
+    body: Expr,
+    return_type: DataType,
+    signature: Signature,
+    /// Number of parameters without a default value
+    required: usize,
+    /// The body planned against the arguments of a call, with the schema of
+    /// the arguments, for each number of arguments from `required` to the
+    /// number of parameters
+    evaluators: Vec<(SchemaRef, Arc<dyn PhysicalExpr>)>,
+}
+
+impl SqlScalarFunction {
+    fn try_new(
+        name: String,
+        params: Vec<OperateFunctionArg>,
+        required: usize,
+        return_type: Option<DataType>,
+        body: Expr,
+        volatility: Volatility,
+    ) -> Result<Self> {
+        body.apply(|expr| {
+            match expr {
+                Expr::Placeholder(Placeholder { id, .. }) => {
+                    parameter_index(id, &params)?;
+                }
+                Expr::ScalarSubquery(_)
+                | Expr::Exists(_)
+                | Expr::InSubquery(_)
+                | Expr::SetComparison(_) => {
+                    return not_impl_err!(
+                        "Subqueries are not supported in SQL scalar functions: {expr}"
+                    );
+                }
+                _ => {}
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+
+        // calls are inlined after type coercion, so the body is coerced here
+        let empty_schema = Arc::new(DFSchema::empty());
+        let props = ExecutionProps::new();
+        let simplifier = ExprSimplifier::new(
+            SimplifyContext::new(&props).with_schema(Arc::clone(&empty_schema)),
+        );
+        let body = simplifier.coerce(body, &empty_schema)?;
+
+        let body_type = body.get_type(empty_schema.as_ref())?;
+        let (body, return_type) = match return_type {
+            Some(return_type) if return_type != body_type => (
+                body.cast_to(&return_type, empty_schema.as_ref())?,
+                return_type,
+            ),
+            _ => (body, body_type),
+        };
+
+        // calls may omit any of the trailing parameters with a default value
+        let types = params
+            .iter()
+            .map(|param| param.data_type.clone())
+            .collect::<Vec<_>>();
+        let signature = if required == types.len() {
+            Signature::exact(types, volatility)
+        } else {
+            Signature::one_of(
+                (required..=types.len())
+                    .map(|n| TypeSignature::Exact(types[..n].to_vec()))
+                    .collect(),
+                volatility,
+            )
+        };
+
+        let mut uses = vec![0; params.len()];
+        body.apply(|expr| {
+            if let Expr::Placeholder(Placeholder { id, .. }) = expr {
+                uses[parameter_index(id, &params)?] += 1;
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+
+        let evaluators = (required..=params.len())
+            .map(|n| plan_evaluator(&body, &params, n, &props))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            name,
+            params,
+            uses,
+            body,
+            return_type,
+            signature,
+            required,
+            evaluators,
+        })
+    }
+}
+
+/// Plans `body` against the first `n` arguments of a call, which are the
+/// columns `$1`, `$2`, ... of the returned schema, and the default values
+/// of the omitted parameters
+fn plan_evaluator(
+    body: &Expr,
+    params: &[OperateFunctionArg],
+    n: usize,
+    props: &ExecutionProps,
+) -> Result<(SchemaRef, Arc<dyn PhysicalExpr>)> {
+    let schema = Arc::new(Schema::new(
+        params[..n]
+            .iter()
+            .enumerate()
+            .map(|(i, param)| {
+                Field::new(format!("${}", i + 1), param.data_type.clone(), true)
+            })
+            .collect::<Vec<_>>(),
+    ));
+    let arguments = schema
+        .fields()
+        .iter()
+        .map(|field| Expr::Column(Column::from_name(field.name())))
+        .collect();
+    let df_schema = Arc::new(DFSchema::try_from(Arc::clone(&schema))?);
+    let expr = replace_parameters(body.clone(), params, arguments)?;
+    let simplifier = ExprSimplifier::new(
+        SimplifyContext::new(props).with_schema(Arc::clone(&df_schema)),
+    );
+    let expr = simplifier.coerce(expr, &df_schema)?;
+    Ok((schema, create_physical_expr(&expr, &df_schema, props)?))
+}
+
+impl ScalarUDFImpl for SqlScalarFunction {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(self.return_type.clone())
+    }
+
+    /// Evaluates the body against the arguments. This is only used when
+    /// the call has not been inlined by [`Self::simplify`].
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let number_rows = args.number_rows;
+        let (schema, expr) = args
+            .args
+            .len()
+            .checked_sub(self.required)
+            .and_then(|i| self.evaluators.get(i))
+            .ok_or_else(|| {
+                internal_datafusion_err!(
+                    "Unexpected number of arguments {} for function {}",
+                    args.args.len(),
+                    self.name
+                )
+            })?;
+        let columns = args
+            .args
+            .into_iter()
+            .map(|arg| arg.into_array(number_rows))
+            .collect::<Result<Vec<ArrayRef>>>()?;
+        let batch = RecordBatch::try_new_with_options(
+            Arc::clone(schema),
+            columns,
+            &RecordBatchOptions::new().with_row_count(Some(number_rows)),
+        )?;
+        expr.evaluate(&batch)
+    }
+
+    /// Inlines the body, replacing the parameters with the arguments.
+    ///
+    /// Calls with volatile arguments, or with arguments other than columns
+    /// and literals that the body references more than once, are not inlined,
+    /// so that each argument is evaluated once per row.
+    fn simplify(
+        &self,
+        args: Vec<Expr>,
+        _info: &dyn SimplifyInfo,
+    ) -> Result<ExprSimplifyResult> {
+        let arguments = bind_arguments(&self.params, args.clone())?;
+        let inlinable = arguments.iter().zip(&self.uses).all(|(arg, uses)| {
+            !arg.is_volatile()
+                && (*uses <= 1 || matches!(arg, Expr::Column(_) | Expr::Literal(_)))
+        });
+        if !inlinable {
+            return Ok(ExprSimplifyResult::Original(args));
+        }
+        let expr = replace_placeholders(self.body.clone(), &arguments)?.data;
+        Ok(ExprSimplifyResult::Simplified(expr))
+    }
+}
+
+/// Table function defined by a SQL query, where the parameters are
+/// placeholders `$1`, `$2`, ...
+#[derive(Debug)]
+struct SqlTableFunction {
+    params: Vec<OperateFunctionArg>,
+    plan: LogicalPlan,
+}
+
+impl SqlTableFunction {
+    async fn try_new(
+        state: &SessionState,
+        params: Vec<OperateFunctionArg>,
+        query: &str,
+    ) -> Result<Self> {
+        // a string body that is not a query can only be the value of a
+        // scalar function, which must declare its return type
+        let dialect = state.config().options().sql_parser.dialect.as_str();
+        let mut statement = match state.sql_to_statement(query, dialect) {
+            Ok(Statement::Statement(statement))
+                if matches!(statement.as_ref(), SQLStatement::Query(_)) =>
+            {
+                statement
+            }
+            Ok(_) | Err(_) => {
+                return plan_err!(
+                    "Body of a SQL table function must be a query, a scalar function \
+                    with a string body must declare its return type with RETURNS"
+                )
+            }
+        };
+
+        // replace the parameter names with their placeholders
+        let normalizer = IdentNormalizer::new(
+            state
+                .config()
+                .options()
+                .sql_parser
+                .enable_ident_normalization,
+        );
+        let names = params
+            .iter()
+            .map(|param| {
+                param
+                    .name
+                    .as_ref()
+                    .map(|name| normalizer.normalize(name.clone()))
+            })
+            .collect::<Vec<_>>();
+        let _ = visit_expressions_mut(statement.as_mut(), |expr| {
+            if let SQLExpr::Identifier(ident) = expr {
+                let name = normalizer.normalize(ident.clone());
+                if let Some(i) = names.iter().position(|n| n.as_ref() == Some(&name)) {
+                    *expr = SQLExpr::Value(Value::Placeholder(format!("${}", i + 1)));
+                }
+            }
+            ControlFlow::<()>::Continue(())
+        });
+
+        let plan = state
+            .statement_to_plan(Statement::Statement(statement))
+            .await?;
+        for id in plan.get_parameter_names()? {
+            parameter_index(&id, &params)?;
+        }
+
+        Ok(Self { params, plan })
+    }
+}
+
+impl TableFunctionImpl for SqlTableFunction {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        let empty_schema = DFSchema::empty();
+        let args = args
+            .iter()
+            .enumerate()
+            .map(|(i, arg)| match self.params.get(i) {
+                Some(param) => arg.clone().cast_to(&param.data_type, &empty_schema),
+                None => Ok(arg.clone()),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let arguments = bind_arguments(&self.params, args)?;
+
+        let plan = self
+            .plan
+            .clone()
+            .transform_up_with_subqueries(|plan| {
+                let name_preserver = NamePreserver::new(&plan);
+                plan.map_expressions(|expr| {
+                    let original_name = name_preserver.save(&expr);
+                    let transformed = replace_placeholders(expr, &arguments)?;
+                    Ok(transformed.update_data(|expr| original_name.restore(expr)))
+                })?
+                .map_data(|plan| plan.recompute_schema())
+            })?
+            .data;
+
+        Ok(Arc::new(ViewTable::try_new(plan, None)?))
+    }
+}
+
+/// Returns the index of the parameter referenced by the placeholder `id`
+fn parameter_index(id: &str, params: &[OperateFunctionArg]) -> Result<usize> {
+    id.strip_prefix('$')
+        .and_then(|index| index.parse::<usize>().ok())
+        .filter(|index| (1..=params.len()).contains(index))
+        .map(|index| index - 1)
+        .ok_or_else(|| {
+            plan_datafusion_err!(
+                "Function body refers to parameter {id}, but the function has {} parameters",
+                params.len()
+            )
+        })
+}
+
+/// Returns the expressions to use for each parameter: the argument, or the
+/// default value when the argument is omitted
+fn bind_arguments(
+    params: &[OperateFunctionArg],
+    mut args: Vec<Expr>,
+) -> Result<Vec<Expr>> {
+    if args.len() > params.len() {
+        return plan_err!(
+            "Function expects at most {} arguments, got {}",
+            params.len(),
+            args.len()
+        );
+    }
+    for param in &params[args.len()..] {
+        match &param.default_expr {
+            Some(default_expr) => args.push(
+                default_expr
+                    .clone()
+                    .cast_to(&param.data_type, &DFSchema::empty())?,
+            ),
+            None => {
+                return plan_err!(
+                    "Function expects at least {} arguments, got {}",
+                    params
+                        .iter()
+                        .take_while(|param| param.default_expr.is_none())
+                        .count(),
+                    args.len()
+                )
+            }
+        }
+    }
+    Ok(args)
+}
+
+/// Replaces the parameters of the function in `expr` with `args`
+fn replace_parameters(
+    expr: Expr,
+    params: &[OperateFunctionArg],
+    args: Vec<Expr>,
+) -> Result<Expr> {
+    let arguments = bind_arguments(params, args)?;
+    replace_placeholders(expr, &arguments).map(|transformed| transformed.data)
+}
+
+/// Replaces the placeholders `$1`, `$2`, ... in `expr` with `arguments`
+fn replace_placeholders(expr: Expr, arguments: &[Expr]) -> Result<Transformed<Expr>> {
+    expr.transform_up(|expr| match expr {
+        Expr::Placeholder(Placeholder { id, .. }) => {
+            let index = id
+                .strip_prefix('$')
+                .and_then(|index| index.parse::<usize>().ok())
+                .filter(|index| (1..=arguments.len()).contains(index))
+                .ok_or_else(|| plan_datafusion_err!("No argument for parameter {id}"))?;
+            Ok(Transformed::yes(arguments[index - 1].clone()))
+        }
+        _ => Ok(Transformed::no(expr)),
+    })
+}
@@ -24,16 +24,17 @@ use std::sync::Arc;
 use crate::datasource::file_format::file_type_to_format;
 use crate::datasource::listing::ListingTableUrl;
 use crate::datasource::physical_plan::FileSinkConfig;
-use crate::datasource::source_as_provider;
+use crate::datasource::{source_as_provider, TableProvider};
 use crate::error::{DataFusionError, Result};
 use crate::execution::context::{ExecutionProps, SessionState};
 use crate::logical_expr::utils::generate_sort_key;
 use crate::logical_expr::{
-    Aggregate, EmptyRelation, Join, Projection, Sort, TableScan, Unnest, Values, Window,
+    Aggregate, EmptyRelation, Join, Projection, Sort, SubqueryAlias, TableScan, Unnest,
+    Values, Window,
 };
 use crate::logical_expr::{
-    Expr, LogicalPlan, Partitioning as LogicalPartitioning, PlanType, Repartition,
-    UserDefinedLogicalNode,
+    Expr, LogicalPlan, LogicalPlanBuilder, Partitioning as LogicalPartitioning, PlanType,
+    Repartition, UserDefinedLogicalNode,
 };
 use crate::physical_expr::{create_physical_expr, create_physical_exprs};
 use crate::physical_plan::aggregates::{AggregateExec, AggregateMode, PhysicalGroupBy};
@@ -42,6 +43,8 @@ use crate::physical_plan::empty::EmptyExec;
 use crate::physical_plan::explain::ExplainExec;
 use crate::physical_plan::expressions::PhysicalSortExpr;
 use crate::physical_plan::filter::FilterExec;
+use crate::physical_plan::gap_fill::GapFillExec;
+use crate::physical_plan::insert::{ConstraintValidationExec, ValidatedConstraint};
 use crate::physical_plan::joins::utils as join_utils;
 use crate::physical_plan::joins::{
     CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode, SortMergeJoinExec,
@@ -50,6 +53,7 @@ use crate::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::recursive_query::RecursiveQueryExec;
 use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::sample::{SampleExec, SystemSample};
 use crate::physical_plan::sorts::sort::SortExec;
 use crate::physical_plan::union::UnionExec;
 use crate::physical_plan::unnest::UnnestExec;
@@ -62,12 +66,12 @@ use crate::physical_plan::{
 use arrow::compute::SortOptions;
 use arrow::datatypes::{Schema, SchemaRef};
 use arrow_array::builder::StringBuilder;
-use arrow_array::RecordBatch;
+use arrow_array::{RecordBatch, RecordBatchOptions};
 use datafusion_common::display::ToStringifiedPlan;
 use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion, TreeNodeVisitor};
 use datafusion_common::{
-    exec_err, internal_datafusion_err, internal_err, not_impl_err, plan_err, DFSchema,
-    ScalarValue,
+    exec_err, internal_datafusion_err, internal_err, not_impl_err, plan_err, Column,
+    Constraint, DFSchema, ScalarValue, TableReference,
 };
 use datafusion_expr::dml::{CopyTo, InsertOp};
 use datafusion_expr::expr::{
@@ -75,10 +79,13 @@ use datafusion_expr::expr::{
 };
 use datafusion_expr::expr_rewriter::unnormalize_cols;
 use datafusion_expr::logical_plan::builder::wrap_projection_for_join_if_necessary;
+use datafusion_expr::utils::split_conjunction;
 use datafusion_expr::{
-    DescribeTable, DmlStatement, Extension, FetchType, Filter, JoinType, RecursiveQuery,
-    SkipType, SortExpr, StringifiedPlan, WindowFrame, WindowFrameBound, WriteOp,
+    lit, ColumnarValue, DescribeTable, DmlStatement, Extension, FetchType, Filter,
+    GapFill, JoinType, RecursiveQuery, Sample, SampleMethod, SkipType, SortExpr,
+    StringifiedPlan, WindowFrame, WindowFrameBound, WriteOp,
 };
+use datafusion_optimizer::push_down_filter::replace_cols_by_name;
 use datafusion_physical_expr::aggregate::{AggregateExprBuilder, AggregateFunctionExpr};
 use datafusion_physical_expr::expressions::Literal;
 use datafusion_physical_expr::LexOrdering;
@@ -86,6 +93,7 @@ use datafusion_physical_optimizer::PhysicalOptimizerRule;
 use datafusion_physical_plan::execution_plan::InvariantLevel;
 use datafusion_physical_plan::memory::MemorySourceConfig;
 use datafusion_physical_plan::placeholder_row::PlaceholderRowExec;
+use datafusion_physical_plan::source::DataSourceExec;
 use datafusion_physical_plan::unnest::ListUnnest;
 use datafusion_sql::utils::window_expr_common_partition_keys;
 
@@ -544,13 +552,22 @@ impl DefaultPhysicalPlanner {
             }
             LogicalPlan::Dml(DmlStatement {
                 table_name,
+                table_schema,
                 op: WriteOp::Insert(insert_op),
                 ..
             }) => {
                 let name = table_name.table();
                 let schema = session_state.schema_for_ref(table_name.clone())?;
                 if let Some(provider) = schema.table(name).await? {
-                    let input_exec = children.one()?;
+                    let input_exec = validate_inserted_rows(
+                        session_state,
+                        table_name,
+                        table_schema,
+                        &provider,
+                        children.one()?,
+                        *insert_op,
+                    )
+                    .await?;
                     provider
                         .insert_into(session_state, input_exec, *insert_op)
                         .await?
@@ -558,6 +575,66 @@ impl DefaultPhysicalPlanner {
                     return exec_err!("Table '{table_name}' does not exist");
                 }
             }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                op: WriteOp::Delete,
+                input,
+                ..
+            }) => {
+                let name = table_name.table();
+                let schema = session_state.schema_for_ref(table_name.clone())?;
+                if let Some(provider) = schema.table(name).await? {
+                    let (filters, _, selection) =
+                        self.plan_dml_input(input, session_state).await?;
+                    provider
+                        .delete_from(session_state, filters, selection)
+                        .await?
+                } else {
+                    return exec_err!("Table '{table_name}' does not exist");
+                }
+            }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                table_schema,
+                op: WriteOp::Update,
+                input,
+                ..
+            }) => {
+                let name = table_name.table();
+                let schema = session_state.schema_for_ref(table_name.clone())?;
+                if let Some(provider) = schema.table(name).await? {
+                    let (filters, output_exprs, selection) =
+                        self.plan_dml_input(input, session_state).await?;
+                    let assignments = table_schema
+                        .fields()
+                        .iter()
+                        .zip(output_exprs)
+                        .filter(|(field, expr)| {
+                            !matches!(expr, Expr::Column(c) if c.name == *field.name())
+                        })
+                        .map(|(field, expr)| (field.name().clone(), expr))
+                        .collect();
+                    provider
+                        .update(session_state, assignments, filters, selection)
+                        .await?
+                } else {
+                    return exec_err!("Table '{table_name}' does not exist");
+                }
+            }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                op: WriteOp::Merge,
+                ..
+            }) => {
+                let name = table_name.table();
+                let schema = session_state.schema_for_ref(table_name.clone())?;
+                if let Some(provider) = schema.table(name).await? {
+                    let input_exec = children.one()?;
+                    provider.merge_into(session_state, input_exec).await?
+                } else {
+                    return exec_err!("Table '{table_name}' does not exist");
+                }
+            }
             LogicalPlan::Window(Window {
                 input, window_expr, ..
             }) => {
@@ -891,6 +968,96 @@ impl DefaultPhysicalPlanner {
                     options.clone(),
                 ))
             }
+            LogicalPlan::GapFill(GapFill {
+                input,
+                partition_by,
+                fill_expr,
+                from,
+                to,
+                step,
+                strategies,
+                schema,
+            }) => {
+                let physical_input = children.one()?;
+                let input_dfschema = input.schema();
+                let execution_props = session_state.execution_props();
+                let partition_by = create_physical_sort_exprs(
+                    partition_by,
+                    input_dfschema,
+                    execution_props,
+                )?;
+                let fill_expr = create_physical_sort_expr(
+                    fill_expr,
+                    input_dfschema,
+                    execution_props,
+                )?;
+                // FROM, TO and STEP may still be wrapped in casts (and aliases
+                // added by the simplifier), so evaluate them once here
+                let empty_schema = DFSchema::empty();
+                let literal = |expr: &Expr| -> Result<ScalarValue> {
+                    let constant = expr.clone().unalias_nested().data;
+                    if let Expr::Literal(value) = constant {
+                        return Ok(value);
+                    }
+                    let Ok(physical_expr) =
+                        create_physical_expr(&constant, &empty_schema, execution_props)
+                    else {
+                        return plan_err!(
+                            "WITH FILL FROM, TO and STEP must be constants, got {expr}"
+                        );
+                    };
+                    let batch = RecordBatch::try_new_with_options(
+                        Arc::new(Schema::empty()),
+                        vec![],
+                        &RecordBatchOptions::new().with_row_count(Some(1)),
+                    )?;
+                    match physical_expr.evaluate(&batch)? {
+                        ColumnarValue::Scalar(value) => Ok(value),
+                        ColumnarValue::Array(array) => {
+                            ScalarValue::try_from_array(&array, 0)
+                        }
+                    }
+                };
+                Arc::new(GapFillExec::try_new(
+                    physical_input,
+                    partition_by,
+                    fill_expr,
+                    from.as_deref().map(literal).transpose()?,
+                    to.as_deref().map(literal).transpose()?,
+                    literal(step)?,
+                    strategies.clone(),
+                    SchemaRef::new(schema.as_ref().to_owned().into()),
+                )?)
+            }
+            LogicalPlan::Sample(Sample {
+                method,
+                fraction,
+                seed,
+                ..
+            }) => {
+                let physical_input = children.one()?;
+                // Every partition must select its rows with the same seed, so
+                // a random seed is chosen once for the whole plan
+                let seed = seed.unwrap_or_else(rand::random);
+                // SYSTEM samples of a data source that supports it skip whole
+                // blocks of data while scanning, instead of after reading them
+                let sampled_source = match method {
+                    SampleMethod::System => push_down_system_sample(
+                        &physical_input,
+                        SystemSample::new(*fraction, seed),
+                    )?,
+                    SampleMethod::Bernoulli => None,
+                };
+                match sampled_source {
+                    Some(exec) => exec,
+                    None => Arc::new(SampleExec::try_new(
+                        physical_input,
+                        *method,
+                        *fraction,
+                        seed,
+                    )?),
+                }
+            }
 
             // 2 Children
             LogicalPlan::Join(Join {
@@ -900,10 +1067,12 @@ impl DefaultPhysicalPlanner {
                 filter,
                 join_type,
                 null_equals_null,
+                null_aware,
                 schema: join_schema,
                 ..
             }) => {
                 let null_equals_null = *null_equals_null;
+                let null_aware = *null_aware;
 
                 let [physical_left, physical_right] = children.two()?;
 
@@ -1118,6 +1287,27 @@ impl DefaultPhysicalPlanner {
                             None,
                         )?)
                     }
+                } else if null_aware {
+                    // A null-aware join can't be partitioned, as the NULL keys
+                    // of one side decide the result of every row of the other
+                    // side. The side of the subquery is collected, so that the
+                    // preserved side is probed without repartitioning it.
+                    let join = HashJoinExec::try_new(
+                        physical_left,
+                        physical_right,
+                        join_on,
+                        join_filter,
+                        join_type,
+                        None,
+                        PartitionMode::CollectLeft,
+                        null_equals_null,
+                    )?
+                    .with_null_aware(true)?;
+                    if *join_type == JoinType::LeftAnti {
+                        join.swap_inputs(PartitionMode::CollectLeft)?
+                    } else {
+                        Arc::new(join)
+                    }
                 } else if session_state.config().target_partitions() > 1
                     && session_state.config().repartition_joins()
                     && !prefer_hash_join
@@ -1744,7 +1934,11 @@ impl DefaultPhysicalPlanner {
                             displayable(input.as_ref())
                                 .set_show_statistics(config.show_statistics)
                                 .set_show_schema(config.show_schema)
-                                .to_stringified(e.verbose, InitialPhysicalPlan),
+                                .to_stringified_with_format(
+                                    e.verbose,
+                                    InitialPhysicalPlan,
+                                    e.explain_format,
+                                )?,
                         );
 
                         // Show statistics + schema in verbose output even if not
@@ -1754,20 +1948,22 @@ impl DefaultPhysicalPlanner {
                                 stringified_plans.push(
                                     displayable(input.as_ref())
                                         .set_show_statistics(true)
-                                        .to_stringified(
+                                        .to_stringified_with_format(
                                             e.verbose,
                                             InitialPhysicalPlanWithStats,
-                                        ),
+                                            e.explain_format,
+                                        )?,
                                 );
                             }
                             if !config.show_schema {
                                 stringified_plans.push(
                                     displayable(input.as_ref())
                                         .set_show_schema(true)
-                                        .to_stringified(
+                                        .to_stringified_with_format(
                                             e.verbose,
                                             InitialPhysicalPlanWithSchema,
-                                        ),
+                                            e.explain_format,
+                                        )?,
                                 );
                             }
                         }
@@ -1778,12 +1974,15 @@ impl DefaultPhysicalPlanner {
                             |plan, optimizer| {
                                 let optimizer_name = optimizer.name().to_string();
                                 let plan_type = OptimizedPhysicalPlan { optimizer_name };
-                                stringified_plans.push(
-                                    displayable(plan)
-                                        .set_show_statistics(config.show_statistics)
-                                        .set_show_schema(config.show_schema)
-                                        .to_stringified(e.verbose, plan_type),
-                                );
+                                // the initial plan was already formatted, so this
+                                // cannot fail because of an unsupported format
+                                let plan = displayable(plan)
+                                    .set_show_statistics(config.show_statistics)
+                                    .set_show_schema(config.show_schema)
+                                    .format(e.verbose, e.explain_format)
+                                    .unwrap_or_else(|err| err.to_string());
+                                stringified_plans
+                                    .push(StringifiedPlan::new(plan_type, plan));
                             },
                         );
                         match optimized_plan {
@@ -1793,7 +1992,11 @@ impl DefaultPhysicalPlanner {
                                     displayable(input.as_ref())
                                         .set_show_statistics(config.show_statistics)
                                         .set_show_schema(config.show_schema)
-                                        .to_stringified(e.verbose, FinalPhysicalPlan),
+                                        .to_stringified_with_format(
+                                            e.verbose,
+                                            FinalPhysicalPlan,
+                                            e.explain_format,
+                                        )?,
                                 );
 
                                 // Show statistics + schema in verbose output even if not
@@ -1803,20 +2006,22 @@ impl DefaultPhysicalPlanner {
                                         stringified_plans.push(
                                             displayable(input.as_ref())
                                                 .set_show_statistics(true)
-                                                .to_stringified(
+                                                .to_stringified_with_format(
                                                     e.verbose,
                                                     FinalPhysicalPlanWithStats,
-                                                ),
+                                                    e.explain_format,
+                                                )?,
                                         );
                                     }
                                     if !config.show_schema {
                                         stringified_plans.push(
                                             displayable(input.as_ref())
                                                 .set_show_schema(true)
-                                                .to_stringified(
+                                                .to_stringified_with_format(
                                                     e.verbose,
                                                     FinalPhysicalPlanWithSchema,
-                                                ),
+                                                    e.explain_format,
+                                                )?,
                                         );
                                     }
                                 }
@@ -1847,12 +2052,10 @@ impl DefaultPhysicalPlanner {
             let input = self.create_physical_plan(&a.input, session_state).await?;
             let schema = SchemaRef::new((*a.schema).clone().into());
             let show_statistics = session_state.config_options().explain.show_statistics;
-            Ok(Some(Arc::new(AnalyzeExec::new(
-                a.verbose,
-                show_statistics,
-                input,
-                schema,
-            ))))
+            Ok(Some(Arc::new(
+                AnalyzeExec::new(a.verbose, show_statistics, input, schema)
+                    .with_explain_format(a.explain_format),
+            )))
         } else {
             Ok(None)
         }
@@ -2004,6 +2207,329 @@ impl DefaultPhysicalPlanner {
             input_exec,
         )?))
     }
+
+    /// Decomposes the input plan of a `DELETE` or `UPDATE` statement into
+    /// the filters and the plan selecting the affected rows, and the
+    /// expressions computing each of its output columns.
+    ///
+    /// The optimizer decorrelates the `IN` and `EXISTS` subqueries of the
+    /// predicates of the statement into joins with the target table. Such an
+    /// input is planned as a selection producing the affected rows, which is
+    /// executed together with the statement, see [`extract_dml_selection`].
+    /// Otherwise the affected rows are only selected by filters, see
+    /// [`extract_dml_input`], which also rejects the joins that can not be
+    /// planned as a selection.
+    async fn plan_dml_input(
+        &self,
+        input: &LogicalPlan,
+        session_state: &SessionState,
+    ) -> Result<(Vec<Expr>, Vec<Expr>, Option<Arc<dyn ExecutionPlan>>)> {
+        if input.exists(|plan| Ok(matches!(plan, LogicalPlan::Join(_))))? {
+            if let Some((selection, output_exprs)) = extract_dml_selection(input)? {
+                let selection =
+                    self.create_initial_plan(&selection, session_state).await?;
+                return Ok((vec![], output_exprs, Some(selection)));
+            }
+        }
+        let (filters, output_exprs) = extract_dml_input(input)?;
+        Ok((filters, output_exprs, None))
+    }
+}
+
+/// Decomposes the input plan of a `DELETE` or `UPDATE` statement into the
+/// predicates selecting the affected rows and the expressions computing
+/// each of its output columns.
+///
+/// The SQL planner builds this input as a scan of the target table,
+/// optionally followed by a filter and (for updates) a projection. Any
+/// projection the optimizer introduced in between is inlined, so that the
+/// returned expressions only refer to the unqualified columns of the
+/// target table. Inputs joining the target table with other relations are
+/// decomposed by [`extract_dml_selection`] instead.
+fn extract_dml_input(input: &LogicalPlan) -> Result<(Vec<Expr>, Vec<Expr>)> {
+    let (filters, replace_map) = extract_dml_input_inner(input)?;
+    Ok((
+        unnormalize_cols(filters.into_iter().unique()),
+        unnormalize_cols(dml_output_exprs(input, &replace_map)),
+    ))
+}
+
+/// Decomposes the input plan of a `DELETE` or `UPDATE` statement, which joins
+/// the target table with other relations, into a plan producing the affected
+/// rows and the expressions computing each of its output columns, if
+/// supported.
+///
+/// The projections on top of the input are inlined into the returned
+/// expressions, which may only refer to the unqualified columns of the
+/// target table. The plan below them produces the affected rows, and the
+/// returned plan projects the columns of the target table from it, so that
+/// these rows can be identified as described in [`SelectedRows`]. The
+/// columns the optimizer pruned from the scan of the target table do not
+/// affect which rows are selected, unless the scan itself filters on them.
+///
+/// [`SelectedRows`]: crate::physical_plan::dml::SelectedRows
+fn extract_dml_selection(
+    input: &LogicalPlan,
+) -> Result<Option<(LogicalPlan, Vec<Expr>)>> {
+    let (root, replace_map) = dml_selection_root(input)?;
+    let Some((qualifier, scan)) = dml_target_scan(root) else {
+        return Ok(None);
+    };
+
+    let output_exprs = dml_output_exprs(input, &replace_map);
+    let columns = root
+        .schema()
+        .columns()
+        .into_iter()
+        .filter(|column| column.relation.as_ref() == Some(&qualifier))
+        .collect::<Vec<_>>();
+    let is_target_column = |column: &Column| {
+        column.relation.as_ref() == Some(&qualifier)
+            && columns.iter().any(|c| c.name == column.name)
+    };
+    // The new values of an `UPDATE ... FROM` may refer to the other relations
+    let refers_to_target_only = output_exprs
+        .iter()
+        .all(|expr| expr.column_refs().into_iter().all(is_target_column));
+    let identifies_rows = scan
+        .filters
+        .iter()
+        .flat_map(|filter| filter.column_refs())
+        .all(|column| columns.iter().any(|c| c.name == column.name));
+    if !refers_to_target_only || !identifies_rows {
+        return Ok(None);
+    }
+
+    let selection = LogicalPlanBuilder::from(root.clone())
+        .project(columns.into_iter().map(|column| {
+            let name = column.name.clone();
+            Expr::Column(column).alias(name)
+        }))?
+        .build()?;
+    Ok(Some((selection, unnormalize_cols(output_exprs))))
+}
+
+/// Returns the expressions computing each of the columns of `input`, the
+/// input of a `DELETE` or `UPDATE` statement, given `replace_map` mapping the
+/// flat name of its columns to their defining expression.
+fn dml_output_exprs(
+    input: &LogicalPlan,
+    replace_map: &HashMap<String, Expr>,
+) -> Vec<Expr> {
+    input
+        .schema()
+        .columns()
+        .into_iter()
+        .map(|column| {
+            replace_map
+                .get(&column.flat_name())
+                .cloned()
+                .unwrap_or(Expr::Column(column))
+        })
+        .collect()
+}
+
+/// Returns the plan below the projections and subquery aliases on top of
+/// `plan`, together with a map from the flat name of each column of `plan`
+/// to its defining expression over the columns of that plan.
+fn dml_selection_root(
+    plan: &LogicalPlan,
+) -> Result<(&LogicalPlan, HashMap<String, Expr>)> {
+    match plan {
+        LogicalPlan::Projection(Projection { input, .. })
+        | LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => {
+            let (root, input_map) = dml_selection_root(input)?;
+            Ok((root, dml_replace_map(plan, &input_map)?))
+        }
+        _ => Ok((plan, HashMap::new())),
+    }
+}
+
+/// Returns the scan of the target table of a `DELETE` or `UPDATE` statement
+/// in `plan`, the leftmost relation of its joins, together with the
+/// qualifier of its columns in `plan`.
+fn dml_target_scan(plan: &LogicalPlan) -> Option<(TableReference, &TableScan)> {
+    match plan {
+        LogicalPlan::TableScan(scan) => Some((scan.table_name.clone(), scan)),
+        LogicalPlan::SubqueryAlias(alias) => {
+            dml_target_scan(&alias.input).map(|(_, scan)| (alias.alias.clone(), scan))
+        }
+        LogicalPlan::Join(join) => dml_target_scan(&join.left),
+        LogicalPlan::Filter(Filter { input, .. })
+        | LogicalPlan::Projection(Projection { input, .. }) => dml_target_scan(input),
+        _ => None,
+    }
+}
+
+/// Returns the predicates found in `plan` together with a map from the
+/// flat name of each column it produces to its defining expression, when
+/// the column is not a plain column of the target table.
+fn extract_dml_input_inner(
+    plan: &LogicalPlan,
+) -> Result<(Vec<Expr>, HashMap<String, Expr>)> {
+    match plan {
+        LogicalPlan::TableScan(scan) => Ok((scan.filters.clone(), HashMap::new())),
+        LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            ..
+        }) => Ok((vec![lit(false)], HashMap::new())),
+        LogicalPlan::Filter(filter) => {
+            let (mut filters, replace_map) = extract_dml_input_inner(&filter.input)?;
+            for predicate in split_conjunction(&filter.predicate) {
+                filters.push(replace_cols_by_name(predicate.clone(), &replace_map)?);
+            }
+            Ok((filters, replace_map))
+        }
+        LogicalPlan::Projection(Projection { input, .. })
+        | LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => {
+            let (filters, input_map) = extract_dml_input_inner(input)?;
+            Ok((filters, dml_replace_map(plan, &input_map)?))
+        }
+        _ => not_impl_err!(
+            "Unsupported input plan for DELETE or UPDATE: {}",
+            plan.display()
+        ),
+    }
+}
+
+/// Returns a map from the flat name of each column produced by `plan`, a
+/// projection or subquery alias, to its defining expression, given
+/// `input_map` the corresponding map of its input.
+fn dml_replace_map(
+    plan: &LogicalPlan,
+    input_map: &HashMap<String, Expr>,
+) -> Result<HashMap<String, Expr>> {
+    match plan {
+        LogicalPlan::Projection(projection) => projection
+            .schema
+            .columns()
+            .into_iter()
+            .zip(&projection.expr)
+            .map(|(column, expr)| {
+                let expr = replace_cols_by_name(expr.clone().unalias(), input_map)?;
+                Ok((column.flat_name(), expr))
+            })
+            .collect(),
+        LogicalPlan::SubqueryAlias(alias) => Ok(alias
+            .schema
+            .columns()
+            .into_iter()
+            .zip(alias.input.schema().columns())
+            .map(|(column, input_column)| {
+                let expr = input_map
+                    .get(&input_column.flat_name())
+                    .cloned()
+                    .unwrap_or(Expr::Column(input_column));
+                (column.flat_name(), expr)
+            })
+            .collect()),
+        _ => internal_err!("Unexpected plan for DELETE or UPDATE: {}", plan.display()),
+    }
+}
+
+/// Wraps `input`, the rows inserted into `provider`, in a
+/// [`ConstraintValidationExec`] validating the rows against the constraints
+/// of the table, if there are any to validate.
+///
+/// Non-nullable columns are validated unless `input` can not produce `NULL`s
+/// for them. Primary and unique keys are only validated when enabled with
+/// `datafusion.execution.enforce_unique_constraints`, and not for `REPLACE`,
+/// which replaces the rows with the same key instead.
+async fn validate_inserted_rows(
+    session_state: &SessionState,
+    table_name: &TableReference,
+    table_schema: &DFSchema,
+    provider: &Arc<dyn TableProvider>,
+    input: Arc<dyn ExecutionPlan>,
+    insert_op: InsertOp,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let input_schema = input.schema();
+    let mut constraints = provider
+        .schema()
+        .fields()
+        .iter()
+        .zip(input_schema.fields())
+        .enumerate()
+        .filter(|(_, (field, input_field))| {
+            !field.is_nullable() && input_field.is_nullable()
+        })
+        .map(|(i, _)| ValidatedConstraint::NotNull(i))
+        .collect::<Vec<_>>();
+
+    let enforce_unique = insert_op != InsertOp::Replace
+        && session_state
+            .config_options()
+            .execution
+            .enforce_unique_constraints;
+    for constraint in provider.constraints().iter().flat_map(|c| c.iter()) {
+        match constraint {
+            Constraint::Check { name, expr: sql } => {
+                let expr = session_state.create_logical_expr(sql, table_schema)?;
+                constraints.push(ValidatedConstraint::Check {
+                    name: name.clone(),
+                    sql: sql.clone(),
+                    expr: session_state.create_physical_expr(expr, table_schema)?,
+                });
+            }
+            Constraint::PrimaryKey(columns) if enforce_unique => {
+                constraints.push(ValidatedConstraint::Unique {
+                    columns: columns.clone(),
+                    primary_key: true,
+                })
+            }
+            Constraint::Unique(columns) if enforce_unique => {
+                constraints.push(ValidatedConstraint::Unique {
+                    columns: columns.clone(),
+                    primary_key: false,
+                })
+            }
+            Constraint::PrimaryKey(_) | Constraint::Unique(_) => {}
+        }
+    }
+    if constraints.is_empty() {
+        return Ok(input);
+    }
+
+    // `INSERT OVERWRITE` removes the existing rows, so only the keys of the
+    // inserted rows have to be unique
+    let validates_keys = constraints
+        .iter()
+        .any(|constraint| matches!(constraint, ValidatedConstraint::Unique { .. }));
+    let existing = if validates_keys && insert_op == InsertOp::Append {
+        Some(provider.scan(session_state, None, &[], None).await?)
+    } else {
+        None
+    };
+    Ok(Arc::new(ConstraintValidationExec::new(
+        input,
+        existing,
+        table_name.to_string(),
+        constraints,
+    )))
+}
+
+/// Pushes a `SYSTEM` sample into the [`DataSourceExec`] below `plan`,
+/// returning `None` if there is none or it does not support sampling.
+///
+/// Selecting whole blocks of rows does not depend on their values, so the
+/// sample can be taken below projections and filters.
+fn push_down_system_sample(
+    plan: &Arc<dyn ExecutionPlan>,
+    sample: SystemSample,
+) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+    let any = plan.as_any();
+    if let Some(exec) = any.downcast_ref::<DataSourceExec>() {
+        return Ok(exec
+            .with_sample(sample)
+            .map(|exec| Arc::new(exec) as Arc<dyn ExecutionPlan>));
+    }
+    if !any.is::<ProjectionExec>() && !any.is::<FilterExec>() {
+        return Ok(None);
+    }
+    let input = Arc::clone(plan.children()[0]);
+    push_down_system_sample(&input, sample)?
+        .map(|input| Arc::clone(plan).with_new_children(vec![input]))
+        .transpose()
 }
 
 fn tuple_err<T, R>(value: (Result<T>, Result<R>)) -> Result<(T, R)> {
@@ -3257,6 +3257,131 @@ async fn right_anti_filter_push_down() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn pivot() -> Result<()> {
+    let ctx = SessionContext::new();
+    let df = ctx.read_batch(record_batch!(
+        ("sensor", Utf8, ["s1", "s1", "s1", "s2", "s2"]),
+        (
+            "metric",
+            Utf8,
+            ["temp", "temp", "humidity", "temp", "pressure"]
+        ),
+        ("value", Int32, [10, 20, 50, 15, 1000])
+    )?)?;
+
+    let results = df
+        .pivot(
+            vec![sum(col("value")), count(lit(1)).alias("n")],
+            col("metric"),
+            vec![lit("temp"), lit("humidity").alias("hum")],
+        )?
+        .collect()
+        .await?;
+
+    assert_batches_sorted_eq!(
+        [
+            "+--------+-----------------+--------+----------------+-------+",
+            "| sensor | temp_sum(value) | temp_n | hum_sum(value) | hum_n |",
+            "+--------+-----------------+--------+----------------+-------+",
+            "| s1     | 30              | 2      | 50             | 1     |",
+            "| s2     | 15              | 1      |                | 0     |",
+            "+--------+-----------------+--------+----------------+-------+",
+        ],
+        &results
+    );
+
+    let err = ctx
+        .read_batch(record_batch!(("a", Int32, [1]))?)?
+        .pivot(vec![col("a")], col("a"), vec![lit(1)])
+        .unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "Error during planning: PIVOT expects an aggregate function, got ?table?.a"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn unpivot() -> Result<()> {
+    let ctx = SessionContext::new();
+    let df = ctx.read_batch(record_batch!(
+        ("sensor", Utf8, ["s1", "s2"]),
+        ("temp", Int32, [Some(10), None]),
+        ("humidity", Int32, [Some(50), Some(40)])
+    )?)?;
+
+    let results = df
+        .unpivot("value", "metric", &["temp", "humidity"])?
+        .collect()
+        .await?;
+
+    assert_batches_sorted_eq!(
+        [
+            "+--------+----------+-------+",
+            "| sensor | metric   | value |",
+            "+--------+----------+-------+",
+            "| s1     | humidity | 50    |",
+            "| s1     | temp     | 10    |",
+            "| s2     | humidity | 40    |",
+            "+--------+----------+-------+",
+        ],
+        &results
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn union_by_name() -> Result<()> {
+    let ctx = SessionContext::new();
+    let left = ctx.read_batch(record_batch!(
+        ("id", Int32, [1, 2]),
+        ("name", Utf8, ["a", "b"])
+    )?)?;
+    let right = ctx.read_batch(record_batch!(
+        ("score", Float64, [0.5]),
+        ("id", Int64, [3])
+    )?)?;
+
+    let df = left.clone().union_by_name(right.clone())?;
+    assert_eq!(
+        df.schema().field_names(),
+        ["?table?.id", "?table?.name", "score"]
+    );
+    let results = df.collect().await?;
+    assert_batches_sorted_eq!(
+        [
+            "+----+------+-------+",
+            "| id | name | score |",
+            "+----+------+-------+",
+            "| 1  | a    |       |",
+            "| 2  | b    |       |",
+            "| 3  |      | 0.5   |",
+            "+----+------+-------+",
+        ],
+        &results
+    );
+    assert_eq!(results[0].schema().field(0).data_type(), &DataType::Int64);
+
+    let results = left
+        .clone()
+        .union_by_name_distinct(left.select_columns(&["name", "id"])?)?
+        .collect()
+        .await?;
+    assert_batches_sorted_eq!(
+        [
+            "+----+------+",
+            "| id | name |",
+            "+----+------+",
+            "| 1  | a    |",
+            "| 2  | b    |",
+            "+----+------+",
+        ],
+        &results
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn unnest_columns() -> Result<()> {
     const NUM_ROWS: usize = 4;
@@ -3480,6 +3605,29 @@ async fn unnest_column_nulls() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn unnest_column_with_ordinality() -> Result<()> {
+    let df = table_with_lists_and_nulls().await?;
+    let options = UnnestOptions::new().with_ordinality_column("n");
+    let results = df
+        .unnest_columns_with_options(&["list"], options)?
+        .collect()
+        .await?;
+    let expected = [
+        "+------+----+---+",
+        "| list | id | n |",
+        "+------+----+---+",
+        "| 1    | A  | 1 |",
+        "| 2    | A  | 2 |",
+        "|      | B  | 1 |",
+        "| 3    | D  | 1 |",
+        "+------+----+---+",
+    ];
+    assert_batches_eq!(expected, &results);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn unnest_fixed_list() -> Result<()> {
     let batch = get_fixed_list_batch()?;
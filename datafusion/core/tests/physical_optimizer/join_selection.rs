@@ -465,6 +465,57 @@ async fn test_join_no_swap() {
     );
 }
 
+#[tokio::test]
+async fn test_null_aware_join_collects_smaller_side() {
+    let (big, small) = create_big_and_small();
+    let bigger: Arc<dyn ExecutionPlan> = Arc::new(StatisticsExec::new(
+        bigger_statistics(),
+        Schema::new(vec![Field::new("bigger_col", DataType::Int32, true)]),
+    ));
+
+    // null-aware joins can't be partitioned, so the smaller side is collected
+    // even if it is above the thresholds
+    for (left, right, expected_left) in [
+        (Arc::clone(&small), Arc::clone(&big), "small_col"),
+        (Arc::clone(&big), Arc::clone(&small), "small_col"),
+        (Arc::clone(&bigger), Arc::clone(&big), "big_col"),
+    ] {
+        let join = Arc::new(
+            HashJoinExec::try_new(
+                Arc::clone(&left),
+                Arc::clone(&right),
+                vec![(
+                    col(left.schema().field(0).name(), &left.schema()).unwrap(),
+                    col(right.schema().field(0).name(), &right.schema()).unwrap(),
+                )],
+                None,
+                &JoinType::RightAnti,
+                None,
+                PartitionMode::Auto,
+                false,
+            )
+            .unwrap()
+            .with_null_aware(true)
+            .unwrap(),
+        );
+
+        let optimized_join = JoinSelection::new()
+            .optimize(join, &ConfigOptions::new())
+            .unwrap();
+
+        let optimized_join = optimized_join
+            .as_any()
+            .downcast_ref::<HashJoinExec>()
+            .expect("The type of the plan should not be changed");
+        assert_eq!(*optimized_join.partition_mode(), PartitionMode::CollectLeft);
+        assert!(optimized_join.null_aware());
+        assert_eq!(
+            optimized_join.left().schema().field(0).name(),
+            expected_left
+        );
+    }
+}
+
 #[rstest(
     join_type,
     case::inner(JoinType::Inner),
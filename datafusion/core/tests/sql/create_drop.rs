@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use datafusion::datasource::file_format::csv::CsvFormat;
+use datafusion::datasource::listing::{ListingOptions, ListingTableUrl};
 use datafusion::execution::session_state::SessionStateBuilder;
 use datafusion::test_util::TestTableFactory;
 
@@ -66,3 +68,78 @@ async fn create_external_table_with_ddl() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn show_create_listing_table_without_definition() -> Result<()> {
+    let config = SessionConfig::new().with_information_schema(true);
+    let ctx = SessionContext::new_with_config(config);
+
+    let dir = TempDir::new()?;
+    let location = ListingTableUrl::parse(format!("{}/", dir.path().display()))?;
+    let format = CsvFormat::default()
+        .with_has_header(true)
+        .with_delimiter(b'|');
+    let options = ListingOptions::new(Arc::new(format))
+        .with_file_extension(".csv")
+        .with_table_partition_cols(vec![("c3".to_string(), DataType::Utf8)])
+        .with_file_sort_order(vec![vec![col("c1").sort(true, false)]]);
+    let schema = Schema::new(vec![
+        Field::new("c1", DataType::Int64, false),
+        Field::new("c2", DataType::Utf8, true),
+    ]);
+    ctx.register_listing_table(
+        "t",
+        location.as_str(),
+        options,
+        Some(Arc::new(schema)),
+        None,
+    )
+    .await?;
+
+    let results = ctx.sql("SHOW CREATE TABLE t").await?.collect().await?;
+    let definition = results[0]
+        .column_by_name("definition")
+        .unwrap()
+        .as_string::<i32>()
+        .value(0);
+    assert_eq!(
+        definition,
+        format!(
+            "CREATE EXTERNAL TABLE \"public\".t (c1 BIGINT NOT NULL, c2 VARCHAR, c3 VARCHAR NOT NULL) \
+            STORED AS CSV PARTITIONED BY (c3) WITH ORDER (c1 ASC NULLS LAST) \
+            LOCATION '{location}' OPTIONS ('format.delimiter' '124', 'format.has_header' 'true')"
+        )
+    );
+
+    // the generated DDL creates an equivalent table
+    ctx.sql(&definition.replace("\"public\".t", "t2")).await?;
+    let t2 = ctx.table_provider("t2").await?;
+    assert_eq!(t2.schema(), ctx.table_provider("t").await?.schema());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn show_create_view_without_definition() -> Result<()> {
+    let config = SessionConfig::new().with_information_schema(true);
+    let ctx = SessionContext::new_with_config(config);
+
+    ctx.sql("CREATE TABLE t (c1 INT, c2 VARCHAR)").await?;
+    let view = ctx
+        .sql("SELECT c1 FROM t WHERE c2 = 'a'")
+        .await?
+        .into_view();
+    ctx.register_table("v", view)?;
+
+    let results = ctx.sql("SHOW CREATE TABLE v").await?.collect().await?;
+    let expected = [
+        "+---------------+--------------+------------+-----------------------------------------------------------------+",
+        "| table_catalog | table_schema | table_name | definition                                                      |",
+        "+---------------+--------------+------------+-----------------------------------------------------------------+",
+        "| datafusion    | public       | v          | CREATE VIEW \"public\".v AS SELECT t.c1 FROM t WHERE (t.c2 = 'a') |",
+        "+---------------+--------------+------------+-----------------------------------------------------------------+",
+    ];
+    assert_batches_eq!(expected, &results);
+
+    Ok(())
+}
@@ -69,7 +69,9 @@ async fn plan_and_collect(sql: &str) -> Result<Vec<RecordBatch>> {
     let config =
         SessionConfig::new().set_str("datafusion.sql_parser.dialect", "postgres");
     let mut ctx = SessionContext::new_with_config(config);
-    ctx.register_expr_planner(Arc::new(MyCustomPlanner))?;
+    // the custom planner is tried before the default ones, which plan `->`
+    // and `->>` as JSON operators
+    ctx.register_expr_planner_first(Arc::new(MyCustomPlanner))?;
     ctx.sql(sql).await?.collect().await
 }
 
@@ -122,3 +124,27 @@ async fn test_question_filter() {
     let expected = ["+---+", "| a |", "+---+", "| 1 |", "+---+"];
     assert_batches_eq!(&expected, &actual);
 }
+
+#[tokio::test]
+async fn test_registered_planner_after_default_planners() -> Result<()> {
+    let config =
+        SessionConfig::new().set_str("datafusion.sql_parser.dialect", "postgres");
+    let mut ctx = SessionContext::new_with_config(config);
+    // planners registered with `register_expr_planner` are tried last, so
+    // the default planners still plan `->>` as a JSON operator
+    ctx.register_expr_planner(Arc::new(MyCustomPlanner))?;
+    let actual = ctx
+        .sql(r#"select '{"a": "b"}'->>'a' as v, 1 ? 2 as q;"#)
+        .await?
+        .collect()
+        .await?;
+    let expected = [
+        "+---+------+",
+        "| v | q    |",
+        "+---+------+",
+        "| b | true |",
+        "+---+------+",
+    ];
+    assert_batches_eq!(&expected, &actual);
+    Ok(())
+}
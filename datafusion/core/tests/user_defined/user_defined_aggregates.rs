@@ -284,6 +284,53 @@ async fn deregister_udaf() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn schema_qualified_udaf() -> Result<()> {
+    let ctx = SessionContext::new();
+    let arr = Float64Array::from(vec![1.0, 2.0, 6.0]);
+    let batch = RecordBatch::try_from_iter(vec![("x", Arc::new(arr) as _)])?;
+    ctx.register_batch("t", batch)?;
+    ctx.sql("CREATE SCHEMA team_a").await?.collect().await?;
+
+    let my_avg = create_udaf(
+        "my_avg",
+        vec![DataType::Float64],
+        Arc::new(DataType::Float64),
+        Volatility::Immutable,
+        Arc::new(|_| Ok(Box::<AvgAccumulator>::default())),
+        Arc::new(vec![DataType::UInt64, DataType::Float64]),
+    );
+    let schema = ctx.catalog("datafusion").unwrap().schema("team_a").unwrap();
+    schema.register_udaf(Arc::new(my_avg))?;
+
+    // the function is not in the search path
+    let err = ctx.sql("SELECT my_avg(x) FROM t").await.unwrap_err();
+    assert_contains!(err.to_string(), "Invalid function 'my_avg'");
+
+    let expected = [
+        "+-------------------------------+",
+        "| datafusion.team_a.my_avg(t.x) |",
+        "+-------------------------------+",
+        "| 3.0                           |",
+        "+-------------------------------+",
+    ];
+    let result = execute(&ctx, "SELECT team_a.my_avg(x) FROM t").await?;
+    assert_batches_eq!(expected, &result);
+
+    ctx.sql("SET datafusion.catalog.search_path = 'team_a'")
+        .await?
+        .collect()
+        .await?;
+    let result = execute(&ctx, "SELECT my_avg(x) FROM t").await?;
+    assert_batches_eq!(expected, &result);
+
+    assert!(schema.deregister_udaf("my_avg")?.is_some());
+    let err = ctx.sql("SELECT team_a.my_avg(x) FROM t").await.unwrap_err();
+    assert_contains!(err.to_string(), "Invalid function 'team_a.my_avg'");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn case_sensitive_identifiers_user_defined_aggregates() -> Result<()> {
     let ctx = SessionContext::new();
@@ -24,7 +24,8 @@ use arrow::compute::kernels::numeric::add;
 use arrow_array::builder::BooleanBuilder;
 use arrow_array::cast::AsArray;
 use arrow_array::{
-    Array, ArrayRef, Float32Array, Float64Array, Int32Array, RecordBatch, StringArray,
+    types::Int64Type, Array, ArrayRef, Float32Array, Float64Array, Int32Array,
+    Int64Array, RecordBatch, StringArray,
 };
 use arrow_schema::{DataType, Field, Schema};
 use datafusion::execution::context::{FunctionFactory, RegisterFunction, SessionState};
@@ -39,8 +40,8 @@ use datafusion_common::{
 use datafusion_expr::simplify::{ExprSimplifyResult, SimplifyInfo};
 use datafusion_expr::{
     Accumulator, ColumnarValue, CreateFunction, CreateFunctionBody, LogicalPlanBuilder,
-    OperateFunctionArg, ReturnInfo, ReturnTypeArgs, ScalarUDF, ScalarUDFImpl, Signature,
-    Volatility,
+    OperateFunctionArg, ReturnInfo, ReturnTypeArgs, ScalarFunctionImplementation,
+    ScalarUDF, ScalarUDFImpl, Signature, Volatility,
 };
 use datafusion_functions_nested::range::range_udf;
 use parking_lot::Mutex;
@@ -785,6 +786,64 @@ async fn deregister_udf() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn schema_qualified_udfs_with_the_same_name() -> Result<()> {
+    let ctx = SessionContext::new();
+    let batch = RecordBatch::try_from_iter([(
+        "x",
+        Arc::new(Int64Array::from(vec![1])) as ArrayRef,
+    )])?;
+    ctx.register_batch("t", batch)?;
+
+    let add = |n: i64| -> ScalarFunctionImplementation {
+        Arc::new(move |args: &[ColumnarValue]| {
+            let args = ColumnarValue::values_to_arrays(args)?;
+            let result: Int64Array = args[0]
+                .as_primitive::<Int64Type>()
+                .iter()
+                .map(|v| v.map(|v| v + n))
+                .collect();
+            Ok(ColumnarValue::Array(Arc::new(result)))
+        })
+    };
+    for (schema, n) in [("team_a", 1), ("team_b", 10)] {
+        ctx.sql(&format!("CREATE SCHEMA {schema}")).await?;
+        let parse_id = create_udf(
+            "parse_id",
+            vec![DataType::Int64],
+            DataType::Int64,
+            Volatility::Immutable,
+            add(n),
+        );
+        let schema = ctx.catalog("datafusion").unwrap().schema(schema).unwrap();
+        schema.register_udf(Arc::new(parse_id))?;
+    }
+
+    // the functions are told apart by their qualified name, and so not
+    // deduplicated nor eliminated as common subexpressions
+    let result = plan_and_collect(
+        &ctx,
+        "SELECT team_a.parse_id(x), team_b.parse_id(x), \
+        team_a.parse_id(x) + team_b.parse_id(x) AS total FROM t",
+    )
+    .await?;
+    let expected = [
+        "+---------------------------------+---------------------------------+-------+",
+        "| datafusion.team_a.parse_id(t.x) | datafusion.team_b.parse_id(t.x) | total |",
+        "+---------------------------------+---------------------------------+-------+",
+        "| 2                               | 11                              | 13    |",
+        "+---------------------------------+---------------------------------+-------+",
+    ];
+    assert_batches_eq!(expected, &result);
+
+    // the qualified name resolves the function, e.g. in serialized plans
+    let udf = ctx.udf("datafusion.team_b.parse_id")?;
+    assert_eq!(udf.name(), "datafusion.team_b.parse_id");
+    assert!(ctx.udf("datafusion.team_c.parse_id").is_err());
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct TakeUDF {
     signature: Signature,
@@ -1033,7 +1092,7 @@ impl TryFrom<CreateFunction> for ScalarFunctionWrapper {
 
     fn try_from(definition: CreateFunction) -> std::result::Result<Self, Self::Error> {
         Ok(Self {
-            name: definition.name,
+            name: definition.name.table().to_string(),
             expr: definition
                 .params
                 .function_body
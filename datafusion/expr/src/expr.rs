@@ -304,6 +304,8 @@ pub enum Expr {
     Exists(Exists),
     /// IN subquery
     InSubquery(InSubquery),
+    /// Comparison with the values of a subquery, such as `x > ANY (subquery)`
+    SetComparison(SetComparison),
     /// Scalar subquery
     ScalarSubquery(Subquery),
     /// Represents a reference to all available fields in a specific schema,
@@ -932,6 +934,60 @@ impl InSubquery {
     }
 }
 
+/// Quantifier of a [`SetComparison`]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub enum SetQuantifier {
+    /// The comparison must hold for at least one value of the subquery
+    Any,
+    /// The comparison must hold for every value of the subquery
+    All,
+}
+
+impl Display for SetQuantifier {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SetQuantifier::Any => write!(f, "ANY"),
+            SetQuantifier::All => write!(f, "ALL"),
+        }
+    }
+}
+
+/// Comparison of an expression with the values of a subquery, such as
+/// `x > ANY (SELECT ...)` or `x <> ALL (SELECT ...)`
+///
+/// The result follows SQL three-valued logic: `ANY` is true if the comparison
+/// is true for some value, otherwise NULL if it is NULL for some value and
+/// false if not. `ALL` is false if the comparison is false for some value,
+/// otherwise NULL if it is NULL for some value and true if not.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub struct SetComparison {
+    /// The expression to compare
+    pub expr: Box<Expr>,
+    /// Subquery that will produce a single column of data to compare against
+    pub subquery: Subquery,
+    /// The comparison operator
+    pub op: Operator,
+    /// Whether the comparison must hold for any or all of the values
+    pub quantifier: SetQuantifier,
+}
+
+impl SetComparison {
+    /// Create a new SetComparison expression
+    pub fn new(
+        expr: Box<Expr>,
+        subquery: Subquery,
+        op: Operator,
+        quantifier: SetQuantifier,
+    ) -> Self {
+        Self {
+            expr,
+            subquery,
+            op,
+            quantifier,
+        }
+    }
+}
+
 /// Placeholder, representing bind parameter values such as `$1` or `$name`.
 ///
 /// The type of these parameters is inferred using [`Expr::infer_placeholder_types`]
@@ -1143,6 +1199,7 @@ impl Expr {
             Expr::GroupingSet(..) => "GroupingSet",
             Expr::InList { .. } => "InList",
             Expr::InSubquery(..) => "InSubquery",
+            Expr::SetComparison(..) => "SetComparison",
             Expr::IsNotNull(..) => "IsNotNull",
             Expr::IsNull(..) => "IsNull",
             Expr::Like { .. } => "Like",
@@ -1328,7 +1385,10 @@ impl Expr {
                 // f_down: skip subqueries.  Check in f_down to avoid recursing into them
                 let recursion = if matches!(
                     expr,
-                    Expr::Exists { .. } | Expr::ScalarSubquery(_) | Expr::InSubquery(_)
+                    Expr::Exists { .. }
+                        | Expr::ScalarSubquery(_)
+                        | Expr::InSubquery(_)
+                        | Expr::SetComparison(_)
                 ) {
                     // Subqueries could contain aliases so don't recurse into those
                     TreeNodeRecursion::Jump
@@ -1644,6 +1704,7 @@ impl Expr {
             | Expr::GroupingSet(..)
             | Expr::InList(..)
             | Expr::InSubquery(..)
+            | Expr::SetComparison(..)
             | Expr::IsFalse(..)
             | Expr::IsNotFalse(..)
             | Expr::IsNotNull(..)
@@ -1975,6 +2036,25 @@ impl NormalizeEq for Expr {
                     && self_expr.normalize_eq(other_expr)
                     && self_subquery.normalize_eq(other_subquery)
             }
+            (
+                Expr::SetComparison(SetComparison {
+                    expr: self_expr,
+                    subquery: self_subquery,
+                    op: self_op,
+                    quantifier: self_quantifier,
+                }),
+                Expr::SetComparison(SetComparison {
+                    expr: other_expr,
+                    subquery: other_subquery,
+                    op: other_op,
+                    quantifier: other_quantifier,
+                }),
+            ) => {
+                self_op == other_op
+                    && self_quantifier == other_quantifier
+                    && self_expr.normalize_eq(other_expr)
+                    && self_subquery.normalize_eq(other_subquery)
+            }
             (
                 Expr::ScalarSubquery(self_subquery),
                 Expr::ScalarSubquery(other_subquery),
@@ -2195,6 +2275,16 @@ impl HashNode for Expr {
                 subquery.hash(state);
                 negated.hash(state);
             }
+            Expr::SetComparison(SetComparison {
+                expr: _expr,
+                subquery,
+                op,
+                quantifier,
+            }) => {
+                subquery.hash(state);
+                op.hash(state);
+                quantifier.hash(state);
+            }
             Expr::ScalarSubquery(subquery) => {
                 subquery.hash(state);
             }
@@ -2400,6 +2490,12 @@ impl Display for SchemaDisplay<'_> {
                 write!(f, "NOT IN")
             }
             Expr::InSubquery(InSubquery { negated: false, .. }) => write!(f, "IN"),
+            Expr::SetComparison(SetComparison {
+                expr,
+                op,
+                quantifier,
+                ..
+            }) => write!(f, "{} {op} {quantifier}", SchemaDisplay(expr)),
             Expr::IsTrue(expr) => write!(f, "{} IS TRUE", SchemaDisplay(expr)),
             Expr::IsFalse(expr) => write!(f, "{} IS FALSE", SchemaDisplay(expr)),
             Expr::IsNotTrue(expr) => {
@@ -2617,6 +2713,12 @@ impl Display for Expr {
                 subquery,
                 negated: false,
             }) => write!(f, "{expr} IN ({subquery:?})"),
+            Expr::SetComparison(SetComparison {
+                expr,
+                subquery,
+                op,
+                quantifier,
+            }) => write!(f, "{expr} {op} {quantifier} ({subquery:?})"),
             Expr::ScalarSubquery(subquery) => write!(f, "({subquery:?})"),
             Expr::BinaryExpr(expr) => write!(f, "{expr}"),
             Expr::ScalarFunction(fun) => {
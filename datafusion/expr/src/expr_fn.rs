@@ -19,7 +19,8 @@
 
 use crate::expr::{
     AggregateFunction, BinaryExpr, Cast, Exists, GroupingSet, InList, InSubquery,
-    Placeholder, TryCast, Unnest, WildcardOptions, WindowFunction,
+    Placeholder, SetComparison, SetQuantifier, TryCast, Unnest, WildcardOptions,
+    WindowFunction,
 };
 use crate::function::{
     AccumulatorArgs, AccumulatorFactoryFunction, PartitionEvaluatorFactory,
@@ -291,6 +292,34 @@ pub fn not_in_subquery(expr: Expr, subquery: Arc<LogicalPlan>) -> Expr {
     ))
 }
 
+/// Create an `expr op ANY (subquery)` expression
+pub fn any_subquery(expr: Expr, op: Operator, subquery: Arc<LogicalPlan>) -> Expr {
+    set_comparison(expr, op, SetQuantifier::Any, subquery)
+}
+
+/// Create an `expr op ALL (subquery)` expression
+pub fn all_subquery(expr: Expr, op: Operator, subquery: Arc<LogicalPlan>) -> Expr {
+    set_comparison(expr, op, SetQuantifier::All, subquery)
+}
+
+fn set_comparison(
+    expr: Expr,
+    op: Operator,
+    quantifier: SetQuantifier,
+    subquery: Arc<LogicalPlan>,
+) -> Expr {
+    let outer_ref_columns = subquery.all_out_ref_exprs();
+    Expr::SetComparison(SetComparison::new(
+        Box::new(expr),
+        Subquery {
+            subquery,
+            outer_ref_columns,
+        },
+        op,
+        quantifier,
+    ))
+}
+
 /// Create a scalar subquery expression
 pub fn scalar_subquery(subquery: Arc<LogicalPlan>) -> Expr {
     let outer_ref_columns = subquery.all_out_ref_exprs();
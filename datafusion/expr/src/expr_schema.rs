@@ -18,7 +18,7 @@
 use super::{Between, Expr, Like};
 use crate::expr::{
     AggregateFunction, Alias, BinaryExpr, Cast, InList, InSubquery, Placeholder,
-    ScalarFunction, TryCast, Unnest, WindowFunction,
+    ScalarFunction, SetComparison, TryCast, Unnest, WindowFunction,
 };
 use crate::type_coercion::functions::{
     data_types_with_aggregate_udf, data_types_with_scalar_udf, data_types_with_window_udf,
@@ -135,13 +135,15 @@ impl ExprSchemable for Expr {
                     DataType::List(field)
                     | DataType::LargeList(field)
                     | DataType::FixedSizeList(field, _) => Ok(field.data_type().clone()),
+                    // Maps are unnested into their key-value entries
+                    DataType::Map(field, _) => Ok(field.data_type().clone()),
                     DataType::Struct(_) => Ok(arg_data_type),
                     DataType::Null => {
                         not_impl_err!("unnest() does not support null yet")
                     }
                     _ => {
                         plan_err!(
-                            "unnest() can only be applied to array, struct and null"
+                            "unnest() can only be applied to array, map, struct and null"
                         )
                     }
                 }
@@ -179,6 +181,7 @@ impl ExprSchemable for Expr {
             | Expr::IsNull(_)
             | Expr::Exists { .. }
             | Expr::InSubquery(_)
+            | Expr::SetComparison(_)
             | Expr::Between { .. }
             | Expr::InList { .. }
             | Expr::IsNotNull(_)
@@ -313,6 +316,9 @@ impl ExprSchemable for Expr {
             | Expr::IsNotUnknown(_)
             | Expr::Exists { .. } => Ok(false),
             Expr::InSubquery(InSubquery { expr, .. }) => expr.nullable(input_schema),
+            Expr::SetComparison(SetComparison { expr, subquery, .. }) => Ok(expr
+                .nullable(input_schema)?
+                || subquery.subquery.schema().field(0).is_nullable()),
             Expr::ScalarSubquery(subquery) => {
                 Ok(subquery.subquery.schema().field(0).is_nullable())
             }
@@ -601,6 +607,62 @@ pub fn cast_subquery(subquery: Subquery, cast_to_type: &DataType) -> Result<Subq
     })
 }
 
+/// Cast the columns of a multi-column subquery in InSubquery to the given types.
+///
+/// Like [`cast_subquery`], the expressions of a projection are casted, and any
+/// other plan gets a projection of its casted columns.
+pub fn cast_subquery_columns(
+    subquery: Subquery,
+    cast_to_types: &[DataType],
+) -> Result<Subquery> {
+    let schema = subquery.subquery.schema();
+    if schema
+        .fields()
+        .iter()
+        .zip(cast_to_types)
+        .all(|(field, cast_to_type)| field.data_type() == cast_to_type)
+    {
+        return Ok(subquery);
+    }
+
+    let plan = subquery.subquery.as_ref();
+    let new_plan = match plan {
+        LogicalPlan::Projection(projection) => {
+            let cast_exprs = projection
+                .expr
+                .iter()
+                .zip(cast_to_types)
+                .map(|(expr, cast_to_type)| {
+                    expr.clone()
+                        .cast_to(cast_to_type, projection.input.schema())
+                })
+                .collect::<Result<Vec<_>>>()?;
+            LogicalPlan::Projection(Projection::try_new(
+                cast_exprs,
+                Arc::clone(&projection.input),
+            )?)
+        }
+        _ => {
+            let cast_exprs = schema
+                .columns()
+                .into_iter()
+                .zip(cast_to_types)
+                .map(|(column, cast_to_type)| {
+                    Expr::Column(column).cast_to(cast_to_type, schema)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            LogicalPlan::Projection(Projection::try_new(
+                cast_exprs,
+                Arc::clone(&subquery.subquery),
+            )?)
+        }
+    };
+    Ok(Subquery {
+        subquery: Arc::new(new_plan),
+        outer_ref_columns: subquery.outer_ref_columns,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
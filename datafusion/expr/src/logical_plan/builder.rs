@@ -33,8 +33,8 @@ use crate::expr_rewriter::{
 use crate::logical_plan::{
     Aggregate, Analyze, Distinct, DistinctOn, EmptyRelation, Explain, Filter, Join,
     JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare,
-    Projection, Repartition, Sort, SubqueryAlias, TableScan, Union, Unnest, Values,
-    Window,
+    Projection, Repartition, Sample, SampleMethod, Sort, SubqueryAlias, TableScan, Union,
+    Unnest, Values, Window,
 };
 use crate::utils::{
     can_hash, columnize_expr, compare_sort_expr, expr_to_columns,
@@ -51,14 +51,15 @@ use arrow::compute::can_cast_types;
 use arrow::datatypes::{DataType, Field, Fields, Schema, SchemaRef};
 use datafusion_common::display::ToStringifiedPlan;
 use datafusion_common::file_options::file_type::FileType;
+use datafusion_common::format::ExplainFormat;
 use datafusion_common::{
-    exec_err, get_target_functional_dependencies, internal_err, not_impl_err,
-    plan_datafusion_err, plan_err, Column, DFSchema, DFSchemaRef, DataFusionError,
-    Result, ScalarValue, TableReference, ToDFSchema, UnnestOptions,
+    exec_err, get_target_functional_dependencies, internal_err, plan_datafusion_err,
+    plan_err, Column, DFSchema, DFSchemaRef, DataFusionError, Result, ScalarValue,
+    TableReference, ToDFSchema, UnnestOptions,
 };
 use datafusion_expr_common::type_coercion::binary::type_union_resolution;
 
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
 
 /// Default table name for unnamed table
 pub const UNNAMED_TABLE: &str = "?table?";
@@ -147,11 +148,6 @@ impl LogicalPlanBuilder {
         is_distinct: bool,
     ) -> Result<Self> {
         // TODO: we need to do a bunch of validation here. Maybe more.
-        if is_distinct {
-            return not_impl_err!(
-                "Recursive queries with a distinct 'UNION' (in which the previous iteration's results will be de-duplicated) is not supported"
-            );
-        }
         // Ensure that the static term and the recursive term have the same number of fields
         let static_fields_len = self.plan.schema().fields().len();
         let recursive_fields_len = recursive_term.schema().fields().len();
@@ -530,6 +526,19 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Return a random sample of the rows, selecting each row or block of
+    /// rows with probability `fraction`. See [`Sample`] for details.
+    pub fn sample(
+        self,
+        method: SampleMethod,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Result<Self> {
+        Sample::try_new(self.plan, method, fraction, seed)
+            .map(LogicalPlan::Sample)
+            .map(Self::new)
+    }
+
     /// Apply an alias
     pub fn alias(self, alias: impl Into<TableReference>) -> Result<Self> {
         subquery_alias(Arc::unwrap_or_clone(self.plan), alias).map(Self::new)
@@ -732,6 +741,25 @@ impl LogicalPlanBuilder {
         )))))
     }
 
+    /// Apply a union by name, preserving duplicate rows
+    ///
+    /// See [`union_by_name`] for how the columns of the inputs are matched.
+    pub fn union_by_name(self, plan: LogicalPlan) -> Result<Self> {
+        union_by_name(Arc::unwrap_or_clone(self.plan), plan).map(Self::new)
+    }
+
+    /// Apply a union by name, removing duplicate rows
+    ///
+    /// See [`union_by_name`] for how the columns of the inputs are matched.
+    pub fn union_by_name_distinct(self, plan: LogicalPlan) -> Result<Self> {
+        let left_plan: LogicalPlan = Arc::unwrap_or_clone(self.plan);
+        let right_plan: LogicalPlan = plan;
+
+        Ok(Self::new(LogicalPlan::Distinct(Distinct::All(Arc::new(
+            union_by_name(left_plan, right_plan)?,
+        )))))
+    }
+
     /// Apply deduplication: Only distinct (different) values are returned)
     pub fn distinct(self) -> Result<Self> {
         Ok(Self::new(LogicalPlan::Distinct(Distinct::All(self.plan))))
@@ -970,6 +998,7 @@ impl LogicalPlanBuilder {
             join_constraint: JoinConstraint::On,
             schema: DFSchemaRef::new(join_schema),
             null_equals_null,
+            null_aware: false,
         })))
     }
 
@@ -1034,6 +1063,7 @@ impl LogicalPlanBuilder {
                 join_constraint: JoinConstraint::Using,
                 schema: DFSchemaRef::new(join_schema),
                 null_equals_null: false,
+                null_aware: false,
             })))
         }
     }
@@ -1050,6 +1080,7 @@ impl LogicalPlanBuilder {
             join_type: JoinType::Inner,
             join_constraint: JoinConstraint::On,
             null_equals_null: false,
+            null_aware: false,
             schema: DFSchemaRef::new(join_schema),
         })))
     }
@@ -1100,6 +1131,19 @@ impl LogicalPlanBuilder {
     ///
     /// if `verbose` is true, prints out additional details.
     pub fn explain(self, verbose: bool, analyze: bool) -> Result<Self> {
+        self.explain_with_format(verbose, analyze, ExplainFormat::default())
+    }
+
+    /// Create an expression to represent the explanation of the plan,
+    /// printing the physical plan in the given [`ExplainFormat`]
+    ///
+    /// See [`Self::explain`] for `verbose` and `analyze`.
+    pub fn explain_with_format(
+        self,
+        verbose: bool,
+        analyze: bool,
+        explain_format: ExplainFormat,
+    ) -> Result<Self> {
         let schema = LogicalPlan::explain_schema();
         let schema = schema.to_dfschema_ref()?;
 
@@ -1108,6 +1152,7 @@ impl LogicalPlanBuilder {
                 verbose,
                 input: self.plan,
                 schema,
+                explain_format,
             })))
         } else {
             let stringified_plans =
@@ -1119,6 +1164,7 @@ impl LogicalPlanBuilder {
                 stringified_plans,
                 schema,
                 logical_optimization_succeeded: false,
+                explain_format,
             })))
         }
     }
@@ -1269,6 +1315,7 @@ impl LogicalPlanBuilder {
             join_constraint: JoinConstraint::On,
             schema: DFSchemaRef::new(join_schema),
             null_equals_null: false,
+            null_aware: false,
         })))
     }
 
@@ -1300,6 +1347,152 @@ impl LogicalPlanBuilder {
         unnest_with_options(Arc::unwrap_or_clone(self.plan), columns, options)
             .map(Self::new)
     }
+
+    /// Rotate the distinct `pivot_values` of `pivot_column` into columns.
+    ///
+    /// The input is grouped by every column that is not referenced by
+    /// `aggr_expr` or `pivot_column`, and each aggregate is computed once per
+    /// pivot value, filtered to the rows where `pivot_column` equals that
+    /// value.
+    ///
+    /// The output columns are named after the pivot values (or their alias,
+    /// if any). When there is more than one aggregate, or an aggregate is
+    /// aliased, the names are suffixed with `_<aggregate name>`.
+    pub fn pivot(
+        self,
+        aggr_expr: Vec<Expr>,
+        pivot_column: Expr,
+        pivot_values: Vec<Expr>,
+    ) -> Result<Self> {
+        if aggr_expr.is_empty() {
+            return plan_err!("PIVOT requires at least one aggregate expression");
+        }
+        if pivot_values.is_empty() {
+            return plan_err!("PIVOT requires at least one pivot value");
+        }
+
+        let suffix_names = aggr_expr.len() > 1
+            || aggr_expr.iter().any(|expr| matches!(expr, Expr::Alias(_)));
+        let aggr_expr = aggr_expr
+            .into_iter()
+            .map(|expr| {
+                let (expr, name) = match expr {
+                    Expr::Alias(Alias { expr, name, .. }) => (*expr, name),
+                    expr => {
+                        let name = expr.schema_name().to_string();
+                        (expr, name)
+                    }
+                };
+                match normalize_col(expr, &self.plan)? {
+                    Expr::AggregateFunction(func) => Ok((func, name)),
+                    expr => plan_err!("PIVOT expects an aggregate function, got {expr}"),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let pivot_column = normalize_col(pivot_column, &self.plan)?;
+
+        let mut referenced = pivot_column.column_refs();
+        aggr_expr.iter().for_each(|(func, _)| {
+            func.args
+                .iter()
+                .chain(func.filter.as_deref())
+                .chain(func.order_by.iter().flatten().map(|sort| &sort.expr))
+                .for_each(|expr| referenced.extend(expr.column_refs()))
+        });
+        let group_expr = self
+            .plan
+            .schema()
+            .columns()
+            .into_iter()
+            .filter(|column| !referenced.contains(column))
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+
+        let mut pivot_expr = Vec::with_capacity(pivot_values.len() * aggr_expr.len());
+        for value in pivot_values {
+            let (value, value_name) = match value {
+                Expr::Alias(Alias { expr, name, .. }) => (*expr, name),
+                Expr::Literal(scalar) => {
+                    let name = scalar.to_string();
+                    (Expr::Literal(scalar), name)
+                }
+                value => {
+                    let name = value.schema_name().to_string();
+                    (value, name)
+                }
+            };
+            let predicate = pivot_column.clone().eq(value);
+
+            for (func, aggr_name) in &aggr_expr {
+                let mut func = func.clone();
+                func.filter = Some(Box::new(match func.filter.take() {
+                    Some(filter) => and(*filter, predicate.clone()),
+                    None => predicate.clone(),
+                }));
+                let name = if suffix_names {
+                    format!("{value_name}_{aggr_name}")
+                } else {
+                    value_name.clone()
+                };
+                pivot_expr.push(Expr::AggregateFunction(func).alias(name));
+            }
+        }
+
+        // Wildcards over an aggregate are expanded against its input (see
+        // `find_base_plan`), so end the pivoted relation with a projection
+        let plan = self.aggregate(group_expr, pivot_expr)?;
+        let columns = plan.schema().columns().into_iter().map(Expr::Column);
+        plan.project(columns)
+    }
+
+    /// Rotate `columns` into rows.
+    ///
+    /// Every input row produces one output row per column in `columns` whose
+    /// value is not null. The name of the column is stored in `name_column`
+    /// and its value in `value_column`, next to all columns that are not
+    /// unpivoted.
+    pub fn unpivot(
+        self,
+        value_column: impl Into<String>,
+        name_column: impl Into<String>,
+        columns: Vec<Column>,
+    ) -> Result<Self> {
+        if columns.is_empty() {
+            return plan_err!("UNPIVOT requires at least one column");
+        }
+        let value_column = value_column.into();
+        let name_column = name_column.into();
+
+        let schema = self.plan.schema();
+        let columns = columns
+            .iter()
+            .map(|column| {
+                let (qualifier, field) = schema.qualified_field_from_column(column)?;
+                Ok(Column::from((qualifier, field)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let keep_columns = schema
+            .columns()
+            .into_iter()
+            .filter(|column| !columns.contains(column))
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+
+        let mut builder: Option<Self> = None;
+        for column in columns {
+            let branch = Self::new_from_arc(Arc::clone(&self.plan))
+                .filter(Expr::Column(column.clone()).is_not_null())?
+                .project(keep_columns.iter().cloned().chain([
+                    lit(column.name.clone()).alias(&name_column),
+                    Expr::Column(column).alias(&value_column),
+                ]))?;
+            builder = Some(match builder {
+                Some(builder) => builder.union(branch.build()?)?,
+                None => branch,
+            });
+        }
+        Ok(builder.unwrap())
+    }
 }
 
 impl From<LogicalPlan> for LogicalPlanBuilder {
@@ -1540,6 +1733,56 @@ pub fn union(left_plan: LogicalPlan, right_plan: LogicalPlan) -> Result<LogicalP
     ])?))
 }
 
+/// Build a UNION plan that matches the columns of its inputs by name
+/// instead of by position.
+///
+/// The output has the columns of `left_plan`, followed by the columns that
+/// only appear in `right_plan`. A column that is missing from one of the
+/// inputs is filled with NULLs of the column's type. As with [`union`],
+/// differing types of the same column are coerced to a common type by the
+/// analyzer.
+pub fn union_by_name(
+    left_plan: LogicalPlan,
+    right_plan: LogicalPlan,
+) -> Result<LogicalPlan> {
+    let mut union_fields: IndexMap<&String, &DataType> = IndexMap::new();
+    for plan in [&left_plan, &right_plan] {
+        let mut names = HashSet::new();
+        for field in plan.schema().fields() {
+            if !names.insert(field.name()) {
+                return plan_err!(
+                    "UNION BY NAME requires unique column names, found duplicate column {}",
+                    field.name()
+                );
+            }
+            union_fields
+                .entry(field.name())
+                .or_insert(field.data_type());
+        }
+    }
+
+    let align_input = |plan: &LogicalPlan| -> Result<Vec<Expr>> {
+        union_fields
+            .iter()
+            .map(|(&name, &data_type)| {
+                match plan.schema().iter().find(|(_, field)| field.name() == name) {
+                    Some(qualified_field) => Ok(Expr::Column(qualified_field.into())),
+                    None => {
+                        Ok(Expr::Literal(ScalarValue::try_from(data_type)?).alias(name))
+                    }
+                }
+            })
+            .collect()
+    };
+    let left_exprs = align_input(&left_plan)?;
+    let right_exprs = align_input(&right_plan)?;
+
+    union(
+        project(left_plan, left_exprs)?,
+        project(right_plan, right_exprs)?,
+    )
+}
+
 /// Create Projection
 /// # Errors
 /// This function errors under any of the following conditions:
@@ -1738,6 +1981,10 @@ fn get_unnested_list_datatype_recursive(
             }
             return get_unnested_list_datatype_recursive(field.data_type(), depth - 1);
         }
+        // A map is unnested into its key-value entries
+        DataType::Map(field, _) if depth == 1 => {
+            return Ok(field.data_type().clone());
+        }
         _ => {}
     };
 
@@ -1759,6 +2006,7 @@ pub fn get_struct_unnested_columns(
 // the input columns.
 // For example, given a column with name "a",
 // - List(Element) returns ["a"] with data type Element
+// - Map(Entries) returns ["a"] with data type Entries, a struct of key and value
 // - Struct(field1, field2) returns ["a.field1","a.field2"]
 // For list data type, an argument depth is used to specify
 // the recursion level
@@ -1770,7 +2018,10 @@ pub fn get_unnested_columns(
     let mut qualified_columns = Vec::with_capacity(1);
 
     match data_type {
-        DataType::List(_) | DataType::FixedSizeList(_, _) | DataType::LargeList(_) => {
+        DataType::List(_)
+        | DataType::FixedSizeList(_, _)
+        | DataType::LargeList(_)
+        | DataType::Map(_, _) => {
             let data_type = get_unnested_list_datatype_recursive(data_type, depth)?;
             let new_field = Arc::new(Field::new(
                 col_name, data_type,
@@ -1860,7 +2111,7 @@ pub fn unnest_with_options(
     // 4.unnest_col2_depth_1: int
     // Meaning the placeholder column will be replaced by its unnested variation(s), note
     // the plural.
-    let fields = input_schema
+    let mut fields = input_schema
         .iter()
         .enumerate()
         .map(|(index, (original_qualifier, original_field))| {
@@ -1903,7 +2154,8 @@ pub fn unnest_with_options(
                             }
                             DataType::List(_)
                             | DataType::FixedSizeList(_, _)
-                            | DataType::LargeList(_) => {
+                            | DataType::LargeList(_)
+                            | DataType::Map(_, _) => {
                                 list_columns.push((
                                     index,
                                     ColumnUnnestList {
@@ -1940,6 +2192,15 @@ pub fn unnest_with_options(
         .flatten()
         .collect::<Vec<_>>();
 
+    // The ordinality column does not depend on any input column, so it has
+    // no entry in `dependency_indices`
+    if let Some(ordinality_column) = &options.ordinality_column {
+        fields.push((
+            None,
+            Arc::new(Field::new(ordinality_column, DataType::Int64, false)),
+        ));
+    }
+
     let metadata = input_schema.metadata().clone();
     let df_schema = DFSchema::new_with_metadata(fields, metadata)?;
     // We can use the existing functional dependencies:
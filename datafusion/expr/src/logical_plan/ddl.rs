@@ -41,12 +41,18 @@ pub enum DdlStatement {
     CreateMemoryTable(CreateMemoryTable),
     /// Creates a new view.
     CreateView(CreateView),
+    /// Creates a new materialized view.
+    CreateMaterializedView(CreateMaterializedView),
+    /// Recomputes the contents of a materialized view.
+    RefreshMaterializedView(RefreshMaterializedView),
     /// Creates a new catalog schema.
     CreateCatalogSchema(CreateCatalogSchema),
     /// Creates a new catalog (aka "Database").
     CreateCatalog(CreateCatalog),
     /// Creates a new index.
     CreateIndex(CreateIndex),
+    /// Sets or removes the comment on a table or a column.
+    CommentOn(CommentOn),
     /// Drops a table.
     DropTable(DropTable),
     /// Drops a view.
@@ -67,12 +73,21 @@ impl DdlStatement {
                 schema
             }
             DdlStatement::CreateMemoryTable(CreateMemoryTable { input, .. })
-            | DdlStatement::CreateView(CreateView { input, .. }) => input.schema(),
+            | DdlStatement::CreateView(CreateView { input, .. })
+            | DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                input,
+                ..
+            }) => input.schema(),
+            DdlStatement::RefreshMaterializedView(RefreshMaterializedView {
+                schema,
+                ..
+            }) => schema,
             DdlStatement::CreateCatalogSchema(CreateCatalogSchema { schema, .. }) => {
                 schema
             }
             DdlStatement::CreateCatalog(CreateCatalog { schema, .. }) => schema,
             DdlStatement::CreateIndex(CreateIndex { schema, .. }) => schema,
+            DdlStatement::CommentOn(CommentOn { schema, .. }) => schema,
             DdlStatement::DropTable(DropTable { schema, .. }) => schema,
             DdlStatement::DropView(DropView { schema, .. }) => schema,
             DdlStatement::DropCatalogSchema(DropCatalogSchema { schema, .. }) => schema,
@@ -88,9 +103,12 @@ impl DdlStatement {
             DdlStatement::CreateExternalTable(_) => "CreateExternalTable",
             DdlStatement::CreateMemoryTable(_) => "CreateMemoryTable",
             DdlStatement::CreateView(_) => "CreateView",
+            DdlStatement::CreateMaterializedView(_) => "CreateMaterializedView",
+            DdlStatement::RefreshMaterializedView(_) => "RefreshMaterializedView",
             DdlStatement::CreateCatalogSchema(_) => "CreateCatalogSchema",
             DdlStatement::CreateCatalog(_) => "CreateCatalog",
             DdlStatement::CreateIndex(_) => "CreateIndex",
+            DdlStatement::CommentOn(_) => "CommentOn",
             DdlStatement::DropTable(_) => "DropTable",
             DdlStatement::DropView(_) => "DropView",
             DdlStatement::DropCatalogSchema(_) => "DropCatalogSchema",
@@ -109,7 +127,13 @@ impl DdlStatement {
                 vec![input]
             }
             DdlStatement::CreateView(CreateView { input, .. }) => vec![input],
+            DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                input,
+                ..
+            }) => vec![input],
+            DdlStatement::RefreshMaterializedView(_) => vec![],
             DdlStatement::CreateIndex(_) => vec![],
+            DdlStatement::CommentOn(_) => vec![],
             DdlStatement::DropTable(_) => vec![],
             DdlStatement::DropView(_) => vec![],
             DdlStatement::DropCatalogSchema(_) => vec![],
@@ -153,6 +177,23 @@ impl DdlStatement {
                     DdlStatement::CreateView(CreateView { name, .. }) => {
                         write!(f, "CreateView: {name:?}")
                     }
+                    DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                        name,
+                        location,
+                        ..
+                    }) => match location {
+                        Some(location) => write!(
+                            f,
+                            "CreateMaterializedView: {name:?} location={location}"
+                        ),
+                        None => write!(f, "CreateMaterializedView: {name:?}"),
+                    },
+                    DdlStatement::RefreshMaterializedView(RefreshMaterializedView {
+                        name,
+                        ..
+                    }) => {
+                        write!(f, "RefreshMaterializedView: {name:?}")
+                    }
                     DdlStatement::CreateCatalogSchema(CreateCatalogSchema {
                         schema_name,
                         ..
@@ -167,6 +208,17 @@ impl DdlStatement {
                     DdlStatement::CreateIndex(CreateIndex { name, .. }) => {
                         write!(f, "CreateIndex: {name:?}")
                     }
+                    DdlStatement::CommentOn(CommentOn {
+                        table,
+                        column,
+                        comment,
+                        ..
+                    }) => match column {
+                        Some(column) => {
+                            write!(f, "CommentOn: {table:?} column={column} comment={comment:?}")
+                        }
+                        None => write!(f, "CommentOn: {table:?} comment={comment:?}"),
+                    },
                     DdlStatement::DropTable(DropTable {
                         name, if_exists, ..
                     }) => {
@@ -186,10 +238,10 @@ impl DdlStatement {
                         write!(f, "DropCatalogSchema: {name:?} if not exist:={if_exists} cascade:={cascade}")
                     }
                     DdlStatement::CreateFunction(CreateFunction { name, .. }) => {
-                        write!(f, "CreateFunction: name {name:?}")
+                        write!(f, "CreateFunction: name {name}")
                     }
                     DdlStatement::DropFunction(DropFunction { name, .. }) => {
-                        write!(f, "CreateFunction: name {name:?}")
+                        write!(f, "DropFunction: name {name}")
                     }
                 }
             }
@@ -313,6 +365,8 @@ pub struct CreateMemoryTable {
     pub column_defaults: Vec<(String, Expr)>,
     /// Whether the table is `TableType::Temporary`
     pub temporary: bool,
+    /// Comment on the table, if any
+    pub comment: Option<String>,
 }
 
 /// Creates a view.
@@ -330,6 +384,44 @@ pub struct CreateView {
     pub temporary: bool,
 }
 
+/// Creates a materialized view.
+///
+/// Unlike a [`CreateView`], the result of `input` is computed when the view
+/// is created and stored, and only recomputed by a
+/// [`RefreshMaterializedView`].
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Hash)]
+pub struct CreateMaterializedView {
+    /// The view name
+    pub name: TableReference,
+    /// The logical plan computing the contents of the view
+    pub input: Arc<LogicalPlan>,
+    /// Option to not error if the view already exists
+    pub if_not_exists: bool,
+    /// Option to replace the view if it already exists
+    pub or_replace: bool,
+    /// Location the contents are written to as Parquet files, or `None` to
+    /// keep them in memory
+    pub location: Option<String>,
+    /// SQL used to create the view, if available
+    pub definition: Option<String>,
+}
+
+/// Recomputes the contents of a materialized view.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RefreshMaterializedView {
+    /// The view name
+    pub name: TableReference,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+// Manual implementation needed because of `schema` field. Comparison excludes this field.
+impl PartialOrd for RefreshMaterializedView {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.name.partial_cmp(&other.name)
+    }
+}
+
 /// Creates a catalog (aka "Database").
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateCatalog {
@@ -450,7 +542,8 @@ pub struct CreateFunction {
     //       user to convert them as needed
     pub or_replace: bool,
     pub temporary: bool,
-    pub name: String,
+    /// Name of the function, qualified with the schema it is created in, if any
+    pub name: TableReference,
     pub args: Option<Vec<OperateFunctionArg>>,
     pub return_type: Option<DataType>,
     pub params: CreateFunctionBody,
@@ -465,7 +558,7 @@ impl PartialOrd for CreateFunction {
         struct ComparableCreateFunction<'a> {
             pub or_replace: &'a bool,
             pub temporary: &'a bool,
-            pub name: &'a String,
+            pub name: &'a TableReference,
             pub args: &'a Option<Vec<OperateFunctionArg>>,
             pub return_type: &'a Option<DataType>,
             pub params: &'a CreateFunctionBody,
@@ -555,7 +648,8 @@ impl<'a> TreeNodeContainer<'a, Expr> for CreateFunctionBody {
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct DropFunction {
-    pub name: String,
+    /// Name of the function, qualified with the schema it is dropped from, if any
+    pub name: TableReference,
     pub if_exists: bool,
     pub schema: DFSchemaRef,
 }
@@ -612,6 +706,38 @@ impl PartialOrd for CreateIndex {
     }
 }
 
+/// Metadata key under which the comment on a column is stored in the
+/// metadata of the field of the column.
+pub const COMMENT_METADATA_KEY: &str = "comment";
+
+/// Sets or removes the comment on a table or on one of its columns, as done
+/// by `COMMENT ON TABLE` and `COMMENT ON COLUMN`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CommentOn {
+    /// The table commented on, or holding the column commented on
+    pub table: TableReference,
+    /// The column commented on, if any
+    pub column: Option<String>,
+    /// The new comment, or `None` to remove the comment
+    pub comment: Option<String>,
+    /// Do nothing if the table does not exist
+    pub if_exists: bool,
+    /// Empty schema
+    pub schema: DFSchemaRef,
+}
+
+// Manual implementation needed because of `schema` field. Comparison excludes this field.
+impl PartialOrd for CommentOn {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (&self.table, &self.column, &self.comment, &self.if_exists).partial_cmp(&(
+            &other.table,
+            &other.column,
+            &other.comment,
+            &other.if_exists,
+        ))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{CreateCatalog, DdlStatement, DropView};
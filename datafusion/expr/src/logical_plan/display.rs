@@ -22,9 +22,9 @@ use std::fmt;
 
 use crate::{
     expr_vec_fmt, Aggregate, DescribeTable, Distinct, DistinctOn, DmlStatement, Expr,
-    Filter, Join, Limit, LogicalPlan, Partitioning, Projection, RecursiveQuery,
-    Repartition, Sort, Subquery, SubqueryAlias, TableProviderFilterPushDown, TableScan,
-    Unnest, Values, Window,
+    Filter, GapFill, Join, Limit, LogicalPlan, Partitioning, Projection, RecursiveQuery,
+    Repartition, Sample, Sort, Subquery, SubqueryAlias, TableProviderFilterPushDown,
+    TableScan, Unnest, Values, Window,
 };
 
 use crate::dml::CopyTo;
@@ -650,6 +650,44 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
                     "StructColumn": expr_vec_fmt!(struct_type_columns),
                 })
             }
+            LogicalPlan::GapFill(GapFill {
+                partition_by,
+                fill_expr,
+                from,
+                to,
+                step,
+                ..
+            }) => {
+                let mut object = json!({
+                    "Node Type": "GapFill",
+                    "Partition By": expr_vec_fmt!(partition_by),
+                    "Fill Key": fill_expr.to_string(),
+                    "Step": step.to_string(),
+                });
+                if let Some(from) = from {
+                    object["From"] = serde_json::Value::String(from.to_string());
+                }
+                if let Some(to) = to {
+                    object["To"] = serde_json::Value::String(to.to_string());
+                }
+                object
+            }
+            LogicalPlan::Sample(Sample {
+                method,
+                fraction,
+                seed,
+                ..
+            }) => {
+                let mut object = json!({
+                    "Node Type": "Sample",
+                    "Sampling Method": method.to_string(),
+                    "Fraction": fraction,
+                });
+                if let Some(seed) = seed {
+                    object["Seed"] = serde_json::Value::from(*seed);
+                }
+                object
+            }
         }
     }
 }
@@ -150,6 +150,13 @@ pub enum WriteOp {
     Delete,
     Update,
     Ctas,
+    /// Merges the rows of a source relation into the table. This corresponds
+    /// to the SQL `MERGE INTO` statement.
+    ///
+    /// The input produces every row of the table after the merge, followed
+    /// by the columns described in [`MERGE_ACTION_COLUMN`] and
+    /// [`MERGE_TARGET_ROW_COLUMN`].
+    Merge,
 }
 
 impl WriteOp {
@@ -160,6 +167,7 @@ impl WriteOp {
             WriteOp::Delete => "Delete",
             WriteOp::Update => "Update",
             WriteOp::Ctas => "Ctas",
+            WriteOp::Merge => "Merge",
         }
     }
 }
@@ -170,6 +178,42 @@ impl Display for WriteOp {
     }
 }
 
+/// Name of the column of the input of a [`WriteOp::Merge`] statement holding
+/// the [`MergeAction`] that produced each row, as a `UInt8`
+pub const MERGE_ACTION_COLUMN: &str = "__merge_action";
+
+/// Name of the column of the input of a [`WriteOp::Merge`] statement that
+/// identifies, as a `UInt64`, the row of the target table a source row was
+/// matched with. It is `NULL` for rows that did not result from a match.
+pub const MERGE_TARGET_ROW_COLUMN: &str = "__merge_target_row";
+
+/// The way a row of the input of a [`WriteOp::Merge`] statement was produced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[repr(u8)]
+pub enum MergeAction {
+    /// An existing row that is kept unchanged
+    Keep = 0,
+    /// A row inserted by a `WHEN NOT MATCHED THEN INSERT` clause
+    Insert = 1,
+    /// An existing row modified by an `UPDATE` clause
+    Update = 2,
+    /// An existing row removed by a `DELETE` clause
+    Delete = 3,
+}
+
+impl MergeAction {
+    /// Returns the [`MergeAction`] encoded as `value`, if any
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(MergeAction::Keep),
+            1 => Some(MergeAction::Insert),
+            2 => Some(MergeAction::Update),
+            3 => Some(MergeAction::Delete),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
 pub enum InsertOp {
     /// Appends new rows to the existing table without modifying any
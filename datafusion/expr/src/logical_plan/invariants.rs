@@ -22,9 +22,9 @@ use datafusion_common::{
 };
 
 use crate::{
-    expr::{Exists, InSubquery},
+    expr::{Exists, InSubquery, SetComparison},
     expr_rewriter::strip_outer_reference,
-    utils::{collect_subquery_cols, split_conjunction},
+    utils::{collect_subquery_cols, row_values, split_conjunction},
     Aggregate, Expr, Filter, Join, JoinType, LogicalPlan, Window,
 };
 
@@ -82,6 +82,7 @@ fn assert_valid_extension_nodes(plan: &LogicalPlan, check: InvariantLevel) -> Re
                 match expr {
                     Expr::Exists(Exists { subquery, .. })
                     | Expr::InSubquery(InSubquery { subquery, .. })
+                    | Expr::SetComparison(SetComparison { subquery, .. })
                     | Expr::ScalarSubquery(subquery) => {
                         assert_valid_extension_nodes(&subquery.subquery, check)?;
                     }
@@ -136,6 +137,7 @@ fn assert_subqueries_are_valid(plan: &LogicalPlan) -> Result<()> {
                 match expr {
                     Expr::Exists(Exists { subquery, .. })
                     | Expr::InSubquery(InSubquery { subquery, .. })
+                    | Expr::SetComparison(SetComparison { subquery, .. })
                     | Expr::ScalarSubquery(subquery) => {
                         check_subquery_expr(plan, &subquery.subquery, expr)?;
                     }
@@ -220,15 +222,29 @@ pub fn check_subquery_expr(
         check_correlations_in_subquery(inner_plan)
     } else {
         if let Expr::InSubquery(subquery) = expr {
-            // InSubquery should only return one column
-            if subquery.subquery.subquery.schema().fields().len() > 1 {
+            // InSubquery should only return one column, unless it is compared
+            // with a row of as many values
+            let len = subquery.subquery.subquery.schema().fields().len();
+            if len > 1 && row_values(&subquery.expr, len).is_none() {
                 return plan_err!(
                     "InSubquery should only return one column, but found {}: {}",
-                    subquery.subquery.subquery.schema().fields().len(),
+                    len,
                     subquery.subquery.subquery.schema().field_names().join(", ")
                 );
             }
         }
+        if let Expr::SetComparison(comparison) = expr {
+            // SetComparison should only return one column
+            let schema = comparison.subquery.subquery.schema();
+            if schema.fields().len() > 1 {
+                return plan_err!(
+                    "{} subquery should only return one column, but found {}: {}",
+                    comparison.quantifier,
+                    schema.fields().len(),
+                    schema.field_names().join(", ")
+                );
+            }
+        }
         match outer_plan {
             LogicalPlan::Projection(_)
             | LogicalPlan::Filter(_)
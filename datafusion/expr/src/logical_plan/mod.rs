@@ -31,17 +31,19 @@ pub use builder::{
     LogicalPlanBuilder, LogicalTableSource, UNNAMED_TABLE,
 };
 pub use ddl::{
-    CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
-    CreateFunctionBody, CreateIndex, CreateMemoryTable, CreateView, DdlStatement,
-    DropCatalogSchema, DropFunction, DropTable, DropView, OperateFunctionArg,
+    CommentOn, CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
+    CreateFunctionBody, CreateIndex, CreateMaterializedView, CreateMemoryTable,
+    CreateView, DdlStatement, DropCatalogSchema, DropFunction, DropTable, DropView,
+    OperateFunctionArg, RefreshMaterializedView, COMMENT_METADATA_KEY,
 };
 pub use dml::{DmlStatement, WriteOp};
 pub use plan::{
     projection_schema, Aggregate, Analyze, ColumnUnnestList, DescribeTable, Distinct,
-    DistinctOn, EmptyRelation, Explain, Extension, FetchType, Filter, Join,
-    JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Projection,
-    RecursiveQuery, Repartition, SkipType, Sort, StringifiedPlan, Subquery,
-    SubqueryAlias, TableScan, ToStringifiedPlan, Union, Unnest, Values, Window,
+    DistinctOn, EmptyRelation, Explain, Extension, FetchType, FillStrategy, Filter,
+    GapFill, Join, JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType,
+    Projection, RecursiveQuery, Repartition, Sample, SampleMethod, SkipType, Sort,
+    StringifiedPlan, Subquery, SubqueryAlias, TableScan, ToStringifiedPlan, Union,
+    Unnest, Values, Window,
 };
 pub use statement::{
     Deallocate, Execute, Prepare, SetVariable, Statement, TransactionAccessMode,
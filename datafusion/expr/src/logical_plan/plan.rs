@@ -43,13 +43,15 @@ use crate::utils::{
     split_conjunction,
 };
 use crate::{
-    build_join_schema, expr_vec_fmt, BinaryExpr, CreateMemoryTable, CreateView, Execute,
-    Expr, ExprSchemable, LogicalPlanBuilder, Operator, Prepare,
-    TableProviderFilterPushDown, TableSource, WindowFunctionDefinition,
+    build_join_schema, expr_vec_fmt, BinaryExpr, CreateMaterializedView,
+    CreateMemoryTable, CreateView, Execute, Expr, ExprSchemable, LogicalPlanBuilder,
+    Operator, Prepare, TableProviderFilterPushDown, TableSource,
+    WindowFunctionDefinition,
 };
 
 use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use datafusion_common::cse::{NormalizeEq, Normalizeable};
+use datafusion_common::format::ExplainFormat;
 use datafusion_common::tree_node::{
     Transformed, TreeNode, TreeNodeContainer, TreeNodeRecursion,
 };
@@ -287,6 +289,12 @@ pub enum LogicalPlan {
     Unnest(Unnest),
     /// A variadic query (e.g. "Recursive CTEs")
     RecursiveQuery(RecursiveQuery),
+    /// Inserts rows for missing values of a sorted column. This is used to
+    /// implement SQL `ORDER BY ... WITH FILL`
+    GapFill(GapFill),
+    /// Returns a random sample of the rows of its input. This is used to
+    /// implement SQL `TABLESAMPLE`
+    Sample(Sample),
 }
 
 impl Default for LogicalPlan {
@@ -347,6 +355,8 @@ impl LogicalPlan {
             LogicalPlan::Copy(CopyTo { input, .. }) => input.schema(),
             LogicalPlan::Ddl(ddl) => ddl.schema(),
             LogicalPlan::Unnest(Unnest { schema, .. }) => schema,
+            LogicalPlan::GapFill(GapFill { schema, .. }) => schema,
+            LogicalPlan::Sample(Sample { input, .. }) => input.schema(),
             LogicalPlan::RecursiveQuery(RecursiveQuery { static_term, .. }) => {
                 // we take the schema of the static term as the schema of the entire recursive query
                 static_term.schema()
@@ -468,6 +478,8 @@ impl LogicalPlan {
             LogicalPlan::Copy(copy) => vec![&copy.input],
             LogicalPlan::Ddl(ddl) => ddl.inputs(),
             LogicalPlan::Unnest(Unnest { input, .. }) => vec![input],
+            LogicalPlan::GapFill(GapFill { input, .. }) => vec![input],
+            LogicalPlan::Sample(Sample { input, .. }) => vec![input],
             LogicalPlan::RecursiveQuery(RecursiveQuery {
                 static_term,
                 recursive_term,
@@ -539,6 +551,8 @@ impl LogicalPlan {
             | LogicalPlan::Sort(Sort { input, .. })
             | LogicalPlan::Limit(Limit { input, .. })
             | LogicalPlan::Repartition(Repartition { input, .. })
+            | LogicalPlan::GapFill(GapFill { input, .. })
+            | LogicalPlan::Sample(Sample { input, .. })
             | LogicalPlan::Window(Window { input, .. }) => input.head_output_expr(),
             LogicalPlan::Join(Join {
                 left,
@@ -659,6 +673,7 @@ impl LogicalPlan {
                 on,
                 schema: _,
                 null_equals_null,
+                null_aware,
             }) => {
                 let schema =
                     build_join_schema(left.schema(), right.schema(), &join_type)?;
@@ -680,6 +695,7 @@ impl LogicalPlan {
                     filter,
                     schema: DFSchemaRef::new(schema),
                     null_equals_null,
+                    null_aware,
                 }))
             }
             LogicalPlan::Subquery(_) => Ok(self),
@@ -733,6 +749,26 @@ impl LogicalPlan {
             LogicalPlan::EmptyRelation(_) => Ok(self),
             LogicalPlan::Statement(_) => Ok(self),
             LogicalPlan::DescribeTable(_) => Ok(self),
+            LogicalPlan::Sample(_) => Ok(self),
+            LogicalPlan::GapFill(GapFill {
+                input,
+                partition_by,
+                fill_expr,
+                from,
+                to,
+                step,
+                strategies,
+                schema: _,
+            }) => GapFill::try_new(
+                input,
+                partition_by,
+                fill_expr,
+                from.map(|e| *e),
+                to.map(|e| *e),
+                Some(*step),
+                strategies,
+            )
+            .map(LogicalPlan::GapFill),
             LogicalPlan::Unnest(Unnest {
                 input,
                 exec_columns,
@@ -891,6 +927,7 @@ impl LogicalPlan {
                 join_constraint,
                 on,
                 null_equals_null,
+                null_aware,
                 ..
             }) => {
                 let (left, right) = self.only_two_inputs(inputs)?;
@@ -931,6 +968,7 @@ impl LogicalPlan {
                     filter: filter_expr,
                     schema: DFSchemaRef::new(schema),
                     null_equals_null: *null_equals_null,
+                    null_aware: *null_aware,
                 }))
             }
             LogicalPlan::Subquery(Subquery {
@@ -975,6 +1013,7 @@ impl LogicalPlan {
                 or_replace,
                 column_defaults,
                 temporary,
+                comment,
                 ..
             })) => {
                 self.assert_no_expressions(expr)?;
@@ -988,6 +1027,7 @@ impl LogicalPlan {
                         or_replace: *or_replace,
                         column_defaults: column_defaults.clone(),
                         temporary: *temporary,
+                        comment: comment.clone(),
                     },
                 )))
             }
@@ -1008,6 +1048,29 @@ impl LogicalPlan {
                     definition: definition.clone(),
                 })))
             }
+            LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(
+                CreateMaterializedView {
+                    name,
+                    if_not_exists,
+                    or_replace,
+                    location,
+                    definition,
+                    ..
+                },
+            )) => {
+                self.assert_no_expressions(expr)?;
+                let input = self.only_input(inputs)?;
+                Ok(LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(
+                    CreateMaterializedView {
+                        input: Arc::new(input),
+                        name: name.clone(),
+                        if_not_exists: *if_not_exists,
+                        or_replace: *or_replace,
+                        location: location.clone(),
+                        definition: definition.clone(),
+                    },
+                )))
+            }
             LogicalPlan::Extension(e) => Ok(LogicalPlan::Extension(Extension {
                 node: e.node.with_exprs_and_inputs(expr, inputs)?,
             })),
@@ -1070,6 +1133,7 @@ impl LogicalPlan {
                     verbose: a.verbose,
                     schema: Arc::clone(&a.schema),
                     input: Arc::new(input),
+                    explain_format: a.explain_format,
                 }))
             }
             LogicalPlan::Explain(e) => {
@@ -1081,6 +1145,7 @@ impl LogicalPlan {
                     stringified_plans: e.stringified_plans.clone(),
                     schema: Arc::clone(&e.schema),
                     logical_optimization_succeeded: e.logical_optimization_succeeded,
+                    explain_format: e.explain_format,
                 }))
             }
             LogicalPlan::Statement(Statement::Prepare(Prepare {
@@ -1131,6 +1196,55 @@ impl LogicalPlan {
                     unnest_with_options(input, columns.clone(), options.clone())?;
                 Ok(new_plan)
             }
+            LogicalPlan::Sample(Sample {
+                method,
+                fraction,
+                seed,
+                ..
+            }) => {
+                self.assert_no_expressions(expr)?;
+                let input = self.only_input(inputs)?;
+                Ok(LogicalPlan::Sample(Sample {
+                    input: Arc::new(input),
+                    method: *method,
+                    fraction: *fraction,
+                    seed: *seed,
+                }))
+            }
+            LogicalPlan::GapFill(GapFill {
+                partition_by,
+                fill_expr,
+                from,
+                to,
+                strategies,
+                ..
+            }) => {
+                let input = self.only_input(inputs)?;
+                // The expressions are the partition and fill expressions,
+                // followed by `from` and `to` if set and then `step`
+                let step = expr.pop();
+                let to = to.as_ref().and_then(|_| expr.pop());
+                let from = from.as_ref().and_then(|_| expr.pop());
+                let new_fill_expr = expr.pop().map(|e| fill_expr.with_expr(e));
+                let (Some(fill_expr), Some(step)) = (new_fill_expr, step) else {
+                    return internal_err!("Missing expressions for GapFill");
+                };
+                let partition_by = expr
+                    .into_iter()
+                    .zip(partition_by.iter())
+                    .map(|(expr, sort)| sort.with_expr(expr))
+                    .collect();
+                GapFill::try_new(
+                    Arc::new(input),
+                    partition_by,
+                    fill_expr,
+                    from,
+                    to,
+                    Some(step),
+                    strategies.clone(),
+                )
+                .map(LogicalPlan::GapFill)
+            }
         }
     }
 
@@ -1355,7 +1469,8 @@ impl LogicalPlan {
                 Distinct::All(input) | Distinct::On(DistinctOn { input, .. }),
             ) => input.max_rows(),
             LogicalPlan::Values(v) => Some(v.values.len()),
-            LogicalPlan::Unnest(_) => None,
+            LogicalPlan::Sample(Sample { input, .. }) => input.max_rows(),
+            LogicalPlan::Unnest(_) | LogicalPlan::GapFill(_) => None,
             LogicalPlan::Ddl(_)
             | LogicalPlan::Explain(_)
             | LogicalPlan::Analyze(_)
@@ -1880,6 +1995,7 @@ impl LogicalPlan {
                         filter,
                         join_constraint,
                         join_type,
+                        null_aware,
                         ..
                     }) => {
                         let join_expr: Vec<String> =
@@ -1893,12 +2009,14 @@ impl LogicalPlan {
                         } else {
                             join_type.to_string()
                         };
+                        let null_aware = if *null_aware { " (NullAware)" } else { "" };
                         match join_constraint {
                             JoinConstraint::On => {
                                 write!(
                                     f,
-                                    "{} Join: {}{}",
+                                    "{} Join{}: {}{}",
                                     join_type,
+                                    null_aware,
                                     join_expr.join(", "),
                                     filter_expr
                                 )
@@ -1906,8 +2024,9 @@ impl LogicalPlan {
                             JoinConstraint::Using => {
                                 write!(
                                     f,
-                                    "{} Join: Using {}{}",
+                                    "{} Join{}: Using {}{}",
                                     join_type,
+                                    null_aware,
                                     join_expr.join(", "),
                                     filter_expr,
                                 )
@@ -1991,7 +2110,8 @@ impl LogicalPlan {
                     LogicalPlan::Unnest(Unnest {
                         input: plan,
                         list_type_columns: list_col_indices,
-                        struct_type_columns: struct_col_indices, .. }) => {
+                        struct_type_columns: struct_col_indices,
+                        options, .. }) => {
                         let input_columns = plan.schema().columns();
                         let list_type_columns = list_col_indices
                             .iter()
@@ -2006,7 +2126,57 @@ impl LogicalPlan {
                         // get items from input_columns indexed by list_col_indices
                         write!(f, "Unnest: lists[{}] structs[{}]",
                         expr_vec_fmt!(list_type_columns),
-                        expr_vec_fmt!(struct_type_columns))
+                        expr_vec_fmt!(struct_type_columns))?;
+                        if let Some(ordinality_column) = &options.ordinality_column {
+                            write!(f, " ordinality={ordinality_column}")?;
+                        }
+                        Ok(())
+                    }
+                    LogicalPlan::GapFill(GapFill {
+                        partition_by,
+                        fill_expr,
+                        from,
+                        to,
+                        step,
+                        strategies,
+                        schema,
+                        ..
+                    }) => {
+                        write!(f, "GapFill: ")?;
+                        if !partition_by.is_empty() {
+                            write!(f, "partition_by=[{}], ", expr_vec_fmt!(partition_by))?;
+                        }
+                        write!(f, "fill={fill_expr}")?;
+                        if let Some(from) = from {
+                            write!(f, ", from={from}")?;
+                        }
+                        if let Some(to) = to {
+                            write!(f, ", to={to}")?;
+                        }
+                        write!(f, ", step={step}")?;
+                        let interpolations = schema
+                            .fields()
+                            .iter()
+                            .zip(strategies)
+                            .filter(|(_, strategy)| **strategy != FillStrategy::Null)
+                            .map(|(field, strategy)| format!("{}:{strategy}", field.name()))
+                            .collect::<Vec<_>>();
+                        if !interpolations.is_empty() {
+                            write!(f, ", interpolate=[{}]", interpolations.join(", "))?;
+                        }
+                        Ok(())
+                    }
+                    LogicalPlan::Sample(Sample {
+                        method,
+                        fraction,
+                        seed,
+                        ..
+                    }) => {
+                        write!(f, "Sample: method={method}, fraction={fraction}")?;
+                        if let Some(seed) = seed {
+                            write!(f, ", seed={seed}")?;
+                        }
+                        Ok(())
                     }
                 }
             }
@@ -2806,6 +2976,8 @@ pub struct Explain {
     pub schema: DFSchemaRef,
     /// Used by physical planner to check if should proceed with planning
     pub logical_optimization_succeeded: bool,
+    /// The format of the physical plan in the output
+    pub explain_format: ExplainFormat,
 }
 
 // Manual implementation needed because of `schema` field. Comparison excludes this field.
@@ -2821,18 +2993,22 @@ impl PartialOrd for Explain {
             pub stringified_plans: &'a Vec<StringifiedPlan>,
             /// Used by physical planner to check if should proceed with planning
             pub logical_optimization_succeeded: &'a bool,
+            /// The format of the physical plan in the output
+            pub explain_format: &'a ExplainFormat,
         }
         let comparable_self = ComparableExplain {
             verbose: &self.verbose,
             plan: &self.plan,
             stringified_plans: &self.stringified_plans,
             logical_optimization_succeeded: &self.logical_optimization_succeeded,
+            explain_format: &self.explain_format,
         };
         let comparable_other = ComparableExplain {
             verbose: &other.verbose,
             plan: &other.plan,
             stringified_plans: &other.stringified_plans,
             logical_optimization_succeeded: &other.logical_optimization_succeeded,
+            explain_format: &other.explain_format,
         };
         comparable_self.partial_cmp(&comparable_other)
     }
@@ -2848,13 +3024,20 @@ pub struct Analyze {
     pub input: Arc<LogicalPlan>,
     /// The output schema of the explain (2 columns of text)
     pub schema: DFSchemaRef,
+    /// The format of the annotated physical plan in the output
+    pub explain_format: ExplainFormat,
 }
 
 // Manual implementation needed because of `schema` field. Comparison excludes this field.
 impl PartialOrd for Analyze {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match self.verbose.partial_cmp(&other.verbose) {
-            Some(Ordering::Equal) => self.input.partial_cmp(&other.input),
+            Some(Ordering::Equal) => match self.input.partial_cmp(&other.input) {
+                Some(Ordering::Equal) => {
+                    self.explain_format.partial_cmp(&other.explain_format)
+                }
+                cmp => cmp,
+            },
             cmp => cmp,
         }
     }
@@ -3398,6 +3581,14 @@ pub struct Join {
     pub schema: DFSchemaRef,
     /// If null_equals_null is true, null == null else null != null
     pub null_equals_null: bool,
+    /// If null_aware is true, this is a null-aware anti join with the semantics
+    /// of `NOT IN`: no rows are returned if the right input contains a NULL
+    /// join key, and left rows with a NULL join key are only returned if the
+    /// right input is empty. Only valid for [`JoinType::LeftAnti`] joins
+    /// without a filter. The first key is the one compared by `NOT IN`, the
+    /// keys after it correlate the inputs, and the semantics apply to each
+    /// group of rows with equal correlation keys on its own.
+    pub null_aware: bool,
 }
 
 impl Join {
@@ -3431,6 +3622,7 @@ impl Join {
             join_constraint: original_join.join_constraint,
             schema: Arc::new(join_schema),
             null_equals_null: original_join.null_equals_null,
+            null_aware: original_join.null_aware,
         })
     }
 }
@@ -3454,6 +3646,8 @@ impl PartialOrd for Join {
             pub join_constraint: &'a JoinConstraint,
             /// If null_equals_null is true, null == null else null != null
             pub null_equals_null: &'a bool,
+            /// If null_aware is true, this is a null-aware anti join
+            pub null_aware: &'a bool,
         }
         let comparable_self = ComparableJoin {
             left: &self.left,
@@ -3463,6 +3657,7 @@ impl PartialOrd for Join {
             join_type: &self.join_type,
             join_constraint: &self.join_constraint,
             null_equals_null: &self.null_equals_null,
+            null_aware: &self.null_aware,
         };
         let comparable_other = ComparableJoin {
             left: &other.left,
@@ -3472,6 +3667,7 @@ impl PartialOrd for Join {
             join_type: &other.join_type,
             join_constraint: &other.join_constraint,
             null_equals_null: &other.null_equals_null,
+            null_aware: &other.null_aware,
         };
         comparable_self.partial_cmp(&comparable_other)
     }
@@ -3590,7 +3786,8 @@ pub struct Unnest {
     /// that have type struct to run unnest on
     pub struct_type_columns: Vec<usize>,
     /// Having items aligned with the output columns
-    /// representing which column in the input schema each output column depends on
+    /// representing which column in the input schema each output column depends on.
+    /// The ordinality column, if any, comes last and has no entry
     pub dependency_indices: Vec<usize>,
     /// The output schema, containing the unnested field column.
     pub schema: DFSchemaRef,
@@ -3639,6 +3836,344 @@ impl PartialOrd for Unnest {
     }
 }
 
+/// How the columns of the rows inserted by [`GapFill`] are filled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Hash)]
+pub enum FillStrategy {
+    /// Fill with NULL
+    #[default]
+    Null,
+    /// Repeat the value of the previous row of the series
+    Previous,
+    /// Linearly interpolate between the previous and the next row of the
+    /// series. Rows inserted before the first or after the last row of a
+    /// series are filled with NULL.
+    Linear,
+}
+
+impl Display for FillStrategy {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FillStrategy::Null => write!(f, "null"),
+            FillStrategy::Previous => write!(f, "previous"),
+            FillStrategy::Linear => write!(f, "linear"),
+        }
+    }
+}
+
+/// Inserts rows for the values of a column missing from its sorted input.
+/// This is used to implement SQL `ORDER BY ... WITH FILL`.
+///
+/// The input is sorted by `partition_by` and then `fill_expr`, and every
+/// distinct value of `partition_by` is a separate series. Within a series,
+/// rows are inserted for the values between consecutive input rows that are
+/// `step` apart, starting at `from` (inclusive) and up to `to` (exclusive)
+/// if they are set. For descending input, the values decrease by `step`.
+///
+/// The fill and partition columns of the inserted rows are set to the
+/// generated value and the series, the other columns according to their
+/// [`FillStrategy`].
+///
+/// ```text
+/// GapFill: fill=t ASC NULLS LAST, step=Int64(1), interpolate=[v:linear]
+///
+/// t | v          t | v
+/// --+---         --+---
+/// 1 | 10    =>   1 | 10
+/// 4 | 40         2 | 20
+///                3 | 30
+///                4 | 40
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GapFill {
+    /// The incoming logical plan
+    pub input: Arc<LogicalPlan>,
+    /// The columns identifying a series, in the sort order of the input
+    pub partition_by: Vec<SortExpr>,
+    /// The column whose missing values are inserted, in the sort order of the input
+    pub fill_expr: SortExpr,
+    /// The first value of every series
+    pub from: Option<Box<Expr>>,
+    /// The value every series is filled up to, exclusive
+    pub to: Option<Box<Expr>>,
+    /// The distance between consecutive values
+    pub step: Box<Expr>,
+    /// How each column of the inserted rows is filled, aligned with the
+    /// input schema. Ignored for the fill and partition columns.
+    pub strategies: Vec<FillStrategy>,
+    /// The output schema, the input schema with the filled columns nullable
+    pub schema: DFSchemaRef,
+}
+
+impl GapFill {
+    /// Create a new `GapFill`, checking the fill column and casting `from`,
+    /// `to` and `step` to its type.
+    ///
+    /// If `step` is not set, it defaults to one for numeric columns and one
+    /// day for dates.
+    pub fn try_new(
+        input: Arc<LogicalPlan>,
+        partition_by: Vec<SortExpr>,
+        fill_expr: SortExpr,
+        from: Option<Expr>,
+        to: Option<Expr>,
+        step: Option<Expr>,
+        strategies: Vec<FillStrategy>,
+    ) -> Result<Self> {
+        let input_schema = input.schema();
+        if strategies.len() != input_schema.fields().len() {
+            return plan_err!(
+                "GapFill expects {} fill strategies, got {}",
+                input_schema.fields().len(),
+                strategies.len()
+            );
+        }
+
+        let mut series_indices = HashSet::new();
+        for sort in partition_by.iter().chain(std::iter::once(&fill_expr)) {
+            let Expr::Column(column) = &sort.expr else {
+                return plan_err!(
+                    "WITH FILL requires the ORDER BY expressions to be columns, got {}",
+                    sort.expr
+                );
+            };
+            series_indices.insert(input_schema.index_of_column(column)?);
+        }
+
+        let fill_type = fill_expr.expr.get_type(input_schema)?;
+        let is_temporal = matches!(
+            fill_type,
+            DataType::Date32 | DataType::Date64 | DataType::Timestamp(_, _)
+        );
+        if !fill_type.is_numeric() && !is_temporal {
+            return plan_err!(
+                "WITH FILL is not supported for {} of type {fill_type}",
+                fill_expr.expr
+            );
+        }
+
+        let step = match step {
+            Some(step) => {
+                let step_type = step.get_type(input_schema)?;
+                match step_type {
+                    DataType::Interval(_) | DataType::Duration(_) if is_temporal => step,
+                    _ if is_temporal => {
+                        return plan_err!(
+                            "WITH FILL STEP for {} must be an interval, got {step_type}",
+                            fill_expr.expr
+                        );
+                    }
+                    _ if step_type.is_numeric() => {
+                        cast_fill_bound(step, &fill_type, input_schema)?
+                    }
+                    _ => {
+                        return plan_err!(
+                            "WITH FILL STEP for {} must be numeric, got {step_type}",
+                            fill_expr.expr
+                        );
+                    }
+                }
+            }
+            None => match fill_type {
+                DataType::Date32 | DataType::Date64 => {
+                    Expr::Literal(ScalarValue::new_interval_mdn(0, 1, 0))
+                }
+                DataType::Timestamp(_, _) => {
+                    return plan_err!(
+                        "WITH FILL for {} of type {fill_type} requires a STEP",
+                        fill_expr.expr
+                    );
+                }
+                _ => Expr::Literal(ScalarValue::new_one(&fill_type)?),
+            },
+        };
+        let from = from
+            .map(|e| cast_fill_bound(e, &fill_type, input_schema))
+            .transpose()?;
+        let to = to
+            .map(|e| cast_fill_bound(e, &fill_type, input_schema))
+            .transpose()?;
+
+        let fields = input_schema
+            .iter()
+            .zip(&strategies)
+            .enumerate()
+            .map(|(i, ((qualifier, field), strategy))| {
+                if series_indices.contains(&i) {
+                    return Ok((qualifier.cloned(), Arc::clone(field)));
+                }
+                if *strategy == FillStrategy::Linear && !field.data_type().is_numeric() {
+                    return plan_err!(
+                        "Linear interpolation is not supported for column {} of type {}",
+                        field.name(),
+                        field.data_type()
+                    );
+                }
+                let field = field.as_ref().clone().with_nullable(true);
+                Ok((qualifier.cloned(), Arc::new(field)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let schema =
+            DFSchema::new_with_metadata(fields, input_schema.metadata().clone())?;
+
+        Ok(Self {
+            input,
+            partition_by,
+            fill_expr,
+            from: from.map(Box::new),
+            to: to.map(Box::new),
+            step: Box::new(step),
+            strategies,
+            schema: Arc::new(schema),
+        })
+    }
+}
+
+// Manual implementation needed because of `schema` field. Comparison excludes this field.
+impl PartialOrd for GapFill {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        #[derive(PartialEq, PartialOrd)]
+        struct ComparableGapFill<'a> {
+            pub input: &'a Arc<LogicalPlan>,
+            pub partition_by: &'a Vec<SortExpr>,
+            pub fill_expr: &'a SortExpr,
+            pub from: Option<&'a Expr>,
+            pub to: Option<&'a Expr>,
+            pub step: &'a Expr,
+            pub strategies: &'a Vec<FillStrategy>,
+        }
+        let comparable_self = ComparableGapFill {
+            input: &self.input,
+            partition_by: &self.partition_by,
+            fill_expr: &self.fill_expr,
+            from: self.from.as_deref(),
+            to: self.to.as_deref(),
+            step: &self.step,
+            strategies: &self.strategies,
+        };
+        let comparable_other = ComparableGapFill {
+            input: &other.input,
+            partition_by: &other.partition_by,
+            fill_expr: &other.fill_expr,
+            from: other.from.as_deref(),
+            to: other.to.as_deref(),
+            step: &other.step,
+            strategies: &other.strategies,
+        };
+        comparable_self.partial_cmp(&comparable_other)
+    }
+}
+
+/// How [`Sample`] selects the rows of its input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub enum SampleMethod {
+    /// Every row is selected independently with the sampling probability
+    Bernoulli,
+    /// Blocks of rows, such as files, row groups or batches, are selected
+    /// with the sampling probability. This is cheaper than
+    /// [`SampleMethod::Bernoulli`] as the rows of skipped blocks do not need
+    /// to be read, but the sample is less random.
+    System,
+}
+
+impl Display for SampleMethod {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SampleMethod::Bernoulli => write!(f, "bernoulli"),
+            SampleMethod::System => write!(f, "system"),
+        }
+    }
+}
+
+/// Returns a random sample of the rows of its input. This is used to
+/// implement SQL `TABLESAMPLE`.
+///
+/// ```text
+/// Sample: method=bernoulli, fraction=0.1, seed=42
+///   TableScan: t
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sample {
+    /// The incoming logical plan
+    pub input: Arc<LogicalPlan>,
+    /// How the rows are selected
+    pub method: SampleMethod,
+    /// The probability of a row or block to be selected, between 0 and 1
+    pub fraction: f64,
+    /// The seed of the random number generator. The same seed selects the
+    /// same rows if the input does not change. A random seed is used if
+    /// it is not set.
+    pub seed: Option<u64>,
+}
+
+impl Sample {
+    /// Create a new `Sample`, checking that `fraction` is a probability
+    pub fn try_new(
+        input: Arc<LogicalPlan>,
+        method: SampleMethod,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Result<Self> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return plan_err!("Sample fraction must be between 0 and 1, got {fraction}");
+        }
+        Ok(Self {
+            input,
+            method,
+            fraction,
+            seed,
+        })
+    }
+}
+
+// Manual implementations needed because `fraction` is a float. Fractions are
+// compared by their bits, and are never NaN.
+impl PartialEq for Sample {
+    fn eq(&self, other: &Self) -> bool {
+        self.input == other.input
+            && self.method == other.method
+            && self.fraction.to_bits() == other.fraction.to_bits()
+            && self.seed == other.seed
+    }
+}
+
+impl Eq for Sample {}
+
+impl Hash for Sample {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.input.hash(state);
+        self.method.hash(state);
+        self.fraction.to_bits().hash(state);
+        self.seed.hash(state);
+    }
+}
+
+impl PartialOrd for Sample {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.input.partial_cmp(&other.input) {
+            Some(Ordering::Equal) => {}
+            cmp => return cmp,
+        }
+        match self.method.partial_cmp(&other.method) {
+            Some(Ordering::Equal) => {}
+            cmp => return cmp,
+        }
+        match self.fraction.partial_cmp(&other.fraction) {
+            Some(Ordering::Equal) => {}
+            cmp => return cmp,
+        }
+        self.seed.partial_cmp(&other.seed)
+    }
+}
+
+/// Casts a `WITH FILL` bound to the type of the filled column, folding the
+/// cast into the value when the bound is a literal
+fn cast_fill_bound(expr: Expr, data_type: &DataType, schema: &DFSchema) -> Result<Expr> {
+    match expr {
+        Expr::Literal(value) => Ok(Expr::Literal(value.cast_to(data_type)?)),
+        expr => expr.cast_to(data_type, schema),
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -38,15 +38,15 @@
 //! * [`LogicalPlan::expressions`]: Return a copy of the plan's expressions
 
 use crate::{
-    dml::CopyTo, Aggregate, Analyze, CreateMemoryTable, CreateView, DdlStatement,
-    Distinct, DistinctOn, DmlStatement, Execute, Explain, Expr, Extension, Filter, Join,
-    Limit, LogicalPlan, Partitioning, Prepare, Projection, RecursiveQuery, Repartition,
-    Sort, Statement, Subquery, SubqueryAlias, TableScan, Union, Unnest,
-    UserDefinedLogicalNode, Values, Window,
+    dml::CopyTo, Aggregate, Analyze, CreateMaterializedView, CreateMemoryTable,
+    CreateView, DdlStatement, Distinct, DistinctOn, DmlStatement, Execute, Explain, Expr,
+    Extension, Filter, GapFill, Join, Limit, LogicalPlan, Partitioning, Prepare,
+    Projection, RecursiveQuery, Repartition, Sample, Sort, Statement, Subquery,
+    SubqueryAlias, TableScan, Union, Unnest, UserDefinedLogicalNode, Values, Window,
 };
 use datafusion_common::tree_node::TreeNodeRefContainer;
 
-use crate::expr::{Exists, InSubquery};
+use crate::expr::{Exists, InSubquery, SetComparison};
 use datafusion_common::tree_node::{
     Transformed, TreeNode, TreeNodeContainer, TreeNodeIterator, TreeNodeRecursion,
     TreeNodeRewriter, TreeNodeVisitor,
@@ -141,6 +141,7 @@ impl TreeNode for LogicalPlan {
                 join_constraint,
                 schema,
                 null_equals_null,
+                null_aware,
             }) => (left, right).map_elements(f)?.update_data(|(left, right)| {
                 LogicalPlan::Join(Join {
                     left,
@@ -151,6 +152,7 @@ impl TreeNode for LogicalPlan {
                     join_constraint,
                     schema,
                     null_equals_null,
+                    null_aware,
                 })
             }),
             LogicalPlan::Limit(Limit { skip, fetch, input }) => input
@@ -206,6 +208,7 @@ impl TreeNode for LogicalPlan {
                 stringified_plans,
                 schema,
                 logical_optimization_succeeded,
+                explain_format,
             }) => plan.map_elements(f)?.update_data(|plan| {
                 LogicalPlan::Explain(Explain {
                     verbose,
@@ -213,17 +216,20 @@ impl TreeNode for LogicalPlan {
                     stringified_plans,
                     schema,
                     logical_optimization_succeeded,
+                    explain_format,
                 })
             }),
             LogicalPlan::Analyze(Analyze {
                 verbose,
                 input,
                 schema,
+                explain_format,
             }) => input.map_elements(f)?.update_data(|input| {
                 LogicalPlan::Analyze(Analyze {
                     verbose,
                     input,
                     schema,
+                    explain_format,
                 })
             }),
             LogicalPlan::Dml(DmlStatement {
@@ -266,6 +272,7 @@ impl TreeNode for LogicalPlan {
                         or_replace,
                         column_defaults,
                         temporary,
+                        comment,
                     }) => input.map_elements(f)?.update_data(|input| {
                         DdlStatement::CreateMemoryTable(CreateMemoryTable {
                             name,
@@ -275,6 +282,7 @@ impl TreeNode for LogicalPlan {
                             or_replace,
                             column_defaults,
                             temporary,
+                            comment,
                         })
                     }),
                     DdlStatement::CreateView(CreateView {
@@ -292,16 +300,35 @@ impl TreeNode for LogicalPlan {
                             temporary,
                         })
                     }),
+                    DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                        name,
+                        input,
+                        if_not_exists,
+                        or_replace,
+                        location,
+                        definition,
+                    }) => input.map_elements(f)?.update_data(|input| {
+                        DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                            name,
+                            input,
+                            if_not_exists,
+                            or_replace,
+                            location,
+                            definition,
+                        })
+                    }),
                     // no inputs in these statements
                     DdlStatement::CreateExternalTable(_)
                     | DdlStatement::CreateCatalogSchema(_)
                     | DdlStatement::CreateCatalog(_)
                     | DdlStatement::CreateIndex(_)
+                    | DdlStatement::CommentOn(_)
                     | DdlStatement::DropTable(_)
                     | DdlStatement::DropView(_)
                     | DdlStatement::DropCatalogSchema(_)
                     | DdlStatement::CreateFunction(_)
-                    | DdlStatement::DropFunction(_) => Transformed::no(ddl),
+                    | DdlStatement::DropFunction(_)
+                    | DdlStatement::RefreshMaterializedView(_) => Transformed::no(ddl),
                 }
                 .update_data(LogicalPlan::Ddl)
             }
@@ -324,6 +351,40 @@ impl TreeNode for LogicalPlan {
                     options,
                 })
             }),
+            LogicalPlan::GapFill(GapFill {
+                input,
+                partition_by,
+                fill_expr,
+                from,
+                to,
+                step,
+                strategies,
+                schema,
+            }) => input.map_elements(f)?.update_data(|input| {
+                LogicalPlan::GapFill(GapFill {
+                    input,
+                    partition_by,
+                    fill_expr,
+                    from,
+                    to,
+                    step,
+                    strategies,
+                    schema,
+                })
+            }),
+            LogicalPlan::Sample(Sample {
+                input,
+                method,
+                fraction,
+                seed,
+            }) => input.map_elements(f)?.update_data(|input| {
+                LogicalPlan::Sample(Sample {
+                    input,
+                    method,
+                    fraction,
+                    seed,
+                })
+            }),
             LogicalPlan::RecursiveQuery(RecursiveQuery {
                 name,
                 static_term,
@@ -457,6 +518,16 @@ impl LogicalPlan {
             LogicalPlan::Limit(Limit { skip, fetch, .. }) => {
                 (skip, fetch).apply_ref_elements(f)
             }
+            LogicalPlan::GapFill(GapFill {
+                partition_by,
+                fill_expr,
+                from,
+                to,
+                step,
+                ..
+            }) => (partition_by, fill_expr, from)
+                .apply_ref_elements(&mut f)?
+                .visit_sibling(|| (to, step).apply_ref_elements(f)),
             LogicalPlan::Statement(stmt) => match stmt {
                 Statement::Execute(Execute { parameters, .. }) => {
                     parameters.apply_elements(f)
@@ -475,6 +546,7 @@ impl LogicalPlan {
             | LogicalPlan::Dml(_)
             | LogicalPlan::Ddl(_)
             | LogicalPlan::Copy(_)
+            | LogicalPlan::Sample(_)
             | LogicalPlan::DescribeTable(_) => Ok(TreeNodeRecursion::Continue),
         }
     }
@@ -573,6 +645,7 @@ impl LogicalPlan {
                 join_constraint,
                 schema,
                 null_equals_null,
+                null_aware,
             }) => (on, filter).map_elements(f)?.update_data(|(on, filter)| {
                 LogicalPlan::Join(Join {
                     left,
@@ -583,6 +656,7 @@ impl LogicalPlan {
                     join_constraint,
                     schema,
                     null_equals_null,
+                    null_aware,
                 })
             }),
             LogicalPlan::Sort(Sort { expr, input, fetch }) => expr
@@ -640,6 +714,29 @@ impl LogicalPlan {
                     LogicalPlan::Limit(Limit { skip, fetch, input })
                 })
             }
+            LogicalPlan::GapFill(GapFill {
+                input,
+                partition_by,
+                fill_expr,
+                from,
+                to,
+                step,
+                strategies,
+                schema,
+            }) => ((partition_by, fill_expr), (from, to), step)
+                .map_elements(f)?
+                .update_data(|((partition_by, fill_expr), (from, to), step)| {
+                    LogicalPlan::GapFill(GapFill {
+                        input,
+                        partition_by,
+                        fill_expr,
+                        from,
+                        to,
+                        step,
+                        strategies,
+                        schema,
+                    })
+                }),
             LogicalPlan::Statement(stmt) => match stmt {
                 Statement::Execute(e) => {
                     e.parameters.map_elements(f)?.update_data(|parameters| {
@@ -662,6 +759,7 @@ impl LogicalPlan {
             | LogicalPlan::Dml(_)
             | LogicalPlan::Ddl(_)
             | LogicalPlan::Copy(_)
+            | LogicalPlan::Sample(_)
             | LogicalPlan::DescribeTable(_) => Transformed::no(self),
         })
     }
@@ -824,6 +922,7 @@ impl LogicalPlan {
             expr.apply(|expr| match expr {
                 Expr::Exists(Exists { subquery, .. })
                 | Expr::InSubquery(InSubquery { subquery, .. })
+                | Expr::SetComparison(SetComparison { subquery, .. })
                 | Expr::ScalarSubquery(subquery) => {
                     // use a synthetic plan so the collector sees a
                     // LogicalPlan::Subquery (even though it is
@@ -865,6 +964,22 @@ impl LogicalPlan {
                     })),
                     _ => internal_err!("Transformation should return Subquery"),
                 }),
+                Expr::SetComparison(SetComparison {
+                    expr,
+                    subquery,
+                    op,
+                    quantifier,
+                }) => f(LogicalPlan::Subquery(subquery))?.map_data(|s| match s {
+                    LogicalPlan::Subquery(subquery) => {
+                        Ok(Expr::SetComparison(SetComparison {
+                            expr,
+                            subquery,
+                            op,
+                            quantifier,
+                        }))
+                    }
+                    _ => internal_err!("Transformation should return Subquery"),
+                }),
                 Expr::ScalarSubquery(subquery) => f(LogicalPlan::Subquery(subquery))?
                     .map_data(|s| match s {
                         LogicalPlan::Subquery(subquery) => {
@@ -78,6 +78,22 @@ pub trait ContextProvider {
     fn get_aggregate_meta(&self, name: &str) -> Option<Arc<AggregateUDF>>;
     /// Getter for a UDWF
     fn get_window_meta(&self, name: &str) -> Option<Arc<WindowUDF>>;
+
+    /// Getter for a UDF registered in the schema `name` is qualified with
+    fn get_schema_function_meta(&self, _name: &TableReference) -> Option<Arc<ScalarUDF>> {
+        None
+    }
+    /// Getter for a UDAF registered in the schema `name` is qualified with
+    fn get_schema_aggregate_meta(
+        &self,
+        _name: &TableReference,
+    ) -> Option<Arc<AggregateUDF>> {
+        None
+    }
+    /// Getter for a UDWF registered in the schema `name` is qualified with
+    fn get_schema_window_meta(&self, _name: &TableReference) -> Option<Arc<WindowUDF>> {
+        None
+    }
     /// Getter for system/user-defined variable type
     fn get_variable_type(&self, variable_names: &[String]) -> Option<DataType>;
 
@@ -211,6 +227,13 @@ pub trait ExprPlanner: Debug + Send + Sync {
     fn plan_any(&self, expr: RawBinaryExpr) -> Result<PlannerResult<RawBinaryExpr>> {
         Ok(PlannerResult::Original(expr))
     }
+
+    /// Plans `ALL` expression, e.g., `expr > ALL(array_expr)`
+    ///
+    /// Returns origin binary expression if not possible
+    fn plan_all(&self, expr: RawBinaryExpr) -> Result<PlannerResult<RawBinaryExpr>> {
+        Ok(PlannerResult::Original(expr))
+    }
 }
 
 /// An operator with two arguments to plan
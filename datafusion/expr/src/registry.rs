@@ -121,6 +121,19 @@ pub trait FunctionRegistry {
     ) -> Result<()> {
         not_impl_err!("Registering ExprPlanner")
     }
+
+    /// Registers a new [`ExprPlanner`] with the registry, which is tried
+    /// before all of the planners registered so far.
+    ///
+    /// Unlike with [`Self::register_expr_planner`], the planner can then
+    /// override how the registered planners plan an expression, such as the
+    /// default planning of the `->` and `->>` operators as JSON functions.
+    fn register_expr_planner_first(
+        &mut self,
+        _expr_planner: Arc<dyn ExprPlanner>,
+    ) -> Result<()> {
+        not_impl_err!("Registering ExprPlanner")
+    }
 }
 
 /// Serializer and deserializer registry for extensions like [UserDefinedLogicalNode].
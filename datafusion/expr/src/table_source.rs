@@ -17,10 +17,10 @@
 
 //! Table source
 
-use crate::{Expr, LogicalPlan};
+use crate::{DdlStatement, Expr, LogicalPlan};
 
 use arrow::datatypes::SchemaRef;
-use datafusion_common::{Constraints, Result};
+use datafusion_common::{Constraints, Result, TableReference};
 
 use std::{any::Any, borrow::Cow};
 
@@ -118,4 +118,15 @@ pub trait TableSource: Sync + Send {
     fn get_column_default(&self, _column: &str) -> Option<&Expr> {
         None
     }
+
+    /// Get the create statement used to create this table, if available.
+    fn get_table_definition(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get a [`DdlStatement`] that creates a table equivalent to this one
+    /// under `name`, generated from the metadata of the table, if available.
+    fn get_table_ddl(&self, _name: &TableReference) -> Result<Option<DdlStatement>> {
+        Ok(None)
+    }
 }
@@ -26,7 +26,9 @@ use std::vec;
 
 use arrow::datatypes::{DataType, Field};
 
-use datafusion_common::{exec_err, not_impl_err, Result, ScalarValue, Statistics};
+use datafusion_common::{
+    exec_err, not_impl_err, Result, ScalarValue, Statistics, TableReference,
+};
 use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
 
 use crate::expr::AggregateFunction;
@@ -140,6 +142,19 @@ impl AggregateUDF {
         ))
     }
 
+    /// Qualifies this function with the schema `catalog.schema` it is
+    /// registered in.
+    ///
+    /// See [`ScalarUDF::with_schema`](crate::ScalarUDF::with_schema) for
+    /// details.
+    pub fn with_schema(self, catalog: &str, schema: &str) -> Self {
+        Self::new_from_impl(SchemaAggregateUDFImpl::new(
+            Arc::clone(&self.inner),
+            catalog,
+            schema,
+        ))
+    }
+
     /// Creates an [`Expr`] that calls the aggregate function.
     ///
     /// This utility allows using the UDAF without requiring access to
@@ -260,6 +275,14 @@ impl AggregateUDF {
         self.inner.is_descending()
     }
 
+    /// Returns true if this function can be called as an ordered-set
+    /// aggregate with a `WITHIN GROUP (ORDER BY ...)` clause.
+    ///
+    /// See [`AggregateUDFImpl::supports_within_group_clause`] for more details.
+    pub fn supports_within_group_clause(&self) -> bool {
+        self.inner.supports_within_group_clause()
+    }
+
     /// Return the value of this aggregate function if it can be determined
     /// entirely from statistics and arguments.
     ///
@@ -608,6 +631,21 @@ pub trait AggregateUDFImpl: Debug + Send + Sync {
         None
     }
 
+    /// Returns true if this function is an ordered-set aggregate that can be
+    /// called with a `WITHIN GROUP (ORDER BY ...)` clause, for example
+    /// `percentile_cont(0.5) WITHIN GROUP (ORDER BY x)`.
+    ///
+    /// When planning such a call, the SQL planner passes the `WITHIN GROUP`
+    /// sort expressions as the leading arguments, followed by the direct
+    /// arguments (`x, 0.5` in the example above). The sort expressions are
+    /// also set as the aggregate's `ORDER BY`, so the requested direction is
+    /// available via [`AccumulatorArgs::ordering_req`].
+    ///
+    /// Defaults to `false`, in which case `WITHIN GROUP` is rejected.
+    fn supports_within_group_clause(&self) -> bool {
+        false
+    }
+
     /// Return the value of this aggregate function if it can be determined
     /// entirely from statistics and arguments.
     ///
@@ -788,9 +826,151 @@ impl AggregateUDFImpl for AliasedAggregateUDFImpl {
         self.inner.is_descending()
     }
 
+    fn supports_within_group_clause(&self) -> bool {
+        self.inner.supports_within_group_clause()
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.inner.documentation()
+    }
+}
+
+/// AggregateUDF qualified with the schema it is registered in
+#[derive(Debug)]
+struct SchemaAggregateUDFImpl {
+    inner: Arc<dyn AggregateUDFImpl>,
+    /// `catalog.schema.name` of the function
+    name: String,
+}
+
+impl SchemaAggregateUDFImpl {
+    fn new(inner: Arc<dyn AggregateUDFImpl>, catalog: &str, schema: &str) -> Self {
+        let name = TableReference::full(catalog, schema, inner.name()).to_string();
+        Self { inner, name }
+    }
+}
+
+impl AggregateUDFImpl for SchemaAggregateUDFImpl {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        self.inner.signature()
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        self.inner.return_type(arg_types)
+    }
+
+    fn is_nullable(&self) -> bool {
+        self.inner.is_nullable()
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        self.inner.accumulator(acc_args)
+    }
+
+    fn aliases(&self) -> &[String] {
+        self.inner.aliases()
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        self.inner.state_fields(args)
+    }
+
+    fn groups_accumulator_supported(&self, args: AccumulatorArgs) -> bool {
+        self.inner.groups_accumulator_supported(args)
+    }
+
+    fn create_groups_accumulator(
+        &self,
+        args: AccumulatorArgs,
+    ) -> Result<Box<dyn GroupsAccumulator>> {
+        self.inner.create_groups_accumulator(args)
+    }
+
+    fn create_sliding_accumulator(
+        &self,
+        args: AccumulatorArgs,
+    ) -> Result<Box<dyn Accumulator>> {
+        self.inner.create_sliding_accumulator(args)
+    }
+
+    fn with_beneficial_ordering(
+        self: Arc<Self>,
+        beneficial_ordering: bool,
+    ) -> Result<Option<Arc<dyn AggregateUDFImpl>>> {
+        Arc::clone(&self.inner)
+            .with_beneficial_ordering(beneficial_ordering)
+            .map(|udf| {
+                udf.map(|udf| {
+                    Arc::new(SchemaAggregateUDFImpl {
+                        inner: udf,
+                        name: self.name.clone(),
+                    }) as Arc<dyn AggregateUDFImpl>
+                })
+            })
+    }
+
+    fn order_sensitivity(&self) -> AggregateOrderSensitivity {
+        self.inner.order_sensitivity()
+    }
+
+    fn simplify(&self) -> Option<AggregateFunctionSimplification> {
+        self.inner.simplify()
+    }
+
+    fn reverse_expr(&self) -> ReversedUDAF {
+        self.inner.reverse_expr()
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        self.inner.coerce_types(arg_types)
+    }
+
+    fn equals(&self, other: &dyn AggregateUDFImpl) -> bool {
+        if let Some(other) = other.as_any().downcast_ref::<SchemaAggregateUDFImpl>() {
+            self.inner.equals(other.inner.as_ref()) && self.name == other.name
+        } else {
+            false
+        }
+    }
+
+    fn hash_value(&self) -> u64 {
+        let hasher = &mut DefaultHasher::new();
+        self.inner.hash_value().hash(hasher);
+        self.name.hash(hasher);
+        hasher.finish()
+    }
+
+    fn is_descending(&self) -> Option<bool> {
+        self.inner.is_descending()
+    }
+
+    fn supports_within_group_clause(&self) -> bool {
+        self.inner.supports_within_group_clause()
+    }
+
+    fn value_from_stats(&self, statistics_args: &StatisticsArgs) -> Option<ScalarValue> {
+        self.inner.value_from_stats(statistics_args)
+    }
+
+    fn default_value(&self, data_type: &DataType) -> Result<ScalarValue> {
+        self.inner.default_value(data_type)
+    }
+
     fn documentation(&self) -> Option<&Documentation> {
         self.inner.documentation()
     }
+
+    fn set_monotonicity(&self, data_type: &DataType) -> SetMonotonicity {
+        self.inner.set_monotonicity(data_type)
+    }
 }
 
 // Aggregate UDF doc sections for use in public documentation
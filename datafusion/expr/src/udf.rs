@@ -24,7 +24,7 @@ use crate::{
     ColumnarValue, Documentation, Expr, ScalarFunctionImplementation, Signature,
 };
 use arrow::datatypes::DataType;
-use datafusion_common::{not_impl_err, ExprSchema, Result, ScalarValue};
+use datafusion_common::{not_impl_err, ExprSchema, Result, ScalarValue, TableReference};
 use datafusion_expr_common::interval_arithmetic::Interval;
 use std::any::Any;
 use std::cmp::Ordering;
@@ -109,6 +109,20 @@ impl ScalarUDF {
         Self::new_from_impl(AliasedScalarUDFImpl::new(Arc::clone(&self.inner), aliases))
     }
 
+    /// Qualifies this function with the schema `catalog.schema` it is
+    /// registered in.
+    ///
+    /// The returned function is named, displayed and compared by its qualified
+    /// name, so it is not confused with functions of the same name registered
+    /// in other schemas.
+    pub fn with_schema(self, catalog: &str, schema: &str) -> Self {
+        Self::new_from_impl(SchemaScalarUDFImpl::new(
+            Arc::clone(&self.inner),
+            catalog,
+            schema,
+        ))
+    }
+
     /// Returns a [`Expr`] logical expression to call this UDF with specified
     /// arguments.
     ///
@@ -961,6 +975,148 @@ impl ScalarUDFImpl for AliasedScalarUDFImpl {
     }
 }
 
+/// ScalarUDF qualified with the schema it is registered in
+#[derive(Debug)]
+struct SchemaScalarUDFImpl {
+    inner: Arc<dyn ScalarUDFImpl>,
+    /// `catalog.schema` the function is registered in
+    qualifier: String,
+    /// `catalog.schema.name` of the function
+    name: String,
+}
+
+impl SchemaScalarUDFImpl {
+    fn new(inner: Arc<dyn ScalarUDFImpl>, catalog: &str, schema: &str) -> Self {
+        let qualifier = TableReference::partial(catalog, schema).to_string();
+        let name = TableReference::full(catalog, schema, inner.name()).to_string();
+        Self {
+            inner,
+            qualifier,
+            name,
+        }
+    }
+}
+
+impl ScalarUDFImpl for SchemaScalarUDFImpl {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn display_name(&self, args: &[Expr]) -> Result<String> {
+        Ok(format!(
+            "{}.{}",
+            self.qualifier,
+            self.inner.display_name(args)?
+        ))
+    }
+
+    fn schema_name(&self, args: &[Expr]) -> Result<String> {
+        Ok(format!(
+            "{}.{}",
+            self.qualifier,
+            self.inner.schema_name(args)?
+        ))
+    }
+
+    fn signature(&self) -> &Signature {
+        self.inner.signature()
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        self.inner.return_type(arg_types)
+    }
+
+    fn aliases(&self) -> &[String] {
+        self.inner.aliases()
+    }
+
+    #[allow(deprecated)]
+    fn return_type_from_exprs(
+        &self,
+        args: &[Expr],
+        schema: &dyn ExprSchema,
+        arg_types: &[DataType],
+    ) -> Result<DataType> {
+        self.inner.return_type_from_exprs(args, schema, arg_types)
+    }
+
+    fn return_type_from_args(&self, args: ReturnTypeArgs) -> Result<ReturnInfo> {
+        self.inner.return_type_from_args(args)
+    }
+
+    fn invoke_batch(
+        &self,
+        args: &[ColumnarValue],
+        number_rows: usize,
+    ) -> Result<ColumnarValue> {
+        #[allow(deprecated)]
+        self.inner.invoke_batch(args, number_rows)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        self.inner.invoke_with_args(args)
+    }
+
+    fn simplify(
+        &self,
+        args: Vec<Expr>,
+        info: &dyn SimplifyInfo,
+    ) -> Result<ExprSimplifyResult> {
+        self.inner.simplify(args, info)
+    }
+
+    fn short_circuits(&self) -> bool {
+        self.inner.short_circuits()
+    }
+
+    fn evaluate_bounds(&self, input: &[&Interval]) -> Result<Interval> {
+        self.inner.evaluate_bounds(input)
+    }
+
+    fn propagate_constraints(
+        &self,
+        interval: &Interval,
+        inputs: &[&Interval],
+    ) -> Result<Option<Vec<Interval>>> {
+        self.inner.propagate_constraints(interval, inputs)
+    }
+
+    fn output_ordering(&self, inputs: &[ExprProperties]) -> Result<SortProperties> {
+        self.inner.output_ordering(inputs)
+    }
+
+    fn preserves_lex_ordering(&self, inputs: &[ExprProperties]) -> Result<bool> {
+        self.inner.preserves_lex_ordering(inputs)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        self.inner.coerce_types(arg_types)
+    }
+
+    fn equals(&self, other: &dyn ScalarUDFImpl) -> bool {
+        if let Some(other) = other.as_any().downcast_ref::<SchemaScalarUDFImpl>() {
+            self.inner.equals(other.inner.as_ref()) && self.name == other.name
+        } else {
+            false
+        }
+    }
+
+    fn hash_value(&self) -> u64 {
+        let hasher = &mut DefaultHasher::new();
+        self.inner.hash_value().hash(hasher);
+        self.name.hash(hasher);
+        hasher.finish()
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.inner.documentation()
+    }
+}
+
 // Scalar UDF doc sections for use in public documentation
 pub mod scalar_doc_sections {
     use crate::DocSection;
@@ -976,6 +1132,7 @@ pub mod scalar_doc_sections {
             DOC_SECTION_ARRAY,
             DOC_SECTION_STRUCT,
             DOC_SECTION_MAP,
+            DOC_SECTION_JSON,
             DOC_SECTION_HASHING,
             DOC_SECTION_OTHER,
         ]
@@ -992,6 +1149,7 @@ pub mod scalar_doc_sections {
             DOC_SECTION_ARRAY,
             DOC_SECTION_STRUCT,
             DOC_SECTION_MAP,
+            DOC_SECTION_JSON,
             DOC_SECTION_HASHING,
             DOC_SECTION_OTHER,
         ]
@@ -1056,6 +1214,19 @@ The following regular expression functions are supported:"#,
         description: None,
     };
 
+    pub const DOC_SECTION_JSON: DocSection = DocSection {
+        include: true,
+        label: "JSON Functions",
+        description: Some(
+            r#"JSON functions read values from strings containing JSON documents.
+Their arguments are a document followed by a path of object keys and array
+indexes. Documents that are not valid JSON are treated as NULL.
+The operators `->`, `->>`, `#>` and `#>>` are shorthands for `json_get` and
+`json_get_str`. To read several paths from the same document,
+`json_get_str_paths` parses it only once."#,
+        ),
+    };
+
     pub const DOC_SECTION_HASHING: DocSection = DocSection {
         include: true,
         label: "Hashing Functions",
@@ -32,7 +32,7 @@ use crate::expr::WindowFunction;
 use crate::{
     function::WindowFunctionSimplification, Expr, PartitionEvaluator, Signature,
 };
-use datafusion_common::{not_impl_err, Result};
+use datafusion_common::{not_impl_err, Result, TableReference};
 use datafusion_doc::Documentation;
 use datafusion_functions_window_common::expr::ExpressionArgs;
 use datafusion_functions_window_common::field::WindowUDFFieldArgs;
@@ -118,6 +118,19 @@ impl WindowUDF {
         Self::new_from_impl(AliasedWindowUDFImpl::new(Arc::clone(&self.inner), aliases))
     }
 
+    /// Qualifies this function with the schema `catalog.schema` it is
+    /// registered in.
+    ///
+    /// See [`ScalarUDF::with_schema`](crate::ScalarUDF::with_schema) for
+    /// details.
+    pub fn with_schema(self, catalog: &str, schema: &str) -> Self {
+        Self::new_from_impl(SchemaWindowUDFImpl::new(
+            Arc::clone(&self.inner),
+            catalog,
+            schema,
+        ))
+    }
+
     /// creates a [`Expr`] that calls the window function with default
     /// values for `order_by`, `partition_by`, `window_frame`.
     ///
@@ -551,6 +564,89 @@ impl WindowUDFImpl for AliasedWindowUDFImpl {
     }
 }
 
+/// WindowUDF qualified with the schema it is registered in
+#[derive(Debug)]
+struct SchemaWindowUDFImpl {
+    inner: Arc<dyn WindowUDFImpl>,
+    /// `catalog.schema.name` of the function
+    name: String,
+}
+
+impl SchemaWindowUDFImpl {
+    fn new(inner: Arc<dyn WindowUDFImpl>, catalog: &str, schema: &str) -> Self {
+        let name = TableReference::full(catalog, schema, inner.name()).to_string();
+        Self { inner, name }
+    }
+}
+
+impl WindowUDFImpl for SchemaWindowUDFImpl {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        self.inner.signature()
+    }
+
+    fn expressions(&self, expr_args: ExpressionArgs) -> Vec<Arc<dyn PhysicalExpr>> {
+        self.inner.expressions(expr_args)
+    }
+
+    fn partition_evaluator(
+        &self,
+        partition_evaluator_args: PartitionEvaluatorArgs,
+    ) -> Result<Box<dyn PartitionEvaluator>> {
+        self.inner.partition_evaluator(partition_evaluator_args)
+    }
+
+    fn aliases(&self) -> &[String] {
+        self.inner.aliases()
+    }
+
+    fn simplify(&self) -> Option<WindowFunctionSimplification> {
+        self.inner.simplify()
+    }
+
+    fn equals(&self, other: &dyn WindowUDFImpl) -> bool {
+        if let Some(other) = other.as_any().downcast_ref::<SchemaWindowUDFImpl>() {
+            self.inner.equals(other.inner.as_ref()) && self.name == other.name
+        } else {
+            false
+        }
+    }
+
+    fn hash_value(&self) -> u64 {
+        let hasher = &mut DefaultHasher::new();
+        self.inner.hash_value().hash(hasher);
+        self.name.hash(hasher);
+        hasher.finish()
+    }
+
+    fn field(&self, field_args: WindowUDFFieldArgs) -> Result<Field> {
+        self.inner.field(field_args)
+    }
+
+    fn sort_options(&self) -> Option<SortOptions> {
+        self.inner.sort_options()
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        self.inner.coerce_types(arg_types)
+    }
+
+    fn reverse_expr(&self) -> ReversedUDWF {
+        self.inner.reverse_expr()
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.inner.documentation()
+    }
+}
+
 // Window UDF doc sections for use in public documentation
 pub mod window_doc_sections {
     use datafusion_doc::DocSection;
@@ -22,7 +22,7 @@ use std::collections::{BTreeSet, HashSet};
 use std::ops::Deref;
 use std::sync::Arc;
 
-use crate::expr::{Alias, Sort, WildcardOptions, WindowFunction};
+use crate::expr::{Alias, ScalarFunction, Sort, WildcardOptions, WindowFunction};
 use crate::expr_rewriter::strip_outer_reference;
 use crate::{
     and, BinaryExpr, Expr, ExprSchemable, Filter, GroupingSet, LogicalPlan, Operator,
@@ -310,6 +310,7 @@ pub fn expr_to_columns(expr: &Expr, accum: &mut HashSet<Column>) -> Result<()> {
             | Expr::InList { .. }
             | Expr::Exists { .. }
             | Expr::InSubquery(_)
+            | Expr::SetComparison(_)
             | Expr::ScalarSubquery(_)
             | Expr::Wildcard { .. }
             | Expr::Placeholder(_)
@@ -780,9 +781,11 @@ pub fn find_base_plan(input: &LogicalPlan) -> &LogicalPlan {
             }
         }
         LogicalPlan::Filter(filter) => {
-            if filter.having {
+            if filter.having || matches!(filter.input.as_ref(), LogicalPlan::Window(_)) {
                 // If a filter is used for a having clause, its input plan is an aggregation.
-                // We should expand the wildcard expression based on the aggregation's input plan.
+                // If a filter is used for a qualify clause, its input plan is a window.
+                // We should expand the wildcard expression based on the input plan of the
+                // aggregation or window.
                 find_base_plan(&filter.input)
             } else {
                 input
@@ -1364,6 +1367,22 @@ pub fn find_join_exprs(exprs: Vec<&Expr>) -> Result<(Vec<Expr>, Vec<Expr>)> {
     Ok((joins, others))
 }
 
+/// Returns the values of `expr` if it is a row value of `len` values
+///
+/// Row values such as `(a, b)` are planned as calls to the `struct` function.
+/// In `(a, b) IN (SELECT x, y ...)` they are compared value by value with the
+/// columns of the subquery.
+pub fn row_values(expr: &Expr, len: usize) -> Option<&[Expr]> {
+    match expr {
+        Expr::ScalarFunction(ScalarFunction { func, args })
+            if len > 1 && func.name() == "struct" && args.len() == len =>
+        {
+            Some(args)
+        }
+        _ => None,
+    }
+}
+
 /// Returns the first (and only) element in a slice, or an error
 ///
 /// # Arguments
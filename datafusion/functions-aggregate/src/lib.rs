@@ -67,6 +67,8 @@ pub mod first_last;
 pub mod hyperloglog;
 pub mod median;
 pub mod min_max;
+pub mod mode;
+pub mod percentile;
 pub mod regr;
 pub mod stddev;
 pub mod sum;
@@ -114,7 +116,10 @@ pub mod expr_fn {
     pub use super::median::median;
     pub use super::min_max::max;
     pub use super::min_max::min;
+    pub use super::mode::mode;
     pub use super::nth_value::nth_value;
+    pub use super::percentile::percentile_cont;
+    pub use super::percentile::percentile_disc;
     pub use super::regr::regr_avgx;
     pub use super::regr::regr_avgy;
     pub use super::regr::regr_count;
@@ -144,6 +149,9 @@ pub fn all_default_aggregate_functions() -> Vec<Arc<AggregateUDF>> {
         min_max::max_udaf(),
         min_max::min_udaf(),
         median::median_udaf(),
+        mode::mode_udaf(),
+        percentile::percentile_cont_udaf(),
+        percentile::percentile_disc_udaf(),
         count::count_udaf(),
         regr::regr_slope_udaf(),
         regr::regr_intercept_udaf(),
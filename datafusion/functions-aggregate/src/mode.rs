@@ -0,0 +1,649 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the `MODE` aggregate expression
+
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter};
+use std::mem::{size_of, size_of_val};
+use std::sync::Arc;
+
+use arrow::array::{
+    downcast_primitive, Array, ArrayRef, ArrowNativeTypeOp, ArrowPrimitiveType, AsArray,
+    BooleanArray, Int64Array, ListArray, PrimitiveArray, PrimitiveBuilder,
+};
+use arrow::buffer::{OffsetBuffer, ScalarBuffer};
+use arrow::datatypes::{DataType, Field, Int64Type};
+
+use datafusion_common::{
+    internal_datafusion_err, not_impl_err, HashMap, Result, ScalarValue,
+};
+use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion_expr::utils::{format_state_name, AggregateOrderSensitivity};
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, Documentation, EmitTo, GroupsAccumulator, Signature,
+    Volatility,
+};
+use datafusion_functions_aggregate_common::aggregate::groups_accumulator::accumulate::accumulate;
+use datafusion_functions_aggregate_common::aggregate::groups_accumulator::nulls::filtered_null_mask;
+use datafusion_functions_aggregate_common::utils::Hashable;
+use datafusion_macros::user_doc;
+
+make_udaf_expr_and_func!(
+    Mode,
+    mode,
+    expression,
+    "Returns the most frequent value of a set of values",
+    mode_udaf
+);
+
+#[user_doc(
+    doc_section(label = "General Functions"),
+    description = "Returns the most frequent input value. If several values are equally frequent, the smallest one is returned, or the largest one when ordering descending.",
+    syntax_example = "mode() WITHIN GROUP (ORDER BY expression)",
+    sql_example = r#"```sql
+> SELECT mode() WITHIN GROUP (ORDER BY column_name) FROM table_name;
++----------------------------------+
+| mode(table_name.column_name)     |
++----------------------------------+
+| 42                               |
++----------------------------------+
+```"#,
+    argument(
+        name = "expression",
+        description = "Expression to compute the mode of. Can also be passed as the only argument, as in `mode(expression)`."
+    )
+)]
+/// MODE aggregate expression. This keeps a count for every distinct input
+/// value, so memory usage grows with the cardinality of the input.
+#[derive(Debug)]
+pub struct Mode {
+    signature: Signature,
+}
+
+impl Default for Mode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Mode {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for Mode {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "mode"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(arg_types[0].clone())
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        // Intermediate state is the list of distinct values seen so far,
+        // and the matching list of their counts
+        let values = Field::new_list_field(args.input_types[0].clone(), true);
+        let counts = Field::new_list_field(DataType::Int64, true);
+        Ok(vec![
+            Field::new(
+                format_state_name(args.name, "values"),
+                DataType::List(Arc::new(values)),
+                true,
+            ),
+            Field::new(
+                format_state_name(args.name, "counts"),
+                DataType::List(Arc::new(counts)),
+                true,
+            ),
+        ])
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        if acc_args.is_distinct {
+            return not_impl_err!("MODE(DISTINCT) aggregations are not supported");
+        }
+        let descending = is_descending(&acc_args);
+
+        macro_rules! helper {
+            ($t:ty, $dt:expr) => {
+                Ok(Box::new(PrimitiveModeAccumulator::<$t> {
+                    data_type: $dt.clone(),
+                    counts: HashMap::new(),
+                    descending,
+                }))
+            };
+        }
+
+        let dt = acc_args.exprs[0].data_type(acc_args.schema)?;
+        downcast_primitive! {
+            dt => (helper, dt),
+            _ => Ok(Box::new(ModeAccumulator {
+                data_type: dt,
+                counts: HashMap::new(),
+                descending,
+            })),
+        }
+    }
+
+    fn groups_accumulator_supported(&self, args: AccumulatorArgs) -> bool {
+        !args.is_distinct
+            && args.exprs[0]
+                .data_type(args.schema)
+                .is_ok_and(|dt| dt.is_primitive())
+    }
+
+    fn create_groups_accumulator(
+        &self,
+        args: AccumulatorArgs,
+    ) -> Result<Box<dyn GroupsAccumulator>> {
+        let descending = is_descending(&args);
+
+        macro_rules! helper {
+            ($t:ty, $dt:expr) => {
+                Ok(Box::new(ModeGroupsAccumulator::<$t>::new($dt, descending)))
+            };
+        }
+
+        let dt = args.exprs[0].data_type(args.schema)?;
+        downcast_primitive! {
+            dt => (helper, dt),
+            _ => not_impl_err!(
+                "ModeGroupsAccumulator not supported for {} with {}",
+                args.name,
+                dt
+            ),
+        }
+    }
+
+    fn order_sensitivity(&self) -> AggregateOrderSensitivity {
+        // The WITHIN GROUP ordering only breaks ties between equally
+        // frequent values, the input does not need to be sorted
+        AggregateOrderSensitivity::Insensitive
+    }
+
+    fn supports_within_group_clause(&self) -> bool {
+        true
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+fn is_descending(args: &AccumulatorArgs) -> bool {
+    args.ordering_req
+        .iter()
+        .next()
+        .is_some_and(|sort_expr| sort_expr.options.descending)
+}
+
+/// Returns the most frequent value, breaking ties by picking the smallest
+/// value, or the largest one if `descending`
+fn most_frequent<'a, V: 'a>(
+    counts: impl Iterator<Item = (&'a V, &'a i64)>,
+    cmp: impl Fn(&V, &V) -> Ordering,
+    descending: bool,
+) -> Option<&'a V> {
+    counts
+        .max_by(|(x, x_count), (y, y_count)| {
+            x_count.cmp(y_count).then_with(|| {
+                let ordering = cmp(x, y);
+                if descending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            })
+        })
+        .map(|(value, _)| value)
+}
+
+/// Builds a single-element list array with the values and a matching list
+/// of their counts, representing the intermediate state of one group
+fn build_state(values: ArrayRef, counts: Vec<i64>) -> [ListArray; 2] {
+    let offsets = OffsetBuffer::from_lengths([counts.len()]);
+    [
+        ListArray::new(
+            Arc::new(Field::new_list_field(values.data_type().clone(), true)),
+            offsets.clone(),
+            values,
+            None,
+        ),
+        ListArray::new(
+            Arc::new(Field::new_list_field(DataType::Int64, true)),
+            offsets,
+            Arc::new(Int64Array::from(counts)),
+            None,
+        ),
+    ]
+}
+
+/// The mode accumulator for primitive types, which counts the occurrences
+/// of each distinct value
+struct PrimitiveModeAccumulator<T: ArrowPrimitiveType> {
+    data_type: DataType,
+    counts: HashMap<Hashable<T::Native>, i64>,
+    descending: bool,
+}
+
+impl<T: ArrowPrimitiveType> Debug for PrimitiveModeAccumulator<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PrimitiveModeAccumulator({})", self.data_type)
+    }
+}
+
+impl<T: ArrowPrimitiveType> Accumulator for PrimitiveModeAccumulator<T> {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let (values, counts): (Vec<_>, Vec<_>) = self
+            .counts
+            .iter()
+            .map(|(value, count)| (value.0, count))
+            .unzip();
+        let values = PrimitiveArray::<T>::new(ScalarBuffer::from(values), None)
+            .with_data_type(self.data_type.clone());
+        let [values, counts] = build_state(Arc::new(values), counts);
+        Ok(vec![
+            ScalarValue::List(Arc::new(values)),
+            ScalarValue::List(Arc::new(counts)),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = values[0].as_primitive::<T>();
+        for value in values.iter().flatten() {
+            *self.counts.entry(Hashable(value)).or_default() += 1;
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let values = states[0].as_list::<i32>();
+        let counts = states[1].as_list::<i32>();
+        for (values, counts) in values.iter().zip(counts.iter()) {
+            if let (Some(values), Some(counts)) = (values, counts) {
+                let values = values.as_primitive::<T>();
+                let counts = counts.as_primitive::<Int64Type>();
+                for (value, count) in values.values().iter().zip(counts.values()) {
+                    *self.counts.entry(Hashable(*value)).or_default() += count;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let mode =
+            most_frequent(self.counts.iter(), |x, y| x.0.compare(y.0), self.descending);
+        ScalarValue::new_primitive::<T>(mode.map(|value| value.0), &self.data_type)
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self)
+            + self.counts.capacity() * (size_of::<T::Native>() + size_of::<i64>())
+    }
+}
+
+/// The mode accumulator for all other types, which counts the occurrences of
+/// each distinct value as a `ScalarValue`
+#[derive(Debug)]
+struct ModeAccumulator {
+    data_type: DataType,
+    counts: HashMap<ScalarValue, i64>,
+    descending: bool,
+}
+
+impl ModeAccumulator {
+    fn add_values(
+        &mut self,
+        values: &ArrayRef,
+        counts: Option<&Int64Array>,
+    ) -> Result<()> {
+        for index in 0..values.len() {
+            if values.is_null(index) {
+                continue;
+            }
+            let value = ScalarValue::try_from_array(values, index)?;
+            let count = counts.map_or(1, |counts| counts.value(index));
+            *self.counts.entry(value).or_default() += count;
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for ModeAccumulator {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let (values, counts): (Vec<_>, Vec<_>) = self
+            .counts
+            .iter()
+            .map(|(value, count)| (value.clone(), count))
+            .unzip();
+        let values = if values.is_empty() {
+            arrow::array::new_empty_array(&self.data_type)
+        } else {
+            ScalarValue::iter_to_array(values)?
+        };
+        let [values, counts] = build_state(values, counts);
+        Ok(vec![
+            ScalarValue::List(Arc::new(values)),
+            ScalarValue::List(Arc::new(counts)),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.add_values(&values[0], None)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let values = states[0].as_list::<i32>();
+        let counts = states[1].as_list::<i32>();
+        for (values, counts) in values.iter().zip(counts.iter()) {
+            if let (Some(values), Some(counts)) = (values, counts) {
+                self.add_values(&values, Some(counts.as_primitive::<Int64Type>()))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let mode = most_frequent(
+            self.counts.iter(),
+            |x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal),
+            self.descending,
+        );
+        match mode {
+            Some(value) => Ok(value.clone()),
+            None => ScalarValue::try_from(&self.data_type),
+        }
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self)
+            + self
+                .counts
+                .keys()
+                .map(|value| value.size() + size_of::<i64>())
+                .sum::<usize>()
+    }
+}
+
+/// The mode groups accumulator keeps the count of each distinct value for
+/// every group
+#[derive(Debug)]
+struct ModeGroupsAccumulator<T: ArrowPrimitiveType + Send> {
+    data_type: DataType,
+    group_counts: Vec<HashMap<Hashable<T::Native>, i64>>,
+    descending: bool,
+}
+
+impl<T: ArrowPrimitiveType + Send> ModeGroupsAccumulator<T> {
+    fn new(data_type: DataType, descending: bool) -> Self {
+        Self {
+            data_type,
+            group_counts: Vec::new(),
+            descending,
+        }
+    }
+}
+
+impl<T: ArrowPrimitiveType + Send> GroupsAccumulator for ModeGroupsAccumulator<T> {
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        assert_eq!(values.len(), 1, "single argument to update_batch");
+        let values = values[0].as_primitive::<T>();
+
+        self.group_counts.resize(total_num_groups, HashMap::new());
+        accumulate(
+            group_indices,
+            values,
+            opt_filter,
+            |group_index, new_value| {
+                *self.group_counts[group_index]
+                    .entry(Hashable(new_value))
+                    .or_default() += 1;
+            },
+        );
+
+        Ok(())
+    }
+
+    fn merge_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        // Since aggregate filter should be applied in partial stage, in final stage there should be no filter
+        _opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        assert_eq!(values.len(), 2, "two arguments to merge_batch");
+        let input_values = values[0].as_list::<i32>();
+        let input_counts = values[1].as_list::<i32>();
+
+        self.group_counts.resize(total_num_groups, HashMap::new());
+        for (&group_index, (values, counts)) in group_indices
+            .iter()
+            .zip(input_values.iter().zip(input_counts.iter()))
+        {
+            if let (Some(values), Some(counts)) = (values, counts) {
+                let group_counts = &mut self.group_counts[group_index];
+                let values = values.as_primitive::<T>();
+                let counts = counts.as_primitive::<Int64Type>();
+                for (value, count) in values.values().iter().zip(counts.values()) {
+                    *group_counts.entry(Hashable(*value)).or_default() += count;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn state(&mut self, emit_to: EmitTo) -> Result<Vec<ArrayRef>> {
+        let emit_group_counts = emit_to.take_needed(&mut self.group_counts);
+
+        let offsets =
+            OffsetBuffer::from_lengths(emit_group_counts.iter().map(HashMap::len));
+        let (values, counts): (Vec<_>, Vec<_>) = emit_group_counts
+            .into_iter()
+            .flatten()
+            .map(|(value, count)| (value.0, count))
+            .unzip();
+        let values = PrimitiveArray::<T>::new(ScalarBuffer::from(values), None)
+            .with_data_type(self.data_type.clone());
+
+        let values = ListArray::new(
+            Arc::new(Field::new_list_field(self.data_type.clone(), true)),
+            offsets.clone(),
+            Arc::new(values),
+            None,
+        );
+        let counts = ListArray::new(
+            Arc::new(Field::new_list_field(DataType::Int64, true)),
+            offsets,
+            Arc::new(Int64Array::from(counts)),
+            None,
+        );
+
+        Ok(vec![Arc::new(values), Arc::new(counts)])
+    }
+
+    fn evaluate(&mut self, emit_to: EmitTo) -> Result<ArrayRef> {
+        let emit_group_counts = emit_to.take_needed(&mut self.group_counts);
+
+        let mut builder =
+            PrimitiveBuilder::<T>::new().with_data_type(self.data_type.clone());
+        for counts in emit_group_counts {
+            let mode =
+                most_frequent(counts.iter(), |x, y| x.0.compare(y.0), self.descending);
+            builder.append_option(mode.map(|value| value.0));
+        }
+
+        Ok(Arc::new(builder.finish()))
+    }
+
+    fn convert_to_state(
+        &self,
+        values: &[ArrayRef],
+        opt_filter: Option<&BooleanArray>,
+    ) -> Result<Vec<ArrayRef>> {
+        let input_array = values[0].as_primitive::<T>();
+
+        // Each row is converted to a list with a single value and a count
+        // of one, or to null lists if the row is null or filtered out
+        let values = PrimitiveArray::<T>::new(input_array.values().clone(), None)
+            .with_data_type(self.data_type.clone());
+        let counts = Int64Array::from_value(1, input_array.len());
+
+        let offset_end = i32::try_from(input_array.len()).map_err(|e| {
+            internal_datafusion_err!(
+                "cast array_len to i32 failed in convert_to_state of group mode, err:{e:?}"
+            )
+        })?;
+        let offsets = (0..=offset_end).collect::<Vec<_>>();
+        // Safety: all checks in `OffsetBuffer::new` are ensured to pass
+        let offsets = unsafe { OffsetBuffer::new_unchecked(ScalarBuffer::from(offsets)) };
+
+        let nulls = filtered_null_mask(opt_filter, input_array);
+
+        let values = ListArray::new(
+            Arc::new(Field::new_list_field(self.data_type.clone(), true)),
+            offsets.clone(),
+            Arc::new(values),
+            nulls.clone(),
+        );
+        let counts = ListArray::new(
+            Arc::new(Field::new_list_field(DataType::Int64, true)),
+            offsets,
+            Arc::new(counts),
+            nulls,
+        );
+
+        Ok(vec![Arc::new(values), Arc::new(counts)])
+    }
+
+    fn supports_convert_to_state(&self) -> bool {
+        true
+    }
+
+    fn size(&self) -> usize {
+        self.group_counts
+            .iter()
+            .map(|counts| counts.capacity() * (size_of::<T::Native>() + size_of::<i64>()))
+            .sum::<usize>()
+            + self.group_counts.capacity()
+                * size_of::<HashMap<Hashable<T::Native>, i64>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::Int32Type;
+
+    #[test]
+    fn mode_breaks_ties_by_ordering() -> Result<()> {
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(3),
+            Some(1),
+            None,
+            Some(3),
+            Some(1),
+        ]));
+        for (descending, expected) in [(false, 1), (true, 3)] {
+            let mut acc = PrimitiveModeAccumulator::<Int32Type> {
+                data_type: DataType::Int32,
+                counts: HashMap::new(),
+                descending,
+            };
+            acc.update_batch(&[Arc::clone(&values)])?;
+            assert_eq!(acc.evaluate()?, ScalarValue::Int32(Some(expected)));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn mode_accumulator_merge() -> Result<()> {
+        let mut acc = ModeAccumulator {
+            data_type: DataType::Utf8,
+            counts: HashMap::new(),
+            descending: false,
+        };
+        let values: ArrayRef = Arc::new(StringArray::from(vec!["b", "a", "b"]));
+        acc.update_batch(&[values])?;
+
+        let mut other = ModeAccumulator {
+            data_type: DataType::Utf8,
+            counts: HashMap::new(),
+            descending: false,
+        };
+        let values: ArrayRef = Arc::new(StringArray::from(vec!["a", "a"]));
+        other.update_batch(&[values])?;
+        let state = other
+            .state()?
+            .iter()
+            .map(|value| value.to_array())
+            .collect::<Result<Vec<_>>>()?;
+        acc.merge_batch(&state)?;
+
+        assert_eq!(acc.evaluate()?, ScalarValue::from("a"));
+        Ok(())
+    }
+
+    #[test]
+    fn mode_groups_accumulator() -> Result<()> {
+        let mut acc = ModeGroupsAccumulator::<Int32Type>::new(DataType::Int32, false);
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(1),
+            Some(5),
+            None,
+            Some(2),
+            Some(5),
+        ]));
+        acc.update_batch(&[values], &[0, 1, 0, 0, 1], None, 3)?;
+
+        // Merge in the state of a second accumulator
+        let mut other = ModeGroupsAccumulator::<Int32Type>::new(DataType::Int32, false);
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![2, 6]));
+        other.update_batch(&[values], &[0, 1], None, 2)?;
+        let state = other.state(EmitTo::All)?;
+        acc.merge_batch(&state, &[0, 1], None, 3)?;
+
+        let result = acc.evaluate(EmitTo::All)?;
+        let result = result.as_primitive::<Int32Type>();
+        assert_eq!(result.value(0), 2);
+        assert_eq!(result.value(1), 5);
+        assert!(result.is_null(2));
+        Ok(())
+    }
+}
@@ -0,0 +1,799 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines exact `PERCENTILE_CONT` and `PERCENTILE_DISC` aggregate expressions
+
+use std::fmt::{Debug, Formatter};
+use std::mem::{size_of, size_of_val};
+use std::sync::Arc;
+
+use arrow::array::{
+    downcast_integer, Array, ArrayRef, ArrowNativeTypeOp, ArrowNumericType, AsArray,
+    BooleanArray, ListArray, PrimitiveArray, PrimitiveBuilder, RecordBatch,
+};
+use arrow::buffer::{OffsetBuffer, ScalarBuffer};
+use arrow::datatypes::{
+    ArrowPrimitiveType, DataType, Decimal128Type, Decimal256Type, Field, Float16Type,
+    Float32Type, Float64Type, Schema,
+};
+
+use datafusion_common::{
+    internal_datafusion_err, internal_err, not_impl_err, plan_err, DataFusionError,
+    Result, ScalarValue,
+};
+use datafusion_expr::function::{AccumulatorArgs, StateFieldsArgs};
+use datafusion_expr::utils::{format_state_name, AggregateOrderSensitivity};
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, ColumnarValue, Documentation, EmitTo,
+    GroupsAccumulator, Signature, Volatility,
+};
+use datafusion_functions_aggregate_common::aggregate::groups_accumulator::accumulate::accumulate;
+use datafusion_functions_aggregate_common::aggregate::groups_accumulator::nulls::filtered_null_mask;
+use datafusion_macros::user_doc;
+use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
+
+make_udaf_expr_and_func!(
+    PercentileCont,
+    percentile_cont,
+    expression percentile,
+    "Computes the exact continuous percentile of a set of numbers",
+    percentile_cont_udaf
+);
+
+make_udaf_expr_and_func!(
+    PercentileDisc,
+    percentile_disc,
+    expression percentile,
+    "Computes the exact discrete percentile of a set of numbers",
+    percentile_disc_udaf
+);
+
+#[user_doc(
+    doc_section(label = "General Functions"),
+    description = "Returns the exact percentile of input values, interpolating between adjacent input values if needed.",
+    syntax_example = "percentile_cont(percentile) WITHIN GROUP (ORDER BY expression)",
+    sql_example = r#"```sql
+> SELECT percentile_cont(0.75) WITHIN GROUP (ORDER BY column_name) FROM table_name;
++-----------------------------------------------------------------------+
+| percentile_cont(table_name.column_name,Float64(0.75))                 |
++-----------------------------------------------------------------------+
+| 45.5                                                                  |
++-----------------------------------------------------------------------+
+```"#,
+    argument(
+        name = "percentile",
+        description = "Percentile to compute. Must be a float value between 0 and 1 (inclusive)."
+    ),
+    argument(
+        name = "expression",
+        description = "Expression to compute the percentile of. Sorting in descending order computes the percentile from the top. Can also be passed as the first argument, as in `percentile_cont(expression, percentile)`."
+    )
+)]
+/// PERCENTILE_CONT aggregate expression. Like MEDIAN, this stores all input
+/// values in memory before a result can be computed. If an approximation is
+/// sufficient then APPROX_PERCENTILE_CONT provides a much more efficient
+/// solution.
+#[derive(Debug)]
+pub struct PercentileCont {
+    signature: Signature,
+}
+
+impl Default for PercentileCont {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PercentileCont {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for PercentileCont {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "percentile_cont"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        let [expression, percentile] = check_arg_types(self.name(), arg_types)?;
+        if !is_numeric_or_null(expression) {
+            return plan_err!(
+                "{} requires a numeric input, got {expression}",
+                self.name()
+            );
+        }
+        check_percentile_type(self.name(), percentile)?;
+        Ok(vec![DataType::Float64, DataType::Float64])
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        percentile_state_fields(args)
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        let args = percentile_args(&acc_args)?;
+        Ok(Box::new(PercentileAccumulator::<Float64Type> {
+            data_type: DataType::Float64,
+            all_values: vec![],
+            args,
+            percentile_fn: calculate_percentile_cont,
+        }))
+    }
+
+    fn groups_accumulator_supported(&self, args: AccumulatorArgs) -> bool {
+        !args.is_distinct
+    }
+
+    fn create_groups_accumulator(
+        &self,
+        args: AccumulatorArgs,
+    ) -> Result<Box<dyn GroupsAccumulator>> {
+        let percentile_args = percentile_args(&args)?;
+        Ok(Box::new(PercentileGroupsAccumulator::<Float64Type>::new(
+            DataType::Float64,
+            percentile_args,
+            calculate_percentile_cont,
+        )))
+    }
+
+    fn order_sensitivity(&self) -> AggregateOrderSensitivity {
+        // The WITHIN GROUP ordering only determines the direction in which
+        // the percentile is taken, the input does not need to be sorted
+        AggregateOrderSensitivity::Insensitive
+    }
+
+    fn supports_within_group_clause(&self) -> bool {
+        true
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+#[user_doc(
+    doc_section(label = "General Functions"),
+    description = "Returns the first input value whose position in the ordering equals or exceeds the specified percentile.",
+    syntax_example = "percentile_disc(percentile) WITHIN GROUP (ORDER BY expression)",
+    sql_example = r#"```sql
+> SELECT percentile_disc(0.75) WITHIN GROUP (ORDER BY column_name) FROM table_name;
++-----------------------------------------------------------------------+
+| percentile_disc(table_name.column_name,Float64(0.75))                 |
++-----------------------------------------------------------------------+
+| 45                                                                    |
++-----------------------------------------------------------------------+
+```"#,
+    argument(
+        name = "percentile",
+        description = "Percentile to compute. Must be a float value between 0 and 1 (inclusive)."
+    ),
+    argument(
+        name = "expression",
+        description = "Expression to compute the percentile of. Sorting in descending order computes the percentile from the top. Can also be passed as the first argument, as in `percentile_disc(expression, percentile)`."
+    )
+)]
+/// PERCENTILE_DISC aggregate expression. Unlike PERCENTILE_CONT, the result is
+/// always one of the input values, so the input type is preserved.
+#[derive(Debug)]
+pub struct PercentileDisc {
+    signature: Signature,
+}
+
+impl Default for PercentileDisc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PercentileDisc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for PercentileDisc {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "percentile_disc"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(arg_types[0].clone())
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        let [expression, percentile] = check_arg_types(self.name(), arg_types)?;
+        let expression = match expression {
+            DataType::Null => DataType::Float64,
+            dt if dt.is_numeric() => dt.clone(),
+            dt => return plan_err!("{} requires a numeric input, got {dt}", self.name()),
+        };
+        check_percentile_type(self.name(), percentile)?;
+        Ok(vec![expression, DataType::Float64])
+    }
+
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        percentile_state_fields(args)
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        let args = percentile_args(&acc_args)?;
+
+        macro_rules! helper {
+            ($t:ty, $dt:expr) => {
+                Ok(Box::new(PercentileAccumulator::<$t> {
+                    data_type: $dt.clone(),
+                    all_values: vec![],
+                    args,
+                    percentile_fn: calculate_percentile_disc::<$t>,
+                }))
+            };
+        }
+
+        let dt = acc_args.exprs[0].data_type(acc_args.schema)?;
+        downcast_integer! {
+            dt => (helper, dt),
+            DataType::Float16 => helper!(Float16Type, dt),
+            DataType::Float32 => helper!(Float32Type, dt),
+            DataType::Float64 => helper!(Float64Type, dt),
+            DataType::Decimal128(_, _) => helper!(Decimal128Type, dt),
+            DataType::Decimal256(_, _) => helper!(Decimal256Type, dt),
+            _ => Err(DataFusionError::NotImplemented(format!(
+                "PercentileAccumulator not supported for {} with {}",
+                acc_args.name,
+                dt,
+            ))),
+        }
+    }
+
+    fn groups_accumulator_supported(&self, args: AccumulatorArgs) -> bool {
+        !args.is_distinct
+    }
+
+    fn create_groups_accumulator(
+        &self,
+        args: AccumulatorArgs,
+    ) -> Result<Box<dyn GroupsAccumulator>> {
+        let percentile_args = percentile_args(&args)?;
+
+        macro_rules! helper {
+            ($t:ty, $dt:expr) => {
+                Ok(Box::new(PercentileGroupsAccumulator::<$t>::new(
+                    $dt,
+                    percentile_args,
+                    calculate_percentile_disc::<$t>,
+                )))
+            };
+        }
+
+        let dt = args.exprs[0].data_type(args.schema)?;
+        downcast_integer! {
+            dt => (helper, dt),
+            DataType::Float16 => helper!(Float16Type, dt),
+            DataType::Float32 => helper!(Float32Type, dt),
+            DataType::Float64 => helper!(Float64Type, dt),
+            DataType::Decimal128(_, _) => helper!(Decimal128Type, dt),
+            DataType::Decimal256(_, _) => helper!(Decimal256Type, dt),
+            _ => Err(DataFusionError::NotImplemented(format!(
+                "PercentileGroupsAccumulator not supported for {} with {}",
+                args.name,
+                dt,
+            ))),
+        }
+    }
+
+    fn order_sensitivity(&self) -> AggregateOrderSensitivity {
+        AggregateOrderSensitivity::Insensitive
+    }
+
+    fn supports_within_group_clause(&self) -> bool {
+        true
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+fn check_arg_types<'a>(
+    name: &str,
+    arg_types: &'a [DataType],
+) -> Result<[&'a DataType; 2]> {
+    match arg_types {
+        [expression, percentile] => Ok([expression, percentile]),
+        _ => plan_err!(
+            "{name} expects a percentile argument and exactly one ordering expression, got {} arguments",
+            arg_types.len()
+        ),
+    }
+}
+
+fn check_percentile_type(name: &str, percentile: &DataType) -> Result<()> {
+    if is_numeric_or_null(percentile) {
+        Ok(())
+    } else {
+        plan_err!("{name} requires a numeric percentile, got {percentile}")
+    }
+}
+
+fn is_numeric_or_null(data_type: &DataType) -> bool {
+    data_type.is_numeric() || data_type.is_null()
+}
+
+fn percentile_state_fields(args: StateFieldsArgs) -> Result<Vec<Field>> {
+    // Intermediate state is a list of the elements we have collected so far
+    let field = Field::new_list_field(args.input_types[0].clone(), true);
+    Ok(vec![Field::new(
+        format_state_name(args.name, "percentile"),
+        DataType::List(Arc::new(field)),
+        true,
+    )])
+}
+
+/// Options for computing a percentile over the collected values
+#[derive(Debug, Clone, Copy)]
+struct PercentileArgs {
+    /// Requested percentile, between 0.0 and 1.0 inclusive
+    percentile: f64,
+    /// Whether the values are ordered descending, as in
+    /// `WITHIN GROUP (ORDER BY x DESC)`
+    descending: bool,
+}
+
+/// Extracts the percentile and the ordering direction from the arguments
+fn percentile_args(args: &AccumulatorArgs) -> Result<PercentileArgs> {
+    if args.is_distinct {
+        return not_impl_err!("{}(DISTINCT) aggregations are not supported", args.name);
+    }
+    if args.exprs.len() != 2 {
+        return internal_err!(
+            "{} should have 2 args, but found num args:{}",
+            args.name,
+            args.exprs.len()
+        );
+    }
+    let percentile = validate_percentile_expr(&args.exprs[1])?;
+    let descending = args
+        .ordering_req
+        .iter()
+        .next()
+        .is_some_and(|sort_expr| sort_expr.options.descending);
+    Ok(PercentileArgs {
+        percentile,
+        descending,
+    })
+}
+
+fn validate_percentile_expr(expr: &Arc<dyn PhysicalExpr>) -> Result<f64> {
+    let batch = RecordBatch::new_empty(Arc::new(Schema::empty()));
+    let percentile = match expr.evaluate(&batch) {
+        Ok(ColumnarValue::Scalar(ScalarValue::Float64(Some(value)))) => value,
+        Ok(ColumnarValue::Scalar(ScalarValue::Float64(None))) => {
+            return plan_err!("Percentile value must not be NULL")
+        }
+        _ => {
+            return plan_err!("Percentile value must be a literal, got: {expr}");
+        }
+    };
+
+    if !(0.0..=1.0).contains(&percentile) {
+        return plan_err!(
+            "Percentile value must be between 0.0 and 1.0 inclusive, {percentile} is invalid"
+        );
+    }
+    Ok(percentile)
+}
+
+/// Computes a percentile over a mutable slice of values, which may be
+/// reordered in the process
+type PercentileFn<T> = fn(
+    &mut [<T as ArrowPrimitiveType>::Native],
+    PercentileArgs,
+) -> Option<<T as ArrowPrimitiveType>::Native>;
+
+/// The percentile accumulator accumulates the raw input values, and computes
+/// the percentile once all values have been collected
+struct PercentileAccumulator<T: ArrowNumericType> {
+    data_type: DataType,
+    all_values: Vec<T::Native>,
+    args: PercentileArgs,
+    percentile_fn: PercentileFn<T>,
+}
+
+impl<T: ArrowNumericType> Debug for PercentileAccumulator<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PercentileAccumulator({}, {:?})",
+            self.data_type, self.args
+        )
+    }
+}
+
+impl<T: ArrowNumericType> Accumulator for PercentileAccumulator<T> {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let offsets =
+            OffsetBuffer::new(ScalarBuffer::from(vec![0, self.all_values.len() as i32]));
+
+        let values_array = PrimitiveArray::<T>::new(
+            ScalarBuffer::from(std::mem::take(&mut self.all_values)),
+            None,
+        )
+        .with_data_type(self.data_type.clone());
+
+        let list_array = ListArray::new(
+            Arc::new(Field::new_list_field(self.data_type.clone(), true)),
+            offsets,
+            Arc::new(values_array),
+            None,
+        );
+
+        Ok(vec![ScalarValue::List(Arc::new(list_array))])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = values[0].as_primitive::<T>();
+        self.all_values.reserve(values.len() - values.null_count());
+        self.all_values.extend(values.iter().flatten());
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let array = states[0].as_list::<i32>();
+        for v in array.iter().flatten() {
+            self.update_batch(&[v])?
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let percentile = (self.percentile_fn)(&mut self.all_values, self.args);
+        ScalarValue::new_primitive::<T>(percentile, &self.data_type)
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self) + self.all_values.capacity() * size_of::<T::Native>()
+    }
+}
+
+/// The percentile groups accumulator accumulates the raw input values of
+/// each group in a `Vec<T>`, see `MedianGroupsAccumulator` for details
+#[derive(Debug)]
+struct PercentileGroupsAccumulator<T: ArrowNumericType + Send> {
+    data_type: DataType,
+    group_values: Vec<Vec<T::Native>>,
+    args: PercentileArgs,
+    percentile_fn: PercentileFn<T>,
+}
+
+impl<T: ArrowNumericType + Send> PercentileGroupsAccumulator<T> {
+    fn new(
+        data_type: DataType,
+        args: PercentileArgs,
+        percentile_fn: PercentileFn<T>,
+    ) -> Self {
+        Self {
+            data_type,
+            group_values: Vec::new(),
+            args,
+            percentile_fn,
+        }
+    }
+}
+
+impl<T: ArrowNumericType + Send> GroupsAccumulator for PercentileGroupsAccumulator<T> {
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        // The percentile itself is passed as the second argument
+        let values = values[0].as_primitive::<T>();
+
+        self.group_values.resize(total_num_groups, Vec::new());
+        accumulate(
+            group_indices,
+            values,
+            opt_filter,
+            |group_index, new_value| {
+                self.group_values[group_index].push(new_value);
+            },
+        );
+
+        Ok(())
+    }
+
+    fn merge_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        _opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        assert_eq!(values.len(), 1, "one argument to merge_batch");
+        let input_group_values = values[0].as_list::<i32>();
+
+        self.group_values.resize(total_num_groups, Vec::new());
+        group_indices
+            .iter()
+            .zip(input_group_values.iter())
+            .for_each(|(&group_index, values_opt)| {
+                if let Some(values) = values_opt {
+                    let values = values.as_primitive::<T>();
+                    self.group_values[group_index].extend(values.values().iter());
+                }
+            });
+
+        Ok(())
+    }
+
+    fn state(&mut self, emit_to: EmitTo) -> Result<Vec<ArrayRef>> {
+        let emit_group_values = emit_to.take_needed(&mut self.group_values);
+
+        let offsets = OffsetBuffer::from_lengths(emit_group_values.iter().map(Vec::len));
+        let flatten_group_values =
+            emit_group_values.into_iter().flatten().collect::<Vec<_>>();
+        let group_values_array =
+            PrimitiveArray::<T>::new(ScalarBuffer::from(flatten_group_values), None)
+                .with_data_type(self.data_type.clone());
+
+        let result_list_array = ListArray::new(
+            Arc::new(Field::new_list_field(self.data_type.clone(), true)),
+            offsets,
+            Arc::new(group_values_array),
+            None,
+        );
+
+        Ok(vec![Arc::new(result_list_array)])
+    }
+
+    fn evaluate(&mut self, emit_to: EmitTo) -> Result<ArrayRef> {
+        let emit_group_values = emit_to.take_needed(&mut self.group_values);
+
+        let mut evaluate_result_builder =
+            PrimitiveBuilder::<T>::new().with_data_type(self.data_type.clone());
+        for mut values in emit_group_values {
+            let percentile = (self.percentile_fn)(&mut values, self.args);
+            evaluate_result_builder.append_option(percentile);
+        }
+
+        Ok(Arc::new(evaluate_result_builder.finish()))
+    }
+
+    fn convert_to_state(
+        &self,
+        values: &[ArrayRef],
+        opt_filter: Option<&BooleanArray>,
+    ) -> Result<Vec<ArrayRef>> {
+        let input_array = values[0].as_primitive::<T>();
+
+        // Each row is converted to a list with a single element, or to a null
+        // list if the row is null or filtered out
+        let values = PrimitiveArray::<T>::new(input_array.values().clone(), None)
+            .with_data_type(self.data_type.clone());
+
+        let offset_end = i32::try_from(input_array.len()).map_err(|e| {
+            internal_datafusion_err!(
+                "cast array_len to i32 failed in convert_to_state of group percentile, err:{e:?}"
+            )
+        })?;
+        let offsets = (0..=offset_end).collect::<Vec<_>>();
+        // Safety: all checks in `OffsetBuffer::new` are ensured to pass
+        let offsets = unsafe { OffsetBuffer::new_unchecked(ScalarBuffer::from(offsets)) };
+
+        let nulls = filtered_null_mask(opt_filter, input_array);
+
+        let converted_list_array = ListArray::new(
+            Arc::new(Field::new_list_field(self.data_type.clone(), true)),
+            offsets,
+            Arc::new(values),
+            nulls,
+        );
+
+        Ok(vec![Arc::new(converted_list_array)])
+    }
+
+    fn supports_convert_to_state(&self) -> bool {
+        true
+    }
+
+    fn size(&self) -> usize {
+        self.group_values
+            .iter()
+            .map(|values| values.capacity() * size_of::<T::Native>())
+            .sum::<usize>()
+            + self.group_values.capacity() * size_of::<Vec<T::Native>>()
+    }
+}
+
+/// Computes the continuous percentile, linearly interpolating between the two
+/// values closest to the requested position
+fn calculate_percentile_cont(values: &mut [f64], args: PercentileArgs) -> Option<f64> {
+    let len = values.len();
+    if len == 0 {
+        return None;
+    }
+
+    // Taking the percentile `p` of the descending ordering is the same as
+    // taking the percentile `1 - p` of the ascending ordering
+    let percentile = if args.descending {
+        1.0 - args.percentile
+    } else {
+        args.percentile
+    };
+    let position = percentile * (len - 1) as f64;
+    let lower = position.floor() as usize;
+    let weight = position - lower as f64;
+
+    let (_, lower_value, upper_values) =
+        values.select_nth_unstable_by(lower, f64::total_cmp);
+    let lower_value = *lower_value;
+    match upper_values.iter().copied().min_by(f64::total_cmp) {
+        Some(upper_value) if weight > 0.0 => {
+            Some(lower_value + (upper_value - lower_value) * weight)
+        }
+        _ => Some(lower_value),
+    }
+}
+
+/// Computes the discrete percentile, i.e. the first value whose position in
+/// the ordering is at or above the requested percentile
+fn calculate_percentile_disc<T: ArrowPrimitiveType>(
+    values: &mut [T::Native],
+    args: PercentileArgs,
+) -> Option<T::Native> {
+    let len = values.len();
+    if len == 0 {
+        return None;
+    }
+
+    let index = ((args.percentile * len as f64).ceil() as usize).clamp(1, len) - 1;
+    let index = if args.descending {
+        len - 1 - index
+    } else {
+        index
+    };
+    let (_, value, _) = values.select_nth_unstable_by(index, |x, y| x.compare(*y));
+    Some(*value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Float64Array, Int32Array};
+    use arrow::datatypes::Int32Type;
+
+    fn args(percentile: f64, descending: bool) -> PercentileArgs {
+        PercentileArgs {
+            percentile,
+            descending,
+        }
+    }
+
+    #[test]
+    fn percentile_cont_interpolates() {
+        let mut values = vec![4.0, 1.0, 3.0, 2.0];
+        assert_eq!(
+            calculate_percentile_cont(&mut values, args(0.5, false)),
+            Some(2.5)
+        );
+        assert_eq!(
+            calculate_percentile_cont(&mut values, args(0.0, false)),
+            Some(1.0)
+        );
+        assert_eq!(
+            calculate_percentile_cont(&mut values, args(1.0, false)),
+            Some(4.0)
+        );
+        assert_eq!(
+            calculate_percentile_cont(&mut values, args(0.25, false)),
+            Some(1.75)
+        );
+        assert_eq!(
+            calculate_percentile_cont(&mut values, args(0.25, true)),
+            Some(3.25)
+        );
+        assert_eq!(calculate_percentile_cont(&mut [], args(0.5, false)), None);
+    }
+
+    #[test]
+    fn percentile_disc_picks_input_value() {
+        let mut values = vec![5, 1, 4, 2, 3];
+        let disc = calculate_percentile_disc::<Int32Type>;
+        assert_eq!(disc(&mut values, args(0.0, false)), Some(1));
+        assert_eq!(disc(&mut values, args(0.2, false)), Some(1));
+        assert_eq!(disc(&mut values, args(0.5, false)), Some(3));
+        assert_eq!(disc(&mut values, args(0.7, false)), Some(4));
+        assert_eq!(disc(&mut values, args(1.0, false)), Some(5));
+        assert_eq!(disc(&mut values, args(0.2, true)), Some(5));
+        assert_eq!(disc(&mut values, args(0.7, true)), Some(2));
+        assert_eq!(disc(&mut [], args(0.5, false)), None);
+    }
+
+    #[test]
+    fn percentile_groups_accumulator() -> Result<()> {
+        let mut acc = PercentileGroupsAccumulator::<Int32Type>::new(
+            DataType::Int32,
+            args(0.5, false),
+            calculate_percentile_disc::<Int32Type>,
+        );
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(1),
+            Some(10),
+            None,
+            Some(3),
+            Some(20),
+        ]));
+        acc.update_batch(&[values], &[0, 1, 0, 0, 1], None, 2)?;
+
+        // Merge in the state of a second accumulator
+        let mut other = PercentileGroupsAccumulator::<Int32Type>::new(
+            DataType::Int32,
+            args(0.5, false),
+            calculate_percentile_disc::<Int32Type>,
+        );
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![2, 30]));
+        other.update_batch(&[values], &[0, 1], None, 2)?;
+        let state = other.state(EmitTo::All)?;
+        acc.merge_batch(&state, &[0, 1], None, 2)?;
+
+        let result = acc.evaluate(EmitTo::All)?;
+        assert_eq!(result.as_primitive::<Int32Type>().values(), &[2, 20]);
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_accumulator() -> Result<()> {
+        let mut acc = PercentileAccumulator::<Float64Type> {
+            data_type: DataType::Float64,
+            all_values: vec![],
+            args: args(0.5, false),
+            percentile_fn: calculate_percentile_cont,
+        };
+        let values: ArrayRef =
+            Arc::new(Float64Array::from(vec![Some(1.0), None, Some(2.0)]));
+        acc.update_batch(&[values])?;
+        assert_eq!(acc.evaluate()?, ScalarValue::Float64(Some(1.5)));
+        Ok(())
+    }
+}
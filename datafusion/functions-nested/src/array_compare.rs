@@ -0,0 +1,445 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ScalarUDFImpl`] definitions for array_compare_any and array_compare_all functions.
+
+use arrow::array::{
+    Array, ArrayRef, BooleanArray, BooleanBuilder, OffsetSizeTrait, UInt64Array,
+};
+use arrow::compute::kernels::cmp::{eq, gt, gt_eq, lt, lt_eq, neq};
+use arrow::compute::{ilike, like, nilike, nlike, take};
+use arrow::datatypes::DataType;
+use arrow_array::GenericListArray;
+use arrow_schema::Field;
+use datafusion_common::cast::as_generic_list_array;
+use datafusion_common::{exec_datafusion_err, exec_err, plan_err, Result, ScalarValue};
+use datafusion_expr::expr::SetQuantifier;
+use datafusion_expr::type_coercion::binary::comparison_coercion;
+use datafusion_expr::{
+    ColumnarValue, Documentation, Operator, ScalarUDFImpl, Signature, Volatility,
+};
+use datafusion_macros::user_doc;
+use datafusion_physical_expr_common::datum::compare_op_for_nested;
+
+use crate::utils::make_scalar_function;
+
+use std::any::Any;
+use std::sync::Arc;
+
+// Create static instances of ScalarUDFs for each function
+make_udf_expr_and_func!(ArrayCompareAny,
+    array_compare_any,
+    element operator array, // arg names
+    "returns true if comparing the element with at least one element of the array using the operator is true.", // doc
+    array_compare_any_udf // internal function name
+);
+make_udf_expr_and_func!(ArrayCompareAll,
+    array_compare_all,
+    element operator array, // arg names
+    "returns true if comparing the element with every element of the array using the operator is true.", // doc
+    array_compare_all_udf // internal function name
+);
+
+/// Operators supported by [`ArrayCompareAny`] and [`ArrayCompareAll`]
+const SUPPORTED_OPERATORS: [Operator; 10] = [
+    Operator::Eq,
+    Operator::NotEq,
+    Operator::Lt,
+    Operator::LtEq,
+    Operator::Gt,
+    Operator::GtEq,
+    Operator::LikeMatch,
+    Operator::ILikeMatch,
+    Operator::NotLikeMatch,
+    Operator::NotILikeMatch,
+];
+
+#[user_doc(
+    doc_section(label = "Array Functions"),
+    description = "Returns true if `element operator value` is true for at least one value of the array, NULL if it is not true for any value but NULL for some, and false otherwise. `expr operator ANY(array)` is planned as a call to this function.",
+    syntax_example = "array_compare_any(element, operator, array)",
+    sql_example = r#"```sql
+SELECT array_compare_any(2, '>', [1, 2, 3]);
+----
+true
+
+SELECT 2 > ANY([3, NULL]);
+----
+NULL
+
+SELECT 'abc' LIKE ANY(['a%', 'x%']);
+----
+true
+```"#,
+    argument(
+        name = "element",
+        description = "Expression to compare with the values of the array."
+    ),
+    argument(
+        name = "operator",
+        description = "Constant string with the comparison operator: `=`, `!=`, `<`, `<=`, `>`, `>=`, `~~` (LIKE), `~~*` (ILIKE), `!~~` (NOT LIKE) or `!~~*` (NOT ILIKE)."
+    ),
+    argument(
+        name = "array",
+        description = "Array expression. Can be a constant, column, or function, and any combination of array operators."
+    )
+)]
+#[derive(Debug)]
+pub struct ArrayCompareAny {
+    signature: Signature,
+}
+
+impl Default for ArrayCompareAny {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArrayCompareAny {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for ArrayCompareAny {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "array_compare_any"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn invoke_batch(
+        &self,
+        args: &[ColumnarValue],
+        _number_rows: usize,
+    ) -> Result<ColumnarValue> {
+        array_compare(self.name(), args, SetQuantifier::Any)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_array_compare_types(self.name(), arg_types)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+#[user_doc(
+    doc_section(label = "Array Functions"),
+    description = "Returns true if `element operator value` is true for every value of the array, NULL if it is not false for any value but NULL for some, and false otherwise. `expr operator ALL(array)` is planned as a call to this function.",
+    syntax_example = "array_compare_all(element, operator, array)",
+    sql_example = r#"```sql
+SELECT array_compare_all(2, '>', [1, 2, 3]);
+----
+false
+
+SELECT 5 > ALL([1, 2, 3]);
+----
+true
+
+SELECT 5 > ALL([1, NULL]);
+----
+NULL
+```"#,
+    argument(
+        name = "element",
+        description = "Expression to compare with the values of the array."
+    ),
+    argument(
+        name = "operator",
+        description = "Constant string with the comparison operator: `=`, `!=`, `<`, `<=`, `>`, `>=`, `~~` (LIKE), `~~*` (ILIKE), `!~~` (NOT LIKE) or `!~~*` (NOT ILIKE)."
+    ),
+    argument(
+        name = "array",
+        description = "Array expression. Can be a constant, column, or function, and any combination of array operators."
+    )
+)]
+#[derive(Debug)]
+pub struct ArrayCompareAll {
+    signature: Signature,
+}
+
+impl Default for ArrayCompareAll {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArrayCompareAll {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for ArrayCompareAll {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "array_compare_all"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn invoke_batch(
+        &self,
+        args: &[ColumnarValue],
+        _number_rows: usize,
+    ) -> Result<ColumnarValue> {
+        array_compare(self.name(), args, SetQuantifier::All)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_array_compare_types(self.name(), arg_types)
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
+
+/// Coerces the element and the values of the array to a common type
+fn coerce_array_compare_types(
+    name: &str,
+    arg_types: &[DataType],
+) -> Result<Vec<DataType>> {
+    let [element_type, operator_type, array_type] = arg_types else {
+        return plan_err!("{name} expects three arguments");
+    };
+    if !matches!(
+        operator_type,
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+    ) {
+        return plan_err!("{name} expects a string operator, got {operator_type}");
+    }
+    let (value_type, large) = match array_type {
+        DataType::List(field) | DataType::FixedSizeList(field, _) => {
+            (field.data_type(), false)
+        }
+        DataType::LargeList(field) => (field.data_type(), true),
+        DataType::Null => (&DataType::Null, false),
+        _ => return plan_err!("{name} expects an array, got {array_type}"),
+    };
+    let Some(common_type) = comparison_coercion(element_type, value_type) else {
+        return plan_err!("{name} cannot compare {element_type} with {value_type}");
+    };
+    let field = Arc::new(Field::new_list_field(common_type.clone(), true));
+    let array_type = if large {
+        DataType::LargeList(field)
+    } else {
+        DataType::List(field)
+    };
+    Ok(vec![common_type, DataType::Utf8, array_type])
+}
+
+fn array_compare(
+    name: &str,
+    args: &[ColumnarValue],
+    quantifier: SetQuantifier,
+) -> Result<ColumnarValue> {
+    let [element, operator, array] = args else {
+        return exec_err!("{name} expects three arguments");
+    };
+    let ColumnarValue::Scalar(ScalarValue::Utf8(Some(operator))) = operator else {
+        return exec_err!("{name} expects a constant string operator");
+    };
+    let op = SUPPORTED_OPERATORS
+        .into_iter()
+        .find(|op| op.to_string() == *operator)
+        .ok_or_else(|| {
+            exec_datafusion_err!("{name} does not support operator '{operator}'")
+        })?;
+
+    make_scalar_function(move |args: &[ArrayRef]| {
+        let [element, array] = args else {
+            return exec_err!("{name} expects an element and an array");
+        };
+        match array.data_type() {
+            DataType::List(_) => compare_list::<i32>(
+                element,
+                as_generic_list_array(array)?,
+                op,
+                quantifier,
+            ),
+            DataType::LargeList(_) => compare_list::<i64>(
+                element,
+                as_generic_list_array(array)?,
+                op,
+                quantifier,
+            ),
+            DataType::Null => Ok(Arc::new(BooleanArray::new_null(array.len()))),
+            other => exec_err!("{name} does not support type '{other}'"),
+        }
+    })(&[element.clone(), array.clone()])
+}
+
+/// Compares the element of every row with the values of the list of the row,
+/// combining the results with three-valued logic
+fn compare_list<O: OffsetSizeTrait>(
+    element: &ArrayRef,
+    list: &GenericListArray<O>,
+    op: Operator,
+    quantifier: SetQuantifier,
+) -> Result<ArrayRef> {
+    let offsets = list.value_offsets();
+    let start = offsets[0].as_usize();
+    let values = list
+        .values()
+        .slice(start, offsets[list.len()].as_usize() - start);
+
+    // repeat the element of every row once for each value of its list
+    let indices = UInt64Array::from_iter_values(offsets.windows(2).enumerate().flat_map(
+        |(row, window)| {
+            std::iter::repeat(row as u64).take((window[1] - window[0]).as_usize())
+        },
+    ));
+    let elements = take(element.as_ref(), &indices, None)?;
+    let matches = compare(&elements, &values, op)?;
+
+    // ANY is decided by a true comparison and ALL by a false one
+    let decisive = quantifier == SetQuantifier::Any;
+    let mut builder = BooleanBuilder::with_capacity(list.len());
+    for (row, window) in offsets.windows(2).enumerate() {
+        if list.is_null(row) {
+            builder.append_null();
+            continue;
+        }
+        let mut has_null = false;
+        let mut decided = false;
+        for i in window[0].as_usize() - start..window[1].as_usize() - start {
+            if matches.is_null(i) {
+                has_null = true;
+            } else if matches.value(i) == decisive {
+                decided = true;
+                break;
+            }
+        }
+        if decided {
+            builder.append_value(decisive);
+        } else if has_null {
+            builder.append_null();
+        } else {
+            builder.append_value(!decisive);
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+fn compare(left: &ArrayRef, right: &ArrayRef, op: Operator) -> Result<BooleanArray> {
+    let result = match op {
+        Operator::LikeMatch => like(left, right),
+        Operator::ILikeMatch => ilike(left, right),
+        Operator::NotLikeMatch => nlike(left, right),
+        Operator::NotILikeMatch => nilike(left, right),
+        _ if left.data_type().is_nested() => {
+            return compare_op_for_nested(op, left, right)
+        }
+        Operator::Eq => eq(left, right),
+        Operator::NotEq => neq(left, right),
+        Operator::Lt => lt(left, right),
+        Operator::LtEq => lt_eq(left, right),
+        Operator::Gt => gt(left, right),
+        Operator::GtEq => gt_eq(left, right),
+        _ => return exec_err!("Unsupported comparison operator '{op}'"),
+    };
+    Ok(result?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::{AsArray, Int64Array, ListArray};
+    use arrow::datatypes::Int64Type;
+
+    fn compare_rows(
+        element: Vec<Option<i64>>,
+        list: Vec<Option<Vec<Option<i64>>>>,
+        op: Operator,
+        quantifier: SetQuantifier,
+    ) -> Vec<Option<bool>> {
+        let element: ArrayRef = Arc::new(Int64Array::from(element));
+        let list = ListArray::from_iter_primitive::<Int64Type, _, _>(list);
+        let result = compare_list(&element, &list, op, quantifier).unwrap();
+        result.as_boolean().iter().collect()
+    }
+
+    #[test]
+    fn test_three_valued_logic() {
+        let element = vec![Some(2), Some(2), Some(2), Some(2), None, Some(2)];
+        let list = vec![
+            Some(vec![Some(1), Some(3)]),
+            Some(vec![Some(1), None]),
+            Some(vec![Some(3), None]),
+            Some(vec![]),
+            Some(vec![Some(1)]),
+            None,
+        ];
+        assert_eq!(
+            compare_rows(
+                element.clone(),
+                list.clone(),
+                Operator::Gt,
+                SetQuantifier::Any
+            ),
+            vec![Some(true), Some(true), None, Some(false), None, None]
+        );
+        assert_eq!(
+            compare_rows(element, list, Operator::Gt, SetQuantifier::All),
+            vec![Some(false), None, Some(false), Some(true), None, None]
+        );
+    }
+
+    #[test]
+    fn test_sliced_list() {
+        let element: ArrayRef = Arc::new(Int64Array::from(vec![1, 2, 3]));
+        let list = ListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+            Some(vec![Some(5)]),
+            Some(vec![Some(1), Some(2)]),
+            Some(vec![Some(3)]),
+        ])
+        .slice(1, 2);
+        let result =
+            compare_list(&element, &list, Operator::Eq, SetQuantifier::Any).unwrap();
+        assert_eq!(
+            result.as_boolean().iter().collect::<Vec<_>>(),
+            vec![Some(true), Some(false)]
+        );
+    }
+}
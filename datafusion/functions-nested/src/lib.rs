@@ -31,6 +31,7 @@
 #[macro_use]
 pub mod macros;
 
+pub mod array_compare;
 pub mod array_has;
 pub mod cardinality;
 pub mod concat;
@@ -68,6 +69,8 @@ use std::sync::Arc;
 
 /// Fluent-style API for creating `Expr`s
 pub mod expr_fn {
+    pub use super::array_compare::array_compare_all;
+    pub use super::array_compare::array_compare_any;
     pub use super::array_has::array_has;
     pub use super::array_has::array_has_all;
     pub use super::array_has::array_has_any;
@@ -135,6 +138,8 @@ pub fn all_default_nested_functions() -> Vec<Arc<ScalarUDF>> {
         array_has::array_has_udf(),
         array_has::array_has_all_udf(),
         array_has::array_has_any_udf(),
+        array_compare::array_compare_any_udf(),
+        array_compare::array_compare_all_udf(),
         empty::array_empty_udf(),
         length::array_length_udf(),
         distance::array_distance_udf(),
@@ -0,0 +1,326 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Helpers shared by the JSON functions
+
+use arrow::array::{
+    Array, ArrayBuilder, ArrayRef, AsArray, Int64Array, StringArray, StringBuilder,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Int64Type};
+use datafusion_common::{exec_err, plan_err, Result, ScalarValue};
+use datafusion_expr::ColumnarValue;
+use serde_json::Value;
+
+/// An element of a path into a JSON document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonPathElement<'a> {
+    /// Member of an object, or element of an array if the key is an integer
+    Key(&'a str),
+    /// Element of an array, negative indexes count from the end of the array
+    Index(i64),
+}
+
+/// Returns the value at `path` in `value`, or `None` if the path does not exist
+pub fn get_path<'v>(value: &'v Value, path: &[JsonPathElement]) -> Option<&'v Value> {
+    path.iter()
+        .try_fold(value, |value, element| match (value, element) {
+            (Value::Object(members), JsonPathElement::Key(key)) => members.get(*key),
+            (Value::Array(elements), JsonPathElement::Index(index)) => {
+                array_element(elements, *index)
+            }
+            (Value::Array(elements), JsonPathElement::Key(key)) => key
+                .parse()
+                .ok()
+                .and_then(|index| array_element(elements, index)),
+            _ => None,
+        })
+}
+
+fn array_element(elements: &[Value], index: i64) -> Option<&Value> {
+    let index = if index < 0 {
+        index.checked_add(elements.len() as i64)?
+    } else {
+        index
+    };
+    usize::try_from(index)
+        .ok()
+        .and_then(|index| elements.get(index))
+}
+
+/// Checks the argument types of a function taking a JSON document followed
+/// by the elements of a path, which must not be empty if `requires_path`
+pub(crate) fn check_arg_types(
+    name: &str,
+    arg_types: &[DataType],
+    requires_path: bool,
+) -> Result<()> {
+    let Some((document, path)) = arg_types.split_first() else {
+        return plan_err!("{name} expects a JSON document argument");
+    };
+    if !matches!(
+        document,
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View | DataType::Null
+    ) {
+        return plan_err!("{name} expects a string JSON document, got {document}");
+    }
+    if requires_path && path.is_empty() {
+        return plan_err!("{name} expects a path into the JSON document");
+    }
+    if let Some(element) = path.iter().find(|element| {
+        !matches!(
+            element,
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View | DataType::Null
+        ) && !element.is_integer()
+    }) {
+        return plan_err!(
+            "{name} expects string or integer path elements, got {element}"
+        );
+    }
+    Ok(())
+}
+
+/// Evaluates a JSON function whose arguments are a JSON document followed by
+/// the elements of a path into it.
+///
+/// For every row, `append` is called with:
+/// * `None` if the document or a path element is NULL, or the document is not
+///   valid JSON
+/// * `Some(None)` if the path does not exist in the document
+/// * `Some(Some(value))` with the value at the path otherwise
+///
+/// and must append exactly one value to `builder`.
+pub(crate) fn invoke_json<B: ArrayBuilder>(
+    args: &[ColumnarValue],
+    mut builder: B,
+    mut append: impl FnMut(&mut B, Option<Option<&Value>>),
+) -> Result<ColumnarValue> {
+    let Some((document, path)) = args.split_first() else {
+        return exec_err!("JSON functions expect a JSON document argument");
+    };
+    let len = args.iter().find_map(|arg| match arg {
+        ColumnarValue::Scalar(_) => None,
+        ColumnarValue::Array(array) => Some(array.len()),
+    });
+
+    let documents = parse_documents(&document.to_array(1)?)?;
+    let document_is_scalar = matches!(document, ColumnarValue::Scalar(_));
+    let path = path
+        .iter()
+        .map(PathColumn::try_new)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut elements = Vec::with_capacity(path.len());
+    for row in 0..len.unwrap_or(1) {
+        let document = &documents[if document_is_scalar { 0 } else { row }];
+        elements.clear();
+        let value = document.as_ref().and_then(|document| {
+            for column in &path {
+                elements.push(column.element(row)?);
+            }
+            Some(get_path(document, &elements))
+        });
+        append(&mut builder, value);
+    }
+
+    let array = builder.finish();
+    match len {
+        Some(_) => Ok(ColumnarValue::Array(array)),
+        None => ScalarValue::try_from_array(&array, 0).map(ColumnarValue::Scalar),
+    }
+}
+
+/// Parses the document of every row of a column of JSON strings, `None` for
+/// NULL or invalid JSON
+pub(crate) fn parse_documents(array: &ArrayRef) -> Result<Vec<Option<Value>>> {
+    Ok(match array.data_type() {
+        DataType::Utf8 => parse_all(array.as_string::<i32>().iter()),
+        DataType::LargeUtf8 => parse_all(array.as_string::<i64>().iter()),
+        DataType::Utf8View => parse_all(array.as_string_view().iter()),
+        DataType::Null => vec![None; array.len()],
+        other => return exec_err!("JSON document must be a string, got {other}"),
+    })
+}
+
+fn parse_all<'a>(texts: impl Iterator<Item = Option<&'a str>>) -> Vec<Option<Value>> {
+    texts
+        .map(|text| text.and_then(|text| serde_json::from_str(text).ok()))
+        .collect()
+}
+
+/// Appends `value` as text: strings without quotes, other values as JSON
+/// text and NULL for JSON `null` or a missing value
+pub(crate) fn append_text(builder: &mut StringBuilder, value: Option<&Value>) {
+    match value {
+        Some(Value::String(value)) => builder.append_value(value),
+        Some(Value::Null) | None => builder.append_null(),
+        Some(value) => builder.append_value(value.to_string()),
+    }
+}
+
+/// Splits a path written as a text array, such as `'{a,0,b}'`, into its
+/// elements, or returns `None` if it is not a text array
+pub fn split_text_path(text: &str) -> Option<Vec<&str>> {
+    let elements = text.trim().strip_prefix('{')?.strip_suffix('}')?;
+    if elements.trim().is_empty() {
+        return Some(vec![]);
+    }
+    Some(
+        elements
+            .split(',')
+            .map(|element| {
+                let element = element.trim();
+                element
+                    .strip_prefix('"')
+                    .and_then(|element| element.strip_suffix('"'))
+                    .unwrap_or(element)
+            })
+            .collect(),
+    )
+}
+
+/// An argument holding one element of the path for every row
+struct PathColumn {
+    elements: PathElements,
+    is_scalar: bool,
+}
+
+enum PathElements {
+    Key(StringArray),
+    Index(Int64Array),
+    Null,
+}
+
+impl PathColumn {
+    fn try_new(arg: &ColumnarValue) -> Result<Self> {
+        let array = arg.to_array(1)?;
+        let elements = match array.data_type() {
+            DataType::Null => PathElements::Null,
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+                PathElements::Key(cast(&array, &DataType::Utf8)?.as_string().clone())
+            }
+            data_type if data_type.is_integer() => PathElements::Index(
+                cast(&array, &DataType::Int64)?
+                    .as_primitive::<Int64Type>()
+                    .clone(),
+            ),
+            other => {
+                return exec_err!(
+                    "JSON path element must be a string or integer, got {other}"
+                )
+            }
+        };
+        Ok(Self {
+            elements,
+            is_scalar: matches!(arg, ColumnarValue::Scalar(_)),
+        })
+    }
+
+    fn element(&self, row: usize) -> Option<JsonPathElement<'_>> {
+        let row = if self.is_scalar { 0 } else { row };
+        match &self.elements {
+            PathElements::Key(keys) => keys
+                .is_valid(row)
+                .then(|| JsonPathElement::Key(keys.value(row))),
+            PathElements::Index(indexes) => indexes
+                .is_valid(row)
+                .then(|| JsonPathElement::Index(indexes.value(row))),
+            PathElements::Null => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    #[test]
+    fn test_get_path() {
+        let document: Value =
+            serde_json::from_str(r#"{"a": {"b": [1, 2, {"c": true}]}}"#).unwrap();
+        let get = |path: &[JsonPathElement]| get_path(&document, path).cloned();
+
+        assert_eq!(get(&[]), Some(document.clone()));
+        assert_eq!(
+            get(&[JsonPathElement::Key("a"), JsonPathElement::Key("b")])
+                .map(|value| value.to_string()),
+            Some(r#"[1,2,{"c":true}]"#.to_string())
+        );
+        for index in [JsonPathElement::Index(2), JsonPathElement::Key("2")] {
+            let path = [
+                JsonPathElement::Key("a"),
+                JsonPathElement::Key("b"),
+                index,
+                JsonPathElement::Key("c"),
+            ];
+            assert_eq!(get(&path), Some(Value::Bool(true)));
+        }
+        let path = [
+            JsonPathElement::Key("a"),
+            JsonPathElement::Key("b"),
+            JsonPathElement::Index(-3),
+        ];
+        assert_eq!(get(&path), Some(Value::from(1)));
+
+        let path = [
+            JsonPathElement::Key("a"),
+            JsonPathElement::Key("b"),
+            JsonPathElement::Index(-4),
+        ];
+        assert_eq!(get(&path), None);
+        assert_eq!(get(&[JsonPathElement::Index(0)]), None);
+        assert_eq!(get(&[JsonPathElement::Key("x")]), None);
+    }
+
+    #[test]
+    fn test_split_text_path() {
+        assert_eq!(split_text_path("{a, 0,\"b\"}"), Some(vec!["a", "0", "b"]));
+        assert_eq!(split_text_path(" {} "), Some(vec![]));
+        assert_eq!(split_text_path("a,b"), None);
+    }
+
+    #[test]
+    fn test_invoke_json() -> Result<()> {
+        let documents: ArrayRef = Arc::new(StringArray::from(vec![
+            Some(r#"{"a": 1}"#),
+            None,
+            Some("invalid"),
+        ]));
+        assert_eq!(
+            parse_documents(&documents)?,
+            vec![Some(serde_json::json!({"a": 1})), None, None]
+        );
+
+        let args = [
+            ColumnarValue::Array(documents),
+            ColumnarValue::Scalar(ScalarValue::from("a")),
+        ];
+        let result = invoke_json(&args, StringBuilder::new(), |builder, value| {
+            builder.append_option(value.map(|value| format!("{value:?}")))
+        })?;
+        let ColumnarValue::Array(result) = result else {
+            unreachable!()
+        };
+        assert_eq!(
+            result.as_string::<i32>().iter().collect::<Vec<_>>(),
+            vec![Some("Some(Number(1))"), None, None]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+
+use arrow::array::Int64Builder;
+use arrow::datatypes::DataType;
+use datafusion_common::Result;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarFunctionArgs, ScalarUDFImpl, Signature,
+    Volatility,
+};
+use datafusion_macros::user_doc;
+use serde_json::Value;
+
+use super::common::{check_arg_types, invoke_json};
+
+#[user_doc(
+    doc_section(label = "JSON Functions"),
+    description = r#"Returns the number of elements of the JSON array at a path in a JSON document, or NULL if the value at the path is not an array."#,
+    syntax_example = "json_array_length(json[, path_element, ...])",
+    sql_example = r#"```sql
+select json_array_length('[1, 2, [3, 4]]');
+----
+3
+
+select json_array_length('{"a": [1, 2]}', 'a');
+----
+2
+```"#,
+    argument(name = "json", description = "String containing a JSON document."),
+    argument(
+        name = "path_element",
+        description = "Object key or array index to descend into. Integers and strings containing an integer index arrays, negative indexes count from the end of the array."
+    )
+)]
+#[derive(Debug)]
+pub struct JsonArrayLengthFunc {
+    signature: Signature,
+}
+
+impl Default for JsonArrayLengthFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonArrayLengthFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::variadic_any(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for JsonArrayLengthFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "json_array_length"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_types(self.name(), arg_types, false)?;
+        Ok(DataType::Int64)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let builder = Int64Builder::with_capacity(args.number_rows);
+        invoke_json(&args.args, builder, |builder, value| {
+            builder.append_option(match value.flatten() {
+                Some(Value::Array(elements)) => Some(elements.len() as i64),
+                _ => None,
+            })
+        })
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
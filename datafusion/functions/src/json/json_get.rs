@@ -0,0 +1,103 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+
+use arrow::array::StringBuilder;
+use arrow::datatypes::DataType;
+use datafusion_common::Result;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarFunctionArgs, ScalarUDFImpl, Signature,
+    Volatility,
+};
+use datafusion_macros::user_doc;
+
+use super::common::{check_arg_types, invoke_json};
+
+#[user_doc(
+    doc_section(label = "JSON Functions"),
+    description = r#"Returns the value at a path in a JSON document as JSON text, or NULL if the path does not exist. The `->` and `#>` operators are planned as calls to this function."#,
+    syntax_example = "json_get(json[, path_element, ...])",
+    sql_example = r#"```sql
+select json_get('{"a": {"b": [1, 2]}}', 'a', 'b', 0);
+----
+1
+
+select '{"a": {"b": [1, 2]}}' -> 'a';
+----
+{"b":[1,2]}
+```"#,
+    argument(name = "json", description = "String containing a JSON document."),
+    argument(
+        name = "path_element",
+        description = "Object key or array index to descend into. Integers and strings containing an integer index arrays, negative indexes count from the end of the array."
+    )
+)]
+#[derive(Debug)]
+pub struct JsonGetFunc {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl Default for JsonGetFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonGetFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::variadic_any(Volatility::Immutable),
+            aliases: vec![String::from("json_extract_path")],
+        }
+    }
+}
+
+impl ScalarUDFImpl for JsonGetFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "json_get"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_types(self.name(), arg_types, false)?;
+        Ok(DataType::Utf8)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let builder = StringBuilder::with_capacity(args.number_rows, 0);
+        invoke_json(&args.args, builder, |builder, value| {
+            builder.append_option(value.flatten().map(|value| value.to_string()))
+        })
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
@@ -0,0 +1,178 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{Array, AsArray, ListBuilder, StringBuilder};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::{exec_err, plan_err, Result, ScalarValue};
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarFunctionArgs, ScalarUDFImpl, Signature,
+    Volatility,
+};
+use datafusion_macros::user_doc;
+
+use super::common::{
+    append_text, get_path, parse_documents, split_text_path, JsonPathElement,
+};
+
+#[user_doc(
+    doc_section(label = "JSON Functions"),
+    description = r#"Returns the values at several paths in a JSON document as a list of text, parsing the document only once. Each element is the value at the corresponding path as returned by `json_get_str`, or NULL if the path does not exist."#,
+    syntax_example = "json_get_str_paths(json, path[, ...])",
+    sql_example = r#"```sql
+select json_get_str_paths('{"a": {"b": "x"}, "c": [1, 2]}', '{a,b}', '{c,1}', '{d}');
+----
+[x, 2, NULL]
+```"#,
+    argument(name = "json", description = "String containing a JSON document."),
+    argument(
+        name = "path",
+        description = "Path into the document written as a text array of object keys and array indexes, like the path of the `#>>` operator, for example `'{a,0,b}'`."
+    )
+)]
+#[derive(Debug)]
+pub struct JsonGetStrPathsFunc {
+    signature: Signature,
+}
+
+impl Default for JsonGetStrPathsFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonGetStrPathsFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::variadic_any(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for JsonGetStrPathsFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "json_get_str_paths"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        let is_string = |data_type: &DataType| {
+            matches!(
+                data_type,
+                DataType::Utf8
+                    | DataType::LargeUtf8
+                    | DataType::Utf8View
+                    | DataType::Null
+            )
+        };
+        match arg_types.split_first() {
+            Some((document, paths)) if !paths.is_empty() => {
+                if !is_string(document) {
+                    return plan_err!(
+                        "{} expects a string JSON document, got {document}",
+                        self.name()
+                    );
+                }
+                if let Some(path) = paths.iter().find(|path| !is_string(path)) {
+                    return plan_err!("{} expects string paths, got {path}", self.name());
+                }
+            }
+            _ => {
+                return plan_err!(
+                    "{} expects a JSON document and at least one path",
+                    self.name()
+                )
+            }
+        }
+        Ok(DataType::List(Arc::new(Field::new_list_field(
+            DataType::Utf8,
+            true,
+        ))))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let Some((document, paths)) = args.args.split_first() else {
+            return exec_err!("{} expects a JSON document argument", self.name());
+        };
+        let len = args.args.iter().find_map(|arg| match arg {
+            ColumnarValue::Scalar(_) => None,
+            ColumnarValue::Array(array) => Some(array.len()),
+        });
+
+        let documents = parse_documents(&document.to_array(1)?)?;
+        let document_is_scalar = matches!(document, ColumnarValue::Scalar(_));
+        let paths = paths
+            .iter()
+            .map(|path| {
+                let array = cast(&path.to_array(1)?, &DataType::Utf8)?;
+                Ok((
+                    array.as_string::<i32>().clone(),
+                    matches!(path, ColumnarValue::Scalar(_)),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut builder =
+            ListBuilder::with_capacity(StringBuilder::new(), len.unwrap_or(1));
+        let mut elements = Vec::new();
+        for row in 0..len.unwrap_or(1) {
+            let Some(document) = &documents[if document_is_scalar { 0 } else { row }]
+            else {
+                builder.append_null();
+                continue;
+            };
+            for (texts, is_scalar) in &paths {
+                let row = if *is_scalar { 0 } else { row };
+                if texts.is_null(row) {
+                    builder.values().append_null();
+                    continue;
+                }
+                let text = texts.value(row);
+                let Some(path) = split_text_path(text) else {
+                    return exec_err!(
+                        "{} expects paths written as text arrays such as '{{a,b}}', got '{text}'",
+                        self.name()
+                    );
+                };
+                elements.clear();
+                elements.extend(path.into_iter().map(JsonPathElement::Key));
+                append_text(builder.values(), get_path(document, &elements));
+            }
+            builder.append(true);
+        }
+
+        let array = builder.finish();
+        match len {
+            Some(_) => Ok(ColumnarValue::Array(Arc::new(array))),
+            None => ScalarValue::try_from_array(&array, 0).map(ColumnarValue::Scalar),
+        }
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
@@ -0,0 +1,112 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{ListBuilder, StringBuilder};
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::Result;
+use datafusion_expr::{
+    ColumnarValue, Documentation, ScalarFunctionArgs, ScalarUDFImpl, Signature,
+    Volatility,
+};
+use datafusion_macros::user_doc;
+use serde_json::Value;
+
+use super::common::{check_arg_types, invoke_json};
+
+#[user_doc(
+    doc_section(label = "JSON Functions"),
+    description = r#"Returns the sorted keys of the JSON object at a path in a JSON document, or NULL if the value at the path is not an object."#,
+    syntax_example = "json_keys(json[, path_element, ...])",
+    sql_example = r#"```sql
+select json_keys('{"b": 1, "a": {"c": 2}}');
+----
+[a, b]
+
+select json_keys('{"b": 1, "a": {"c": 2}}', 'a');
+----
+[c]
+```"#,
+    argument(name = "json", description = "String containing a JSON document."),
+    argument(
+        name = "path_element",
+        description = "Object key or array index to descend into. Integers and strings containing an integer index arrays, negative indexes count from the end of the array."
+    )
+)]
+#[derive(Debug)]
+pub struct JsonKeysFunc {
+    signature: Signature,
+}
+
+impl Default for JsonKeysFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonKeysFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::variadic_any(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for JsonKeysFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "json_keys"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_types(self.name(), arg_types, false)?;
+        Ok(DataType::List(Arc::new(Field::new_list_field(
+            DataType::Utf8,
+            true,
+        ))))
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let builder = ListBuilder::with_capacity(StringBuilder::new(), args.number_rows);
+        invoke_json(&args.args, builder, |builder, value| {
+            match value.flatten() {
+                Some(Value::Object(members)) => {
+                    let mut keys = members.keys().collect::<Vec<_>>();
+                    keys.sort_unstable();
+                    for key in keys {
+                        builder.values().append_value(key);
+                    }
+                    builder.append(true)
+                }
+                _ => builder.append(false),
+            }
+        })
+    }
+
+    fn documentation(&self) -> Option<&Documentation> {
+        self.doc()
+    }
+}
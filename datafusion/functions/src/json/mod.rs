@@ -0,0 +1,89 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! "json" DataFusion functions
+
+use std::sync::Arc;
+
+use datafusion_expr::ScalarUDF;
+
+pub mod common;
+pub mod json_array_length;
+pub mod json_contains;
+pub mod json_get;
+pub mod json_get_str;
+pub mod json_get_str_paths;
+pub mod json_keys;
+
+// create UDFs
+make_udf_function!(json_array_length::JsonArrayLengthFunc, json_array_length);
+make_udf_function!(json_contains::JsonContainsFunc, json_contains);
+make_udf_function!(json_get::JsonGetFunc, json_get);
+make_udf_function!(json_get_str::JsonGetStrFunc, json_get_str);
+make_udf_function!(json_get_str_paths::JsonGetStrPathsFunc, json_get_str_paths);
+make_udf_function!(json_keys::JsonKeysFunc, json_keys);
+
+pub mod expr_fn {
+    use datafusion_expr::Expr;
+
+    /// Returns the number of elements of the JSON array at `path` in `json`.
+    pub fn json_array_length(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_array_length().call(with_path(json, path))
+    }
+
+    /// Returns true if `path` exists in `json`.
+    pub fn json_contains(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_contains().call(with_path(json, path))
+    }
+
+    /// Returns the value at `path` in `json` as JSON text.
+    pub fn json_get(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_get().call(with_path(json, path))
+    }
+
+    /// Returns the value at `path` in `json` as text.
+    pub fn json_get_str(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_get_str().call(with_path(json, path))
+    }
+
+    /// Returns the keys of the JSON object at `path` in `json`.
+    pub fn json_keys(json: Expr, path: Vec<Expr>) -> Expr {
+        super::json_keys().call(with_path(json, path))
+    }
+
+    /// Returns the values at several `paths` in `json` as a list of text,
+    /// parsing `json` only once.
+    pub fn json_get_str_paths(json: Expr, paths: Vec<Expr>) -> Expr {
+        super::json_get_str_paths().call(with_path(json, paths))
+    }
+
+    fn with_path(json: Expr, path: Vec<Expr>) -> Vec<Expr> {
+        std::iter::once(json).chain(path).collect()
+    }
+}
+
+/// Returns all DataFusion functions defined in this package
+pub fn functions() -> Vec<Arc<ScalarUDF>> {
+    vec![
+        json_array_length(),
+        json_contains(),
+        json_get(),
+        json_get_str(),
+        json_get_str_paths(),
+        json_keys(),
+    ]
+}
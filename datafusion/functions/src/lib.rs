@@ -113,6 +113,13 @@ make_stub_package!(datetime, "datetime_expressions");
 pub mod encoding;
 make_stub_package!(encoding, "encoding_expressions");
 
+/// JSON functions.
+/// Contains functions such as `json_get` that read values from JSON strings.
+/// Enabled via feature flag `json_expressions`
+#[cfg(feature = "json_expressions")]
+pub mod json;
+make_stub_package!(json, "json_expressions");
+
 /// Mathematical functions.
 /// Enabled via feature flag `math_expressions`
 #[cfg(feature = "math_expressions")]
@@ -133,7 +140,11 @@ make_stub_package!(crypto, "crypto_expressions");
 pub mod unicode;
 make_stub_package!(unicode, "unicode_expressions");
 
-#[cfg(any(feature = "datetime_expressions", feature = "unicode_expressions"))]
+#[cfg(any(
+    feature = "datetime_expressions",
+    feature = "json_expressions",
+    feature = "unicode_expressions"
+))]
 pub mod planner;
 
 pub mod strings;
@@ -150,6 +161,8 @@ pub mod expr_fn {
     pub use super::datetime::expr_fn::*;
     #[cfg(feature = "encoding_expressions")]
     pub use super::encoding::expr_fn::*;
+    #[cfg(feature = "json_expressions")]
+    pub use super::json::expr_fn::*;
     #[cfg(feature = "math_expressions")]
     pub use super::math::expr_fn::*;
     #[cfg(feature = "regex_expressions")]
@@ -166,6 +179,7 @@ pub fn all_default_functions() -> Vec<Arc<ScalarUDF>> {
         .into_iter()
         .chain(datetime::functions())
         .chain(encoding::functions())
+        .chain(json::functions())
         .chain(math::functions())
         .chain(regex::functions())
         .chain(crypto::functions())
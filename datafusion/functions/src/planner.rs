@@ -18,11 +18,15 @@
 //! SQL planning extensions like [`UserDefinedFunctionPlanner`]
 
 use datafusion_common::Result;
+#[cfg(feature = "json_expressions")]
+use datafusion_common::{plan_err, DFSchema, ScalarValue};
 use datafusion_expr::{
     expr::ScalarFunction,
     planner::{ExprPlanner, PlannerResult},
     Expr,
 };
+#[cfg(feature = "json_expressions")]
+use datafusion_expr::{lit, planner::RawBinaryExpr, sqlparser::ast::BinaryOperator};
 
 #[derive(Default, Debug)]
 pub struct UserDefinedFunctionPlanner;
@@ -48,4 +52,65 @@ impl ExprPlanner for UserDefinedFunctionPlanner {
             ScalarFunction::new_udf(crate::unicode::substr(), args),
         )))
     }
+
+    /// Plans the JSON operators `->`, `->>`, `#>` and `#>>` as calls to
+    /// `json_get` and `json_get_str`
+    #[cfg(feature = "json_expressions")]
+    fn plan_binary_op(
+        &self,
+        expr: RawBinaryExpr,
+        _schema: &DFSchema,
+    ) -> Result<PlannerResult<RawBinaryExpr>> {
+        let RawBinaryExpr { op, left, right } = expr;
+        let (func, path) = match op {
+            BinaryOperator::Arrow => (crate::json::json_get(), vec![right]),
+            BinaryOperator::LongArrow => (crate::json::json_get_str(), vec![right]),
+            BinaryOperator::HashArrow => {
+                (crate::json::json_get(), text_array_path(&op, right)?)
+            }
+            BinaryOperator::HashLongArrow => {
+                (crate::json::json_get_str(), text_array_path(&op, right)?)
+            }
+            _ => return Ok(PlannerResult::Original(RawBinaryExpr { op, left, right })),
+        };
+
+        // `doc -> 'a' ->> 'b'` reads the whole path from a single `doc`
+        let mut args = match left {
+            Expr::ScalarFunction(ScalarFunction { func: inner, args })
+                if inner
+                    .inner()
+                    .as_any()
+                    .is::<crate::json::json_get::JsonGetFunc>() =>
+            {
+                args
+            }
+            left => vec![left],
+        };
+        args.extend(path);
+        Ok(PlannerResult::Planned(func.call(args)))
+    }
+}
+
+/// Splits a path literal of the `#>` and `#>>` operators, such as `'{a,0,b}'`,
+/// into its elements
+#[cfg(feature = "json_expressions")]
+fn text_array_path(op: &BinaryOperator, path: Expr) -> Result<Vec<Expr>> {
+    let text = match &path {
+        Expr::Literal(
+            ScalarValue::Utf8(Some(text))
+            | ScalarValue::LargeUtf8(Some(text))
+            | ScalarValue::Utf8View(Some(text)),
+        ) => text.trim(),
+        _ => {
+            return plan_err!(
+                "Path of the {op} operator must be a string literal, got {path}"
+            )
+        }
+    };
+    let Some(elements) = crate::json::common::split_text_path(text) else {
+        return plan_err!(
+            "Path of the {op} operator must be a text array such as '{{a,b}}', got '{text}'"
+        );
+    };
+    Ok(elements.into_iter().map(lit).collect())
 }
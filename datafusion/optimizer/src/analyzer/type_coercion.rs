@@ -34,10 +34,10 @@ use datafusion_common::{
 };
 use datafusion_expr::expr::{
     self, Alias, Between, BinaryExpr, Case, Exists, InList, InSubquery, Like,
-    ScalarFunction, Sort, WindowFunction,
+    ScalarFunction, SetComparison, Sort, WindowFunction,
 };
 use datafusion_expr::expr_rewriter::coerce_plan_expr_for_schema;
-use datafusion_expr::expr_schema::cast_subquery;
+use datafusion_expr::expr_schema::{cast_subquery, cast_subquery_columns};
 use datafusion_expr::logical_plan::Subquery;
 use datafusion_expr::type_coercion::binary::{comparison_coercion, like_coercion};
 use datafusion_expr::type_coercion::functions::{
@@ -47,7 +47,7 @@ use datafusion_expr::type_coercion::other::{
     get_coerce_type_for_case_expression, get_coerce_type_for_list,
 };
 use datafusion_expr::type_coercion::{is_datetime, is_utf8_or_large_utf8};
-use datafusion_expr::utils::merge_schema;
+use datafusion_expr::utils::{merge_schema, row_values};
 use datafusion_expr::{
     is_false, is_not_false, is_not_true, is_not_unknown, is_true, is_unknown, not,
     AggregateUDF, Expr, ExprFunctionExt, ExprSchemable, Join, Limit, LogicalPlan,
@@ -298,6 +298,41 @@ impl<'a> TypeCoercionRewriter<'a> {
             right.cast_to(&right_type, right_schema)?,
         ))
     }
+
+    /// Coerces the values of a row and the columns of a multi-column `IN`
+    /// subquery such as `(a, b) IN (SELECT x, y ...)` to common types
+    fn coerce_row_in_subquery(
+        &self,
+        row: Expr,
+        subquery: Subquery,
+        negated: bool,
+    ) -> Result<Transformed<Expr>> {
+        let Expr::ScalarFunction(ScalarFunction { func, args }) = row else {
+            return internal_err!("Expected a row value in InSubquery, got {row}");
+        };
+        let subquery_schema = Arc::clone(subquery.subquery.schema());
+        let common_types = args
+            .iter()
+            .zip(subquery_schema.fields())
+            .map(|(arg, field)| {
+                let expr_type = arg.get_type(self.schema)?;
+                let subquery_type = field.data_type();
+                comparison_coercion(&expr_type, subquery_type).ok_or(plan_datafusion_err!(
+                    "expr type {expr_type:?} can't cast to {subquery_type:?} in InSubquery"
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let args = args
+            .into_iter()
+            .zip(&common_types)
+            .map(|(arg, common_type)| arg.cast_to(common_type, self.schema))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Transformed::yes(Expr::InSubquery(InSubquery::new(
+            Box::new(Expr::ScalarFunction(ScalarFunction::new_udf(func, args))),
+            cast_subquery_columns(subquery, &common_types)?,
+            negated,
+        ))))
+    }
 }
 
 impl TreeNodeRewriter for TypeCoercionRewriter<'_> {
@@ -343,6 +378,17 @@ impl TreeNodeRewriter for TypeCoercionRewriter<'_> {
                     Arc::unwrap_or_clone(subquery.subquery),
                 )?
                 .data;
+                let len = new_plan.schema().fields().len();
+                if row_values(&expr, len).is_some() {
+                    return self.coerce_row_in_subquery(
+                        *expr,
+                        Subquery {
+                            subquery: Arc::new(new_plan),
+                            outer_ref_columns: subquery.outer_ref_columns,
+                        },
+                        negated,
+                    );
+                }
                 let expr_type = expr.get_type(self.schema)?;
                 let subquery_type = new_plan.schema().field(0).data_type();
                 let common_type = comparison_coercion(&expr_type, subquery_type).ok_or(plan_datafusion_err!(
@@ -359,6 +405,44 @@ impl TreeNodeRewriter for TypeCoercionRewriter<'_> {
                     negated,
                 ))))
             }
+            Expr::SetComparison(SetComparison {
+                expr,
+                subquery,
+                op,
+                quantifier,
+            }) => {
+                let new_plan = analyze_internal(
+                    self.schema,
+                    Arc::unwrap_or_clone(subquery.subquery),
+                )?
+                .data;
+                let expr_type = expr.get_type(self.schema)?;
+                let subquery_type = new_plan.schema().field(0).data_type();
+                let coerced_type = if matches!(
+                    op,
+                    Operator::LikeMatch
+                        | Operator::ILikeMatch
+                        | Operator::NotLikeMatch
+                        | Operator::NotILikeMatch
+                ) {
+                    like_coercion(&expr_type, subquery_type)
+                } else {
+                    comparison_coercion(&expr_type, subquery_type)
+                };
+                let common_type = coerced_type.ok_or(plan_datafusion_err!(
+                    "expr type {expr_type:?} can't cast to {subquery_type:?} in {op} {quantifier} subquery"
+                ))?;
+                let new_subquery = Subquery {
+                    subquery: Arc::new(new_plan),
+                    outer_ref_columns: subquery.outer_ref_columns,
+                };
+                Ok(Transformed::yes(Expr::SetComparison(SetComparison::new(
+                    Box::new(expr.cast_to(&common_type, self.schema)?),
+                    cast_subquery(new_subquery, &common_type)?,
+                    op,
+                    quantifier,
+                ))))
+            }
             Expr::Not(expr) => Ok(Transformed::yes(not(get_casted_expr_for_bool_op(
                 *expr,
                 self.schema,
@@ -49,6 +49,8 @@ pub struct PullUpCorrelatedExpr {
     /// mapping from the plan to its holding correlated columns
     pub correlated_subquery_cols_map: HashMap<LogicalPlan, BTreeSet<Column>>,
     pub in_predicate_opt: Option<Expr>,
+    /// Remove the join filters that are the same as the in_predicate. Defaults to **TRUE**
+    pub remove_duplicated_filters: bool,
     /// Is this an Exists(Not Exists) SubQuery. Defaults to **FALSE**
     pub exists_sub_query: bool,
     /// Can the correlated expressions be pulled up. Defaults to **TRUE**
@@ -79,6 +81,7 @@ impl PullUpCorrelatedExpr {
             join_filters: vec![],
             correlated_subquery_cols_map: HashMap::new(),
             in_predicate_opt: None,
+            remove_duplicated_filters: true,
             exists_sub_query: false,
             can_pull_up: true,
             can_pull_over_aggregation: true,
@@ -103,6 +106,15 @@ impl PullUpCorrelatedExpr {
         self
     }
 
+    /// Set if the join filters that are the same as the in_predicate are removed
+    pub fn with_remove_duplicated_filters(
+        mut self,
+        remove_duplicated_filters: bool,
+    ) -> Self {
+        self.remove_duplicated_filters = remove_duplicated_filters;
+        self
+    }
+
     /// Set if this is an Exists(Not Exists) SubQuery
     pub fn with_exists_sub_query(mut self, exists_sub_query: bool) -> Self {
         self.exists_sub_query = exists_sub_query;
@@ -169,9 +181,16 @@ impl TreeNodeRewriter for PullUpCorrelatedExpr {
                         .all(|&e| can_pullup_over_aggregation(e));
                 let (mut join_filters, subquery_filters) =
                     find_join_exprs(subquery_filter_exprs)?;
-                if let Some(in_predicate) = &self.in_predicate_opt {
+                if let Some(in_predicate) = self
+                    .in_predicate_opt
+                    .as_ref()
+                    .filter(|_| self.remove_duplicated_filters)
+                {
                     // in_predicate may be already included in the join filters, remove it from the join filters first.
-                    join_filters = remove_duplicated_filter(join_filters, in_predicate);
+                    // The in_predicate of a multi-column IN is a conjunction of comparisons.
+                    for predicate in split_conjunction(in_predicate) {
+                        join_filters = remove_duplicated_filter(join_filters, predicate);
+                    }
                 }
                 let correlated_subquery_cols =
                     collect_subquery_cols(&join_filters, subquery_schema)?;
@@ -458,9 +477,12 @@ fn remove_duplicated_filter(filters: Vec<Expr>, in_predicate: &Expr) -> Vec<Expr
             // ignore the binary order
             !match (filter, in_predicate) {
                 (Expr::BinaryExpr(a_expr), Expr::BinaryExpr(b_expr)) => {
-                    (a_expr.op == b_expr.op)
-                        && (a_expr.left == b_expr.left && a_expr.right == b_expr.right)
-                        || (a_expr.left == b_expr.right && a_expr.right == b_expr.left)
+                    (a_expr.op == b_expr.op
+                        && a_expr.left == b_expr.left
+                        && a_expr.right == b_expr.right)
+                        || (a_expr.op.swap() == Some(b_expr.op)
+                            && a_expr.left == b_expr.right
+                            && a_expr.right == b_expr.left)
                 }
                 _ => false,
             }
@@ -468,7 +490,7 @@ fn remove_duplicated_filter(filters: Vec<Expr>, in_predicate: &Expr) -> Vec<Expr
         .collect::<Vec<_>>()
 }
 
-fn agg_exprs_evaluation_result_on_empty_batch(
+pub(crate) fn agg_exprs_evaluation_result_on_empty_batch(
     agg_expr: &[Expr],
     schema: &DFSchemaRef,
     expr_result_map_for_count_bug: &mut ExprResultMap,
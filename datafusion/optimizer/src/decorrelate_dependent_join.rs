@@ -0,0 +1,1258 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`DecorrelateDependentJoin`] rewrites `LATERAL` joins and correlated scalar
+//! subqueries of any shape into regular joins
+
+use std::sync::Arc;
+
+use crate::decorrelate::{
+    agg_exprs_evaluation_result_on_empty_batch, UN_MATCHED_ROW_INDICATOR,
+};
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::alias::AliasGenerator;
+use datafusion_common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
+};
+use datafusion_common::{Column, DFSchema, HashMap, Result};
+use datafusion_expr::expr;
+use datafusion_expr::expr_rewriter::{create_col_from_scalar_expr, NamePreserver};
+use datafusion_expr::logical_plan::{
+    build_join_schema, Aggregate, Distinct, FetchType, Filter, Join, JoinType,
+    Projection, SkipType, Subquery, SubqueryAlias, Union, Window,
+};
+use datafusion_expr::utils::conjunction;
+use datafusion_expr::{
+    lit, BinaryExpr, Expr, ExprFunctionExt, LogicalPlan, LogicalPlanBuilder, Operator,
+    SortExpr, WindowUDF,
+};
+
+/// Optimizer rule that decorrelates dependent joins: `LATERAL` joins, and the
+/// correlated scalar subqueries that [`ScalarSubqueryToJoin`] can not pull up,
+/// such as subqueries with `LIMIT` or non-equality correlation predicates.
+///
+/// The rewrite follows "Unnesting Arbitrary Queries" (Neumann, Kemper). The
+/// distinct values of the outer columns referenced by the subquery, the
+/// *domain*, are pushed down through the subquery until no operator depends
+/// on the outer query anymore. The rewritten subquery computes its result for
+/// every domain value at once, and carries the domain columns in its output,
+/// so that it can be joined to the outer query on them:
+///
+/// ```text
+/// SELECT * FROM t1, LATERAL (SELECT * FROM t2 WHERE t2.a < t1.a ORDER BY t2.b LIMIT 3)
+/// ```
+///
+/// is rewritten to
+///
+/// ```text
+/// SELECT t1.*, s.a, s.b FROM t1 JOIN (
+///     SELECT * FROM (
+///         SELECT t2.*, d.a AS outer_a, row_number() OVER (PARTITION BY d.a ORDER BY t2.b) AS rn
+///         FROM t2, (SELECT DISTINCT a FROM t1) d
+///         WHERE t2.a < d.a
+///     ) WHERE rn <= 3
+/// ) s ON t1.a IS NOT DISTINCT FROM s.outer_a
+/// ```
+///
+/// [`ScalarSubqueryToJoin`]: crate::scalar_subquery_to_join::ScalarSubqueryToJoin
+#[derive(Default, Debug)]
+pub struct DecorrelateDependentJoin {}
+
+impl DecorrelateDependentJoin {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl OptimizerRule for DecorrelateDependentJoin {
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        match plan {
+            LogicalPlan::Join(join) => decorrelate_lateral_join(join, config),
+            LogicalPlan::Projection(projection)
+                if projection.expr.iter().any(contains_scalar_subquery) =>
+            {
+                let Some((input, exprs)) = decorrelate_scalar_subqueries(
+                    &projection.input,
+                    &projection.expr,
+                    config,
+                )?
+                else {
+                    return Ok(Transformed::no(LogicalPlan::Projection(projection)));
+                };
+                let name_preserver = NamePreserver::new_for_projection();
+                let exprs = projection
+                    .expr
+                    .iter()
+                    .zip(exprs)
+                    .map(|(old_expr, new_expr)| {
+                        name_preserver.save(old_expr).restore(new_expr)
+                    })
+                    .collect();
+                let new_plan = Projection::try_new(exprs, Arc::new(input))?;
+                Ok(Transformed::yes(LogicalPlan::Projection(new_plan)))
+            }
+            LogicalPlan::Filter(filter)
+                if contains_scalar_subquery(&filter.predicate) =>
+            {
+                let Some((input, mut predicate)) = decorrelate_scalar_subqueries(
+                    &filter.input,
+                    std::slice::from_ref(&filter.predicate),
+                    config,
+                )?
+                else {
+                    return Ok(Transformed::no(LogicalPlan::Filter(filter)));
+                };
+                // remove the columns of the joined subqueries again
+                let columns = filter.input.schema().columns();
+                let new_plan = LogicalPlanBuilder::from(input)
+                    .filter(predicate.swap_remove(0))?
+                    .project(columns.into_iter().map(Expr::Column))?
+                    .build()?;
+                Ok(Transformed::yes(new_plan))
+            }
+            plan => Ok(Transformed::no(plan)),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "decorrelate_dependent_join"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+}
+
+/// Rewrites a join whose right input is a `LATERAL` subquery referencing
+/// columns of the left input
+fn decorrelate_lateral_join(
+    join: Join,
+    config: &dyn OptimizerConfig,
+) -> Result<Transformed<LogicalPlan>> {
+    let Some(outer_columns) = lateral_outer_columns(&join.right, join.left.schema())
+    else {
+        return Ok(Transformed::no(LogicalPlan::Join(join)));
+    };
+
+    let domain = Domain::try_new(&join.left, outer_columns, config.alias_generator())?;
+    let rewriter = DependentJoinRewriter::new(&domain, config);
+    let Some(right) = rewriter.unnest(&join.right)? else {
+        return Ok(Transformed::no(LogicalPlan::Join(join)));
+    };
+
+    let mut filters: Vec<Expr> = join.filter.into_iter().collect();
+    filters.extend(domain.join_predicates(right.schema())?);
+    let schema = build_join_schema(join.left.schema(), right.schema(), &join.join_type)?;
+    let new_plan = LogicalPlan::Join(Join {
+        left: join.left,
+        right: Arc::new(right),
+        on: join.on,
+        filter: conjunction(filters),
+        join_type: join.join_type,
+        join_constraint: join.join_constraint,
+        schema: Arc::new(schema),
+        null_equals_null: join.null_equals_null,
+        null_aware: join.null_aware,
+    });
+
+    // remove the domain columns of the subquery from the join output
+    if !new_plan
+        .schema()
+        .fields()
+        .iter()
+        .any(|f| domain.is_domain_column(f.name()))
+    {
+        return Ok(Transformed::yes(new_plan));
+    }
+    let columns = new_plan
+        .schema()
+        .columns()
+        .into_iter()
+        .filter(|column| !domain.is_domain_column(&column.name))
+        .map(Expr::Column);
+    LogicalPlanBuilder::from(new_plan)
+        .project(columns)?
+        .build()
+        .map(Transformed::yes)
+}
+
+/// Returns the outer columns referenced by `plan` if it is a `LATERAL`
+/// subquery, optionally under an alias, whose outer references are all
+/// columns of `outer_schema`
+fn lateral_outer_columns(
+    plan: &LogicalPlan,
+    outer_schema: &DFSchema,
+) -> Option<Vec<Column>> {
+    match plan {
+        LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => {
+            lateral_outer_columns(input, outer_schema)
+        }
+        LogicalPlan::Subquery(Subquery { subquery, .. }) => {
+            let outer_columns = outer_columns(subquery);
+            (!outer_columns.is_empty()
+                && outer_columns.iter().all(|c| outer_schema.has_column(c)))
+            .then_some(outer_columns)
+        }
+        _ => None,
+    }
+}
+
+/// Rewrites the scalar subqueries in `exprs` to left joins with `input`
+///
+/// Returns the new input and the expressions with every subquery replaced by
+/// the column of its result, or `None` if a subquery can not be rewritten
+fn decorrelate_scalar_subqueries(
+    input: &LogicalPlan,
+    exprs: &[Expr],
+    config: &dyn OptimizerConfig,
+) -> Result<Option<(LogicalPlan, Vec<Expr>)>> {
+    let alias_gen = config.alias_generator();
+    let mut subqueries = vec![];
+    let exprs = exprs
+        .iter()
+        .map(|expr| {
+            expr.clone()
+                .transform_down(|expr| match expr {
+                    Expr::ScalarSubquery(subquery) => {
+                        let alias = alias_gen.next("__scalar_sq");
+                        let column = match subquery.subquery.head_output_expr()? {
+                            Some(scalar_expr) => {
+                                create_col_from_scalar_expr(&scalar_expr, alias.clone())?
+                            }
+                            None => {
+                                return Ok(Transformed::no(Expr::ScalarSubquery(
+                                    subquery,
+                                )))
+                            }
+                        };
+                        subqueries.push((subquery, alias));
+                        Ok(Transformed::new(
+                            Expr::Column(column),
+                            true,
+                            TreeNodeRecursion::Jump,
+                        ))
+                    }
+                    _ => Ok(Transformed::no(expr)),
+                })
+                .data()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut cur_input = input.clone();
+    for (subquery, alias) in subqueries {
+        let outer_columns = outer_columns(&subquery.subquery);
+        if !outer_columns
+            .iter()
+            .all(|c| cur_input.schema().has_column(c))
+        {
+            return Ok(None);
+        }
+        let (right, filters) = if outer_columns.is_empty() {
+            let right = LogicalPlanBuilder::from(subquery.subquery.as_ref().clone())
+                .alias(alias)?
+                .build()?;
+            (right, vec![])
+        } else {
+            let domain = Domain::try_new(&cur_input, outer_columns, alias_gen)?;
+            let rewriter = DependentJoinRewriter::new(&domain, config);
+            let Some(right) = rewriter.unnest(&subquery.subquery)? else {
+                return Ok(None);
+            };
+            let right = LogicalPlanBuilder::from(right).alias(alias)?.build()?;
+            let filters = domain.join_predicates(right.schema())?;
+            (right, filters)
+        };
+        cur_input = LogicalPlanBuilder::from(cur_input)
+            .join_on(right, JoinType::Left, filters)?
+            .build()?;
+    }
+    Ok(Some((cur_input, exprs)))
+}
+
+/// Returns true if the expression has a scalar subquery somewhere in it
+fn contains_scalar_subquery(expr: &Expr) -> bool {
+    expr.exists(|expr| Ok(matches!(expr, Expr::ScalarSubquery(_))))
+        .expect("Inner is always Ok")
+}
+
+/// Returns true if the expression has a subquery somewhere in it
+fn contains_subquery(expr: &Expr) -> bool {
+    expr.exists(|expr| {
+        Ok(matches!(
+            expr,
+            Expr::ScalarSubquery(_) | Expr::Exists(_) | Expr::InSubquery(_)
+        ))
+    })
+    .expect("Inner is always Ok")
+}
+
+/// Returns the distinct outer columns referenced by `plan`
+fn outer_columns(plan: &LogicalPlan) -> Vec<Column> {
+    let mut columns = vec![];
+    for expr in plan.all_out_ref_exprs() {
+        expr.apply(|expr| {
+            if let Expr::OuterReferenceColumn(_, column) = expr {
+                if !columns.contains(column) {
+                    columns.push(column.clone());
+                }
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })
+        .expect("Inner is always Ok");
+    }
+    columns
+}
+
+/// The distinct values of the outer columns a dependent subquery references
+struct Domain {
+    /// Computes the distinct values of the outer columns
+    plan: LogicalPlan,
+    /// The outer columns, and the names of the domain columns replacing them
+    columns: Vec<(Column, String)>,
+}
+
+impl Domain {
+    fn try_new(
+        outer: &LogicalPlan,
+        outer_columns: Vec<Column>,
+        alias_gen: &Arc<AliasGenerator>,
+    ) -> Result<Self> {
+        let columns: Vec<_> = outer_columns
+            .into_iter()
+            .map(|column| (column, alias_gen.next("__outer_col")))
+            .collect();
+        let plan = LogicalPlanBuilder::from(outer.clone())
+            .project(
+                columns
+                    .iter()
+                    .map(|(column, name)| Expr::Column(column.clone()).alias(name)),
+            )?
+            .distinct()?
+            .build()?;
+        Ok(Self { plan, columns })
+    }
+
+    fn is_domain_column(&self, name: &str) -> bool {
+        self.columns
+            .iter()
+            .any(|(_, domain_name)| domain_name == name)
+    }
+
+    /// Returns the domain columns of `schema`
+    fn columns_of(&self, schema: &DFSchema) -> Result<Vec<Column>> {
+        self.columns
+            .iter()
+            .map(|(_, name)| {
+                schema
+                    .qualified_field_with_unqualified_name(name)
+                    .map(Column::from)
+            })
+            .collect()
+    }
+
+    /// Returns the predicates joining the outer columns to the domain columns
+    /// of `schema`
+    fn join_predicates(&self, schema: &DFSchema) -> Result<Vec<Expr>> {
+        Ok(self
+            .columns
+            .iter()
+            .zip(self.columns_of(schema)?)
+            .map(|((outer_column, _), domain_column)| {
+                is_not_distinct_from(outer_column.clone(), domain_column)
+            })
+            .collect())
+    }
+
+    /// Returns true if `plan` references any of the outer columns
+    fn is_referenced_by(&self, plan: &LogicalPlan) -> bool {
+        outer_columns(plan)
+            .iter()
+            .any(|column| self.columns.iter().any(|(outer, _)| outer == column))
+    }
+
+    /// Returns true if `expr` references any of the outer columns
+    fn is_referenced_in(&self, expr: &Expr) -> bool {
+        expr.exists(|expr| {
+            Ok(matches!(
+                expr,
+                Expr::OuterReferenceColumn(_, column)
+                    if self.columns.iter().any(|(outer, _)| outer == column)
+            ))
+        })
+        .expect("Inner is always Ok")
+    }
+
+    /// Replaces the references to the outer columns in `expr` by the domain
+    /// columns of `schema`
+    fn replace_outer_refs(&self, expr: Expr, schema: &DFSchema) -> Result<Expr> {
+        expr.transform_up(|expr| {
+            let Expr::OuterReferenceColumn(_, column) = &expr else {
+                return Ok(Transformed::no(expr));
+            };
+            match self.columns.iter().find(|(outer, _)| outer == column) {
+                Some((_, name)) => {
+                    let domain_column =
+                        Column::from(schema.qualified_field_with_unqualified_name(name)?);
+                    Ok(Transformed::yes(Expr::Column(domain_column)))
+                }
+                None => Ok(Transformed::no(expr)),
+            }
+        })
+        .data()
+    }
+}
+
+/// Pushes a [`Domain`] down through a dependent subquery
+///
+/// Every rewritten plan produces the columns of the original plan, followed
+/// by the domain columns. Methods return `None` for plans that can not be
+/// rewritten.
+struct DependentJoinRewriter<'a> {
+    domain: &'a Domain,
+    alias_gen: &'a Arc<AliasGenerator>,
+    row_number: Option<Arc<WindowUDF>>,
+}
+
+impl<'a> DependentJoinRewriter<'a> {
+    fn new(domain: &'a Domain, config: &'a dyn OptimizerConfig) -> Self {
+        let row_number = config
+            .function_registry()
+            .and_then(|registry| registry.udwf("row_number").ok());
+        Self {
+            domain,
+            alias_gen: config.alias_generator(),
+            row_number,
+        }
+    }
+
+    fn unnest(&self, plan: &LogicalPlan) -> Result<Option<LogicalPlan>> {
+        // the subquery is the same for all domain values
+        if !self.domain.is_referenced_by(plan) {
+            return LogicalPlanBuilder::from(plan.clone())
+                .cross_join(self.domain.plan.clone())?
+                .build()
+                .map(Some);
+        }
+        if plan.expressions().iter().any(contains_subquery) {
+            return Ok(None);
+        }
+
+        let new_plan = match plan {
+            LogicalPlan::Filter(filter) => {
+                let Some(input) = self.unnest(&filter.input)? else {
+                    return Ok(None);
+                };
+                let predicate = self
+                    .domain
+                    .replace_outer_refs(filter.predicate.clone(), input.schema())?;
+                LogicalPlan::Filter(Filter::try_new(predicate, Arc::new(input))?)
+            }
+            LogicalPlan::Projection(projection) => {
+                let Some(input) = self.unnest(&projection.input)? else {
+                    return Ok(None);
+                };
+                let mut exprs = self.rewrite_exprs(plan, &projection.expr, &input)?;
+                exprs.extend(self.domain_exprs(&input)?);
+                LogicalPlan::Projection(Projection::try_new(exprs, Arc::new(input))?)
+            }
+            LogicalPlan::Aggregate(aggregate) => {
+                if aggregate
+                    .group_expr
+                    .iter()
+                    .any(|expr| matches!(expr, Expr::GroupingSet(_)))
+                {
+                    return Ok(None);
+                }
+                let Some(input) = self.unnest(&aggregate.input)? else {
+                    return Ok(None);
+                };
+                let mut group_expr =
+                    self.rewrite_exprs(plan, &aggregate.group_expr, &input)?;
+                let aggr_expr = self.rewrite_exprs(plan, &aggregate.aggr_expr, &input)?;
+                if aggregate.group_expr.is_empty() {
+                    // the aggregate must produce a row for every domain value,
+                    // even if the subquery is empty for it
+                    group_expr.extend(
+                        self.domain.columns_of(input.schema())?.into_iter().map(
+                            |column| {
+                                let renamed = renamed_domain_column(&column.name);
+                                Expr::Column(column).alias(renamed)
+                            },
+                        ),
+                    );
+                    group_expr.push(lit(true).alias(UN_MATCHED_ROW_INDICATOR));
+                    let new_aggregate =
+                        Aggregate::try_new(Arc::new(input), group_expr, aggr_expr)?;
+                    return self
+                        .pad_scalar_aggregate(aggregate, new_aggregate)
+                        .map(Some);
+                }
+                group_expr.extend(self.domain_exprs(&input)?);
+                let new_aggregate =
+                    Aggregate::try_new(Arc::new(input), group_expr, aggr_expr)?;
+                self.move_domain_last(LogicalPlan::Aggregate(new_aggregate), &[])?
+            }
+            LogicalPlan::Window(window) => {
+                let Some(input) = self.unnest(&window.input)? else {
+                    return Ok(None);
+                };
+                let partition_by = self.domain_exprs(&input)?;
+                let window_expr = self
+                    .rewrite_exprs(plan, &window.window_expr, &input)?
+                    .into_iter()
+                    .map(|expr| {
+                        let saved_name = NamePreserver::new(plan).save(&expr);
+                        let expr = expr
+                            .transform_down(|expr| match expr {
+                                Expr::WindowFunction(mut window_function) => {
+                                    window_function
+                                        .partition_by
+                                        .extend(partition_by.iter().cloned());
+                                    Ok(Transformed::new(
+                                        Expr::WindowFunction(window_function),
+                                        true,
+                                        TreeNodeRecursion::Jump,
+                                    ))
+                                }
+                                _ => Ok(Transformed::no(expr)),
+                            })
+                            .data()?;
+                        Ok(saved_name.restore(expr))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let new_window = Window::try_new(window_expr, Arc::new(input))?;
+                self.move_domain_last(LogicalPlan::Window(new_window), &[])?
+            }
+            LogicalPlan::Sort(sort) => match sort.fetch {
+                Some(fetch) => {
+                    return self.unnest_top_n(
+                        &sort.input,
+                        Some(&sort.expr),
+                        0,
+                        Some(fetch),
+                    )
+                }
+                // the order of the subquery does not survive the join, a sort
+                // only matters for the limits above it
+                None => return self.unnest(&sort.input),
+            },
+            LogicalPlan::Limit(limit) => {
+                let (SkipType::Literal(skip), FetchType::Literal(fetch)) =
+                    (limit.get_skip_type()?, limit.get_fetch_type()?)
+                else {
+                    return Ok(None);
+                };
+                return match limit.input.as_ref() {
+                    LogicalPlan::Sort(sort) if sort.fetch.is_none() => {
+                        self.unnest_top_n(&sort.input, Some(&sort.expr), skip, fetch)
+                    }
+                    input => self.unnest_top_n(input, None, skip, fetch),
+                };
+            }
+            LogicalPlan::Join(join) => return self.unnest_join(join),
+            LogicalPlan::Union(union) => {
+                let Some(inputs) = union
+                    .inputs
+                    .iter()
+                    .map(|input| self.unnest(input))
+                    .collect::<Result<Option<Vec<_>>>>()?
+                else {
+                    return Ok(None);
+                };
+                LogicalPlan::Union(Union::try_new_with_loose_types(
+                    inputs.into_iter().map(Arc::new).collect(),
+                )?)
+            }
+            LogicalPlan::Distinct(Distinct::All(input)) => {
+                let Some(input) = self.unnest(input)? else {
+                    return Ok(None);
+                };
+                LogicalPlan::Distinct(Distinct::All(Arc::new(input)))
+            }
+            LogicalPlan::SubqueryAlias(subquery_alias) => {
+                let Some(input) = self.unnest(&subquery_alias.input)? else {
+                    return Ok(None);
+                };
+                LogicalPlan::SubqueryAlias(SubqueryAlias::try_new(
+                    Arc::new(input),
+                    subquery_alias.alias.clone(),
+                )?)
+            }
+            // nested subqueries referencing the same outer columns
+            LogicalPlan::Subquery(subquery) => return self.unnest(&subquery.subquery),
+            LogicalPlan::Unnest(unnest) => {
+                let Some(input) = self.unnest(&unnest.input)? else {
+                    return Ok(None);
+                };
+                let mut unnest = unnest.clone();
+                unnest.input = Arc::new(input);
+                LogicalPlan::Unnest(unnest).recompute_schema()?
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(new_plan))
+    }
+
+    /// Replaces the outer references in the expressions of `plan`, keeping
+    /// their names
+    fn rewrite_exprs(
+        &self,
+        plan: &LogicalPlan,
+        exprs: &[Expr],
+        input: &LogicalPlan,
+    ) -> Result<Vec<Expr>> {
+        let name_preserver = NamePreserver::new(plan);
+        exprs
+            .iter()
+            .map(|expr| {
+                let saved_name = name_preserver.save(expr);
+                let expr = self
+                    .domain
+                    .replace_outer_refs(expr.clone(), input.schema())?;
+                Ok(saved_name.restore(expr))
+            })
+            .collect()
+    }
+
+    fn domain_exprs(&self, plan: &LogicalPlan) -> Result<Vec<Expr>> {
+        Ok(self
+            .domain
+            .columns_of(plan.schema())?
+            .into_iter()
+            .map(Expr::Column)
+            .collect())
+    }
+
+    /// Projects the columns of `plan` in the order of the original plan,
+    /// followed by the domain columns, and without the `excluded` columns
+    fn move_domain_last(
+        &self,
+        plan: LogicalPlan,
+        excluded: &[String],
+    ) -> Result<LogicalPlan> {
+        let domain_columns = self.domain.columns_of(plan.schema())?;
+        let mut columns: Vec<_> = plan
+            .schema()
+            .columns()
+            .into_iter()
+            .filter(|column| {
+                !self.domain.is_domain_column(&column.name)
+                    && !excluded.contains(&column.name)
+            })
+            .collect();
+        columns.extend(domain_columns);
+        if columns == plan.schema().columns() {
+            return Ok(plan);
+        }
+        LogicalPlanBuilder::from(plan)
+            .project(columns.into_iter().map(Expr::Column))?
+            .build()
+    }
+
+    /// Joins the domain to a scalar aggregate grouped by the renamed domain
+    /// columns, so that domain values for which the subquery is empty get the
+    /// result of the aggregate on no rows, like `0` for `count`
+    fn pad_scalar_aggregate(
+        &self,
+        original: &Aggregate,
+        aggregate: Aggregate,
+    ) -> Result<LogicalPlan> {
+        let mut empty_results = HashMap::new();
+        agg_exprs_evaluation_result_on_empty_batch(
+            &original.aggr_expr,
+            original.input.schema(),
+            &mut empty_results,
+        )?;
+
+        let domain_columns = self.domain.plan.schema().columns();
+        let on = domain_columns.iter().map(|column| {
+            let renamed = Column::new_unqualified(renamed_domain_column(&column.name));
+            is_not_distinct_from(column.clone(), renamed)
+        });
+        let unmatched =
+            Expr::Column(Column::new_unqualified(UN_MATCHED_ROW_INDICATOR)).is_null();
+        let mut exprs = original
+            .schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let name = field.name();
+                let column = Expr::Column(Column::new_unqualified(name));
+                match empty_results.get(name) {
+                    Some(empty_result) => Expr::Case(expr::Case {
+                        expr: None,
+                        when_then_expr: vec![(
+                            Box::new(unmatched.clone()),
+                            Box::new(empty_result.clone()),
+                        )],
+                        else_expr: Some(Box::new(column)),
+                    })
+                    .alias(name),
+                    None => column,
+                }
+            })
+            .collect::<Vec<_>>();
+        exprs.extend(domain_columns.iter().cloned().map(Expr::Column));
+
+        LogicalPlanBuilder::from(self.domain.plan.clone())
+            .join_on(LogicalPlan::Aggregate(aggregate), JoinType::Left, on)?
+            .project(exprs)?
+            .build()
+    }
+
+    /// Rewrites `LIMIT fetch OFFSET skip` over `input`, optionally sorted by
+    /// `sort_exprs`, to a filter on the row number of the rows within each
+    /// domain value
+    fn unnest_top_n(
+        &self,
+        input: &LogicalPlan,
+        sort_exprs: Option<&Vec<SortExpr>>,
+        skip: usize,
+        fetch: Option<usize>,
+    ) -> Result<Option<LogicalPlan>> {
+        let Some(row_number) = &self.row_number else {
+            return Ok(None);
+        };
+        let Some(input) = self.unnest(input)? else {
+            return Ok(None);
+        };
+        let order_by = sort_exprs
+            .into_iter()
+            .flatten()
+            .map(|sort| {
+                let expr = self
+                    .domain
+                    .replace_outer_refs(sort.expr.clone(), input.schema())?;
+                Ok(sort.with_expr(expr))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let row_number_name = self.alias_gen.next("__row_number");
+        let row_number = row_number
+            .call(vec![])
+            .partition_by(self.domain_exprs(&input)?)
+            .order_by(order_by)
+            .build()?
+            .alias(&row_number_name);
+        let row_number_column = Expr::Column(Column::new_unqualified(row_number_name));
+
+        let mut predicates = vec![];
+        if skip > 0 {
+            predicates.push(row_number_column.clone().gt(lit(skip as u64)));
+        }
+        if let Some(fetch) = fetch {
+            predicates.push(row_number_column.lt_eq(lit((skip + fetch) as u64)));
+        }
+        let columns = input.schema().columns();
+        let mut builder = LogicalPlanBuilder::from(input).window(vec![row_number])?;
+        if let Some(predicate) = conjunction(predicates) {
+            builder = builder.filter(predicate)?;
+        }
+        builder
+            .project(columns.into_iter().map(Expr::Column))?
+            .build()
+            .map(Some)
+    }
+
+    fn unnest_join(&self, join: &Join) -> Result<Option<LogicalPlan>> {
+        let left_dependent = self.domain.is_referenced_by(&join.left);
+        let right_dependent = self.domain.is_referenced_by(&join.right);
+        let condition_dependent = join.on.iter().any(|(l, r)| {
+            self.domain.is_referenced_in(l) || self.domain.is_referenced_in(r)
+        }) || join
+            .filter
+            .as_ref()
+            .is_some_and(|filter| self.domain.is_referenced_in(filter));
+
+        // an inner join only needs the domain on its dependent side
+        if join.join_type == JoinType::Inner
+            && !condition_dependent
+            && left_dependent != right_dependent
+        {
+            let (left, right) = if left_dependent {
+                let Some(left) = self.unnest(&join.left)? else {
+                    return Ok(None);
+                };
+                (Arc::new(left), Arc::clone(&join.right))
+            } else {
+                let Some(right) = self.unnest(&join.right)? else {
+                    return Ok(None);
+                };
+                (Arc::clone(&join.left), Arc::new(right))
+            };
+            let schema =
+                build_join_schema(left.schema(), right.schema(), &join.join_type)?;
+            let new_join = LogicalPlan::Join(Join {
+                left,
+                right,
+                schema: Arc::new(schema),
+                ..join.clone()
+            });
+            return self.move_domain_last(new_join, &[]).map(Some);
+        }
+
+        // otherwise both sides are joined on the domain columns, and the
+        // output keeps the domain columns of the side it preserves
+        let preserve_left = match join.join_type {
+            JoinType::Inner
+            | JoinType::Left
+            | JoinType::LeftSemi
+            | JoinType::LeftAnti
+            | JoinType::LeftMark => true,
+            JoinType::Right | JoinType::RightSemi | JoinType::RightAnti => false,
+            JoinType::Full => return Ok(None),
+        };
+        let (Some(left), Some(right)) =
+            (self.unnest(&join.left)?, self.unnest(&join.right)?)
+        else {
+            return Ok(None);
+        };
+        let (left, right) = if preserve_left {
+            (left, self.rename_domain(right)?)
+        } else {
+            (self.rename_domain(left)?, right)
+        };
+
+        let on = join
+            .on
+            .iter()
+            .map(|(l, r)| {
+                Ok((
+                    self.domain.replace_outer_refs(l.clone(), left.schema())?,
+                    self.domain.replace_outer_refs(r.clone(), right.schema())?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let input_schema = left.schema().join(right.schema())?;
+        let mut filters = join
+            .filter
+            .iter()
+            .map(|filter| {
+                self.domain
+                    .replace_outer_refs(filter.clone(), &input_schema)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let renamed = self
+            .domain
+            .columns
+            .iter()
+            .map(|(_, name)| renamed_domain_column(name))
+            .collect::<Vec<_>>();
+        for (domain_column, renamed) in self
+            .domain
+            .columns_of(&input_schema)?
+            .into_iter()
+            .zip(&renamed)
+        {
+            let renamed = Column::from(
+                input_schema.qualified_field_with_unqualified_name(renamed)?,
+            );
+            filters.push(is_not_distinct_from(domain_column, renamed));
+        }
+
+        let schema = build_join_schema(left.schema(), right.schema(), &join.join_type)?;
+        let new_join = LogicalPlan::Join(Join {
+            left: Arc::new(left),
+            right: Arc::new(right),
+            on,
+            filter: conjunction(filters),
+            join_type: join.join_type,
+            join_constraint: join.join_constraint,
+            schema: Arc::new(schema),
+            null_equals_null: join.null_equals_null,
+            null_aware: join.null_aware,
+        });
+        self.move_domain_last(new_join, &renamed).map(Some)
+    }
+
+    /// Renames the domain columns of `plan`, so that it can be joined to
+    /// another plan with domain columns
+    fn rename_domain(&self, plan: LogicalPlan) -> Result<LogicalPlan> {
+        let exprs = plan
+            .schema()
+            .columns()
+            .into_iter()
+            .map(|column| {
+                if self.domain.is_domain_column(&column.name) {
+                    let renamed = renamed_domain_column(&column.name);
+                    Expr::Column(column).alias(renamed)
+                } else {
+                    Expr::Column(column)
+                }
+            })
+            .collect::<Vec<_>>();
+        LogicalPlanBuilder::from(plan).project(exprs)?.build()
+    }
+}
+
+fn is_not_distinct_from(left: Column, right: Column) -> Expr {
+    Expr::BinaryExpr(BinaryExpr::new(
+        Box::new(Expr::Column(left)),
+        Operator::IsNotDistinctFrom,
+        Box::new(Expr::Column(right)),
+    ))
+}
+
+fn renamed_domain_column(name: &str) -> String {
+    format!("{name}_other")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+
+    use crate::{Optimizer, OptimizerContext};
+
+    use arrow::datatypes::DataType;
+    use chrono::{DateTime, Utc};
+    use datafusion_common::alias::AliasGenerator;
+    use datafusion_common::config::ConfigOptions;
+    use datafusion_common::plan_err;
+    use datafusion_expr::planner::ExprPlanner;
+    use datafusion_expr::registry::FunctionRegistry;
+    use datafusion_expr::{
+        col, out_ref_col, scalar_subquery, AggregateUDF, ScalarUDF, WindowUDF,
+    };
+    use datafusion_functions_aggregate::count::count;
+    use datafusion_functions_window::row_number::row_number_udwf;
+    use std::collections::HashSet;
+
+    fn assert_optimized_plan_equal(plan: LogicalPlan, expected: &str) -> Result<()> {
+        assert_optimized_plan_eq(
+            Arc::new(DecorrelateDependentJoin::new()),
+            plan,
+            expected,
+        )
+    }
+
+    /// Registry providing `row_number`, which limits are rewritten to
+    #[derive(Debug)]
+    struct RowNumberRegistry {}
+
+    impl FunctionRegistry for RowNumberRegistry {
+        fn udfs(&self) -> HashSet<String> {
+            HashSet::new()
+        }
+
+        fn udf(&self, name: &str) -> Result<Arc<ScalarUDF>> {
+            plan_err!("There is no UDF named \"{name}\" in the registry")
+        }
+
+        fn udaf(&self, name: &str) -> Result<Arc<AggregateUDF>> {
+            plan_err!("There is no UDAF named \"{name}\" in the registry")
+        }
+
+        fn udwf(&self, name: &str) -> Result<Arc<WindowUDF>> {
+            match name {
+                "row_number" => Ok(row_number_udwf()),
+                _ => plan_err!("There is no UDWF named \"{name}\" in the registry"),
+            }
+        }
+
+        fn expr_planners(&self) -> Vec<Arc<dyn ExprPlanner>> {
+            vec![]
+        }
+    }
+
+    /// [`OptimizerContext`] with a [`RowNumberRegistry`]
+    struct RowNumberConfig {
+        context: OptimizerContext,
+        registry: RowNumberRegistry,
+    }
+
+    impl OptimizerConfig for RowNumberConfig {
+        fn query_execution_start_time(&self) -> DateTime<Utc> {
+            self.context.query_execution_start_time()
+        }
+
+        fn alias_generator(&self) -> &Arc<AliasGenerator> {
+            self.context.alias_generator()
+        }
+
+        fn options(&self) -> &ConfigOptions {
+            self.context.options()
+        }
+
+        fn function_registry(&self) -> Option<&dyn FunctionRegistry> {
+            Some(&self.registry)
+        }
+    }
+
+    fn assert_optimized_plan_with_row_number_equal(
+        plan: LogicalPlan,
+        expected: &str,
+    ) -> Result<()> {
+        let config = RowNumberConfig {
+            context: OptimizerContext::new().with_max_passes(1),
+            registry: RowNumberRegistry {},
+        };
+        let optimizer =
+            Optimizer::with_rules(vec![Arc::new(DecorrelateDependentJoin::new())]);
+        let optimized_plan = optimizer.optimize(plan, &config, |_, _| {})?;
+        assert_eq!(format!("{optimized_plan}"), expected);
+        Ok(())
+    }
+
+    /// Returns `customer CROSS JOIN LATERAL (subquery) AS o`
+    fn customer_lateral_join(subquery: LogicalPlan) -> Result<LogicalPlan> {
+        let lateral = LogicalPlan::Subquery(Subquery {
+            subquery: Arc::new(subquery),
+            outer_ref_columns: vec![out_ref_col(DataType::Int64, "customer.c_custkey")],
+        });
+        LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .cross_join(LogicalPlanBuilder::from(lateral).alias("o")?.build()?)?
+            .build()
+    }
+
+    /// Returns the orders of the outer customer
+    fn customer_orders() -> Result<LogicalPlanBuilder> {
+        LogicalPlanBuilder::from(scan_tpch_table("orders")).filter(
+            col("orders.o_custkey")
+                .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+        )
+    }
+
+    /// Test a lateral join with ORDER BY and LIMIT, rewritten to a filter on
+    /// the row number within each outer row
+    #[test]
+    fn lateral_join_top_n() -> Result<()> {
+        let subquery = customer_orders()?
+            .sort(vec![col("orders.o_totalprice").sort(false, true)])?
+            .limit(1, Some(2))?
+            .project(vec![col("orders.o_orderkey")])?
+            .build()?;
+        let plan = customer_lateral_join(subquery)?;
+
+        let expected = "Projection: customer.c_custkey, customer.c_name, o.o_orderkey\
+        \n  Inner Join:  Filter: customer.c_custkey IS NOT DISTINCT FROM o.__outer_col_1\
+        \n    TableScan: customer\
+        \n    SubqueryAlias: o\
+        \n      Projection: orders.o_orderkey, __outer_col_1\
+        \n        Projection: orders.o_orderkey, orders.o_custkey, orders.o_orderstatus, orders.o_totalprice, __outer_col_1\
+        \n          Filter: __row_number_2 > UInt64(1) AND __row_number_2 <= UInt64(3)\
+        \n            WindowAggr: windowExpr=[[row_number() PARTITION BY [__outer_col_1] ORDER BY [orders.o_totalprice DESC NULLS FIRST] ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS __row_number_2]]\
+        \n              Filter: orders.o_custkey = __outer_col_1\
+        \n                Cross Join: \
+        \n                  TableScan: orders\
+        \n                  Distinct:\
+        \n                    Projection: customer.c_custkey AS __outer_col_1\
+        \n                      TableScan: customer";
+        assert_optimized_plan_with_row_number_equal(plan, expected)
+    }
+
+    /// Test that a limit is not decorrelated without `row_number`
+    #[test]
+    fn lateral_join_limit_without_row_number() -> Result<()> {
+        let subquery = customer_orders()?
+            .limit(0, Some(1))?
+            .project(vec![col("orders.o_orderkey")])?
+            .build()?;
+        let plan = customer_lateral_join(subquery)?;
+
+        let expected = "Cross Join: \
+        \n  TableScan: customer\
+        \n  SubqueryAlias: o\
+        \n    Subquery:\
+        \n      Projection: orders.o_orderkey\
+        \n        Limit: skip=0, fetch=1\
+        \n          Filter: orders.o_custkey = outer_ref(customer.c_custkey)\
+        \n            TableScan: orders";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test a lateral join with a grouped aggregate, which is grouped by the
+    /// outer columns as well
+    #[test]
+    fn lateral_join_grouped_aggregate() -> Result<()> {
+        let subquery = customer_orders()?
+            .aggregate(
+                vec![col("orders.o_orderstatus")],
+                vec![count(col("orders.o_orderkey"))],
+            )?
+            .build()?;
+        let plan = customer_lateral_join(subquery)?;
+
+        let expected = "Projection: customer.c_custkey, customer.c_name, o.o_orderstatus, o.count(orders.o_orderkey)\
+        \n  Inner Join:  Filter: customer.c_custkey IS NOT DISTINCT FROM o.__outer_col_1\
+        \n    TableScan: customer\
+        \n    SubqueryAlias: o\
+        \n      Projection: orders.o_orderstatus, count(orders.o_orderkey), __outer_col_1\
+        \n        Aggregate: groupBy=[[orders.o_orderstatus, __outer_col_1]], aggr=[[count(orders.o_orderkey)]]\
+        \n          Filter: orders.o_custkey = __outer_col_1\
+        \n            Cross Join: \
+        \n              TableScan: orders\
+        \n              Distinct:\
+        \n                Projection: customer.c_custkey AS __outer_col_1\
+        \n                  TableScan: customer";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test a lateral join with a union, whose inputs are all decorrelated
+    #[test]
+    fn lateral_join_union() -> Result<()> {
+        let subquery = customer_orders()?
+            .project(vec![col("orders.o_orderkey")])?
+            .union(
+                LogicalPlanBuilder::from(scan_tpch_table("lineitem"))
+                    .filter(
+                        col("lineitem.l_partkey")
+                            .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+                    )?
+                    .project(vec![col("lineitem.l_orderkey")])?
+                    .build()?,
+            )?
+            .build()?;
+        let plan = customer_lateral_join(subquery)?;
+
+        let expected = "Projection: customer.c_custkey, customer.c_name, o.o_orderkey\
+        \n  Inner Join:  Filter: customer.c_custkey IS NOT DISTINCT FROM o.__outer_col_1\
+        \n    TableScan: customer\
+        \n    SubqueryAlias: o\
+        \n      Union\
+        \n        Projection: orders.o_orderkey, __outer_col_1\
+        \n          Filter: orders.o_custkey = __outer_col_1\
+        \n            Cross Join: \
+        \n              TableScan: orders\
+        \n              Distinct:\
+        \n                Projection: customer.c_custkey AS __outer_col_1\
+        \n                  TableScan: customer\
+        \n        Projection: lineitem.l_orderkey, __outer_col_1\
+        \n          Filter: lineitem.l_partkey = __outer_col_1\
+        \n            Cross Join: \
+        \n              TableScan: lineitem\
+        \n              Distinct:\
+        \n                Projection: customer.c_custkey AS __outer_col_1\
+        \n                  TableScan: customer";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test a lateral join with a join in which only one side depends on the
+    /// outer row
+    #[test]
+    fn lateral_join_with_join() -> Result<()> {
+        let subquery = customer_orders()?
+            .join(
+                scan_tpch_table("lineitem"),
+                JoinType::Inner,
+                (vec!["orders.o_orderkey"], vec!["lineitem.l_orderkey"]),
+                None,
+            )?
+            .project(vec![col("orders.o_orderkey"), col("lineitem.l_quantity")])?
+            .build()?;
+        let plan = customer_lateral_join(subquery)?;
+
+        let expected = "Projection: customer.c_custkey, customer.c_name, o.o_orderkey, o.l_quantity\
+        \n  Inner Join:  Filter: customer.c_custkey IS NOT DISTINCT FROM o.__outer_col_1\
+        \n    TableScan: customer\
+        \n    SubqueryAlias: o\
+        \n      Projection: orders.o_orderkey, lineitem.l_quantity, __outer_col_1\
+        \n        Projection: orders.o_orderkey, orders.o_custkey, orders.o_orderstatus, orders.o_totalprice, lineitem.l_orderkey, lineitem.l_partkey, lineitem.l_suppkey, lineitem.l_linenumber, lineitem.l_quantity, lineitem.l_extendedprice, __outer_col_1\
+        \n          Inner Join: orders.o_orderkey = lineitem.l_orderkey\
+        \n            Filter: orders.o_custkey = __outer_col_1\
+        \n              Cross Join: \
+        \n                TableScan: orders\
+        \n                Distinct:\
+        \n                  Projection: customer.c_custkey AS __outer_col_1\
+        \n                    TableScan: customer\
+        \n            TableScan: lineitem";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test a lateral join with a non-equality correlation predicate
+    #[test]
+    fn lateral_join_non_equal_predicate() -> Result<()> {
+        let lateral = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                col("orders.o_custkey")
+                    .lt(out_ref_col(DataType::Int64, "customer.c_custkey")),
+            )?
+            .project(vec![col("orders.o_orderkey")])?
+            .build()?;
+        let lateral = LogicalPlan::Subquery(Subquery {
+            subquery: Arc::new(lateral),
+            outer_ref_columns: vec![out_ref_col(DataType::Int64, "customer.c_custkey")],
+        });
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .cross_join(LogicalPlanBuilder::from(lateral).alias("o")?.build()?)?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey, customer.c_name, o.o_orderkey\
+        \n  Inner Join:  Filter: customer.c_custkey IS NOT DISTINCT FROM o.__outer_col_1\
+        \n    TableScan: customer\
+        \n    SubqueryAlias: o\
+        \n      Projection: orders.o_orderkey, __outer_col_1\
+        \n        Filter: orders.o_custkey < __outer_col_1\
+        \n          Cross Join: \
+        \n            TableScan: orders\
+        \n            Distinct:\
+        \n              Projection: customer.c_custkey AS __outer_col_1\
+        \n                TableScan: customer";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test a correlated scalar `count` subquery with a non-equality
+    /// correlation predicate, which must return 0 for customers without orders
+    #[test]
+    fn scalar_subquery_count_non_equal_predicate() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(
+                    col("orders.o_custkey")
+                        .lt(out_ref_col(DataType::Int64, "customer.c_custkey")),
+                )?
+                .aggregate(Vec::<Expr>::new(), vec![count(col("orders.o_orderkey"))])?
+                .project(vec![count(col("orders.o_orderkey"))])?
+                .build()?,
+        );
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .project(vec![
+                col("customer.c_custkey"),
+                scalar_subquery(sq).alias("cnt"),
+            ])?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey, __scalar_sq_1.count(orders.o_orderkey) AS cnt\
+        \n  Left Join:  Filter: customer.c_custkey IS NOT DISTINCT FROM __scalar_sq_1.__outer_col_2\
+        \n    TableScan: customer\
+        \n    SubqueryAlias: __scalar_sq_1\
+        \n      Projection: count(orders.o_orderkey), __outer_col_2\
+        \n        Projection: CASE WHEN __always_true IS NULL THEN Int64(0) ELSE count(orders.o_orderkey) END AS count(orders.o_orderkey), __outer_col_2\
+        \n          Left Join:  Filter: __outer_col_2 IS NOT DISTINCT FROM __outer_col_2_other\
+        \n            Distinct:\
+        \n              Projection: customer.c_custkey AS __outer_col_2\
+        \n                TableScan: customer\
+        \n            Aggregate: groupBy=[[__outer_col_2 AS __outer_col_2_other, Boolean(true) AS __always_true]], aggr=[[count(orders.o_orderkey)]]\
+        \n              Filter: orders.o_custkey < __outer_col_2\
+        \n                Cross Join: \
+        \n                  TableScan: orders\
+        \n                  Distinct:\
+        \n                    Projection: customer.c_custkey AS __outer_col_2\
+        \n                      TableScan: customer";
+        assert_optimized_plan_equal(plan, expected)
+    }
+}
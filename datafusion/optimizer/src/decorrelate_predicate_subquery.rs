@@ -15,31 +15,32 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! [`DecorrelatePredicateSubquery`] converts `IN`/`EXISTS` subquery predicates to `SEMI`/`ANTI` joins
+//! [`DecorrelatePredicateSubquery`] converts `IN`/`EXISTS`/`ANY`/`ALL` subquery predicates to `SEMI`/`ANTI` joins
 use std::collections::BTreeSet;
 use std::ops::Deref;
 use std::sync::Arc;
 
 use crate::decorrelate::PullUpCorrelatedExpr;
+use crate::extract_equijoin_predicate::split_eq_and_noneq_join_predicate;
 use crate::optimizer::ApplyOrder;
 use crate::utils::replace_qualified_name;
 use crate::{OptimizerConfig, OptimizerRule};
 
 use datafusion_common::alias::AliasGenerator;
 use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
-use datafusion_common::{internal_err, plan_err, Column, Result};
-use datafusion_expr::expr::{Exists, InSubquery};
+use datafusion_common::{internal_err, plan_err, Column, DFSchema, Result, ScalarValue};
+use datafusion_expr::expr::{Exists, InSubquery, SetComparison, SetQuantifier};
 use datafusion_expr::expr_rewriter::create_col_from_scalar_expr;
 use datafusion_expr::logical_plan::{JoinType, Subquery};
-use datafusion_expr::utils::{conjunction, split_conjunction_owned};
+use datafusion_expr::utils::{conjunction, row_values, split_conjunction_owned};
 use datafusion_expr::{
-    exists, in_subquery, lit, not, not_exists, not_in_subquery, BinaryExpr, Expr, Filter,
-    LogicalPlan, LogicalPlanBuilder, Operator,
+    binary_expr, exists, in_subquery, lit, not, not_exists, not_in_subquery, when,
+    BinaryExpr, Expr, ExprSchemable, Filter, LogicalPlan, LogicalPlanBuilder, Operator,
 };
 
 use log::debug;
 
-/// Optimizer rule for rewriting predicate(IN/EXISTS) subquery to left semi/anti joins
+/// Optimizer rule for rewriting predicate(IN/EXISTS/ANY/ALL) subquery to left semi/anti joins
 #[derive(Default, Debug)]
 pub struct DecorrelatePredicateSubquery {}
 
@@ -149,24 +150,63 @@ fn rewrite_inner_subqueries(
             subquery: Subquery { subquery, .. },
             negated,
         }) => {
-            let in_predicate = subquery
+            let in_predicate = in_predicate(*expr.clone(), &subquery)?;
+            let joined = if in_subquery_nullable(&expr, &cur_input, &subquery)? {
+                // the rows without an equal value have a NULL result if
+                // some comparison is NULL
+                let undecided_predicate = in_predicate.clone().is_not_false();
+                three_valued_mark_join(
+                    &cur_input,
+                    &subquery,
+                    in_predicate,
+                    undecided_predicate,
+                    true,
+                    alias,
+                )?
+                .map(|(plan, in_expr)| {
+                    (plan, if negated { not(in_expr) } else { in_expr })
+                })
+            } else {
+                mark_join(
+                    &cur_input,
+                    Arc::clone(&subquery),
+                    Some(in_predicate),
+                    negated,
+                    alias,
+                )?
+            };
+            match joined {
+                Some((plan, in_expr)) => {
+                    cur_input = plan;
+                    Ok(Transformed::yes(in_expr))
+                }
+                None if negated => Ok(Transformed::no(not_in_subquery(*expr, subquery))),
+                None => Ok(Transformed::no(in_subquery(*expr, subquery))),
+            }
+        }
+        Expr::SetComparison(comparison) => {
+            let (decisive_predicate, undecided_predicate) = comparison
+                .subquery
+                .subquery
                 .head_output_expr()?
                 .map_or(plan_err!("single expression required."), |output_expr| {
-                    Ok(Expr::eq(*expr.clone(), output_expr))
+                    Ok(set_comparison_mark_predicates(&comparison, output_expr))
                 })?;
-            match mark_join(
+            // ANY is true if some comparison is true and ALL is false if some
+            // comparison is false
+            match three_valued_mark_join(
                 &cur_input,
-                Arc::clone(&subquery),
-                Some(in_predicate),
-                negated,
+                &comparison.subquery.subquery,
+                decisive_predicate,
+                undecided_predicate,
+                comparison.quantifier == SetQuantifier::Any,
                 alias,
             )? {
-                Some((plan, exists_expr)) => {
+                Some((plan, expr)) => {
                     cur_input = plan;
-                    Ok(Transformed::yes(exists_expr))
+                    Ok(Transformed::yes(expr))
                 }
-                None if negated => Ok(Transformed::no(not_in_subquery(*expr, subquery))),
-                None => Ok(Transformed::no(in_subquery(*expr, subquery))),
+                None => Ok(Transformed::no(Expr::SetComparison(comparison))),
             }
         }
         _ => Ok(Transformed::no(e)),
@@ -174,6 +214,47 @@ fn rewrite_inner_subqueries(
     Ok((cur_input, expr_without_subqueries.data))
 }
 
+/// Joins `subquery` into `left` with two mark joins and returns the plan and
+/// the result of comparing the rows of `left` with the rows of the subquery.
+///
+/// The result is `decided_value` if `decisive_predicate` matches some row of
+/// the subquery, otherwise it is NULL if `undecided_predicate` matches some
+/// row, which is the case if some comparison is NULL, and the opposite of
+/// `decided_value` if not.
+fn three_valued_mark_join(
+    left: &LogicalPlan,
+    subquery: &Arc<LogicalPlan>,
+    decisive_predicate: Expr,
+    undecided_predicate: Expr,
+    decided_value: bool,
+    alias: &Arc<AliasGenerator>,
+) -> Result<Option<(LogicalPlan, Expr)>> {
+    let Some((plan, decisive)) = mark_join(
+        left,
+        Arc::clone(subquery),
+        Some(decisive_predicate),
+        false,
+        alias,
+    )?
+    else {
+        return Ok(None);
+    };
+    let Some((plan, undecided)) = mark_join(
+        &plan,
+        Arc::clone(subquery),
+        Some(undecided_predicate),
+        false,
+        alias,
+    )?
+    else {
+        return Ok(None);
+    };
+    let expr = when(decisive, lit(decided_value))
+        .when(undecided, lit(ScalarValue::Boolean(None)))
+        .otherwise(lit(!decided_value))?;
+    Ok(Some((plan, expr)))
+}
+
 enum SubqueryPredicate {
     // The subquery expression is at the top level of the filter and can be fully replaced by a
     // semi/anti join
@@ -196,6 +277,9 @@ fn extract_subquery_info(expr: Expr) -> SubqueryPredicate {
             Expr::Exists(Exists { subquery, negated }) => {
                 SubqueryPredicate::Top(SubqueryInfo::new(subquery, !negated))
             }
+            Expr::SetComparison(comparison) => SubqueryPredicate::Top(
+                SubqueryInfo::new_with_set_comparison(comparison, true),
+            ),
             expr => SubqueryPredicate::Embedded(not(expr)),
         },
         Expr::InSubquery(InSubquery {
@@ -208,13 +292,52 @@ fn extract_subquery_info(expr: Expr) -> SubqueryPredicate {
         Expr::Exists(Exists { subquery, negated }) => {
             SubqueryPredicate::Top(SubqueryInfo::new(subquery, negated))
         }
+        Expr::SetComparison(comparison) => SubqueryPredicate::Top(
+            SubqueryInfo::new_with_set_comparison(comparison, false),
+        ),
         expr => SubqueryPredicate::Embedded(expr),
     }
 }
 
+/// Returns the join filters of the two mark joins evaluating an embedded
+/// `SetComparison` with `output_expr` being the output of its subquery.
+///
+/// The first join marks the rows that have a comparison deciding the result,
+/// a true one for `ANY` and a false one for `ALL`. The second join marks the
+/// rows that have a comparison that is not the opposite, and so the result
+/// is NULL if only the second one matches.
+fn set_comparison_mark_predicates(
+    comparison: &SetComparison,
+    output_expr: Expr,
+) -> (Expr, Expr) {
+    let predicate = binary_expr(*comparison.expr.clone(), comparison.op, output_expr);
+    match comparison.quantifier {
+        SetQuantifier::Any => (predicate.clone(), predicate.is_not_false()),
+        SetQuantifier::All => (predicate.clone().is_false(), predicate.is_not_true()),
+    }
+}
+
+/// Returns true if NULLs can occur in the comparison of an `IN` subquery,
+/// which is the case if `expr`, or one of its values if it is a row, or an
+/// output column of `subquery` is nullable
+fn in_subquery_nullable(
+    expr: &Expr,
+    left: &LogicalPlan,
+    subquery: &LogicalPlan,
+) -> Result<bool> {
+    let fields = subquery.schema().fields();
+    let values = row_values(expr, fields.len()).unwrap_or(std::slice::from_ref(expr));
+    for value in values {
+        if value.nullable(left.schema())? {
+            return Ok(true);
+        }
+    }
+    Ok(fields.iter().any(|field| field.is_nullable()))
+}
+
 fn has_subquery(expr: &Expr) -> bool {
     expr.exists(|e| match e {
-        Expr::InSubquery(_) | Expr::Exists(_) => Ok(true),
+        Expr::InSubquery(_) | Expr::Exists(_) | Expr::SetComparison(_) => Ok(true),
         _ => Ok(false),
     })
     .unwrap()
@@ -250,6 +373,37 @@ fn has_subquery(expr: &Expr) -> bool {
 ///       Projection: t2.id
 ///         TableScan: t2
 /// ```
+///
+/// `ALL` subqueries are converted to left-anti joins on the comparison not
+/// being true, so given a query like:
+/// `select t1.id from t1 where t1.id > all(SELECT t2.id FROM t2)`
+///
+/// The optimized plan will be:
+///
+/// ```text
+/// Projection: t1.id
+///   LeftAnti Join:  Filter: t1.id > __correlated_sq_1.id IS NOT TRUE
+///     TableScan: t1
+///     SubqueryAlias: __correlated_sq_1
+///       Projection: t2.id
+///         TableScan: t2
+/// ```
+///
+/// `NOT IN` with nullable keys removes the rows for which the comparison is
+/// not false. This is a null-aware anti join if a single value is compared
+/// and the subquery is not correlated, so given a query like:
+/// `select t1.id from t1 where t1.id not in (SELECT t2.id FROM t2)`
+///
+/// The optimized plan will be:
+///
+/// ```text
+/// Projection: t1.id
+///   LeftAnti Join (NullAware):  Filter: t1.id = __correlated_sq_1.id
+///     TableScan: t1
+///     SubqueryAlias: __correlated_sq_1
+///       Projection: t2.id
+///         TableScan: t2
+/// ```
 fn build_join_top(
     query_info: &SubqueryInfo,
     left: &LogicalPlan,
@@ -258,24 +412,32 @@ fn build_join_top(
     let where_in_expr_opt = &query_info.where_in_expr;
     let in_predicate_opt = where_in_expr_opt
         .clone()
-        .map(|where_in_expr| {
-            query_info
-                .query
-                .subquery
-                .head_output_expr()?
-                .map_or(plan_err!("single expression required."), |expr| {
-                    Ok(Expr::eq(where_in_expr, expr))
-                })
-        })
-        .map_or(Ok(None), |v| v.map(Some))?;
+        .map(|where_in_expr| query_info.join_predicate(where_in_expr))
+        .transpose()?;
 
-    let join_type = match query_info.negated {
+    // `NOT ALL` is `ANY` with the negated comparison and `ALL` is `NOT ANY`
+    // with the negated comparison, see `SubqueryInfo::join_predicate`
+    let anti = query_info.negated != (query_info.quantifier == SetQuantifier::All);
+    let join_type = match anti {
         true => JoinType::LeftAnti,
         false => JoinType::LeftSemi,
     };
     let subquery = query_info.query.subquery.as_ref();
+    let null_aware = match where_in_expr_opt {
+        Some(expr) if anti && query_info.is_in_subquery() => {
+            in_subquery_nullable(expr, left, subquery)?
+        }
+        _ => false,
+    };
     let subquery_alias = alias.next("__correlated_sq");
-    build_join(left, subquery, in_predicate_opt, join_type, subquery_alias)
+    build_join(
+        left,
+        subquery,
+        in_predicate_opt,
+        join_type,
+        subquery_alias,
+        null_aware,
+    )
 }
 
 /// This is used to handle the case when the subquery is embedded in a more complex boolean
@@ -305,21 +467,43 @@ fn mark_join(
     let exists_col = Expr::Column(Column::new(Some(alias.clone()), "mark"));
     let exists_expr = if negated { !exists_col } else { exists_col };
 
-    Ok(
-        build_join(left, &subquery, in_predicate_opt, JoinType::LeftMark, alias)?
-            .map(|plan| (plan, exists_expr)),
-    )
+    Ok(build_join(
+        left,
+        &subquery,
+        in_predicate_opt,
+        JoinType::LeftMark,
+        alias,
+        false,
+    )?
+    .map(|plan| (plan, exists_expr)))
 }
 
+/// Joins `subquery` into `left` on `in_predicate_opt` and the correlated
+/// filters of the subquery.
+///
+/// If `null_aware` is true, the join is a `NOT IN` anti join with nullable
+/// keys. It is built as a null-aware join on the compared value followed by
+/// the correlation keys, if it compares a single value and is only correlated
+/// by equalities. Otherwise, such as for `(a, b) NOT IN (SELECT ...)` or
+/// subqueries correlated by `t.x < y`, it removes the rows for which the
+/// comparison is not false, which needs a nested loop join.
 fn build_join(
     left: &LogicalPlan,
     subquery: &LogicalPlan,
     in_predicate_opt: Option<Expr>,
     join_type: JoinType,
     alias: String,
+    null_aware: bool,
 ) -> Result<Option<LogicalPlan>> {
+    // a correlation filter that is the same as the comparison is redundant
+    // only if the join matches the rows for which the comparison is true, so
+    // it is kept for anti joins and for the comparisons of `ALL` and the
+    // negated forms, which are wrapped in `IS NOT TRUE` and the like
+    let remove_duplicated_filters = join_type != JoinType::LeftAnti
+        && matches!(in_predicate_opt, Some(Expr::BinaryExpr(_)));
     let mut pull_up = PullUpCorrelatedExpr::new()
         .with_in_predicate_opt(in_predicate_opt.clone())
+        .with_remove_duplicated_filters(remove_duplicated_filters)
         .with_exists_sub_query(in_predicate_opt.is_none());
 
     let new_plan = subquery.clone().rewrite(&mut pull_up).data()?;
@@ -342,39 +526,42 @@ fn build_join(
             replace_qualified_name(filter, &all_correlated_cols, &alias).map(Some)
         })?;
 
+    let in_predicate_opt = match in_predicate_opt {
+        Some(in_predicate) => match alias_in_predicate(in_predicate, &alias)? {
+            Some(in_predicate) => Some(in_predicate),
+            None => return Ok(None),
+        },
+        None => None,
+    };
+
+    let null_aware_on = match &in_predicate_opt {
+        Some(in_predicate) if null_aware => null_aware_join_keys(
+            in_predicate,
+            join_filter_opt.as_ref(),
+            left.schema(),
+            sub_query_alias.schema(),
+        )?,
+        _ => None,
+    };
+    let in_predicate_opt = match in_predicate_opt {
+        Some(in_predicate) if null_aware => Some(in_predicate.is_not_false()),
+        in_predicate_opt => in_predicate_opt,
+    };
     let join_filter = match (join_filter_opt, in_predicate_opt) {
-        (
-            Some(join_filter),
-            Some(Expr::BinaryExpr(BinaryExpr {
-                left,
-                op: Operator::Eq,
-                right,
-            })),
-        ) => {
-            let right_col = create_col_from_scalar_expr(right.deref(), alias)?;
-            let in_predicate = Expr::eq(left.deref().clone(), Expr::Column(right_col));
-            in_predicate.and(join_filter)
-        }
-        (Some(join_filter), _) => join_filter,
-        (
-            _,
-            Some(Expr::BinaryExpr(BinaryExpr {
-                left,
-                op: Operator::Eq,
-                right,
-            })),
-        ) => {
-            let right_col = create_col_from_scalar_expr(right.deref(), alias)?;
-            let in_predicate = Expr::eq(left.deref().clone(), Expr::Column(right_col));
-            in_predicate
-        }
-        (None, None) => lit(true),
-        _ => return Ok(None),
+        _ if null_aware_on.is_some() => None,
+        (Some(join_filter), Some(in_predicate)) => Some(in_predicate.and(join_filter)),
+        (Some(join_filter), None) => Some(join_filter),
+        (None, Some(in_predicate)) => Some(in_predicate),
+        (None, None) => Some(lit(true)),
     };
     // join our sub query into the main plan
-    let new_plan = LogicalPlanBuilder::from(left.clone())
-        .join_on(sub_query_alias, join_type, Some(join_filter))?
+    let mut new_plan = LogicalPlanBuilder::from(left.clone())
+        .join_on(sub_query_alias, join_type, join_filter)?
         .build()?;
+    if let (LogicalPlan::Join(join), Some(on)) = (&mut new_plan, null_aware_on) {
+        join.on = on;
+        join.null_aware = true;
+    }
     debug!(
         "predicate subquery optimized:\n{}",
         new_plan.display_indent()
@@ -382,10 +569,99 @@ fn build_join(
     Ok(Some(new_plan))
 }
 
+/// Returns the keys of a null-aware join, which are the values compared by
+/// `in_predicate` followed by the correlation keys of `join_filter`, if both
+/// are equalities of expressions of either side
+fn null_aware_join_keys(
+    in_predicate: &Expr,
+    join_filter: Option<&Expr>,
+    left_schema: &DFSchema,
+    right_schema: &DFSchema,
+) -> Result<Option<Vec<(Expr, Expr)>>> {
+    let (mut on, remaining) = split_eq_and_noneq_join_predicate(
+        in_predicate.clone(),
+        Operator::Eq,
+        left_schema,
+        right_schema,
+    )?;
+    if on.len() != 1 || remaining.is_some() {
+        return Ok(None);
+    }
+    if let Some(join_filter) = join_filter {
+        let (correlation_keys, remaining) = split_eq_and_noneq_join_predicate(
+            join_filter.clone(),
+            Operator::Eq,
+            left_schema,
+            right_schema,
+        )?;
+        if remaining.is_some() {
+            return Ok(None);
+        }
+        on.extend(correlation_keys);
+    }
+    Ok(Some(on))
+}
+
+/// Returns the predicate of the join between `expr` and the output of an `IN`
+/// subquery.
+///
+/// The values of a row such as `(a, b)` are compared with the columns of the
+/// subquery one by one, so `(a, b) IN (SELECT x, y ...)` joins on
+/// `a = x AND b = y`.
+fn in_predicate(expr: Expr, subquery: &LogicalPlan) -> Result<Expr> {
+    let schema = subquery.schema();
+    if let Some(values) = row_values(&expr, schema.fields().len()) {
+        let equalities = values
+            .iter()
+            .zip(schema.columns())
+            .map(|(value, column)| value.clone().eq(Expr::Column(column)));
+        return Ok(conjunction(equalities).unwrap_or_else(|| lit(true)));
+    }
+    subquery
+        .head_output_expr()?
+        .map_or(plan_err!("single expression required."), |output_expr| {
+            Ok(Expr::eq(expr, output_expr))
+        })
+}
+
+/// Replaces the subquery output on the right of the comparison of an `IN` or
+/// `ANY`/`ALL` join predicate with the column of the aliased subquery
+fn alias_in_predicate(in_predicate: Expr, alias: &str) -> Result<Option<Expr>> {
+    Ok(match in_predicate {
+        // the comparisons of the values of a row in a multi-column `IN`
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        }) => match (
+            alias_in_predicate(*left, alias)?,
+            alias_in_predicate(*right, alias)?,
+        ) {
+            (Some(left), Some(right)) => Some(left.and(right)),
+            _ => None,
+        },
+        Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+            let right_col =
+                create_col_from_scalar_expr(right.deref(), alias.to_string())?;
+            Some(binary_expr(*left, op, Expr::Column(right_col)))
+        }
+        Expr::IsNotTrue(expr) => alias_in_predicate(*expr, alias)?.map(Expr::is_not_true),
+        Expr::IsFalse(expr) => alias_in_predicate(*expr, alias)?.map(Expr::is_false),
+        Expr::IsNotFalse(expr) => {
+            alias_in_predicate(*expr, alias)?.map(Expr::is_not_false)
+        }
+        _ => None,
+    })
+}
+
 #[derive(Debug)]
 struct SubqueryInfo {
     query: Subquery,
     where_in_expr: Option<Expr>,
+    /// The comparison operator of `ANY`/`ALL` subqueries, `IN` uses `=`
+    op: Operator,
+    /// `IN` and `EXISTS` subqueries are `ANY` subqueries
+    quantifier: SetQuantifier,
     negated: bool,
 }
 
@@ -394,6 +670,8 @@ impl SubqueryInfo {
         Self {
             query,
             where_in_expr: None,
+            op: Operator::Eq,
+            quantifier: SetQuantifier::Any,
             negated,
         }
     }
@@ -402,16 +680,70 @@ impl SubqueryInfo {
         Self {
             query,
             where_in_expr: Some(expr),
+            op: Operator::Eq,
+            quantifier: SetQuantifier::Any,
             negated,
         }
     }
 
+    pub fn new_with_set_comparison(comparison: SetComparison, negated: bool) -> Self {
+        Self {
+            query: comparison.subquery,
+            where_in_expr: Some(*comparison.expr),
+            op: comparison.op,
+            quantifier: comparison.quantifier,
+            negated,
+        }
+    }
+
+    /// Returns the predicate of the join between `expr` and the subquery
+    /// output:
+    ///
+    /// * `ANY` matches the rows for which the comparison is true
+    /// * `NOT ANY` matches the rows for which the comparison is not false,
+    ///   which are removed by an anti join
+    /// * `ALL` matches the rows for which the comparison is not true, which
+    ///   are removed by an anti join
+    /// * `NOT ALL` matches the rows for which the comparison is false
+    fn join_predicate(&self, expr: Expr) -> Result<Expr> {
+        let subquery = self.query.subquery.as_ref();
+        if self.is_in_subquery() {
+            return in_predicate(expr, subquery);
+        }
+        let Some(output_expr) = subquery.head_output_expr()? else {
+            return plan_err!("single expression required.");
+        };
+        let predicate = binary_expr(expr, self.op, output_expr);
+        Ok(match (self.quantifier, self.negated) {
+            (SetQuantifier::Any, false) => predicate,
+            (SetQuantifier::Any, true) => predicate.is_not_false(),
+            (SetQuantifier::All, false) => predicate.is_not_true(),
+            (SetQuantifier::All, true) => predicate.is_false(),
+        })
+    }
+
+    fn is_in_subquery(&self) -> bool {
+        self.op == Operator::Eq && self.quantifier == SetQuantifier::Any
+    }
+
     pub fn expr(self) -> Expr {
         match self.where_in_expr {
-            Some(expr) => match self.negated {
+            Some(expr) if self.is_in_subquery() => match self.negated {
                 true => not_in_subquery(expr, self.query.subquery),
                 false => in_subquery(expr, self.query.subquery),
             },
+            Some(expr) => {
+                let comparison = Expr::SetComparison(SetComparison::new(
+                    Box::new(expr),
+                    self.query,
+                    self.op,
+                    self.quantifier,
+                ));
+                match self.negated {
+                    true => not(comparison),
+                    false => comparison,
+                }
+            }
             None => match self.negated {
                 true => not_exists(self.query.subquery),
                 false => exists(self.query.subquery),
@@ -429,7 +761,10 @@ mod tests {
 
     use arrow::datatypes::{DataType, Field, Schema};
     use datafusion_expr::builder::table_source;
-    use datafusion_expr::{and, binary_expr, col, lit, not, out_ref_col, table_scan};
+    use datafusion_expr::{
+        all_subquery, and, any_subquery, binary_expr, col, lit, not, out_ref_col,
+        table_scan,
+    };
 
     fn assert_optimized_plan_equal(plan: LogicalPlan, expected: &str) -> Result<()> {
         assert_optimized_plan_eq_display_indent(
@@ -932,7 +1267,7 @@ mod tests {
 
         let expected = "Invalid (non-executable) plan after Analyzer\
         \ncaused by\
-        \nError during planning: InSubquery should only return one column";
+        \nError during planning: InSubquery should only return one column, but found 2: orders.o_custkey, orders.o_orderkey";
         assert_analyzer_check_err(vec![], plan, expected);
 
         Ok(())
@@ -1102,6 +1437,137 @@ mod tests {
         Ok(())
     }
 
+    fn nullable_table_scan() -> Result<LogicalPlan> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::UInt32, true),
+            Field::new("b", DataType::UInt32, true),
+        ]);
+        table_scan(Some("t"), &schema, None)?.build()
+    }
+
+    /// Test for NOT IN subquery with nullable keys
+    #[test]
+    fn not_in_subquery_nullable() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let subquery = LogicalPlanBuilder::from(nullable_table_scan()?)
+            .project(vec![col("t.a")])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(not_in_subquery(col("c"), Arc::new(subquery)))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  LeftAnti Join (NullAware): test.c = __correlated_sq_1.a [a:UInt32, b:UInt32, c:UInt32]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n    SubqueryAlias: __correlated_sq_1 [a:UInt32;N]\
+        \n      Projection: t.a [a:UInt32;N]\
+        \n        TableScan: t [a:UInt32;N, b:UInt32;N]";
+
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test for correlated NOT IN subquery with nullable keys
+    #[test]
+    fn not_in_subquery_nullable_correlated() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let subquery = LogicalPlanBuilder::from(nullable_table_scan()?)
+            .filter(out_ref_col(DataType::UInt32, "test.b").eq(col("t.b")))?
+            .project(vec![col("t.a")])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(not_in_subquery(col("c"), Arc::new(subquery)))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  LeftAnti Join (NullAware): test.c = __correlated_sq_1.a, test.b = __correlated_sq_1.b [a:UInt32, b:UInt32, c:UInt32]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n    SubqueryAlias: __correlated_sq_1 [a:UInt32;N, b:UInt32;N]\
+        \n      Projection: t.a, t.b [a:UInt32;N, b:UInt32;N]\
+        \n        TableScan: t [a:UInt32;N, b:UInt32;N]";
+
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test for NOT IN subquery with nullable keys correlated by a
+    /// non-equality, which is not null-aware
+    #[test]
+    fn not_in_subquery_nullable_non_equi_correlated() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let subquery = LogicalPlanBuilder::from(nullable_table_scan()?)
+            .filter(out_ref_col(DataType::UInt32, "test.b").lt(col("t.b")))?
+            .project(vec![col("t.a")])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(not_in_subquery(col("c"), Arc::new(subquery)))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  LeftAnti Join:  Filter: test.c = __correlated_sq_1.a IS NOT FALSE AND test.b < __correlated_sq_1.b [a:UInt32, b:UInt32, c:UInt32]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n    SubqueryAlias: __correlated_sq_1 [a:UInt32;N, b:UInt32;N]\
+        \n      Projection: t.a, t.b [a:UInt32;N, b:UInt32;N]\
+        \n        TableScan: t [a:UInt32;N, b:UInt32;N]";
+
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test for NOT IN subquery with nullable keys correlated by the same
+    /// equality, which is kept as a key of the null-aware join
+    #[test]
+    fn not_in_subquery_nullable_correlated_by_comparison() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let subquery = LogicalPlanBuilder::from(nullable_table_scan()?)
+            .filter(col("t.a").eq(out_ref_col(DataType::UInt32, "test.c")))?
+            .project(vec![col("t.a")])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(not_in_subquery(col("c"), Arc::new(subquery)))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  LeftAnti Join (NullAware): test.c = __correlated_sq_1.a, test.c = __correlated_sq_1.a [a:UInt32, b:UInt32, c:UInt32]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n    SubqueryAlias: __correlated_sq_1 [a:UInt32;N]\
+        \n      Projection: t.a [a:UInt32;N]\
+        \n        TableScan: t [a:UInt32;N, b:UInt32;N]";
+
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test for NOT IN subquery with nullable keys in a disjunction, which is
+    /// NULL if no value is equal but some comparison is NULL
+    #[test]
+    fn not_in_subquery_nullable_disjunction() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let subquery = LogicalPlanBuilder::from(nullable_table_scan()?)
+            .project(vec![col("t.a")])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(
+                not_in_subquery(col("c"), Arc::new(subquery)).or(col("a").eq(lit(1u32))),
+            )?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  Filter: NOT CASE WHEN __correlated_sq_1.mark THEN Boolean(true) WHEN __correlated_sq_2.mark THEN Boolean(NULL) ELSE Boolean(false) END OR test.a = UInt32(1) [a:UInt32, b:UInt32, c:UInt32, mark:Boolean, mark:Boolean]\
+        \n    LeftMark Join:  Filter: test.c = __correlated_sq_2.a IS NOT FALSE [a:UInt32, b:UInt32, c:UInt32, mark:Boolean, mark:Boolean]\
+        \n      LeftMark Join:  Filter: test.c = __correlated_sq_1.a [a:UInt32, b:UInt32, c:UInt32, mark:Boolean]\
+        \n        TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n        SubqueryAlias: __correlated_sq_1 [a:UInt32;N]\
+        \n          Projection: t.a [a:UInt32;N]\
+        \n            TableScan: t [a:UInt32;N, b:UInt32;N]\
+        \n      SubqueryAlias: __correlated_sq_2 [a:UInt32;N]\
+        \n        Projection: t.a [a:UInt32;N]\
+        \n          TableScan: t [a:UInt32;N, b:UInt32;N]";
+
+        assert_optimized_plan_equal(plan, expected)
+    }
+
     #[test]
     fn in_subquery_both_side_expr() -> Result<()> {
         let table_scan = test_table_scan()?;
@@ -1955,4 +2421,128 @@ mod tests {
 
         assert_optimized_plan_equal(plan, expected)
     }
+
+    #[test]
+    fn any_subquery_simple() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(any_subquery(
+                col("c"),
+                Operator::Gt,
+                test_subquery_with_name("sq")?,
+            ))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  LeftSemi Join:  Filter: test.c > __correlated_sq_1.c [a:UInt32, b:UInt32, c:UInt32]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n    SubqueryAlias: __correlated_sq_1 [c:UInt32]\
+        \n      Projection: sq.c [c:UInt32]\
+        \n        TableScan: sq [a:UInt32, b:UInt32, c:UInt32]";
+
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn all_subquery_simple() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(all_subquery(
+                col("c"),
+                Operator::Gt,
+                test_subquery_with_name("sq")?,
+            ))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  LeftAnti Join:  Filter: test.c > __correlated_sq_1.c IS NOT TRUE [a:UInt32, b:UInt32, c:UInt32]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n    SubqueryAlias: __correlated_sq_1 [c:UInt32]\
+        \n      Projection: sq.c [c:UInt32]\
+        \n        TableScan: sq [a:UInt32, b:UInt32, c:UInt32]";
+
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn not_all_subquery_correlated() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let subquery_scan = test_table_scan_with_name("sq")?;
+        let subquery = LogicalPlanBuilder::from(subquery_scan)
+            .filter(out_ref_col(DataType::UInt32, "test.a").eq(col("sq.a")))?
+            .project(vec![col("c")])?
+            .build()?;
+
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(not(all_subquery(
+                col("c"),
+                Operator::Lt,
+                Arc::new(subquery),
+            )))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  LeftSemi Join:  Filter: test.c < __correlated_sq_1.c IS FALSE AND test.a = __correlated_sq_1.a [a:UInt32, b:UInt32, c:UInt32]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n    SubqueryAlias: __correlated_sq_1 [c:UInt32, a:UInt32]\
+        \n      Projection: sq.c, sq.a [c:UInt32, a:UInt32]\
+        \n        TableScan: sq [a:UInt32, b:UInt32, c:UInt32]";
+
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    /// Test for ALL subquery correlated by the same comparison, which must
+    /// be kept in the filter of the anti join
+    #[test]
+    fn all_subquery_correlated_by_comparison() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let subquery_scan = test_table_scan_with_name("sq")?;
+        let subquery = LogicalPlanBuilder::from(subquery_scan)
+            .filter(col("sq.c").lt(out_ref_col(DataType::UInt32, "test.c")))?
+            .project(vec![col("c")])?
+            .build()?;
+
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(all_subquery(col("c"), Operator::Gt, Arc::new(subquery)))?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  LeftAnti Join:  Filter: test.c > __correlated_sq_1.c IS NOT TRUE AND __correlated_sq_1.c < test.c [a:UInt32, b:UInt32, c:UInt32]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n    SubqueryAlias: __correlated_sq_1 [c:UInt32]\
+        \n      Projection: sq.c [c:UInt32]\
+        \n        TableScan: sq [a:UInt32, b:UInt32, c:UInt32]";
+
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn all_subquery_disjunction() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(
+                all_subquery(col("c"), Operator::Gt, test_subquery_with_name("sq")?)
+                    .or(col("a").eq(lit(1u32))),
+            )?
+            .project(vec![col("test.b")])?
+            .build()?;
+
+        let expected = "Projection: test.b [b:UInt32]\
+        \n  Filter: CASE WHEN __correlated_sq_1.mark THEN Boolean(false) WHEN __correlated_sq_2.mark THEN Boolean(NULL) ELSE Boolean(true) END OR test.a = UInt32(1) [a:UInt32, b:UInt32, c:UInt32, mark:Boolean, mark:Boolean]\
+        \n    LeftMark Join:  Filter: test.c > __correlated_sq_2.c IS NOT TRUE [a:UInt32, b:UInt32, c:UInt32, mark:Boolean, mark:Boolean]\
+        \n      LeftMark Join:  Filter: test.c > __correlated_sq_1.c IS FALSE [a:UInt32, b:UInt32, c:UInt32, mark:Boolean]\
+        \n        TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n        SubqueryAlias: __correlated_sq_1 [c:UInt32]\
+        \n          Projection: sq.c [c:UInt32]\
+        \n            TableScan: sq [a:UInt32, b:UInt32, c:UInt32]\
+        \n      SubqueryAlias: __correlated_sq_2 [c:UInt32]\
+        \n        Projection: sq.c [c:UInt32]\
+        \n          TableScan: sq [a:UInt32, b:UInt32, c:UInt32]";
+
+        assert_optimized_plan_equal(plan, expected)
+    }
 }
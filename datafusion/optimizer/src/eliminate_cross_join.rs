@@ -95,10 +95,7 @@ impl OptimizerRule for EliminateCrossJoin {
             // avoid unwrapping the input
             let rewritable = matches!(
                 filter.input.as_ref(),
-                LogicalPlan::Join(Join {
-                    join_type: JoinType::Inner,
-                    ..
-                })
+                LogicalPlan::Join(join) if is_flattenable_join(join)
             );
 
             if !rewritable {
@@ -122,13 +119,7 @@ impl OptimizerRule for EliminateCrossJoin {
 
             extract_possible_join_keys(&predicate, &mut possible_join_keys);
             Some(predicate)
-        } else if matches!(
-            plan,
-            LogicalPlan::Join(Join {
-                join_type: JoinType::Inner,
-                ..
-            })
-        ) {
+        } else if matches!(&plan, LogicalPlan::Join(join) if is_flattenable_join(join)) {
             if !can_flatten_join_inputs(&plan) {
                 return Ok(Transformed::no(plan));
             }
@@ -223,7 +214,7 @@ fn flatten_join_inputs(
     all_filters: &mut Vec<Expr>,
 ) -> Result<()> {
     match plan {
-        LogicalPlan::Join(join) if join.join_type == JoinType::Inner => {
+        LogicalPlan::Join(join) if is_flattenable_join(&join) => {
             if let Some(filter) = join.filter {
                 all_filters.push(filter);
             }
@@ -255,17 +246,13 @@ fn flatten_join_inputs(
 fn can_flatten_join_inputs(plan: &LogicalPlan) -> bool {
     // can only flatten inner / cross joins
     match plan {
-        LogicalPlan::Join(join) if join.join_type == JoinType::Inner => {}
+        LogicalPlan::Join(join) if is_flattenable_join(join) => {}
         _ => return false,
     };
 
     for child in plan.inputs() {
-        if let LogicalPlan::Join(Join {
-            join_type: JoinType::Inner,
-            ..
-        }) = child
-        {
-            if !can_flatten_join_inputs(child) {
+        if let LogicalPlan::Join(join) = child {
+            if is_flattenable_join(join) && !can_flatten_join_inputs(child) {
                 return false;
             }
         }
@@ -273,6 +260,12 @@ fn can_flatten_join_inputs(plan: &LogicalPlan) -> bool {
     true
 }
 
+/// Returns true for inner and cross joins whose keys are compared with `=`,
+/// the keys of joins with `null_equals_null` also match NULLs
+fn is_flattenable_join(join: &Join) -> bool {
+    join.join_type == JoinType::Inner && !join.null_equals_null
+}
+
 /// Finds the next to join with the left input plan,
 ///
 /// Finds the next `right` from `rights` that can be joined with `left_input`
@@ -329,6 +322,7 @@ fn find_inner_join(
                 filter: None,
                 schema: join_schema,
                 null_equals_null: false,
+                null_aware: false,
             }));
         }
     }
@@ -351,6 +345,7 @@ fn find_inner_join(
         join_type: JoinType::Inner,
         join_constraint: JoinConstraint::On,
         null_equals_null: false,
+        null_aware: false,
     }))
 }
 
@@ -485,6 +480,51 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn eliminate_cross_not_flattening_null_equals_null_join() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+        let t3 = test_table_scan_with_name("t3")?;
+
+        // the keys of the first join match NULLs, so they must not be merged
+        // with the keys compared with `=`
+        let plan = LogicalPlanBuilder::from(t1)
+            .join_detailed(
+                t2,
+                JoinType::Inner,
+                (vec!["t1.a"], vec!["t2.a"]),
+                None,
+                true,
+            )?
+            .cross_join(t3)?
+            .filter(col("t3.a").eq(col("t1.a")))?
+            .build()?;
+
+        let expected = vec![
+            "Inner Join: t1.a = t3.a [a:UInt32, b:UInt32, c:UInt32, a:UInt32, b:UInt32, c:UInt32, a:UInt32, b:UInt32, c:UInt32]",
+            "  Inner Join: t1.a = t2.a [a:UInt32, b:UInt32, c:UInt32, a:UInt32, b:UInt32, c:UInt32]",
+            "    TableScan: t1 [a:UInt32, b:UInt32, c:UInt32]",
+            "    TableScan: t2 [a:UInt32, b:UInt32, c:UInt32]",
+            "  TableScan: t3 [a:UInt32, b:UInt32, c:UInt32]",
+        ];
+
+        assert_optimized_plan_eq(plan.clone(), expected);
+
+        let optimized = EliminateCrossJoin::new()
+            .rewrite(plan, &OptimizerContext::new())?
+            .data;
+        let LogicalPlan::Join(join) = &optimized else {
+            panic!("expected a join, got {optimized}");
+        };
+        let LogicalPlan::Join(left) = join.left.as_ref() else {
+            panic!("expected a join, got {}", join.left);
+        };
+        assert!(!join.null_equals_null);
+        assert!(left.null_equals_null);
+
+        Ok(())
+    }
+
     #[test]
     fn eliminate_cross_with_simple_or() -> Result<()> {
         let t1 = test_table_scan_with_name("t1")?;
@@ -76,11 +76,41 @@ impl OptimizerRule for ExtractEquijoinPredicate {
                 join_constraint,
                 schema,
                 null_equals_null,
+                null_aware,
             }) => {
                 let left_schema = left.schema();
                 let right_schema = right.schema();
-                let (equijoin_predicates, non_equijoin_expr) =
-                    split_eq_and_noneq_join_predicate(expr, left_schema, right_schema)?;
+                // Keys of joins with `null_equals_null` match like
+                // `IS NOT DISTINCT FROM`, other keys like `=`
+                let key_op = if null_equals_null {
+                    Operator::IsNotDistinctFrom
+                } else {
+                    Operator::Eq
+                };
+                let (mut equijoin_predicates, mut non_equijoin_expr) =
+                    split_eq_and_noneq_join_predicate(
+                        expr,
+                        key_op,
+                        left_schema,
+                        right_schema,
+                    )?;
+
+                // A join without keys can match NULLs instead, so that joins of
+                // decorrelated subqueries to their domain, which compare the
+                // domain columns with `IS NOT DISTINCT FROM`, are hash joins
+                let mut null_equals_null = null_equals_null;
+                if on.is_empty() && equijoin_predicates.is_empty() {
+                    if let Some(expr) = non_equijoin_expr.take() {
+                        (equijoin_predicates, non_equijoin_expr) =
+                            split_eq_and_noneq_join_predicate(
+                                expr,
+                                Operator::IsNotDistinctFrom,
+                                left_schema,
+                                right_schema,
+                            )?;
+                        null_equals_null |= !equijoin_predicates.is_empty();
+                    }
+                }
 
                 if !equijoin_predicates.is_empty() {
                     on.extend(equijoin_predicates);
@@ -93,6 +123,7 @@ impl OptimizerRule for ExtractEquijoinPredicate {
                         join_constraint,
                         schema,
                         null_equals_null,
+                        null_aware,
                     })))
                 } else {
                     Ok(Transformed::no(LogicalPlan::Join(Join {
@@ -104,6 +135,7 @@ impl OptimizerRule for ExtractEquijoinPredicate {
                         join_constraint,
                         schema,
                         null_equals_null,
+                        null_aware,
                     })))
                 }
             }
@@ -112,8 +144,11 @@ impl OptimizerRule for ExtractEquijoinPredicate {
     }
 }
 
-fn split_eq_and_noneq_join_predicate(
+/// Splits the conjunction `filter` into the join keys compared with `op` and
+/// the remaining filter
+pub(crate) fn split_eq_and_noneq_join_predicate(
     filter: Expr,
+    op: Operator,
     left_schema: &DFSchema,
     right_schema: &DFSchema,
 ) -> Result<(Vec<EquijoinPredicate>, Option<Expr>)> {
@@ -125,9 +160,9 @@ fn split_eq_and_noneq_join_predicate(
         match expr {
             Expr::BinaryExpr(BinaryExpr {
                 ref left,
-                op: Operator::Eq,
+                op: expr_op,
                 ref right,
-            }) => {
+            }) if expr_op == op => {
                 let join_key_pair =
                     find_valid_equijoin_key_pair(left, right, left_schema, right_schema)?;
 
@@ -156,12 +191,17 @@ fn split_eq_and_noneq_join_predicate(
 mod tests {
     use super::*;
     use crate::test::*;
+    use crate::OptimizerContext;
     use arrow::datatypes::DataType;
     use datafusion_expr::{
-        col, lit, logical_plan::builder::LogicalPlanBuilder, JoinType,
+        binary_expr, col, lit, logical_plan::builder::LogicalPlanBuilder, JoinType,
     };
     use std::sync::Arc;
 
+    fn is_not_distinct_from(left: Expr, right: Expr) -> Expr {
+        binary_expr(left, Operator::IsNotDistinctFrom, right)
+    }
+
     fn assert_plan_eq(plan: LogicalPlan, expected: &str) -> Result<()> {
         assert_optimized_plan_eq_display_indent(
             Arc::new(ExtractEquijoinPredicate {}),
@@ -206,6 +246,59 @@ mod tests {
         assert_plan_eq(plan, expected)
     }
 
+    #[test]
+    fn join_with_only_is_not_distinct_from_predicate() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        let plan = LogicalPlanBuilder::from(t1)
+            .join_on(
+                t2,
+                JoinType::Left,
+                Some(
+                    is_not_distinct_from(col("t1.a"), col("t2.a"))
+                        .and(col("t1.b").lt(col("t2.b"))),
+                ),
+            )?
+            .build()?;
+        let expected = "Left Join: t1.a = t2.a Filter: t1.b < t2.b [a:UInt32, b:UInt32, c:UInt32, a:UInt32;N, b:UInt32;N, c:UInt32;N]\
+            \n  TableScan: t1 [a:UInt32, b:UInt32, c:UInt32]\
+            \n  TableScan: t2 [a:UInt32, b:UInt32, c:UInt32]";
+
+        let optimized = ExtractEquijoinPredicate::new()
+            .rewrite(plan.clone(), &OptimizerContext::new())?
+            .data;
+        let LogicalPlan::Join(join) = optimized else {
+            panic!("expected a join, got {optimized}");
+        };
+        assert!(join.null_equals_null);
+        assert_plan_eq(plan, expected)
+    }
+
+    #[test]
+    fn join_with_equi_and_is_not_distinct_from_predicate() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        // keys compared with `=` don't match NULLs, so the other predicate
+        // stays in the filter
+        let plan = LogicalPlanBuilder::from(t1)
+            .join_on(
+                t2,
+                JoinType::Left,
+                Some(
+                    is_not_distinct_from(col("t1.a"), col("t2.a"))
+                        .and(col("t1.b").eq(col("t2.b"))),
+                ),
+            )?
+            .build()?;
+        let expected = "Left Join: t1.b = t2.b Filter: t1.a IS NOT DISTINCT FROM t2.a [a:UInt32, b:UInt32, c:UInt32, a:UInt32;N, b:UInt32;N, c:UInt32;N]\
+            \n  TableScan: t1 [a:UInt32, b:UInt32, c:UInt32]\
+            \n  TableScan: t2 [a:UInt32, b:UInt32, c:UInt32]";
+
+        assert_plan_eq(plan, expected)
+    }
+
     #[test]
     fn join_with_only_none_equi_predicate() -> Result<()> {
         let t1 = test_table_scan_with_name("t1")?;
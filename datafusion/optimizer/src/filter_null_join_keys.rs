@@ -50,8 +50,10 @@ impl OptimizerRule for FilterNullJoinKeys {
             return Ok(Transformed::no(plan));
         }
         match plan {
+            // NULL keys decide the result of a null-aware join, so they must
+            // not be filtered out
             LogicalPlan::Join(mut join)
-                if !join.on.is_empty() && !join.null_equals_null =>
+                if !join.on.is_empty() && !join.null_equals_null && !join.null_aware =>
             {
                 let (left_preserved, right_preserved) =
                     on_lr_is_preserved(join.join_type);
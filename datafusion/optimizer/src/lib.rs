@@ -34,6 +34,7 @@
 pub mod analyzer;
 pub mod common_subexpr_eliminate;
 pub mod decorrelate;
+pub mod decorrelate_dependent_join;
 pub mod decorrelate_predicate_subquery;
 pub mod eliminate_cross_join;
 pub mod eliminate_duplicated_expr;
@@ -46,6 +47,8 @@ pub mod eliminate_one_union;
 pub mod eliminate_outer_join;
 pub mod extract_equijoin_predicate;
 pub mod filter_null_join_keys;
+pub mod materialized_view_rewrite;
+pub mod merge_json_access;
 pub mod optimize_projections;
 pub mod optimizer;
 pub mod propagate_empty_relation;
@@ -0,0 +1,861 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`MaterializedViewRewrite`] answers queries from materialized views
+
+use std::sync::Arc;
+
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_common::{plan_err, Column, DFSchema, Result, SchemaExt, TableReference};
+use datafusion_expr::expr::AggregateFunction;
+use datafusion_expr::logical_plan::{
+    Aggregate, DmlStatement, Filter, Join, JoinType, LogicalPlan, Projection,
+    SubqueryAlias, TableScan, WriteOp,
+};
+use datafusion_expr::utils::{conjunction, split_conjunction};
+use datafusion_expr::{BinaryExpr, Expr, ExprSchemable, LogicalPlanBuilder, TableSource};
+
+/// A materialized view that [`MaterializedViewRewrite`] may answer queries
+/// from.
+#[derive(Debug, Clone)]
+pub struct MaterializedViewCandidate {
+    /// Name the view is registered under
+    name: TableReference,
+    /// Analyzed plan computing the contents of the view
+    plan: LogicalPlan,
+    /// Scan of the stored contents of the view
+    scan: LogicalPlan,
+}
+
+impl MaterializedViewCandidate {
+    /// Create a candidate for the view `name`, whose contents are the result
+    /// of the analyzed `plan` and are read from `source`
+    pub fn try_new(
+        name: impl Into<TableReference>,
+        plan: LogicalPlan,
+        source: Arc<dyn TableSource>,
+    ) -> Result<Self> {
+        let name = name.into();
+        let scan = LogicalPlanBuilder::scan(name.clone(), source, None)?.build()?;
+        let plan_schema = plan.schema().as_arrow();
+        if !scan
+            .schema()
+            .as_arrow()
+            .logically_equivalent_names_and_types(plan_schema)
+        {
+            return plan_err!(
+                "Materialized view '{name}' must have the schema of its query. \
+                Expected: {plan_schema}, got: {}",
+                scan.schema().as_arrow()
+            );
+        }
+
+        Ok(Self { name, plan, scan })
+    }
+
+    /// Get name ref
+    pub fn name(&self) -> &TableReference {
+        &self.name
+    }
+
+    /// Get plan ref
+    pub fn plan(&self) -> &LogicalPlan {
+        &self.plan
+    }
+}
+
+/// Optimizer rule that rewrites a query, or part of it, to read from a
+/// materialized view returned by [`OptimizerConfig::materialized_views`].
+///
+/// Plans made of table scans, filters, inner joins and at most one aggregate
+/// are matched against the definitions of the views. A plan can be answered
+/// from a view when both scan the same tables, and each predicate of the
+/// view is also a predicate of the plan. The remaining predicates of the plan
+/// become a filter on the view, and aggregates of a view with finer groups
+/// than the plan are rolled up. For example, given
+///
+/// ```sql
+/// CREATE MATERIALIZED VIEW v AS
+/// SELECT region, city, sum(amount) AS total FROM sales GROUP BY region, city
+/// ```
+///
+/// the query
+///
+/// ```sql
+/// SELECT region, sum(amount) FROM sales WHERE region <> 'north' GROUP BY region
+/// ```
+///
+/// is rewritten to
+///
+/// ```sql
+/// SELECT region, sum(total) FROM v WHERE region <> 'north' GROUP BY region
+/// ```
+///
+/// Views are only used as of their last refresh, so the result of a
+/// rewritten query does not include changes made to the tables since then.
+#[derive(Default, Debug)]
+pub struct MaterializedViewRewrite {}
+
+impl MaterializedViewRewrite {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for MaterializedViewRewrite {
+    fn name(&self) -> &str {
+        "materialized_view_rewrite"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let views = config.materialized_views();
+        // these statements must keep reading the table they modify
+        if let LogicalPlan::Dml(DmlStatement {
+            op: WriteOp::Delete | WriteOp::Update | WriteOp::Merge,
+            ..
+        }) = plan
+        {
+            return Ok(Transformed::new(plan, false, TreeNodeRecursion::Jump));
+        }
+        if views.is_empty()
+            || !config.options().optimizer.enable_materialized_view_rewrite
+            || !matches!(
+                plan,
+                LogicalPlan::Projection(_)
+                    | LogicalPlan::Filter(_)
+                    | LogicalPlan::Join(_)
+                    | LogicalPlan::Aggregate(_)
+            )
+        {
+            return Ok(Transformed::no(plan));
+        }
+
+        let Some(query) = Normalized::try_new(&plan, config)? else {
+            return Ok(Transformed::no(plan));
+        };
+        for view in views {
+            let Some(definition) = Normalized::try_new(&view.plan, config)? else {
+                continue;
+            };
+            // a view that only projects columns of a table is a copy of it
+            if definition.tables.len() == 1
+                && definition.predicates.is_empty()
+                && definition.aggregate.is_none()
+            {
+                continue;
+            }
+            if let Some(rewritten) =
+                rewrite_from_view(&query, plan.schema(), &definition, view, config)?
+            {
+                // the children of the plan are no longer part of the query
+                return Ok(Transformed::new(rewritten, true, TreeNodeRecursion::Jump));
+            }
+        }
+        Ok(Transformed::no(plan))
+    }
+}
+
+/// A plan made of table scans, filters, inner joins and at most one
+/// aggregate, with all expressions rewritten over the columns of the scanned
+/// tables
+#[derive(Debug)]
+struct Normalized {
+    /// Fully qualified names of the scanned tables
+    tables: Vec<TableReference>,
+    /// Conjunction of predicates every row of the plan satisfies
+    predicates: Vec<Expr>,
+    /// Grouping and aggregate expressions of the aggregate, if any
+    aggregate: Option<(Vec<Expr>, Vec<Expr>)>,
+    /// Expression computing each output column of the plan
+    outputs: Vec<Expr>,
+}
+
+impl Normalized {
+    /// Normalizes `plan`, or returns `None` if it contains other nodes
+    fn try_new(plan: &LogicalPlan, config: &dyn OptimizerConfig) -> Result<Option<Self>> {
+        match plan {
+            LogicalPlan::TableScan(TableScan {
+                table_name,
+                projected_schema,
+                filters,
+                fetch,
+                ..
+            }) => {
+                if !filters.is_empty() || fetch.is_some() {
+                    return Ok(None);
+                }
+                let catalog = &config.options().catalog;
+                let table = TableReference::from(
+                    table_name
+                        .clone()
+                        .resolve(&catalog.default_catalog, &catalog.default_schema),
+                );
+                let outputs = projected_schema
+                    .fields()
+                    .iter()
+                    .map(|field| {
+                        Expr::Column(Column::new(Some(table.clone()), field.name()))
+                    })
+                    .collect();
+                Ok(Some(Self {
+                    tables: vec![table],
+                    predicates: vec![],
+                    aggregate: None,
+                    outputs,
+                }))
+            }
+            LogicalPlan::Projection(Projection { expr, input, .. }) => {
+                let Some(mut normalized) = Self::try_new(input, config)? else {
+                    return Ok(None);
+                };
+                let Some(outputs) =
+                    substitute_all(expr, input.schema(), &normalized.outputs)?
+                else {
+                    return Ok(None);
+                };
+                normalized.outputs = outputs;
+                Ok(Some(normalized))
+            }
+            LogicalPlan::Filter(Filter {
+                predicate, input, ..
+            }) => {
+                let Some(mut normalized) = Self::try_new(input, config)? else {
+                    return Ok(None);
+                };
+                if normalized.aggregate.is_some() {
+                    return Ok(None);
+                }
+                let predicates = split_conjunction(predicate);
+                let Some(predicates) =
+                    substitute_all(predicates, input.schema(), &normalized.outputs)?
+                else {
+                    return Ok(None);
+                };
+                normalized.predicates.extend(predicates);
+                Ok(Some(normalized))
+            }
+            LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => {
+                Self::try_new(input, config)
+            }
+            LogicalPlan::Join(Join {
+                left,
+                right,
+                on,
+                filter,
+                join_type: JoinType::Inner,
+                schema,
+                null_equals_null: false,
+                ..
+            }) => {
+                let (Some(left_normalized), Some(right_normalized)) =
+                    (Self::try_new(left, config)?, Self::try_new(right, config)?)
+                else {
+                    return Ok(None);
+                };
+                if left_normalized.aggregate.is_some()
+                    || right_normalized.aggregate.is_some()
+                    // both sides must be distinguishable by their table names
+                    || right_normalized
+                        .tables
+                        .iter()
+                        .any(|table| left_normalized.tables.contains(table))
+                {
+                    return Ok(None);
+                }
+
+                let mut predicates = left_normalized.predicates;
+                predicates.extend(right_normalized.predicates);
+                for (l, r) in on {
+                    let (Some(l), Some(r)) = (
+                        substitute(l, left.schema(), &left_normalized.outputs)?,
+                        substitute(r, right.schema(), &right_normalized.outputs)?,
+                    ) else {
+                        return Ok(None);
+                    };
+                    predicates.push(l.eq(r));
+                }
+
+                let mut tables = left_normalized.tables;
+                tables.extend(right_normalized.tables);
+                let mut outputs = left_normalized.outputs;
+                outputs.extend(right_normalized.outputs);
+
+                if let Some(filter) = filter {
+                    let Some(filter) =
+                        substitute_all(split_conjunction(filter), schema, &outputs)?
+                    else {
+                        return Ok(None);
+                    };
+                    predicates.extend(filter);
+                }
+
+                Ok(Some(Self {
+                    tables,
+                    predicates,
+                    aggregate: None,
+                    outputs,
+                }))
+            }
+            LogicalPlan::Aggregate(Aggregate {
+                input,
+                group_expr,
+                aggr_expr,
+                ..
+            }) => {
+                let Some(mut normalized) = Self::try_new(input, config)? else {
+                    return Ok(None);
+                };
+                if normalized.aggregate.is_some() {
+                    return Ok(None);
+                }
+                let (Some(group_expr), Some(aggr_expr)) = (
+                    substitute_all(group_expr, input.schema(), &normalized.outputs)?,
+                    substitute_all(aggr_expr, input.schema(), &normalized.outputs)?,
+                ) else {
+                    return Ok(None);
+                };
+                normalized.outputs =
+                    group_expr.iter().chain(&aggr_expr).cloned().collect();
+                normalized.aggregate = Some((group_expr, aggr_expr));
+                Ok(Some(normalized))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Rewrites `expr`, an expression over the columns of `schema`, to an
+/// expression over the tables of a [`Normalized`] plan, given the `outputs`
+/// computing each column of `schema`
+fn substitute(expr: &Expr, schema: &DFSchema, outputs: &[Expr]) -> Result<Option<Expr>> {
+    // such expressions are never equal between a query and a view
+    let unsupported = expr.exists(|e| {
+        Ok(e.is_volatile_node()
+            || matches!(
+                e,
+                Expr::ScalarSubquery(_)
+                    | Expr::Exists(_)
+                    | Expr::InSubquery(_)
+                    | Expr::SetComparison(_)
+                    | Expr::OuterReferenceColumn(..)
+                    | Expr::WindowFunction(_)
+                    | Expr::GroupingSet(_)
+            ))
+    })?;
+    if unsupported {
+        return Ok(None);
+    }
+
+    expr.clone()
+        .unalias()
+        .transform_up(|e| match e {
+            Expr::Column(column) => {
+                let index = schema.index_of_column(&column)?;
+                Ok(Transformed::yes(outputs[index].clone()))
+            }
+            _ => Ok(Transformed::no(e)),
+        })
+        .map(|transformed| Some(transformed.data))
+}
+
+/// Calls [`substitute`] for each of `exprs`
+fn substitute_all<'a>(
+    exprs: impl IntoIterator<Item = &'a Expr>,
+    schema: &DFSchema,
+    outputs: &[Expr],
+) -> Result<Option<Vec<Expr>>> {
+    exprs
+        .into_iter()
+        .map(|expr| substitute(expr, schema, outputs))
+        .collect::<Result<Option<Vec<_>>>>()
+}
+
+/// Returns true if `predicates` contain `predicate`, possibly with the
+/// operands of a comparison swapped, such as `b = a` for `a = b`
+fn contains_predicate(predicates: &[Expr], predicate: &Expr) -> bool {
+    predicates.iter().any(|p| {
+        p == predicate
+            || match p {
+                Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                    op.swap().is_some_and(|swapped| {
+                        *predicate
+                            == Expr::BinaryExpr(BinaryExpr::new(
+                                right.clone(),
+                                swapped,
+                                left.clone(),
+                            ))
+                    })
+                }
+                _ => false,
+            }
+    })
+}
+
+/// Returns true if `expr` contains an aggregate function
+fn contains_aggregate(expr: &Expr) -> bool {
+    expr.exists(|e| Ok(matches!(e, Expr::AggregateFunction(_))))
+        .expect("exists closure is infallible")
+}
+
+/// Rewrites `expr`, an expression over the tables of a [`Normalized`] plan,
+/// by replacing the expressions in `substitutions` with the columns they are
+/// paired with. Returns `None` if `expr` cannot be computed from these columns.
+fn replace(expr: &Expr, substitutions: &[(Expr, Expr)]) -> Result<Option<Expr>> {
+    let mut computable = true;
+    let expr = expr
+        .clone()
+        .transform_down(|e| {
+            if let Some((_, column)) = substitutions.iter().find(|(from, _)| *from == e) {
+                return Ok(Transformed::new(
+                    column.clone(),
+                    true,
+                    TreeNodeRecursion::Jump,
+                ));
+            }
+            if matches!(e, Expr::Column(_) | Expr::AggregateFunction(_)) {
+                computable = false;
+                return Ok(Transformed::new(e, false, TreeNodeRecursion::Stop));
+            }
+            Ok(Transformed::no(e))
+        })?
+        .data;
+    Ok(computable.then_some(expr))
+}
+
+/// Calls [`replace`] for each of `exprs`
+fn replace_all(
+    exprs: &[Expr],
+    substitutions: &[(Expr, Expr)],
+) -> Result<Option<Vec<Expr>>> {
+    exprs
+        .iter()
+        .map(|expr| replace(expr, substitutions))
+        .collect::<Result<Option<Vec<_>>>>()
+}
+
+/// Calls [`replace`] for the arguments of the aggregate function `aggr`
+fn replace_arguments(
+    aggr: &Expr,
+    substitutions: &[(Expr, Expr)],
+) -> Result<Option<Expr>> {
+    if !matches!(aggr, Expr::AggregateFunction(_)) {
+        return Ok(None);
+    }
+    let mut computable = true;
+    let aggr = aggr
+        .clone()
+        .map_children(|child| match replace(&child, substitutions)? {
+            Some(child) => Ok(Transformed::yes(child)),
+            None => {
+                computable = false;
+                Ok(Transformed::new(child, false, TreeNodeRecursion::Stop))
+            }
+        })?
+        .data;
+    Ok(computable.then_some(aggr))
+}
+
+/// Computes the aggregate function `aggr` from the partial aggregates in
+/// `substitutions`, or returns `None` if it cannot be rolled up
+fn rollup(
+    aggr: &Expr,
+    substitutions: &[(Expr, Expr)],
+    grouped: bool,
+    config: &dyn OptimizerConfig,
+) -> Result<Option<Expr>> {
+    let Expr::AggregateFunction(function) = aggr else {
+        return Ok(None);
+    };
+    let Some((_, partial)) = substitutions
+        .iter()
+        .find(|(from, _)| same_aggregate(from, function))
+    else {
+        return Ok(None);
+    };
+    if function.distinct {
+        return Ok(None);
+    }
+
+    let func = match function.func.name() {
+        "sum" | "min" | "max" => Arc::clone(&function.func),
+        // a count of no rows is 0, but a sum of no counts is null, so only
+        // groups that are known to be non-empty can be rolled up
+        "count" if grouped => {
+            match config
+                .function_registry()
+                .and_then(|registry| registry.udaf("sum").ok())
+            {
+                Some(sum) => sum,
+                None => return Ok(None),
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(Expr::AggregateFunction(AggregateFunction::new_udf(
+        func,
+        vec![partial.clone()],
+        false,
+        None,
+        None,
+        None,
+    ))))
+}
+
+/// Returns true if `view_aggr` is the aggregate function `aggr`: the same
+/// function of the same arguments, with the same `DISTINCT`, `FILTER`,
+/// `ORDER BY` and null treatment
+fn same_aggregate(view_aggr: &Expr, aggr: &AggregateFunction) -> bool {
+    let Expr::AggregateFunction(view_aggr) = view_aggr else {
+        return false;
+    };
+    let AggregateFunction {
+        func,
+        args,
+        distinct,
+        filter,
+        order_by,
+        null_treatment,
+    } = aggr;
+    view_aggr.func == *func
+        && view_aggr.args == *args
+        && view_aggr.distinct == *distinct
+        && view_aggr.filter == *filter
+        && view_aggr.order_by == *order_by
+        && view_aggr.null_treatment == *null_treatment
+}
+
+/// Applies the predicates of `residual` to `scan`, or returns `None` if
+/// they cannot be computed from the columns in `substitutions`
+fn filter(
+    scan: &LogicalPlan,
+    residual: &[Expr],
+    substitutions: &[(Expr, Expr)],
+) -> Result<Option<LogicalPlan>> {
+    let Some(predicates) = replace_all(residual, substitutions)? else {
+        return Ok(None);
+    };
+    let plan = match conjunction(predicates) {
+        Some(predicate) => {
+            LogicalPlan::Filter(Filter::try_new(predicate, Arc::new(scan.clone()))?)
+        }
+        None => scan.clone(),
+    };
+    Ok(Some(plan))
+}
+
+/// Aggregates `input` by `group_expr` into `aggr_expr`, which compute the
+/// grouping and aggregate expressions `query_group_expr` and `query_aggr_expr`
+/// of the query. Returns the aggregate and the substitutions for its output
+/// columns.
+fn aggregate(
+    input: LogicalPlan,
+    group_expr: Vec<Expr>,
+    aggr_expr: Vec<Expr>,
+    query_group_expr: &[Expr],
+    query_aggr_expr: &[Expr],
+) -> Result<(LogicalPlan, Vec<(Expr, Expr)>)> {
+    let plan = LogicalPlan::Aggregate(Aggregate::try_new(
+        Arc::new(input),
+        group_expr,
+        aggr_expr,
+    )?);
+    let substitutions = query_group_expr
+        .iter()
+        .chain(query_aggr_expr)
+        .cloned()
+        .zip(plan.schema().columns().into_iter().map(Expr::Column))
+        .collect();
+    Ok((plan, substitutions))
+}
+
+/// Returns true if `left` and `right` contain the same elements
+fn same_elements<T: PartialEq>(left: &[T], right: &[T]) -> bool {
+    left.iter().all(|expr| right.contains(expr))
+        && right.iter().all(|expr| left.contains(expr))
+}
+
+/// Rewrites the plan `query` with output `schema` to read from `view`, whose
+/// definition is `definition`, or returns `None` if the view does not contain
+/// the result of the plan
+fn rewrite_from_view(
+    query: &Normalized,
+    schema: &DFSchema,
+    definition: &Normalized,
+    view: &MaterializedViewCandidate,
+    config: &dyn OptimizerConfig,
+) -> Result<Option<LogicalPlan>> {
+    if !same_elements(&query.tables, &definition.tables) {
+        return Ok(None);
+    }
+    // every row of the query must be a row of the view
+    if !definition
+        .predicates
+        .iter()
+        .all(|predicate| contains_predicate(&query.predicates, predicate))
+    {
+        return Ok(None);
+    }
+    let residual: Vec<Expr> = query
+        .predicates
+        .iter()
+        .filter(|predicate| !contains_predicate(&definition.predicates, predicate))
+        .cloned()
+        .collect();
+
+    // each output of the view, paired with the column storing it
+    let outputs: Vec<(Expr, Expr)> = definition
+        .outputs
+        .iter()
+        .cloned()
+        .zip(view.scan.schema().columns().into_iter().map(Expr::Column))
+        .collect();
+
+    let (input, substitutions) = match (&query.aggregate, &definition.aggregate) {
+        (None, None) => {
+            let Some(input) = filter(&view.scan, &residual, &outputs)? else {
+                return Ok(None);
+            };
+            (input, outputs)
+        }
+        (Some((group_expr, aggr_expr)), None) => {
+            let Some(input) = filter(&view.scan, &residual, &outputs)? else {
+                return Ok(None);
+            };
+            let Some(new_group_expr) = replace_all(group_expr, &outputs)? else {
+                return Ok(None);
+            };
+            let Some(new_aggr_expr) = aggr_expr
+                .iter()
+                .map(|aggr| replace_arguments(aggr, &outputs))
+                .collect::<Result<Option<Vec<_>>>>()?
+            else {
+                return Ok(None);
+            };
+            aggregate(input, new_group_expr, new_aggr_expr, group_expr, aggr_expr)?
+        }
+        (Some((group_expr, aggr_expr)), Some((view_group_expr, _))) => {
+            // rows of the view are groups, which can only be filtered by
+            // their grouping expressions
+            let grouped: Vec<(Expr, Expr)> = outputs
+                .iter()
+                .filter(|(output, _)| !contains_aggregate(output))
+                .cloned()
+                .collect();
+            let Some(input) = filter(&view.scan, &residual, &grouped)? else {
+                return Ok(None);
+            };
+            if same_elements(group_expr, view_group_expr) {
+                (input, outputs)
+            } else {
+                let Some(new_group_expr) = replace_all(group_expr, &grouped)? else {
+                    return Ok(None);
+                };
+                let Some(new_aggr_expr) = aggr_expr
+                    .iter()
+                    .map(|aggr| rollup(aggr, &outputs, !group_expr.is_empty(), config))
+                    .collect::<Result<Option<Vec<_>>>>()?
+                else {
+                    return Ok(None);
+                };
+                aggregate(input, new_group_expr, new_aggr_expr, group_expr, aggr_expr)?
+            }
+        }
+        (None, Some(_)) => return Ok(None),
+    };
+
+    // keep the names and types of the output columns of the query
+    let mut exprs = Vec::with_capacity(query.outputs.len());
+    for (output, (qualifier, field)) in query.outputs.iter().zip(schema.iter()) {
+        let Some(expr) = replace(output, &substitutions)? else {
+            return Ok(None);
+        };
+        let expr = if expr.get_type(input.schema())? == *field.data_type() {
+            expr
+        } else {
+            expr.cast_to(field.data_type(), input.schema())?
+        };
+        exprs.push(expr.alias_qualified(qualifier.cloned(), field.name()));
+    }
+    Ok(Some(LogicalPlan::Projection(Projection::try_new(
+        exprs,
+        Arc::new(input),
+    )?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+    use crate::{Optimizer, OptimizerContext};
+
+    use datafusion_expr::logical_plan::LogicalTableSource;
+    use datafusion_expr::{col, lit, ExprFunctionExt};
+    use datafusion_functions_aggregate::expr_fn::{max, sum};
+
+    fn view(name: &str, plan: LogicalPlan) -> Result<MaterializedViewCandidate> {
+        let schema = Arc::new(plan.schema().as_arrow().clone());
+        let source = Arc::new(LogicalTableSource::new(schema));
+        MaterializedViewCandidate::try_new(name, plan, source)
+    }
+
+    fn optimize(plan: LogicalPlan, view: MaterializedViewCandidate) -> Result<String> {
+        let config = OptimizerContext::new()
+            .with_max_passes(1)
+            .with_materialized_view_rewrite(true)
+            .with_materialized_view(view);
+        let optimizer =
+            Optimizer::with_rules(vec![Arc::new(MaterializedViewRewrite::new())]);
+        let optimized = optimizer.optimize(plan, &config, |_, _| {})?;
+        Ok(format!("{optimized}"))
+    }
+
+    #[test]
+    fn read_aggregate_from_view() -> Result<()> {
+        let definition = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![col("a")], vec![sum(col("b"))])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![col("a")], vec![sum(col("b"))])?
+            .build()?;
+
+        let expected = "Projection: mv.a AS a, mv.sum(test.b) AS sum(test.b)\
+        \n  TableScan: mv";
+        assert_eq!(optimize(plan, view("mv", definition)?)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn roll_up_aggregate_of_view() -> Result<()> {
+        let definition = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![col("a"), col("b")], vec![sum(col("c")), max(col("c"))])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").gt(lit(1u32)))?
+            .aggregate(vec![col("a")], vec![sum(col("c"))])?
+            .build()?;
+
+        let expected = "Projection: mv.a AS a, sum(mv.sum(test.c)) AS sum(test.c)\
+        \n  Aggregate: groupBy=[[mv.a]], aggr=[[sum(mv.sum(test.c))]]\
+        \n    Filter: mv.a > UInt32(1)\
+        \n      TableScan: mv";
+        assert_eq!(optimize(plan, view("mv", definition)?)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn roll_up_only_same_aggregate() -> Result<()> {
+        let filtered_sum = |predicate: Expr| {
+            sum(col("c"))
+                .filter(predicate)
+                .order_by(vec![col("c").sort(true, false)])
+                .build()
+        };
+        let definition = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(
+                vec![col("a"), col("b")],
+                vec![filtered_sum(col("c").gt(lit(1u32)))?],
+            )?
+            .build()?;
+
+        // the sum of the view only adds up the rows with c > 1
+        for aggr in [sum(col("c")), filtered_sum(col("c").gt(lit(2u32)))?] {
+            let plan = LogicalPlanBuilder::from(test_table_scan()?)
+                .aggregate(vec![col("a")], vec![aggr])?
+                .build()?;
+            let expected = format!("{plan}");
+            assert_eq!(optimize(plan, view("mv", definition.clone())?)?, expected);
+        }
+
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![col("a")], vec![filtered_sum(col("c").gt(lit(1u32)))?])?
+            .build()?;
+        let optimized = optimize(plan, view("mv", definition)?)?;
+        assert!(optimized.contains("TableScan: mv"), "{optimized}");
+        Ok(())
+    }
+
+    #[test]
+    fn filter_join_of_view() -> Result<()> {
+        let definition = LogicalPlanBuilder::from(test_table_scan_with_name("t1")?)
+            .join(
+                test_table_scan_with_name("t2")?,
+                JoinType::Inner,
+                (vec!["t1.a"], vec!["t2.a"]),
+                None,
+            )?
+            .filter(col("t1.b").gt(lit(10u32)))?
+            .project(vec![col("t1.a"), col("t1.b"), col("t2.c")])?
+            .build()?;
+        // the tables are joined in the opposite order
+        let plan = LogicalPlanBuilder::from(test_table_scan_with_name("t2")?)
+            .join(
+                test_table_scan_with_name("t1")?,
+                JoinType::Inner,
+                (vec!["t2.a"], vec!["t1.a"]),
+                None,
+            )?
+            .filter(col("t1.b").gt(lit(10u32)).and(col("t2.c").lt(lit(5u32))))?
+            .project(vec![col("t1.a"), col("t2.c")])?
+            .build()?;
+
+        let expected = "Projection: mv.a AS a, mv.c AS c\
+        \n  Filter: mv.c < UInt32(5)\
+        \n    TableScan: mv";
+        assert_eq!(optimize(plan, view("mv", definition)?)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn keep_plan_not_contained_in_view() -> Result<()> {
+        let definition = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").gt(lit(1u32)))?
+            .aggregate(vec![col("a")], vec![sum(col("b"))])?
+            .build()?;
+
+        // the view lacks the rows with a <= 1
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![col("a")], vec![sum(col("b"))])?
+            .build()?;
+        let expected = format!("{plan}");
+        assert_eq!(optimize(plan, view("mv", definition.clone())?)?, expected);
+
+        // the view lacks the column c
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("a").gt(lit(1u32)))?
+            .aggregate(vec![col("a")], vec![sum(col("c"))])?
+            .build()?;
+        let expected = format!("{plan}");
+        assert_eq!(optimize(plan, view("mv", definition)?)?, expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`MergeJsonAccess`] reads several paths of the same JSON document with a
+//! single `json_get_str_paths` call
+
+use std::sync::Arc;
+
+use crate::optimizer::ApplyOrder;
+use crate::utils::NamePreserver;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_common::{Result, ScalarValue};
+use datafusion_expr::expr::ScalarFunction;
+use datafusion_expr::{lit, Expr, LogicalPlan, ScalarUDF};
+use indexmap::{IndexMap, IndexSet};
+
+/// Optimizer rule that merges `json_get_str` calls reading different paths
+/// of the same document, such as `a->>'x'` and `a->>'y'`, into element
+/// accesses of one `json_get_str_paths` call, so that the document is parsed
+/// once per row after [`CommonSubexprEliminate`] extracts the shared call.
+///
+/// Only calls in projections and filters whose path elements are all string
+/// literals are merged. The rule does nothing if `json_get_str_paths` or
+/// `array_element` are not registered.
+///
+/// [`CommonSubexprEliminate`]: crate::common_subexpr_eliminate::CommonSubexprEliminate
+#[derive(Default, Debug)]
+pub struct MergeJsonAccess {}
+
+impl MergeJsonAccess {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for MergeJsonAccess {
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        if !matches!(plan, LogicalPlan::Projection(_) | LogicalPlan::Filter(_)) {
+            return Ok(Transformed::no(plan));
+        }
+
+        let mut paths_by_document = IndexMap::<Expr, IndexSet<String>>::new();
+        plan.apply_expressions(|expr| {
+            expr.apply(|expr| {
+                if let Some((document, path)) = json_get_str_path(expr) {
+                    paths_by_document
+                        .entry(document.clone())
+                        .or_default()
+                        .insert(path);
+                }
+                Ok(TreeNodeRecursion::Continue)
+            })
+        })?;
+        paths_by_document.retain(|_, paths| paths.len() > 1);
+        if paths_by_document.is_empty() {
+            return Ok(Transformed::no(plan));
+        }
+
+        let Some((get_str_paths, array_element)) =
+            config.function_registry().and_then(|registry| {
+                Some((
+                    registry.udf("json_get_str_paths").ok()?,
+                    registry.udf("array_element").ok()?,
+                ))
+            })
+        else {
+            return Ok(Transformed::no(plan));
+        };
+
+        let name_preserver = NamePreserver::new(&plan);
+        plan.map_expressions(|expr| {
+            let original_name = name_preserver.save(&expr);
+            let transformed_expr = expr.transform_down(|expr| {
+                let Some((document, path)) = json_get_str_path(&expr) else {
+                    return Ok(Transformed::no(expr));
+                };
+                let Some(paths) = paths_by_document.get(document) else {
+                    return Ok(Transformed::no(expr));
+                };
+                let index = paths.get_index_of(&path).unwrap() as i64 + 1;
+                let all_paths = Expr::ScalarFunction(ScalarFunction::new_udf(
+                    Arc::clone(&get_str_paths),
+                    std::iter::once(document.clone())
+                        .chain(paths.iter().map(|path| lit(path.as_str())))
+                        .collect(),
+                ));
+                Ok(Transformed::yes(element(&array_element, all_paths, index)))
+            })?;
+            Ok(transformed_expr.update_data(|expr| original_name.restore(expr)))
+        })
+    }
+
+    fn name(&self) -> &str {
+        "merge_json_access"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+}
+
+fn element(array_element: &Arc<ScalarUDF>, array: Expr, index: i64) -> Expr {
+    Expr::ScalarFunction(ScalarFunction::new_udf(
+        Arc::clone(array_element),
+        vec![array, lit(index)],
+    ))
+}
+
+/// Returns the document of a `json_get_str` call and its path written as the
+/// text array `json_get_str_paths` expects, if the path can be written so
+fn json_get_str_path(expr: &Expr) -> Option<(&Expr, String)> {
+    let Expr::ScalarFunction(ScalarFunction { func, args }) = expr else {
+        return None;
+    };
+    if func.name() != "json_get_str" {
+        return None;
+    }
+    let (document, elements) = args.split_first()?;
+    // merging would evaluate a volatile document once instead of once per call
+    if elements.is_empty() || document.is_volatile() {
+        return None;
+    }
+    let elements = elements
+        .iter()
+        .map(|element| match element {
+            // integers index arrays only, while keys of text arrays are
+            // also looked up in objects
+            Expr::Literal(
+                ScalarValue::Utf8(Some(key))
+                | ScalarValue::LargeUtf8(Some(key))
+                | ScalarValue::Utf8View(Some(key)),
+            ) if !key.contains(',') => Some(format!("\"{key}\"")),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some((document, format!("{{{}}}", elements.join(","))))
+}
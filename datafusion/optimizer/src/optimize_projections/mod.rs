@@ -295,7 +295,7 @@ fn optimize_projections(
                 })
                 .collect::<Result<_>>()?
         }
-        LogicalPlan::Limit(_) => {
+        LogicalPlan::Limit(_) | LogicalPlan::Sample(_) => {
             // Pass index requirements from the parent as well as column indices
             // that appear in this plan's expressions to its child. These operators
             // do not benefit from "small" inputs, so the projection_beneficial
@@ -308,6 +308,7 @@ fn optimize_projections(
         LogicalPlan::Copy(_)
         | LogicalPlan::Ddl(_)
         | LogicalPlan::Dml(_)
+        | LogicalPlan::GapFill(_)
         | LogicalPlan::Explain(_)
         | LogicalPlan::Analyze(_)
         | LogicalPlan::Subquery(_)
@@ -618,6 +619,12 @@ fn outer_columns<'a>(expr: &'a Expr, columns: &mut HashSet<&'a Column>) {
                     columns,
                 );
             }
+            Expr::SetComparison(comparison) => {
+                outer_columns_helper_multi(
+                    &comparison.subquery.outer_ref_columns,
+                    columns,
+                );
+            }
             _ => {}
         };
         Ok(TreeNodeRecursion::Continue)
@@ -259,6 +259,7 @@ fn can_evaluate_as_join_condition(predicate: &Expr) -> Result<bool> {
         | Expr::ScalarVariable(_, _) => Ok(TreeNodeRecursion::Jump),
         Expr::Exists { .. }
         | Expr::InSubquery(_)
+        | Expr::SetComparison(_)
         | Expr::ScalarSubquery(_)
         | Expr::OuterReferenceColumn(_, _)
         | Expr::Unnest(_) => {
@@ -563,6 +564,12 @@ fn infer_join_predicates(
     predicates: &[Expr],
     on_filters: &[Expr],
 ) -> Result<Vec<Expr>> {
+    // A predicate inferred for the right input of a null-aware join would
+    // filter out the NULL keys that decide its result.
+    if join.null_aware {
+        return Ok(vec![]);
+    }
+
     // Only allow both side key is column.
     let join_col_keys = join
         .on
@@ -863,9 +870,19 @@ impl OptimizerRule for PushDownFilter {
                     let mut accum: HashSet<Column> = HashSet::new();
                     expr_to_columns(&predicate, &mut accum)?;
 
-                    if unnest.list_type_columns.iter().any(|(_, unnest_list)| {
-                        accum.contains(&unnest_list.output_column)
-                    }) {
+                    // The ordinality column is produced by the unnest too
+                    let uses_ordinality = unnest
+                        .options
+                        .ordinality_column
+                        .as_ref()
+                        .is_some_and(|name| {
+                            accum.iter().any(|column| &column.name == name)
+                        });
+                    if uses_ordinality
+                        || unnest.list_type_columns.iter().any(|(_, unnest_list)| {
+                            accum.contains(&unnest_list.output_column)
+                        })
+                    {
                         unnest_predicates.push(predicate);
                     } else {
                         non_unnest_predicates.push(predicate);
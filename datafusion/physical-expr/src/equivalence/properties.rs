@@ -582,6 +582,7 @@ impl EquivalenceProperties {
                     indices,
                     matches!(constraint, Constraint::Unique(_)),
                 ),
+            Constraint::Check { .. } => false,
         })
     }
 
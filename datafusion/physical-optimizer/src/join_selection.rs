@@ -207,7 +207,8 @@ impl PhysicalOptimizerRule for JoinSelection {
 /// This function will first consider the given join type and check whether the
 /// `CollectLeft` mode is applicable. Otherwise, it will try to swap the join sides.
 /// When the `ignore_threshold` is false, this function will also check left
-/// and right sizes in bytes or rows.
+/// and right sizes in bytes or rows. As null-aware joins can not be
+/// partitioned, they ignore the thresholds if neither side is below them.
 pub(crate) fn try_collect_left(
     hash_join: &HashJoinExec,
     ignore_threshold: bool,
@@ -217,6 +218,19 @@ pub(crate) fn try_collect_left(
     let left = hash_join.left();
     let right = hash_join.right();
 
+    let ignore_threshold = ignore_threshold
+        || hash_join.null_aware()
+            && !supports_collect_by_thresholds(
+                &**left,
+                threshold_byte_size,
+                threshold_num_rows,
+            )
+            && !supports_collect_by_thresholds(
+                &**right,
+                threshold_byte_size,
+                threshold_num_rows,
+            );
+
     let left_can_collect = ignore_threshold
         || supports_collect_by_thresholds(
             &**left,
@@ -237,28 +251,34 @@ pub(crate) fn try_collect_left(
             {
                 Ok(Some(hash_join.swap_inputs(PartitionMode::CollectLeft)?))
             } else {
-                Ok(Some(Arc::new(HashJoinExec::try_new(
-                    Arc::clone(left),
-                    Arc::clone(right),
-                    hash_join.on().to_vec(),
-                    hash_join.filter().cloned(),
-                    hash_join.join_type(),
-                    hash_join.projection.clone(),
-                    PartitionMode::CollectLeft,
-                    hash_join.null_equals_null(),
-                )?)))
+                Ok(Some(Arc::new(
+                    HashJoinExec::try_new(
+                        Arc::clone(left),
+                        Arc::clone(right),
+                        hash_join.on().to_vec(),
+                        hash_join.filter().cloned(),
+                        hash_join.join_type(),
+                        hash_join.projection.clone(),
+                        PartitionMode::CollectLeft,
+                        hash_join.null_equals_null(),
+                    )?
+                    .with_null_aware(hash_join.null_aware())?,
+                )))
             }
         }
-        (true, false) => Ok(Some(Arc::new(HashJoinExec::try_new(
-            Arc::clone(left),
-            Arc::clone(right),
-            hash_join.on().to_vec(),
-            hash_join.filter().cloned(),
-            hash_join.join_type(),
-            hash_join.projection.clone(),
-            PartitionMode::CollectLeft,
-            hash_join.null_equals_null(),
-        )?))),
+        (true, false) => Ok(Some(Arc::new(
+            HashJoinExec::try_new(
+                Arc::clone(left),
+                Arc::clone(right),
+                hash_join.on().to_vec(),
+                hash_join.filter().cloned(),
+                hash_join.join_type(),
+                hash_join.projection.clone(),
+                PartitionMode::CollectLeft,
+                hash_join.null_equals_null(),
+            )?
+            .with_null_aware(hash_join.null_aware())?,
+        ))),
         (false, true) => {
             if hash_join.join_type().supports_swap() {
                 hash_join.swap_inputs(PartitionMode::CollectLeft).map(Some)
@@ -284,16 +304,19 @@ pub(crate) fn partitioned_hash_join(
     {
         hash_join.swap_inputs(PartitionMode::Partitioned)
     } else {
-        Ok(Arc::new(HashJoinExec::try_new(
-            Arc::clone(left),
-            Arc::clone(right),
-            hash_join.on().to_vec(),
-            hash_join.filter().cloned(),
-            hash_join.join_type(),
-            hash_join.projection.clone(),
-            PartitionMode::Partitioned,
-            hash_join.null_equals_null(),
-        )?))
+        Ok(Arc::new(
+            HashJoinExec::try_new(
+                Arc::clone(left),
+                Arc::clone(right),
+                hash_join.on().to_vec(),
+                hash_join.filter().cloned(),
+                hash_join.join_type(),
+                hash_join.projection.clone(),
+                PartitionMode::Partitioned,
+                hash_join.null_equals_null(),
+            )?
+            .with_null_aware(hash_join.null_aware())?,
+        ))
     }
 }
 
@@ -402,7 +425,11 @@ fn hash_join_convert_symmetric_subrule(
             EmissionType::Incremental | EmissionType::Both
         );
         // Process only if both left and right sides are unbounded and incrementally emit.
-        if left_unbounded && right_unbounded & left_incremental & right_incremental {
+        // Symmetric hash joins do not support null-aware anti joins.
+        if left_unbounded
+            && right_unbounded & left_incremental & right_incremental
+            && !hash_join.null_aware()
+        {
             // Determine the partition mode based on configuration.
             let mode = if config_options.optimizer.repartition_joins {
                 StreamJoinPartitionMode::Partitioned
@@ -29,6 +29,7 @@ use crate::display::DisplayableExecutionPlan;
 use crate::{DisplayFormatType, ExecutionPlan, Partitioning};
 
 use arrow::{array::StringBuilder, datatypes::SchemaRef, record_batch::RecordBatch};
+use datafusion_common::format::ExplainFormat;
 use datafusion_common::instant::Instant;
 use datafusion_common::{internal_err, DataFusionError, Result};
 use datafusion_execution::TaskContext;
@@ -44,6 +45,8 @@ pub struct AnalyzeExec {
     verbose: bool,
     /// If statistics should be displayed
     show_statistics: bool,
+    /// The format of the annotated plan
+    explain_format: ExplainFormat,
     /// The input plan (the plan being analyzed)
     pub(crate) input: Arc<dyn ExecutionPlan>,
     /// The output schema for RecordBatches of this exec node
@@ -63,6 +66,7 @@ impl AnalyzeExec {
         AnalyzeExec {
             verbose,
             show_statistics,
+            explain_format: ExplainFormat::default(),
             input,
             schema,
             cache,
@@ -79,6 +83,17 @@ impl AnalyzeExec {
         self.show_statistics
     }
 
+    /// Set the format of the annotated plan
+    pub fn with_explain_format(mut self, explain_format: ExplainFormat) -> Self {
+        self.explain_format = explain_format;
+        self
+    }
+
+    /// Access to explain_format
+    pub fn explain_format(&self) -> ExplainFormat {
+        self.explain_format
+    }
+
     /// The input plan
     pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
         &self.input
@@ -139,12 +154,15 @@ impl ExecutionPlan for AnalyzeExec {
         self: Arc<Self>,
         mut children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(Self::new(
-            self.verbose,
-            self.show_statistics,
-            children.pop().unwrap(),
-            Arc::clone(&self.schema),
-        )))
+        Ok(Arc::new(
+            Self::new(
+                self.verbose,
+                self.show_statistics,
+                children.pop().unwrap(),
+                Arc::clone(&self.schema),
+            )
+            .with_explain_format(self.explain_format),
+        ))
     }
 
     fn execute(
@@ -179,6 +197,7 @@ impl ExecutionPlan for AnalyzeExec {
         let captured_schema = Arc::clone(&self.schema);
         let verbose = self.verbose;
         let show_statistics = self.show_statistics;
+        let explain_format = self.explain_format;
 
         // future that gathers the results from all the tasks in the
         // JoinSet that computes the overall row count and final
@@ -194,6 +213,7 @@ impl ExecutionPlan for AnalyzeExec {
             create_output_batch(
                 verbose,
                 show_statistics,
+                explain_format,
                 total_rows,
                 duration,
                 captured_input,
@@ -212,6 +232,7 @@ impl ExecutionPlan for AnalyzeExec {
 fn create_output_batch(
     verbose: bool,
     show_statistics: bool,
+    explain_format: ExplainFormat,
     total_rows: usize,
     duration: std::time::Duration,
     input: Arc<dyn ExecutionPlan>,
@@ -225,8 +246,7 @@ fn create_output_batch(
 
     let annotated_plan = DisplayableExecutionPlan::with_metrics(input.as_ref())
         .set_show_statistics(show_statistics)
-        .indent(verbose)
-        .to_string();
+        .format(verbose, explain_format)?;
     plan_builder.append_value(annotated_plan);
 
     // Verbose output
@@ -236,8 +256,7 @@ fn create_output_batch(
 
         let annotated_plan = DisplayableExecutionPlan::with_full_metrics(input.as_ref())
             .set_show_statistics(show_statistics)
-            .indent(verbose)
-            .to_string();
+            .format(verbose, explain_format)?;
         plan_builder.append_value(annotated_plan);
 
         type_builder.append_value("Output Rows");
@@ -24,6 +24,8 @@ use std::fmt::Formatter;
 use arrow_schema::SchemaRef;
 
 use datafusion_common::display::{GraphvizBuilder, PlanType, StringifiedPlan};
+use datafusion_common::format::ExplainFormat;
+use datafusion_common::Result;
 use datafusion_expr::display_schema;
 use datafusion_physical_expr::LexOrdering;
 
@@ -224,6 +226,67 @@ impl<'a> DisplayableExecutionPlan<'a> {
         }
     }
 
+    /// Returns a `format`able structure that produces a JSON tree of the
+    /// execution plan, for consumption by other tools.
+    ///
+    /// Requires the `explain_json` feature.
+    ///
+    /// Every operator is an object with its one line description, its
+    /// properties (partitioning, ordering, emission type and boundedness),
+    /// its children and, if enabled, its statistics, schema and metrics:
+    ///
+    /// ```json
+    /// {
+    ///   "operator": "ProjectionExec",
+    ///   "details": "ProjectionExec: expr=[id@0 + 2 as employee.id + Int32(2)]",
+    ///   "properties": {
+    ///     "partitioning": "UnknownPartitioning(1)",
+    ///     "output_partitions": 1,
+    ///     "ordering": null,
+    ///     "emission_type": "incremental",
+    ///     "boundedness": "bounded"
+    ///   },
+    ///   "children": [
+    ///     ...
+    ///   ]
+    /// }
+    /// ```
+    #[cfg(feature = "explain_json")]
+    pub fn json(&self, verbose: bool) -> impl fmt::Display + 'a {
+        let format_type = if verbose {
+            DisplayFormatType::Verbose
+        } else {
+            DisplayFormatType::Default
+        };
+        struct Wrapper<'a> {
+            format_type: DisplayFormatType,
+            plan: &'a dyn ExecutionPlan,
+            show_metrics: ShowMetrics,
+            show_statistics: bool,
+            show_schema: bool,
+        }
+        impl fmt::Display for Wrapper<'_> {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                let visitor = json::JsonVisitor {
+                    t: self.format_type,
+                    show_metrics: self.show_metrics,
+                    show_statistics: self.show_statistics,
+                    show_schema: self.show_schema,
+                };
+                let json = serde_json::to_string_pretty(&visitor.plan_to_json(self.plan))
+                    .map_err(|_| fmt::Error)?;
+                write!(f, "{json}")
+            }
+        }
+        Wrapper {
+            format_type,
+            plan: self.inner,
+            show_metrics: self.show_metrics,
+            show_statistics: self.show_statistics,
+            show_schema: self.show_schema,
+        }
+    }
+
     /// Return a single-line summary of the root of the plan
     /// Example: `ProjectionExec: expr=[a@0 as a]`.
     pub fn one_line(&self) -> impl fmt::Display + 'a {
@@ -261,6 +324,38 @@ impl<'a> DisplayableExecutionPlan<'a> {
     pub fn to_stringified(&self, verbose: bool, plan_type: PlanType) -> StringifiedPlan {
         StringifiedPlan::new(plan_type, self.indent(verbose).to_string())
     }
+
+    /// format as a `StringifiedPlan` in the given [`ExplainFormat`]
+    pub fn to_stringified_with_format(
+        &self,
+        verbose: bool,
+        plan_type: PlanType,
+        explain_format: ExplainFormat,
+    ) -> Result<StringifiedPlan> {
+        Ok(StringifiedPlan::new(
+            plan_type,
+            self.format(verbose, explain_format)?,
+        ))
+    }
+
+    /// Format the plan in the given [`ExplainFormat`]
+    ///
+    /// Returns an error for [`ExplainFormat::Json`] if the `explain_json`
+    /// feature is not enabled.
+    pub fn format(&self, verbose: bool, explain_format: ExplainFormat) -> Result<String> {
+        match explain_format {
+            ExplainFormat::Indent => Ok(self.indent(verbose).to_string()),
+            #[cfg(feature = "explain_json")]
+            ExplainFormat::Json => Ok(self.json(verbose).to_string()),
+            #[cfg(not(feature = "explain_json"))]
+            ExplainFormat::Json => {
+                datafusion_common::not_impl_err!(
+                    "EXPLAIN FORMAT JSON requires the explain_json feature"
+                )
+            }
+            ExplainFormat::Graphviz => Ok(self.graphviz().to_string()),
+        }
+    }
 }
 
 /// Enum representing the different levels of metrics to display
@@ -448,6 +543,177 @@ impl ExecutionPlanVisitor for GraphvizVisitor<'_, '_> {
     }
 }
 
+#[cfg(feature = "explain_json")]
+mod json {
+    use std::fmt::{self, Formatter};
+
+    use datafusion_common::stats::Precision;
+    use datafusion_common::Statistics;
+    use serde_json::{json, Map as JsonMap, Value as JsonValue};
+
+    use super::{DisplayFormatType, ShowMetrics};
+    use crate::execution_plan::{Boundedness, EmissionType};
+    use crate::metrics::Metric;
+    use crate::ExecutionPlan;
+
+    /// Formats plans as a JSON tree, see [`super::DisplayableExecutionPlan::json`]
+    pub(super) struct JsonVisitor {
+        /// How to format each node
+        pub(super) t: DisplayFormatType,
+        /// How to show metrics
+        pub(super) show_metrics: ShowMetrics,
+        /// If statistics should be displayed
+        pub(super) show_statistics: bool,
+        /// If schema should be displayed
+        pub(super) show_schema: bool,
+    }
+
+    impl JsonVisitor {
+        pub(super) fn plan_to_json(&self, plan: &dyn ExecutionPlan) -> JsonValue {
+            struct Wrapper<'a>(&'a dyn ExecutionPlan, DisplayFormatType);
+
+            impl fmt::Display for Wrapper<'_> {
+                fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    self.0.fmt_as(self.1, f)
+                }
+            }
+
+            let mut node = JsonMap::new();
+            node.insert("operator".to_string(), json!(plan.name()));
+            node.insert(
+                "details".to_string(),
+                json!(Wrapper(plan, self.t).to_string()),
+            );
+
+            let properties = plan.properties();
+            let emission_type = match properties.emission_type {
+                EmissionType::Incremental => "incremental",
+                EmissionType::Final => "final",
+                EmissionType::Both => "both",
+            };
+            let boundedness = match properties.boundedness {
+                Boundedness::Bounded => "bounded",
+                Boundedness::Unbounded { .. } => "unbounded",
+            };
+            node.insert(
+                "properties".to_string(),
+                json!({
+                    "partitioning": properties.output_partitioning().to_string(),
+                    "output_partitions": properties.output_partitioning().partition_count(),
+                    "ordering": properties.output_ordering().map(|ordering| ordering.to_string()),
+                    "emission_type": emission_type,
+                    "boundedness": boundedness,
+                }),
+            );
+
+            if self.show_statistics {
+                // Not all plans can compute statistics, which is not an error here
+                let statistics = plan
+                    .statistics()
+                    .map_or(JsonValue::Null, |stats| statistics_to_json(&stats));
+                node.insert("statistics".to_string(), statistics);
+            }
+
+            if self.show_schema {
+                let fields = plan
+                    .schema()
+                    .fields()
+                    .iter()
+                    .map(|field| {
+                        json!({
+                            "name": field.name(),
+                            "data_type": field.data_type().to_string(),
+                            "nullable": field.is_nullable(),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                node.insert("schema".to_string(), JsonValue::Array(fields));
+            }
+
+            let metrics = match self.show_metrics {
+                ShowMetrics::None => None,
+                ShowMetrics::Aggregated => Some(plan.metrics().map(|metrics| {
+                    metrics
+                        .aggregate_by_name()
+                        .sorted_for_display()
+                        .timestamps_removed()
+                })),
+                ShowMetrics::Full => Some(plan.metrics()),
+            };
+            if let Some(metrics) = metrics {
+                let metrics = metrics
+                    .map(|metrics| metrics.iter().map(|m| metric_to_json(m)).collect())
+                    .unwrap_or_default();
+                node.insert("metrics".to_string(), JsonValue::Array(metrics));
+            }
+
+            let children = plan
+                .children()
+                .into_iter()
+                .map(|child| self.plan_to_json(child.as_ref()))
+                .collect();
+            node.insert("children".to_string(), JsonValue::Array(children));
+
+            JsonValue::Object(node)
+        }
+    }
+
+    fn statistics_to_json(statistics: &Statistics) -> JsonValue {
+        let columns = statistics
+            .column_statistics
+            .iter()
+            .map(|column| {
+                json!({
+                    "null_count": precision_to_json(&column.null_count, |v| json!(v)),
+                    "min_value": precision_to_json(&column.min_value, |v| json!(v.to_string())),
+                    "max_value": precision_to_json(&column.max_value, |v| json!(v.to_string())),
+                    "sum_value": precision_to_json(&column.sum_value, |v| json!(v.to_string())),
+                    "distinct_count": precision_to_json(&column.distinct_count, |v| json!(v)),
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "num_rows": precision_to_json(&statistics.num_rows, |v| json!(v)),
+            "total_byte_size": precision_to_json(&statistics.total_byte_size, |v| json!(v)),
+            "columns": columns,
+        })
+    }
+
+    /// `{"value": .., "exact": true|false}`, or `null` if absent
+    fn precision_to_json<T>(
+        precision: &Precision<T>,
+        value_to_json: impl Fn(&T) -> JsonValue,
+    ) -> JsonValue
+    where
+        T: fmt::Debug + Clone + PartialEq + Eq + PartialOrd,
+    {
+        match precision {
+            Precision::Exact(value) => {
+                json!({"value": value_to_json(value), "exact": true})
+            }
+            Precision::Inexact(value) => {
+                json!({"value": value_to_json(value), "exact": false})
+            }
+            Precision::Absent => JsonValue::Null,
+        }
+    }
+
+    fn metric_to_json(metric: &Metric) -> JsonValue {
+        let labels = metric
+            .labels()
+            .iter()
+            .map(|label| (label.name().to_string(), json!(label.value())))
+            .collect::<JsonMap<_, _>>();
+        json!({
+            "name": metric.value().name(),
+            "value": metric.value().as_usize(),
+            "display": metric.value().to_string(),
+            "partition": metric.partition(),
+            "labels": labels,
+        })
+    }
+}
+
 /// Trait for types which could have additional details when formatted in `Verbose` mode
 pub trait DisplayAs {
     /// Format according to `DisplayFormatType`, used when verbose representation looks
@@ -629,4 +895,47 @@ mod tests {
     fn test_display_when_stats_ok_with_show_stats() {
         test_stats_display(TestStatsExecPlan::Ok, false);
     }
+
+    #[cfg(feature = "explain_json")]
+    #[test]
+    fn test_display_json() {
+        use arrow_schema::{DataType, Field, Schema};
+        use serde_json::json;
+
+        use crate::empty::EmptyExec;
+
+        let schema = Schema::new(vec![Field::new("i", DataType::Int32, false)]);
+        let exec = EmptyExec::new(Arc::new(schema));
+        let display = DisplayableExecutionPlan::new(&exec)
+            .set_show_statistics(true)
+            .set_show_schema(true);
+
+        let actual: serde_json::Value =
+            serde_json::from_str(&display.json(false).to_string()).unwrap();
+        let expected = json!({
+            "operator": "EmptyExec",
+            "details": "EmptyExec",
+            "properties": {
+                "partitioning": "UnknownPartitioning(1)",
+                "output_partitions": 1,
+                "ordering": null,
+                "emission_type": "incremental",
+                "boundedness": "bounded",
+            },
+            "statistics": {
+                "num_rows": {"value": 0, "exact": true},
+                "total_byte_size": {"value": 0, "exact": true},
+                "columns": [{
+                    "null_count": {"value": 0, "exact": true},
+                    "min_value": null,
+                    "max_value": null,
+                    "sum_value": null,
+                    "distinct_count": null,
+                }],
+            },
+            "schema": [{"name": "i", "data_type": "Int32", "nullable": false}],
+            "children": [],
+        });
+        assert_eq!(actual, expected);
+    }
 }
@@ -0,0 +1,826 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Execution plan for modifying existing rows through [`DataMutation`]s
+//! (`DELETE` and `UPDATE` statements), and helpers for `MERGE` and
+//! `REPLACE INTO` statements
+
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use super::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, PlanProperties,
+    SendableRecordBatchStream,
+};
+use crate::execution_plan::{execute_stream, Boundedness, EmissionType};
+use crate::insert::{make_count_batch, make_count_schema};
+use crate::metrics::MetricsSet;
+use crate::stream::RecordBatchStreamAdapter;
+
+use arrow::array::{Array, BooleanArray, BooleanBuilder};
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{and, filter_record_batch, not, prep_null_mask_filter};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use arrow::row::{OwnedRow, RowConverter, Rows, SortField};
+use datafusion_common::cast::{as_uint64_array, as_uint8_array};
+use datafusion_common::{
+    exec_err, internal_err, plan_err, Constraint, Constraints, Result,
+};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+use datafusion_expr::dml::{MergeAction, MERGE_ACTION_COLUMN, MERGE_TARGET_ROW_COLUMN};
+use datafusion_physical_expr::{EquivalenceProperties, PhysicalExpr};
+
+use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
+
+/// `DataMutation` implements modifying or removing the existing rows
+/// of a user defined destination, such as a table.
+///
+/// The `Display` impl is used to format the mutation for explain plan
+/// output.
+#[async_trait]
+pub trait DataMutation: DisplayAs + Debug + Send + Sync {
+    /// Returns the data mutation as [`Any`](std::any::Any) so that it can be
+    /// downcast to a specific implementation.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Return a snapshot of the [MetricsSet] for this
+    /// [DataMutation].
+    ///
+    /// See [ExecutionPlan::metrics()] for more details
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
+
+    /// Applies the mutation, returns the number of rows affected
+    ///
+    /// If the [`DataMutationExec`] has a selection plan, only the
+    /// `selected` rows may be modified, in addition to any predicate
+    /// of the mutation itself.
+    ///
+    /// This method will be called exactly once during each DML
+    /// statement. Thus prior to return, the mutation should do any
+    /// commit or rollback required.
+    async fn apply(
+        &self,
+        context: &Arc<TaskContext>,
+        selected: Option<Arc<SelectedRows>>,
+    ) -> Result<u64>;
+}
+
+/// Execution plan for applying a [`DataMutation`]
+///
+/// The rows to modify may be restricted by a selection plan, such as the
+/// semi or anti join of a decorrelated subquery predicate, which is executed
+/// before the mutation is applied. See [`SelectedRows`].
+///
+/// Returns a single row with the number of rows affected
+#[derive(Clone)]
+pub struct DataMutationExec {
+    /// Mutation to apply
+    mutation: Arc<dyn DataMutation>,
+    /// Plan producing the rows to modify, if restricted
+    selection: Option<Arc<dyn ExecutionPlan>>,
+    /// Schema describing the structure of the output data.
+    count_schema: SchemaRef,
+    cache: PlanProperties,
+}
+
+impl Debug for DataMutationExec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DataMutationExec schema: {:?}", self.count_schema)
+    }
+}
+
+impl DataMutationExec {
+    /// Create a plan to apply `mutation`
+    pub fn new(mutation: Arc<dyn DataMutation>) -> Self {
+        let count_schema = make_count_schema();
+        let cache = Self::compute_properties(Arc::clone(&count_schema));
+        Self {
+            mutation,
+            selection: None,
+            count_schema,
+            cache,
+        }
+    }
+
+    /// Only modify the rows produced by `selection`, see [`SelectedRows`]
+    pub fn with_selection(mut self, selection: Option<Arc<dyn ExecutionPlan>>) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Returns the mutation
+    pub fn mutation(&self) -> &dyn DataMutation {
+        self.mutation.as_ref()
+    }
+
+    /// Returns the plan producing the rows to modify, if any
+    pub fn selection(&self) -> Option<&Arc<dyn ExecutionPlan>> {
+        self.selection.as_ref()
+    }
+
+    fn compute_properties(schema: SchemaRef) -> PlanProperties {
+        PlanProperties::new(
+            EquivalenceProperties::new(schema),
+            Partitioning::UnknownPartitioning(1),
+            EmissionType::Final,
+            Boundedness::Bounded,
+        )
+    }
+}
+
+impl DisplayAs for DataMutationExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "DataMutationExec: mutation=")?;
+                self.mutation.fmt_as(t, f)
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for DataMutationExec {
+    fn name(&self) -> &'static str {
+        "DataMutationExec"
+    }
+
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        self.selection.iter().collect()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if children.len() != self.selection.iter().len() {
+            return internal_err!("DataMutationExec wrong number of children");
+        }
+        let mut exec = self.as_ref().clone();
+        exec.selection = children.into_iter().next();
+        Ok(Arc::new(exec))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return internal_err!("DataMutationExec can only be called on partition 0!");
+        }
+
+        let count_schema = Arc::clone(&self.count_schema);
+        let mutation = Arc::clone(&self.mutation);
+        let selection = self.selection.clone();
+
+        let stream = futures::stream::once(async move {
+            let selected = match selection {
+                Some(selection) => {
+                    Some(Arc::new(SelectedRows::try_new(selection, &context).await?))
+                }
+                None => None,
+            };
+            mutation
+                .apply(&context, selected)
+                .await
+                .map(make_count_batch)
+        })
+        .boxed();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            count_schema,
+            stream,
+        )))
+    }
+
+    /// Returns the metrics of the underlying [DataMutation]
+    fn metrics(&self) -> Option<MetricsSet> {
+        self.mutation.metrics()
+    }
+}
+
+/// The rows produced by the selection plan of a [`DataMutationExec`]
+///
+/// The selection plan produces columns of the modified table, with the
+/// same names. Rows are identified by the values of these columns, so rows
+/// with equal values are either all selected or none of them, and `NULL`
+/// values are equal to each other. The selection plan must therefore
+/// produce every column its predicates depend on.
+#[derive(Debug)]
+pub struct SelectedRows {
+    /// Names of the columns identifying the rows
+    columns: Vec<String>,
+    converter: RowConverter,
+    rows: HashSet<OwnedRow>,
+    /// Memory used by `rows`
+    reservation: MemoryReservation,
+}
+
+impl SelectedRows {
+    /// Executes `selection` and collects the distinct rows it produces
+    pub async fn try_new(
+        selection: Arc<dyn ExecutionPlan>,
+        context: &Arc<TaskContext>,
+    ) -> Result<Self> {
+        let schema = selection.schema();
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect();
+        let converter = RowConverter::new(
+            schema
+                .fields()
+                .iter()
+                .map(|field| SortField::new(field.data_type().clone()))
+                .collect(),
+        )?;
+        let mut reservation =
+            MemoryConsumer::new("DataMutationExec").register(context.memory_pool());
+
+        let mut rows = HashSet::new();
+        let mut batches = execute_stream(selection, Arc::clone(context))?;
+        while let Some(batch) = batches.try_next().await? {
+            for row in converter.convert_columns(batch.columns())?.iter() {
+                let size = row.as_ref().len();
+                if rows.insert(row.owned()) {
+                    reservation.try_grow(size)?;
+                }
+            }
+        }
+        Ok(Self {
+            columns,
+            converter,
+            rows,
+            reservation,
+        })
+    }
+
+    /// Returns a mask that is `true` for the selected rows of `batch`, a
+    /// batch of rows of the modified table
+    pub fn contains(&self, batch: &RecordBatch) -> Result<BooleanArray> {
+        let schema = batch.schema();
+        let arrays = self
+            .columns
+            .iter()
+            .map(|name| Ok(Arc::clone(batch.column(schema.index_of(name)?))))
+            .collect::<Result<Vec<_>>>()?;
+        let rows = self.converter.convert_columns(&arrays)?;
+        Ok(rows
+            .iter()
+            .map(|row| Some(self.rows.contains(&row.owned())))
+            .collect())
+    }
+
+    /// Returns the number of distinct selected rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns true if no row is selected
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the memory reserved for the selected rows, in bytes
+    pub fn size(&self) -> usize {
+        self.reservation.size()
+    }
+}
+
+/// Evaluates `predicate` against `batch`, returning a mask that is `true`
+/// for the rows the predicate selects that are also `selected`. Rows for
+/// which the predicate evaluates to `NULL` are not selected.
+///
+/// A `None` predicate selects every row.
+fn selection_mask(
+    batch: &RecordBatch,
+    predicate: Option<&Arc<dyn PhysicalExpr>>,
+    selected: Option<&SelectedRows>,
+) -> Result<BooleanArray> {
+    let mask = match predicate {
+        None => BooleanArray::from(vec![true; batch.num_rows()]),
+        Some(predicate) => {
+            let result = predicate.evaluate(batch)?.into_array(batch.num_rows())?;
+            let Some(mask) = result.as_any().downcast_ref::<BooleanArray>() else {
+                return internal_err!(
+                    "Cannot create filter_array from non-boolean predicates, got {}",
+                    result.data_type()
+                );
+            };
+            match mask.null_count() {
+                0 => mask.clone(),
+                _ => prep_null_mask_filter(mask),
+            }
+        }
+    };
+    match selected {
+        Some(selected) => Ok(and(&mask, &selected.contains(batch)?)?),
+        None => Ok(mask),
+    }
+}
+
+/// Removes the rows of `batch` selected by `predicate` and `selected`,
+/// returning the remaining rows and the number of rows removed.
+pub fn delete_rows(
+    batch: &RecordBatch,
+    predicate: Option<&Arc<dyn PhysicalExpr>>,
+    selected: Option<&SelectedRows>,
+) -> Result<(RecordBatch, usize)> {
+    let mask = selection_mask(batch, predicate, selected)?;
+    let deleted = mask.true_count();
+    if deleted == 0 {
+        return Ok((batch.clone(), 0));
+    }
+    let remaining = filter_record_batch(batch, &not(&mask)?)?;
+    Ok((remaining, deleted))
+}
+
+/// Replaces the values of the rows of `batch` selected by `predicate` and
+/// `selected`, returning the modified batch and the number of rows updated.
+///
+/// `assignments` holds the index of each modified column together with
+/// the expression computing its new value, which is evaluated against
+/// the original `batch`.
+pub fn update_rows(
+    batch: &RecordBatch,
+    predicate: Option<&Arc<dyn PhysicalExpr>>,
+    selected: Option<&SelectedRows>,
+    assignments: &[(usize, Arc<dyn PhysicalExpr>)],
+) -> Result<(RecordBatch, usize)> {
+    let mask = selection_mask(batch, predicate, selected)?;
+    let updated = mask.true_count();
+    if updated == 0 {
+        return Ok((batch.clone(), 0));
+    }
+    let mut columns = batch.columns().to_vec();
+    for (index, expr) in assignments {
+        let new_values = expr.evaluate(batch)?.into_array(batch.num_rows())?;
+        columns[*index] = zip(&mask, &new_values, batch.column(*index))?;
+    }
+    let updated_batch = RecordBatch::try_new(batch.schema(), columns)?;
+    Ok((updated_batch, updated))
+}
+
+/// Returns the schema of the input of a `MERGE` statement into a table
+/// with the schema `table_schema`: the columns of the table followed by
+/// the [`MERGE_ACTION_COLUMN`] and [`MERGE_TARGET_ROW_COLUMN`] columns.
+pub fn merge_input_schema(table_schema: &Schema) -> SchemaRef {
+    let mut fields = table_schema.fields().to_vec();
+    fields.push(Arc::new(Field::new(
+        MERGE_ACTION_COLUMN,
+        DataType::UInt8,
+        true,
+    )));
+    fields.push(Arc::new(Field::new(
+        MERGE_TARGET_ROW_COLUMN,
+        DataType::UInt64,
+        true,
+    )));
+    Arc::new(Schema::new(fields))
+}
+
+/// Validates the rows produced by the input of a `MERGE` statement and
+/// returns the rows making up the contents of the table after the merge.
+///
+/// `input` produces rows as described in `TableProvider::merge_into`.
+/// The returned stream produces the rows of `input` that are not deleted,
+/// with the schema `table_schema`. The number of rows inserted, updated
+/// or deleted is added to `affected` as the stream is consumed.
+///
+/// The stream returns an error if a row of the table is matched by more
+/// than one row of the source relation and modified or deleted.
+pub fn merge_rows(
+    input: SendableRecordBatchStream,
+    table_schema: SchemaRef,
+    affected: Arc<AtomicU64>,
+) -> Result<SendableRecordBatchStream> {
+    let input_schema = input.schema();
+    let action_index = input_schema.index_of(MERGE_ACTION_COLUMN)?;
+    let target_row_index = input_schema.index_of(MERGE_TARGET_ROW_COLUMN)?;
+    let num_columns = table_schema.fields().len();
+    if action_index < num_columns || target_row_index < num_columns {
+        return internal_err!("Unexpected input schema for MERGE: {input_schema}");
+    }
+
+    // Action applied to each row of the table matched so far
+    let mut matched = HashMap::<u64, MergeAction>::new();
+    let output_schema = Arc::clone(&table_schema);
+    let stream = input.map(move |batch| {
+        let batch = batch?;
+        let actions = as_uint8_array(batch.column(action_index))?;
+        let target_rows = as_uint64_array(batch.column(target_row_index))?;
+        if actions.null_count() > 0 {
+            return internal_err!("Unexpected NULL action in MERGE input");
+        }
+
+        let mut keep = BooleanBuilder::with_capacity(batch.num_rows());
+        for (action, target_row) in actions.values().iter().zip(target_rows) {
+            let Some(action) = MergeAction::from_u8(*action) else {
+                return internal_err!("Unexpected action {action} in MERGE input");
+            };
+            if let Some(target_row) = target_row {
+                match matched.insert(target_row, action) {
+                    None => {}
+                    // A row matched several times but left unchanged is kept once
+                    Some(MergeAction::Keep) if action == MergeAction::Keep => {
+                        keep.append_value(false);
+                        continue;
+                    }
+                    Some(_) => {
+                        return exec_err!(
+                            "MERGE statement attempted to modify a target row \
+                            matched by more than one source row"
+                        );
+                    }
+                }
+            }
+            if action != MergeAction::Keep {
+                affected.fetch_add(1, Ordering::Relaxed);
+            }
+            keep.append_value(action != MergeAction::Delete);
+        }
+
+        let batch = filter_record_batch(&batch, &keep.finish())?;
+        let columns = batch.columns()[..num_columns].to_vec();
+        Ok(RecordBatch::try_new(Arc::clone(&output_schema), columns)?)
+    });
+    Ok(Box::pin(RecordBatchStreamAdapter::new(
+        table_schema,
+        stream,
+    )))
+}
+
+/// Returns an error if `constraints` has no primary key or unique
+/// constraint, as `REPLACE INTO` would then not replace any row.
+pub fn check_replace_keys(constraints: &Constraints) -> Result<()> {
+    let has_key = constraints.iter().any(|constraint| {
+        matches!(
+            constraint,
+            Constraint::PrimaryKey(_) | Constraint::Unique(_)
+        )
+    });
+    if !has_key {
+        return plan_err!(
+            "REPLACE INTO requires the table to have a primary key or unique constraint"
+        );
+    }
+    Ok(())
+}
+
+/// The keys of the rows inserted by a `REPLACE INTO` statement, used to
+/// remove the existing rows they replace.
+///
+/// A row is replaced by an inserted row if the values of any primary key
+/// or unique constraint of the table are equal in both rows. Keys with a
+/// `NULL` value never match.
+#[derive(Debug)]
+pub struct ReplacedKeys {
+    /// Columns of each key, their row converter, and the inserted keys
+    keys: Vec<(Vec<usize>, RowConverter, HashSet<OwnedRow>)>,
+}
+
+impl ReplacedKeys {
+    /// Collects the keys of the `inserted` rows for the keys of
+    /// `constraints`.
+    ///
+    /// Returns the inserted rows that are not themselves replaced by a
+    /// later inserted row.
+    pub fn try_new(
+        schema: &SchemaRef,
+        constraints: &Constraints,
+        inserted: &[RecordBatch],
+    ) -> Result<(Self, Vec<RecordBatch>)> {
+        let mut keys = constraints
+            .iter()
+            .filter_map(|constraint| match constraint {
+                Constraint::PrimaryKey(columns) | Constraint::Unique(columns) => {
+                    Some(columns)
+                }
+                Constraint::Check { .. } => None,
+            })
+            .map(|columns| {
+                let fields = columns
+                    .iter()
+                    .map(|i| SortField::new(schema.field(*i).data_type().clone()))
+                    .collect();
+                Ok((columns.clone(), RowConverter::new(fields)?, HashSet::new()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Later rows replace earlier ones, so visit the rows from last to
+        // first and only keep those whose keys were not seen yet
+        let mut kept = Vec::with_capacity(inserted.len());
+        for batch in inserted.iter().rev() {
+            let rows = keys
+                .iter()
+                .map(|(columns, converter, _)| key_rows(batch, columns, converter))
+                .collect::<Result<Vec<_>>>()?;
+            let mut keep = vec![true; batch.num_rows()];
+            for row in (0..batch.num_rows()).rev() {
+                keep[row] = rows.iter().zip(&keys).all(|((rows, valid), (.., seen))| {
+                    !valid[row] || !seen.contains(&rows.row(row).owned())
+                });
+                if keep[row] {
+                    for ((rows, valid), (.., seen)) in rows.iter().zip(keys.iter_mut()) {
+                        if valid[row] {
+                            seen.insert(rows.row(row).owned());
+                        }
+                    }
+                }
+            }
+            kept.push(filter_record_batch(batch, &BooleanArray::from(keep))?);
+        }
+        kept.reverse();
+        Ok((Self { keys }, kept))
+    }
+
+    /// Removes the rows of `batch` replaced by an inserted row, returning
+    /// the remaining rows and the number of rows removed.
+    pub fn remove_replaced(&self, batch: &RecordBatch) -> Result<(RecordBatch, usize)> {
+        if self.keys.is_empty() {
+            return Ok((batch.clone(), 0));
+        }
+        let rows = self
+            .keys
+            .iter()
+            .map(|(columns, converter, _)| key_rows(batch, columns, converter))
+            .collect::<Result<Vec<_>>>()?;
+        let replaced = (0..batch.num_rows())
+            .map(|row| {
+                rows.iter()
+                    .zip(&self.keys)
+                    .any(|((rows, valid), (.., inserted))| {
+                        valid[row] && inserted.contains(&rows.row(row).owned())
+                    })
+            })
+            .collect::<Vec<_>>();
+        let replaced = BooleanArray::from(replaced);
+        let removed = replaced.true_count();
+        if removed == 0 {
+            return Ok((batch.clone(), 0));
+        }
+        Ok((filter_record_batch(batch, &not(&replaced)?)?, removed))
+    }
+}
+
+/// Converts the values of the key `columns` of `batch` to rows, together
+/// with whether each row has no `NULL` key value
+pub(crate) fn key_rows(
+    batch: &RecordBatch,
+    columns: &[usize],
+    converter: &RowConverter,
+) -> Result<(Rows, Vec<bool>)> {
+    let arrays = columns
+        .iter()
+        .map(|i| Arc::clone(batch.column(*i)))
+        .collect::<Vec<_>>();
+    let valid = (0..batch.num_rows())
+        .map(|row| arrays.iter().all(|array| array.is_valid(row)))
+        .collect();
+    Ok((converter.convert_columns(&arrays)?, valid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::{binary, col, lit};
+
+    use crate::common::collect;
+    use crate::memory::MemorySourceConfig;
+
+    use arrow::array::{ArrayRef, Int32Array, StringArray, UInt64Array, UInt8Array};
+    use datafusion_common::cast::{as_int32_array, as_string_array};
+    use datafusion_expr::Operator;
+
+    fn test_batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3), Some(4)]))
+                    as ArrayRef,
+                Arc::new(StringArray::from(vec!["w", "x", "y", "z"])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn delete_rows_skips_null_predicate_results() -> Result<()> {
+        let batch = test_batch();
+        let schema = batch.schema();
+        let predicate = binary(col("a", &schema)?, Operator::Gt, lit(2), &schema)?;
+
+        let (remaining, deleted) = delete_rows(&batch, Some(&predicate), None)?;
+        assert_eq!(deleted, 2);
+        assert_eq!(
+            as_int32_array(remaining.column(0))?,
+            &Int32Array::from(vec![Some(1), None])
+        );
+
+        let (remaining, deleted) = delete_rows(&batch, None, None)?;
+        assert_eq!(deleted, 4);
+        assert_eq!(remaining.num_rows(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn update_rows_only_modifies_selected_rows() -> Result<()> {
+        let batch = test_batch();
+        let schema = batch.schema();
+        let predicate = binary(col("a", &schema)?, Operator::Lt, lit(4), &schema)?;
+        let assignments = vec![(1, lit("updated"))];
+
+        let (updated_batch, updated) =
+            update_rows(&batch, Some(&predicate), None, &assignments)?;
+        assert_eq!(updated, 2);
+        assert_eq!(updated_batch.column(0), batch.column(0));
+        assert_eq!(
+            as_string_array(updated_batch.column(1))?,
+            &StringArray::from(vec!["updated", "x", "updated", "z"])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn selected_rows_restrict_modified_rows() -> Result<()> {
+        let batch = test_batch();
+        let schema = batch.schema();
+        let selection_schema =
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let selection = RecordBatch::try_new(
+            Arc::clone(&selection_schema),
+            vec![Arc::new(Int32Array::from(vec![
+                Some(1),
+                None,
+                Some(4),
+                Some(1),
+            ]))],
+        )?;
+        let selection =
+            MemorySourceConfig::try_new_exec(&[vec![selection]], selection_schema, None)?;
+        let context = Arc::new(TaskContext::default());
+        let selected = SelectedRows::try_new(selection, &context).await?;
+        assert_eq!(selected.len(), 3);
+
+        // NULL values of the selection select the rows with NULL values
+        let (remaining, deleted) = delete_rows(&batch, None, Some(&selected))?;
+        assert_eq!(deleted, 3);
+        assert_eq!(
+            as_string_array(remaining.column(1))?,
+            &StringArray::from(vec!["y"])
+        );
+
+        let predicate = binary(col("a", &schema)?, Operator::Lt, lit(4), &schema)?;
+        let assignments = vec![(1, lit("updated"))];
+        let (updated_batch, updated) =
+            update_rows(&batch, Some(&predicate), Some(&selected), &assignments)?;
+        assert_eq!(updated, 1);
+        assert_eq!(
+            as_string_array(updated_batch.column(1))?,
+            &StringArray::from(vec!["updated", "x", "y", "z"])
+        );
+        Ok(())
+    }
+
+    fn merge_input(
+        values: Vec<i32>,
+        actions: Vec<MergeAction>,
+        target_rows: Vec<Option<u64>>,
+    ) -> Result<(SchemaRef, SendableRecordBatchStream)> {
+        let table_schema =
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let input_schema = merge_input_schema(&table_schema);
+        let actions = actions.into_iter().map(|a| a as u8).collect::<Vec<_>>();
+        let batch = RecordBatch::try_new(
+            Arc::clone(&input_schema),
+            vec![
+                Arc::new(Int32Array::from(values)) as ArrayRef,
+                Arc::new(UInt8Array::from(actions)),
+                Arc::new(UInt64Array::from(target_rows)),
+            ],
+        )?;
+        let stream = futures::stream::iter(vec![Ok(batch)]);
+        let stream = Box::pin(RecordBatchStreamAdapter::new(input_schema, stream));
+        Ok((table_schema, stream))
+    }
+
+    #[tokio::test]
+    async fn merge_rows_applies_actions() -> Result<()> {
+        let (table_schema, input) = merge_input(
+            vec![1, 2, 2, 3, 4, 5],
+            vec![
+                MergeAction::Keep,
+                MergeAction::Keep,
+                MergeAction::Keep,
+                MergeAction::Update,
+                MergeAction::Delete,
+                MergeAction::Insert,
+            ],
+            vec![None, Some(1), Some(1), Some(2), Some(3), None],
+        )?;
+        let affected = Arc::new(AtomicU64::new(0));
+        let output = collect(merge_rows(
+            input,
+            Arc::clone(&table_schema),
+            Arc::clone(&affected),
+        )?)
+        .await?;
+        assert_eq!(affected.load(Ordering::Relaxed), 3);
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].schema(), table_schema);
+        assert_eq!(
+            as_int32_array(output[0].column(0))?,
+            &Int32Array::from(vec![1, 2, 3, 5])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn merge_rows_rejects_modifying_row_matched_twice() -> Result<()> {
+        let (table_schema, input) = merge_input(
+            vec![1, 2],
+            vec![MergeAction::Keep, MergeAction::Update],
+            vec![Some(1), Some(1)],
+        )?;
+        let affected = Arc::new(AtomicU64::new(0));
+        let err = collect(merge_rows(input, table_schema, affected)?)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("matched by more than one source row"));
+        Ok(())
+    }
+
+    #[test]
+    fn replaced_keys_remove_rows_with_same_key() -> Result<()> {
+        let batch = test_batch();
+        let schema = batch.schema();
+        let constraints = Constraints::new_unverified(vec![
+            Constraint::Unique(vec![0]),
+            Constraint::Unique(vec![1]),
+        ]);
+        let inserted = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(3), None, Some(5), Some(5)]))
+                    as ArrayRef,
+                Arc::new(StringArray::from(vec!["a", "x", "b", "c"])),
+            ],
+        )?;
+
+        // the inserted row (5, 'b') is replaced by the later row (5, 'c')
+        let (replaced_keys, inserted) =
+            ReplacedKeys::try_new(&schema, &constraints, &[inserted])?;
+        assert_eq!(
+            as_string_array(inserted[0].column(1))?,
+            &StringArray::from(vec!["a", "x", "c"])
+        );
+
+        // NULL keys never match
+        let (remaining, removed) = replaced_keys.remove_replaced(&batch)?;
+        assert_eq!(removed, 2);
+        assert_eq!(
+            as_string_array(remaining.column(1))?,
+            &StringArray::from(vec!["w", "z"])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,801 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the gap filling plan, used to implement `ORDER BY ... WITH FILL`
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use super::{DisplayAs, ExecutionPlanProperties, PlanProperties};
+use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use crate::{
+    DisplayFormatType, Distribution, ExecutionPlan, RecordBatchStream,
+    SendableRecordBatchStream,
+};
+
+use arrow::array::{Array, ArrayRef};
+use arrow::compute::interleave;
+use arrow::datatypes::{DataType, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{exec_err, internal_err, plan_err, Result, ScalarValue};
+use datafusion_execution::TaskContext;
+use datafusion_expr::FillStrategy;
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_expr::{EquivalenceProperties, LexOrdering, PhysicalSortExpr};
+use datafusion_physical_expr_common::sort_expr::LexRequirement;
+
+use futures::{Stream, StreamExt};
+
+/// Inserts rows for the values of a column missing from its sorted input.
+///
+/// The input must be sorted by `partition_by` and then `fill_expr`, every
+/// distinct value of `partition_by` is a separate series. Within a series,
+/// rows are inserted for the values between consecutive input rows that are
+/// `step` apart, starting at `from` (inclusive) and up to `to` (exclusive) if
+/// they are set. Rows with a NULL fill value are passed through unchanged.
+///
+/// The columns of the inserted rows are filled according to their
+/// [`FillStrategy`], except the fill and partition columns which are set to
+/// the generated value and the series.
+#[derive(Debug, Clone)]
+pub struct GapFillExec {
+    /// Input execution plan
+    input: Arc<dyn ExecutionPlan>,
+    /// The columns identifying a series, in the sort order of the input
+    partition_by: LexOrdering,
+    /// The column whose missing values are inserted
+    fill_expr: PhysicalSortExpr,
+    /// The first value of every series
+    from: Option<ScalarValue>,
+    /// The value every series is filled up to, exclusive
+    to: Option<ScalarValue>,
+    /// The distance between consecutive values
+    step: ScalarValue,
+    /// How each column of the inserted rows is filled
+    strategies: Vec<FillStrategy>,
+    /// The output schema
+    schema: SchemaRef,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl GapFillExec {
+    /// Create a new [`GapFillExec`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        partition_by: LexOrdering,
+        fill_expr: PhysicalSortExpr,
+        from: Option<ScalarValue>,
+        to: Option<ScalarValue>,
+        step: ScalarValue,
+        strategies: Vec<FillStrategy>,
+        schema: SchemaRef,
+    ) -> Result<Self> {
+        for sort_expr in partition_by.iter().chain(std::iter::once(&fill_expr)) {
+            if sort_expr.expr.as_any().downcast_ref::<Column>().is_none() {
+                return plan_err!(
+                    "GapFillExec requires the sort expressions to be columns, got {}",
+                    sort_expr.expr
+                );
+            }
+        }
+        if strategies.len() != schema.fields().len() {
+            return plan_err!(
+                "GapFillExec expects {} fill strategies, got {}",
+                schema.fields().len(),
+                strategies.len()
+            );
+        }
+        if step.is_null() {
+            return plan_err!("WITH FILL STEP must not be NULL");
+        }
+
+        let cache = Self::compute_properties(
+            &input,
+            Arc::clone(&schema),
+            Self::ordering(&partition_by, &fill_expr),
+        );
+        Ok(Self {
+            input,
+            partition_by,
+            fill_expr,
+            from,
+            to,
+            step,
+            strategies,
+            schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// The ordering of the input and output, the partition columns followed
+    /// by the fill column
+    fn ordering(partition_by: &LexOrdering, fill_expr: &PhysicalSortExpr) -> LexOrdering {
+        let mut ordering = partition_by.clone();
+        ordering.push(fill_expr.clone());
+        ordering
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        input: &Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+        ordering: LexOrdering,
+    ) -> PlanProperties {
+        // The inserted rows keep the ordering, but not any other equivalences
+        // or constants of the input
+        PlanProperties::new(
+            EquivalenceProperties::new_with_orderings(schema, &[ordering]),
+            input.output_partitioning().to_owned(),
+            input.pipeline_behavior(),
+            input.boundedness(),
+        )
+    }
+
+    /// Input execution plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// The columns identifying a series
+    pub fn partition_by(&self) -> &LexOrdering {
+        &self.partition_by
+    }
+
+    /// The column whose missing values are inserted
+    pub fn fill_expr(&self) -> &PhysicalSortExpr {
+        &self.fill_expr
+    }
+
+    /// The first value of every series
+    pub fn from(&self) -> Option<&ScalarValue> {
+        self.from.as_ref()
+    }
+
+    /// The value every series is filled up to, exclusive
+    pub fn to(&self) -> Option<&ScalarValue> {
+        self.to.as_ref()
+    }
+
+    /// The distance between consecutive values
+    pub fn step(&self) -> &ScalarValue {
+        &self.step
+    }
+
+    /// How each column of the inserted rows is filled
+    pub fn strategies(&self) -> &[FillStrategy] {
+        &self.strategies
+    }
+}
+
+impl DisplayAs for GapFillExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "GapFillExec: ")?;
+                if !self.partition_by.is_empty() {
+                    write!(f, "partition_by=[{}], ", self.partition_by)?;
+                }
+                write!(f, "fill={}", self.fill_expr)?;
+                if let Some(from) = &self.from {
+                    write!(f, ", from={from}")?;
+                }
+                if let Some(to) = &self.to {
+                    write!(f, ", to={to}")?;
+                }
+                write!(f, ", step={}", self.step)?;
+                let interpolations = self
+                    .schema
+                    .fields()
+                    .iter()
+                    .zip(&self.strategies)
+                    .filter(|(_, strategy)| **strategy != FillStrategy::Null)
+                    .map(|(field, strategy)| format!("{}:{strategy}", field.name()))
+                    .collect::<Vec<_>>();
+                if !interpolations.is_empty() {
+                    write!(f, ", interpolate=[{}]", interpolations.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for GapFillExec {
+    fn name(&self) -> &'static str {
+        "GapFillExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.input]
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition]
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<LexRequirement>> {
+        let ordering = Self::ordering(&self.partition_by, &self.fill_expr);
+        vec![Some(LexRequirement::from(ordering))]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        vec![false]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(GapFillExec::try_new(
+            Arc::clone(&children[0]),
+            self.partition_by.clone(),
+            self.fill_expr.clone(),
+            self.from.clone(),
+            self.to.clone(),
+            self.step.clone(),
+            self.strategies.clone(),
+            Arc::clone(&self.schema),
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let input = self.input.execute(partition, context)?;
+        let column_index = |sort_expr: &PhysicalSortExpr| {
+            sort_expr
+                .expr
+                .as_any()
+                .downcast_ref::<Column>()
+                .map(|c| c.index())
+                .unwrap_or_default()
+        };
+        let filler = GapFiller {
+            partition_indices: self.partition_by.iter().map(column_index).collect(),
+            fill_index: column_index(&self.fill_expr),
+            descending: self.fill_expr.options.descending,
+            from: self.from.clone(),
+            to: self.to.clone(),
+            step: self.step.clone(),
+            strategies: self.strategies.clone(),
+            schema: Arc::clone(&self.schema),
+        };
+        Ok(Box::pin(GapFillStream {
+            input,
+            filler,
+            previous: None,
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
+
+/// Generates the rows missing from the series of a sorted input
+#[derive(Debug)]
+struct GapFiller {
+    partition_indices: Vec<usize>,
+    fill_index: usize,
+    descending: bool,
+    from: Option<ScalarValue>,
+    to: Option<ScalarValue>,
+    step: ScalarValue,
+    strategies: Vec<FillStrategy>,
+    schema: SchemaRef,
+}
+
+/// A row of a [`RecordBatch`]
+type RowRef<'a> = (&'a RecordBatch, usize);
+
+/// The rows of an output batch, as indices into the input batch (0) or the
+/// generated rows (1), and the generated rows themselves
+#[derive(Default)]
+struct FillOutput {
+    indices: Vec<(usize, usize)>,
+    rows: Vec<Vec<ScalarValue>>,
+}
+
+impl FillOutput {
+    fn push_input(&mut self, row: usize) {
+        self.indices.push((0, row));
+    }
+
+    fn push_generated(&mut self, row: Vec<ScalarValue>) {
+        self.indices.push((1, self.rows.len()));
+        self.rows.push(row);
+    }
+}
+
+impl GapFiller {
+    /// Fills the gaps of `batch`, given the last row of the previous batch
+    fn fill_batch(
+        &self,
+        batch: &RecordBatch,
+        previous: Option<&RecordBatch>,
+    ) -> Result<(RecordBatch, Option<RecordBatch>)> {
+        let fill_column = batch.column(self.fill_index);
+        let mut output = FillOutput::default();
+        let mut last: Option<RowRef> = previous.map(|batch| (batch, 0));
+        for row in 0..batch.num_rows() {
+            if fill_column.is_null(row) {
+                output.push_input(row);
+                continue;
+            }
+            let current = (batch, row);
+            match last {
+                Some(last) if self.same_series(last, current)? => {
+                    self.fill_between(last, current, &mut output)?;
+                }
+                _ => {
+                    if let Some(last) = last {
+                        self.fill_trailing(last, &mut output)?;
+                    }
+                    self.fill_leading(current, &mut output)?;
+                }
+            }
+            output.push_input(row);
+            last = Some(current);
+        }
+        let last = last.map(|(batch, row)| batch.slice(row, 1));
+
+        if output.rows.is_empty() {
+            let batch =
+                RecordBatch::try_new(Arc::clone(&self.schema), batch.columns().to_vec())?;
+            return Ok((batch, last));
+        }
+        let generated = self.generated_columns(output.rows)?;
+        let columns = batch
+            .columns()
+            .iter()
+            .zip(&generated)
+            .map(|(input, generated)| {
+                Ok(interleave(
+                    &[input.as_ref(), generated.as_ref()],
+                    &output.indices,
+                )?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let batch = RecordBatch::try_new(Arc::clone(&self.schema), columns)?;
+        Ok((batch, last))
+    }
+
+    /// Fills the gap after the last row of the input
+    fn finish(&self, previous: &RecordBatch) -> Result<Option<RecordBatch>> {
+        let mut output = FillOutput::default();
+        self.fill_trailing((previous, 0), &mut output)?;
+        if output.rows.is_empty() {
+            return Ok(None);
+        }
+        let columns = self.generated_columns(output.rows)?;
+        Ok(Some(RecordBatch::try_new(
+            Arc::clone(&self.schema),
+            columns,
+        )?))
+    }
+
+    /// Converts the generated rows into columns
+    fn generated_columns(&self, rows: Vec<Vec<ScalarValue>>) -> Result<Vec<ArrayRef>> {
+        let mut columns =
+            vec![Vec::with_capacity(rows.len()); self.schema.fields().len()];
+        for row in rows {
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+        columns
+            .into_iter()
+            .map(ScalarValue::iter_to_array)
+            .collect()
+    }
+
+    fn same_series(&self, left: RowRef, right: RowRef) -> Result<bool> {
+        for &index in &self.partition_indices {
+            if value_at(left, index)? != value_at(right, index)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Inserts the missing values between two rows of the same series
+    fn fill_between(
+        &self,
+        last: RowRef,
+        current: RowRef,
+        output: &mut FillOutput,
+    ) -> Result<()> {
+        let start = self.advance(&value_at(last, self.fill_index)?)?;
+        let end = value_at(current, self.fill_index)?;
+        for value in self.values(start, &end)? {
+            let row = self.generated_row(value, last, Some(last), Some(current))?;
+            output.push_generated(row);
+        }
+        Ok(())
+    }
+
+    /// Inserts the missing values from `from` before the first row of a series
+    fn fill_leading(&self, first: RowRef, output: &mut FillOutput) -> Result<()> {
+        let Some(from) = &self.from else {
+            return Ok(());
+        };
+        let end = value_at(first, self.fill_index)?;
+        for value in self.values(from.clone(), &end)? {
+            let row = self.generated_row(value, first, None, None)?;
+            output.push_generated(row);
+        }
+        Ok(())
+    }
+
+    /// Inserts the missing values after the last row of a series up to `to`
+    fn fill_trailing(&self, last: RowRef, output: &mut FillOutput) -> Result<()> {
+        let Some(to) = &self.to else {
+            return Ok(());
+        };
+        let start = self.advance(&value_at(last, self.fill_index)?)?;
+        for value in self.values(start, to)? {
+            let row = self.generated_row(value, last, Some(last), None)?;
+            output.push_generated(row);
+        }
+        Ok(())
+    }
+
+    /// Returns the values from `start` before `end` that are within the
+    /// `from` and `to` bounds
+    fn values(&self, start: ScalarValue, end: &ScalarValue) -> Result<Vec<ScalarValue>> {
+        let mut values = vec![];
+        let mut value = start;
+        while self.before(&value, end)?
+            && self
+                .to
+                .as_ref()
+                .map_or(Ok(true), |to| self.before(&value, to))?
+        {
+            let next = self.advance(&value)?;
+            if !self.before(&value, &next)? {
+                return exec_err!(
+                    "WITH FILL STEP {} does not advance the fill values from {value}",
+                    self.step
+                );
+            }
+            let after_from = match &self.from {
+                Some(from) => !self.before(&value, from)?,
+                None => true,
+            };
+            if after_from {
+                values.push(value);
+            }
+            value = next;
+        }
+        Ok(values)
+    }
+
+    /// Returns the next value in the sort order
+    fn advance(&self, value: &ScalarValue) -> Result<ScalarValue> {
+        if self.descending {
+            value.sub(&self.step)
+        } else {
+            value.add(&self.step)
+        }
+    }
+
+    /// Returns true if `left` comes before `right` in the sort order
+    fn before(&self, left: &ScalarValue, right: &ScalarValue) -> Result<bool> {
+        let Some(ordering) = left.partial_cmp(right) else {
+            return internal_err!("Cannot compare fill values {left} and {right}");
+        };
+        Ok(if self.descending {
+            ordering == Ordering::Greater
+        } else {
+            ordering == Ordering::Less
+        })
+    }
+
+    /// Creates the row for `value`, in the series of `series`, between the
+    /// rows `last` and `next` of the series if they exist
+    fn generated_row(
+        &self,
+        value: ScalarValue,
+        series: RowRef,
+        last: Option<RowRef>,
+        next: Option<RowRef>,
+    ) -> Result<Vec<ScalarValue>> {
+        let mut row = Vec::with_capacity(self.schema.fields().len());
+        for (index, field) in self.schema.fields().iter().enumerate() {
+            let data_type = field.data_type();
+            let cell = if index == self.fill_index {
+                value.clone()
+            } else if self.partition_indices.contains(&index) {
+                value_at(series, index)?
+            } else {
+                match (self.strategies[index], last, next) {
+                    (FillStrategy::Previous, Some(last), _) => value_at(last, index)?,
+                    (FillStrategy::Linear, Some(last), Some(next)) => {
+                        self.interpolate(&value, last, next, index, data_type)?
+                    }
+                    _ => ScalarValue::try_from(data_type)?,
+                }
+            };
+            row.push(cell);
+        }
+        Ok(row)
+    }
+
+    /// Linearly interpolates column `index` at fill value `value` between the
+    /// rows `last` and `next`
+    fn interpolate(
+        &self,
+        value: &ScalarValue,
+        last: RowRef,
+        next: RowRef,
+        index: usize,
+        data_type: &DataType,
+    ) -> Result<ScalarValue> {
+        let (Some(last_value), Some(next_value)) = (
+            as_f64(&value_at(last, index)?)?,
+            as_f64(&value_at(next, index)?)?,
+        ) else {
+            return ScalarValue::try_from(data_type);
+        };
+        let (Some(position), Some(last_position), Some(next_position)) = (
+            as_f64(value)?,
+            as_f64(&value_at(last, self.fill_index)?)?,
+            as_f64(&value_at(next, self.fill_index)?)?,
+        ) else {
+            return internal_err!("Fill values must not be NULL");
+        };
+        let fraction = (position - last_position) / (next_position - last_position);
+        let mut result = last_value + (next_value - last_value) * fraction;
+        if data_type.is_integer() {
+            result = result.round();
+        }
+        ScalarValue::Float64(Some(result)).cast_to(data_type)
+    }
+}
+
+/// Returns the value of column `index` of `row`
+fn value_at((batch, row): RowRef, index: usize) -> Result<ScalarValue> {
+    ScalarValue::try_from_array(batch.column(index), row)
+}
+
+/// Converts a numeric or temporal value to a float
+fn as_f64(value: &ScalarValue) -> Result<Option<f64>> {
+    let value = match value.data_type() {
+        DataType::Date32 | DataType::Date64 | DataType::Timestamp(_, _) => {
+            value.cast_to(&DataType::Int64)?
+        }
+        _ => value.clone(),
+    };
+    match value.cast_to(&DataType::Float64)? {
+        ScalarValue::Float64(value) => Ok(value),
+        other => internal_err!("Expected a Float64 value, got {other:?}"),
+    }
+}
+
+/// A stream that inserts the rows missing from its sorted input
+struct GapFillStream {
+    /// Input stream
+    input: SendableRecordBatchStream,
+    /// Generates the missing rows
+    filler: GapFiller,
+    /// The last row with a fill value seen so far
+    previous: Option<RecordBatch>,
+    /// Execution metrics
+    baseline_metrics: BaselineMetrics,
+}
+
+impl GapFillStream {
+    fn poll_next_impl(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        loop {
+            return Poll::Ready(match ready!(self.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    if batch.num_rows() == 0 {
+                        continue;
+                    }
+                    let timer = self.baseline_metrics.elapsed_compute().timer();
+                    let result = self.filler.fill_batch(&batch, self.previous.as_ref());
+                    timer.done();
+                    match result {
+                        Ok((batch, previous)) => {
+                            if previous.is_some() {
+                                self.previous = previous;
+                            }
+                            Some(Ok(batch))
+                        }
+                        Err(e) => Some(Err(e)),
+                    }
+                }
+                None => {
+                    let Some(previous) = self.previous.take() else {
+                        return Poll::Ready(None);
+                    };
+                    let timer = self.baseline_metrics.elapsed_compute().timer();
+                    let result = self.filler.finish(&previous);
+                    timer.done();
+                    result.transpose()
+                }
+                other => other,
+            });
+        }
+    }
+}
+
+impl Stream for GapFillStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.poll_next_impl(cx);
+        self.baseline_metrics.record_poll(poll)
+    }
+}
+
+impl RecordBatchStream for GapFillStream {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.filler.schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect;
+    use crate::memory::MemorySourceConfig;
+
+    use arrow::array::{Float64Array, Int64Array, StringArray};
+    use arrow::compute::SortOptions;
+    use arrow::datatypes::Schema;
+    use datafusion_common::assert_batches_eq;
+
+    fn sort_expr(name: &str, schema: &Schema) -> Result<PhysicalSortExpr> {
+        Ok(PhysicalSortExpr::new(
+            Arc::new(Column::new_with_schema(name, schema)?),
+            SortOptions::default(),
+        ))
+    }
+
+    async fn run(
+        batches: Vec<RecordBatch>,
+        partition_by: &[&str],
+        from: Option<i64>,
+        to: Option<i64>,
+        strategies: Vec<FillStrategy>,
+    ) -> Result<Vec<RecordBatch>> {
+        let schema = batches[0].schema();
+        let input =
+            MemorySourceConfig::try_new_exec(&[batches], Arc::clone(&schema), None)?;
+        let partition_by = partition_by
+            .iter()
+            .map(|name| sort_expr(name, &schema))
+            .collect::<Result<LexOrdering>>()?;
+        let output_schema = Arc::new(Schema::new(
+            schema
+                .fields()
+                .iter()
+                .map(|f| f.as_ref().clone().with_nullable(true))
+                .collect::<Vec<_>>(),
+        ));
+        let exec = GapFillExec::try_new(
+            input,
+            partition_by,
+            sort_expr("t", &schema)?,
+            from.map(|v| ScalarValue::Int64(Some(v))),
+            to.map(|v| ScalarValue::Int64(Some(v))),
+            ScalarValue::Int64(Some(1)),
+            strategies,
+            output_schema,
+        )?;
+        let task_ctx = Arc::new(TaskContext::default());
+        collect(Arc::new(exec), task_ctx).await
+    }
+
+    fn batch(series: Vec<&str>, t: Vec<Option<i64>>, v: Vec<f64>) -> RecordBatch {
+        RecordBatch::try_from_iter(vec![
+            ("s", Arc::new(StringArray::from(series)) as ArrayRef),
+            ("t", Arc::new(Int64Array::from(t)) as ArrayRef),
+            ("v", Arc::new(Float64Array::from(v)) as ArrayRef),
+        ])
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn fill_across_batches() -> Result<()> {
+        let batches = vec![
+            batch(vec!["a", "a"], vec![Some(1), Some(3)], vec![1.0, 3.0]),
+            batch(vec!["a"], vec![Some(6)], vec![9.0]),
+        ];
+        let strategies =
+            vec![FillStrategy::Null, FillStrategy::Null, FillStrategy::Linear];
+        let results = run(batches, &[], None, None, strategies).await?;
+        let expected = [
+            "+---+---+-----+",
+            "| s | t | v   |",
+            "+---+---+-----+",
+            "| a | 1 | 1.0 |",
+            "|   | 2 | 2.0 |",
+            "| a | 3 | 3.0 |",
+            "|   | 4 | 5.0 |",
+            "|   | 5 | 7.0 |",
+            "| a | 6 | 9.0 |",
+            "+---+---+-----+",
+        ];
+        assert_batches_eq!(expected, &results);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fill_series_with_bounds() -> Result<()> {
+        let batches = vec![batch(
+            vec!["a", "b", "b", "b"],
+            vec![Some(2), Some(1), Some(3), None],
+            vec![2.0, 1.0, 3.0, 0.0],
+        )];
+        let strategies = vec![
+            FillStrategy::Null,
+            FillStrategy::Null,
+            FillStrategy::Previous,
+        ];
+        let results = run(batches, &["s"], Some(1), Some(4), strategies).await?;
+        let expected = [
+            "+---+---+-----+",
+            "| s | t | v   |",
+            "+---+---+-----+",
+            "| a | 1 |     |",
+            "| a | 2 | 2.0 |",
+            "| a | 3 | 2.0 |",
+            "| b | 1 | 1.0 |",
+            "| b | 2 | 1.0 |",
+            "| b | 3 | 3.0 |",
+            "| b |   | 0.0 |",
+            "+---+---+-----+",
+        ];
+        assert_batches_eq!(expected, &results);
+        Ok(())
+    }
+}
@@ -15,9 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! Execution plan for writing data to [`DataSink`]s
+//! Execution plans for writing data to [`DataSink`]s and validating the
+//! written rows against the constraints of a table
 
 use std::any::Any;
+use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -26,21 +28,25 @@ use super::{
     execute_input_stream, DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning,
     PlanProperties, SendableRecordBatchStream,
 };
+use crate::dml::key_rows;
+use crate::execution_plan::execute_stream;
 use crate::metrics::MetricsSet;
 use crate::stream::RecordBatchStreamAdapter;
 use crate::ExecutionPlanProperties;
 
 use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
+use arrow::row::{OwnedRow, RowConverter, SortField};
 use arrow_array::{ArrayRef, UInt64Array};
 use arrow_schema::{DataType, Field, Schema};
-use datafusion_common::{internal_err, Result};
+use datafusion_common::cast::as_boolean_array;
+use datafusion_common::{exec_err, internal_err, DataFusionError, Result, ScalarValue};
 use datafusion_execution::TaskContext;
-use datafusion_physical_expr::{Distribution, EquivalenceProperties};
+use datafusion_physical_expr::{Distribution, EquivalenceProperties, PhysicalExpr};
 
 use async_trait::async_trait;
 use datafusion_physical_expr_common::sort_expr::LexRequirement;
-use futures::StreamExt;
+use futures::{StreamExt, TryStreamExt};
 
 /// `DataSink` implements writing streams of [`RecordBatch`]es to
 /// user defined destinations.
@@ -251,6 +257,371 @@ impl ExecutionPlan for DataSinkExec {
     }
 }
 
+/// A constraint of a table validated by [`ConstraintValidationExec`]
+#[derive(Debug, Clone)]
+pub enum ValidatedConstraint {
+    /// The column with the given index must not contain `NULL` values
+    NotNull(usize),
+    /// The expression must not evaluate to `false` for any row
+    Check {
+        /// Name of the constraint, if one was given
+        name: Option<String>,
+        /// SQL text of the expression, used in error messages
+        sql: String,
+        /// The expression, evaluated against the inserted rows
+        expr: Arc<dyn PhysicalExpr>,
+    },
+    /// Columns with the given indices must be jointly unique. Rows with a
+    /// `NULL` value in any of the columns are not checked.
+    Unique {
+        /// Indices of the key columns
+        columns: Vec<usize>,
+        /// Whether the key is the primary key of the table
+        primary_key: bool,
+    },
+}
+
+/// Execution plan validating the rows inserted into a table against the
+/// constraints of the table, placed in front of the plan writing them,
+/// such as a [`DataSinkExec`].
+///
+/// The rows are passed through unchanged, and execution fails on the first
+/// row violating a constraint. Unique keys are checked against the inserted
+/// rows and the rows of the optional `existing` plan, which reads the
+/// current contents of the table.
+#[derive(Debug)]
+pub struct ConstraintValidationExec {
+    /// Input plan that produces the inserted rows
+    input: Arc<dyn ExecutionPlan>,
+    /// Plan reading the existing rows of the table
+    existing: Option<Arc<dyn ExecutionPlan>>,
+    /// Name of the table, used in error messages
+    table_name: String,
+    /// Constraints to validate
+    constraints: Vec<ValidatedConstraint>,
+    cache: PlanProperties,
+}
+
+impl ConstraintValidationExec {
+    /// Create a plan validating the rows of `input` against `constraints`
+    pub fn new(
+        input: Arc<dyn ExecutionPlan>,
+        existing: Option<Arc<dyn ExecutionPlan>>,
+        table_name: impl Into<String>,
+        constraints: Vec<ValidatedConstraint>,
+    ) -> Self {
+        let cache = PlanProperties::new(
+            input.equivalence_properties().clone(),
+            Partitioning::UnknownPartitioning(1),
+            input.pipeline_behavior(),
+            input.boundedness(),
+        );
+        Self {
+            input,
+            existing,
+            table_name: table_name.into(),
+            constraints,
+            cache,
+        }
+    }
+
+    /// Input execution plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// Plan reading the existing rows of the table, if unique keys are
+    /// validated against them
+    pub fn existing(&self) -> Option<&Arc<dyn ExecutionPlan>> {
+        self.existing.as_ref()
+    }
+
+    /// Name of the table the rows are inserted into
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    /// Constraints validated by this plan
+    pub fn constraints(&self) -> &[ValidatedConstraint] {
+        &self.constraints
+    }
+}
+
+impl DisplayAs for ConstraintValidationExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let schema = self.input.schema();
+                let column_list = |columns: &[usize]| {
+                    columns
+                        .iter()
+                        .map(|i| schema.field(*i).name().as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                let constraints = self
+                    .constraints
+                    .iter()
+                    .map(|constraint| match constraint {
+                        ValidatedConstraint::NotNull(i) => {
+                            format!("{} NOT NULL", schema.field(*i).name())
+                        }
+                        ValidatedConstraint::Check { sql, .. } => {
+                            format!("CHECK ({sql})")
+                        }
+                        ValidatedConstraint::Unique {
+                            columns,
+                            primary_key: true,
+                        } => format!("PRIMARY KEY ({})", column_list(columns)),
+                        ValidatedConstraint::Unique {
+                            columns,
+                            primary_key: false,
+                        } => format!("UNIQUE ({})", column_list(columns)),
+                    })
+                    .collect::<Vec<_>>();
+                write!(
+                    f,
+                    "ConstraintValidationExec: table={}, constraints=[{}]",
+                    self.table_name,
+                    constraints.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for ConstraintValidationExec {
+    fn name(&self) -> &'static str {
+        "ConstraintValidationExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        vec![false; self.children().len()]
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        // Keys are collected across all inserted rows, so they are read from
+        // a single partition. The existing rows are read from all partitions.
+        let mut distribution = vec![Distribution::SinglePartition];
+        if self.existing.is_some() {
+            distribution.push(Distribution::UnspecifiedDistribution);
+        }
+        distribution
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        let mut maintains = vec![true];
+        if self.existing.is_some() {
+            maintains.push(false);
+        }
+        maintains
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        let mut children = vec![&self.input];
+        children.extend(self.existing.as_ref());
+        children
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let expected = self.children().len();
+        if children.len() != expected {
+            return internal_err!(
+                "ConstraintValidationExec expects {expected} children, got {}",
+                children.len()
+            );
+        }
+        let existing = self.existing.is_some().then(|| children.remove(1));
+        Ok(Arc::new(Self::new(
+            children.remove(0),
+            existing,
+            self.table_name.clone(),
+            self.constraints.clone(),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return internal_err!(
+                "ConstraintValidationExec can only be called on partition 0!"
+            );
+        }
+        let mut validator = ConstraintValidator::try_new(self)?;
+        let input = self.input.execute(0, Arc::clone(&context))?;
+        let existing = self
+            .existing
+            .as_ref()
+            .map(|existing| execute_stream(Arc::clone(existing), context))
+            .transpose()?;
+
+        let stream = futures::stream::once(async move {
+            // All existing keys are collected before validating the first
+            // inserted row
+            if let Some(mut existing) = existing {
+                while let Some(batch) = existing.next().await {
+                    validator.add_existing(&batch?)?;
+                }
+            }
+            Ok::<_, DataFusionError>(input.map(move |batch| {
+                let batch = batch?;
+                validator.validate(&batch)?;
+                Ok(batch)
+            }))
+        })
+        .try_flatten();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+}
+
+/// Validates the rows of a [`ConstraintValidationExec`]
+struct ConstraintValidator {
+    table_name: String,
+    schema: SchemaRef,
+    constraints: Vec<ValidatedConstraint>,
+    /// The keys seen so far, for each unique constraint
+    keys: Vec<UniqueKeys>,
+}
+
+/// The values of a unique key seen so far
+struct UniqueKeys {
+    columns: Vec<usize>,
+    primary_key: bool,
+    converter: RowConverter,
+    seen: HashSet<OwnedRow>,
+}
+
+impl ConstraintValidator {
+    fn try_new(exec: &ConstraintValidationExec) -> Result<Self> {
+        let schema = exec.input.schema();
+        let keys = exec
+            .constraints
+            .iter()
+            .filter_map(|constraint| match constraint {
+                ValidatedConstraint::Unique {
+                    columns,
+                    primary_key,
+                } => Some((columns, *primary_key)),
+                _ => None,
+            })
+            .map(|(columns, primary_key)| {
+                let fields = columns
+                    .iter()
+                    .map(|i| SortField::new(schema.field(*i).data_type().clone()))
+                    .collect();
+                Ok(UniqueKeys {
+                    columns: columns.clone(),
+                    primary_key,
+                    converter: RowConverter::new(fields)?,
+                    seen: HashSet::new(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            table_name: exec.table_name.clone(),
+            schema,
+            constraints: exec.constraints.clone(),
+            keys,
+        })
+    }
+
+    /// Records the keys of existing rows of the table
+    fn add_existing(&mut self, batch: &RecordBatch) -> Result<()> {
+        for keys in &mut self.keys {
+            let (rows, valid) = key_rows(batch, &keys.columns, &keys.converter)?;
+            for row in (0..batch.num_rows()).filter(|row| valid[*row]) {
+                keys.seen.insert(rows.row(row).owned());
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates inserted rows, recording their keys
+    fn validate(&mut self, batch: &RecordBatch) -> Result<()> {
+        let table_name = &self.table_name;
+        for constraint in &self.constraints {
+            match constraint {
+                ValidatedConstraint::NotNull(i) => {
+                    if batch.column(*i).null_count() > 0 {
+                        return exec_err!(
+                            "NULL value in column '{}' of table '{table_name}' \
+                            violates NOT NULL constraint",
+                            self.schema.field(*i).name()
+                        );
+                    }
+                }
+                ValidatedConstraint::Check { name, sql, expr } => {
+                    let result = expr.evaluate(batch)?.into_array(batch.num_rows())?;
+                    // Rows for which the expression is NULL satisfy the constraint
+                    if as_boolean_array(&result)?.iter().any(|v| v == Some(false)) {
+                        let name = name
+                            .as_ref()
+                            .map(|name| format!(" '{name}'"))
+                            .unwrap_or_default();
+                        return exec_err!(
+                            "Row inserted into table '{table_name}' violates \
+                            CHECK constraint{name}: {sql}"
+                        );
+                    }
+                }
+                ValidatedConstraint::Unique { .. } => {}
+            }
+        }
+
+        for keys in &mut self.keys {
+            let (rows, valid) = key_rows(batch, &keys.columns, &keys.converter)?;
+            for row in (0..batch.num_rows()).filter(|row| valid[*row]) {
+                if keys.seen.insert(rows.row(row).owned()) {
+                    continue;
+                }
+                let columns = keys
+                    .columns
+                    .iter()
+                    .map(|i| self.schema.field(*i).name().as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let values = keys
+                    .columns
+                    .iter()
+                    .map(|i| {
+                        ScalarValue::try_from_array(batch.column(*i), row)
+                            .map(|value| value.to_string())
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .join(", ");
+                let kind = if keys.primary_key {
+                    "PRIMARY KEY"
+                } else {
+                    "UNIQUE"
+                };
+                return exec_err!(
+                    "Duplicate key ({columns})=({values}) violates {kind} \
+                    constraint of table '{table_name}'"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Create a output record batch with a count
 ///
 /// ```text
@@ -260,13 +631,13 @@ impl ExecutionPlan for DataSinkExec {
 /// | 6     |,
 /// +-------+,
 /// ```
-fn make_count_batch(count: u64) -> RecordBatch {
+pub(crate) fn make_count_batch(count: u64) -> RecordBatch {
     let array = Arc::new(UInt64Array::from(vec![count])) as ArrayRef;
 
     RecordBatch::try_from_iter_with_nullable(vec![("count", array, false)]).unwrap()
 }
 
-fn make_count_schema() -> SchemaRef {
+pub(crate) fn make_count_schema() -> SchemaRef {
     // Define a schema.
     Arc::new(Schema::new(vec![Field::new(
         "count",
@@ -19,7 +19,7 @@
 
 use std::fmt;
 use std::mem::size_of;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::task::Poll;
 use std::{any::Any, vec};
@@ -60,17 +60,19 @@ use crate::{
 use arrow::array::{
     Array, ArrayRef, BooleanArray, BooleanBufferBuilder, UInt32Array, UInt64Array,
 };
+use arrow::buffer::NullBuffer;
 use arrow::compute::kernels::cmp::{eq, not_distinct};
-use arrow::compute::{and, concat_batches, take, FilterBuilder};
+use arrow::compute::{and, concat_batches, is_not_null, take, FilterBuilder};
 use arrow::datatypes::{Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
+use arrow::row::{RowConverter, SortField};
 use arrow::util::bit_util;
 use arrow_array::cast::downcast_array;
 use arrow_schema::ArrowError;
 use datafusion_common::utils::memory::estimate_memory_size;
 use datafusion_common::{
     internal_datafusion_err, internal_err, plan_err, project_schema, DataFusionError,
-    JoinSide, JoinType, Result,
+    HashMap, JoinSide, JoinType, Result,
 };
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
 use datafusion_execution::TaskContext;
@@ -98,6 +100,14 @@ struct JoinLeftData {
     /// Counter of running probe-threads, potentially
     /// able to update `visited_indices_bitmap`
     probe_threads_counter: AtomicUsize,
+    /// Set by the probe threads of a null-aware anti join once they have
+    /// seen a probe side row
+    probe_side_non_empty: AtomicBool,
+    /// Set by the probe threads of a null-aware anti join once they have
+    /// seen a NULL probe side join key
+    probe_side_has_null: AtomicBool,
+    /// The groups of a null-aware anti join with more than one join key
+    null_aware_groups: Option<NullAwareGroups>,
     /// We need to keep this field to maintain accurate memory accounting, even though we don't directly use it.
     /// Without holding onto this reservation, the recorded memory usage would become inconsistent with actual usage.
     /// This could hide potential out-of-memory issues, especially when upstream operators increase their memory consumption.
@@ -113,6 +123,7 @@ impl JoinLeftData {
         values: Vec<ArrayRef>,
         visited_indices_bitmap: SharedBitmapBuilder,
         probe_threads_counter: AtomicUsize,
+        null_aware_groups: Option<NullAwareGroups>,
         reservation: MemoryReservation,
     ) -> Self {
         Self {
@@ -121,6 +132,9 @@ impl JoinLeftData {
             values,
             visited_indices_bitmap,
             probe_threads_counter,
+            probe_side_non_empty: AtomicBool::new(false),
+            probe_side_has_null: AtomicBool::new(false),
+            null_aware_groups,
             _reservation: reservation,
         }
     }
@@ -147,9 +161,208 @@ impl JoinLeftData {
 
     /// Decrements the counter of running threads, and returns `true`
     /// if caller is the last running thread
+    ///
+    /// The decrement releases the flags set by the caller and acquires those
+    /// set by the threads that completed before, so that the last thread
+    /// sees all of them.
     fn report_probe_completed(&self) -> bool {
-        self.probe_threads_counter.fetch_sub(1, Ordering::Relaxed) == 1
+        self.probe_threads_counter.fetch_sub(1, Ordering::AcqRel) == 1
     }
+
+    /// Records the join keys of a probe side batch of a null-aware anti join,
+    /// and returns `true` if any probe thread has seen a NULL join key, after
+    /// which no rows are produced
+    fn report_probe_keys(&self, keys: &[ArrayRef]) -> Result<bool> {
+        if let Some(groups) = &self.null_aware_groups {
+            groups.report_probe_keys(keys)?;
+            return Ok(false);
+        }
+        if !keys[0].is_empty() {
+            self.probe_side_non_empty.store(true, Ordering::Release);
+        }
+        if keys[0].null_count() > 0 {
+            self.probe_side_has_null.store(true, Ordering::Release);
+        }
+        Ok(self.probe_side_has_null())
+    }
+
+    /// Returns `true` if a probe thread of a null-aware anti join has seen a
+    /// NULL join key
+    fn probe_side_has_null(&self) -> bool {
+        self.probe_side_has_null.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if a probe thread of a null-aware anti join has seen a
+    /// probe side row
+    fn probe_side_non_empty(&self) -> bool {
+        self.probe_side_non_empty.load(Ordering::Acquire)
+    }
+
+    /// returns the groups of a null-aware anti join with more than one key
+    fn null_aware_groups(&self) -> Option<&NullAwareGroups> {
+        self.null_aware_groups.as_ref()
+    }
+}
+
+/// The groups of the build side rows of a null-aware anti join with more than
+/// one join key.
+///
+/// The keys after the first one are the correlation keys of a `NOT IN`
+/// subquery, such as `b` in `a NOT IN (SELECT x FROM t WHERE t.b = b)`. The
+/// semantics of `NOT IN` apply to the rows of each group of equal correlation
+/// keys on their own, rows with a NULL correlation key belong to no group.
+struct NullAwareGroups {
+    /// Converts the correlation keys to rows that can be compared
+    converter: RowConverter,
+    /// The index of each group by its correlation keys
+    indices: HashMap<Vec<u8>, usize>,
+    /// The group of each build side row
+    build_groups: Vec<Option<usize>>,
+    /// Whether a build side row of each group has a NULL first key
+    build_has_null: Vec<bool>,
+    /// Set by the probe threads once they have seen a probe side row of
+    /// each group
+    probe_non_empty: Vec<AtomicBool>,
+    /// Set by the probe threads once they have seen a probe side row of
+    /// each group with a NULL first key
+    probe_has_null: Vec<AtomicBool>,
+}
+
+impl NullAwareGroups {
+    /// Groups the build side rows by the join keys after the first one
+    fn try_new(keys: &[ArrayRef]) -> Result<Self> {
+        let (first, correlation) = keys
+            .split_first()
+            .ok_or_else(|| internal_datafusion_err!("missing null-aware join keys"))?;
+        let converter = RowConverter::new(
+            correlation
+                .iter()
+                .map(|key| SortField::new(key.data_type().clone()))
+                .collect(),
+        )?;
+
+        let mut indices = HashMap::new();
+        let mut build_groups = Vec::with_capacity(first.len());
+        let mut build_has_null = vec![];
+        let rows = converter.convert_columns(correlation)?;
+        let nulls = correlation_nulls(correlation);
+        for (row_idx, row) in rows.iter().enumerate() {
+            if nulls.as_ref().is_some_and(|nulls| nulls.is_null(row_idx)) {
+                build_groups.push(None);
+                continue;
+            }
+            let group = match indices.get(row.as_ref()) {
+                Some(group) => *group,
+                None => {
+                    indices.insert(row.as_ref().to_vec(), build_has_null.len());
+                    build_has_null.push(false);
+                    build_has_null.len() - 1
+                }
+            };
+            build_groups.push(Some(group));
+            build_has_null[group] |= first.is_null(row_idx);
+        }
+
+        let num_groups = build_has_null.len();
+        Ok(Self {
+            converter,
+            indices,
+            build_groups,
+            build_has_null,
+            probe_non_empty: (0..num_groups).map(|_| AtomicBool::new(false)).collect(),
+            probe_has_null: (0..num_groups).map(|_| AtomicBool::new(false)).collect(),
+        })
+    }
+
+    /// Returns the group of each row of the probe side join keys `keys`, which
+    /// is `None` if the build side has no rows with its correlation keys
+    fn probe_groups(&self, keys: &[ArrayRef]) -> Result<Vec<Option<usize>>> {
+        let correlation = &keys[1..];
+        let rows = self.converter.convert_columns(correlation)?;
+        let nulls = correlation_nulls(correlation);
+        Ok(rows
+            .iter()
+            .enumerate()
+            .map(|(row_idx, row)| {
+                if nulls.as_ref().is_some_and(|nulls| nulls.is_null(row_idx)) {
+                    None
+                } else {
+                    self.indices.get(row.as_ref()).copied()
+                }
+            })
+            .collect())
+    }
+
+    /// Records the groups of the join keys of a probe side batch
+    fn report_probe_keys(&self, keys: &[ArrayRef]) -> Result<()> {
+        for (row_idx, group) in self.probe_groups(keys)?.into_iter().enumerate() {
+            if let Some(group) = group {
+                self.probe_non_empty[group].store(true, Ordering::Release);
+                if keys[0].is_null(row_idx) {
+                    self.probe_has_null[group].store(true, Ordering::Release);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a mask of the unmatched probe side rows with the join keys
+    /// `keys` that are produced by a null-aware right anti join
+    ///
+    /// A probe side row of a non-empty group is not produced if its first key
+    /// is NULL, or if a build side row of its group has a NULL first key.
+    fn probe_mask(&self, keys: &[ArrayRef]) -> Result<BooleanArray> {
+        Ok(self
+            .probe_groups(keys)?
+            .into_iter()
+            .enumerate()
+            .map(|(row_idx, group)| {
+                Some(group.map_or(true, |group| {
+                    !self.build_has_null[group] && keys[0].is_valid(row_idx)
+                }))
+            })
+            .collect())
+    }
+
+    /// Returns a mask of the unmatched build side rows at `indices` that are
+    /// produced by a null-aware left anti join, once the probe side is
+    /// exhausted
+    ///
+    /// A build side row is not produced if a probe side row of its group has
+    /// a NULL first key, or if its first key is NULL and its group has probe
+    /// side rows.
+    fn build_mask(&self, first_key: &ArrayRef, indices: &UInt64Array) -> BooleanArray {
+        indices
+            .values()
+            .iter()
+            .map(|&row_idx| {
+                let row_idx = row_idx as usize;
+                Some(self.build_groups[row_idx].map_or(true, |group| {
+                    !self.probe_has_null[group].load(Ordering::Acquire)
+                        && (first_key.is_valid(row_idx)
+                            || !self.probe_non_empty[group].load(Ordering::Acquire))
+                }))
+            })
+            .collect()
+    }
+
+    /// Returns the memory used by the groups, besides the converter
+    fn size(&self) -> usize {
+        self.indices
+            .keys()
+            .map(|row| row.capacity() + size_of::<(Vec<u8>, usize)>())
+            .sum::<usize>()
+            + self.build_groups.capacity() * size_of::<Option<usize>>()
+            + self.build_has_null.capacity()
+            + self.probe_non_empty.capacity() * 2 * size_of::<AtomicBool>()
+    }
+}
+
+/// Returns the rows with a NULL key in `keys`
+fn correlation_nulls(keys: &[ArrayRef]) -> Option<NullBuffer> {
+    keys.iter().fold(None, |nulls, key| {
+        NullBuffer::union(nulls.as_ref(), key.logical_nulls().as_ref())
+    })
 }
 
 #[allow(rustdoc::private_intra_doc_links)]
@@ -353,6 +566,14 @@ pub struct HashJoinExec {
     /// Otherwise, rows that have `null`s in the join columns will not be
     /// matched and thus will not appear in the output.
     pub null_equals_null: bool,
+    /// If `null_aware` is true, this is a null-aware anti join that implements
+    /// the semantics of `NOT IN`: no rows are produced if the non-preserved
+    /// side contains a NULL join key, and rows of the preserved side with a
+    /// NULL join key are only produced if the non-preserved side is empty.
+    /// Further join keys correlate the rows of both sides, and these
+    /// semantics apply to each group of equal correlation keys on its own.
+    /// See [`Self::with_null_aware`].
+    pub null_aware: bool,
     /// Cache holding plan properties like equivalences, output partitioning etc.
     cache: PlanProperties,
 }
@@ -415,10 +636,42 @@ impl HashJoinExec {
             projection,
             column_indices,
             null_equals_null,
+            null_aware: false,
             cache,
         })
     }
 
+    /// Return new instance of [HashJoinExec] with the given null-aware flag.
+    ///
+    /// A null-aware join must be a [`JoinType::LeftAnti`] or
+    /// [`JoinType::RightAnti`] join without a filter. Its first key is the one
+    /// compared by `NOT IN`, and the keys after it, if any, are correlation
+    /// keys. As the NULL keys of the whole non-preserved side must be known
+    /// before any row of the preserved side is produced, it cannot use
+    /// [`PartitionMode::Partitioned`]. A [`JoinType::RightAnti`] join, which
+    /// collects the non-preserved side, can still probe several partitions of
+    /// the preserved side in parallel.
+    pub fn with_null_aware(mut self, null_aware: bool) -> Result<Self> {
+        if null_aware {
+            if !matches!(self.join_type, JoinType::LeftAnti | JoinType::RightAnti) {
+                return plan_err!(
+                    "Null-aware HashJoinExec requires an anti join, found {}",
+                    self.join_type
+                );
+            }
+            if self.filter.is_some() {
+                return plan_err!("Null-aware HashJoinExec does not support a filter");
+            }
+            if self.mode == PartitionMode::Partitioned {
+                return plan_err!(
+                    "Null-aware HashJoinExec does not support PartitionMode::Partitioned"
+                );
+            }
+        }
+        self.null_aware = null_aware;
+        Ok(self)
+    }
+
     /// left (build) side which gets hashed
     pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
         &self.left
@@ -460,6 +713,11 @@ impl HashJoinExec {
         self.null_equals_null
     }
 
+    /// Get null_aware
+    pub fn null_aware(&self) -> bool {
+        self.null_aware
+    }
+
     /// Calculate order preservation flags for this hash join.
     fn maintains_input_order(join_type: JoinType) -> Vec<bool> {
         vec![
@@ -505,7 +763,8 @@ impl HashJoinExec {
             projection,
             self.mode,
             self.null_equals_null,
-        )
+        )?
+        .with_null_aware(self.null_aware)
     }
 
     /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
@@ -613,7 +872,8 @@ impl HashJoinExec {
             ),
             partition_mode,
             self.null_equals_null(),
-        )?;
+        )?
+        .with_null_aware(self.null_aware())?;
         // In case of anti / semi joins or if there is embedded projection in HashJoinExec, output column order is preserved, no need to add projection again
         if matches!(
             self.join_type(),
@@ -662,10 +922,20 @@ impl DisplayAs for HashJoinExec {
                     .map(|(c1, c2)| format!("({}, {})", c1, c2))
                     .collect::<Vec<String>>()
                     .join(", ");
+                let display_null_aware = if self.null_aware {
+                    ", null_aware=true"
+                } else {
+                    ""
+                };
                 write!(
                     f,
-                    "HashJoinExec: mode={:?}, join_type={:?}, on=[{}]{}{}",
-                    self.mode, self.join_type, on, display_filter, display_projections
+                    "HashJoinExec: mode={:?}, join_type={:?}, on=[{}]{}{}{}",
+                    self.mode,
+                    self.join_type,
+                    on,
+                    display_filter,
+                    display_projections,
+                    display_null_aware
                 )
             }
         }
@@ -737,16 +1007,19 @@ impl ExecutionPlan for HashJoinExec {
         self: Arc<Self>,
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(HashJoinExec::try_new(
-            Arc::clone(&children[0]),
-            Arc::clone(&children[1]),
-            self.on.clone(),
-            self.filter.clone(),
-            &self.join_type,
-            self.projection.clone(),
-            self.mode,
-            self.null_equals_null,
-        )?))
+        Ok(Arc::new(
+            HashJoinExec::try_new(
+                Arc::clone(&children[0]),
+                Arc::clone(&children[1]),
+                self.on.clone(),
+                self.filter.clone(),
+                &self.join_type,
+                self.projection.clone(),
+                self.mode,
+                self.null_equals_null,
+            )?
+            .with_null_aware(self.null_aware)?,
+        ))
     }
 
     fn execute(
@@ -790,6 +1063,7 @@ impl ExecutionPlan for HashJoinExec {
                     reservation,
                     need_produce_result_in_final(self.join_type),
                     self.right().output_partitioning().partition_count(),
+                    self.null_aware,
                 )
             }),
             PartitionMode::Partitioned => {
@@ -807,6 +1081,7 @@ impl ExecutionPlan for HashJoinExec {
                     reservation,
                     need_produce_result_in_final(self.join_type),
                     1,
+                    self.null_aware,
                 ))
             }
             PartitionMode::Auto => {
@@ -842,6 +1117,7 @@ impl ExecutionPlan for HashJoinExec {
             random_state: self.random_state.clone(),
             join_metrics,
             null_equals_null: self.null_equals_null,
+            null_aware: self.null_aware,
             state: HashJoinStreamState::WaitBuildSide,
             build_side: BuildSide::Initial(BuildSideInitialState { left_fut }),
             batch_size,
@@ -894,17 +1170,20 @@ impl ExecutionPlan for HashJoinExec {
             self.schema(),
             self.filter(),
         )? {
-            Ok(Some(Arc::new(HashJoinExec::try_new(
-                Arc::new(projected_left_child),
-                Arc::new(projected_right_child),
-                join_on,
-                join_filter,
-                self.join_type(),
-                // Returned early if projection is not None
-                None,
-                *self.partition_mode(),
-                self.null_equals_null,
-            )?)))
+            Ok(Some(Arc::new(
+                HashJoinExec::try_new(
+                    Arc::new(projected_left_child),
+                    Arc::new(projected_right_child),
+                    join_on,
+                    join_filter,
+                    self.join_type(),
+                    // Returned early if projection is not None
+                    None,
+                    *self.partition_mode(),
+                    self.null_equals_null,
+                )?
+                .with_null_aware(self.null_aware)?,
+            )))
         } else {
             try_embed_projection(projection, self)
         }
@@ -924,6 +1203,7 @@ async fn collect_left_input(
     reservation: MemoryReservation,
     with_visited_indices_bitmap: bool,
     probe_threads_count: usize,
+    null_aware: bool,
 ) -> Result<JoinLeftData> {
     let schema = left.schema();
 
@@ -1013,12 +1293,22 @@ async fn collect_left_input(
         })
         .collect::<Result<Vec<_>>>()?;
 
+    let null_aware_groups = if null_aware && left_values.len() > 1 {
+        let groups = NullAwareGroups::try_new(&left_values)?;
+        reservation.try_grow(groups.size())?;
+        metrics.build_mem_used.add(groups.size());
+        Some(groups)
+    } else {
+        None
+    };
+
     let data = JoinLeftData::new(
         hashmap,
         single_batch,
         left_values,
         Mutex::new(visited_indices_bitmap),
         AtomicUsize::new(probe_threads_count),
+        null_aware_groups,
         reservation,
     );
 
@@ -1210,6 +1500,8 @@ struct HashJoinStream {
     column_indices: Vec<ColumnIndex>,
     /// If null_equals_null is true, null == null else null != null
     null_equals_null: bool,
+    /// If null_aware is true, the stream computes a null-aware anti join
+    null_aware: bool,
     /// State of the stream
     state: HashJoinStreamState,
     /// Build side
@@ -1368,6 +1660,44 @@ pub fn equal_rows_arr(
     ))
 }
 
+/// Returns a mask of the rows at `indices` whose join key in `keys` is not NULL
+fn non_null_key_mask(keys: &ArrayRef, indices: &dyn Array) -> Result<BooleanArray> {
+    let keys = take(keys.as_ref(), indices, None)?;
+    Ok(is_not_null(&keys)?)
+}
+
+/// Adjusts the unmatched build side rows produced by a null-aware left anti join.
+///
+/// No rows are produced if the probe side contains a NULL key, and build side
+/// rows with a NULL key are only produced if the probe side is empty. A join
+/// with [`NullAwareGroups`] applies this to each group on its own.
+fn null_aware_final_indices(
+    left_data: &JoinLeftData,
+    left_indices: UInt64Array,
+    right_indices: UInt32Array,
+) -> Result<(UInt64Array, UInt32Array)> {
+    let mask = if let Some(groups) = left_data.null_aware_groups() {
+        groups.build_mask(&left_data.values()[0], &left_indices)
+    } else if left_data.probe_side_has_null() {
+        return Ok((
+            UInt64Array::from_iter_values(vec![]),
+            UInt32Array::from_iter_values(vec![]),
+        ));
+    } else if !left_data.probe_side_non_empty() {
+        return Ok((left_indices, right_indices));
+    } else {
+        non_null_key_mask(&left_data.values()[0], &left_indices)?
+    };
+    let filter_builder = FilterBuilder::new(&mask).optimize().build();
+    let left_filtered = filter_builder.filter(&left_indices)?;
+    let right_filtered = filter_builder.filter(&right_indices)?;
+
+    Ok((
+        downcast_array(left_filtered.as_ref()),
+        downcast_array(right_filtered.as_ref()),
+    ))
+}
+
 impl HashJoinStream {
     /// Separate implementation function that unpins the [`HashJoinStream`] so
     /// that partial borrows work correctly
@@ -1410,7 +1740,17 @@ impl HashJoinStream {
             .get_shared(cx))?;
         build_timer.done();
 
-        self.state = HashJoinStreamState::FetchProbeBatch;
+        // a NULL key on the build side of a null-aware right anti join
+        // filters out every probe side row, unless the join has groups
+        self.state = if self.null_aware
+            && self.join_type == JoinType::RightAnti
+            && left_data.null_aware_groups().is_none()
+            && left_data.values()[0].null_count() > 0
+        {
+            HashJoinStreamState::Completed
+        } else {
+            HashJoinStreamState::FetchProbeBatch
+        };
         self.build_side = BuildSide::Ready(BuildSideReadyState { left_data });
 
         Poll::Ready(Ok(StatefulStreamResult::Continue))
@@ -1424,6 +1764,21 @@ impl HashJoinStream {
         &mut self,
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Result<StatefulStreamResult<Option<RecordBatch>>>> {
+        let null_aware_left_anti =
+            self.null_aware && self.join_type == JoinType::LeftAnti;
+        // once a NULL probe side key has been seen, a null-aware left anti
+        // join produces no rows, so the rest of the probe side can be skipped
+        if null_aware_left_anti
+            && self
+                .build_side
+                .try_as_ready()?
+                .left_data
+                .probe_side_has_null()
+        {
+            self.state = HashJoinStreamState::ExhaustedProbeSide;
+            return Poll::Ready(Ok(StatefulStreamResult::Continue));
+        }
+
         match ready!(self.right.poll_next_unpin(cx)) {
             None => {
                 self.state = HashJoinStreamState::ExhaustedProbeSide;
@@ -1436,6 +1791,17 @@ impl HashJoinStream {
                     .map(|c| c.evaluate(&batch)?.into_array(batch.num_rows()))
                     .collect::<Result<Vec<_>>>()?;
 
+                if null_aware_left_anti
+                    && self
+                        .build_side
+                        .try_as_ready()?
+                        .left_data
+                        .report_probe_keys(&keys_values)?
+                {
+                    self.state = HashJoinStreamState::ExhaustedProbeSide;
+                    return Poll::Ready(Ok(StatefulStreamResult::Continue));
+                }
+
                 self.hashes_buffer.clear();
                 self.hashes_buffer.resize(batch.num_rows(), 0);
                 create_hashes(&keys_values, &self.random_state, &mut self.hashes_buffer)?;
@@ -1540,6 +1906,33 @@ impl HashJoinStream {
             self.right_side_ordered,
         )?;
 
+        // unless the build side is empty, probe side rows with a NULL key are
+        // not produced by a null-aware right anti join
+        let right_indices = if self.null_aware
+            && self.join_type == JoinType::RightAnti
+            && build_side.left_data.batch().num_rows() > 0
+        {
+            let mask = match build_side.left_data.null_aware_groups() {
+                Some(groups) => {
+                    let keys = state
+                        .values
+                        .iter()
+                        .map(|keys| take(keys.as_ref(), &right_indices, None))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    groups.probe_mask(&keys)?
+                }
+                None => non_null_key_mask(&state.values[0], &right_indices)?,
+            };
+            downcast_array(
+                FilterBuilder::new(&mask)
+                    .build()
+                    .filter(&right_indices)?
+                    .as_ref(),
+            )
+        } else {
+            right_indices
+        };
+
         let result = build_batch_from_indices(
             &self.schema,
             build_side.left_data.batch(),
@@ -1591,6 +1984,11 @@ impl HashJoinStream {
             build_side.left_data.visited_indices_bitmap(),
             self.join_type,
         );
+        let (left_side, right_side) = if self.null_aware {
+            null_aware_final_indices(&build_side.left_data, left_side, right_side)?
+        } else {
+            (left_side, right_side)
+        };
         let empty_right_batch = RecordBatch::new_empty(self.right.schema());
         // use the left and right indices to produce the batch result
         let result = build_batch_from_indices(
@@ -4206,6 +4604,209 @@ mod tests {
         Ok(())
     }
 
+    fn build_table_nullable(
+        name: &str,
+        values: Vec<Option<i32>>,
+    ) -> Arc<dyn ExecutionPlan> {
+        let schema = Arc::new(Schema::new(vec![Field::new(name, DataType::Int32, true)]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(Int32Array::from(values))],
+        )
+        .unwrap();
+        MemorySourceConfig::try_new_exec(&[vec![batch]], schema, None).unwrap()
+    }
+
+    /// Runs a null-aware anti join between a1 and a2, and returns the values
+    /// of the anti side
+    async fn null_aware_anti_join(
+        left: Vec<Option<i32>>,
+        right: Vec<Option<i32>>,
+        join_type: JoinType,
+    ) -> Result<Vec<RecordBatch>> {
+        let task_ctx = prepare_task_ctx(8192);
+        let left = build_table_nullable("a1", left);
+        let right = build_table_nullable("a2", right);
+        let on = vec![(
+            Arc::new(Column::new_with_schema("a1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("a2", &right.schema())?) as _,
+        )];
+        let join = join(left, right, on, &join_type, false)?.with_null_aware(true)?;
+        let stream = join.execute(0, task_ctx)?;
+        common::collect(stream).await
+    }
+
+    #[tokio::test]
+    async fn join_left_anti_null_aware() -> Result<()> {
+        let outer = vec![Some(1), Some(2), None, Some(3)];
+
+        let batches = null_aware_anti_join(
+            outer.clone(),
+            vec![Some(2), Some(4)],
+            JoinType::LeftAnti,
+        )
+        .await?;
+        let expected = ["+----+", "| a1 |", "+----+", "| 1  |", "| 3  |", "+----+"];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        // a NULL on the probe side removes all rows
+        let batches =
+            null_aware_anti_join(outer.clone(), vec![Some(2), None], JoinType::LeftAnti)
+                .await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+
+        // an empty probe side keeps all rows, including NULLs
+        let batches = null_aware_anti_join(outer, vec![], JoinType::LeftAnti).await?;
+        let expected = [
+            "+----+", "| a1 |", "+----+", "|    |", "| 1  |", "| 2  |", "| 3  |",
+            "+----+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_right_anti_null_aware() -> Result<()> {
+        let outer = vec![Some(1), Some(2), None, Some(3)];
+
+        let batches = null_aware_anti_join(
+            vec![Some(2), Some(4)],
+            outer.clone(),
+            JoinType::RightAnti,
+        )
+        .await?;
+        let expected = ["+----+", "| a2 |", "+----+", "| 1  |", "| 3  |", "+----+"];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        // a NULL on the build side removes all rows
+        let batches =
+            null_aware_anti_join(vec![Some(2), None], outer.clone(), JoinType::RightAnti)
+                .await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+
+        // an empty build side keeps all rows, including NULLs
+        let batches = null_aware_anti_join(vec![], outer, JoinType::RightAnti).await?;
+        let expected = [
+            "+----+", "| a2 |", "+----+", "|    |", "| 1  |", "| 2  |", "| 3  |",
+            "+----+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        Ok(())
+    }
+
+    /// Runs a null-aware anti join between (a1, b1) and (a2, b2), whose second
+    /// columns are correlation keys, and returns the rows of the anti side
+    async fn null_aware_correlated_anti_join(
+        left: Vec<(Option<i32>, Option<i32>)>,
+        right: Vec<(Option<i32>, Option<i32>)>,
+        join_type: JoinType,
+    ) -> Result<Vec<RecordBatch>> {
+        let task_ctx = prepare_task_ctx(8192);
+        let build_table = |a: &str, b: &str, rows: Vec<(Option<i32>, Option<i32>)>| {
+            let schema = Arc::new(Schema::new(vec![
+                Field::new(a, DataType::Int32, true),
+                Field::new(b, DataType::Int32, true),
+            ]));
+            let (a, b): (Vec<_>, Vec<_>) = rows.into_iter().unzip();
+            let batch = RecordBatch::try_new(
+                Arc::clone(&schema),
+                vec![Arc::new(Int32Array::from(a)), Arc::new(Int32Array::from(b))],
+            )
+            .unwrap();
+            MemorySourceConfig::try_new_exec(&[vec![batch]], schema, None).unwrap()
+        };
+        let left = build_table("a1", "b1", left);
+        let right = build_table("a2", "b2", right);
+        let on = vec![
+            (
+                Arc::new(Column::new_with_schema("a1", &left.schema())?) as _,
+                Arc::new(Column::new_with_schema("a2", &right.schema())?) as _,
+            ),
+            (
+                Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+                Arc::new(Column::new_with_schema("b2", &right.schema())?) as _,
+            ),
+        ];
+        let join = join(left, right, on, &join_type, false)?.with_null_aware(true)?;
+        let stream = join.execute(0, task_ctx)?;
+        common::collect(stream).await
+    }
+
+    #[tokio::test]
+    async fn join_anti_null_aware_correlated() -> Result<()> {
+        let outer = vec![
+            (Some(1), Some(1)),
+            (Some(2), Some(1)),
+            (None, Some(1)),
+            (Some(1), Some(2)),
+            (None, Some(2)),
+            (Some(3), Some(3)),
+            (None, Some(3)),
+            (None, Some(4)),
+            (Some(1), None),
+        ];
+        // a NULL in group 2 removes all its rows, group 4 is empty and the
+        // inner row with a NULL correlation key belongs to no group
+        let inner = vec![
+            (Some(2), Some(1)),
+            (None, Some(2)),
+            (Some(5), Some(3)),
+            (None, None),
+        ];
+
+        let batches = null_aware_correlated_anti_join(
+            outer.clone(),
+            inner.clone(),
+            JoinType::LeftAnti,
+        )
+        .await?;
+        let expected = [
+            "+----+----+",
+            "| a1 | b1 |",
+            "+----+----+",
+            "|    | 4  |",
+            "| 1  |    |",
+            "| 1  | 1  |",
+            "| 3  | 3  |",
+            "+----+----+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        let batches =
+            null_aware_correlated_anti_join(inner, outer, JoinType::RightAnti).await?;
+        let expected = [
+            "+----+----+",
+            "| a2 | b2 |",
+            "+----+----+",
+            "|    | 4  |",
+            "| 1  |    |",
+            "| 1  | 1  |",
+            "| 3  | 3  |",
+            "+----+----+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        Ok(())
+    }
+
+    #[test]
+    fn null_aware_requires_anti_join() -> Result<()> {
+        let left = build_table_nullable("a1", vec![Some(1)]);
+        let right = build_table_nullable("a2", vec![Some(1)]);
+        let on = vec![(
+            Arc::new(Column::new_with_schema("a1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("a2", &right.schema())?) as _,
+        )];
+        let err = join(left, right, on, &JoinType::Inner, false)?
+            .with_null_aware(true)
+            .unwrap_err();
+        assert_contains!(err.to_string(), "Null-aware");
+
+        Ok(())
+    }
+
     /// Returns the column names on the schema
     fn columns(schema: &Schema) -> Vec<String> {
         schema.fields().iter().map(|f| f.name().clone()).collect()
@@ -56,10 +56,12 @@ pub mod coalesce_batches;
 pub mod coalesce_partitions;
 pub mod common;
 pub mod display;
+pub mod dml;
 pub mod empty;
 pub mod execution_plan;
 pub mod explain;
 pub mod filter;
+pub mod gap_fill;
 pub mod insert;
 pub mod joins;
 pub mod limit;
@@ -69,6 +71,7 @@ pub mod placeholder_row;
 pub mod projection;
 pub mod recursive_query;
 pub mod repartition;
+pub mod sample;
 pub mod sorts;
 pub mod spill;
 pub mod stream;
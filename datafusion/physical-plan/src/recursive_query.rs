@@ -26,14 +26,24 @@ use super::{
     work_table::{ReservedBatches, WorkTable, WorkTableExec},
     PlanProperties, RecordBatchStream, SendableRecordBatchStream, Statistics,
 };
+use crate::aggregates::group_values::{new_group_values, GroupValues};
+use crate::aggregates::order::GroupOrdering;
 use crate::execution_plan::{Boundedness, EmissionType};
+use crate::metrics::{Count, MetricBuilder};
+use crate::spill::{get_record_batch_memory_size, spill_record_batches};
 use crate::{DisplayAs, DisplayFormatType, ExecutionPlan};
 
+use arrow::array::BooleanArray;
+use arrow::compute::filter_record_batch;
 use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
-use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
-use datafusion_common::{not_impl_err, DataFusionError, Result};
+use datafusion_common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
+};
+use datafusion_common::{exec_err, not_impl_err, DataFusionError, Result};
+use datafusion_execution::disk_manager::RefCountedTempFile;
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::runtime_env::RuntimeEnv;
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::{EquivalenceProperties, Partitioning};
 
@@ -49,11 +59,16 @@ use futures::{ready, Stream, StreamExt};
 ///
 /// Before each execution of the dynamic part, the rows from the previous
 /// iteration will be available in a "working table" (not a real table,
-/// can be only accessed using a continuance operation).
+/// can be only accessed using a continuance operation). The working table
+/// is spilled to disk if it does not fit in memory.
+///
+/// With `UNION` (`is_distinct`), rows that were already produced by the
+/// static term or an earlier iteration are dropped, so that the recursion
+/// ends once no new rows are found.
 ///
-/// Note that there won't be any limit or checks applied to detect
-/// an infinite recursion, so it is up to the planner to ensure that
-/// it won't happen.
+/// The number of iterations is only limited by
+/// `datafusion.execution.recursive_cte_max_iterations`, so it is up to the
+/// query to ensure that the recursion terminates.
 #[derive(Debug, Clone)]
 pub struct RecursiveQueryExec {
     /// Name of the query handler
@@ -83,7 +98,8 @@ impl RecursiveQueryExec {
         // Each recursive query needs its own work table
         let work_table = Arc::new(WorkTable::new());
         // Use the same work table for both the WorkTableExec and the recursive term
-        let recursive_term = assign_work_table(recursive_term, Arc::clone(&work_table))?;
+        let recursive_term =
+            assign_work_table(recursive_term, &name, Arc::clone(&work_table))?;
         let cache = Self::compute_properties(static_term.schema());
         Ok(RecursiveQueryExec {
             name,
@@ -191,13 +207,17 @@ impl ExecutionPlan for RecursiveQueryExec {
 
         let static_stream = self.static_term.execute(partition, Arc::clone(&context))?;
         let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
-        Ok(Box::pin(RecursiveQueryStream::new(
+        let spill_metrics = SpillMetrics::new(&self.metrics, partition);
+        Ok(Box::pin(RecursiveQueryStream::try_new(
             context,
+            self.name.clone(),
             Arc::clone(&self.work_table),
             Arc::clone(&self.recursive_term),
             static_stream,
+            self.is_distinct,
             baseline_metrics,
-        )))
+            spill_metrics,
+        )?))
     }
 
     fn metrics(&self) -> Option<MetricsSet> {
@@ -245,9 +265,13 @@ impl DisplayAs for RecursiveQueryExec {
 ///        buffer.append(batch)
 ///        yield buffer
 ///
+/// With `UNION`, the rows seen before are removed from every batch before it
+/// is added to the buffer.
 struct RecursiveQueryStream {
     /// The context to be used for managing handlers & executing new tasks
     task_context: Arc<TaskContext>,
+    /// Name of the query handler
+    name: String,
     /// The working table state, representing the self referencing cte table
     work_table: Arc<WorkTable>,
     /// The dynamic part (recursive term) as is (without being executed)
@@ -265,24 +289,52 @@ struct RecursiveQueryStream {
     buffer: Vec<RecordBatch>,
     /// Tracks the memory used by the buffer
     reservation: MemoryReservation,
-    // /// Metrics.
-    _baseline_metrics: BaselineMetrics,
+    /// The files the current results were spilled to, if they did not fit
+    /// in memory. Will be cleared after each iteration.
+    spills: Vec<RefCountedTempFile>,
+    /// The rows produced so far, if the results are deduplicated (`UNION`)
+    distinct: Option<DistinctRows>,
+    /// The number of times the recursive term was executed
+    iterations: usize,
+    /// The maximum number of times the recursive term may be executed
+    max_iterations: Option<usize>,
+    /// Runtime, for creating spill files
+    runtime: Arc<RuntimeEnv>,
+    /// Metrics.
+    baseline_metrics: BaselineMetrics,
+    /// Spilling metrics
+    spill_metrics: SpillMetrics,
 }
 
 impl RecursiveQueryStream {
     /// Create a new recursive query stream
-    fn new(
+    #[allow(clippy::too_many_arguments)]
+    fn try_new(
         task_context: Arc<TaskContext>,
+        name: String,
         work_table: Arc<WorkTable>,
         recursive_term: Arc<dyn ExecutionPlan>,
         static_stream: SendableRecordBatchStream,
+        is_distinct: bool,
         baseline_metrics: BaselineMetrics,
-    ) -> Self {
+        spill_metrics: SpillMetrics,
+    ) -> Result<Self> {
         let schema = static_stream.schema();
-        let reservation =
-            MemoryConsumer::new("RecursiveQuery").register(task_context.memory_pool());
-        Self {
+        let reservation = MemoryConsumer::new("RecursiveQuery")
+            .with_can_spill(true)
+            .register(task_context.memory_pool());
+        let distinct = is_distinct
+            .then(|| DistinctRows::try_new(Arc::clone(&schema), &task_context))
+            .transpose()?;
+        let max_iterations = task_context
+            .session_config()
+            .options()
+            .execution
+            .recursive_cte_max_iterations;
+        let runtime = task_context.runtime_env();
+        Ok(Self {
             task_context,
+            name,
             work_table,
             recursive_term,
             static_stream: Some(static_stream),
@@ -290,22 +342,83 @@ impl RecursiveQueryStream {
             schema,
             buffer: vec![],
             reservation,
-            _baseline_metrics: baseline_metrics,
-        }
+            spills: vec![],
+            distinct,
+            iterations: 0,
+            max_iterations,
+            runtime,
+            baseline_metrics,
+            spill_metrics,
+        })
     }
 
     /// Push a clone of the given batch to the in memory buffer, and then return
-    /// a poll with it.
+    /// a poll with it. With `UNION`, only the new rows of the batch are kept,
+    /// and `None` is returned if there are none.
     fn push_batch(
         mut self: std::pin::Pin<&mut Self>,
         batch: RecordBatch,
-    ) -> Poll<Option<Result<RecordBatch>>> {
-        if let Err(e) = self.reservation.try_grow(batch.get_array_memory_size()) {
-            return Poll::Ready(Some(Err(e)));
+    ) -> Option<Poll<Option<Result<RecordBatch>>>> {
+        // The recursive term may name its columns differently from the
+        // static term, whose schema is the schema of the query
+        let batch = if batch.schema() == self.schema {
+            batch
+        } else {
+            match RecordBatch::try_new(Arc::clone(&self.schema), batch.columns().to_vec())
+            {
+                Ok(batch) => batch,
+                Err(e) => return Some(Poll::Ready(Some(Err(e.into())))),
+            }
+        };
+        let batch = match self.distinct.as_mut() {
+            Some(distinct) => match distinct.new_rows(&batch) {
+                Ok(batch) => batch,
+                Err(e) => return Some(Poll::Ready(Some(Err(e)))),
+            },
+            None => batch,
+        };
+        if batch.num_rows() == 0 {
+            return None;
+        }
+
+        let size = get_record_batch_memory_size(&batch);
+        if let Err(e) = self.reservation.try_grow(size) {
+            if self.buffer.is_empty() || !self.runtime.disk_manager.tmp_files_enabled() {
+                return Some(Poll::Ready(Some(Err(e))));
+            }
+            if let Err(e) = self.as_mut().spill() {
+                return Some(Poll::Ready(Some(Err(e))));
+            }
+            if let Err(e) = self.reservation.try_grow(size) {
+                return Some(Poll::Ready(Some(Err(e))));
+            }
         }
 
         self.buffer.push(batch.clone());
-        Poll::Ready(Some(Ok(batch)))
+        Some(
+            self.baseline_metrics
+                .record_poll(Poll::Ready(Some(Ok(batch)))),
+        )
+    }
+
+    /// Writes the buffered results to a spill file, and frees their memory
+    fn spill(mut self: std::pin::Pin<&mut Self>) -> Result<()> {
+        let spill_file = self
+            .runtime
+            .disk_manager
+            .create_tmp_file("RecursiveQuery")?;
+        let batches = std::mem::take(&mut self.buffer);
+        let spilled_rows = spill_record_batches(
+            batches,
+            spill_file.path().into(),
+            Arc::clone(&self.schema),
+        )?;
+        let spilled_bytes = self.reservation.free();
+        self.spill_metrics.spill_count.add(1);
+        self.spill_metrics.spilled_bytes.add(spilled_bytes);
+        self.spill_metrics.spilled_rows.add(spilled_rows);
+        self.spills.push(spill_file);
+        Ok(())
     }
 
     /// Start polling for the next iteration, will be called either after the static term
@@ -320,15 +433,29 @@ impl RecursiveQueryStream {
             .iter()
             .fold(0, |acc, batch| acc + batch.num_rows());
 
-        if total_length == 0 {
+        if total_length == 0 && self.spills.is_empty() {
             return Poll::Ready(None);
         }
 
+        if let Some(max_iterations) = self.max_iterations {
+            if self.iterations >= max_iterations {
+                return Poll::Ready(Some(exec_err!(
+                    "Recursive query '{}' exceeded the maximum of {} iterations, \
+                    see datafusion.execution.recursive_cte_max_iterations",
+                    self.name,
+                    max_iterations
+                )));
+            }
+        }
+        self.iterations += 1;
+
         // Update the work table with the current buffer
+        let spills = std::mem::take(&mut self.spills);
         let reserved_batches = ReservedBatches::new(
             std::mem::take(&mut self.buffer),
             self.reservation.take(),
-        );
+        )
+        .with_spills(spills);
         self.work_table.update(reserved_batches);
 
         // We always execute (and re-execute iteratively) the first partition.
@@ -342,25 +469,54 @@ impl RecursiveQueryStream {
     }
 }
 
+/// Assigns `work_table` to the [`WorkTableExec`]s of the recursive query
+/// `name` in `plan`. The recursive term may contain other recursive queries,
+/// which assign their own work tables.
 fn assign_work_table(
     plan: Arc<dyn ExecutionPlan>,
+    name: &str,
     work_table: Arc<WorkTable>,
 ) -> Result<Arc<dyn ExecutionPlan>> {
     let mut work_table_refs = 0;
+    assign_work_table_refs(plan, name, &work_table, &mut work_table_refs)
+}
+
+fn assign_work_table_refs(
+    plan: Arc<dyn ExecutionPlan>,
+    name: &str,
+    work_table: &Arc<WorkTable>,
+    work_table_refs: &mut usize,
+) -> Result<Arc<dyn ExecutionPlan>> {
     plan.transform_down(|plan| {
         if let Some(exec) = plan.as_any().downcast_ref::<WorkTableExec>() {
-            if work_table_refs > 0 {
+            if exec.name() != name {
+                Ok(Transformed::no(plan))
+            } else if *work_table_refs > 0 {
                 not_impl_err!(
                     "Multiple recursive references to the same CTE are not supported"
                 )
             } else {
-                work_table_refs += 1;
+                *work_table_refs += 1;
                 Ok(Transformed::yes(Arc::new(
-                    exec.with_work_table(Arc::clone(&work_table)),
+                    exec.with_work_table(Arc::clone(work_table)),
                 )))
             }
-        } else if plan.as_any().is::<RecursiveQueryExec>() {
-            not_impl_err!("Recursive queries cannot be nested")
+        } else if let Some(exec) = plan
+            .as_any()
+            .downcast_ref::<RecursiveQueryExec>()
+            .filter(|exec| exec.name() == name)
+        {
+            // A nested recursive query of the same name shadows this one in
+            // its recursive term
+            let static_term = assign_work_table_refs(
+                Arc::clone(exec.static_term()),
+                name,
+                work_table,
+                work_table_refs,
+            )?;
+            let recursive_term = Arc::clone(exec.recursive_term());
+            let new_plan = plan.with_new_children(vec![static_term, recursive_term])?;
+            Ok(Transformed::new(new_plan, true, TreeNodeRecursion::Jump))
         } else {
             Ok(Transformed::no(plan))
         }
@@ -368,6 +524,77 @@ fn assign_work_table(
     .data()
 }
 
+/// Tracks the distinct rows produced by a recursive query with `UNION`
+struct DistinctRows {
+    /// The rows produced so far
+    group_values: Box<dyn GroupValues>,
+    /// Tracks the memory used by `group_values`
+    reservation: MemoryReservation,
+    /// Scratch space for the indices of the rows of a batch
+    groups: Vec<usize>,
+}
+
+impl DistinctRows {
+    fn try_new(schema: SchemaRef, task_context: &TaskContext) -> Result<Self> {
+        let group_values = new_group_values(schema, &GroupOrdering::None)?;
+        let reservation = MemoryConsumer::new("RecursiveQuery[distinct]")
+            .register(task_context.memory_pool());
+        Ok(Self {
+            group_values,
+            reservation,
+            groups: vec![],
+        })
+    }
+
+    /// Returns the rows of `batch` that were not seen before, and remembers
+    /// them
+    fn new_rows(&mut self, batch: &RecordBatch) -> Result<RecordBatch> {
+        let seen = self.group_values.len();
+        self.group_values
+            .intern(batch.columns(), &mut self.groups)?;
+        self.reservation.try_resize(self.group_values.size())?;
+        if self.group_values.len() == seen + batch.num_rows() {
+            // all rows are new
+            return Ok(batch.clone());
+        }
+
+        // keep the first occurrence of every new row
+        let mut emitted = vec![false; self.group_values.len() - seen];
+        let is_new_row: BooleanArray = self
+            .groups
+            .iter()
+            .map(|&group| {
+                let is_new = group >= seen && !emitted[group - seen];
+                if is_new {
+                    emitted[group - seen] = true;
+                }
+                Some(is_new)
+            })
+            .collect();
+        Ok(filter_record_batch(batch, &is_new_row)?)
+    }
+}
+
+/// Metrics for the spilling of the work table
+struct SpillMetrics {
+    /// count of spills during the execution of the operator
+    spill_count: Count,
+    /// total spilled bytes during the execution of the operator
+    spilled_bytes: Count,
+    /// total spilled rows during the execution of the operator
+    spilled_rows: Count,
+}
+
+impl SpillMetrics {
+    fn new(metrics: &ExecutionPlanMetricsSet, partition: usize) -> Self {
+        Self {
+            spill_count: MetricBuilder::new(metrics).spill_count(partition),
+            spilled_bytes: MetricBuilder::new(metrics).spilled_bytes(partition),
+            spilled_rows: MetricBuilder::new(metrics).spilled_rows(partition),
+        }
+    }
+}
+
 /// Some plans will change their internal states after execution, making them unable to be executed again.
 /// This function uses `ExecutionPlan::with_new_children` to fork a new plan with initial states.
 ///
@@ -395,32 +622,41 @@ impl Stream for RecursiveQueryStream {
         mut self: std::pin::Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        // TODO: we should use this poll to record some metrics!
-        if let Some(static_stream) = &mut self.static_stream {
-            // While the static term's stream is available, we'll be forwarding the batches from it (also
-            // saving them for the initial iteration of the recursive term).
-            let batch_result = ready!(static_stream.poll_next_unpin(cx));
-            match &batch_result {
-                None => {
-                    // Once this is done, we can start running the setup for the recursive term.
-                    self.static_stream = None;
-                    self.poll_next_iteration(cx)
+        loop {
+            if let Some(static_stream) = &mut self.static_stream {
+                // While the static term's stream is available, we'll be forwarding the batches from it (also
+                // saving them for the initial iteration of the recursive term).
+                let batch_result = ready!(static_stream.poll_next_unpin(cx));
+                match batch_result {
+                    None => {
+                        // Once this is done, we can start running the setup for the recursive term.
+                        self.static_stream = None;
+                        return self.poll_next_iteration(cx);
+                    }
+                    Some(Ok(batch)) => {
+                        if let Some(poll) = self.as_mut().push_batch(batch) {
+                            return poll;
+                        }
+                    }
+                    _ => return Poll::Ready(batch_result),
                 }
-                Some(Ok(batch)) => self.push_batch(batch.clone()),
-                _ => Poll::Ready(batch_result),
-            }
-        } else if let Some(recursive_stream) = &mut self.recursive_stream {
-            let batch_result = ready!(recursive_stream.poll_next_unpin(cx));
-            match batch_result {
-                None => {
-                    self.recursive_stream = None;
-                    self.poll_next_iteration(cx)
+            } else if let Some(recursive_stream) = &mut self.recursive_stream {
+                let batch_result = ready!(recursive_stream.poll_next_unpin(cx));
+                match batch_result {
+                    None => {
+                        self.recursive_stream = None;
+                        return self.poll_next_iteration(cx);
+                    }
+                    Some(Ok(batch)) => {
+                        if let Some(poll) = self.as_mut().push_batch(batch) {
+                            return poll;
+                        }
+                    }
+                    _ => return Poll::Ready(batch_result),
                 }
-                Some(Ok(batch)) => self.push_batch(batch),
-                _ => Poll::Ready(batch_result),
+            } else {
+                return Poll::Ready(None);
             }
-        } else {
-            Poll::Ready(None)
         }
     }
 }
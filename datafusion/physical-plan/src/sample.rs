@@ -0,0 +1,419 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the sampling plan, used to implement `TABLESAMPLE`
+
+use std::any::Any;
+use std::fmt;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use super::{DisplayAs, ExecutionPlanProperties, PlanProperties};
+use crate::execution_plan::CardinalityEffect;
+use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use crate::{
+    DisplayFormatType, ExecutionPlan, RecordBatchStream, SendableRecordBatchStream,
+};
+
+use ahash::RandomState;
+use arrow::array::BooleanArray;
+use arrow::compute::filter_record_batch;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{plan_err, Result, Statistics};
+use datafusion_execution::TaskContext;
+pub use datafusion_expr::SampleMethod;
+
+use futures::{Stream, StreamExt};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// A `SYSTEM` sample pushed into a data source with
+/// [`DataSource::with_sample`], which selects whole blocks of rows such as
+/// files or row groups.
+///
+/// [`DataSource::with_sample`]: crate::source::DataSource::with_sample
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemSample {
+    /// The probability of a block to be selected, between 0 and 1
+    pub fraction: f64,
+    /// The seed selecting the blocks
+    pub seed: u64,
+}
+
+impl SystemSample {
+    /// Create a new [`SystemSample`]
+    pub fn new(fraction: f64, seed: u64) -> Self {
+        Self { fraction, seed }
+    }
+
+    /// Returns true if the block identified by `key` is part of the sample.
+    /// The result only depends on the seed and the key, so a block is
+    /// selected consistently by every partition that reads it.
+    pub fn selects(&self, key: impl Hash) -> bool {
+        if self.fraction >= 1.0 {
+            return true;
+        }
+        // Unlike `DefaultHasher`, whose algorithm may change between Rust
+        // releases, hashing with fixed seeds selects the same blocks for the
+        // same seed
+        let random_state = RandomState::with_seeds(self.seed, 0, 0, 0);
+        (random_state.hash_one(key) as f64) < self.fraction * u64::MAX as f64
+    }
+}
+
+impl fmt::Display for SystemSample {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "sample_fraction={}, sample_seed={}",
+            self.fraction, self.seed
+        )
+    }
+}
+
+/// Estimates the statistics of a sample of `fraction` of the rows described
+/// by `statistics`
+pub fn sample_statistics(statistics: Statistics, fraction: f64) -> Statistics {
+    let mut statistics = statistics.to_inexact();
+    statistics.num_rows = statistics.num_rows.with_estimated_selectivity(fraction);
+    statistics.total_byte_size = statistics
+        .total_byte_size
+        .with_estimated_selectivity(fraction);
+    statistics
+}
+
+/// Returns a random sample of the rows of its input.
+///
+/// With [`SampleMethod::Bernoulli`], every row is selected independently
+/// with probability `fraction`. With [`SampleMethod::System`], whole batches
+/// are selected instead. This is only used if the sample can not be pushed
+/// into the data source with [`DataSourceExec::with_sample`].
+///
+/// Every partition selects its rows with its own random number generator,
+/// derived from `seed`, so the same seed selects the same rows as long as the
+/// input does not change.
+///
+/// [`DataSourceExec::with_sample`]: crate::source::DataSourceExec::with_sample
+#[derive(Debug, Clone)]
+pub struct SampleExec {
+    /// Input execution plan
+    input: Arc<dyn ExecutionPlan>,
+    /// How the rows are selected
+    method: SampleMethod,
+    /// The probability of a row or batch to be selected
+    fraction: f64,
+    /// The seed of the random number generators
+    seed: u64,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl SampleExec {
+    /// Create a new [`SampleExec`]
+    pub fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        method: SampleMethod,
+        fraction: f64,
+        seed: u64,
+    ) -> Result<Self> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return plan_err!(
+                "SampleExec fraction must be between 0 and 1, got {fraction}"
+            );
+        }
+        let cache = Self::compute_properties(&input);
+        Ok(Self {
+            input,
+            method,
+            fraction,
+            seed,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(input: &Arc<dyn ExecutionPlan>) -> PlanProperties {
+        // A subset of the rows keeps the orderings, equivalences and
+        // constants of the input
+        PlanProperties::new(
+            input.equivalence_properties().clone(),
+            input.output_partitioning().to_owned(),
+            input.pipeline_behavior(),
+            input.boundedness(),
+        )
+    }
+
+    /// Input execution plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// How the rows are selected
+    pub fn method(&self) -> SampleMethod {
+        self.method
+    }
+
+    /// The probability of a row or batch to be selected
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// The seed of the random number generators
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl DisplayAs for SampleExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "SampleExec: method={}, fraction={}, seed={}",
+                    self.method, self.fraction, self.seed
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for SampleExec {
+    fn name(&self) -> &'static str {
+        "SampleExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.input]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    /// Repartitioning the input would change the rows selected for a seed
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        vec![false]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(SampleExec::try_new(
+            Arc::clone(&children[0]),
+            self.method,
+            self.fraction,
+            self.seed,
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let input = self.input.execute(partition, context)?;
+        let seed = self.seed.wrapping_add(partition as u64);
+        Ok(Box::pin(SampleStream {
+            input,
+            method: self.method,
+            fraction: self.fraction,
+            rng: StdRng::seed_from_u64(seed),
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        Ok(sample_statistics(self.input.statistics()?, self.fraction))
+    }
+
+    fn cardinality_effect(&self) -> CardinalityEffect {
+        CardinalityEffect::LowerEqual
+    }
+}
+
+/// Selects the rows of a [`SampleExec`] partition
+struct SampleStream {
+    input: SendableRecordBatchStream,
+    method: SampleMethod,
+    fraction: f64,
+    rng: StdRng,
+    baseline_metrics: BaselineMetrics,
+}
+
+impl SampleStream {
+    /// Returns the selected rows of `batch`, if any
+    fn sample(&mut self, batch: RecordBatch) -> Result<Option<RecordBatch>> {
+        match self.method {
+            SampleMethod::System => Ok(self.rng.gen_bool(self.fraction).then_some(batch)),
+            SampleMethod::Bernoulli => {
+                let mask = (0..batch.num_rows())
+                    .map(|_| Some(self.rng.gen_bool(self.fraction)))
+                    .collect::<BooleanArray>();
+                let batch = filter_record_batch(&batch, &mask)?;
+                Ok((batch.num_rows() > 0).then_some(batch))
+            }
+        }
+    }
+
+    fn poll_next_impl(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        loop {
+            match ready!(self.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    let elapsed_compute = self.baseline_metrics.elapsed_compute().clone();
+                    let timer = elapsed_compute.timer();
+                    let sampled = self.sample(batch);
+                    timer.done();
+                    match sampled {
+                        Ok(Some(batch)) => return Poll::Ready(Some(Ok(batch))),
+                        Ok(None) => continue,
+                        Err(e) => return Poll::Ready(Some(Err(e))),
+                    }
+                }
+                other => return Poll::Ready(other),
+            }
+        }
+    }
+}
+
+impl Stream for SampleStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.poll_next_impl(cx);
+        self.baseline_metrics.record_poll(poll)
+    }
+}
+
+impl RecordBatchStream for SampleStream {
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collect;
+    use crate::memory::MemorySourceConfig;
+
+    use arrow::array::{Array, Int32Array};
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn input(num_batches: usize) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batches = (0..num_batches)
+            .map(|i| {
+                let values = (0..100).map(|v| (i * 100 + v) as i32);
+                RecordBatch::try_new(
+                    Arc::clone(&schema),
+                    vec![Arc::new(Int32Array::from_iter_values(values))],
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(MemorySourceConfig::try_new_exec(&[batches], schema, None)?)
+    }
+
+    async fn sample(method: SampleMethod, fraction: f64, seed: u64) -> Result<Vec<i32>> {
+        let exec = SampleExec::try_new(input(10)?, method, fraction, seed)?;
+        let batches = collect(Arc::new(exec), Arc::new(TaskContext::default())).await?;
+        Ok(batches
+            .iter()
+            .flat_map(|batch| {
+                let values = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap();
+                values.values().to_vec()
+            })
+            .collect())
+    }
+
+    #[tokio::test]
+    async fn bernoulli_sample() -> Result<()> {
+        let rows = sample(SampleMethod::Bernoulli, 0.3, 42).await?;
+        assert!(rows.len() > 200 && rows.len() < 400, "{}", rows.len());
+        assert!(rows.windows(2).all(|w| w[0] < w[1]));
+
+        // the same seed selects the same rows
+        assert_eq!(rows, sample(SampleMethod::Bernoulli, 0.3, 42).await?);
+
+        assert_eq!(sample(SampleMethod::Bernoulli, 1.0, 1).await?.len(), 1000);
+        assert!(sample(SampleMethod::Bernoulli, 0.0, 1).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn system_sample_selects_batches() -> Result<()> {
+        let rows = sample(SampleMethod::System, 0.5, 7).await?;
+        assert_eq!(rows.len() % 100, 0);
+        for batch in rows.chunks(100) {
+            assert_eq!(batch[0] % 100, 0);
+            assert_eq!(batch[99], batch[0] + 99);
+        }
+        assert_eq!(rows, sample(SampleMethod::System, 0.5, 7).await?);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_fraction() -> Result<()> {
+        let err =
+            SampleExec::try_new(input(1)?, SampleMethod::Bernoulli, 1.5, 1).unwrap_err();
+        assert!(err.to_string().contains("between 0 and 1"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn system_sample_selects_blocks() {
+        let sample = SystemSample::new(0.25, 1);
+        let selected = (0..1000).filter(|i| sample.selects(i)).count();
+        assert!(selected > 150 && selected < 350, "{selected}");
+        assert_eq!(
+            (0..1000).filter(|i| sample.selects(i)).collect::<Vec<_>>(),
+            (0..1000).filter(|i| sample.selects(i)).collect::<Vec<_>>()
+        );
+
+        assert!((0..100).all(|i| SystemSample::new(1.0, 3).selects(i)));
+        assert!(!(0..100).any(|i| SystemSample::new(0.0, 3).selects(i)));
+    }
+}
@@ -23,6 +23,7 @@ use std::sync::Arc;
 use crate::execution_plan::{Boundedness, EmissionType};
 use crate::metrics::{ExecutionPlanMetricsSet, MetricsSet};
 use crate::projection::ProjectionExec;
+use crate::sample::SystemSample;
 use crate::{DisplayAs, DisplayFormatType, ExecutionPlan, PlanProperties};
 
 use datafusion_common::config::ConfigOptions;
@@ -62,6 +63,11 @@ pub trait DataSource: Send + Sync {
         &self,
         _projection: &ProjectionExec,
     ) -> datafusion_common::Result<Option<Arc<dyn ExecutionPlan>>>;
+    /// Returns a new data source that only reads a `SYSTEM` sample of its
+    /// data, or `None` if the source does not support sampling
+    fn with_sample(&self, _sample: SystemSample) -> Option<Arc<dyn DataSource>> {
+        None
+    }
 }
 
 impl Debug for dyn DataSource {
@@ -179,6 +185,14 @@ impl DataSourceExec {
         &self.source
     }
 
+    /// Returns a new [`DataSourceExec`] that only reads a `SYSTEM` sample of
+    /// the source, or `None` if the source does not support sampling
+    pub fn with_sample(&self, sample: SystemSample) -> Option<Self> {
+        let source = self.source.with_sample(sample)?;
+        let cache = self.cache.clone();
+        Some(Self { source, cache })
+    }
+
     pub fn with_source(mut self, source: Arc<dyn DataSource>) -> Self {
         self.cache = Self::compute_properties(Arc::clone(&source));
         self.source = source;
@@ -333,9 +333,8 @@ impl UnnestStream {
 /// {a.item: 3, a.name: "b", b: 4}
 fn flatten_struct_cols(
     input_batch: &[Arc<dyn Array>],
-    schema: &SchemaRef,
     struct_column_indices: &HashSet<usize>,
-) -> Result<RecordBatch> {
+) -> Result<Vec<ArrayRef>> {
     // horizontal expansion because of struct unnest
     let columns_expanded = input_batch
         .iter()
@@ -359,7 +358,7 @@ fn flatten_struct_cols(
         .into_iter()
         .flatten()
         .collect();
-    Ok(RecordBatch::try_new(Arc::clone(schema), columns_expanded)?)
+    Ok(columns_expanded)
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -435,6 +434,10 @@ fn list_unnest_at_level(
                 None
             })
             .unzip();
+    let arrs_to_unnest = arrs_to_unnest
+        .into_iter()
+        .map(map_as_list)
+        .collect::<Result<Vec<_>>>()?;
 
     // Filter out so that list_arrays only contain column with the highest depth
     // at the same time, during iteration remove this depth so next time we don't have to unnest them again
@@ -558,8 +561,26 @@ fn build_batch(
     options: &UnnestOptions,
 ) -> Result<Option<RecordBatch>> {
     let transformed = match list_type_columns.len() {
-        0 => flatten_struct_cols(batch.columns(), schema, struct_column_indices),
+        0 => {
+            let mut columns =
+                flatten_struct_cols(batch.columns(), struct_column_indices)?;
+            if options.ordinality_column.is_some() {
+                // Unnesting structs does not add rows, every row is the first
+                columns.push(Arc::new(Int64Array::from_value(1, batch.num_rows())));
+            }
+            columns
+        }
         _ => {
+            // To compute the ordinality, track which input row each output
+            // row originates from with an extra column of row indices,
+            // which is repeated like any other column that is not unnested
+            let mut input_columns = batch.columns().to_vec();
+            if options.ordinality_column.is_some() {
+                input_columns.push(Arc::new(Int64Array::from_iter_values(
+                    0..batch.num_rows() as i64,
+                )));
+            }
+
             let mut temp_unnested_result = HashMap::new();
             let max_recursion = list_type_columns
                 .iter()
@@ -574,7 +595,7 @@ fn build_batch(
             // All unnesting results are written to temp_batch
             for depth in (1..=max_recursion).rev() {
                 let input = match depth == max_recursion {
-                    true => batch.columns(),
+                    true => &input_columns,
                     false => &flatten_arrs,
                 };
                 let Some(temp_result) = list_unnest_at_level(
@@ -654,7 +675,7 @@ fn build_batch(
                 )
                 .collect::<HashMap<_, _>>();
 
-            let ret = flatten_arrs
+            let mut ret = flatten_arrs
                 .into_iter()
                 .enumerate()
                 .flat_map(|(col_idx, arr)| {
@@ -668,10 +689,58 @@ fn build_batch(
                 })
                 .collect::<Vec<_>>();
 
-            flatten_struct_cols(&ret, schema, struct_column_indices)
+            let row_indices = options
+                .ordinality_column
+                .is_some()
+                .then(|| ret.pop())
+                .flatten();
+            let mut columns = flatten_struct_cols(&ret, struct_column_indices)?;
+            if let Some(row_indices) = row_indices {
+                columns.push(ordinality_from_row_indices(
+                    row_indices.as_primitive::<Int64Type>(),
+                ));
+            }
+            columns
+        }
+    };
+    Ok(Some(RecordBatch::try_new(Arc::clone(schema), transformed)?))
+}
+
+/// Computes the 1-based position of each output row among the rows unnested
+/// from the same input row, given the input row index of each output row.
+///
+/// For example, the row indices `[0, 0, 0, 2, 3, 3]` result in the
+/// positions `[1, 2, 3, 1, 1, 2]`.
+fn ordinality_from_row_indices(row_indices: &PrimitiveArray<Int64Type>) -> ArrayRef {
+    let mut previous_row = None;
+    let mut position = 0;
+    let positions = row_indices.values().iter().map(|&row| {
+        if previous_row == Some(row) {
+            position += 1;
+        } else {
+            previous_row = Some(row);
+            position = 1;
+        }
+        position
+    });
+    Arc::new(Int64Array::from_iter_values(positions))
+}
+
+/// Maps are unnested into their key-value entries, so a map array can be
+/// unnested as a list of structs
+fn map_as_list(array: ArrayRef) -> Result<ArrayRef> {
+    match array.data_type() {
+        DataType::Map(field, _) => {
+            let map = array.as_map();
+            Ok(Arc::new(ListArray::try_new(
+                Arc::clone(field),
+                map.offsets().clone(),
+                Arc::new(map.entries().clone()),
+                map.nulls().cloned(),
+            )?))
         }
-    }?;
-    Ok(Some(transformed))
+        _ => Ok(array),
+    }
 }
 
 /// Find the longest list length among the given list arrays for each row.
@@ -952,7 +1021,8 @@ fn repeat_arrs_from_indices(
 mod tests {
     use super::*;
     use arrow::array::{
-        GenericListArray, NullBufferBuilder, OffsetSizeTrait, StringArray,
+        GenericListArray, Int32Builder, MapBuilder, NullBufferBuilder, OffsetSizeTrait,
+        StringArray, StringBuilder,
     };
     use arrow::buffer::{NullBuffer, OffsetBuffer};
     use arrow::datatypes::{Field, Int32Type};
@@ -1139,6 +1209,7 @@ mod tests {
             &UnnestOptions {
                 preserve_nulls: true,
                 recursions: vec![],
+                ordinality_column: None,
             },
         )?
         .unwrap();
@@ -1173,6 +1244,126 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_build_batch_with_ordinality() -> Result<()> {
+        // col1        | col2
+        // [1, 2, 3]   | [10]
+        // []          | [20, 30]
+        // null        | null
+        // [4]         | []
+        let col1 = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2), Some(3)]),
+            Some(vec![]),
+            None,
+            Some(vec![Some(4)]),
+        ]);
+        let col2 = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(10)]),
+            Some(vec![Some(20), Some(30)]),
+            None,
+            Some(vec![]),
+        ]);
+        let batch = RecordBatch::try_from_iter(vec![
+            ("col1", Arc::new(col1) as ArrayRef),
+            ("col2", Arc::new(col2) as ArrayRef),
+        ])?;
+        let out_schema = Arc::new(Schema::new(vec![
+            Field::new("col1", DataType::Int32, true),
+            Field::new("col2", DataType::Int32, true),
+            Field::new("n", DataType::Int64, false),
+        ]));
+        let list_type_columns = vec![
+            ListUnnest {
+                index_in_input_schema: 0,
+                depth: 1,
+            },
+            ListUnnest {
+                index_in_input_schema: 1,
+                depth: 1,
+            },
+        ];
+        let ret = build_batch(
+            &batch,
+            &out_schema,
+            &list_type_columns,
+            &HashSet::default(),
+            &UnnestOptions::new()
+                .with_preserve_nulls(false)
+                .with_ordinality_column("n"),
+        )?
+        .unwrap();
+
+        let expected = &[
+            "+------+------+---+",
+            "| col1 | col2 | n |",
+            "+------+------+---+",
+            "| 1    | 10   | 1 |",
+            "| 2    |      | 2 |",
+            "| 3    |      | 3 |",
+            "|      | 20   | 1 |",
+            "|      | 30   | 2 |",
+            "| 4    |      | 1 |",
+            "+------+------+---+",
+        ];
+        assert_batches_eq!(expected, &[ret]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_batch_map() -> Result<()> {
+        // col1
+        // {a: 1, b: 2}
+        // null
+        // {c: 3}
+        let mut builder =
+            MapBuilder::new(None, StringBuilder::new(), Int32Builder::new());
+        builder.keys().append_value("a");
+        builder.values().append_value(1);
+        builder.keys().append_value("b");
+        builder.values().append_value(2);
+        builder.append(true)?;
+        builder.append(false)?;
+        builder.keys().append_value("c");
+        builder.values().append_value(3);
+        builder.append(true)?;
+        let map = builder.finish();
+
+        let DataType::Map(entries_field, _) = map.data_type().clone() else {
+            unreachable!()
+        };
+        let batch =
+            RecordBatch::try_from_iter(vec![("col1", Arc::new(map) as ArrayRef)])?;
+        let out_schema = Arc::new(Schema::new(vec![
+            entries_field.as_ref().clone().with_name("col1"),
+            Field::new("ordinality", DataType::Int64, false),
+        ]));
+        let ret = build_batch(
+            &batch,
+            &out_schema,
+            &[ListUnnest {
+                index_in_input_schema: 0,
+                depth: 1,
+            }],
+            &HashSet::default(),
+            &UnnestOptions::new()
+                .with_preserve_nulls(false)
+                .with_ordinality_column("ordinality"),
+        )?
+        .unwrap();
+
+        let expected = &[
+            "+----------------------+------------+",
+            "| col1                 | ordinality |",
+            "+----------------------+------------+",
+            "| {keys: a, values: 1} | 1          |",
+            "| {keys: b, values: 2} | 2          |",
+            "| {keys: c, values: 3} | 1          |",
+            "+----------------------+------------+",
+        ];
+        assert_batches_eq!(expected, &[ret]);
+        Ok(())
+    }
+
     #[test]
     fn test_unnest_list_array() -> Result<()> {
         // [A, B, C], [], NULL, [D], NULL, [NULL, F]
@@ -1226,6 +1417,7 @@ mod tests {
         let options = UnnestOptions {
             preserve_nulls,
             recursions: vec![],
+            ordinality_column: None,
         };
         let longest_length = find_longest_length(list_arrays, &options)?;
         let expected_array = Int64Array::from(expected);
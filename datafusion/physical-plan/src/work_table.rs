@@ -26,21 +26,28 @@ use super::{
 };
 use crate::execution_plan::{Boundedness, EmissionType};
 use crate::memory::MemoryStream;
+use crate::spill::read_spill_as_stream;
+use crate::stream::RecordBatchStreamAdapter;
 use crate::{DisplayAs, DisplayFormatType, ExecutionPlan, PlanProperties};
 
 use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
 use datafusion_common::{internal_datafusion_err, internal_err, Result};
+use datafusion_execution::disk_manager::RefCountedTempFile;
 use datafusion_execution::memory_pool::MemoryReservation;
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::{EquivalenceProperties, Partitioning};
 
-/// A vector of record batches with a memory reservation.
+use futures::StreamExt;
+
+/// A vector of record batches with a memory reservation, and the files the
+/// batches that did not fit in memory were spilled to.
 #[derive(Debug)]
 pub(super) struct ReservedBatches {
     batches: Vec<RecordBatch>,
     #[allow(dead_code)]
     reservation: MemoryReservation,
+    spills: Vec<RefCountedTempFile>,
 }
 
 impl ReservedBatches {
@@ -48,8 +55,15 @@ impl ReservedBatches {
         ReservedBatches {
             batches,
             reservation,
+            spills: vec![],
         }
     }
+
+    /// Adds spill files (in Arrow IPC format) holding more batches
+    pub(super) fn with_spills(mut self, spills: Vec<RefCountedTempFile>) -> Self {
+        self.spills = spills;
+        self
+    }
 }
 
 /// The name is from PostgreSQL's terminology.
@@ -211,10 +225,22 @@ impl ExecutionPlan for WorkTableExec {
             );
         }
         let batch = self.work_table.take()?;
-        Ok(Box::pin(
+        let memory_stream =
             MemoryStream::try_new(batch.batches, Arc::clone(&self.schema), None)?
-                .with_reservation(batch.reservation),
-        ))
+                .with_reservation(batch.reservation);
+        if batch.spills.is_empty() {
+            return Ok(Box::pin(memory_stream));
+        }
+
+        // read the spilled batches after the ones kept in memory
+        let mut streams: Vec<SendableRecordBatchStream> = vec![Box::pin(memory_stream)];
+        for spill in batch.spills {
+            streams.push(read_spill_as_stream(spill, Arc::clone(&self.schema), 2)?);
+        }
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            futures::stream::iter(streams).flatten(),
+        )))
     }
 
     fn metrics(&self) -> Option<MetricsSet> {
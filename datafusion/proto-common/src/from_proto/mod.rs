@@ -679,6 +679,10 @@ impl From<protobuf::Constraint> for Constraint {
             protobuf::constraint::ConstraintMode::Unique(elem) => Constraint::Unique(
                 elem.indices.into_iter().map(|item| item as usize).collect(),
             ),
+            protobuf::constraint::ConstraintMode::Check(elem) => Constraint::Check {
+                name: elem.name,
+                expr: elem.expr,
+            },
         }
     }
 }
@@ -808,6 +812,12 @@ impl From<&protobuf::Constraint> for Constraint {
                     elem.indices.iter().map(|&item| item as usize).collect(),
                 )
             }
+            Some(protobuf::constraint::ConstraintMode::Check(elem)) => {
+                Constraint::Check {
+                    name: elem.name.clone(),
+                    expr: elem.expr.clone(),
+                }
+            }
             None => panic!("constraint_mode not set"),
         }
     }
@@ -681,9 +681,15 @@ impl From<Constraint> for protobuf::Constraint {
             }
             Constraint::Unique(indices) => {
                 let indices = indices.into_iter().map(|item| item as u64).collect();
-                protobuf::constraint::ConstraintMode::PrimaryKey(
-                    protobuf::PrimaryKeyConstraint { indices },
-                )
+                protobuf::constraint::ConstraintMode::Unique(protobuf::UniqueConstraint {
+                    indices,
+                })
+            }
+            Constraint::Check { name, expr } => {
+                protobuf::constraint::ConstraintMode::Check(protobuf::CheckConstraint {
+                    name,
+                    expr,
+                })
             }
         };
         protobuf::Constraint {
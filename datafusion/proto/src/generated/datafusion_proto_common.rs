@@ -56,8 +56,15 @@ pub struct UniqueConstraint {
     pub indices: ::prost::alloc::vec::Vec<u64>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckConstraint {
+    #[prost(string, optional, tag = "1")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag = "2")]
+    pub expr: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Constraint {
-    #[prost(oneof = "constraint::ConstraintMode", tags = "1, 2")]
+    #[prost(oneof = "constraint::ConstraintMode", tags = "1, 2, 3")]
     pub constraint_mode: ::core::option::Option<constraint::ConstraintMode>,
 }
 /// Nested message and enum types in `Constraint`.
@@ -68,6 +75,8 @@ pub mod constraint {
         PrimaryKey(super::PrimaryKeyConstraint),
         #[prost(message, tag = "2")]
         Unique(super::UniqueConstraint),
+        #[prost(message, tag = "3")]
+        Check(super::CheckConstraint),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
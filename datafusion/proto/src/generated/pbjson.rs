@@ -4906,6 +4906,7 @@ impl serde::Serialize for dml_node::Type {
             Self::InsertAppend => "INSERT_APPEND",
             Self::InsertOverwrite => "INSERT_OVERWRITE",
             Self::InsertReplace => "INSERT_REPLACE",
+            Self::Merge => "MERGE",
         };
         serializer.serialize_str(variant)
     }
@@ -4923,6 +4924,7 @@ impl<'de> serde::Deserialize<'de> for dml_node::Type {
             "INSERT_APPEND",
             "INSERT_OVERWRITE",
             "INSERT_REPLACE",
+            "MERGE",
         ];
 
         struct GeneratedVisitor;
@@ -4969,6 +4971,7 @@ impl<'de> serde::Deserialize<'de> for dml_node::Type {
                     "INSERT_APPEND" => Ok(dml_node::Type::InsertAppend),
                     "INSERT_OVERWRITE" => Ok(dml_node::Type::InsertOverwrite),
                     "INSERT_REPLACE" => Ok(dml_node::Type::InsertReplace),
+                    "MERGE" => Ok(dml_node::Type::Merge),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -5761,6 +5764,9 @@ impl serde::Serialize for FileScanExecConf {
         if self.constraints.is_some() {
             len += 1;
         }
+        if self.sample.is_some() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion.FileScanExecConf", len)?;
         if !self.file_groups.is_empty() {
             struct_ser.serialize_field("fileGroups", &self.file_groups)?;
@@ -5789,6 +5795,9 @@ impl serde::Serialize for FileScanExecConf {
         if let Some(v) = self.constraints.as_ref() {
             struct_ser.serialize_field("constraints", v)?;
         }
+        if let Some(v) = self.sample.as_ref() {
+            struct_ser.serialize_field("sample", v)?;
+        }
         struct_ser.end()
     }
 }
@@ -5812,6 +5821,7 @@ impl<'de> serde::Deserialize<'de> for FileScanExecConf {
             "output_ordering",
             "outputOrdering",
             "constraints",
+            "sample",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -5825,6 +5835,7 @@ impl<'de> serde::Deserialize<'de> for FileScanExecConf {
             ObjectStoreUrl,
             OutputOrdering,
             Constraints,
+            Sample,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -5855,6 +5866,7 @@ impl<'de> serde::Deserialize<'de> for FileScanExecConf {
                             "objectStoreUrl" | "object_store_url" => Ok(GeneratedField::ObjectStoreUrl),
                             "outputOrdering" | "output_ordering" => Ok(GeneratedField::OutputOrdering),
                             "constraints" => Ok(GeneratedField::Constraints),
+                            "sample" => Ok(GeneratedField::Sample),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -5883,6 +5895,7 @@ impl<'de> serde::Deserialize<'de> for FileScanExecConf {
                 let mut object_store_url__ = None;
                 let mut output_ordering__ = None;
                 let mut constraints__ = None;
+                let mut sample__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::FileGroups => {
@@ -5942,6 +5955,12 @@ impl<'de> serde::Deserialize<'de> for FileScanExecConf {
                             }
                             constraints__ = map_.next_value()?;
                         }
+                        GeneratedField::Sample => {
+                            if sample__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sample"));
+                            }
+                            sample__ = map_.next_value()?;
+                        }
                     }
                 }
                 Ok(FileScanExecConf {
@@ -5954,6 +5973,7 @@ impl<'de> serde::Deserialize<'de> for FileScanExecConf {
                     object_store_url: object_store_url__.unwrap_or_default(),
                     output_ordering: output_ordering__.unwrap_or_default(),
                     constraints: constraints__,
+                    sample: sample__,
                 })
             }
         }
@@ -6800,6 +6820,9 @@ impl serde::Serialize for HashJoinExecNode {
         if !self.projection.is_empty() {
             len += 1;
         }
+        if self.null_aware {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion.HashJoinExecNode", len)?;
         if let Some(v) = self.left.as_ref() {
             struct_ser.serialize_field("left", v)?;
@@ -6829,6 +6852,9 @@ impl serde::Serialize for HashJoinExecNode {
         if !self.projection.is_empty() {
             struct_ser.serialize_field("projection", &self.projection)?;
         }
+        if self.null_aware {
+            struct_ser.serialize_field("nullAware", &self.null_aware)?;
+        }
         struct_ser.end()
     }
 }
@@ -6850,6 +6876,8 @@ impl<'de> serde::Deserialize<'de> for HashJoinExecNode {
             "nullEqualsNull",
             "filter",
             "projection",
+            "null_aware",
+            "nullAware",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -6862,6 +6890,7 @@ impl<'de> serde::Deserialize<'de> for HashJoinExecNode {
             NullEqualsNull,
             Filter,
             Projection,
+            NullAware,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -6891,6 +6920,7 @@ impl<'de> serde::Deserialize<'de> for HashJoinExecNode {
                             "nullEqualsNull" | "null_equals_null" => Ok(GeneratedField::NullEqualsNull),
                             "filter" => Ok(GeneratedField::Filter),
                             "projection" => Ok(GeneratedField::Projection),
+                            "nullAware" | "null_aware" => Ok(GeneratedField::NullAware),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -6918,6 +6948,7 @@ impl<'de> serde::Deserialize<'de> for HashJoinExecNode {
                 let mut null_equals_null__ = None;
                 let mut filter__ = None;
                 let mut projection__ = None;
+                let mut null_aware__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Left => {
@@ -6971,6 +7002,12 @@ impl<'de> serde::Deserialize<'de> for HashJoinExecNode {
                                     .into_iter().map(|x| x.0).collect())
                             ;
                         }
+                        GeneratedField::NullAware => {
+                            if null_aware__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("nullAware"));
+                            }
+                            null_aware__ = Some(map_.next_value()?);
+                        }
                     }
                 }
                 Ok(HashJoinExecNode {
@@ -6982,6 +7019,7 @@ impl<'de> serde::Deserialize<'de> for HashJoinExecNode {
                     null_equals_null: null_equals_null__.unwrap_or_default(),
                     filter: filter__,
                     projection: projection__.unwrap_or_default(),
+                    null_aware: null_aware__.unwrap_or_default(),
                 })
             }
         }
@@ -8421,6 +8459,9 @@ impl serde::Serialize for JoinNode {
         if self.filter.is_some() {
             len += 1;
         }
+        if self.null_aware {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion.JoinNode", len)?;
         if let Some(v) = self.left.as_ref() {
             struct_ser.serialize_field("left", v)?;
@@ -8450,6 +8491,9 @@ impl serde::Serialize for JoinNode {
         if let Some(v) = self.filter.as_ref() {
             struct_ser.serialize_field("filter", v)?;
         }
+        if self.null_aware {
+            struct_ser.serialize_field("nullAware", &self.null_aware)?;
+        }
         struct_ser.end()
     }
 }
@@ -8473,6 +8517,8 @@ impl<'de> serde::Deserialize<'de> for JoinNode {
             "null_equals_null",
             "nullEqualsNull",
             "filter",
+            "null_aware",
+            "nullAware",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -8485,6 +8531,7 @@ impl<'de> serde::Deserialize<'de> for JoinNode {
             RightJoinKey,
             NullEqualsNull,
             Filter,
+            NullAware,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -8514,6 +8561,7 @@ impl<'de> serde::Deserialize<'de> for JoinNode {
                             "rightJoinKey" | "right_join_key" => Ok(GeneratedField::RightJoinKey),
                             "nullEqualsNull" | "null_equals_null" => Ok(GeneratedField::NullEqualsNull),
                             "filter" => Ok(GeneratedField::Filter),
+                            "nullAware" | "null_aware" => Ok(GeneratedField::NullAware),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -8541,6 +8589,7 @@ impl<'de> serde::Deserialize<'de> for JoinNode {
                 let mut right_join_key__ = None;
                 let mut null_equals_null__ = None;
                 let mut filter__ = None;
+                let mut null_aware__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::Left => {
@@ -8591,6 +8640,12 @@ impl<'de> serde::Deserialize<'de> for JoinNode {
                             }
                             filter__ = map_.next_value()?;
                         }
+                        GeneratedField::NullAware => {
+                            if null_aware__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("nullAware"));
+                            }
+                            null_aware__ = Some(map_.next_value()?);
+                        }
                     }
                 }
                 Ok(JoinNode {
@@ -8602,6 +8657,7 @@ impl<'de> serde::Deserialize<'de> for JoinNode {
                     right_join_key: right_join_key__.unwrap_or_default(),
                     null_equals_null: null_equals_null__.unwrap_or_default(),
                     filter: filter__,
+                    null_aware: null_aware__.unwrap_or_default(),
                 })
             }
         }
@@ -20074,6 +20130,120 @@ impl<'de> serde::Deserialize<'de> for SymmetricHashJoinExecNode {
         deserializer.deserialize_struct("datafusion.SymmetricHashJoinExecNode", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for SystemSample {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.fraction != 0. {
+            len += 1;
+        }
+        if self.seed != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("datafusion.SystemSample", len)?;
+        if self.fraction != 0. {
+            struct_ser.serialize_field("fraction", &self.fraction)?;
+        }
+        if self.seed != 0 {
+            #[allow(clippy::needless_borrow)]
+            #[allow(clippy::needless_borrows_for_generic_args)]
+            struct_ser.serialize_field("seed", ToString::to_string(&self.seed).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SystemSample {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "fraction",
+            "seed",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Fraction,
+            Seed,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "fraction" => Ok(GeneratedField::Fraction),
+                            "seed" => Ok(GeneratedField::Seed),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SystemSample;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct datafusion.SystemSample")
+            }
+
+            fn visit_map<V>(self, mut map_: V) -> std::result::Result<SystemSample, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut fraction__ = None;
+                let mut seed__ = None;
+                while let Some(k) = map_.next_key()? {
+                    match k {
+                        GeneratedField::Fraction => {
+                            if fraction__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("fraction"));
+                            }
+                            fraction__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Seed => {
+                            if seed__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("seed"));
+                            }
+                            seed__ = 
+                                Some(map_.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(SystemSample {
+                    fraction: fraction__.unwrap_or_default(),
+                    seed: seed__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("datafusion.SystemSample", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for TableReference {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -21050,6 +21220,9 @@ impl serde::Serialize for UnnestOptions {
         if !self.recursions.is_empty() {
             len += 1;
         }
+        if !self.ordinality_column.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion.UnnestOptions", len)?;
         if self.preserve_nulls {
             struct_ser.serialize_field("preserveNulls", &self.preserve_nulls)?;
@@ -21057,6 +21230,9 @@ impl serde::Serialize for UnnestOptions {
         if !self.recursions.is_empty() {
             struct_ser.serialize_field("recursions", &self.recursions)?;
         }
+        if !self.ordinality_column.is_empty() {
+            struct_ser.serialize_field("ordinalityColumn", &self.ordinality_column)?;
+        }
         struct_ser.end()
     }
 }
@@ -21070,12 +21246,15 @@ impl<'de> serde::Deserialize<'de> for UnnestOptions {
             "preserve_nulls",
             "preserveNulls",
             "recursions",
+            "ordinality_column",
+            "ordinalityColumn",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             PreserveNulls,
             Recursions,
+            OrdinalityColumn,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -21099,6 +21278,7 @@ impl<'de> serde::Deserialize<'de> for UnnestOptions {
                         match value {
                             "preserveNulls" | "preserve_nulls" => Ok(GeneratedField::PreserveNulls),
                             "recursions" => Ok(GeneratedField::Recursions),
+                            "ordinalityColumn" | "ordinality_column" => Ok(GeneratedField::OrdinalityColumn),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -21120,6 +21300,7 @@ impl<'de> serde::Deserialize<'de> for UnnestOptions {
             {
                 let mut preserve_nulls__ = None;
                 let mut recursions__ = None;
+                let mut ordinality_column__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::PreserveNulls => {
@@ -21134,11 +21315,18 @@ impl<'de> serde::Deserialize<'de> for UnnestOptions {
                             }
                             recursions__ = Some(map_.next_value()?);
                         }
+                        GeneratedField::OrdinalityColumn => {
+                            if ordinality_column__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ordinalityColumn"));
+                            }
+                            ordinality_column__ = Some(map_.next_value()?);
+                        }
                     }
                 }
                 Ok(UnnestOptions {
                     preserve_nulls: preserve_nulls__.unwrap_or_default(),
                     recursions: recursions__.unwrap_or_default(),
+                    ordinality_column: ordinality_column__.unwrap_or_default(),
                 })
             }
         }
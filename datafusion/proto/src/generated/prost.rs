@@ -373,6 +373,8 @@ pub struct JoinNode {
     pub null_equals_null: bool,
     #[prost(message, optional, tag = "8")]
     pub filter: ::core::option::Option<LogicalExprNode>,
+    #[prost(bool, tag = "9")]
+    pub null_aware: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DistinctNode {
@@ -433,6 +435,7 @@ pub mod dml_node {
         InsertAppend = 3,
         InsertOverwrite = 4,
         InsertReplace = 5,
+        Merge = 6,
     }
     impl Type {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -447,6 +450,7 @@ pub mod dml_node {
                 Self::InsertAppend => "INSERT_APPEND",
                 Self::InsertOverwrite => "INSERT_OVERWRITE",
                 Self::InsertReplace => "INSERT_REPLACE",
+                Self::Merge => "MERGE",
             }
         }
         /// Creates an enum from field names used in the ProtoBuf definition.
@@ -458,6 +462,7 @@ pub mod dml_node {
                 "INSERT_APPEND" => Some(Self::InsertAppend),
                 "INSERT_OVERWRITE" => Some(Self::InsertOverwrite),
                 "INSERT_REPLACE" => Some(Self::InsertReplace),
+                "MERGE" => Some(Self::Merge),
                 _ => None,
             }
         }
@@ -505,6 +510,9 @@ pub struct UnnestOptions {
     pub preserve_nulls: bool,
     #[prost(message, repeated, tag = "2")]
     pub recursions: ::prost::alloc::vec::Vec<RecursionUnnestOption>,
+    /// Empty if no ordinality column is emitted
+    #[prost(string, tag = "3")]
+    pub ordinality_column: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RecursionUnnestOption {
@@ -1510,6 +1518,15 @@ pub struct FileScanExecConf {
     pub output_ordering: ::prost::alloc::vec::Vec<PhysicalSortExprNodeCollection>,
     #[prost(message, optional, tag = "11")]
     pub constraints: ::core::option::Option<super::datafusion_common::Constraints>,
+    #[prost(message, optional, tag = "12")]
+    pub sample: ::core::option::Option<SystemSample>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SystemSample {
+    #[prost(double, tag = "1")]
+    pub fraction: f64,
+    #[prost(uint64, tag = "2")]
+    pub seed: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ParquetScanExecNode {
@@ -1575,6 +1592,8 @@ pub struct HashJoinExecNode {
     pub filter: ::core::option::Option<JoinFilter>,
     #[prost(uint32, repeated, tag = "9")]
     pub projection: ::prost::alloc::vec::Vec<u32>,
+    #[prost(bool, tag = "10")]
+    pub null_aware: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SymmetricHashJoinExecNode {
@@ -66,6 +66,8 @@ impl From<&protobuf::UnnestOptions> for UnnestOptions {
                     depth: r.depth as usize,
                 })
                 .collect::<Vec<_>>(),
+            ordinality_column: (!opts.ordinality_column.is_empty())
+                .then(|| opts.ordinality_column.clone()),
         }
     }
 }
@@ -229,6 +231,7 @@ impl From<protobuf::dml_node::Type> for WriteOp {
             }
             protobuf::dml_node::Type::InsertReplace => WriteOp::Insert(InsertOp::Replace),
             protobuf::dml_node::Type::Ctas => WriteOp::Ctas,
+            protobuf::dml_node::Type::Merge => WriteOp::Merge,
         }
     }
 }
@@ -735,7 +735,15 @@ impl AsLogicalPlan for LogicalPlanNode {
                     }
                 };
 
-                builder.build()
+                match builder.build()? {
+                    LogicalPlan::Join(plan) if join.null_aware => {
+                        Ok(LogicalPlan::Join(Join {
+                            null_aware: true,
+                            ..plan
+                        }))
+                    }
+                    plan => Ok(plan),
+                }
             }
             LogicalPlanType::Union(union) => {
                 if union.inputs.len() < 2 {
@@ -1251,6 +1259,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 join_type,
                 join_constraint,
                 null_equals_null,
+                null_aware,
                 ..
             }) => {
                 let left: LogicalPlanNode = LogicalPlanNode::try_from_logical_plan(
@@ -1290,6 +1299,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                             right_join_key,
                             null_equals_null: *null_equals_null,
                             filter,
+                            null_aware: *null_aware,
                         },
                     ))),
                 })
@@ -1622,12 +1632,31 @@ impl AsLogicalPlan for LogicalPlanNode {
                     ))),
                 })
             }
+            LogicalPlan::GapFill(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for GapFill",
+            )),
+            LogicalPlan::Sample(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for Sample",
+            )),
             LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for CreateMemoryTable",
             )),
             LogicalPlan::Ddl(DdlStatement::CreateIndex(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for CreateIndex",
             )),
+            LogicalPlan::Ddl(DdlStatement::CommentOn(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for CommentOn",
+            )),
+            LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(_)) => {
+                Err(proto_error(
+                    "LogicalPlan serde is not yet implemented for CreateMaterializedView",
+                ))
+            }
+            LogicalPlan::Ddl(DdlStatement::RefreshMaterializedView(_)) => {
+                Err(proto_error(
+                    "LogicalPlan serde is not yet implemented for RefreshMaterializedView",
+                ))
+            }
             LogicalPlan::Ddl(DdlStatement::DropTable(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for DropTable",
             )),
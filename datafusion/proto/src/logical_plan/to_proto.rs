@@ -62,6 +62,7 @@ impl From<&UnnestOptions> for protobuf::UnnestOptions {
                     depth: r.depth as u32,
                 })
                 .collect(),
+            ordinality_column: opts.ordinality_column.clone().unwrap_or_default(),
         }
     }
 }
@@ -561,11 +562,12 @@ pub fn serialize_expr(
         },
         Expr::ScalarSubquery(_)
         | Expr::InSubquery(_)
+        | Expr::SetComparison(_)
         | Expr::Exists { .. }
         | Expr::OuterReferenceColumn { .. } => {
             // we would need to add logical plan operators to datafusion.proto to support this
             // see discussion in https://github.com/apache/datafusion/issues/2565
-            return Err(Error::General("Proto serialization error: Expr::ScalarSubquery(_) | Expr::InSubquery(_) | Expr::Exists { .. } | Exp:OuterReferenceColumn not supported".to_string()));
+            return Err(Error::General("Proto serialization error: Expr::ScalarSubquery(_) | Expr::InSubquery(_) | Expr::SetComparison(_) | Expr::Exists { .. } | Exp:OuterReferenceColumn not supported".to_string()));
         }
         Expr::GroupingSet(GroupingSet::Cube(exprs)) => protobuf::LogicalExprNode {
             expr_type: Some(ExprType::Cube(CubeNode {
@@ -700,6 +702,7 @@ impl From<&WriteOp> for protobuf::dml_node::Type {
             WriteOp::Delete => protobuf::dml_node::Type::Delete,
             WriteOp::Update => protobuf::dml_node::Type::Update,
             WriteOp::Ctas => protobuf::dml_node::Type::Ctas,
+            WriteOp::Merge => protobuf::dml_node::Type::Merge,
         }
     }
 }
@@ -41,6 +41,7 @@ use datafusion::physical_plan::expressions::{
     in_list, BinaryExpr, CaseExpr, CastExpr, Column, IsNotNullExpr, IsNullExpr, LikeExpr,
     Literal, NegativeExpr, NotExpr, TryCastExpr, UnKnownColumn,
 };
+use datafusion::physical_plan::sample::SystemSample;
 use datafusion::physical_plan::windows::{create_window_expr, schema_add_window_field};
 use datafusion::physical_plan::{Partitioning, PhysicalExpr, WindowExpr};
 use datafusion_common::{not_impl_err, DataFusionError, Result};
@@ -545,7 +546,12 @@ pub fn parse_protobuf_file_scan_config(
         .with_projection(projection)
         .with_limit(proto.limit.as_ref().map(|sl| sl.limit as usize))
         .with_table_partition_cols(table_partition_cols)
-        .with_output_ordering(output_ordering))
+        .with_output_ordering(output_ordering)
+        .with_sample(
+            proto
+                .sample
+                .map(|sample| SystemSample::new(sample.fraction, sample.seed)),
+        ))
 }
 
 impl TryFrom<&protobuf::PartitionedFile> for PartitionedFile {
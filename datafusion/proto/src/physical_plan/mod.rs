@@ -675,16 +675,19 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
                 } else {
                     None
                 };
-                Ok(Arc::new(HashJoinExec::try_new(
-                    left,
-                    right,
-                    on,
-                    filter,
-                    &join_type.into(),
-                    projection,
-                    partition_mode,
-                    hashjoin.null_equals_null,
-                )?))
+                Ok(Arc::new(
+                    HashJoinExec::try_new(
+                        left,
+                        right,
+                        on,
+                        filter,
+                        &join_type.into(),
+                        projection,
+                        partition_mode,
+                        hashjoin.null_equals_null,
+                    )?
+                    .with_null_aware(hashjoin.null_aware)?,
+                ))
             }
             PhysicalPlanType::SymmetricHashJoin(sym_join) => {
                 let left = into_physical_plan(
@@ -1365,6 +1368,7 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
                         projection: exec.projection.as_ref().map_or_else(Vec::new, |v| {
                             v.iter().map(|x| *x as u32).collect::<Vec<u32>>()
                         }),
+                        null_aware: exec.null_aware(),
                     },
                 ))),
             });
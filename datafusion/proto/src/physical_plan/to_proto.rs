@@ -530,6 +530,10 @@ pub fn serialize_file_scan_config(
             })
             .collect::<Vec<_>>(),
         constraints: Some(conf.constraints.clone().into()),
+        sample: conf.sample().map(|sample| protobuf::SystemSample {
+            fraction: sample.fraction,
+            seed: sample.seed,
+        }),
     })
 }
 
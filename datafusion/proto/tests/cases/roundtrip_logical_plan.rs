@@ -270,8 +270,9 @@ async fn roundtrip_custom_listing_tables() -> Result<()> {
               a INTEGER DEFAULT 1*2 + 3,
               b INTEGER DEFAULT NULL,
               c INTEGER,
-              d INTEGER,
-              primary key(c)
+              d INTEGER CHECK (d >= 0),
+              primary key(c),
+              constraint valid_a check (a0 < a)
             )
             STORED AS CSV
             WITH ORDER (a ASC, b ASC)
@@ -837,8 +838,32 @@ async fn roundtrip_logical_plan_unnest() -> Result<()> {
         CsvReadOptions::default().schema(&schema),
     )
     .await?;
-    let query = "SELECT unnest(b) FROM t1";
-    let plan = ctx.sql(query).await?.into_optimized_plan()?;
+    let queries = [
+        "SELECT unnest(b) FROM t1",
+        "SELECT * FROM unnest([1, 2, 3]) WITH ORDINALITY AS u(v, n)",
+    ];
+    for query in queries {
+        let plan = ctx.sql(query).await?.into_optimized_plan()?;
+        let bytes = logical_plan_to_bytes(&plan)?;
+        let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+        assert_eq!(format!("{plan}"), format!("{logical_round_trip}"));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn roundtrip_logical_plan_null_aware_join() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.register_csv("t1", "tests/testdata/test.csv", CsvReadOptions::default())
+        .await?;
+    ctx.register_csv("t2", "tests/testdata/test.csv", CsvReadOptions::default())
+        .await?;
+    let plan = ctx
+        .sql("SELECT a FROM t1 WHERE a NOT IN (SELECT b FROM t2)")
+        .await?
+        .into_optimized_plan()?;
+    assert!(format!("{plan}").contains("LeftAnti Join (NullAware)"));
+
     let bytes = logical_plan_to_bytes(&plan)?;
     let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
     assert_eq!(format!("{plan}"), format!("{logical_round_trip}"));
@@ -2274,6 +2299,36 @@ fn roundtrip_scalar_udf() {
     roundtrip_expr_test(test_expr, ctx);
 }
 
+#[test]
+fn roundtrip_schema_scalar_udf() {
+    let scalar_fn = Arc::new(|args: &[ColumnarValue]| {
+        let ColumnarValue::Array(array) = &args[0] else {
+            panic!("should be array")
+        };
+        Ok(ColumnarValue::from(Arc::new(array.clone()) as ArrayRef))
+    });
+
+    let udf = create_udf(
+        "dummy",
+        vec![DataType::Utf8],
+        DataType::Utf8,
+        Volatility::Immutable,
+        scalar_fn,
+    );
+
+    // functions registered in a schema are serialized by their qualified name
+    let test_expr = Expr::ScalarFunction(ScalarFunction::new_udf(
+        Arc::new(udf.clone().with_schema("datafusion", "public")),
+        vec![lit("")],
+    ));
+
+    let ctx = SessionContext::new();
+    let schema = ctx.catalog("datafusion").unwrap().schema("public").unwrap();
+    schema.register_udf(Arc::new(udf)).unwrap();
+
+    roundtrip_expr_test(test_expr, ctx);
+}
+
 #[test]
 fn roundtrip_scalar_udf_extension_codec() {
     let udf = ScalarUDF::from(MyRegexUdf::new(".*".to_owned()));
@@ -15,20 +15,58 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::ops::ControlFlow;
 use std::sync::Arc;
 
-use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use crate::parser::{
+    CopyToSource, CopyToStatement, CycleClause, RecursiveCteClauses,
+    RecursiveCteStatement, Statement as DFStatement,
+};
+use crate::planner::{ContextProvider, IdentNormalizer, PlannerContext, SqlToRel};
 
 use arrow::datatypes::Schema;
 use datafusion_common::{
-    not_impl_err, plan_err,
+    not_impl_err, plan_datafusion_err, plan_err,
     tree_node::{TreeNode, TreeNodeRecursion},
-    Result,
+    DataFusionError, Result,
 };
 use datafusion_expr::{LogicalPlan, LogicalPlanBuilder, TableSource};
-use sqlparser::ast::{Query, SetExpr, SetOperator, With};
+use sqlparser::ast::{
+    BinaryOperator, Cte, Expr, Function, FunctionArg, FunctionArgExpr,
+    FunctionArgumentList, FunctionArguments, Ident, ObjectName, Query, Select,
+    SelectItem, SetExpr, SetOperator, TableAliasColumnDef, TableFactor, Value, VisitMut,
+    VisitorMut, With,
+};
 
 impl<S: ContextProvider> SqlToRel<'_, S> {
+    /// Generate a logical plan from a statement with `SEARCH` or `CYCLE`
+    /// clauses on its recursive CTEs, see [`rewrite_recursive_cte`]
+    pub(super) fn recursive_cte_statement_to_plan(
+        &self,
+        statement: RecursiveCteStatement,
+    ) -> Result<LogicalPlan> {
+        let RecursiveCteStatement {
+            mut statement,
+            clauses,
+        } = statement;
+        let mut rewriter = RecursiveCteRewriter {
+            clauses: clauses.into_iter().map(Some).collect(),
+            normalizer: &self.ident_normalizer,
+        };
+        let result = match statement.as_mut() {
+            DFStatement::Statement(statement) => statement.as_mut().visit(&mut rewriter),
+            DFStatement::CopyTo(CopyToStatement {
+                source: CopyToSource::Query(query),
+                ..
+            }) => query.as_mut().visit(&mut rewriter),
+            _ => ControlFlow::Continue(()),
+        };
+        if let ControlFlow::Break(e) = result {
+            return Err(e);
+        }
+        self.statement_to_plan(*statement)
+    }
+
     pub(super) fn plan_with_clause(
         &self,
         with: With,
@@ -47,7 +85,13 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
             // Create a logical plan for the CTE
             let cte_plan = if is_recursive {
-                self.recursive_cte(cte_name.clone(), *cte.query, planner_context)?
+                let columns = cte.alias.columns.iter().map(|c| c.name.clone()).collect();
+                self.recursive_cte(
+                    cte_name.clone(),
+                    columns,
+                    *cte.query,
+                    planner_context,
+                )?
             } else {
                 self.non_recursive_cte(*cte.query, planner_context)?
             };
@@ -72,6 +116,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     fn recursive_cte(
         &self,
         cte_name: String,
+        columns: Vec<Ident>,
         mut cte_query: Query,
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
@@ -98,6 +143,13 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             }
         };
 
+        // The CTEs defined in the query of the CTE are only visible within it
+        let mut cte_planner_context = planner_context.clone();
+        let planner_context = &mut cte_planner_context;
+        if let Some(with) = cte_query.with {
+            self.plan_with_clause(with, planner_context)?;
+        }
+
         // Each recursive CTE consists of two parts in the logical plan:
         //   1. A static term   (the left-hand side on the SQL, where the
         //                       referencing to the same CTE is not allowed)
@@ -111,6 +163,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
         // ---------- Step 1: Compile the static term ------------------
         let static_plan = self.set_expr_to_plan(*left_expr, planner_context)?;
+        // The recursive term refers to the columns of the CTE by the names
+        // given in its column list, if any
+        let static_plan = self.apply_expr_alias(static_plan, columns)?;
 
         // Since the recursive CTEs include a component that references a
         // table with its name, like the example below:
@@ -187,6 +242,349 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     }
 }
 
+/// Rewrites the CTEs that have `SEARCH` or `CYCLE` clauses
+struct RecursiveCteRewriter<'a> {
+    /// The clauses not yet applied, in the order of their CTEs
+    clauses: Vec<Option<RecursiveCteClauses>>,
+    normalizer: &'a IdentNormalizer,
+}
+
+impl VisitorMut for RecursiveCteRewriter<'_> {
+    type Break = DataFusionError;
+
+    fn pre_visit_query(&mut self, query: &mut Query) -> ControlFlow<DataFusionError> {
+        let Some(with) = query.with.as_mut() else {
+            return ControlFlow::Continue(());
+        };
+        for cte in with.cte_tables.iter_mut() {
+            let clauses = self.clauses.iter_mut().find_map(|clauses| {
+                clauses.take_if(|clauses| clauses.cte_name.span == cte.alias.name.span)
+            });
+            if let Some(clauses) = clauses {
+                if let Err(e) = rewrite_recursive_cte(cte, clauses, self.normalizer) {
+                    return ControlFlow::Break(e);
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Rewrites the `SEARCH` and `CYCLE` clauses of a recursive CTE into
+/// additional columns of its static and recursive terms.
+///
+/// For example
+///
+/// ```sql
+/// WITH RECURSIVE t(id) AS (
+///     SELECT 1
+///     UNION ALL
+///     SELECT e.dst FROM edges e JOIN t ON e.src = t.id
+/// ) CYCLE id SET is_cycle USING path
+/// ```
+///
+/// is planned as
+///
+/// ```sql
+/// WITH RECURSIVE t(id, is_cycle, path) AS (
+///     SELECT 1, FALSE, make_array(1)
+///     UNION ALL
+///     SELECT
+///         e.dst,
+///         CASE WHEN array_has(t.path, e.dst) THEN TRUE ELSE FALSE END,
+///         array_append(t.path, e.dst)
+///     FROM edges e JOIN t ON e.src = t.id
+///     WHERE t.is_cycle = FALSE
+/// )
+/// ```
+///
+/// `SEARCH DEPTH FIRST` adds the path of the key columns as the sequence
+/// column, while `SEARCH BREADTH FIRST` adds a struct of the recursion depth
+/// and the key columns. Ordering by the sequence column returns the rows in
+/// depth first or breadth first order respectively.
+fn rewrite_recursive_cte(
+    cte: &mut Cte,
+    clauses: RecursiveCteClauses,
+    normalizer: &IdentNormalizer,
+) -> Result<()> {
+    let name = cte.alias.name.clone();
+    let SetExpr::SetOperation {
+        op: SetOperator::Union,
+        left,
+        right,
+        ..
+    } = cte.query.body.as_mut()
+    else {
+        return plan_err!(
+            "SEARCH and CYCLE clauses require the query of CTE {name} to be a UNION of a static and a recursive term"
+        );
+    };
+    let (SetExpr::Select(static_term), SetExpr::Select(recursive_term)) =
+        (left.as_mut(), right.as_mut())
+    else {
+        return plan_err!(
+            "SEARCH and CYCLE clauses require the static and recursive terms of CTE {name} to be SELECT statements"
+        );
+    };
+
+    let columns = if cte.alias.columns.is_empty() {
+        static_term
+            .projection
+            .iter()
+            .map(|item| select_item_name(item, &name))
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        cte.alias.columns.iter().map(|c| c.name.clone()).collect()
+    };
+    let columns = columns
+        .into_iter()
+        .map(|column| normalizer.normalize(column))
+        .collect::<Vec<_>>();
+    let static_exprs = select_item_exprs(&static_term.projection, &name)?;
+    let recursive_exprs = select_item_exprs(&recursive_term.projection, &name)?;
+    let Some(work_table) = work_table_reference(recursive_term, &name, normalizer) else {
+        return plan_err!(
+            "The recursive term of CTE {name} must reference {name} in its FROM clause"
+        );
+    };
+    let work_table_column = |column: &Ident| {
+        Expr::CompoundIdentifier(vec![work_table.clone(), column.clone()])
+    };
+    let key_expr = |key_columns: &[Ident], exprs: &[Expr]| {
+        key_expr(&columns, key_columns, exprs, &name, normalizer)
+    };
+
+    let mut new_columns = vec![];
+    if let Some(search) = clauses.search {
+        let static_key = key_expr(&search.by, &static_exprs)?;
+        let recursive_key = key_expr(&search.by, &recursive_exprs)?;
+        let sequence = work_table_column(&search.set);
+        let (static_expr, recursive_expr) = if search.depth_first {
+            (
+                function("make_array", vec![static_key]),
+                function("array_append", vec![sequence, recursive_key]),
+            )
+        } else {
+            let depth = Expr::BinaryOp {
+                left: Box::new(function("get_field", vec![sequence, string("depth")])),
+                op: BinaryOperator::Plus,
+                right: Box::new(number("1")),
+            };
+            (
+                function(
+                    "named_struct",
+                    vec![string("depth"), number("0"), string("key"), static_key],
+                ),
+                function(
+                    "named_struct",
+                    vec![string("depth"), depth, string("key"), recursive_key],
+                ),
+            )
+        };
+        static_term
+            .projection
+            .push(select_item(static_expr, &search.set));
+        recursive_term
+            .projection
+            .push(select_item(recursive_expr, &search.set));
+        new_columns.push(search.set);
+    }
+
+    if let Some(cycle) = clauses.cycle {
+        let static_key = key_expr(&cycle.columns, &static_exprs)?;
+        let recursive_key = key_expr(&cycle.columns, &recursive_exprs)?;
+        let mark = work_table_column(&cycle.set);
+        let path = work_table_column(&cycle.using);
+        let CycleClause {
+            columns: _,
+            set,
+            values,
+            using,
+        } = cycle;
+        let (to_value, default_value) = values.unwrap_or((
+            Expr::Value(Value::Boolean(true)),
+            Expr::Value(Value::Boolean(false)),
+        ));
+
+        static_term
+            .projection
+            .push(select_item(default_value.clone(), &set));
+        static_term.projection.push(select_item(
+            function("make_array", vec![static_key]),
+            &using,
+        ));
+        recursive_term.projection.push(select_item(
+            Expr::Case {
+                operand: None,
+                conditions: vec![function(
+                    "array_has",
+                    vec![path.clone(), recursive_key.clone()],
+                )],
+                results: vec![to_value],
+                else_result: Some(Box::new(default_value.clone())),
+            },
+            &set,
+        ));
+        recursive_term.projection.push(select_item(
+            function("array_append", vec![path, recursive_key]),
+            &using,
+        ));
+
+        // Rows that closed a cycle are returned but not expanded further
+        let condition = Expr::BinaryOp {
+            left: Box::new(mark),
+            op: BinaryOperator::Eq,
+            right: Box::new(default_value),
+        };
+        recursive_term.selection = Some(match recursive_term.selection.take() {
+            Some(selection) => Expr::BinaryOp {
+                left: Box::new(Expr::Nested(Box::new(selection))),
+                op: BinaryOperator::And,
+                right: Box::new(condition),
+            },
+            None => condition,
+        });
+        new_columns.push(set);
+        new_columns.push(using);
+    }
+
+    if !cte.alias.columns.is_empty() {
+        cte.alias
+            .columns
+            .extend(new_columns.into_iter().map(|name| TableAliasColumnDef {
+                name,
+                data_type: None,
+            }));
+    }
+    Ok(())
+}
+
+/// Returns the output name of a projection item of the static term
+fn select_item_name(item: &SelectItem, cte_name: &Ident) -> Result<Ident> {
+    match item {
+        SelectItem::UnnamedExpr(Expr::Identifier(ident))
+        | SelectItem::ExprWithAlias { alias: ident, .. } => Ok(ident.clone()),
+        SelectItem::UnnamedExpr(Expr::CompoundIdentifier(idents)) if !idents.is_empty() => {
+            Ok(idents[idents.len() - 1].clone())
+        }
+        _ => plan_err!(
+            "SEARCH and CYCLE clauses require named columns, add an alias to {item} or a column list to CTE {cte_name}"
+        ),
+    }
+}
+
+fn select_item_exprs(items: &[SelectItem], cte_name: &Ident) -> Result<Vec<Expr>> {
+    items
+        .iter()
+        .map(|item| match item {
+            SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                Ok(expr.clone())
+            }
+            _ => plan_err!(
+                "SEARCH and CYCLE clauses do not support wildcards in CTE {cte_name}"
+            ),
+        })
+        .collect()
+}
+
+/// Returns the key made of the `key_columns` of a term of the CTE, i.e. the
+/// expression itself for a single column or a struct of the expressions
+/// otherwise
+fn key_expr(
+    columns: &[String],
+    key_columns: &[Ident],
+    exprs: &[Expr],
+    cte_name: &Ident,
+    normalizer: &IdentNormalizer,
+) -> Result<Expr> {
+    let mut key = key_columns
+        .iter()
+        .map(|key_column| {
+            let key_column_name = normalizer.normalize(key_column.clone());
+            columns
+                .iter()
+                .position(|column| *column == key_column_name)
+                .and_then(|index| exprs.get(index))
+                .cloned()
+                .ok_or_else(|| {
+                    plan_datafusion_err!(
+                        "Column {key_column} is not a column of CTE {cte_name}"
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(if key.len() == 1 {
+        key.remove(0)
+    } else {
+        function("struct", key)
+    })
+}
+
+/// Returns the name by which the recursive term refers to the CTE itself
+fn work_table_reference(
+    select: &Select,
+    cte_name: &Ident,
+    normalizer: &IdentNormalizer,
+) -> Option<Ident> {
+    let cte_name = normalizer.normalize(cte_name.clone());
+    select
+        .from
+        .iter()
+        .flat_map(|table| {
+            std::iter::once(&table.relation)
+                .chain(table.joins.iter().map(|j| &j.relation))
+        })
+        .find_map(|relation| match relation {
+            TableFactor::Table { name, alias, .. }
+                if name.0.len() == 1
+                    && normalizer.normalize(name.0[0].clone()) == cte_name =>
+            {
+                Some(
+                    alias
+                        .as_ref()
+                        .map_or(&name.0[0], |alias| &alias.name)
+                        .clone(),
+                )
+            }
+            _ => None,
+        })
+}
+
+fn function(name: &str, args: Vec<Expr>) -> Expr {
+    Expr::Function(Function {
+        name: ObjectName(vec![Ident::new(name)]),
+        uses_odbc_syntax: false,
+        parameters: FunctionArguments::None,
+        args: FunctionArguments::List(FunctionArgumentList {
+            duplicate_treatment: None,
+            args: args
+                .into_iter()
+                .map(|arg| FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)))
+                .collect(),
+            clauses: vec![],
+        }),
+        filter: None,
+        null_treatment: None,
+        over: None,
+        within_group: vec![],
+    })
+}
+
+fn string(value: &str) -> Expr {
+    Expr::Value(Value::SingleQuotedString(value.to_string()))
+}
+
+fn number(value: &str) -> Expr {
+    Expr::Value(Value::Number(value.to_string(), false))
+}
+
+fn select_item(expr: Expr, alias: &Ident) -> SelectItem {
+    SelectItem::ExprWithAlias {
+        expr,
+        alias: alias.clone(),
+    }
+}
+
 fn has_work_table_reference(
     plan: &LogicalPlan,
     work_table_source: &Arc<dyn TableSource>,
@@ -15,10 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use crate::planner::{ContextProvider, SqlToRel};
-use datafusion_common::{not_impl_err, Result};
-use datafusion_expr::Operator;
-use sqlparser::ast::BinaryOperator;
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use datafusion_common::{internal_err, not_impl_err, DFSchema, Result};
+use datafusion_expr::utils::{conjunction, disjunction};
+use datafusion_expr::{binary_expr, lit, Expr, Operator};
+use sqlparser::ast::{BinaryOperator, Expr as SQLExpr};
 
 impl<S: ContextProvider> SqlToRel<'_, S> {
     pub(crate) fn parse_sql_binary_op(&self, op: BinaryOperator) -> Result<Operator> {
@@ -57,4 +58,68 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             _ => not_impl_err!("Unsupported SQL binary operator {op:?}"),
         }
     }
+
+    /// Returns true if `op` compares two rows such as `(a, b) < (c, d)`
+    pub(crate) fn is_row_comparison_op(op: &BinaryOperator) -> bool {
+        matches!(
+            op,
+            BinaryOperator::Eq
+                | BinaryOperator::NotEq
+                | BinaryOperator::Lt
+                | BinaryOperator::LtEq
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq
+        )
+    }
+
+    /// Plans the comparison of two rows with the same number of values, such
+    /// as `(a, b) < (c, d)`, as comparisons of their values.
+    ///
+    /// Rows are equal if all their values are equal, and are ordered by their
+    /// first values that are not equal, so `(a, b) < (c, d)` is planned as
+    /// `a < c OR (a = c AND b < d)`. The result is NULL if it depends on the
+    /// comparison of a NULL value, as in Postgres.
+    pub(crate) fn sql_row_comparison_to_expr(
+        &self,
+        left: Vec<SQLExpr>,
+        op: BinaryOperator,
+        right: Vec<SQLExpr>,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        let op = self.parse_sql_binary_op(op)?;
+        let mut values = Vec::with_capacity(left.len());
+        for (left, right) in left.into_iter().zip(right) {
+            values.push((
+                self.sql_expr_to_logical_expr(left, schema, planner_context)?,
+                self.sql_expr_to_logical_expr(right, schema, planner_context)?,
+            ));
+        }
+
+        let strict_op = match op {
+            Operator::Eq => {
+                let equalities = values.into_iter().map(|(left, right)| left.eq(right));
+                return Ok(conjunction(equalities).unwrap_or_else(|| lit(true)));
+            }
+            Operator::NotEq => {
+                let inequalities =
+                    values.into_iter().map(|(left, right)| left.not_eq(right));
+                return Ok(disjunction(inequalities).unwrap_or_else(|| lit(false)));
+            }
+            Operator::Lt | Operator::LtEq => Operator::Lt,
+            Operator::Gt | Operator::GtEq => Operator::Gt,
+            _ => return internal_err!("Unsupported operator in row comparison: {op}"),
+        };
+        let mut values = values.into_iter().rev();
+        let Some((last_left, last_right)) = values.next() else {
+            return Ok(lit(matches!(op, Operator::LtEq | Operator::GtEq)));
+        };
+        Ok(values.fold(
+            binary_expr(last_left, op, last_right),
+            |rest, (left, right)| {
+                binary_expr(left.clone(), strict_op, right.clone())
+                    .or(left.eq(right).and(rest))
+            },
+        ))
+    }
 }
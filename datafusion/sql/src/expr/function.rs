@@ -15,18 +15,20 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::sync::Arc;
+
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 
 use arrow_schema::DataType;
 use datafusion_common::{
     internal_datafusion_err, internal_err, not_impl_err, plan_datafusion_err, plan_err,
-    DFSchema, Dependency, Result,
+    DFSchema, Dependency, Result, TableReference,
 };
 use datafusion_expr::expr::{ScalarFunction, Unnest};
 use datafusion_expr::planner::PlannerResult;
 use datafusion_expr::{
-    expr, qualified_wildcard, wildcard, Expr, ExprFunctionExt, ExprSchemable,
-    WindowFrame, WindowFunctionDefinition,
+    expr, qualified_wildcard, wildcard, AggregateUDF, Expr, ExprFunctionExt,
+    ExprSchemable, ScalarUDF, WindowFrame, WindowFunctionDefinition,
 };
 use sqlparser::ast::{
     DuplicateTreatment, Expr as SQLExpr, Function as SQLFunction, FunctionArg,
@@ -92,6 +94,8 @@ struct FunctionArgs {
     null_treatment: Option<NullTreatment>,
     /// DISTINCT
     distinct: bool,
+    /// WITHIN GROUP clause, if any
+    within_group: Vec<OrderByExpr>,
 }
 
 impl FunctionArgs {
@@ -106,6 +110,12 @@ impl FunctionArgs {
             ..
         } = function;
 
+        if !within_group.is_empty() && over.is_some() {
+            return not_impl_err!(
+                "Calling {name}: WITHIN GROUP is not supported for window functions"
+            );
+        }
+
         // Handle no argument form (aka `current_time`  as opposed to `current_time()`)
         let FunctionArguments::List(args) = args else {
             return Ok(Self {
@@ -116,6 +126,7 @@ impl FunctionArgs {
                 filter,
                 null_treatment,
                 distinct: false,
+                within_group,
             });
         };
 
@@ -177,8 +188,10 @@ impl FunctionArgs {
             }
         }
 
-        if !within_group.is_empty() {
-            return not_impl_err!("WITHIN GROUP is not supported yet: {within_group:?}");
+        if !within_group.is_empty() && order_by.is_some() {
+            return plan_err!(
+                "Calling {name}: ORDER BY in function arguments cannot be combined with WITHIN GROUP"
+            );
         }
 
         let order_by = order_by.unwrap_or_default();
@@ -191,6 +204,7 @@ impl FunctionArgs {
             filter,
             null_treatment,
             distinct,
+            within_group,
         })
     }
 }
@@ -211,6 +225,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             filter,
             null_treatment,
             distinct,
+            within_group,
         } = function_args;
 
         // If function is a window function (it has an OVER clause),
@@ -218,13 +233,25 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         // required ordering should be defined in OVER clause.
         let is_function_window = over.is_some();
 
-        let name = if name.0.len() > 1 {
-            // DF doesn't handle compound identifiers
-            // (e.g. "foo.bar") for function names yet
-            name.to_string()
+        // Functions qualified with a schema (e.g. "foo.bar") are only looked
+        // up in that schema
+        let (name, qualified_name) = if name.0.len() > 1 {
+            let qualified_name = self.object_name_to_table_reference(name)?;
+            (qualified_name.to_string(), Some(qualified_name))
         } else {
-            crate::utils::normalize_ident(name.0[0].clone())
+            (crate::utils::normalize_ident(name.0[0].clone()), None)
         };
+        let qualified_name = qualified_name.as_ref();
+
+        if !within_group.is_empty()
+            && !self
+                .aggregate_function_meta(&name, qualified_name)
+                .is_some_and(|fm| fm.supports_within_group_clause())
+        {
+            return plan_err!(
+                "WITHIN GROUP is only supported for ordered-set aggregate functions, got {name}"
+            );
+        }
 
         if name.eq("make_map") {
             let mut fn_args =
@@ -238,7 +265,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
 
         // User-defined function (UDF) should have precedence
-        if let Some(fm) = self.context_provider.get_function_meta(&name) {
+        if let Some(fm) = self.scalar_function_meta(&name, qualified_name) {
             let args = self.function_args_to_expr(args, schema, planner_context)?;
             return Ok(Expr::ScalarFunction(ScalarFunction::new_udf(fm, args)));
         }
@@ -314,7 +341,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 WindowFrame::new((!order_by.is_empty()).then_some(false))
             };
 
-            if let Ok(fun) = self.find_window_func(&name) {
+            if let Ok(fun) = self.find_window_func(&name, qualified_name) {
                 return Expr::WindowFunction(expr::WindowFunction::new(
                     fun,
                     self.function_args_to_expr(args, schema, planner_context)?,
@@ -327,7 +354,14 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             }
         } else {
             // User defined aggregate functions (UDAF) have precedence in case it has the same name as a scalar built-in function
-            if let Some(fm) = self.context_provider.get_aggregate_meta(&name) {
+            if let Some(fm) = self.aggregate_function_meta(&name, qualified_name) {
+                let within_group = self.order_by_to_sort_expr(
+                    within_group,
+                    schema,
+                    planner_context,
+                    false,
+                    None,
+                )?;
                 let order_by = self.order_by_to_sort_expr(
                     order_by,
                     schema,
@@ -335,8 +369,18 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     true,
                     None,
                 )?;
+                let mut args =
+                    self.function_args_to_expr(args, schema, planner_context)?;
+                // Ordered-set aggregates receive the WITHIN GROUP sort
+                // expressions as leading arguments, and the sort itself as
+                // the aggregate ordering
+                let order_by = if within_group.is_empty() {
+                    order_by
+                } else {
+                    args.splice(0..0, within_group.iter().map(|sort| sort.expr.clone()));
+                    within_group
+                };
                 let order_by = (!order_by.is_empty()).then_some(order_by);
-                let args = self.function_args_to_expr(args, schema, planner_context)?;
                 let filter: Option<Box<Expr>> = filter
                     .map(|e| self.sql_expr_to_logical_expr(*e, schema, planner_context))
                     .transpose()?
@@ -353,7 +397,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
 
         // Could not find the relevant function, so return an error
-        if let Some(suggested_func_name) =
+        if qualified_name.is_some() {
+            plan_err!("Invalid function '{name}'")
+        } else if let Some(suggested_func_name) =
             suggest_valid_function(&name, is_function_window, self.context_provider)
         {
             plan_err!("Invalid function '{name}'.\nDid you mean '{suggested_func_name}'?")
@@ -379,12 +425,43 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         Ok(Expr::ScalarFunction(ScalarFunction::new_udf(fun, args)))
     }
 
+    /// Returns the scalar function `name`, looking it up in the schema of
+    /// `qualified_name` if the function is qualified
+    fn scalar_function_meta(
+        &self,
+        name: &str,
+        qualified_name: Option<&TableReference>,
+    ) -> Option<Arc<ScalarUDF>> {
+        match qualified_name {
+            Some(qualified_name) => self
+                .context_provider
+                .get_schema_function_meta(qualified_name),
+            None => self.context_provider.get_function_meta(name),
+        }
+    }
+
+    /// Returns the aggregate function `name`, looking it up in the schema of
+    /// `qualified_name` if the function is qualified
+    fn aggregate_function_meta(
+        &self,
+        name: &str,
+        qualified_name: Option<&TableReference>,
+    ) -> Option<Arc<AggregateUDF>> {
+        match qualified_name {
+            Some(qualified_name) => self
+                .context_provider
+                .get_schema_aggregate_meta(qualified_name),
+            None => self.context_provider.get_aggregate_meta(name),
+        }
+    }
+
     pub(super) fn find_window_func(
         &self,
         name: &str,
+        qualified_name: Option<&TableReference>,
     ) -> Result<WindowFunctionDefinition> {
         // Check udaf first
-        let udaf = self.context_provider.get_aggregate_meta(name);
+        let udaf = self.aggregate_function_meta(name, qualified_name);
         // Use the builtin window function instead of the user-defined aggregate function
         if udaf.as_ref().is_some_and(|udaf| {
             udaf.name() != "first_value"
@@ -393,12 +470,16 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }) {
             Ok(WindowFunctionDefinition::AggregateUDF(udaf.unwrap()))
         } else {
-            self.context_provider
-                .get_window_meta(name)
-                .map(WindowFunctionDefinition::WindowUDF)
-                .ok_or_else(|| {
-                    plan_datafusion_err!("There is no window function named {name}")
-                })
+            match qualified_name {
+                Some(qualified_name) => {
+                    self.context_provider.get_schema_window_meta(qualified_name)
+                }
+                None => self.context_provider.get_window_meta(name),
+            }
+            .map(WindowFunctionDefinition::WindowUDF)
+            .ok_or_else(|| {
+                plan_datafusion_err!("There is no window function named {name}")
+            })
         }
     }
 
@@ -453,12 +534,13 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             DataType::List(_)
             | DataType::LargeList(_)
             | DataType::FixedSizeList(_, _)
+            | DataType::Map(_, _)
             | DataType::Struct(_) => Ok(()),
             DataType::Null => {
                 not_impl_err!("unnest() does not support null yet")
             }
             _ => {
-                plan_err!("unnest() can only be applied to array, struct and null")
+                plan_err!("unnest() can only be applied to array, map, struct and null")
             }
         }
     }
@@ -15,6 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::sync::Arc;
+
+use arrow::array::StructArray;
 use arrow_schema::DataType;
 use arrow_schema::TimeUnit;
 use datafusion_expr::planner::{
@@ -32,7 +35,7 @@ use datafusion_common::{
 };
 
 use datafusion_expr::expr::ScalarFunction;
-use datafusion_expr::expr::{InList, WildcardOptions};
+use datafusion_expr::expr::{InList, SetQuantifier, WildcardOptions};
 use datafusion_expr::{
     lit, Between, BinaryExpr, Cast, Expr, ExprSchemable, GetFieldAccess, Like, Literal,
     Operator, TryCast,
@@ -87,11 +90,29 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 StackEntry::SQLExpr(sql_expr) => {
                     match *sql_expr {
                         SQLExpr::BinaryOp { left, op, right } => {
-                            // Note the order that we push the entries to the stack
-                            // is important. We want to visit the left node first.
-                            stack.push(StackEntry::Operator(op));
-                            stack.push(StackEntry::SQLExpr(right));
-                            stack.push(StackEntry::SQLExpr(left));
+                            match (*left, *right) {
+                                // compare rows such as `(a, b) < (c, d)` value by value
+                                (SQLExpr::Tuple(left), SQLExpr::Tuple(right))
+                                    if left.len() == right.len()
+                                        && Self::is_row_comparison_op(&op) =>
+                                {
+                                    let expr = self.sql_row_comparison_to_expr(
+                                        left,
+                                        op,
+                                        right,
+                                        schema,
+                                        planner_context,
+                                    )?;
+                                    eval_stack.push(expr);
+                                }
+                                (left, right) => {
+                                    // Note the order that we push the entries to the stack
+                                    // is important. We want to visit the left node first.
+                                    stack.push(StackEntry::Operator(op));
+                                    stack.push(StackEntry::SQLExpr(Box::new(right)));
+                                    stack.push(StackEntry::SQLExpr(Box::new(left)));
+                                }
+                            }
                         }
                         _ => {
                             let expr = self.sql_expr_to_logical_expr_internal(
@@ -569,30 +590,32 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 // specified but it doesn't affect the plan so ignore the field
                 is_some: _,
             } => {
-                let mut binary_expr = RawBinaryExpr {
-                    op: compare_op,
-                    left: self.sql_expr_to_logical_expr(
-                        *left,
-                        schema,
-                        planner_context,
-                    )?,
-                    right: self.sql_expr_to_logical_expr(
-                        *right,
-                        schema,
-                        planner_context,
-                    )?,
-                };
-                for planner in self.context_provider.get_expr_planners() {
-                    match planner.plan_any(binary_expr)? {
-                        PlannerResult::Planned(expr) => {
-                            return Ok(expr);
-                        }
-                        PlannerResult::Original(expr) => {
-                            binary_expr = expr;
-                        }
-                    }
-                }
-                not_impl_err!("AnyOp not supported by ExprPlanner: {binary_expr:?}")
+                let left =
+                    self.sql_expr_to_logical_expr(*left, schema, planner_context)?;
+                self.sql_quantified_to_expr(
+                    left,
+                    compare_op,
+                    *right,
+                    SetQuantifier::Any,
+                    schema,
+                    planner_context,
+                )
+            }
+            SQLExpr::AllOp {
+                left,
+                compare_op,
+                right,
+            } => {
+                let left =
+                    self.sql_expr_to_logical_expr(*left, schema, planner_context)?;
+                self.sql_quantified_to_expr(
+                    left,
+                    compare_op,
+                    *right,
+                    SetQuantifier::All,
+                    schema,
+                    planner_context,
+                )
             }
             SQLExpr::Wildcard(_token) => Ok(Expr::Wildcard {
                 qualifier: None,
@@ -643,15 +666,12 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         planner_context: &mut PlannerContext,
         values: Vec<SQLExpr>,
     ) -> Result<Expr> {
-        match values.first() {
-            Some(SQLExpr::Identifier(_)) | Some(SQLExpr::Value(_)) => {
-                self.parse_struct(schema, planner_context, values, vec![])
-            }
-            None => not_impl_err!("Empty tuple not supported yet"),
-            _ => {
-                not_impl_err!("Only identifiers and literals are supported in tuples")
-            }
+        if values.is_empty() {
+            // a row without values
+            let row = StructArray::new_empty_fields(1, None);
+            return Ok(lit(ScalarValue::Struct(Arc::new(row))));
         }
+        self.parse_struct(schema, planner_context, values, vec![])
     }
 
     fn sql_position_to_expr(
@@ -817,10 +837,6 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         case_insensitive: bool,
         any: bool,
     ) -> Result<Expr> {
-        if any {
-            return not_impl_err!("ANY in LIKE expression");
-        }
-        let pattern = self.sql_expr_to_logical_expr(pattern, schema, planner_context)?;
         let escape_char = if let Some(char) = escape_char {
             if char.len() != 1 {
                 return plan_err!("Invalid escape character in LIKE expression");
@@ -829,6 +845,18 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         } else {
             None
         };
+        if any {
+            return self.sql_like_any_to_expr(
+                negated,
+                expr,
+                pattern,
+                escape_char,
+                schema,
+                planner_context,
+                case_insensitive,
+            );
+        }
+        let pattern = self.sql_expr_to_logical_expr(pattern, schema, planner_context)?;
         Ok(Expr::Like(Like::new(
             negated,
             Box::new(self.sql_expr_to_logical_expr(expr, schema, planner_context)?),
@@ -838,6 +866,146 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         )))
     }
 
+    /// Plans `expr LIKE ANY (patterns)`, where the patterns are a list of
+    /// expressions, an array or a subquery
+    #[allow(clippy::too_many_arguments)]
+    fn sql_like_any_to_expr(
+        &self,
+        negated: bool,
+        expr: SQLExpr,
+        pattern: SQLExpr,
+        escape_char: Option<char>,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+        case_insensitive: bool,
+    ) -> Result<Expr> {
+        let expr = self.sql_expr_to_logical_expr(expr, schema, planner_context)?;
+        let mut pattern = pattern;
+        while let SQLExpr::Nested(inner) = pattern {
+            pattern = *inner;
+        }
+        let op = match (negated, case_insensitive) {
+            (false, false) => BinaryOperator::PGLikeMatch,
+            (false, true) => BinaryOperator::PGILikeMatch,
+            (true, false) => BinaryOperator::PGNotLikeMatch,
+            (true, true) => BinaryOperator::PGNotILikeMatch,
+        };
+        let patterns = match pattern {
+            // a list of patterns matches if any of them does
+            SQLExpr::Tuple(patterns) => patterns,
+            SQLExpr::Subquery(_) if escape_char.is_some() => {
+                return not_impl_err!("ESCAPE in LIKE ANY expression with a subquery");
+            }
+            SQLExpr::Subquery(_) => {
+                return self.sql_quantified_to_expr(
+                    expr,
+                    op,
+                    pattern,
+                    SetQuantifier::Any,
+                    schema,
+                    planner_context,
+                );
+            }
+            pattern => {
+                let pattern =
+                    self.sql_expr_to_logical_expr(pattern, schema, planner_context)?;
+                if !matches!(
+                    pattern.get_type(schema)?,
+                    DataType::List(_)
+                        | DataType::LargeList(_)
+                        | DataType::FixedSizeList(_, _)
+                ) {
+                    return Ok(Expr::Like(Like::new(
+                        negated,
+                        Box::new(expr),
+                        Box::new(pattern),
+                        escape_char,
+                        case_insensitive,
+                    )));
+                }
+                if escape_char.is_some() {
+                    return not_impl_err!("ESCAPE in LIKE ANY expression with an array");
+                }
+                let binary_expr = RawBinaryExpr {
+                    op,
+                    left: expr,
+                    right: pattern,
+                };
+                return self.plan_quantified_array(binary_expr, SetQuantifier::Any);
+            }
+        };
+        patterns
+            .into_iter()
+            .map(|pattern| {
+                let pattern =
+                    self.sql_expr_to_logical_expr(pattern, schema, planner_context)?;
+                Ok(Expr::Like(Like::new(
+                    negated,
+                    Box::new(expr.clone()),
+                    Box::new(pattern),
+                    escape_char,
+                    case_insensitive,
+                )))
+            })
+            .reduce(|left, right| Ok(left?.or(right?)))
+            .unwrap_or_else(|| plan_err!("LIKE ANY expects at least one pattern"))
+    }
+
+    /// Plans `left op ANY (right)` and `left op ALL (right)`, where `right` is
+    /// a subquery or an array
+    fn sql_quantified_to_expr(
+        &self,
+        left: Expr,
+        op: BinaryOperator,
+        right: SQLExpr,
+        quantifier: SetQuantifier,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        if let SQLExpr::Subquery(subquery) = right {
+            let op = self.parse_sql_binary_op(op)?;
+            return self.parse_set_comparison_subquery(
+                left,
+                *subquery,
+                op,
+                quantifier,
+                schema,
+                planner_context,
+            );
+        }
+        let right = self.sql_expr_to_logical_expr(right, schema, planner_context)?;
+        self.plan_quantified_array(RawBinaryExpr { op, left, right }, quantifier)
+    }
+
+    fn plan_quantified_array(
+        &self,
+        mut binary_expr: RawBinaryExpr,
+        quantifier: SetQuantifier,
+    ) -> Result<Expr> {
+        for planner in self.context_provider.get_expr_planners() {
+            let result = match quantifier {
+                SetQuantifier::Any => planner.plan_any(binary_expr)?,
+                SetQuantifier::All => planner.plan_all(binary_expr)?,
+            };
+            match result {
+                PlannerResult::Planned(expr) => {
+                    return Ok(expr);
+                }
+                PlannerResult::Original(expr) => {
+                    binary_expr = expr;
+                }
+            }
+        }
+        match quantifier {
+            SetQuantifier::Any => {
+                not_impl_err!("AnyOp not supported by ExprPlanner: {binary_expr:?}")
+            }
+            SetQuantifier::All => {
+                not_impl_err!("AllOp not supported by ExprPlanner: {binary_expr:?}")
+            }
+        }
+    }
+
     fn sql_similarto_to_expr(
         &self,
         negated: bool,
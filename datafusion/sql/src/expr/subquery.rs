@@ -16,10 +16,11 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use datafusion_common::{DFSchema, Result};
+use datafusion_common::{plan_err, DFSchema, Result};
 use datafusion_expr::expr::Exists;
 use datafusion_expr::expr::InSubquery;
-use datafusion_expr::{Expr, Subquery};
+use datafusion_expr::expr::{SetComparison, SetQuantifier};
+use datafusion_expr::{Expr, Operator, Subquery};
 use sqlparser::ast::Expr as SQLExpr;
 use sqlparser::ast::Query;
 use std::sync::Arc;
@@ -59,6 +60,18 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         let sub_plan = self.query_to_plan(subquery, planner_context)?;
         let outer_ref_columns = sub_plan.all_out_ref_exprs();
         planner_context.set_outer_query_schema(old_outer_query_schema);
+        // `(a, b) IN (SELECT x, y ...)` compares the values of the row with the
+        // columns of the subquery
+        if let SQLExpr::Tuple(values) = &expr {
+            let columns = sub_plan.schema().fields().len();
+            if values.len() > 1 && values.len() != columns {
+                return plan_err!(
+                    "IN subquery with a row of {} values should return {} columns, but found {columns}",
+                    values.len(),
+                    values.len()
+                );
+            }
+        }
         let expr = Box::new(self.sql_to_expr(expr, input_schema, planner_context)?);
         Ok(Expr::InSubquery(InSubquery::new(
             expr,
@@ -70,6 +83,44 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         )))
     }
 
+    /// Plans `expr op ANY (subquery)` and `expr op ALL (subquery)`
+    ///
+    /// `= ANY` and `<> ALL` are planned as `IN` and `NOT IN`, which they are
+    /// equivalent to.
+    pub(super) fn parse_set_comparison_subquery(
+        &self,
+        expr: Expr,
+        subquery: Query,
+        op: Operator,
+        quantifier: SetQuantifier,
+        input_schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        let old_outer_query_schema =
+            planner_context.set_outer_query_schema(Some(input_schema.clone().into()));
+        let sub_plan = self.query_to_plan(subquery, planner_context)?;
+        let outer_ref_columns = sub_plan.all_out_ref_exprs();
+        planner_context.set_outer_query_schema(old_outer_query_schema);
+        let subquery = Subquery {
+            subquery: Arc::new(sub_plan),
+            outer_ref_columns,
+        };
+        Ok(match (op, quantifier) {
+            (Operator::Eq, SetQuantifier::Any) => {
+                Expr::InSubquery(InSubquery::new(Box::new(expr), subquery, false))
+            }
+            (Operator::NotEq, SetQuantifier::All) => {
+                Expr::InSubquery(InSubquery::new(Box::new(expr), subquery, true))
+            }
+            _ => Expr::SetComparison(SetComparison::new(
+                Box::new(expr),
+                subquery,
+                op,
+                quantifier,
+            )),
+        })
+    }
+
     pub(super) fn parse_scalar_subquery(
         &self,
         subquery: Query,
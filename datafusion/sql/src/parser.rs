@@ -19,13 +19,15 @@
 
 use std::collections::VecDeque;
 use std::fmt;
+use std::ops::ControlFlow;
 
 use sqlparser::ast::ExprWithAlias;
 use sqlparser::tokenizer::TokenWithSpan;
 use sqlparser::{
     ast::{
-        ColumnDef, ColumnOptionDef, ObjectName, OrderByExpr, Query,
-        Statement as SQLStatement, TableConstraint, Value,
+        display_comma_separated, ColumnDef, ColumnOptionDef, Cte, Expr, Ident,
+        ObjectName, OrderByExpr, Query, Statement as SQLStatement, TableConstraint,
+        Value, Visit, Visitor,
     },
     dialect::{keywords::Keyword, Dialect, GenericDialect},
     parser::{Parser, ParserError},
@@ -49,6 +51,8 @@ fn parse_file_type(s: &str) -> Result<String, ParserError> {
 pub struct ExplainStatement {
     pub analyze: bool,
     pub verbose: bool,
+    /// The output format, e.g. `JSON` in `EXPLAIN FORMAT JSON`
+    pub format: Option<String>,
     pub statement: Box<Statement>,
 }
 
@@ -57,6 +61,7 @@ impl fmt::Display for ExplainStatement {
         let Self {
             analyze,
             verbose,
+            format,
             statement,
         } = self;
 
@@ -67,6 +72,9 @@ impl fmt::Display for ExplainStatement {
         if *verbose {
             write!(f, "VERBOSE ")?;
         }
+        if let Some(format) = format {
+            write!(f, "FORMAT {format} ")?;
+        }
 
         write!(f, "{statement}")
     }
@@ -208,13 +216,191 @@ pub struct CreateExternalTable {
 
 impl fmt::Display for CreateExternalTable {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "CREATE EXTERNAL TABLE ")?;
+        write!(f, "CREATE ")?;
+        if self.unbounded {
+            write!(f, "UNBOUNDED ")?;
+        }
+        write!(f, "EXTERNAL ")?;
+        if self.temporary {
+            write!(f, "TEMPORARY ")?;
+        }
+        write!(f, "TABLE ")?;
         if self.if_not_exists {
             write!(f, "IF NOT EXISTS ")?;
         }
-        write!(f, "{} ", self.name)?;
-        write!(f, "STORED AS {} ", self.file_type)?;
-        write!(f, "LOCATION {} ", self.location)
+        write!(f, "{}", self.name)?;
+        if !self.columns.is_empty() || !self.constraints.is_empty() {
+            let elements: Vec<_> = self
+                .columns
+                .iter()
+                .map(ToString::to_string)
+                .chain(self.constraints.iter().map(ToString::to_string))
+                .collect();
+            write!(f, " ({})", elements.join(", "))?;
+        }
+        write!(f, " STORED AS {}", self.file_type)?;
+        if !self.table_partition_cols.is_empty() {
+            write!(
+                f,
+                " PARTITIONED BY ({})",
+                self.table_partition_cols.join(", ")
+            )?;
+        }
+        for ordering in &self.order_exprs {
+            let exprs: Vec<_> = ordering.iter().map(ToString::to_string).collect();
+            write!(f, " WITH ORDER ({})", exprs.join(", "))?;
+        }
+        write!(
+            f,
+            " LOCATION {}",
+            Value::SingleQuotedString(self.location.clone())
+        )?;
+        if !self.options.is_empty() {
+            let opts: Vec<_> = self
+                .options
+                .iter()
+                .map(|(k, v)| format!("'{k}' {v}"))
+                .collect();
+            write!(f, " OPTIONS ({})", opts.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// DataFusion extension DDL for `REFRESH MATERIALIZED VIEW`
+///
+/// Recomputes the stored contents of a materialized view created with
+/// `CREATE MATERIALIZED VIEW`.
+///
+/// # Syntax:
+///
+/// ```text
+/// REFRESH MATERIALIZED VIEW <view_name>
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshMaterializedViewStatement {
+    /// View name
+    pub name: ObjectName,
+}
+
+impl fmt::Display for RefreshMaterializedViewStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "REFRESH MATERIALIZED VIEW {}", self.name)
+    }
+}
+
+/// `SEARCH { DEPTH | BREADTH } FIRST BY <column> [, ...] SET <sequence column>`
+///
+/// Adds a sequence column to a recursive CTE, ordering by which returns its
+/// rows in depth first or breadth first order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchClause {
+    /// `DEPTH FIRST` if true, `BREADTH FIRST` otherwise
+    pub depth_first: bool,
+    /// Columns identifying a row
+    pub by: Vec<Ident>,
+    /// Name of the sequence column
+    pub set: Ident,
+}
+
+impl fmt::Display for SearchClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let order = if self.depth_first { "DEPTH" } else { "BREADTH" };
+        write!(
+            f,
+            "SEARCH {order} FIRST BY {} SET {}",
+            display_comma_separated(&self.by),
+            self.set
+        )
+    }
+}
+
+/// `CYCLE <column> [, ...] SET <mark column> [ TO <value> DEFAULT <value> ] USING <path column>`
+///
+/// Stops the recursion of a recursive CTE at rows that were already visited,
+/// marking the rows that close a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleClause {
+    /// Columns identifying a row
+    pub columns: Vec<Ident>,
+    /// Name of the mark column
+    pub set: Ident,
+    /// Values of the mark column for rows that close a cycle and for all
+    /// other rows, `TRUE` and `FALSE` if not specified
+    pub values: Option<(Expr, Expr)>,
+    /// Name of the path column
+    pub using: Ident,
+}
+
+impl fmt::Display for CycleClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CYCLE {} SET {}",
+            display_comma_separated(&self.columns),
+            self.set
+        )?;
+        if let Some((to_value, default_value)) = &self.values {
+            write!(f, " TO {to_value} DEFAULT {default_value}")?;
+        }
+        write!(f, " USING {}", self.using)
+    }
+}
+
+/// The `SEARCH` and `CYCLE` clauses following the query of a recursive CTE,
+/// which are not supported by [`sqlparser`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecursiveCteClauses {
+    /// Name of the CTE, whose span identifies the CTE in the statement
+    pub cte_name: Ident,
+    pub search: Option<SearchClause>,
+    pub cycle: Option<CycleClause>,
+}
+
+impl fmt::Display for RecursiveCteClauses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.search, &self.cycle) {
+            (Some(search), Some(cycle)) => write!(f, "{search} {cycle}"),
+            (Some(search), None) => write!(f, "{search}"),
+            (None, Some(cycle)) => write!(f, "{cycle}"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// A statement with `SEARCH` or `CYCLE` clauses on its recursive CTEs
+///
+/// # Syntax:
+///
+/// ```text
+/// WITH RECURSIVE <name> AS ( <query> )
+///     [ SEARCH { DEPTH | BREADTH } FIRST BY <column> [, ...] SET <sequence column> ]
+///     [ CYCLE <column> [, ...] SET <mark column> [ TO <value> DEFAULT <value> ] USING <path column> ]
+/// ...
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecursiveCteStatement {
+    pub statement: Box<Statement>,
+    /// The clauses of the recursive CTEs of `statement`, in the order of the
+    /// CTEs in the statement
+    pub clauses: Vec<RecursiveCteClauses>,
+}
+
+impl fmt::Display for RecursiveCteStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut sql = self.statement.to_string();
+        // Insert the clauses after the CTEs they belong to. The clauses are
+        // ordered by the position of the CTE names, so that a CTE is found
+        // before the clauses of the CTEs nested in it are inserted.
+        for (clauses, cte) in self.clauses.iter().zip(find_ctes(self)) {
+            let Some((_, cte)) = cte else {
+                return Err(fmt::Error);
+            };
+            let cte = cte.to_string();
+            sql = sql.replacen(&cte, &format!("{cte} {clauses}"), 1);
+        }
+        write!(f, "{sql}")
     }
 }
 
@@ -233,8 +419,12 @@ pub enum Statement {
     CreateExternalTable(CreateExternalTable),
     /// Extension: `COPY TO`
     CopyTo(CopyToStatement),
+    /// Extension: `REFRESH MATERIALIZED VIEW`
+    RefreshMaterializedView(RefreshMaterializedViewStatement),
     /// EXPLAIN for extensions
     Explain(ExplainStatement),
+    /// Extension: `SEARCH` and `CYCLE` clauses of recursive CTEs
+    RecursiveCte(RecursiveCteStatement),
 }
 
 impl fmt::Display for Statement {
@@ -243,7 +433,9 @@ impl fmt::Display for Statement {
             Statement::Statement(stmt) => write!(f, "{stmt}"),
             Statement::CreateExternalTable(stmt) => write!(f, "{stmt}"),
             Statement::CopyTo(stmt) => write!(f, "{stmt}"),
+            Statement::RefreshMaterializedView(stmt) => write!(f, "{stmt}"),
             Statement::Explain(stmt) => write!(f, "{stmt}"),
+            Statement::RecursiveCte(stmt) => write!(f, "{stmt}"),
         }
     }
 }
@@ -267,6 +459,9 @@ fn ensure_not_set<T>(field: &Option<T>, name: &str) -> Result<(), ParserError> {
 /// [`Statement`] for a list of this special syntax
 pub struct DFParser<'a> {
     pub parser: Parser<'a>,
+    /// `SEARCH` and `CYCLE` clauses removed from the token stream, in the
+    /// order of the CTEs they belong to
+    recursive_cte_clauses: VecDeque<RecursiveCteClauses>,
 }
 
 impl<'a> DFParser<'a> {
@@ -285,9 +480,12 @@ impl<'a> DFParser<'a> {
     ) -> Result<Self, ParserError> {
         let mut tokenizer = Tokenizer::new(dialect, sql);
         let tokens = tokenizer.tokenize_with_location()?;
+        let (tokens, recursive_cte_clauses) =
+            extract_recursive_cte_clauses(tokens, dialect)?;
 
         Ok(DFParser {
             parser: Parser::new(dialect).with_tokens_with_locations(tokens),
+            recursive_cte_clauses: recursive_cte_clauses.into(),
         })
     }
 
@@ -346,6 +544,11 @@ impl<'a> DFParser<'a> {
 
     /// Parse a new expression
     pub fn parse_statement(&mut self) -> Result<Statement, ParserError> {
+        let statement = self.parse_statement_inner()?;
+        self.attach_recursive_cte_clauses(statement)
+    }
+
+    fn parse_statement_inner(&mut self) -> Result<Statement, ParserError> {
         match self.parser.peek_token().token {
             Token::Word(w) => {
                 match w.keyword {
@@ -370,6 +573,10 @@ impl<'a> DFParser<'a> {
                         self.parser.next_token(); // EXPLAIN
                         self.parse_explain()
                     }
+                    Keyword::NoKeyword if w.value.eq_ignore_ascii_case("REFRESH") => {
+                        self.parser.next_token(); // REFRESH
+                        self.parse_refresh()
+                    }
                     _ => {
                         // use sqlparser-rs parser
                         Ok(Statement::Statement(Box::from(
@@ -387,6 +594,43 @@ impl<'a> DFParser<'a> {
         }
     }
 
+    /// Attach the `SEARCH` and `CYCLE` clauses of the CTEs of `statement`
+    /// to it, see [`RecursiveCteStatement`]
+    fn attach_recursive_cte_clauses(
+        &mut self,
+        statement: Statement,
+    ) -> Result<Statement, ParserError> {
+        // The clauses of the CTEs of this statement precede its end
+        let end = self.parser.peek_token();
+        let count = self
+            .recursive_cte_clauses
+            .iter()
+            .take_while(|clauses| {
+                let start = clauses.cte_name.span.start;
+                end.token == Token::EOF
+                    || (start.line, start.column)
+                        < (end.span.start.line, end.span.start.column)
+            })
+            .count();
+        if count == 0 {
+            return Ok(statement);
+        }
+
+        let statement = RecursiveCteStatement {
+            statement: Box::new(statement),
+            clauses: self.recursive_cte_clauses.drain(..count).collect(),
+        };
+        if find_ctes(&statement)
+            .iter()
+            .any(|cte| !matches!(cte, Some((true, _))))
+        {
+            return parser_err!(
+                "SEARCH and CYCLE clauses are only supported for CTEs of a WITH RECURSIVE query"
+            );
+        }
+        Ok(Statement::RecursiveCte(statement))
+    }
+
     pub fn parse_expr(&mut self) -> Result<ExprWithAlias, ParserError> {
         if let Token::Word(w) = self.parser.peek_token().token {
             match w.keyword {
@@ -538,17 +782,85 @@ impl<'a> DFParser<'a> {
 
     /// Parse a SQL `EXPLAIN`
     pub fn parse_explain(&mut self) -> Result<Statement, ParserError> {
-        let analyze = self.parser.parse_keyword(Keyword::ANALYZE);
-        let verbose = self.parser.parse_keyword(Keyword::VERBOSE);
+        let mut analyze = false;
+        let mut verbose = false;
+        let mut format = None;
+
+        // `EXPLAIN ( option [, ...] )`
+        if self.parser.peek_token() == Token::LParen
+            && matches!(
+                self.parser.peek_nth_token(1).token,
+                Token::Word(w) if matches!(w.keyword, Keyword::ANALYZE | Keyword::VERBOSE | Keyword::FORMAT)
+            )
+        {
+            self.parser.expect_token(&Token::LParen)?;
+            loop {
+                match self.parser.expect_one_of_keywords(&[
+                    Keyword::ANALYZE,
+                    Keyword::VERBOSE,
+                    Keyword::FORMAT,
+                ])? {
+                    Keyword::ANALYZE => analyze = self.parse_explain_option_value()?,
+                    Keyword::VERBOSE => verbose = self.parse_explain_option_value()?,
+                    _ => {
+                        ensure_not_set(&format, "FORMAT")?;
+                        format = Some(self.parse_explain_format()?);
+                    }
+                }
+                if !self.parser.consume_token(&Token::Comma) {
+                    break;
+                }
+            }
+            self.parser.expect_token(&Token::RParen)?;
+        } else {
+            analyze = self.parser.parse_keyword(Keyword::ANALYZE);
+            verbose = self.parser.parse_keyword(Keyword::VERBOSE);
+            if self.parser.parse_keyword(Keyword::FORMAT) {
+                format = Some(self.parse_explain_format()?);
+            }
+        }
         let statement = self.parse_statement()?;
 
         Ok(Statement::Explain(ExplainStatement {
             statement: Box::new(statement),
             analyze,
             verbose,
+            format,
         }))
     }
 
+    /// Parse the optional boolean value of an `EXPLAIN ( ... )` option,
+    /// which defaults to `true`
+    fn parse_explain_option_value(&mut self) -> Result<bool, ParserError> {
+        if self.parser.parse_keyword(Keyword::TRUE) {
+            Ok(true)
+        } else if self.parser.parse_keyword(Keyword::FALSE) {
+            Ok(false)
+        } else {
+            Ok(true)
+        }
+    }
+
+    /// Parse the format name of `EXPLAIN FORMAT <format>`
+    fn parse_explain_format(&mut self) -> Result<String, ParserError> {
+        let token = self.parser.next_token();
+        match &token.token {
+            Token::Word(w) => Ok(w.value.clone()),
+            Token::SingleQuotedString(s) => Ok(s.clone()),
+            _ => self.expected("an explain format such as JSON", token),
+        }
+    }
+
+    /// Parse a SQL `REFRESH MATERIALIZED VIEW` statement
+    pub fn parse_refresh(&mut self) -> Result<Statement, ParserError> {
+        self.parser
+            .expect_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])?;
+        let name = self.parser.parse_object_name(true)?;
+        Ok(Statement::RefreshMaterializedView(
+            RefreshMaterializedViewStatement { name },
+        ))
+    }
+
     /// Parse a SQL `CREATE` statement handling `CREATE EXTERNAL TABLE`
     pub fn parse_create(&mut self) -> Result<Statement, ParserError> {
         if self.parser.parse_keyword(Keyword::EXTERNAL) {
@@ -880,11 +1192,204 @@ impl<'a> DFParser<'a> {
     }
 }
 
+/// Removes the `SEARCH` and `CYCLE` clauses following CTE definitions from
+/// `tokens`, as [`sqlparser`] does not support them, returning the remaining
+/// tokens and the parsed clauses.
+fn extract_recursive_cte_clauses(
+    tokens: Vec<TokenWithSpan>,
+    dialect: &dyn Dialect,
+) -> Result<(Vec<TokenWithSpan>, Vec<RecursiveCteClauses>), ParserError> {
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut clauses = vec![];
+    // Positions in `output` of the currently open parentheses
+    let mut open_parens = vec![];
+
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = &tokens[index];
+        match &token.token {
+            Token::LParen => open_parens.push(output.len()),
+            Token::RParen => {
+                let cte_name = open_parens.pop().and_then(|open| cte_name(&output, open));
+                if let Some(cte_name) = cte_name {
+                    let mut parser = Parser::new(dialect)
+                        .with_tokens_with_locations(tokens[index + 1..].to_vec());
+                    let search = parse_search_clause(&mut parser)?;
+                    let cycle = parse_cycle_clause(&mut parser)?;
+                    if search.is_some() || cycle.is_some() {
+                        output.push(token.clone());
+                        clauses.push(RecursiveCteClauses {
+                            cte_name,
+                            search,
+                            cycle,
+                        });
+                        index += 1 + parser.index();
+                        continue;
+                    }
+                }
+            }
+            _ => {}
+        }
+        output.push(token.clone());
+        index += 1;
+    }
+    // The clauses of a nested CTE are found before the clauses of the CTE
+    // containing it, order them by the position of the CTE names instead
+    clauses.sort_by_key(|clauses: &RecursiveCteClauses| {
+        let start = clauses.cte_name.span.start;
+        (start.line, start.column)
+    });
+    Ok((output, clauses))
+}
+
+/// If the parenthesis at `open` in `tokens` starts the query of a CTE, i.e.
+/// it is preceded by `<name> [ ( <columns> ) ] AS [ [ NOT ] MATERIALIZED ]`,
+/// returns the CTE name.
+fn cte_name(tokens: &[TokenWithSpan], open: usize) -> Option<Ident> {
+    let mut rev = tokens[..open]
+        .iter()
+        .rev()
+        .filter(|t| !matches!(t.token, Token::Whitespace(_)))
+        .peekable();
+
+    if matches!(rev.peek(), Some(t) if is_keyword(t, Keyword::MATERIALIZED)) {
+        rev.next();
+        if matches!(rev.peek(), Some(t) if is_keyword(t, Keyword::NOT)) {
+            rev.next();
+        }
+    }
+    if !is_keyword(rev.next()?, Keyword::AS) {
+        return None;
+    }
+    if matches!(rev.peek(), Some(t) if t.token == Token::RParen) {
+        // Skip the column list
+        let mut depth = 0;
+        for t in rev.by_ref() {
+            match t.token {
+                Token::RParen => depth += 1,
+                Token::LParen => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+    }
+    match rev.next()? {
+        TokenWithSpan {
+            token: Token::Word(w),
+            span,
+        } => Some(w.clone().into_ident(*span)),
+        _ => None,
+    }
+}
+
+fn is_keyword(token: &TokenWithSpan, keyword: Keyword) -> bool {
+    matches!(&token.token, Token::Word(w) if w.keyword == keyword)
+}
+
+/// Parse an optional `SEARCH { DEPTH | BREADTH } FIRST BY <column> [, ...] SET <sequence column>` clause
+fn parse_search_clause(parser: &mut Parser) -> Result<Option<SearchClause>, ParserError> {
+    let depth_first = match parser.peek_tokens() {
+        [Token::Word(search), Token::Word(order)]
+            if search.keyword == Keyword::SEARCH =>
+        {
+            if order.value.eq_ignore_ascii_case("DEPTH") {
+                true
+            } else if order.value.eq_ignore_ascii_case("BREADTH") {
+                false
+            } else {
+                return Ok(None);
+            }
+        }
+        _ => return Ok(None),
+    };
+    parser.next_token(); // SEARCH
+    parser.next_token(); // DEPTH or BREADTH
+    parser.expect_keywords(&[Keyword::FIRST, Keyword::BY])?;
+    let by = parser.parse_comma_separated(Parser::parse_identifier)?;
+    parser.expect_keyword_is(Keyword::SET)?;
+    let set = parser.parse_identifier()?;
+    Ok(Some(SearchClause {
+        depth_first,
+        by,
+        set,
+    }))
+}
+
+/// Parse an optional `CYCLE <column> [, ...] SET <mark column> [ TO <value> DEFAULT <value> ] USING <path column>` clause
+fn parse_cycle_clause(parser: &mut Parser) -> Result<Option<CycleClause>, ParserError> {
+    if !parser.parse_keyword(Keyword::CYCLE) {
+        return Ok(None);
+    }
+    let columns = parser.parse_comma_separated(Parser::parse_identifier)?;
+    parser.expect_keyword_is(Keyword::SET)?;
+    let set = parser.parse_identifier()?;
+    let values = if parser.parse_keyword(Keyword::TO) {
+        let to_value = parser.parse_expr()?;
+        parser.expect_keyword_is(Keyword::DEFAULT)?;
+        Some((to_value, parser.parse_expr()?))
+    } else {
+        None
+    };
+    parser.expect_keyword_is(Keyword::USING)?;
+    let using = parser.parse_identifier()?;
+    Ok(Some(CycleClause {
+        columns,
+        set,
+        values,
+        using,
+    }))
+}
+
+/// Finds the CTEs the clauses of `statement` belong to, returning for each
+/// clause whether its CTE is part of a `WITH RECURSIVE` query and the CTE
+fn find_ctes(statement: &RecursiveCteStatement) -> Vec<Option<(bool, Cte)>> {
+    let mut finder = CteFinder {
+        clauses: &statement.clauses,
+        ctes: vec![None; statement.clauses.len()],
+    };
+    let _ = match statement.statement.as_ref() {
+        Statement::Statement(statement) => statement.visit(&mut finder),
+        Statement::CopyTo(CopyToStatement {
+            source: CopyToSource::Query(query),
+            ..
+        }) => query.visit(&mut finder),
+        _ => ControlFlow::Continue(()),
+    };
+    finder.ctes
+}
+
+struct CteFinder<'a> {
+    clauses: &'a [RecursiveCteClauses],
+    ctes: Vec<Option<(bool, Cte)>>,
+}
+
+impl Visitor for CteFinder<'_> {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<()> {
+        let Some(with) = &query.with else {
+            return ControlFlow::Continue(());
+        };
+        for cte in &with.cte_tables {
+            if let Some(index) = self
+                .clauses
+                .iter()
+                .position(|clauses| clauses.cte_name.span == cte.alias.name.span)
+            {
+                self.ctes[index] = Some((with.recursive, cte.clone()));
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use sqlparser::ast::Expr::Identifier;
-    use sqlparser::ast::{BinaryOperator, DataType, Expr, Ident};
+    use sqlparser::ast::{BinaryOperator, DataType};
     use sqlparser::dialect::SnowflakeDialect;
     use sqlparser::tokenizer::Span;
 
@@ -1396,6 +1901,16 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn create_external_table_display() {
+        verified_stmt("CREATE EXTERNAL TABLE t STORED AS CSV LOCATION 'foo.csv'");
+        verified_stmt(
+            "CREATE UNBOUNDED EXTERNAL TABLE IF NOT EXISTS t (c1 INT NOT NULL, c2 VARCHAR, PRIMARY KEY (c1)) \
+            STORED AS CSV PARTITIONED BY (c2) WITH ORDER (c1 ASC NULLS LAST) \
+            LOCATION 'foo''s.csv' OPTIONS ('format.has_header' 'true')",
+        );
+    }
+
     #[test]
     fn copy_to_table_to_table() -> Result<(), ParserError> {
         // positive case
@@ -1412,6 +1927,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn refresh_materialized_view() -> Result<(), ParserError> {
+        let sql = "REFRESH MATERIALIZED VIEW foo.bar";
+        let expected =
+            Statement::RefreshMaterializedView(RefreshMaterializedViewStatement {
+                name: ObjectName(vec![Ident::new("foo"), Ident::new("bar")]),
+            });
+        assert_eq!(verified_stmt(sql), expected);
+
+        expect_parse_error(
+            "REFRESH VIEW foo",
+            "sql parser error: Expected: MATERIALIZED, found: VIEW",
+        );
+        Ok(())
+    }
+
     #[test]
     fn skip_copy_into_snowflake() -> Result<(), ParserError> {
         let sql = "COPY INTO foo FROM @~/staged FILE_FORMAT = (FORMAT_NAME = 'mycsv');";
@@ -1462,6 +1993,7 @@ mod tests {
             let expected = Statement::Explain(ExplainStatement {
                 analyze,
                 verbose,
+                format: None,
                 statement: Box::new(expected_copy),
             });
             assert_eq!(verified_stmt(sql), expected);
@@ -1469,6 +2001,56 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn explain_format() -> Result<(), ParserError> {
+        let cases = vec![
+            (
+                "EXPLAIN FORMAT JSON SELECT 1",
+                "EXPLAIN FORMAT JSON SELECT 1",
+                false,
+            ),
+            (
+                "EXPLAIN ANALYZE FORMAT graphviz SELECT 1",
+                "EXPLAIN ANALYZE FORMAT graphviz SELECT 1",
+                true,
+            ),
+            (
+                "EXPLAIN (FORMAT JSON) SELECT 1",
+                "EXPLAIN FORMAT JSON SELECT 1",
+                false,
+            ),
+            (
+                "EXPLAIN (ANALYZE, FORMAT JSON) SELECT 1",
+                "EXPLAIN ANALYZE FORMAT JSON SELECT 1",
+                true,
+            ),
+            (
+                "EXPLAIN (ANALYZE TRUE, VERBOSE FALSE, FORMAT JSON) SELECT 1",
+                "EXPLAIN ANALYZE FORMAT JSON SELECT 1",
+                true,
+            ),
+        ];
+        for (sql, canonical, analyze) in cases {
+            let Statement::Explain(explain) = one_statement_parses_to(sql, canonical)
+            else {
+                panic!("Expected explain");
+            };
+            assert_eq!(explain.analyze, analyze);
+            assert!(!explain.verbose);
+            assert!(explain.format.is_some());
+        }
+
+        expect_parse_error(
+            "EXPLAIN (FORMAT JSON, FORMAT GRAPHVIZ) SELECT 1",
+            "FORMAT specified more than once",
+        );
+        expect_parse_error(
+            "EXPLAIN FORMAT 1 SELECT 1",
+            "Expected an explain format such as JSON, found: 1",
+        );
+        Ok(())
+    }
+
     #[test]
     fn copy_to_query_to_table() -> Result<(), ParserError> {
         let statement = verified_stmt("SELECT 1");
@@ -1572,6 +2154,53 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn recursive_cte_search_and_cycle() {
+        verified_stmt(
+            "WITH RECURSIVE t (id) AS (SELECT 1 UNION ALL SELECT e.dst FROM edges AS e JOIN t ON e.src = t.id) \
+            CYCLE id SET is_cycle USING path SELECT * FROM t",
+        );
+
+        verified_stmt(
+            "WITH RECURSIVE t AS (SELECT 1 AS id UNION SELECT p.id + 1 FROM t AS p WHERE p.id < 3) \
+            SEARCH DEPTH FIRST BY id SET seq SELECT * FROM t ORDER BY seq",
+        );
+
+        verified_stmt(
+            "WITH RECURSIVE t AS (SELECT 1 AS id UNION ALL SELECT id + 1 FROM t WHERE id < 3) \
+            SEARCH BREADTH FIRST BY id SET seq CYCLE id SET c TO 'Y' DEFAULT 'N' USING p SELECT id FROM t",
+        );
+
+        // Clauses of nested recursive CTEs
+        verified_stmt(
+            "WITH RECURSIVE a AS (WITH RECURSIVE b AS (SELECT 1 AS id UNION ALL SELECT id + 1 FROM b WHERE id < 3) \
+            CYCLE id SET c USING p SELECT id FROM b UNION ALL SELECT id + 1 FROM a WHERE id < 5) \
+            SEARCH DEPTH FIRST BY id SET seq SELECT * FROM a",
+        );
+
+        let statement = verified_stmt(
+            "EXPLAIN WITH RECURSIVE t AS (SELECT 1 AS id UNION ALL SELECT id + 1 FROM t) \
+            CYCLE id SET c USING p SELECT * FROM t",
+        );
+        let Statement::Explain(explain) = statement else {
+            panic!("Expected EXPLAIN, got {statement:?}");
+        };
+        let Statement::RecursiveCte(recursive_cte) = *explain.statement else {
+            panic!("Expected recursive CTE, got {:?}", explain.statement);
+        };
+        assert_eq!(recursive_cte.clauses.len(), 1);
+        assert_eq!(recursive_cte.clauses[0].cte_name.value, "t");
+
+        expect_parse_error(
+            "WITH t AS (SELECT 1 AS id) CYCLE id SET c USING p SELECT * FROM t",
+            "SEARCH and CYCLE clauses are only supported for CTEs of a WITH RECURSIVE query",
+        );
+        expect_parse_error(
+            "WITH RECURSIVE t AS (SELECT 1 AS id UNION ALL SELECT id + 1 FROM t) CYCLE id SET c TO 1 USING p SELECT * FROM t",
+            "Expected: DEFAULT, found: USING",
+        );
+    }
+
     // For error cases, see: `copy.slt`
 
     fn object_name(name: &str) -> CopyToSource {
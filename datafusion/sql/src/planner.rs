@@ -34,7 +34,7 @@ use datafusion_common::TableReference;
 use datafusion_common::{not_impl_err, plan_err, DFSchema, DataFusionError, Result};
 use datafusion_expr::logical_plan::{LogicalPlan, LogicalPlanBuilder};
 use datafusion_expr::utils::find_column_exprs;
-use datafusion_expr::{col, Expr};
+use datafusion_expr::{col, Expr, COMMENT_METADATA_KEY};
 
 use crate::utils::make_decimal_type;
 pub use datafusion_expr::planner::ContextProvider;
@@ -256,11 +256,22 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 .options
                 .iter()
                 .any(|x| x.option == ColumnOption::NotNull);
-            fields.push(Field::new(
+            let mut field = Field::new(
                 self.ident_normalizer.normalize(column.name),
                 data_type,
                 !not_nullable,
-            ));
+            );
+            // the comment on the column is kept in the metadata of its field
+            if let Some(comment) = column.options.iter().find_map(|x| match &x.option {
+                ColumnOption::Comment(comment) => Some(comment),
+                _ => None,
+            }) {
+                field = field.with_metadata(HashMap::from([(
+                    COMMENT_METADATA_KEY.to_string(),
+                    comment.clone(),
+                )]));
+            }
+            fields.push(field);
         }
 
         Ok(Schema::new(fields))
@@ -20,14 +20,17 @@ use std::sync::Arc;
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 
 use crate::stack::StackGuard;
-use datafusion_common::{not_impl_err, Constraints, DFSchema, Result};
+use datafusion_common::{
+    internal_err, not_impl_err, plan_err, Column, Constraints, DFSchema, Result,
+};
 use datafusion_expr::expr::Sort;
 use datafusion_expr::{
-    CreateMemoryTable, DdlStatement, Distinct, LogicalPlan, LogicalPlanBuilder,
+    CreateMemoryTable, DdlStatement, Distinct, FillStrategy, GapFill, LogicalPlan,
+    LogicalPlanBuilder,
 };
 use sqlparser::ast::{
-    Expr as SQLExpr, Offset as SQLOffset, OrderBy, OrderByExpr, Query, SelectInto,
-    SetExpr,
+    Expr as SQLExpr, Interpolate, InterpolateExpr, Offset as SQLOffset, OrderBy,
+    OrderByExpr, Query, SelectInto, SetExpr, Value, WithFill,
 };
 
 impl<S: ContextProvider> SqlToRel<'_, S> {
@@ -52,8 +55,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 let select_into = select.into.take();
                 // Order-by expressions may refer to columns in the `FROM` clause,
                 // so we need to process `SELECT` and `ORDER BY` together.
-                let oby_exprs = to_order_by_exprs(query.order_by)?;
+                let (oby_exprs, fill) = to_order_by_exprs(query.order_by)?;
                 let plan = self.select_to_plan(*select, oby_exprs, planner_context)?;
+                let plan = self.gap_fill(plan, fill, planner_context)?;
                 let plan =
                     self.limit(plan, query.offset, query.limit, planner_context)?;
                 // Process the `SELECT INTO` after `LIMIT`.
@@ -68,7 +72,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     let _guard = StackGuard::new(256 * 1024);
                     self.set_expr_to_plan(other, planner_context)
                 }?;
-                let oby_exprs = to_order_by_exprs(query.order_by)?;
+                let (oby_exprs, fill) = to_order_by_exprs(query.order_by)?;
                 let order_by_rex = self.order_by_to_sort_expr(
                     oby_exprs,
                     plan.schema(),
@@ -77,6 +81,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     None,
                 )?;
                 let plan = self.order_by(plan, order_by_rex)?;
+                let plan = self.gap_fill(plan, fill, planner_context)?;
                 self.limit(plan, query.offset, query.limit, planner_context)
             }
         }
@@ -128,6 +133,97 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
     }
 
+    /// Wrap the sorted logical plan in a [`GapFill`] for `ORDER BY ... WITH FILL`
+    fn gap_fill(
+        &self,
+        plan: LogicalPlan,
+        fill: Option<OrderByFill>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let Some(OrderByFill {
+            exprs,
+            with_fill,
+            interpolate,
+        }) = fill
+        else {
+            return Ok(plan);
+        };
+
+        // The sort expressions must be columns of the output, so they are
+        // resolved against the sorted plan rather than its input
+        let mut partition_by = self.order_by_to_sort_expr(
+            exprs,
+            plan.schema(),
+            planner_context,
+            true,
+            None,
+        )?;
+        let Some(fill_expr) = partition_by.pop() else {
+            return internal_err!("WITH FILL without an ORDER BY expression");
+        };
+
+        // FROM, TO and STEP are constants
+        let empty_schema = DFSchema::empty();
+        let mut constant = |expr: Option<SQLExpr>| {
+            expr.map(|e| self.sql_to_expr(e, &empty_schema, planner_context))
+                .transpose()
+        };
+        let from = constant(with_fill.from)?;
+        let to = constant(with_fill.to)?;
+        let step = constant(with_fill.step)?;
+
+        let schema = plan.schema();
+        let mut strategies = vec![FillStrategy::Null; schema.fields().len()];
+        match interpolate {
+            None => {}
+            // A bare INTERPOLATE repeats the previous value of every column
+            Some(Interpolate { exprs: None }) => {
+                strategies.fill(FillStrategy::Previous);
+            }
+            Some(Interpolate {
+                exprs: Some(interpolations),
+            }) => {
+                for InterpolateExpr { column, expr } in interpolations {
+                    let name = self.ident_normalizer.normalize(column);
+                    let (qualifier, field) =
+                        schema.qualified_field_with_unqualified_name(&name)?;
+                    let index =
+                        schema.index_of_column(&Column::from((qualifier, field)))?;
+                    strategies[index] = match expr {
+                        None => FillStrategy::Previous,
+                        Some(SQLExpr::Value(Value::Null)) => FillStrategy::Null,
+                        Some(SQLExpr::Identifier(ident))
+                            if ident.value.eq_ignore_ascii_case("previous") =>
+                        {
+                            FillStrategy::Previous
+                        }
+                        Some(SQLExpr::Identifier(ident))
+                            if ident.value.eq_ignore_ascii_case("linear") =>
+                        {
+                            FillStrategy::Linear
+                        }
+                        Some(expr) => {
+                            return not_impl_err!(
+                                "INTERPOLATE {name} AS {expr} is not supported, expected NULL, PREVIOUS or LINEAR"
+                            );
+                        }
+                    };
+                }
+            }
+        }
+
+        let gap_fill = GapFill::try_new(
+            Arc::new(plan),
+            partition_by,
+            fill_expr,
+            from,
+            to,
+            step,
+            strategies,
+        )?;
+        Ok(LogicalPlan::GapFill(gap_fill))
+    }
+
     /// Wrap the logical plan in a `SelectInto`
     fn select_into(
         &self,
@@ -144,6 +240,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     or_replace: false,
                     temporary: false,
                     column_defaults: vec![],
+                    comment: None,
                 },
             ))),
             _ => Ok(plan),
@@ -151,14 +248,52 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     }
 }
 
-/// Returns the order by expressions from the query.
-fn to_order_by_exprs(order_by: Option<OrderBy>) -> Result<Vec<OrderByExpr>> {
-    let Some(OrderBy { exprs, interpolate }) = order_by else {
+/// The `WITH FILL` and `INTERPOLATE` modifiers of an `ORDER BY` clause
+struct OrderByFill {
+    /// The order by expressions, the last one is filled
+    exprs: Vec<OrderByExpr>,
+    with_fill: WithFill,
+    interpolate: Option<Interpolate>,
+}
+
+/// Returns the order by expressions from the query, and the `WITH FILL`
+/// modifier if any.
+fn to_order_by_exprs(
+    order_by: Option<OrderBy>,
+) -> Result<(Vec<OrderByExpr>, Option<OrderByFill>)> {
+    let Some(OrderBy {
+        mut exprs,
+        interpolate,
+    }) = order_by
+    else {
         // If no order by, return an empty array.
-        return Ok(vec![]);
+        return Ok((vec![], None));
+    };
+
+    let Some(last) = exprs.last_mut() else {
+        return Ok((exprs, None));
+    };
+    let Some(with_fill) = last.with_fill.take() else {
+        if exprs.iter().any(|e| e.with_fill.is_some()) {
+            return not_impl_err!(
+                "WITH FILL is only supported on the last ORDER BY expression"
+            );
+        }
+        if interpolate.is_some() {
+            return plan_err!("INTERPOLATE requires an ORDER BY expression WITH FILL");
+        }
+        return Ok((exprs, None));
     };
-    if let Some(_interpolate) = interpolate {
-        return not_impl_err!("ORDER BY INTERPOLATE is not supported");
+    if exprs.iter().any(|e| e.with_fill.is_some()) {
+        return not_impl_err!(
+            "WITH FILL is only supported on the last ORDER BY expression"
+        );
     }
-    Ok(exprs)
+
+    let fill = OrderByFill {
+        exprs: exprs.clone(),
+        with_fill,
+        interpolate,
+    };
+    Ok((exprs, Some(fill)))
 }
@@ -21,18 +21,24 @@ use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 
 use datafusion_common::tree_node::{Transformed, TreeNode};
 use datafusion_common::{
-    not_impl_err, plan_err, DFSchema, Diagnostic, Result, Span, TableReference,
+    not_impl_err, plan_err, Column, DFSchema, Diagnostic, Result, Span, TableReference,
 };
 use datafusion_expr::builder::subquery_alias;
-use datafusion_expr::{expr::Unnest, Expr, LogicalPlan, LogicalPlanBuilder};
+use datafusion_expr::{
+    expr::Unnest, when, Expr, LogicalPlan, LogicalPlanBuilder, SampleMethod,
+};
 use datafusion_expr::{Subquery, SubqueryAlias};
-use sqlparser::ast::{FunctionArg, FunctionArgExpr, Spanned, TableFactor};
+use sqlparser::ast::{
+    Expr as SQLExpr, ExprWithAlias, FunctionArg, FunctionArgExpr, Ident,
+    PivotValueSource, Spanned, TableFactor, TableSampleKind, TableSampleMethod,
+    TableSampleQuantity, TableSampleSeed, TableSampleUnit, Value,
+};
 
 mod join;
 
 impl<S: ContextProvider> SqlToRel<'_, S> {
     /// Create a `LogicalPlan` that scans the named relation
-    fn create_relation(
+    pub(crate) fn create_relation(
         &self,
         relation: TableFactor,
         planner_context: &mut PlannerContext,
@@ -40,9 +46,13 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         let relation_span = relation.span();
         let (plan, alias) = match relation {
             TableFactor::Table {
-                name, alias, args, ..
+                name,
+                alias,
+                args,
+                sample,
+                ..
             } => {
-                if let Some(func_args) = args {
+                let (plan, alias) = if let Some(func_args) = args {
                     let tbl_func_name = name.0.first().unwrap().value.to_string();
                     let args = func_args
                         .args
@@ -99,6 +109,10 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                         }?,
                         alias,
                     )
+                };
+                match sample {
+                    Some(sample) => (self.table_sample_to_plan(plan, sample)?, alias),
+                    None => (plan, alias),
                 }
             }
             TableFactor::Derived {
@@ -121,10 +135,6 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 with_offset_alias: None,
                 with_ordinality,
             } => {
-                if with_ordinality {
-                    return not_impl_err!("UNNEST with ordinality is not supported yet");
-                }
-
                 // Unnest table factor has empty input
                 let schema = DFSchema::empty();
                 let input = LogicalPlanBuilder::empty(true).build()?;
@@ -145,7 +155,9 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 if unnest_exprs.is_empty() {
                     return plan_err!("UNNEST must have at least one argument");
                 }
-                let logical_plan = self.try_process_unnest(input, unnest_exprs)?;
+                let ordinality_column = with_ordinality.then_some("ordinality");
+                let logical_plan =
+                    self.try_process_unnest(input, unnest_exprs, ordinality_column)?;
                 (logical_plan, alias)
             }
             TableFactor::UNNEST { .. } => {
@@ -153,6 +165,48 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     "UNNEST table factor with offset is not supported yet"
                 );
             }
+            TableFactor::Pivot {
+                table,
+                aggregate_functions,
+                value_column,
+                value_source,
+                default_on_null,
+                alias,
+            } => {
+                let input = self.create_relation(*table, planner_context)?;
+                let plan = self.pivot_to_plan(
+                    input,
+                    aggregate_functions,
+                    value_column,
+                    value_source,
+                    default_on_null,
+                    planner_context,
+                )?;
+                (plan, alias)
+            }
+            TableFactor::Unpivot {
+                table,
+                value,
+                name,
+                columns,
+                alias,
+            } => {
+                let input = self.create_relation(*table, planner_context)?;
+                let columns = columns
+                    .into_iter()
+                    .map(|column| {
+                        Column::from_name(self.ident_normalizer.normalize(column))
+                    })
+                    .collect();
+                let plan = LogicalPlanBuilder::from(input)
+                    .unpivot(
+                        self.ident_normalizer.normalize(value),
+                        self.ident_normalizer.normalize(name),
+                        columns,
+                    )?
+                    .build()?;
+                (plan, alias)
+            }
             // @todo Support TableFactory::TableFunction?
             _ => {
                 return not_impl_err!(
@@ -169,6 +223,147 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
     }
 
+    /// Wrap the scan of a table in a [`Sample`] for its `TABLESAMPLE` clause
+    ///
+    /// [`Sample`]: datafusion_expr::Sample
+    fn table_sample_to_plan(
+        &self,
+        input: LogicalPlan,
+        sample: TableSampleKind,
+    ) -> Result<LogicalPlan> {
+        let sample = match sample {
+            TableSampleKind::BeforeTableAlias(sample)
+            | TableSampleKind::AfterTableAlias(sample) => *sample,
+        };
+        if sample.bucket.is_some() || sample.offset.is_some() {
+            return not_impl_err!("Unsupported TABLESAMPLE clause: {sample}");
+        }
+        let method = match sample.name {
+            None | Some(TableSampleMethod::Bernoulli | TableSampleMethod::Row) => {
+                SampleMethod::Bernoulli
+            }
+            Some(TableSampleMethod::System | TableSampleMethod::Block) => {
+                SampleMethod::System
+            }
+        };
+        let fraction = match &sample.quantity {
+            Some(TableSampleQuantity {
+                value: SQLExpr::Value(Value::Number(n, _)),
+                unit: None | Some(TableSampleUnit::Percent),
+                ..
+            }) => match n.parse::<f64>() {
+                Ok(percent) => percent / 100.0,
+                Err(_) => return plan_err!("Invalid TABLESAMPLE percentage: {n}"),
+            },
+            _ => {
+                return not_impl_err!(
+                    "TABLESAMPLE only supports a literal percentage: {sample}"
+                )
+            }
+        };
+        let seed = match &sample.seed {
+            None => None,
+            Some(TableSampleSeed {
+                value: Value::Number(n, _),
+                ..
+            }) => match n.parse::<u64>() {
+                Ok(seed) => Some(seed),
+                Err(_) => return plan_err!("Invalid TABLESAMPLE seed: {n}"),
+            },
+            Some(seed) => return plan_err!("Invalid TABLESAMPLE seed: {}", seed.value),
+        };
+        LogicalPlanBuilder::from(input)
+            .sample(method, fraction, seed)?
+            .build()
+    }
+
+    /// Lower a `PIVOT` table factor to an aggregate with one filtered
+    /// aggregate expression per pivot value
+    fn pivot_to_plan(
+        &self,
+        input: LogicalPlan,
+        aggregate_functions: Vec<ExprWithAlias>,
+        value_column: Vec<Ident>,
+        value_source: PivotValueSource,
+        default_on_null: Option<SQLExpr>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let PivotValueSource::List(values) = value_source else {
+            return not_impl_err!(
+                "PIVOT with dynamic pivot values ({value_source}) is not supported"
+            );
+        };
+
+        let schema = Arc::clone(input.schema());
+        let aggr_exprs = aggregate_functions
+            .into_iter()
+            .map(|ExprWithAlias { expr, alias }| {
+                let expr =
+                    self.sql_expr_to_logical_expr(expr, &schema, planner_context)?;
+                Ok(match alias {
+                    Some(alias) => expr.alias(self.ident_normalizer.normalize(alias)),
+                    None => expr,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let pivot_column = match <[Ident; 1]>::try_from(value_column) {
+            Ok([ident]) => SQLExpr::Identifier(ident),
+            Err(idents) => SQLExpr::CompoundIdentifier(idents),
+        };
+        let pivot_column =
+            self.sql_expr_to_logical_expr(pivot_column, &schema, planner_context)?;
+        let pivot_values = values
+            .into_iter()
+            .map(|ExprWithAlias { expr, alias }| {
+                let expr = self.sql_expr_to_logical_expr(
+                    expr,
+                    &DFSchema::empty(),
+                    planner_context,
+                )?;
+                Ok(match alias {
+                    Some(alias) => expr.alias(self.ident_normalizer.normalize(alias)),
+                    None => expr,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let pivot_len = pivot_values.len() * aggr_exprs.len();
+
+        let plan = LogicalPlanBuilder::from(input)
+            .pivot(aggr_exprs, pivot_column, pivot_values)?
+            .build()?;
+        let Some(default_on_null) = default_on_null else {
+            return Ok(plan);
+        };
+
+        // Replace the NULL results of the pivoted aggregates, which follow
+        // the grouping columns, with the default value
+        let default_on_null = self.sql_expr_to_logical_expr(
+            default_on_null,
+            &DFSchema::empty(),
+            planner_context,
+        )?;
+        let group_len = plan.schema().fields().len() - pivot_len;
+        let exprs = plan
+            .schema()
+            .columns()
+            .into_iter()
+            .enumerate()
+            .map(|(i, column)| {
+                if i < group_len {
+                    return Ok(Expr::Column(column));
+                }
+                let name = column.name.clone();
+                Ok(when(
+                    Expr::Column(column.clone()).is_null(),
+                    default_on_null.clone(),
+                )
+                .otherwise(Expr::Column(column))?
+                .alias(name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        LogicalPlanBuilder::from(plan).project(exprs)?.build()
+    }
+
     pub(crate) fn create_relation_subquery(
         &self,
         subquery: TableFactor,
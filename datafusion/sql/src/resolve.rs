@@ -146,7 +146,13 @@ fn visit_statement(statement: &DFStatement, visitor: &mut RelationVisitor) {
                 query.visit(visitor);
             }
         },
+        DFStatement::RefreshMaterializedView(refresh) => {
+            visitor.insert_relation(&refresh.name);
+        }
         DFStatement::Explain(explain) => visit_statement(&explain.statement, visitor),
+        DFStatement::RecursiveCte(recursive_cte) => {
+            visit_statement(&recursive_cte.statement, visitor)
+        }
     }
 }
 
@@ -26,7 +26,7 @@ use crate::utils::{
 };
 
 use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
-use datafusion_common::{not_impl_err, plan_err, Result};
+use datafusion_common::{internal_err, not_impl_err, plan_err, Result};
 use datafusion_common::{RecursionUnnestOption, UnnestOptions};
 use datafusion_expr::expr::{Alias, PlannedReplaceSelectItem, WildcardOptions};
 use datafusion_expr::expr_rewriter::{
@@ -36,7 +36,7 @@ use datafusion_expr::utils::{
     expr_as_column_expr, expr_to_columns, find_aggregate_exprs, find_window_exprs,
 };
 use datafusion_expr::{
-    qualified_wildcard_with_options, wildcard_with_options, Aggregate, Expr, Filter,
+    col, qualified_wildcard_with_options, wildcard_with_options, Aggregate, Expr, Filter,
     GroupingSet, LogicalPlan, LogicalPlanBuilder, Partitioning,
 };
 
@@ -62,9 +62,6 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         if !select.lateral_views.is_empty() {
             return not_impl_err!("LATERAL VIEWS");
         }
-        if select.qualify.is_some() {
-            return not_impl_err!("QUALIFY");
-        }
         if select.top.is_some() {
             return not_impl_err!("TOP");
         }
@@ -140,9 +137,28 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             })
             .transpose()?;
 
+        // Optionally the QUALIFY expression, which may also refer to aliased
+        // columns, e.g. the alias of a window function in the SELECT list.
+        let qualify_expr_opt = select
+            .qualify
+            .map::<Result<Expr>, _>(|qualify_expr| {
+                let qualify_expr = self.sql_expr_to_logical_expr(
+                    qualify_expr,
+                    &combined_schema,
+                    planner_context,
+                )?;
+                let qualify_expr = resolve_aliases_to_exprs(qualify_expr, &alias_map)?;
+                normalize_col(qualify_expr, &projected_plan)
+            })
+            .transpose()?;
+
         // The outer expressions we will search through for aggregates.
-        // Aggregates may be sourced from the SELECT list or from the HAVING expression.
-        let aggr_expr_haystack = select_exprs.iter().chain(having_expr_opt.iter());
+        // Aggregates may be sourced from the SELECT list or from the HAVING
+        // and QUALIFY expressions.
+        let aggr_expr_haystack = select_exprs
+            .iter()
+            .chain(having_expr_opt.iter())
+            .chain(qualify_expr_opt.iter());
         // All of the aggregate expressions (deduplicated).
         let aggr_exprs = find_aggregate_exprs(aggr_expr_haystack);
 
@@ -191,21 +207,29 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         };
 
         // Process group by, aggregation or having
-        let (plan, mut select_exprs_post_aggr, having_expr_post_aggr) = if !group_by_exprs
-            .is_empty()
-            || !aggr_exprs.is_empty()
-        {
+        let AggregatePlanResult {
+            plan,
+            select_exprs: mut select_exprs_post_aggr,
+            having_expr: having_expr_post_aggr,
+            qualify_expr: qualify_expr_post_aggr,
+        } = if !group_by_exprs.is_empty() || !aggr_exprs.is_empty() {
             self.aggregate(
                 &base_plan,
                 &select_exprs,
                 having_expr_opt.as_ref(),
+                qualify_expr_opt.as_ref(),
                 &group_by_exprs,
                 &aggr_exprs,
             )?
         } else {
             match having_expr_opt {
                 Some(having_expr) => return plan_err!("HAVING clause references: {having_expr} must appear in the GROUP BY clause or be used in an aggregate function"),
-                None => (base_plan.clone(), select_exprs.clone(), having_expr_opt)
+                None => AggregatePlanResult {
+                    plan: base_plan.clone(),
+                    select_exprs: select_exprs.clone(),
+                    having_expr: having_expr_opt,
+                    qualify_expr: qualify_expr_opt,
+                },
             }
         };
 
@@ -217,10 +241,22 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             plan
         };
 
-        // Process window function
-        let window_func_exprs = find_window_exprs(&select_exprs_post_aggr);
+        // Process window function, including the window functions only
+        // referenced by the QUALIFY expression
+        let window_func_exprs = find_window_exprs(
+            &select_exprs_post_aggr
+                .iter()
+                .chain(qualify_expr_post_aggr.iter())
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
 
         let plan = if window_func_exprs.is_empty() {
+            if qualify_expr_post_aggr.is_some() {
+                return plan_err!(
+                    "QUALIFY clause requires a window function in the SELECT list or QUALIFY clause"
+                );
+            }
             plan
         } else {
             let plan = LogicalPlanBuilder::window_plan(plan, window_func_exprs.clone())?;
@@ -234,8 +270,18 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             plan
         };
 
+        // Filter the results of the window functions with the QUALIFY expression
+        let plan = if let Some(qualify_expr) = qualify_expr_post_aggr {
+            let qualify_expr = rebase_expr(&qualify_expr, &window_func_exprs, &plan)?;
+            LogicalPlanBuilder::from(plan)
+                .filter(qualify_expr)?
+                .build()?
+        } else {
+            plan
+        };
+
         // Try processing unnest expression or do the final projection
-        let plan = self.try_process_unnest(plan, select_exprs_post_aggr)?;
+        let plan = self.try_process_unnest(plan, select_exprs_post_aggr, None)?;
 
         // Process distinct clause
         let plan = match select.distinct {
@@ -289,10 +335,14 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     }
 
     /// Try converting Expr(Unnest(Expr)) to Projection/Unnest/Projection
+    ///
+    /// If `ordinality_column` is set, the last unnest also emits the position
+    /// of each element under that name, which is appended to the select exprs.
     pub(super) fn try_process_unnest(
         &self,
         input: LogicalPlan,
         select_exprs: Vec<Expr>,
+        ordinality_column: Option<&str>,
     ) -> Result<LogicalPlan> {
         // Try process group by unnest
         let input = self.try_process_aggregate_unnest(input)?;
@@ -359,6 +409,20 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             }
         }
 
+        if let Some(name) = ordinality_column {
+            let LogicalPlan::Unnest(unnest) = intermediate_plan else {
+                return internal_err!("Expected an Unnest plan to add ordinality to");
+            };
+            intermediate_plan =
+                LogicalPlanBuilder::from(Arc::unwrap_or_clone(unnest.input))
+                    .unnest_columns_with_options(
+                        unnest.exec_columns,
+                        unnest.options.with_ordinality_column(name),
+                    )?
+                    .build()?;
+            intermediate_select_exprs.push(col(name));
+        }
+
         LogicalPlanBuilder::from(intermediate_plan)
             .project(intermediate_select_exprs)?
             .build()
@@ -712,31 +776,28 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
     ///
     /// # Arguments
     ///
-    /// * `input`           - The input plan that will be aggregated. The grouping, aggregate, and
-    ///                       "having" expressions must all be resolvable from this plan.
-    /// * `select_exprs`    - The projection expressions from the SELECT clause.
-    /// * `having_expr_opt` - Optional HAVING clause.
-    /// * `group_by_exprs`  - Grouping expressions from the GROUP BY clause. These can be column
-    ///                       references or more complex expressions.
-    /// * `aggr_exprs`      - Aggregate expressions, such as `SUM(a)` or `COUNT(1)`.
+    /// * `input`            - The input plan that will be aggregated. The grouping, aggregate, and
+    ///                        "having" expressions must all be resolvable from this plan.
+    /// * `select_exprs`     - The projection expressions from the SELECT clause.
+    /// * `having_expr_opt`  - Optional HAVING clause.
+    /// * `qualify_expr_opt` - Optional QUALIFY clause.
+    /// * `group_by_exprs`   - Grouping expressions from the GROUP BY clause. These can be column
+    ///                        references or more complex expressions.
+    /// * `aggr_exprs`       - Aggregate expressions, such as `SUM(a)` or `COUNT(1)`.
     ///
     /// # Return
     ///
-    /// The return value is a triplet of the following items:
-    ///
-    /// * `plan`                   - A [LogicalPlan::Aggregate] plan for the newly created aggregate.
-    /// * `select_exprs_post_aggr` - The projection expressions rewritten to reference columns from
-    ///                              the aggregate
-    /// * `having_expr_post_aggr`  - The "having" expression rewritten to reference a column from
-    ///                              the aggregate
+    /// An [`AggregatePlanResult`] with the aggregate plan and the SELECT, HAVING
+    /// and QUALIFY expressions rewritten to reference columns from the aggregate.
     fn aggregate(
         &self,
         input: &LogicalPlan,
         select_exprs: &[Expr],
         having_expr_opt: Option<&Expr>,
+        qualify_expr_opt: Option<&Expr>,
         group_by_exprs: &[Expr],
         aggr_exprs: &[Expr],
-    ) -> Result<(LogicalPlan, Vec<Expr>, Option<Expr>)> {
+    ) -> Result<AggregatePlanResult> {
         // create the aggregate plan
         let plan = LogicalPlanBuilder::from(input.clone())
             .aggregate(group_by_exprs.to_vec(), aggr_exprs.to_vec())?
@@ -753,7 +814,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         // the aggregate plan.
 
         // combine the original grouping and aggregate expressions into one list (note that
-        // we do not add the "having" expression since that is not part of the projection)
+        // we do not add the "having" and "qualify" expressions since they are not part of
+        // the projection)
         let mut aggr_projection_exprs = vec![];
         for expr in group_by_exprs {
             match expr {
@@ -817,10 +879,44 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             None
         };
 
-        Ok((plan, select_exprs_post_aggr, having_expr_post_aggr))
+        // Rewrite the QUALIFY expression to use the columns produced by the
+        // aggregation.
+        let qualify_expr_post_aggr = if let Some(qualify_expr) = qualify_expr_opt {
+            let qualify_expr_post_aggr =
+                rebase_expr(qualify_expr, &aggr_projection_exprs, input)?;
+
+            check_columns_satisfy_exprs(
+                &column_exprs_post_aggr,
+                std::slice::from_ref(&qualify_expr_post_aggr),
+                CheckColumnsSatisfyExprsPurpose::QualifyMustReferenceAggregate,
+            )?;
+
+            Some(qualify_expr_post_aggr)
+        } else {
+            None
+        };
+
+        Ok(AggregatePlanResult {
+            plan,
+            select_exprs: select_exprs_post_aggr,
+            having_expr: having_expr_post_aggr,
+            qualify_expr: qualify_expr_post_aggr,
+        })
     }
 }
 
+/// The result of planning the aggregation of a SELECT statement
+struct AggregatePlanResult {
+    /// A [LogicalPlan::Aggregate] plan for the newly created aggregate
+    plan: LogicalPlan,
+    /// The projection expressions rewritten to reference columns from the aggregate
+    select_exprs: Vec<Expr>,
+    /// The "having" expression rewritten to reference a column from the aggregate
+    having_expr: Option<Expr>,
+    /// The "qualify" expression rewritten to reference a column from the aggregate
+    qualify_expr: Option<Expr>,
+}
+
 // If there are any multiple-defined windows, we raise an error.
 fn check_conflicting_windows(window_defs: &[NamedWindowDefinition]) -> Result<()> {
     for (i, window_def_i) in window_defs.iter().enumerate() {
@@ -41,14 +41,16 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 let right_span = Span::try_from_sqlparser_span(right.span());
                 let left_plan = self.set_expr_to_plan(*left, planner_context)?;
                 let right_plan = self.set_expr_to_plan(*right, planner_context)?;
-                self.validate_set_expr_num_of_columns(
-                    op,
-                    left_span,
-                    right_span,
-                    &left_plan,
-                    &right_plan,
-                    set_expr_span,
-                )?;
+                if !Self::is_by_name(set_quantifier) {
+                    self.validate_set_expr_num_of_columns(
+                        op,
+                        left_span,
+                        right_span,
+                        &left_plan,
+                        &right_plan,
+                        set_expr_span,
+                    )?;
+                }
                 self.set_operation_to_plan(op, left_plan, right_plan, set_quantifier)
             }
             SetExpr::Query(q) => self.query_to_plan(*q, planner_context),
@@ -72,6 +74,15 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }
     }
 
+    fn is_by_name(set_quantifier: SetQuantifier) -> bool {
+        matches!(
+            set_quantifier,
+            SetQuantifier::ByName
+                | SetQuantifier::AllByName
+                | SetQuantifier::DistinctByName
+        )
+    }
+
     fn validate_set_expr_num_of_columns(
         &self,
         op: SetOperator,
@@ -113,6 +124,19 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         right_plan: LogicalPlan,
         set_quantifier: SetQuantifier,
     ) -> Result<LogicalPlan> {
+        if Self::is_by_name(set_quantifier) {
+            let builder = LogicalPlanBuilder::from(left_plan);
+            return match (op, set_quantifier) {
+                (SetOperator::Union, SetQuantifier::AllByName) => {
+                    builder.union_by_name(right_plan)?.build()
+                }
+                (SetOperator::Union, _) => {
+                    builder.union_by_name_distinct(right_plan)?.build()
+                }
+                _ => not_impl_err!("{op} {set_quantifier} not implemented"),
+            };
+        }
+
         let all = Self::is_union_all(set_quantifier)?;
         match (op, all) {
             (SetOperator::Union, true) => LogicalPlanBuilder::from(left_plan)
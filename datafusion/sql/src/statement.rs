@@ -22,51 +22,63 @@ use std::sync::Arc;
 
 use crate::parser::{
     CopyToSource, CopyToStatement, CreateExternalTable, DFParser, ExplainStatement,
-    LexOrdering, Statement as DFStatement,
+    LexOrdering, RefreshMaterializedViewStatement, Statement as DFStatement,
 };
 use crate::planner::{
     object_name_to_qualifier, ContextProvider, PlannerContext, SqlToRel,
 };
+use crate::unparser::ddl_to_sql;
 use crate::utils::normalize_ident;
 
-use arrow_schema::{DataType, Fields};
+use arrow_schema::{DataType, Field, Fields};
 use datafusion_common::error::_plan_err;
+use datafusion_common::format::ExplainFormat;
 use datafusion_common::parsers::CompressionTypeVariant;
+use datafusion_common::tree_node::{Transformed, TreeNode};
 use datafusion_common::{
     exec_err, internal_err, not_impl_err, plan_datafusion_err, plan_err, schema_err,
     unqualified_field_not_found, Column, Constraint, Constraints, DFSchema, DFSchemaRef,
     DataFusionError, Result, ScalarValue, SchemaError, SchemaReference, TableReference,
     ToDFSchema,
 };
-use datafusion_expr::dml::{CopyTo, InsertOp};
+use datafusion_expr::dml::{
+    CopyTo, InsertOp, MergeAction as WriteMergeAction, MERGE_ACTION_COLUMN,
+    MERGE_TARGET_ROW_COLUMN,
+};
+use datafusion_expr::expr::{Case, Placeholder, WindowFunction};
 use datafusion_expr::expr_rewriter::normalize_col_with_schemas_and_ambiguity_check;
 use datafusion_expr::logical_plan::builder::project;
 use datafusion_expr::logical_plan::DdlStatement;
 use datafusion_expr::utils::expr_to_columns;
 use datafusion_expr::{
-    cast, col, Analyze, CreateCatalog, CreateCatalogSchema,
+    cast, col, lit, Analyze, CommentOn, CreateCatalog, CreateCatalogSchema,
     CreateExternalTable as PlanCreateExternalTable, CreateFunction, CreateFunctionBody,
-    CreateIndex as PlanCreateIndex, CreateMemoryTable, CreateView, Deallocate,
-    DescribeTable, DmlStatement, DropCatalogSchema, DropFunction, DropTable, DropView,
-    EmptyRelation, Execute, Explain, Expr, ExprSchemable, Filter, LogicalPlan,
-    LogicalPlanBuilder, OperateFunctionArg, PlanType, Prepare, SetVariable, SortExpr,
-    Statement as PlanStatement, ToStringifiedPlan, TransactionAccessMode,
-    TransactionConclusion, TransactionEnd, TransactionIsolationLevel, TransactionStart,
-    Volatility, WriteOp,
+    CreateIndex as PlanCreateIndex, CreateMaterializedView, CreateMemoryTable,
+    CreateView, Deallocate, DescribeTable, DmlStatement, DropCatalogSchema, DropFunction,
+    DropTable, DropView, EmptyRelation, Execute, Explain, Expr, ExprSchemable, Filter,
+    JoinType, LogicalPlan, LogicalPlanBuilder, OperateFunctionArg, PlanType, Prepare,
+    RefreshMaterializedView, SetVariable, SortExpr, Statement as PlanStatement,
+    TableSource, ToStringifiedPlan, TransactionAccessMode, TransactionConclusion,
+    TransactionEnd, TransactionIsolationLevel, TransactionStart, Volatility, WriteOp,
 };
 use sqlparser::ast::{
     self, BeginTransactionKind, NullsDistinctOption, ShowStatementIn,
     ShowStatementOptions, SqliteOnConflict, TableObject, UpdateTableFromKind,
 };
 use sqlparser::ast::{
-    Assignment, AssignmentTarget, ColumnDef, CreateIndex, CreateTable,
-    CreateTableOptions, Delete, DescribeAlias, Expr as SQLExpr, FromTable, Ident, Insert,
-    ObjectName, ObjectType, OneOrManyWithParens, Query, SchemaName, SetExpr,
-    ShowCreateObject, ShowStatementFilter, Statement, TableConstraint, TableFactor,
-    TableWithJoins, TransactionMode, UnaryOperator, Value,
+    AnalyzeFormat, Assignment, AssignmentTarget, ColumnDef, CommentDef, CommentObject,
+    CreateIndex, CreateTable, CreateTableOptions, Delete, DescribeAlias, Expr as SQLExpr,
+    FromTable, Ident, Insert, MergeAction, MergeClause, MergeClauseKind, MergeInsertExpr,
+    MergeInsertKind, ObjectName, ObjectType, OneOrManyWithParens, Query, SchemaName,
+    SetExpr, ShowCreateObject, ShowStatementFilter, SqlOption, Statement,
+    TableConstraint, TableFactor, TableWithJoins, TransactionMode, UnaryOperator, Value,
 };
 use sqlparser::parser::ParserError::ParserError;
 
+/// Name of the column marking the rows of the source relation of a `MERGE`
+/// statement, which is `NULL` for target rows not matched by any source row
+const MERGE_SOURCE_EXISTS_COLUMN: &str = "__merge_source_exists";
+
 fn ident_to_string(ident: &Ident) -> String {
     normalize_ident(ident.to_owned())
 }
@@ -174,11 +186,22 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             DFStatement::CreateExternalTable(s) => self.external_table_to_plan(s),
             DFStatement::Statement(s) => self.sql_statement_to_plan(*s),
             DFStatement::CopyTo(s) => self.copy_to_plan(s),
+            DFStatement::RefreshMaterializedView(s) => {
+                self.refresh_materialized_view_to_plan(s)
+            }
             DFStatement::Explain(ExplainStatement {
                 verbose,
                 analyze,
+                format,
                 statement,
-            }) => self.explain_to_plan(verbose, analyze, *statement),
+            }) => {
+                let explain_format = match format {
+                    Some(format) => format.parse()?,
+                    None => ExplainFormat::default(),
+                };
+                self.explain_to_plan(verbose, analyze, explain_format, *statement)
+            }
+            DFStatement::RecursiveCte(s) => self.recursive_cte_statement_to_plan(s),
         }
     }
 
@@ -214,11 +237,21 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 verbose,
                 statement,
                 analyze,
-                format: _,
+                format,
                 describe_alias: _,
                 ..
             } => {
-                self.explain_to_plan(verbose, analyze, DFStatement::Statement(statement))
+                let explain_format = match format {
+                    Some(AnalyzeFormat::TEXT) | None => ExplainFormat::Indent,
+                    Some(AnalyzeFormat::JSON) => ExplainFormat::Json,
+                    Some(AnalyzeFormat::GRAPHVIZ) => ExplainFormat::Graphviz,
+                };
+                self.explain_to_plan(
+                    verbose,
+                    analyze,
+                    explain_format,
+                    DFStatement::Statement(statement),
+                )
             }
             Statement::Query(query) => self.query_to_plan(*query, planner_context),
             Statement::ShowVariable { variable } => self.show_variable_to_plan(&variable),
@@ -274,9 +307,6 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 with_row_access_policy,
                 with_tags,
             }) if table_properties.is_empty() && with_options.is_empty() => {
-                if temporary {
-                    return not_impl_err!("Temporary tables not supported")?;
-                }
                 if external {
                     return not_impl_err!("External tables not supported")?;
                 }
@@ -325,9 +355,6 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 if engine.is_some() {
                     return not_impl_err!("Engine not supported")?;
                 }
-                if comment.is_some() {
-                    return not_impl_err!("Comment not supported")?;
-                }
                 if auto_increment_offset.is_some() {
                     return not_impl_err!("Auto increment offset not supported")?;
                 }
@@ -402,6 +429,24 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 let column_defaults =
                     self.build_column_defaults(&columns, planner_context)?;
 
+                // column comments are kept in the metadata of the fields of
+                // the schema, which is not known ahead of the query
+                if query.is_some()
+                    && columns
+                        .iter()
+                        .flat_map(|c| &c.options)
+                        .any(|o| matches!(o.option, ast::ColumnOption::Comment(_)))
+                {
+                    return not_impl_err!(
+                        "Column comments are not supported in CREATE TABLE AS SELECT"
+                    )?;
+                }
+                let comment = comment.map(|comment| match comment {
+                    CommentDef::WithEq(comment)
+                    | CommentDef::WithoutEq(comment)
+                    | CommentDef::AfterColumnDefsWithoutEq(comment) => comment,
+                });
+
                 let has_columns = !columns.is_empty();
                 let schema = self.build_schema(columns)?.to_dfschema_ref()?;
                 if has_columns {
@@ -441,7 +486,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                             plan
                         };
 
-                        let constraints = Self::new_constraint_from_table_constraints(
+                        let constraints = self.new_constraint_from_table_constraints(
                             &all_constraints,
                             plan.schema(),
                         )?;
@@ -455,6 +500,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                                 or_replace,
                                 column_defaults,
                                 temporary,
+                                comment,
                             },
                         )))
                     }
@@ -465,7 +511,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                             schema,
                         };
                         let plan = LogicalPlan::EmptyRelation(plan);
-                        let constraints = Self::new_constraint_from_table_constraints(
+                        let constraints = self.new_constraint_from_table_constraints(
                             &all_constraints,
                             plan.schema(),
                         )?;
@@ -478,12 +524,17 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                                 or_replace,
                                 column_defaults,
                                 temporary,
+                                comment,
                             },
                         )))
                     }
                 }
             }
 
+            statement @ Statement::CreateView {
+                materialized: true, ..
+            } => self.create_materialized_view_to_plan(statement),
+
             Statement::CreateView {
                 or_replace,
                 materialized,
@@ -499,9 +550,6 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 to,
                 params,
             } => {
-                if materialized {
-                    return not_impl_err!("Materialized views not supported")?;
-                }
                 if !cluster_by.is_empty() {
                     return not_impl_err!("Cluster by not supported")?;
                 }
@@ -1082,12 +1130,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     }
                     None => None,
                 };
-                // At the moment functions can't be qualified `schema.name`
-                let name = match &name.0[..] {
-                    [] => exec_err!("Function should have name")?,
-                    [n] => n.value.clone(),
-                    [..] => not_impl_err!("Qualified functions are not supported")?,
-                };
+                let name = self.object_name_to_table_reference(name)?;
                 //
                 // Convert resulting expression to data fusion expression
                 //
@@ -1097,16 +1140,66 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 let mut planner_context = PlannerContext::new()
                     .with_prepare_param_data_types(arg_types.unwrap_or_default());
 
+                // Named arguments can be referenced by name in the body, plan
+                // them as columns and replace them with the positional
+                // placeholders `$1`, `$2`, ... afterwards
+                let named_args = args
+                    .iter()
+                    .flatten()
+                    .enumerate()
+                    .filter_map(|(i, arg)| {
+                        arg.name.as_ref().map(|name| {
+                            (
+                                self.ident_normalizer.normalize(name.clone()),
+                                i,
+                                arg.data_type.clone(),
+                            )
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                let args_schema = DFSchema::from_unqualified_fields(
+                    named_args
+                        .iter()
+                        .map(|(name, _, data_type)| {
+                            Field::new(name, data_type.clone(), true)
+                        })
+                        .collect(),
+                    HashMap::new(),
+                )?;
+
                 let function_body = match function_body {
-                    Some(r) => Some(self.sql_to_expr(
-                        match r {
-                            ast::CreateFunctionBody::AsBeforeOptions(expr) => expr,
-                            ast::CreateFunctionBody::AsAfterOptions(expr) => expr,
-                            ast::CreateFunctionBody::Return(expr) => expr,
-                        },
-                        &DFSchema::empty(),
-                        &mut planner_context,
-                    )?),
+                    Some(r) => {
+                        let expr = self.sql_to_expr(
+                            match r {
+                                ast::CreateFunctionBody::AsBeforeOptions(expr) => expr,
+                                ast::CreateFunctionBody::AsAfterOptions(expr) => expr,
+                                ast::CreateFunctionBody::Return(expr) => expr,
+                            },
+                            &args_schema,
+                            &mut planner_context,
+                        )?;
+                        let expr = expr
+                            .transform_up(|expr| match expr {
+                                Expr::Column(Column {
+                                    relation: None,
+                                    ref name,
+                                    ..
+                                }) => {
+                                    match named_args.iter().find(|(n, _, _)| n == name) {
+                                        Some((_, i, data_type)) => Ok(Transformed::yes(
+                                            Expr::Placeholder(Placeholder::new(
+                                                format!("${}", i + 1),
+                                                Some(data_type.clone()),
+                                            )),
+                                        )),
+                                        None => Ok(Transformed::no(expr)),
+                                    }
+                                }
+                                _ => Ok(Transformed::no(expr)),
+                            })?
+                            .data;
+                        Some(expr)
+                    }
                     None => None,
                 };
 
@@ -1139,13 +1232,8 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
             } => {
                 // According to postgresql documentation it can be only one function
                 // specified in drop statement
-                if let Some(desc) = func_desc.first() {
-                    // At the moment functions can't be qualified `schema.name`
-                    let name = match &desc.name.0[..] {
-                        [] => exec_err!("Function should have name")?,
-                        [n] => n.value.clone(),
-                        [..] => not_impl_err!("Qualified functions are not supported")?,
-                    };
+                if let Some(desc) = func_desc.into_iter().next() {
+                    let name = self.object_name_to_table_reference(desc.name)?;
                     let statement = DdlStatement::DropFunction(DropFunction {
                         if_exists,
                         name,
@@ -1192,6 +1280,20 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                     },
                 )))
             }
+            Statement::Merge {
+                into: _,
+                table,
+                source,
+                on,
+                clauses,
+            } => self.merge_to_plan(table, source, *on, clauses),
+            Statement::Comment {
+                object_type,
+                object_name,
+                comment,
+                if_exists,
+            } => self.comment_to_plan(object_type, object_name, comment, if_exists),
+
             stmt => {
                 not_impl_err!("Unsupported SQL statement: {stmt}")
             }
@@ -1250,6 +1352,156 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         }))
     }
 
+    /// Generate a logical plan from a `CREATE MATERIALIZED VIEW` statement
+    fn create_materialized_view_to_plan(
+        &self,
+        statement: Statement,
+    ) -> Result<LogicalPlan> {
+        // the statement itself is kept as the definition of the view
+        let definition = statement.to_string();
+        let Statement::CreateView {
+            or_replace,
+            name,
+            columns,
+            query,
+            options,
+            cluster_by,
+            comment,
+            with_no_schema_binding,
+            if_not_exists,
+            temporary,
+            to,
+            ..
+        } = statement
+        else {
+            return internal_err!("Expected a CREATE MATERIALIZED VIEW statement");
+        };
+
+        if temporary {
+            return not_impl_err!("Temporary materialized views not supported");
+        }
+        if !cluster_by.is_empty() {
+            return not_impl_err!("Cluster by not supported");
+        }
+        if comment.is_some() {
+            return not_impl_err!("Comment not supported");
+        }
+        if with_no_schema_binding {
+            return not_impl_err!("With no schema binding not supported");
+        }
+        if to.is_some() {
+            return not_impl_err!("To not supported");
+        }
+        if if_not_exists && or_replace {
+            return plan_err!("'IF NOT EXISTS' cannot coexist with 'REPLACE'");
+        }
+
+        let options = match options {
+            CreateTableOptions::None => vec![],
+            CreateTableOptions::With(options) => options,
+            CreateTableOptions::Options(_) => {
+                return not_impl_err!("OPTIONS not supported for materialized views");
+            }
+        };
+        let mut location = None;
+        for option in options {
+            let SqlOption::KeyValue { key, value } = option else {
+                return not_impl_err!("Unsupported materialized view option: {option}");
+            };
+            let key = ident_to_string(&key);
+            match (key.as_str(), value) {
+                ("location", SQLExpr::Value(Value::SingleQuotedString(value))) => {
+                    location = Some(value)
+                }
+                ("location", value) => {
+                    return plan_err!(
+                        "Expected a string literal for 'location', found: {value}"
+                    );
+                }
+                _ => return plan_err!("Unknown materialized view option '{key}'"),
+            }
+        }
+
+        let columns = columns
+            .into_iter()
+            .map(|view_column_def| {
+                if let Some(options) = view_column_def.options {
+                    plan_err!("Options not supported for view columns: {options:?}")
+                } else {
+                    Ok(view_column_def.name)
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let plan = self.query_to_plan(*query, &mut PlannerContext::new())?;
+        let plan = self.apply_expr_alias(plan, columns)?;
+
+        Ok(LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(
+            CreateMaterializedView {
+                name: self.object_name_to_table_reference(name)?,
+                input: Arc::new(plan),
+                if_not_exists,
+                or_replace,
+                location,
+                definition: Some(definition),
+            },
+        )))
+    }
+
+    /// Generate a logical plan from a `REFRESH MATERIALIZED VIEW` statement
+    fn refresh_materialized_view_to_plan(
+        &self,
+        statement: RefreshMaterializedViewStatement,
+    ) -> Result<LogicalPlan> {
+        let name = self.object_name_to_table_reference(statement.name)?;
+        // Do a table lookup to verify the view exists
+        let _ = self.context_provider.get_table_source(name.clone())?;
+
+        Ok(LogicalPlan::Ddl(DdlStatement::RefreshMaterializedView(
+            RefreshMaterializedView {
+                name,
+                schema: DFSchemaRef::new(DFSchema::empty()),
+            },
+        )))
+    }
+
+    /// Generate a logical plan from a `COMMENT ON TABLE` or `COMMENT ON COLUMN`
+    /// statement
+    fn comment_to_plan(
+        &self,
+        object_type: CommentObject,
+        mut object_name: ObjectName,
+        comment: Option<String>,
+        if_exists: bool,
+    ) -> Result<LogicalPlan> {
+        let column = match object_type {
+            CommentObject::Table => None,
+            // the column name is qualified with the name of its table
+            CommentObject::Column => match object_name.0.pop() {
+                Some(column) if !object_name.0.is_empty() => {
+                    Some(self.ident_normalizer.normalize(column))
+                }
+                _ => {
+                    return plan_err!(
+                    "COMMENT ON COLUMN requires a column name qualified with its table"
+                )
+                }
+            },
+            _ => return not_impl_err!("COMMENT ON {object_type} not supported"),
+        };
+        // The existence of the table and of the column is only checked when
+        // the comment is set, as `IF EXISTS` allows the table to be missing
+        let table = self.object_name_to_table_reference(object_name)?;
+
+        Ok(LogicalPlan::Ddl(DdlStatement::CommentOn(CommentOn {
+            table,
+            column,
+            comment,
+            if_exists,
+            schema: DFSchemaRef::new(DFSchema::empty()),
+        })))
+    }
+
     fn copy_to_plan(&self, statement: CopyToStatement) -> Result<LogicalPlan> {
         // Determine if source is table or query and handle accordingly
         let copy_source = statement.source;
@@ -1434,7 +1686,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
         let name = self.object_name_to_table_reference(name)?;
         let constraints =
-            Self::new_constraint_from_table_constraints(&all_constraints, &df_schema)?;
+            self.new_constraint_from_table_constraints(&all_constraints, &df_schema)?;
         Ok(LogicalPlan::Ddl(DdlStatement::CreateExternalTable(
             PlanCreateExternalTable {
                 schema: df_schema,
@@ -1456,6 +1708,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
 
     /// Convert each [TableConstraint] to corresponding [Constraint]
     fn new_constraint_from_table_constraints(
+        &self,
         constraints: &[TableConstraint],
         df_schema: &DFSchemaRef,
     ) -> Result<Constraints> {
@@ -1508,8 +1761,41 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 TableConstraint::ForeignKey { .. } => {
                     _plan_err!("Foreign key constraints are not currently supported")
                 }
-                TableConstraint::Check { .. } => {
-                    _plan_err!("Check constraints are not currently supported")
+                TableConstraint::Check { name, expr } => {
+                    // Plan the expression to validate it, but keep its SQL
+                    // text to evaluate it against the rows inserted later
+                    let check = self.sql_to_expr(
+                        *expr.clone(),
+                        df_schema,
+                        &mut PlannerContext::new(),
+                    )?;
+                    let data_type = check.get_type(df_schema)?;
+                    if data_type != DataType::Boolean {
+                        return plan_err!(
+                            "CHECK constraint expression must be a boolean, \
+                            got {data_type}: {expr}"
+                        );
+                    }
+                    if check.exists(|e| {
+                        Ok(matches!(
+                            e,
+                            Expr::AggregateFunction(_)
+                                | Expr::WindowFunction(_)
+                                | Expr::ScalarSubquery(_)
+                                | Expr::Exists(_)
+                                | Expr::InSubquery(_)
+                                | Expr::SetComparison(_)
+                        ))
+                    })? {
+                        return plan_err!(
+                            "CHECK constraint expression cannot contain aggregate \
+                            functions, window functions or subqueries: {expr}"
+                        );
+                    }
+                    Ok(Constraint::Check {
+                        name: name.as_ref().map(|name| name.value.clone()),
+                        expr: expr.to_string(),
+                    })
                 }
                 TableConstraint::Index { .. } => {
                     _plan_err!("Indexes are not currently supported")
@@ -1559,6 +1845,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         &self,
         verbose: bool,
         analyze: bool,
+        explain_format: ExplainFormat,
         statement: DFStatement,
     ) -> Result<LogicalPlan> {
         let plan = self.statement_to_plan(statement)?;
@@ -1574,6 +1861,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 verbose,
                 input: plan,
                 schema,
+                explain_format,
             }))
         } else {
             let stringified_plans =
@@ -1584,6 +1872,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
                 stringified_plans,
                 schema,
                 logical_optimization_succeeded: false,
+                explain_format,
             }))
         }
     }
@@ -1722,9 +2011,11 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         let source = match predicate_expr {
             None => scan,
             Some(predicate_expr) => {
+                // the columns are qualified by the table, so that subqueries of
+                // the predicate can refer to them
+                let schema = Arc::clone(scan.schema());
                 let filter_expr =
                     self.sql_to_expr(predicate_expr, &schema, &mut planner_context)?;
-                let schema = Arc::new(schema.clone());
                 let mut using_columns = HashSet::new();
                 expr_to_columns(&filter_expr, &mut using_columns)?;
                 let filter_expr = normalize_col_with_schemas_and_ambiguity_check(
@@ -1858,6 +2149,313 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         Ok(plan)
     }
 
+    fn merge_to_plan(
+        &self,
+        table: TableFactor,
+        source: TableFactor,
+        on: SQLExpr,
+        clauses: Vec<MergeClause>,
+    ) -> Result<LogicalPlan> {
+        let table_name = match &table {
+            TableFactor::Table { name, .. } => name.clone(),
+            _ => plan_err!("Cannot merge into non-table relation!")?,
+        };
+        if clauses.is_empty() {
+            plan_err!("MERGE requires at least one WHEN clause")?;
+        }
+
+        // Do a table lookup to verify the table exists
+        let table_name = self.object_name_to_table_reference(table_name)?;
+        let table_source = self.context_provider.get_table_source(table_name.clone())?;
+        let table_schema = DFSchema::try_from(table_source.schema().as_ref().clone())?;
+
+        // Number the rows of the target table, so that a target row matched by
+        // several source rows can be detected during execution
+        let Some(row_number) = self.context_provider.get_window_meta("row_number") else {
+            return plan_err!("MERGE requires the row_number window function");
+        };
+        let mut planner_context = PlannerContext::new();
+        let target = self.create_relation(table, &mut planner_context)?;
+        let row_number = Expr::WindowFunction(WindowFunction::new(row_number, vec![]));
+        let target_row =
+            Expr::Column(Column::from_name(row_number.schema_name().to_string()));
+        let mut target_exprs = target
+            .schema()
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+        target_exprs.push(target_row.alias(MERGE_TARGET_ROW_COLUMN));
+        let target = LogicalPlanBuilder::from(target)
+            .window(vec![row_number])?
+            .project(target_exprs)?
+            .build()?;
+
+        let source = self.create_relation(source, &mut planner_context)?;
+        let source_columns = source.schema().columns();
+        let mut source_exprs = source_columns
+            .iter()
+            .cloned()
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+        source_exprs.push(lit(true).alias(MERGE_SOURCE_EXISTS_COLUMN));
+        let source = project(source, source_exprs)?;
+
+        // Source rows that match no target row only need to be kept when they
+        // may be inserted
+        let join_type = if clauses
+            .iter()
+            .any(|clause| clause.clause_kind == MergeClauseKind::NotMatched)
+        {
+            JoinType::Full
+        } else {
+            JoinType::Left
+        };
+        let join_schema = target.schema().join(source.schema())?;
+        let on = self.sql_to_expr(on, &join_schema, &mut planner_context)?;
+        let joined = LogicalPlanBuilder::from(target.clone())
+            .join_on(source, join_type, vec![on])?
+            .build()?;
+        let joined_schema = Arc::clone(joined.schema());
+
+        let target_row = Expr::Column(Column::from(
+            target.schema().qualified_field(table_schema.fields().len()),
+        ));
+        let source_exists = Expr::Column(Column::from_name(MERGE_SOURCE_EXISTS_COLUMN));
+        let matched = target_row
+            .clone()
+            .is_not_null()
+            .and(source_exists.clone().is_not_null());
+
+        // The first clause whose condition holds applies to each row. Rows of
+        // the target table to which no clause applies are kept unchanged, and
+        // unmatched source rows are dropped.
+        //
+        // For each column, `values` holds the condition of every clause with
+        // the value the clause assigns to the column, or `None` if the clause
+        // keeps the value of the target row.
+        let mut actions = vec![];
+        let mut values = vec![vec![]; table_schema.fields().len()];
+        for clause in clauses {
+            let MergeClause {
+                clause_kind,
+                predicate,
+                action,
+            } = clause;
+            let mut condition = match clause_kind {
+                MergeClauseKind::Matched => matched.clone(),
+                MergeClauseKind::NotMatched | MergeClauseKind::NotMatchedByTarget => {
+                    target_row.clone().is_null()
+                }
+                MergeClauseKind::NotMatchedBySource => source_exists.clone().is_null(),
+            };
+            if let Some(predicate) = predicate {
+                let predicate =
+                    self.sql_to_expr(predicate, &joined_schema, &mut planner_context)?;
+                condition = condition.and(predicate.is_true());
+            }
+
+            let action = match (clause_kind, action) {
+                (
+                    MergeClauseKind::NotMatched | MergeClauseKind::NotMatchedByTarget,
+                    MergeAction::Insert(insert),
+                ) => {
+                    let insert_values = self.merge_insert_values(
+                        insert,
+                        &table_schema,
+                        &source_columns,
+                        &joined_schema,
+                        &mut planner_context,
+                    )?;
+                    for (i, value) in insert_values.into_iter().enumerate() {
+                        let value = match value {
+                            Some(value) => value,
+                            // The value is not specified. Fill in the default value for the column.
+                            None => table_source
+                                .get_column_default(table_schema.field(i).name())
+                                .cloned()
+                                .unwrap_or(Expr::Literal(ScalarValue::Null)),
+                        };
+                        values[i].push((condition.clone(), Some(value)));
+                    }
+                    WriteMergeAction::Insert
+                }
+                (
+                    MergeClauseKind::Matched | MergeClauseKind::NotMatchedBySource,
+                    MergeAction::Update { assignments },
+                ) => {
+                    let mut assigned = vec![None; table_schema.fields().len()];
+                    for assignment in assignments {
+                        let cols = match &assignment.target {
+                            AssignmentTarget::ColumnName(cols) => cols,
+                            _ => plan_err!("Tuples are not supported")?,
+                        };
+                        let col_name = cols
+                            .0
+                            .iter()
+                            .last()
+                            .ok_or_else(|| plan_datafusion_err!("Empty column id"))?;
+                        let col_name = self.ident_normalizer.normalize(col_name.clone());
+                        let index = table_schema
+                            .index_of_column_by_name(None, &col_name)
+                            .ok_or_else(|| {
+                                unqualified_field_not_found(&col_name, &table_schema)
+                            })?;
+                        let value = self.sql_to_expr(
+                            assignment.value,
+                            &joined_schema,
+                            &mut planner_context,
+                        )?;
+                        assigned[index] = Some(value);
+                    }
+                    for (i, value) in assigned.into_iter().enumerate() {
+                        values[i].push((condition.clone(), value));
+                    }
+                    WriteMergeAction::Update
+                }
+                (
+                    MergeClauseKind::Matched | MergeClauseKind::NotMatchedBySource,
+                    MergeAction::Delete,
+                ) => {
+                    for column_values in values.iter_mut() {
+                        column_values.push((condition.clone(), None));
+                    }
+                    WriteMergeAction::Delete
+                }
+                (clause_kind, action) => {
+                    return plan_err!(
+                        "Invalid MERGE clause: WHEN {clause_kind} THEN {action}"
+                    );
+                }
+            };
+            actions.push((
+                Box::new(condition),
+                Box::new(lit(ScalarValue::UInt8(Some(action as u8)))),
+            ));
+        }
+        actions.push((
+            Box::new(target_row.clone().is_not_null()),
+            Box::new(lit(ScalarValue::UInt8(Some(WriteMergeAction::Keep as u8)))),
+        ));
+
+        // Build the value of each column of the table, using the value of the
+        // target row if no clause modifies it
+        let mut exprs = table_schema
+            .fields()
+            .iter()
+            .zip(values)
+            .enumerate()
+            .map(|(i, (field, mut values))| {
+                let previous =
+                    Expr::Column(Column::from(target.schema().qualified_field(i)));
+                // Clauses after the last one modifying the column keep the previous value
+                while values.last().is_some_and(|(_, value)| value.is_none()) {
+                    values.pop();
+                }
+                let expr = if values.is_empty() {
+                    previous
+                } else {
+                    let when_then = values
+                        .into_iter()
+                        .map(|(condition, value)| {
+                            let value = value
+                                .unwrap_or_else(|| previous.clone())
+                                .cast_to(field.data_type(), &joined_schema)?;
+                            Ok((Box::new(condition), Box::new(value)))
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    Expr::Case(Case::new(None, when_then, Some(Box::new(previous))))
+                };
+                Ok(expr
+                    .cast_to(field.data_type(), &joined_schema)?
+                    .alias(field.name()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        exprs.push(
+            Expr::Case(Case::new(
+                None,
+                actions,
+                Some(Box::new(lit(ScalarValue::UInt8(None)))),
+            ))
+            .alias(MERGE_ACTION_COLUMN),
+        );
+        exprs.push(
+            Expr::Case(Case::new(
+                None,
+                vec![(Box::new(matched), Box::new(target_row))],
+                None,
+            ))
+            .alias(MERGE_TARGET_ROW_COLUMN),
+        );
+
+        let source = LogicalPlanBuilder::from(joined)
+            .project(exprs)?
+            .filter(col(MERGE_ACTION_COLUMN).is_not_null())?
+            .build()?;
+
+        let plan = LogicalPlan::Dml(DmlStatement::new(
+            table_name,
+            Arc::new(table_schema),
+            WriteOp::Merge,
+            Arc::new(source),
+        ));
+        Ok(plan)
+    }
+
+    /// Returns the value inserted into each column of the target table by a
+    /// `WHEN NOT MATCHED THEN INSERT` clause of a `MERGE` statement, or
+    /// `None` if the column is not specified.
+    fn merge_insert_values(
+        &self,
+        insert: MergeInsertExpr,
+        table_schema: &DFSchema,
+        source_columns: &[Column],
+        joined_schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Vec<Option<Expr>>> {
+        let values = match insert.kind {
+            MergeInsertKind::Values(ast::Values { mut rows, .. }) => {
+                if rows.len() != 1 {
+                    plan_err!("MERGE INSERT clause must specify exactly one row")?;
+                }
+                rows.remove(0)
+                    .into_iter()
+                    .map(|value| self.sql_to_expr(value, joined_schema, planner_context))
+                    .collect::<Result<Vec<_>>>()?
+            }
+            MergeInsertKind::Row => source_columns
+                .iter()
+                .cloned()
+                .map(Expr::Column)
+                .collect::<Vec<_>>(),
+        };
+
+        let mut value_indices = vec![None; table_schema.fields().len()];
+        if insert.columns.is_empty() {
+            if values.len() != table_schema.fields().len() {
+                plan_err!("Column count doesn't match insert query!")?;
+            }
+            value_indices = values.into_iter().map(Some).collect();
+        } else {
+            if values.len() != insert.columns.len() {
+                plan_err!("Column count doesn't match insert query!")?;
+            }
+            for (column, value) in insert.columns.into_iter().zip(values) {
+                let c = self.ident_normalizer.normalize(column);
+                let column_index = table_schema
+                    .index_of_column_by_name(None, &c)
+                    .ok_or_else(|| unqualified_field_not_found(&c, table_schema))?;
+                if value_indices[column_index].is_some() {
+                    return schema_err!(SchemaError::DuplicateUnqualifiedField {
+                        name: c,
+                    });
+                }
+                value_indices[column_index] = Some(value);
+            }
+        }
+        Ok(value_indices)
+    }
+
     fn insert_to_plan(
         &self,
         table_name: ObjectName,
@@ -2013,7 +2611,7 @@ impl<S: ContextProvider> SqlToRel<'_, S> {
         let select_list = if full || extended {
             "*"
         } else {
-            "table_catalog, table_schema, table_name, column_name, data_type, is_nullable"
+            "table_catalog, table_schema, table_name, column_name, data_type, is_nullable, column_comment"
         };
 
         let query = format!(
@@ -2126,15 +2724,45 @@ ON p.function_name = r.routine_name
 
         // Do a table lookup to verify the table exists
         let table_ref = self.object_name_to_table_reference(sql_table_name)?;
-        let _ = self.context_provider.get_table_source(table_ref)?;
+        let source = self.context_provider.get_table_source(table_ref.clone())?;
+        let definition = self.table_definition(source.as_ref(), &table_ref);
 
         let query = format!(
-            "SELECT table_catalog, table_schema, table_name, definition FROM information_schema.views WHERE {where_clause}"
+            "SELECT table_catalog, table_schema, table_name FROM information_schema.tables WHERE {where_clause}"
         );
 
         let mut rewrite = DFParser::parse_sql(&query)?;
         assert_eq!(rewrite.len(), 1);
-        self.statement_to_plan(rewrite.pop_front().unwrap()) // length of rewrite is 1
+        let plan = self.statement_to_plan(rewrite.pop_front().unwrap())?; // length of rewrite is 1
+        LogicalPlanBuilder::from(plan)
+            .project(vec![
+                col("table_catalog"),
+                col("table_schema"),
+                col("table_name"),
+                lit(ScalarValue::Utf8(definition)).alias("definition"),
+            ])?
+            .build()
+    }
+
+    /// Returns the SQL creating `table`: the definition it was created with,
+    /// or DDL generated from its metadata otherwise.
+    ///
+    /// Tables that cannot be described in SQL, for example because a column
+    /// has a type without SQL syntax, have no definition.
+    fn table_definition(
+        &self,
+        table: &dyn TableSource,
+        table_ref: &TableReference,
+    ) -> Option<String> {
+        if let Some(definition) = table.get_table_definition() {
+            return Some(definition.to_string());
+        }
+        let schema = table_ref
+            .schema()
+            .unwrap_or(&self.context_provider.options().catalog.default_schema);
+        let name = TableReference::partial(schema, table_ref.table());
+        let ddl = table.get_table_ddl(&name).ok()??;
+        ddl_to_sql(&ddl).ok().map(|statement| statement.to_string())
     }
 
     /// Return true if there is a table provider available for "schema.table"
@@ -0,0 +1,352 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use super::Unparser;
+use crate::parser::{self, DFParser, Statement};
+use arrow_schema::{DataType, Field, Fields};
+use datafusion_common::{
+    internal_err, not_impl_err, Constraint, Constraints, Result, TableReference,
+};
+use datafusion_expr::{
+    CreateExternalTable, CreateMemoryTable, CreateView, DdlStatement, EmptyRelation,
+    Expr, LogicalPlan, COMMENT_METADATA_KEY,
+};
+use sqlparser::ast::helpers::stmt_create_table::CreateTableBuilder;
+use sqlparser::ast::{self, Ident};
+use sqlparser::dialect::GenericDialect;
+
+/// Convert a DataFusion [`DdlStatement`] to a DataFusion SQL [`Statement`]
+///
+/// Unlike [`plan_to_sql`], this returns a DataFusion [`Statement`] rather
+/// than a [`ast::Statement`], as some DDL such as `CREATE EXTERNAL TABLE` is
+/// specific to DataFusion.
+///
+/// # Example
+/// ```
+/// use arrow::datatypes::{DataType, Field, Schema};
+/// use datafusion_common::{Constraints, DFSchema};
+/// use datafusion_expr::{CreateMemoryTable, DdlStatement, EmptyRelation, LogicalPlan};
+/// use datafusion_sql::unparser::ddl_to_sql;
+/// use std::sync::Arc;
+/// let schema = Schema::new(vec![
+///     Field::new("c1", DataType::Int32, false),
+///     Field::new("c2", DataType::Utf8, true),
+/// ]);
+/// let ddl = DdlStatement::CreateMemoryTable(CreateMemoryTable {
+///     name: "t".into(),
+///     constraints: Constraints::empty(),
+///     input: Arc::new(LogicalPlan::EmptyRelation(EmptyRelation {
+///         produce_one_row: false,
+///         schema: Arc::new(DFSchema::try_from(schema).unwrap()),
+///     })),
+///     if_not_exists: false,
+///     or_replace: false,
+///     column_defaults: vec![],
+///     temporary: false,
+///     comment: None,
+/// });
+/// let sql = ddl_to_sql(&ddl).unwrap();
+/// assert_eq!(sql.to_string(), "CREATE TABLE t (c1 INTEGER NOT NULL, c2 VARCHAR)");
+/// ```
+///
+/// [`plan_to_sql`]: crate::unparser::plan_to_sql
+pub fn ddl_to_sql(ddl: &DdlStatement) -> Result<Statement> {
+    let unparser = Unparser::default();
+    unparser.ddl_to_sql(ddl)
+}
+
+impl Unparser<'_> {
+    pub fn ddl_to_sql(&self, ddl: &DdlStatement) -> Result<Statement> {
+        match ddl {
+            DdlStatement::CreateExternalTable(create) => {
+                Ok(Statement::CreateExternalTable(
+                    self.create_external_table_to_sql(create)?,
+                ))
+            }
+            DdlStatement::CreateMemoryTable(create) => Ok(Statement::Statement(
+                Box::new(self.create_memory_table_to_sql(create)?),
+            )),
+            DdlStatement::CreateView(create) => Ok(Statement::Statement(Box::new(
+                self.create_view_to_sql(create)?,
+            ))),
+            _ => not_impl_err!("Unsupported DDL: {}", ddl.name()),
+        }
+    }
+
+    fn create_external_table_to_sql(
+        &self,
+        create: &CreateExternalTable,
+    ) -> Result<parser::CreateExternalTable> {
+        let CreateExternalTable {
+            schema,
+            name,
+            location,
+            file_type,
+            table_partition_cols,
+            if_not_exists,
+            temporary,
+            definition: _,
+            order_exprs,
+            unbounded,
+            options,
+            constraints,
+            column_defaults,
+        } = create;
+
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| self.column_def_to_sql(field, column_defaults.get(field.name())))
+            .collect::<Result<Vec<_>>>()?;
+        let table_partition_cols = table_partition_cols
+            .iter()
+            .map(|col| self.new_ident_quoted_if_needs(col.clone()).to_string())
+            .collect();
+        let order_exprs = order_exprs
+            .iter()
+            .map(|ordering| {
+                ordering
+                    .iter()
+                    .map(|sort| self.sort_to_sql(sort))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // sort the options so the output does not depend on the hash map order
+        let mut options = options
+            .iter()
+            .map(|(key, value)| {
+                (key.clone(), ast::Value::SingleQuotedString(value.clone()))
+            })
+            .collect::<Vec<_>>();
+        options.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(parser::CreateExternalTable {
+            name: self.table_reference_to_object_name(name),
+            columns,
+            file_type: file_type.clone(),
+            location: location.clone(),
+            table_partition_cols,
+            order_exprs,
+            if_not_exists: *if_not_exists,
+            temporary: *temporary,
+            unbounded: *unbounded,
+            options,
+            constraints: self.constraints_to_sql(constraints, schema.fields())?,
+        })
+    }
+
+    fn create_memory_table_to_sql(
+        &self,
+        create: &CreateMemoryTable,
+    ) -> Result<ast::Statement> {
+        let CreateMemoryTable {
+            name,
+            constraints,
+            input,
+            if_not_exists,
+            or_replace,
+            column_defaults,
+            temporary,
+            comment,
+        } = create;
+
+        // the comment is displayed verbatim between single quotes
+        let comment = comment
+            .as_ref()
+            .map(|comment| ast::CommentDef::WithoutEq(comment.replace('\'', "''")));
+        let builder = CreateTableBuilder::new(self.table_reference_to_object_name(name))
+            .if_not_exists(*if_not_exists)
+            .or_replace(*or_replace)
+            .temporary(*temporary)
+            .comment(comment);
+
+        let builder = match input.as_ref() {
+            // a table without data is described by its columns
+            LogicalPlan::EmptyRelation(EmptyRelation {
+                produce_one_row: false,
+                schema,
+            }) => {
+                let columns = schema
+                    .fields()
+                    .iter()
+                    .map(|field| {
+                        let default = column_defaults
+                            .iter()
+                            .find(|(name, _)| name == field.name())
+                            .map(|(_, default)| default);
+                        self.column_def_to_sql(field, default)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                builder
+                    .columns(columns)
+                    .constraints(self.constraints_to_sql(constraints, schema.fields())?)
+            }
+            input => builder.query(Some(self.plan_to_query(input)?)),
+        };
+
+        Ok(builder.build())
+    }
+
+    fn create_view_to_sql(&self, create: &CreateView) -> Result<ast::Statement> {
+        let CreateView {
+            name,
+            input,
+            or_replace,
+            definition: _,
+            temporary,
+        } = create;
+
+        Ok(ast::Statement::CreateView {
+            or_replace: *or_replace,
+            materialized: false,
+            name: self.table_reference_to_object_name(name),
+            columns: vec![],
+            query: self.plan_to_query(input)?,
+            options: ast::CreateTableOptions::None,
+            cluster_by: vec![],
+            comment: None,
+            with_no_schema_binding: false,
+            if_not_exists: false,
+            temporary: *temporary,
+            to: None,
+            params: None,
+        })
+    }
+
+    fn plan_to_query(&self, plan: &LogicalPlan) -> Result<Box<ast::Query>> {
+        match self.plan_to_sql(plan)? {
+            ast::Statement::Query(query) => Ok(query),
+            statement => internal_err!("Expected a query, got: {statement}"),
+        }
+    }
+
+    fn table_reference_to_object_name(&self, name: &TableReference) -> ast::ObjectName {
+        let parts = match name {
+            TableReference::Bare { table } => vec![table],
+            TableReference::Partial { schema, table } => vec![schema, table],
+            TableReference::Full {
+                catalog,
+                schema,
+                table,
+            } => vec![catalog, schema, table],
+        };
+        ast::ObjectName(
+            parts
+                .into_iter()
+                .map(|part| self.new_ident_quoted_if_needs(part.to_string()))
+                .collect(),
+        )
+    }
+
+    fn column_def_to_sql(
+        &self,
+        field: &Field,
+        default: Option<&Expr>,
+    ) -> Result<ast::ColumnDef> {
+        let mut options = vec![];
+        if let Some(default) = default {
+            options.push(ast::ColumnOptionDef {
+                name: None,
+                option: ast::ColumnOption::Default(self.expr_to_sql(default)?),
+            });
+        }
+        if !field.is_nullable() {
+            options.push(ast::ColumnOptionDef {
+                name: None,
+                option: ast::ColumnOption::NotNull,
+            });
+        }
+        if let Some(comment) = field.metadata().get(COMMENT_METADATA_KEY) {
+            options.push(ast::ColumnOptionDef {
+                name: None,
+                option: ast::ColumnOption::Comment(comment.clone()),
+            });
+        }
+
+        Ok(ast::ColumnDef {
+            name: self.new_ident_quoted_if_needs(field.name().to_string()),
+            data_type: self.column_dtype_to_ast_dtype(field.data_type())?,
+            collation: None,
+            options,
+        })
+    }
+
+    /// SQL has no syntax for dictionary encoding, so dictionary encoded
+    /// columns are declared with their value type
+    fn column_dtype_to_ast_dtype(&self, data_type: &DataType) -> Result<ast::DataType> {
+        match data_type {
+            DataType::Dictionary(_, value_type) => {
+                self.column_dtype_to_ast_dtype(value_type)
+            }
+            data_type => self.arrow_dtype_to_ast_dtype(data_type),
+        }
+    }
+
+    fn constraints_to_sql(
+        &self,
+        constraints: &Constraints,
+        fields: &Fields,
+    ) -> Result<Vec<ast::TableConstraint>> {
+        let columns = |indices: &[usize]| -> Result<Vec<Ident>> {
+            indices
+                .iter()
+                .map(|idx| match fields.get(*idx) {
+                    Some(field) => {
+                        Ok(self.new_ident_quoted_if_needs(field.name().clone()))
+                    }
+                    None => internal_err!("Constraint refers to unknown column {idx}"),
+                })
+                .collect()
+        };
+
+        constraints
+            .iter()
+            .map(|constraint| match constraint {
+                Constraint::PrimaryKey(indices) => Ok(ast::TableConstraint::PrimaryKey {
+                    name: None,
+                    index_name: None,
+                    index_type: None,
+                    columns: columns(indices)?,
+                    index_options: vec![],
+                    characteristics: None,
+                }),
+                Constraint::Unique(indices) => Ok(ast::TableConstraint::Unique {
+                    name: None,
+                    index_name: None,
+                    index_type_display: ast::KeyOrIndexDisplay::None,
+                    index_type: None,
+                    columns: columns(indices)?,
+                    index_options: vec![],
+                    characteristics: None,
+                    nulls_distinct: ast::NullsDistinctOption::None,
+                }),
+                Constraint::Check { name, expr } => {
+                    // the expression is kept as SQL text, see `Constraint::Check`
+                    let expr = DFParser::parse_sql_into_expr_with_dialect(
+                        expr,
+                        &GenericDialect {},
+                    )?
+                    .expr;
+                    Ok(ast::TableConstraint::Check {
+                        name: name.clone().map(Ident::new),
+                        expr: Box::new(expr),
+                    })
+                }
+            })
+            .collect()
+    }
+}
@@ -40,7 +40,11 @@ use datafusion_common::{
     ScalarValue,
 };
 use datafusion_expr::{
-    expr::{Alias, Exists, InList, ScalarFunction, Sort, WindowFunction},
+    expr::{
+        Alias, Exists, InList, ScalarFunction, SetComparison, SetQuantifier, Sort,
+        WindowFunction,
+    },
+    utils::row_values,
     Between, BinaryExpr, Case, Cast, Expr, GroupingSet, Like, Operator, TryCast,
 };
 use sqlparser::ast::helpers::attached_token::AttachedToken;
@@ -284,7 +288,19 @@ impl Unparser<'_> {
             Expr::AggregateFunction(agg) => {
                 let func_name = agg.func.name();
 
-                let args = self.function_args_to_sql(&agg.args)?;
+                // Ordered-set aggregates take their WITHIN GROUP sort
+                // expressions as leading arguments
+                let (within_group, args) = match &agg.order_by {
+                    Some(order_by) if agg.func.supports_within_group_clause() => {
+                        let within_group = order_by
+                            .iter()
+                            .map(|sort| self.sort_to_sql(sort))
+                            .collect::<Result<Vec<_>>>()?;
+                        let direct_args = agg.args.get(order_by.len()..).unwrap_or(&[]);
+                        (within_group, self.function_args_to_sql(direct_args)?)
+                    }
+                    _ => (vec![], self.function_args_to_sql(&agg.args)?),
+                };
                 let filter = match &agg.filter {
                     Some(filter) => Some(Box::new(self.expr_to_sql_inner(filter)?)),
                     None => None,
@@ -305,7 +321,7 @@ impl Unparser<'_> {
                     filter,
                     null_treatment: None,
                     over: None,
-                    within_group: vec![],
+                    within_group,
                     parameters: ast::FunctionArguments::None,
                     uses_odbc_syntax: false,
                 }))
@@ -323,7 +339,17 @@ impl Unparser<'_> {
                 Ok(ast::Expr::Subquery(sub_query))
             }
             Expr::InSubquery(insubq) => {
-                let inexpr = Box::new(self.expr_to_sql_inner(insubq.expr.as_ref())?);
+                let len = insubq.subquery.subquery.schema().fields().len();
+                let inexpr = match row_values(insubq.expr.as_ref(), len) {
+                    // `(a, b) IN (SELECT x, y ...)`
+                    Some(values) => Box::new(ast::Expr::Tuple(
+                        values
+                            .iter()
+                            .map(|value| self.expr_to_sql_inner(value))
+                            .collect::<Result<Vec<_>>>()?,
+                    )),
+                    None => Box::new(self.expr_to_sql_inner(insubq.expr.as_ref())?),
+                };
                 let sub_statement =
                     self.plan_to_sql(insubq.subquery.subquery.as_ref())?;
                 let sub_query = if let ast::Statement::Query(inner_query) = sub_statement
@@ -340,6 +366,38 @@ impl Unparser<'_> {
                     negated: insubq.negated,
                 })
             }
+            Expr::SetComparison(SetComparison {
+                expr,
+                subquery,
+                op,
+                quantifier,
+            }) => {
+                let left = Box::new(self.expr_to_sql_inner(expr.as_ref())?);
+                let sub_statement = self.plan_to_sql(subquery.subquery.as_ref())?;
+                let sub_query = if let ast::Statement::Query(inner_query) = sub_statement
+                {
+                    inner_query
+                } else {
+                    return plan_err!(
+                        "Subquery must be a Query, but found {sub_statement:?}"
+                    );
+                };
+                let compare_op = self.op_to_sql(op)?;
+                let right = Box::new(ast::Expr::Subquery(sub_query));
+                Ok(match quantifier {
+                    SetQuantifier::Any => ast::Expr::AnyOp {
+                        left,
+                        compare_op,
+                        right,
+                        is_some: false,
+                    },
+                    SetQuantifier::All => ast::Expr::AllOp {
+                        left,
+                        compare_op,
+                        right,
+                    },
+                })
+            }
             Expr::Exists(Exists { subquery, negated }) => {
                 let sub_statement = self.plan_to_sql(subquery.subquery.as_ref())?;
                 let sub_query = if let ast::Statement::Query(inner_query) = sub_statement
@@ -1543,7 +1601,10 @@ impl Unparser<'_> {
         }))
     }
 
-    fn arrow_dtype_to_ast_dtype(&self, data_type: &DataType) -> Result<ast::DataType> {
+    pub(super) fn arrow_dtype_to_ast_dtype(
+        &self,
+        data_type: &DataType,
+    ) -> Result<ast::DataType> {
         match data_type {
             DataType::Null => {
                 not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
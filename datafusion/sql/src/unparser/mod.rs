@@ -18,6 +18,7 @@
 //! [`Unparser`] for converting `Expr` to SQL text
 
 pub mod ast;
+mod ddl;
 mod expr;
 mod plan;
 mod rewrite;
@@ -25,6 +26,7 @@ mod utils;
 
 use self::dialect::{DefaultDialect, Dialect};
 use crate::unparser::extension_unparser::UserDefinedLogicalNodeUnparser;
+pub use ddl::ddl_to_sql;
 pub use expr::expr_to_sql;
 pub use plan::plan_to_sql;
 use std::sync::Arc;
@@ -32,6 +32,7 @@ use super::{
     },
     Unparser,
 };
+use crate::parser::Statement as DFStatement;
 use crate::unparser::ast::UnnestRelationBuilder;
 use crate::unparser::extension_unparser::{
     UnparseToStatementResult, UnparseWithinStatementResult,
@@ -118,12 +119,18 @@ impl Unparser<'_> {
             LogicalPlan::Extension(extension) => {
                 self.extension_to_statement(extension.node.as_ref())
             }
+            LogicalPlan::Ddl(ddl) => match self.ddl_to_sql(&ddl)? {
+                DFStatement::Statement(statement) => Ok(*statement),
+                // e.g. `CREATE EXTERNAL TABLE`, use `ddl_to_sql` instead
+                statement => not_impl_err!("Unsupported DDL statement: {statement}"),
+            },
             LogicalPlan::Explain(_)
             | LogicalPlan::Analyze(_)
-            | LogicalPlan::Ddl(_)
             | LogicalPlan::Copy(_)
             | LogicalPlan::DescribeTable(_)
             | LogicalPlan::RecursiveQuery(_)
+            | LogicalPlan::GapFill(_)
+            | LogicalPlan::Sample(_)
             | LogicalPlan::Unnest(_) => not_impl_err!("Unsupported plan: {plan:?}"),
         }
     }
@@ -565,6 +572,12 @@ impl Unparser<'_> {
                 self.select_to_sql_recursively(input, query, select, relation)
             }
             LogicalPlan::Join(join) => {
+                // a null-aware anti join has the semantics of `NOT IN`, which
+                // `LEFT ANTI JOIN` does not have
+                if join.null_aware {
+                    return not_impl_err!("Unsupported null-aware join: {plan:?}");
+                }
+
                 let mut table_scan_filters = vec![];
 
                 let left_plan =
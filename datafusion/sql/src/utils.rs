@@ -94,6 +94,7 @@ pub(crate) fn rebase_expr(
 pub(crate) enum CheckColumnsSatisfyExprsPurpose {
     ProjectionMustReferenceAggregate,
     HavingMustReferenceAggregate,
+    QualifyMustReferenceAggregate,
 }
 
 impl CheckColumnsSatisfyExprsPurpose {
@@ -105,6 +106,9 @@ impl CheckColumnsSatisfyExprsPurpose {
             CheckColumnsSatisfyExprsPurpose::HavingMustReferenceAggregate => {
                 "HAVING clause references non-aggregate values"
             }
+            CheckColumnsSatisfyExprsPurpose::QualifyMustReferenceAggregate => {
+                "QUALIFY clause references non-aggregate values"
+            }
         }
     }
 
@@ -422,7 +426,8 @@ impl RecursiveUnnestRewriter<'_> {
             }
             DataType::List(_)
             | DataType::FixedSizeList(_, _)
-            | DataType::LargeList(_) => {
+            | DataType::LargeList(_)
+            | DataType::Map(_, _) => {
                 push_projection_dedupl(
                     self.inner_projection_exprs,
                     expr_in_unnest.clone().alias(placeholder_name.clone()),
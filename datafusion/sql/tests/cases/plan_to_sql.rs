@@ -26,15 +26,17 @@ use datafusion_expr::{
 };
 use datafusion_functions::unicode;
 use datafusion_functions_aggregate::grouping::grouping_udaf;
+use datafusion_functions_aggregate::percentile::percentile_cont_udaf;
 use datafusion_functions_nested::make_array::make_array_udf;
 use datafusion_functions_nested::map::map_udf;
 use datafusion_functions_window::rank::rank_udwf;
+use datafusion_sql::parser::DFParser;
 use datafusion_sql::planner::{ContextProvider, PlannerContext, SqlToRel};
 use datafusion_sql::unparser::dialect::{
     CustomDialectBuilder, DefaultDialect as UnparserDefaultDialect, DefaultDialect,
     Dialect as UnparserDialect, MySqlDialect as UnparserMySqlDialect, SqliteDialect,
 };
-use datafusion_sql::unparser::{expr_to_sql, plan_to_sql, Unparser};
+use datafusion_sql::unparser::{ddl_to_sql, expr_to_sql, plan_to_sql, Unparser};
 use sqlparser::ast::Statement;
 use std::hash::Hash;
 use std::sync::Arc;
@@ -76,12 +78,19 @@ fn roundtrip_expr() {
             "sum((age * 2))",
             r#"sum((age * 2))"#,
         ),
+        (
+            TableReference::bare("person"),
+            "percentile_cont(0.5) WITHIN GROUP (ORDER BY age DESC)",
+            r#"percentile_cont(0.5) WITHIN GROUP (ORDER BY age DESC NULLS FIRST)"#,
+        ),
     ];
 
     let roundtrip = |table, sql: &str| -> Result<String> {
         let dialect = GenericDialect {};
         let sql_expr = Parser::new(&dialect).try_with_sql(sql)?.parse_expr()?;
-        let state = MockSessionState::default().with_aggregate_function(sum_udaf());
+        let state = MockSessionState::default()
+            .with_aggregate_function(sum_udaf())
+            .with_aggregate_function(percentile_cont_udaf());
         let context = MockContextProvider { state };
         let schema = context.get_table_source(table)?.schema();
         let df_schema = DFSchema::try_from(schema.as_ref().clone())?;
@@ -203,7 +212,9 @@ fn roundtrip_statement() -> Result<()> {
             "SELECT left[1] FROM array",
             "SELECT {a:1, b:2}",
             "SELECT s.a FROM (SELECT {a:1, b:2} AS s)",
-            "SELECT MAP {'a': 1, 'b': 2}"
+            "SELECT MAP {'a': 1, 'b': 2}",
+            "SELECT id FROM person WHERE (id, first_name) IN (SELECT j1_id, j1_string FROM j1)",
+            "SELECT id FROM person WHERE (id, age) < (1, 2)"
     ];
 
     // For each test sql string, we transform as follows:
@@ -791,6 +802,52 @@ fn test_table_references_in_plan_to_sql() {
     );
 }
 
+#[test]
+fn test_ddl_to_sql() -> Result<()> {
+    let tests = [
+        (
+            "CREATE TABLE t (c1 INT NOT NULL, c2 VARCHAR DEFAULT 'x', PRIMARY KEY (c1), CHECK (c1 > 0))",
+            "CREATE TABLE t (c1 INTEGER NOT NULL, c2 VARCHAR DEFAULT 'x', PRIMARY KEY (c1), CHECK (c1 > 0))",
+        ),
+        (
+            "CREATE TABLE t (c1 INT NOT NULL COMMENT 'first', c2 VARCHAR) COMMENT 'it''s t'",
+            "CREATE TABLE t (c1 INTEGER NOT NULL COMMENT 'first', c2 VARCHAR) COMMENT 'it''s t'",
+        ),
+        (
+            "CREATE TABLE t AS SELECT id FROM person",
+            "CREATE TABLE t AS SELECT person.id FROM person",
+        ),
+        (
+            "CREATE VIEW v AS SELECT id FROM person WHERE age > 35",
+            "CREATE VIEW v AS SELECT person.id FROM person WHERE (person.age > 35)",
+        ),
+        (
+            "CREATE EXTERNAL TABLE t (c1 INT, c2 VARCHAR) STORED AS CSV PARTITIONED BY (c2) \
+            WITH ORDER (c1) LOCATION 'foo/' OPTIONS ('format.has_header' 'true')",
+            "CREATE EXTERNAL TABLE t (c1 INTEGER, c2 VARCHAR) STORED AS CSV PARTITIONED BY (c2) \
+            WITH ORDER (c1 ASC NULLS LAST) LOCATION 'foo/' OPTIONS ('format.has_header' 'true')",
+        ),
+        (
+            "CREATE EXTERNAL TABLE t (c1 INT COMMENT 'it''s first') STORED AS CSV LOCATION 'foo.csv'",
+            "CREATE EXTERNAL TABLE t (c1 INTEGER COMMENT 'it''s first') STORED AS CSV LOCATION 'foo.csv'",
+        ),
+    ];
+
+    for (sql, expected) in tests {
+        let statement = DFParser::parse_sql(sql)?.pop_front().unwrap();
+        let context = MockContextProvider {
+            state: MockSessionState::default(),
+        };
+        let plan = SqlToRel::new(&context).statement_to_plan(statement)?;
+        let LogicalPlan::Ddl(ddl) = &plan else {
+            panic!("Expected a DDL plan, got: {plan:?}");
+        };
+        assert_eq!(ddl_to_sql(ddl)?.to_string(), expected);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_table_scan_with_none_projection_in_plan_to_sql() {
     fn test(table_name: &str, expected_sql: &str) {
@@ -1543,6 +1600,34 @@ impl UserDefinedLogicalNodeUnparser for UnusedUnparser {
     }
 }
 
+#[test]
+fn test_unparse_null_aware_join() -> Result<()> {
+    let schema = Schema::new(vec![Field::new("id", DataType::Int32, true)]);
+    let right = table_scan(Some("t2"), &schema, None)?.build()?;
+    let plan = table_scan(Some("t1"), &schema, None)?
+        .join(
+            right,
+            datafusion_expr::JoinType::LeftAnti,
+            (vec!["t1.id"], vec!["t2.id"]),
+            None,
+        )?
+        .build()?;
+    let LogicalPlan::Join(join) = plan else {
+        panic!("Expected a join");
+    };
+    let plan = LogicalPlan::Join(datafusion_expr::Join {
+        null_aware: true,
+        ..join
+    });
+
+    let err = plan_to_sql(&plan).unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "This feature is not implemented: Unsupported null-aware join"
+    );
+    Ok(())
+}
+
 #[test]
 fn test_unparse_extension_to_statement() -> Result<()> {
     let dialect = GenericDialect {};
@@ -46,7 +46,9 @@ use datafusion_functions_aggregate::{
     approx_median::approx_median_udaf, count::count_udaf, min_max::max_udaf,
     min_max::min_udaf,
 };
-use datafusion_functions_aggregate::{average::avg_udaf, grouping::grouping_udaf};
+use datafusion_functions_aggregate::{
+    average::avg_udaf, grouping::grouping_udaf, percentile::percentile_cont_udaf,
+};
 use datafusion_functions_nested::make_array::make_array_udf;
 use datafusion_functions_window::rank::rank_udwf;
 use rstest::rstest;
@@ -549,7 +551,7 @@ fn plan_delete() {
     let sql = "delete from person where id=1";
     let plan = r#"
 Dml: op=[Delete] table=[person]
-  Filter: id = Int64(1)
+  Filter: person.id = Int64(1)
     TableScan: person
     "#
     .trim();
@@ -1642,6 +1644,32 @@ fn select_approx_median() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn select_percentile_cont_within_group() {
+    let sql = "SELECT percentile_cont(0.25) WITHIN GROUP (ORDER BY age DESC) FROM person";
+    let expected = "Projection: percentile_cont(person.age,Float64(0.25)) ORDER BY [person.age DESC NULLS FIRST]\
+                        \n  Aggregate: groupBy=[[]], aggr=[[percentile_cont(person.age, Float64(0.25)) ORDER BY [person.age DESC NULLS FIRST]]]\
+                        \n    TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_within_group_unsupported_function() {
+    let sql = "SELECT sum(1) WITHIN GROUP (ORDER BY age) FROM person";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: WITHIN GROUP is only supported for ordered-set aggregate functions, got sum",
+        err.strip_backtrace()
+    );
+
+    let sql = "SELECT percentile_cont(0.5 ORDER BY age) WITHIN GROUP (ORDER BY age) FROM person";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: Calling percentile_cont: ORDER BY in function arguments cannot be combined with WITHIN GROUP",
+        err.strip_backtrace()
+    );
+}
+
 #[test]
 fn select_scalar_func() {
     let sql = "SELECT sqrt(age) FROM person";
@@ -1858,6 +1886,38 @@ fn create_external_table_wih_schema() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn plan_comment_on() {
+    let sql = "COMMENT ON TABLE person IS 'People'";
+    let expected = "CommentOn: Bare { table: \"person\" } comment=Some(\"People\")";
+    quick_test(sql, expected);
+
+    let sql = "COMMENT IF EXISTS ON COLUMN public.person.AGE IS NULL";
+    let expected = "CommentOn: Partial { schema: \"public\", table: \"person\" } column=age comment=None";
+    quick_test(sql, expected);
+
+    let sql = "COMMENT ON COLUMN age IS 'Age'";
+    assert_eq!(
+        logical_plan(sql).unwrap_err().strip_backtrace(),
+        "Error during planning: COMMENT ON COLUMN requires a column name qualified with its table"
+    );
+
+    let sql = "COMMENT ON SCHEMA public IS 'Public'";
+    assert_eq!(
+        logical_plan(sql).unwrap_err().strip_backtrace(),
+        "This feature is not implemented: COMMENT ON SCHEMA not supported"
+    );
+}
+
+#[test]
+fn create_table_as_select_with_column_comment() {
+    let sql = "CREATE TABLE t (c1 INT COMMENT 'first') AS SELECT 1";
+    assert_eq!(
+        logical_plan(sql).unwrap_err().strip_backtrace(),
+        "This feature is not implemented: Column comments are not supported in CREATE TABLE AS SELECT"
+    );
+}
+
 #[test]
 fn create_schema_with_quoted_name() {
     let sql = "CREATE SCHEMA \"quoted_schema_name\"";
@@ -2112,6 +2172,22 @@ fn union_all() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn union_by_name() {
+    let sql =
+        "SELECT order_id, qty from orders UNION BY NAME SELECT price, order_id FROM orders";
+    let expected = "\
+        Distinct:\
+        \n  Union\
+        \n    Projection: orders.order_id, orders.qty, Float64(NULL) AS price\
+        \n      Projection: orders.order_id, orders.qty\
+        \n        TableScan: orders\
+        \n    Projection: orders.order_id, Int32(NULL) AS qty, orders.price\
+        \n      Projection: orders.price, orders.order_id\
+        \n        TableScan: orders";
+    quick_test(sql, expected);
+}
+
 #[test]
 fn empty_over() {
     let sql = "SELECT order_id, MAX(order_id) OVER () from orders";
@@ -2586,6 +2662,7 @@ fn logical_plan_with_dialect_and_options(
         .with_aggregate_function(min_udaf())
         .with_aggregate_function(max_udaf())
         .with_aggregate_function(grouping_udaf())
+        .with_aggregate_function(percentile_cont_udaf())
         .with_window_function(rank_udwf())
         .with_expr_planner(Arc::new(CoreFunctionPlanner::default()));
 
@@ -3199,6 +3276,34 @@ fn lateral_unnest() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn lateral_unnest_with_ordinality() {
+    let sql =
+        "SELECT * from unnest_table u, unnest(u.array_col) WITH ORDINALITY AS t(v, n)";
+    let expected = "Projection: *\
+            \n  Cross Join: \
+            \n    SubqueryAlias: u\
+            \n      TableScan: unnest_table\
+            \n    SubqueryAlias: t\
+            \n      Subquery:\
+            \n        Projection: UNNEST(outer_ref(u.array_col)) AS v, ordinality AS n\
+            \n          Projection: __unnest_placeholder(outer_ref(u.array_col),depth=1) AS UNNEST(outer_ref(u.array_col)), ordinality\
+            \n            Unnest: lists[__unnest_placeholder(outer_ref(u.array_col))|depth=1] structs[] ordinality=ordinality\
+            \n              Projection: outer_ref(u.array_col) AS __unnest_placeholder(outer_ref(u.array_col))\
+            \n                EmptyRelation";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn order_by_with_fill() {
+    let sql = "SELECT id, age FROM person ORDER BY id WITH FILL FROM 0 TO 10 STEP 2 INTERPOLATE (age AS LINEAR)";
+    let expected = "GapFill: fill=person.id ASC NULLS LAST, from=UInt32(0), to=UInt32(10), step=UInt32(2), interpolate=[age:linear]\
+            \n  Sort: person.id ASC NULLS LAST\
+            \n    Projection: person.id, person.age\
+            \n      TableScan: person";
+    quick_test(sql, expected);
+}
+
 #[test]
 fn hive_aggregate_with_filter() -> Result<()> {
     let dialect = &HiveDialect {};
@@ -3518,10 +3623,6 @@ fn test_select_distinct_order_by() {
     "SELECT id, number FROM person LATERAL VIEW explode(numbers) exploded_table AS number",
     "This feature is not implemented: LATERAL VIEWS"
 )]
-#[case::select_qualify_unsupported(
-    "SELECT i, p, o FROM person QUALIFY ROW_NUMBER() OVER (PARTITION BY p ORDER BY o) = 1",
-    "This feature is not implemented: QUALIFY"
-)]
 #[case::select_top_unsupported(
     "SELECT TOP (5) * FROM person",
     "This feature is not implemented: TOP"
@@ -3536,6 +3637,79 @@ fn test_select_unsupported_syntax_errors(#[case] sql: &str, #[case] error: &str)
     assert_eq!(err.strip_backtrace(), error)
 }
 
+#[test]
+fn select_qualify() {
+    let sql = "SELECT order_id, customer_id FROM orders \
+               QUALIFY rank() OVER (PARTITION BY customer_id ORDER BY qty DESC) = 1";
+    let expected = "Projection: orders.order_id, orders.customer_id\
+    \n  Filter: rank() PARTITION BY [orders.customer_id] ORDER BY [orders.qty DESC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW = Int64(1)\
+    \n    WindowAggr: windowExpr=[[rank() PARTITION BY [orders.customer_id] ORDER BY [orders.qty DESC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]]\
+    \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_qualify_without_window_function() {
+    let sql = "SELECT id FROM person QUALIFY id > 1";
+    let err = logical_plan(sql).unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "Error during planning: QUALIFY clause requires a window function in the SELECT list or QUALIFY clause"
+    );
+}
+
+#[test]
+fn select_pivot() {
+    let sql = "SELECT * FROM orders \
+               PIVOT(sum(qty) FOR order_id IN (1, 2 AS two) DEFAULT ON NULL (0))";
+    let expected = "Projection: *\
+    \n  Projection: orders.customer_id, orders.o_item_id, orders.price, orders.delivered, CASE WHEN 1 IS NULL THEN Int64(0) ELSE 1 END AS 1, CASE WHEN two IS NULL THEN Int64(0) ELSE two END AS two\
+    \n    Projection: orders.customer_id, orders.o_item_id, orders.price, orders.delivered, 1, two\
+    \n      Aggregate: groupBy=[[orders.customer_id, orders.o_item_id, orders.price, orders.delivered]], aggr=[[sum(orders.qty) FILTER (WHERE orders.order_id = Int64(1)) AS 1, sum(orders.qty) FILTER (WHERE orders.order_id = Int64(2)) AS two]]\
+    \n        TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_unpivot() {
+    let sql = "SELECT * FROM person UNPIVOT(val FOR col IN (age, salary))";
+    let expected = "Projection: *\
+    \n  Union\
+    \n    Projection: person.id, person.first_name, person.last_name, person.state, person.birth_date, person.😀, Utf8(\"age\") AS col, person.age AS val\
+    \n      Filter: person.age IS NOT NULL\
+    \n        TableScan: person\
+    \n    Projection: person.id, person.first_name, person.last_name, person.state, person.birth_date, person.😀, Utf8(\"salary\") AS col, person.salary AS val\
+    \n      Filter: person.salary IS NOT NULL\
+    \n        TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_tablesample() {
+    let sql = "SELECT id FROM person AS p TABLESAMPLE BERNOULLI (10) REPEATABLE (42)";
+    let expected = "Projection: p.id\
+    \n  SubqueryAlias: p\
+    \n    Sample: method=bernoulli, fraction=0.1, seed=42\
+    \n      TableScan: person";
+    quick_test(sql, expected);
+
+    let sql = "SELECT id FROM person TABLESAMPLE SYSTEM (50)";
+    let expected = "Projection: person.id\
+    \n  Sample: method=system, fraction=0.5\
+    \n    TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_tablesample_invalid_fraction() {
+    let sql = "SELECT id FROM person TABLESAMPLE BERNOULLI (150)";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_contains!(
+        err.strip_backtrace(),
+        "Sample fraction must be between 0 and 1"
+    );
+}
+
 #[test]
 fn select_order_by_with_cast() {
     let sql =
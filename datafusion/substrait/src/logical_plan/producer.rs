@@ -535,6 +535,8 @@ pub fn to_substrait_rel(
         LogicalPlan::RecursiveQuery(plan) => {
             not_impl_err!("Unsupported plan type: {plan:?}")?
         }
+        LogicalPlan::GapFill(plan) => not_impl_err!("Unsupported plan type: {plan:?}")?,
+        LogicalPlan::Sample(plan) => not_impl_err!("Unsupported plan type: {plan:?}")?,
     }
 }
 
@@ -825,6 +827,9 @@ pub fn from_join(producer: &mut impl SubstraitProducer, join: &Join) -> Result<B
         JoinConstraint::On => {}
         JoinConstraint::Using => return not_impl_err!("join constraint: `using`"),
     }
+    if join.null_aware {
+        return not_impl_err!("null-aware anti join");
+    }
     let in_join_schema = Arc::new(join.left.schema().join(join.right.schema())?);
 
     // convert filter if present
@@ -1359,6 +1364,9 @@ pub fn to_substrait_rex(
         Expr::InList(expr) => producer.handle_in_list(expr, schema),
         Expr::Exists(expr) => not_impl_err!("Cannot convert {expr:?} to Substrait"),
         Expr::InSubquery(expr) => producer.handle_in_subquery(expr, schema),
+        Expr::SetComparison(expr) => {
+            not_impl_err!("Cannot convert {expr:?} to Substrait")
+        }
         Expr::ScalarSubquery(expr) => {
             not_impl_err!("Cannot convert {expr:?} to Substrait")
         }
@@ -534,6 +534,21 @@ async fn roundtrip_exists_filter() -> Result<()> {
     ).await
 }
 
+#[tokio::test]
+async fn null_aware_anti_join_not_supported() -> Result<()> {
+    let ctx = create_context().await?;
+    let plan = ctx
+        .sql("SELECT b FROM data WHERE a NOT IN (SELECT a FROM data2)")
+        .await?
+        .into_optimized_plan()?;
+    let err = to_substrait_plan(&plan, &ctx.state()).unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "This feature is not implemented: null-aware anti join"
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn inner_join() -> Result<()> {
     assert_expected_plan(